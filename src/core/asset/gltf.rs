@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+
+use base64::Engine as _;
+use glam::{Mat4, Vec4};
+use gltf::Gltf;
+use gltf::texture::{MagFilter, WrappingMode};
+
+use crate::core::buffers::state::{UploadBuffer, UploadType};
+use crate::core::cmd::EngineCmd;
+use crate::core::image::ImageEncoding;
+use crate::core::resources::{
+    CmdGeometryCreateArgs, CmdMaterialCreateArgs, CmdModelCreateArgs,
+    CmdTextureCreateFromBufferArgs, GeometryPrimitiveEntry, GeometryPrimitiveType, MaterialKind,
+    MaterialOptions, MaterialSampler, PbrOptions, SurfaceType, TextureCreateMode,
+};
+
+use super::{GltfImport, GltfImportOptions};
+
+/// Converts a glTF/GLB document into upload buffers and create commands.
+pub fn build_gltf_import(bytes: &[u8], options: &GltfImportOptions) -> Result<GltfImport, String> {
+    let gltf = Gltf::from_slice(bytes).map_err(|e| format!("Invalid glTF: {}", e))?;
+    let buffers = load_buffers(&gltf.document, gltf.blob.as_deref())?;
+
+    let mut builder = ImportBuilder {
+        options,
+        buffers: &buffers,
+        next_id: options.base_id,
+        next_buffer_id: options.base_buffer_id,
+        geometries: HashMap::new(),
+        materials: HashMap::new(),
+        textures: HashMap::new(),
+        import: GltfImport::default(),
+    };
+
+    let scene = gltf
+        .document
+        .default_scene()
+        .or_else(|| gltf.document.scenes().next());
+
+    match scene {
+        Some(scene) => {
+            for node in scene.nodes() {
+                builder.visit_node(&node, Mat4::IDENTITY)?;
+            }
+        }
+        None => {
+            // No scene graph: place every mesh at the origin.
+            for mesh in gltf.document.meshes() {
+                builder.emit_mesh(&mesh, Mat4::IDENTITY, mesh.name())?;
+            }
+        }
+    }
+
+    Ok(builder.import)
+}
+
+struct ImportBuilder<'a> {
+    options: &'a GltfImportOptions,
+    buffers: &'a [Vec<u8>],
+    next_id: u32,
+    next_buffer_id: u64,
+    /// (mesh index, primitive index) -> geometry id
+    geometries: HashMap<(usize, usize), u32>,
+    /// glTF material index (None = default material) -> material id
+    materials: HashMap<Option<usize>, u32>,
+    /// (image index, srgb) -> texture id
+    textures: HashMap<(usize, bool), u32>,
+    import: GltfImport,
+}
+
+impl ImportBuilder<'_> {
+    fn alloc_id(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        id
+    }
+
+    fn push_upload(&mut self, upload_type: UploadType, data: Vec<u8>) -> u64 {
+        let buffer_id = self.next_buffer_id;
+        self.next_buffer_id = self.next_buffer_id.wrapping_add(1);
+        self.import
+            .uploads
+            .push((buffer_id, UploadBuffer { upload_type, data }));
+        buffer_id
+    }
+
+    fn visit_node(&mut self, node: &gltf::Node, parent: Mat4) -> Result<(), String> {
+        let world = parent * Mat4::from_cols_array_2d(&node.transform().matrix());
+        if let Some(mesh) = node.mesh() {
+            self.emit_mesh(&mesh, world, node.name().or(mesh.name()))?;
+        }
+        for child in node.children() {
+            self.visit_node(&child, world)?;
+        }
+        Ok(())
+    }
+
+    fn emit_mesh(
+        &mut self,
+        mesh: &gltf::Mesh,
+        transform: Mat4,
+        label: Option<&str>,
+    ) -> Result<(), String> {
+        for primitive in mesh.primitives() {
+            if primitive.mode() != gltf::mesh::Mode::Triangles {
+                continue;
+            }
+            let geometry_id = match self.geometries.get(&(mesh.index(), primitive.index())) {
+                Some(id) => *id,
+                None => match self.emit_geometry(mesh, &primitive)? {
+                    Some(id) => id,
+                    None => continue,
+                },
+            };
+            let material_id = self.emit_material(&primitive.material())?;
+
+            let model_id = self.alloc_id();
+            self.import
+                .cmds
+                .push(EngineCmd::CmdModelCreate(CmdModelCreateArgs {
+                    window_id: self.options.window_id,
+                    model_id,
+                    label: label.map(|name| name.to_string()),
+                    geometry_id,
+                    material_id: Some(material_id),
+                    transform,
+                    layer_mask: 0xFFFFFFFF,
+                    cast_shadow: true,
+                    receive_shadow: true,
+                    cast_outline: false,
+                    outline_color: Vec4::ZERO,
+                    overwrite: false,
+                    sort_order: 0,
+                }));
+        }
+        Ok(())
+    }
+
+    fn emit_geometry(
+        &mut self,
+        mesh: &gltf::Mesh,
+        primitive: &gltf::Primitive,
+    ) -> Result<Option<u32>, String> {
+        let buffers = self.buffers;
+        let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|b| b.as_slice()));
+
+        let positions: Vec<[f32; 3]> = match reader.read_positions() {
+            Some(iter) => iter.collect(),
+            None => return Ok(None),
+        };
+
+        let mut streams: Vec<(GeometryPrimitiveType, UploadType, Vec<u8>)> = Vec::new();
+        streams.push((
+            GeometryPrimitiveType::Position,
+            UploadType::VertexData,
+            bytemuck::cast_slice(&positions).to_vec(),
+        ));
+        if let Some(indices) = reader.read_indices() {
+            let indices: Vec<u32> = indices.into_u32().collect();
+            streams.push((
+                GeometryPrimitiveType::Index,
+                UploadType::IndexData,
+                bytemuck::cast_slice(&indices).to_vec(),
+            ));
+        }
+        if let Some(normals) = reader.read_normals() {
+            let normals: Vec<[f32; 3]> = normals.collect();
+            streams.push((
+                GeometryPrimitiveType::Normal,
+                UploadType::VertexData,
+                bytemuck::cast_slice(&normals).to_vec(),
+            ));
+        }
+        if let Some(tangents) = reader.read_tangents() {
+            let tangents: Vec<[f32; 4]> = tangents.collect();
+            streams.push((
+                GeometryPrimitiveType::Tangent,
+                UploadType::VertexData,
+                bytemuck::cast_slice(&tangents).to_vec(),
+            ));
+        }
+        if let Some(colors) = reader.read_colors(0) {
+            let colors: Vec<[f32; 4]> = colors.into_rgba_f32().collect();
+            streams.push((
+                GeometryPrimitiveType::Color,
+                UploadType::VertexData,
+                bytemuck::cast_slice(&colors).to_vec(),
+            ));
+        }
+        for set in 0..2 {
+            if let Some(uvs) = reader.read_tex_coords(set) {
+                let uvs: Vec<[f32; 2]> = uvs.into_f32().collect();
+                streams.push((
+                    GeometryPrimitiveType::UV,
+                    UploadType::VertexData,
+                    bytemuck::cast_slice(&uvs).to_vec(),
+                ));
+            }
+        }
+        if let (Some(joints), Some(weights)) = (reader.read_joints(0), reader.read_weights(0)) {
+            let joints: Vec<[u16; 4]> = joints.into_u16().collect();
+            let weights: Vec<[f32; 4]> = weights.into_f32().collect();
+            streams.push((
+                GeometryPrimitiveType::SkinJoints,
+                UploadType::VertexData,
+                bytemuck::cast_slice(&joints).to_vec(),
+            ));
+            streams.push((
+                GeometryPrimitiveType::SkinWeights,
+                UploadType::VertexData,
+                bytemuck::cast_slice(&weights).to_vec(),
+            ));
+        }
+
+        let entries = streams
+            .into_iter()
+            .map(
+                |(primitive_type, upload_type, data)| GeometryPrimitiveEntry {
+                    primitive_type,
+                    buffer_id: self.push_upload(upload_type, data),
+                },
+            )
+            .collect();
+
+        let geometry_id = self.alloc_id();
+        self.geometries
+            .insert((mesh.index(), primitive.index()), geometry_id);
+        self.import
+            .cmds
+            .push(EngineCmd::CmdGeometryCreate(CmdGeometryCreateArgs {
+                window_id: self.options.window_id,
+                geometry_id,
+                label: mesh.name().map(|name| name.to_string()),
+                entries,
+                interleaved: None,
+                overwrite: false,
+                compute_normals: true,
+                compute_tangents: true,
+                retain_cpu_copy: false,
+            }));
+        Ok(Some(geometry_id))
+    }
+
+    fn emit_material(&mut self, material: &gltf::Material) -> Result<u32, String> {
+        if let Some(id) = self.materials.get(&material.index()) {
+            return Ok(*id);
+        }
+
+        let pbr = material.pbr_metallic_roughness();
+        let [er, eg, eb] = material.emissive_factor();
+        let mut options = PbrOptions {
+            base_color: Vec4::from_array(pbr.base_color_factor()),
+            surface_type: match material.alpha_mode() {
+                gltf::material::AlphaMode::Opaque => SurfaceType::Opaque,
+                gltf::material::AlphaMode::Mask => SurfaceType::Masked,
+                gltf::material::AlphaMode::Blend => SurfaceType::Transparent,
+            },
+            emissive_color: Vec4::new(er, eg, eb, 1.0),
+            metallic: pbr.metallic_factor(),
+            roughness: pbr.roughness_factor(),
+            ..Default::default()
+        };
+
+        if let Some(info) = pbr.base_color_texture() {
+            options.base_tex_id = Some(self.emit_texture(&info.texture(), true)?);
+            options.base_sampler = Some(map_sampler(&info.texture().sampler()));
+        }
+        if let Some(info) = pbr.metallic_roughness_texture() {
+            options.metallic_roughness_tex_id = Some(self.emit_texture(&info.texture(), false)?);
+            options.metallic_roughness_sampler = Some(map_sampler(&info.texture().sampler()));
+        }
+        if let Some(normal) = material.normal_texture() {
+            options.normal_scale = normal.scale();
+            options.normal_tex_id = Some(self.emit_texture(&normal.texture(), false)?);
+            options.normal_sampler = Some(map_sampler(&normal.texture().sampler()));
+        }
+        if let Some(occlusion) = material.occlusion_texture() {
+            options.ao = occlusion.strength();
+            options.ao_tex_id = Some(self.emit_texture(&occlusion.texture(), false)?);
+            options.ao_sampler = Some(map_sampler(&occlusion.texture().sampler()));
+        }
+        if let Some(info) = material.emissive_texture() {
+            options.emissive_tex_id = Some(self.emit_texture(&info.texture(), true)?);
+            options.emissive_sampler = Some(map_sampler(&info.texture().sampler()));
+        }
+
+        let material_id = self.alloc_id();
+        self.materials.insert(material.index(), material_id);
+        self.import
+            .cmds
+            .push(EngineCmd::CmdMaterialCreate(CmdMaterialCreateArgs {
+                window_id: self.options.window_id,
+                material_id,
+                label: material.name().map(|name| name.to_string()),
+                kind: MaterialKind::Pbr,
+                options: Some(MaterialOptions::Pbr(options)),
+                overwrite: false,
+            }));
+        Ok(material_id)
+    }
+
+    fn emit_texture(&mut self, texture: &gltf::Texture, srgb: bool) -> Result<u32, String> {
+        let image = texture.source();
+        if let Some(id) = self.textures.get(&(image.index(), srgb)) {
+            return Ok(*id);
+        }
+
+        let data = match image.source() {
+            gltf::image::Source::View { view, .. } => {
+                let buffer = self.buffers.get(view.buffer().index()).ok_or_else(|| {
+                    format!("Image {} references a missing buffer", image.index())
+                })?;
+                let start = view.offset();
+                let end = start + view.length();
+                buffer
+                    .get(start..end)
+                    .ok_or_else(|| format!("Image {} view is out of bounds", image.index()))?
+                    .to_vec()
+            }
+            gltf::image::Source::Uri { uri, .. } => resolve_uri(uri)?,
+        };
+
+        let buffer_id = self.push_upload(UploadType::ImageData, data);
+        let texture_id = self.alloc_id();
+        self.textures.insert((image.index(), srgb), texture_id);
+        self.import.cmds.push(EngineCmd::CmdTextureCreateFromBuffer(
+            CmdTextureCreateFromBufferArgs {
+                window_id: self.options.window_id,
+                texture_id,
+                label: image.name().map(|name| name.to_string()),
+                buffer_id,
+                encoding: ImageEncoding::Auto,
+                size: None,
+                srgb: Some(srgb),
+                mode: TextureCreateMode::Standalone,
+                atlas_options: None,
+                overwrite: false,
+                retain_cpu_copy: false,
+            },
+        ));
+        Ok(texture_id)
+    }
+}
+
+fn map_sampler(sampler: &gltf::texture::Sampler) -> MaterialSampler {
+    let point = matches!(sampler.mag_filter(), Some(MagFilter::Nearest));
+    let clamp = matches!(sampler.wrap_s(), WrappingMode::ClampToEdge);
+    match (point, clamp) {
+        (true, true) => MaterialSampler::PointClamp,
+        (false, true) => MaterialSampler::LinearClamp,
+        (true, false) => MaterialSampler::PointRepeat,
+        (false, false) => MaterialSampler::LinearRepeat,
+    }
+}
+
+fn load_buffers(document: &gltf::Document, blob: Option<&[u8]>) -> Result<Vec<Vec<u8>>, String> {
+    let mut buffers = Vec::new();
+    for buffer in document.buffers() {
+        let mut data = match buffer.source() {
+            gltf::buffer::Source::Bin => blob
+                .ok_or_else(|| "GLB binary chunk is missing".to_string())?
+                .to_vec(),
+            gltf::buffer::Source::Uri(uri) => resolve_uri(uri)?,
+        };
+        if data.len() < buffer.length() {
+            return Err(format!(
+                "Buffer {} is shorter than declared ({} < {})",
+                buffer.index(),
+                data.len(),
+                buffer.length()
+            ));
+        }
+        data.truncate(buffer.length());
+        buffers.push(data);
+    }
+    Ok(buffers)
+}
+
+/// Decodes a data URI; external files cannot be reached from an in-memory import
+fn resolve_uri(uri: &str) -> Result<Vec<u8>, String> {
+    if let Some(rest) = uri.strip_prefix("data:") {
+        let (_, payload) = rest
+            .split_once(";base64,")
+            .ok_or_else(|| format!("Unsupported data URI: {}", uri))?;
+        return base64::engine::general_purpose::STANDARD
+            .decode(payload)
+            .map_err(|e| format!("Invalid base64 data URI: {}", e));
+    }
+
+    Err(format!(
+        "External resource {} is not supported; embed it in a GLB or a data URI",
+        uri
+    ))
+}
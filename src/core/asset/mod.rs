@@ -0,0 +1,93 @@
+//! Asset import helpers that translate external formats into engine commands.
+//!
+//! Importers never touch the scene directly: they stage vertex/index/image data
+//! in the upload table and return the `EngineCmd` batch that creates the
+//! resources through the regular command path.
+
+mod gltf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::VulframResult;
+use crate::core::buffers::state::UploadBuffer;
+use crate::core::cmd::EngineCmd;
+use crate::core::singleton::with_engine;
+
+pub use self::gltf::build_gltf_import;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GltfImportOptions {
+    pub window_id: u32,
+    /// First ID handed out to generated geometries, materials, textures and models.
+    pub base_id: u32,
+    /// First upload buffer ID used for staged vertex, index and image data.
+    pub base_buffer_id: u64,
+}
+
+/// Result of converting a glTF document.
+#[derive(Debug, Default)]
+pub struct GltfImport {
+    pub uploads: Vec<(u64, UploadBuffer)>,
+    pub cmds: Vec<EngineCmd>,
+}
+
+/// Import a GLB (or a glTF with embedded data URIs) into the upload table and
+/// return the MessagePack-encoded `Vec<EngineCmd>` that creates its resources.
+pub fn vulfram_load_gltf(
+    bytes_ptr: *const u8,
+    bytes_length: usize,
+    options_ptr: *const u8,
+    options_length: usize,
+    out_ptr: *mut *const u8,
+    out_length: *mut usize,
+) -> VulframResult {
+    let bytes = unsafe { std::slice::from_raw_parts(bytes_ptr, bytes_length) };
+    let options_data = unsafe { std::slice::from_raw_parts(options_ptr, options_length) };
+
+    let options = match rmp_serde::from_slice::<GltfImportOptions>(options_data) {
+        Ok(options) => options,
+        Err(_) => return VulframResult::CmdInvalidMessagePackError,
+    };
+
+    let import = match build_gltf_import(bytes, &options) {
+        Ok(import) => import,
+        Err(message) => {
+            log::error!("glTF import failed: {}", message);
+            return VulframResult::AssetImportError;
+        }
+    };
+
+    match with_engine(|engine| {
+        if import
+            .uploads
+            .iter()
+            .any(|(id, _)| engine.buffers.uploads.contains_key(id))
+        {
+            return VulframResult::BufferIdCollision;
+        }
+
+        let serialized_data = match rmp_serde::to_vec_named(&import.cmds) {
+            Ok(data) => data,
+            Err(_) => return VulframResult::UnknownError,
+        };
+
+        for (id, buffer) in import.uploads {
+            engine.buffers.insert_upload(id, buffer);
+        }
+
+        let data_length = serialized_data.len();
+        let boxed = serialized_data.into_boxed_slice();
+        let ptr = Box::into_raw(boxed) as *mut u8;
+
+        unsafe {
+            *out_ptr = ptr;
+            *out_length = data_length;
+        }
+
+        VulframResult::Success
+    }) {
+        Err(e) => e,
+        Ok(result) => result,
+    }
+}
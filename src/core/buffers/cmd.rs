@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 
+use crate::core::resources::GeometryPrimitiveType;
 use crate::core::state::EngineState;
 
+// MARK: - Discard All
+
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
 #[serde(default, rename_all = "camelCase")]
 pub struct CmdUploadBufferDiscardAllArgs {}
@@ -27,3 +30,90 @@ pub fn engine_cmd_upload_buffer_discard_all(
         message: format!("Discarded {} upload(s)", discarded_count),
     }
 }
+
+// MARK: - Buffer Update
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdBufferUpdateArgs {
+    pub window_id: u32,
+    pub geometry_id: u32,
+    pub primitive_type: GeometryPrimitiveType,
+    /// UV set (0 or 1) when `primitive_type` is `uv`
+    #[serde(default)]
+    pub uv_set: u32,
+    pub buffer_id: u64,
+    /// Byte offset into the stream (or index buffer) where the upload is written
+    #[serde(default)]
+    pub offset: u64,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultBufferUpdate {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_buffer_update(
+    engine: &mut EngineState,
+    args: &CmdBufferUpdateArgs,
+) -> CmdResultBufferUpdate {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => window_state,
+        None => {
+            return CmdResultBufferUpdate {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    let vertex_rc = match window_state.render_state.vertex.clone() {
+        Some(vertex_allocator) => vertex_allocator,
+        None => {
+            return CmdResultBufferUpdate {
+                success: false,
+                message: format!(
+                    "Vertex allocator not initialized for window {}",
+                    args.window_id
+                ),
+            };
+        }
+    };
+    let mut vertex_guard = vertex_rc.borrow_mut();
+    let vertex_allocator = &mut *vertex_guard;
+
+    let buffer = match engine.buffers.uploads.get(&args.buffer_id) {
+        Some(buffer) => buffer,
+        None => {
+            return CmdResultBufferUpdate {
+                success: false,
+                message: format!("Buffer {} not found", args.buffer_id),
+            };
+        }
+    };
+
+    match vertex_allocator.write_range(
+        args.geometry_id,
+        args.primitive_type,
+        args.uv_set,
+        args.offset,
+        &buffer.data,
+    ) {
+        Ok(()) => {
+            // One-shot like every other upload; kept on failure so the host can retry
+            engine.buffers.uploads.remove(&args.buffer_id);
+            window_state.is_dirty = true;
+
+            CmdResultBufferUpdate {
+                success: true,
+                message: "Buffer range updated successfully".into(),
+            }
+        }
+        Err(e) => CmdResultBufferUpdate {
+            success: false,
+            message: format!("Geometry {}: {}", args.geometry_id, e),
+        },
+    }
+}
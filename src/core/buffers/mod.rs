@@ -21,6 +21,15 @@ pub fn vulfram_upload_buffer(
     let data = unsafe { std::slice::from_raw_parts(bfr_ptr, bfr_length).to_vec() };
 
     match with_engine(|engine| {
+        #[cfg(not(feature = "wasm"))]
+        if let Some(recorder) = engine.recorder.as_mut() {
+            recorder.write(&crate::core::trace::TraceEntry::Upload {
+                id: bfr_id,
+                upload_type,
+                data: data.clone(),
+            });
+        }
+
         let storage: &mut BufferStorage = &mut engine.buffers;
 
         // Check for ID collision (one-shot semantics)
@@ -34,3 +43,81 @@ pub fn vulfram_upload_buffer(
         Ok(result) => result,
     }
 }
+
+/// Overwrite `bfr_length` bytes of a pending upload starting at `offset`, in place.
+/// The upload keeps its size; the range must lie inside it.
+pub fn vulfram_update_buffer(
+    bfr_id: u64,
+    offset: usize,
+    bfr_ptr: *const u8,
+    bfr_length: usize,
+) -> VulframResult {
+    let data = unsafe { std::slice::from_raw_parts(bfr_ptr, bfr_length).to_vec() };
+
+    match with_engine(|engine| {
+        #[cfg(not(feature = "wasm"))]
+        if let Some(recorder) = engine.recorder.as_mut() {
+            recorder.write(&crate::core::trace::TraceEntry::Update {
+                id: bfr_id,
+                offset,
+                data: data.clone(),
+            });
+        }
+
+        let upload = match engine.buffers.uploads.get_mut(&bfr_id) {
+            Some(upload) => upload,
+            None => return VulframResult::BufferNotFound,
+        };
+
+        let end = match offset.checked_add(data.len()) {
+            Some(end) if end <= upload.data.len() => end,
+            _ => return VulframResult::BufferOutOfRange,
+        };
+        upload.data[offset..end].copy_from_slice(&data);
+
+        VulframResult::Success
+    }) {
+        Err(e) => e,
+        Ok(result) => result,
+    }
+}
+
+/// Take a download buffer produced by the engine (e.g. a `CmdWindowScreenshot` image).
+/// One-shot: the buffer is removed and must be released with `vulfram_free_buffer`.
+pub fn vulfram_download_buffer(
+    bfr_id: u64,
+    out_ptr: *mut *const u8,
+    out_length: *mut usize,
+) -> VulframResult {
+    match with_engine(|engine| {
+        let data = match engine.buffers.remove_download(bfr_id) {
+            Some(data) => data,
+            None => return VulframResult::BufferNotFound,
+        };
+
+        let data_length = data.len();
+        let ptr = Box::into_raw(data.into_boxed_slice()) as *mut u8;
+
+        unsafe {
+            *out_ptr = ptr;
+            *out_length = data_length;
+        }
+
+        VulframResult::Success
+    }) {
+        Err(e) => e,
+        Ok(result) => result,
+    }
+}
+
+/// Release a buffer returned through `out_ptr`/`out_length` by any `vulfram_*` function.
+/// Lets bindings decode the bytes in place instead of copying them first.
+pub fn vulfram_free_buffer(ptr: *const u8, length: usize) -> VulframResult {
+    if ptr.is_null() || length == 0 {
+        return VulframResult::Success;
+    }
+
+    // Buffers are handed out via Box::into_raw on a boxed slice
+    drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr as *mut u8, length)) });
+    VulframResult::Success
+}
@@ -37,12 +37,15 @@ pub struct UploadBuffer {
 #[derive(Debug, Default)]
 pub struct BufferStorage {
     pub uploads: HashMap<u64, UploadBuffer>,
+    /// Engine-produced blobs waiting for `vulfram_download_buffer`
+    pub downloads: HashMap<u64, Vec<u8>>,
 }
 
 impl BufferStorage {
     pub fn new() -> Self {
         Self {
             uploads: HashMap::new(),
+            downloads: HashMap::new(),
         }
     }
 
@@ -53,4 +56,12 @@ impl BufferStorage {
     pub fn remove_upload(&mut self, id: u64) -> Option<UploadBuffer> {
         self.uploads.remove(&id)
     }
+
+    pub fn insert_download(&mut self, id: u64, data: Vec<u8>) -> bool {
+        self.downloads.insert(id, data).is_none()
+    }
+
+    pub fn remove_download(&mut self, id: u64) -> Option<Vec<u8>> {
+        self.downloads.remove(&id)
+    }
 }
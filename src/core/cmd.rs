@@ -10,23 +10,42 @@ use crate::core::window::WindowEvent;
 
 pub use crate::core::audio;
 pub use crate::core::buffers as buf;
+pub use crate::core::gamepad::cmd as pad;
 pub use crate::core::render::cmd as render;
 pub use crate::core::render::gizmos as gizmo;
 pub use crate::core::resources as res;
 pub use crate::core::system as sys;
 pub use crate::core::window as win;
 
+/// Host → core commands. Changing this enum (or any payload) requires
+/// bumping `info::SCHEMA_VERSION`; the same applies to `CommandResponse` and `EngineEvent`.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(tag = "type", content = "content", rename_all = "kebab-case")]
 pub enum EngineCmd {
     CmdNotificationSend(sys::CmdNotificationSendArgs),
+    CmdQueueConfigure(sys::CmdQueueConfigureArgs),
+    CmdFrameStatsConfigure(sys::CmdFrameStatsConfigureArgs),
+    CmdFixedStepConfigure(sys::CmdFixedStepConfigureArgs),
+    CmdMemoryBudgetConfigure(sys::CmdMemoryBudgetConfigureArgs),
+    CmdAdapterGetInfo(sys::CmdAdapterGetInfoArgs),
+    CmdEventSubscriptionSet(sys::CmdEventSubscriptionSetArgs),
     CmdWindowCreate(win::CmdWindowCreateArgs),
     CmdWindowClose(win::CmdWindowCloseArgs),
     CmdWindowSetTitle(win::CmdWindowSetTitleArgs),
     CmdWindowSetPosition(win::CmdWindowSetPositionArgs),
     CmdWindowGetPosition(win::CmdWindowGetPositionArgs),
     CmdWindowSetSize(win::CmdWindowSetSizeArgs),
+    CmdWindowSetSizeConstraints(win::CmdWindowSetSizeConstraintsArgs),
     CmdWindowGetSize(win::CmdWindowGetSizeArgs),
+    CmdWindowGetScaleFactor(win::CmdWindowGetScaleFactorArgs),
+    CmdWindowScreenshot(win::CmdWindowScreenshotArgs),
+    CmdWindowSetFrameLatency(win::CmdWindowSetFrameLatencyArgs),
+    CmdWindowSetClearColor(win::CmdWindowSetClearColorArgs),
+    CmdWindowSetLayerVisibility(win::CmdWindowSetLayerVisibilityArgs),
+    CmdWindowSetFitMode(win::CmdWindowSetFitModeArgs),
+    CmdWindowSetDynamicResolution(win::CmdWindowSetDynamicResolutionArgs),
+    CmdWindowSetUpscaler(win::CmdWindowSetUpscalerArgs),
+    CmdWindowSetBackgroundRendering(win::CmdWindowSetBackgroundRenderingArgs),
     CmdWindowGetOuterSize(win::CmdWindowGetOuterSizeArgs),
     CmdWindowGetSurfaceSize(win::CmdWindowGetSurfaceSizeArgs),
     CmdWindowSetState(win::CmdWindowSetStateArgs),
@@ -36,28 +55,50 @@ pub enum EngineCmd {
     CmdWindowHasDecorations(win::CmdWindowHasDecorationsArgs),
     CmdWindowSetResizable(win::CmdWindowSetResizableArgs),
     CmdWindowIsResizable(win::CmdWindowIsResizableArgs),
+    CmdWindowSetWindowLevel(win::CmdWindowSetWindowLevelArgs),
+    CmdWindowSetTransparent(win::CmdWindowSetTransparentArgs),
+    CmdWindowSetClickThrough(win::CmdWindowSetClickThroughArgs),
     CmdWindowRequestAttention(win::CmdWindowRequestAttentionArgs),
     CmdWindowFocus(win::CmdWindowFocusArgs),
     CmdWindowSetCursorVisible(win::CmdWindowSetCursorVisibleArgs),
     CmdWindowSetCursorGrab(win::CmdWindowSetCursorGrabArgs),
+    CmdWindowSetCursorPosition(win::CmdWindowSetCursorPositionArgs),
     CmdWindowSetCursorIcon(win::CmdWindowSetCursorIconArgs),
+    CmdWindowSetCursorImage(win::CmdWindowSetCursorImageArgs),
+    CmdGamepadSetDeadzone(pad::CmdGamepadSetDeadzoneArgs),
     CmdUploadBufferDiscardAll(buf::CmdUploadBufferDiscardAllArgs),
+    CmdBufferUpdate(buf::CmdBufferUpdateArgs),
     CmdCameraCreate(res::CmdCameraCreateArgs),
     CmdCameraUpdate(res::CmdCameraUpdateArgs),
+    CmdCameraLookAt(res::CmdCameraLookAtArgs),
+    CmdCameraSetPerspective(res::CmdCameraSetPerspectiveArgs),
+    CmdCameraSetController(res::CmdCameraSetControllerArgs),
     CmdCameraDispose(res::CmdCameraDisposeArgs),
     CmdModelCreate(res::CmdModelCreateArgs),
     CmdModelUpdate(res::CmdModelUpdateArgs),
+    CmdModelSetTransform(res::CmdModelSetTransformArgs),
+    CmdModelSetParent(res::CmdModelSetParentArgs),
     CmdPoseUpdate(res::CmdPoseUpdateArgs),
     CmdModelDispose(res::CmdModelDisposeArgs),
     CmdLightCreate(res::CmdLightCreateArgs),
     CmdLightUpdate(res::CmdLightUpdateArgs),
     CmdLightDispose(res::CmdLightDisposeArgs),
+    CmdParticleSystemCreate(res::CmdParticleSystemCreateArgs),
+    CmdParticleSystemUpdate(res::CmdParticleSystemUpdateArgs),
+    CmdParticleSystemDispose(res::CmdParticleSystemDisposeArgs),
+    CmdSpriteBatchCreate(res::CmdSpriteBatchCreateArgs),
+    CmdSpriteBatchUpdate(res::CmdSpriteBatchUpdateArgs),
+    CmdSpriteBatchDispose(res::CmdSpriteBatchDisposeArgs),
     CmdMaterialCreate(res::CmdMaterialCreateArgs),
     CmdMaterialUpdate(res::CmdMaterialUpdateArgs),
     CmdMaterialDispose(res::CmdMaterialDisposeArgs),
     CmdTextureCreateFromBuffer(res::CmdTextureCreateFromBufferArgs),
     CmdTextureCreateSolidColor(res::CmdTextureCreateSolidColorArgs),
+    CmdTextureCreate(res::CmdTextureCreateArgs),
+    CmdTextureCreateCubemap(res::CmdTextureCreateCubemapArgs),
+    CmdTextureCreateArray(res::CmdTextureCreateArrayArgs),
     CmdTextureDispose(res::CmdTextureDisposeArgs),
+    CmdTextureUploadConfigure(res::CmdTextureUploadConfigureArgs),
     CmdAudioListenerUpdate(audio::CmdAudioListenerUpdateArgs),
     CmdAudioListenerCreate(audio::CmdAudioListenerCreateArgs),
     CmdAudioListenerDispose(audio::CmdAudioListenerDisposeArgs),
@@ -73,18 +114,28 @@ pub enum EngineCmd {
     CmdGeometryCreate(res::CmdGeometryCreateArgs),
     CmdGeometryUpdate(res::CmdGeometryUpdateArgs),
     CmdGeometryDispose(res::CmdGeometryDisposeArgs),
+    CmdGeometryGetBounds(res::CmdGeometryGetBoundsArgs),
+    CmdGeometrySetBudget(res::CmdGeometrySetBudgetArgs),
     CmdPrimitiveGeometryCreate(res::CmdPrimitiveGeometryCreateArgs),
     CmdEnvironmentCreate(res::CmdEnvironmentCreateArgs),
     CmdEnvironmentUpdate(res::CmdEnvironmentUpdateArgs),
     CmdEnvironmentDispose(res::CmdEnvironmentDisposeArgs),
     CmdShadowConfigure(res::shadow::CmdShadowConfigureArgs),
     CmdRenderGraphSet(render::CmdRenderGraphSetArgs),
+    CmdRenderGraphGet(render::CmdRenderGraphGetArgs),
+    CmdCustomPassCreate(render::CmdCustomPassCreateArgs),
+    CmdCustomPassUpdate(render::CmdCustomPassUpdateArgs),
+    CmdCustomPassDispose(render::CmdCustomPassDisposeArgs),
+    CmdRenderSetDebugMode(render::CmdRenderSetDebugModeArgs),
+    CmdRenderWarmupPipelines(render::CmdRenderWarmupPipelinesArgs),
+    CmdFlush(render::CmdFlushArgs),
     CmdModelList(res::CmdModelListArgs),
     CmdMaterialList(res::CmdMaterialListArgs),
     CmdTextureList(res::CmdTextureListArgs),
     CmdGeometryList(res::CmdGeometryListArgs),
     CmdLightList(res::CmdLightListArgs),
     CmdCameraList(res::CmdCameraListArgs),
+    CmdSceneReset(res::CmdSceneResetArgs),
     CmdGizmoDrawLine(gizmo::CmdGizmoDrawLineArgs),
     CmdGizmoDrawAabb(gizmo::CmdGizmoDrawAabbArgs),
 }
@@ -105,13 +156,29 @@ pub enum EngineEvent {
 #[serde(tag = "type", content = "content", rename_all = "kebab-case")]
 pub enum CommandResponse {
     NotificationSend(sys::CmdResultNotificationSend),
+    QueueConfigure(sys::CmdResultQueueConfigure),
+    FrameStatsConfigure(sys::CmdResultFrameStatsConfigure),
+    FixedStepConfigure(sys::CmdResultFixedStepConfigure),
+    MemoryBudgetConfigure(sys::CmdResultMemoryBudgetConfigure),
+    AdapterGetInfo(sys::CmdResultAdapterGetInfo),
+    EventSubscriptionSet(sys::CmdResultEventSubscriptionSet),
     WindowCreate(win::CmdResultWindowCreate),
     WindowClose(win::CmdResultWindowClose),
     WindowSetTitle(win::CmdResultWindowSetTitle),
     WindowSetPosition(win::CmdResultWindowSetPosition),
     WindowGetPosition(win::CmdResultWindowGetPosition),
     WindowSetSize(win::CmdResultWindowSetSize),
+    WindowSetSizeConstraints(win::CmdResultWindowSetSizeConstraints),
     WindowGetSize(win::CmdResultWindowGetSize),
+    WindowGetScaleFactor(win::CmdResultWindowGetScaleFactor),
+    WindowScreenshot(win::CmdResultWindowScreenshot),
+    WindowSetFrameLatency(win::CmdResultWindowSetFrameLatency),
+    WindowSetClearColor(win::CmdResultWindowSetClearColor),
+    WindowSetLayerVisibility(win::CmdResultWindowSetLayerVisibility),
+    WindowSetFitMode(win::CmdResultWindowSetFitMode),
+    WindowSetDynamicResolution(win::CmdResultWindowSetDynamicResolution),
+    WindowSetUpscaler(win::CmdResultWindowSetUpscaler),
+    WindowSetBackgroundRendering(win::CmdResultWindowSetBackgroundRendering),
     WindowGetOuterSize(win::CmdResultWindowGetOuterSize),
     WindowGetSurfaceSize(win::CmdResultWindowGetSurfaceSize),
     WindowSetState(win::CmdResultWindowSetState),
@@ -121,28 +188,50 @@ pub enum CommandResponse {
     WindowHasDecorations(win::CmdResultWindowHasDecorations),
     WindowSetResizable(win::CmdResultWindowSetResizable),
     WindowIsResizable(win::CmdResultWindowIsResizable),
+    WindowSetWindowLevel(win::CmdResultWindowSetWindowLevel),
+    WindowSetTransparent(win::CmdResultWindowSetTransparent),
+    WindowSetClickThrough(win::CmdResultWindowSetClickThrough),
     WindowRequestAttention(win::CmdResultWindowRequestAttention),
     WindowFocus(win::CmdResultWindowFocus),
     WindowSetCursorVisible(win::CmdResultWindowSetCursorVisible),
     WindowSetCursorGrab(win::CmdResultWindowSetCursorGrab),
+    WindowSetCursorPosition(win::CmdResultWindowSetCursorPosition),
     WindowSetCursorIcon(win::CmdResultWindowSetCursorIcon),
+    WindowSetCursorImage(win::CmdResultWindowSetCursorImage),
+    GamepadSetDeadzone(pad::CmdResultGamepadSetDeadzone),
     UploadBufferDiscardAll(buf::CmdResultUploadBufferDiscardAll),
+    BufferUpdate(buf::CmdResultBufferUpdate),
     CameraCreate(res::CmdResultCameraCreate),
     CameraUpdate(res::CmdResultCameraUpdate),
+    CameraLookAt(res::CmdResultCameraLookAt),
+    CameraSetPerspective(res::CmdResultCameraSetPerspective),
+    CameraSetController(res::CmdResultCameraSetController),
     CameraDispose(res::CmdResultCameraDispose),
     ModelCreate(res::CmdResultModelCreate),
     ModelUpdate(res::CmdResultModelUpdate),
+    ModelSetTransform(res::CmdResultModelSetTransform),
+    ModelSetParent(res::CmdResultModelSetParent),
     PoseUpdate(res::CmdResultPoseUpdate),
     ModelDispose(res::CmdResultModelDispose),
     LightCreate(res::CmdResultLightCreate),
     LightUpdate(res::CmdResultLightUpdate),
     LightDispose(res::CmdResultLightDispose),
+    ParticleSystemCreate(res::CmdResultParticleSystemCreate),
+    ParticleSystemUpdate(res::CmdResultParticleSystemUpdate),
+    ParticleSystemDispose(res::CmdResultParticleSystemDispose),
+    SpriteBatchCreate(res::CmdResultSpriteBatchCreate),
+    SpriteBatchUpdate(res::CmdResultSpriteBatchUpdate),
+    SpriteBatchDispose(res::CmdResultSpriteBatchDispose),
     MaterialCreate(res::CmdResultMaterialCreate),
     MaterialUpdate(res::CmdResultMaterialUpdate),
     MaterialDispose(res::CmdResultMaterialDispose),
     TextureCreateFromBuffer(res::CmdResultTextureCreateFromBuffer),
     TextureCreateSolidColor(res::CmdResultTextureCreateSolidColor),
+    TextureCreate(res::CmdResultTextureCreate),
+    TextureCreateCubemap(res::CmdResultTextureCreateCubemap),
+    TextureCreateArray(res::CmdResultTextureCreateArray),
     TextureDispose(res::CmdResultTextureDispose),
+    TextureUploadConfigure(res::CmdResultTextureUploadConfigure),
     AudioListenerUpdate(audio::CmdResultAudioListenerUpdate),
     AudioListenerCreate(audio::CmdResultAudioListenerCreate),
     AudioListenerDispose(audio::CmdResultAudioListenerDispose),
@@ -158,18 +247,28 @@ pub enum CommandResponse {
     GeometryCreate(res::CmdResultGeometryCreate),
     GeometryUpdate(res::CmdResultGeometryUpdate),
     GeometryDispose(res::CmdResultGeometryDispose),
+    GeometryGetBounds(res::CmdResultGeometryGetBounds),
+    GeometrySetBudget(res::CmdResultGeometrySetBudget),
     PrimitiveGeometryCreate(res::CmdResultPrimitiveGeometryCreate),
     EnvironmentCreate(res::CmdResultEnvironment),
     EnvironmentUpdate(res::CmdResultEnvironment),
     EnvironmentDispose(res::CmdResultEnvironment),
     ShadowConfigure(res::shadow::CmdResultShadowConfigure),
     RenderGraphSet(render::CmdResultRenderGraphSet),
+    RenderGraphGet(render::CmdResultRenderGraphGet),
+    CustomPassCreate(render::CmdResultCustomPassCreate),
+    CustomPassUpdate(render::CmdResultCustomPassUpdate),
+    CustomPassDispose(render::CmdResultCustomPassDispose),
+    RenderSetDebugMode(render::CmdResultRenderSetDebugMode),
+    RenderWarmupPipelines(render::CmdResultRenderWarmupPipelines),
+    Flush(render::CmdResultFlush),
     ModelList(res::CmdResultModelList),
     MaterialList(res::CmdResultMaterialList),
     TextureList(res::CmdResultTextureList),
     GeometryList(res::CmdResultGeometryList),
     LightList(res::CmdResultLightList),
     CameraList(res::CmdResultCameraList),
+    SceneReset(res::CmdResultSceneReset),
     GizmoDrawLine(gizmo::CmdResultGizmoDraw),
     GizmoDrawAabb(gizmo::CmdResultGizmoDraw),
 }
@@ -211,9 +310,51 @@ pub fn engine_process_batch(
                     response: CommandResponse::NotificationSend(result),
                 });
             }
+            EngineCmd::CmdQueueConfigure(args) => {
+                let result = sys::engine_cmd_queue_configure(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::QueueConfigure(result),
+                });
+            }
+            EngineCmd::CmdFrameStatsConfigure(args) => {
+                let result = sys::engine_cmd_frame_stats_configure(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::FrameStatsConfigure(result),
+                });
+            }
+            EngineCmd::CmdFixedStepConfigure(args) => {
+                let result = sys::engine_cmd_fixed_step_configure(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::FixedStepConfigure(result),
+                });
+            }
+            EngineCmd::CmdMemoryBudgetConfigure(args) => {
+                let result = sys::engine_cmd_memory_budget_configure(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::MemoryBudgetConfigure(result),
+                });
+            }
+            EngineCmd::CmdAdapterGetInfo(args) => {
+                let result = sys::engine_cmd_adapter_get_info(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::AdapterGetInfo(result),
+                });
+            }
+            EngineCmd::CmdEventSubscriptionSet(args) => {
+                let result = sys::engine_cmd_event_subscription_set(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::EventSubscriptionSet(result),
+                });
+            }
             EngineCmd::CmdWindowCreate(args) => {
                 match platform.handle_window_create(engine, pack.id, &args) {
-                    Ok(()) => {}
+                    Ok(()) => engine.pending_async_cmds += 1,
                     Err(result) => {
                         engine.response_queue.push(CommandResponseEnvelope {
                             id: pack.id,
@@ -257,6 +398,13 @@ pub fn engine_process_batch(
                     response: CommandResponse::WindowSetSize(result),
                 });
             }
+            EngineCmd::CmdWindowSetSizeConstraints(args) => {
+                let result = win::engine_cmd_window_set_size_constraints(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetSizeConstraints(result),
+                });
+            }
             EngineCmd::CmdWindowGetSize(args) => {
                 let result = win::engine_cmd_window_get_size(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -264,6 +412,73 @@ pub fn engine_process_batch(
                     response: CommandResponse::WindowGetSize(result),
                 });
             }
+            EngineCmd::CmdWindowGetScaleFactor(args) => {
+                let result = win::engine_cmd_window_get_scale_factor(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowGetScaleFactor(result),
+                });
+            }
+            EngineCmd::CmdWindowScreenshot(args) => {
+                match win::engine_cmd_window_screenshot(engine, pack.id, &args) {
+                    Ok(()) => engine.pending_async_cmds += 1,
+                    Err(result) => {
+                        engine.response_queue.push(CommandResponseEnvelope {
+                            id: pack.id,
+                            response: CommandResponse::WindowScreenshot(result),
+                        });
+                    }
+                }
+            }
+            EngineCmd::CmdWindowSetFrameLatency(args) => {
+                let result = win::engine_cmd_window_set_frame_latency(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetFrameLatency(result),
+                });
+            }
+            EngineCmd::CmdWindowSetClearColor(args) => {
+                let result = win::engine_cmd_window_set_clear_color(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetClearColor(result),
+                });
+            }
+            EngineCmd::CmdWindowSetLayerVisibility(args) => {
+                let result = win::engine_cmd_window_set_layer_visibility(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetLayerVisibility(result),
+                });
+            }
+            EngineCmd::CmdWindowSetFitMode(args) => {
+                let result = win::engine_cmd_window_set_fit_mode(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetFitMode(result),
+                });
+            }
+            EngineCmd::CmdWindowSetDynamicResolution(args) => {
+                let result = win::engine_cmd_window_set_dynamic_resolution(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetDynamicResolution(result),
+                });
+            }
+            EngineCmd::CmdWindowSetUpscaler(args) => {
+                let result = win::engine_cmd_window_set_upscaler(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetUpscaler(result),
+                });
+            }
+            EngineCmd::CmdWindowSetBackgroundRendering(args) => {
+                let result = win::engine_cmd_window_set_background_rendering(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetBackgroundRendering(result),
+                });
+            }
             EngineCmd::CmdWindowGetOuterSize(args) => {
                 let result = win::engine_cmd_window_get_outer_size(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -327,6 +542,27 @@ pub fn engine_process_batch(
                     response: CommandResponse::WindowIsResizable(result),
                 });
             }
+            EngineCmd::CmdWindowSetWindowLevel(args) => {
+                let result = win::engine_cmd_window_set_window_level(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetWindowLevel(result),
+                });
+            }
+            EngineCmd::CmdWindowSetTransparent(args) => {
+                let result = win::engine_cmd_window_set_transparent(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetTransparent(result),
+                });
+            }
+            EngineCmd::CmdWindowSetClickThrough(args) => {
+                let result = win::engine_cmd_window_set_click_through(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetClickThrough(result),
+                });
+            }
             EngineCmd::CmdWindowRequestAttention(args) => {
                 let result = win::engine_cmd_window_request_attention(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -355,6 +591,13 @@ pub fn engine_process_batch(
                     response: CommandResponse::WindowSetCursorGrab(result),
                 });
             }
+            EngineCmd::CmdWindowSetCursorPosition(args) => {
+                let result = win::engine_cmd_window_set_cursor_position(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::WindowSetCursorPosition(result),
+                });
+            }
             EngineCmd::CmdWindowSetCursorIcon(args) => {
                 let result = win::engine_cmd_window_set_cursor_icon(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -362,6 +605,29 @@ pub fn engine_process_batch(
                     response: CommandResponse::WindowSetCursorIcon(result),
                 });
             }
+            EngineCmd::CmdWindowSetCursorImage(args) => {
+                match win::engine_cmd_window_set_cursor_image(
+                    engine,
+                    platform.event_loop_proxy(),
+                    pack.id,
+                    &args,
+                ) {
+                    Ok(()) => engine.pending_async_cmds += 1,
+                    Err(result) => {
+                        engine.response_queue.push(CommandResponseEnvelope {
+                            id: pack.id,
+                            response: CommandResponse::WindowSetCursorImage(result),
+                        });
+                    }
+                }
+            }
+            EngineCmd::CmdGamepadSetDeadzone(args) => {
+                let result = pad::engine_cmd_gamepad_set_deadzone(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::GamepadSetDeadzone(result),
+                });
+            }
             EngineCmd::CmdUploadBufferDiscardAll(args) => {
                 let result = buf::engine_cmd_upload_buffer_discard_all(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -369,6 +635,13 @@ pub fn engine_process_batch(
                     response: CommandResponse::UploadBufferDiscardAll(result),
                 });
             }
+            EngineCmd::CmdBufferUpdate(args) => {
+                let result = buf::engine_cmd_buffer_update(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::BufferUpdate(result),
+                });
+            }
             EngineCmd::CmdCameraCreate(args) => {
                 let result = res::engine_cmd_camera_create(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -383,6 +656,27 @@ pub fn engine_process_batch(
                     response: CommandResponse::CameraUpdate(result),
                 });
             }
+            EngineCmd::CmdCameraLookAt(args) => {
+                let result = res::engine_cmd_camera_look_at(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::CameraLookAt(result),
+                });
+            }
+            EngineCmd::CmdCameraSetPerspective(args) => {
+                let result = res::engine_cmd_camera_set_perspective(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::CameraSetPerspective(result),
+                });
+            }
+            EngineCmd::CmdCameraSetController(args) => {
+                let result = res::engine_cmd_camera_set_controller(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::CameraSetController(result),
+                });
+            }
             EngineCmd::CmdCameraDispose(args) => {
                 let result = res::engine_cmd_camera_dispose(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -404,6 +698,20 @@ pub fn engine_process_batch(
                     response: CommandResponse::ModelUpdate(result),
                 });
             }
+            EngineCmd::CmdModelSetTransform(args) => {
+                let result = res::engine_cmd_model_set_transform(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::ModelSetTransform(result),
+                });
+            }
+            EngineCmd::CmdModelSetParent(args) => {
+                let result = res::engine_cmd_model_set_parent(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::ModelSetParent(result),
+                });
+            }
             EngineCmd::CmdPoseUpdate(args) => {
                 let result = res::engine_cmd_pose_update(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -439,6 +747,48 @@ pub fn engine_process_batch(
                     response: CommandResponse::LightDispose(result),
                 });
             }
+            EngineCmd::CmdParticleSystemCreate(args) => {
+                let result = res::engine_cmd_particle_system_create(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::ParticleSystemCreate(result),
+                });
+            }
+            EngineCmd::CmdParticleSystemUpdate(args) => {
+                let result = res::engine_cmd_particle_system_update(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::ParticleSystemUpdate(result),
+                });
+            }
+            EngineCmd::CmdParticleSystemDispose(args) => {
+                let result = res::engine_cmd_particle_system_dispose(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::ParticleSystemDispose(result),
+                });
+            }
+            EngineCmd::CmdSpriteBatchCreate(args) => {
+                let result = res::engine_cmd_sprite_batch_create(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::SpriteBatchCreate(result),
+                });
+            }
+            EngineCmd::CmdSpriteBatchUpdate(args) => {
+                let result = res::engine_cmd_sprite_batch_update(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::SpriteBatchUpdate(result),
+                });
+            }
+            EngineCmd::CmdSpriteBatchDispose(args) => {
+                let result = res::engine_cmd_sprite_batch_dispose(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::SpriteBatchDispose(result),
+                });
+            }
             EngineCmd::CmdMaterialCreate(args) => {
                 let result = res::engine_cmd_material_create(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -474,6 +824,27 @@ pub fn engine_process_batch(
                     response: CommandResponse::TextureCreateSolidColor(result),
                 });
             }
+            EngineCmd::CmdTextureCreate(args) => {
+                let result = res::engine_cmd_texture_create(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::TextureCreate(result),
+                });
+            }
+            EngineCmd::CmdTextureCreateCubemap(args) => {
+                let result = res::engine_cmd_texture_create_cubemap(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::TextureCreateCubemap(result),
+                });
+            }
+            EngineCmd::CmdTextureCreateArray(args) => {
+                let result = res::engine_cmd_texture_create_array(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::TextureCreateArray(result),
+                });
+            }
             EngineCmd::CmdTextureDispose(args) => {
                 let result = res::engine_cmd_texture_dispose(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -481,6 +852,13 @@ pub fn engine_process_batch(
                     response: CommandResponse::TextureDispose(result),
                 });
             }
+            EngineCmd::CmdTextureUploadConfigure(args) => {
+                let result = res::engine_cmd_texture_upload_configure(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::TextureUploadConfigure(result),
+                });
+            }
             EngineCmd::CmdAudioListenerUpdate(args) => {
                 let result = audio::engine_cmd_audio_listener_update(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -586,6 +964,20 @@ pub fn engine_process_batch(
                     response: CommandResponse::GeometryDispose(result),
                 });
             }
+            EngineCmd::CmdGeometryGetBounds(args) => {
+                let result = res::engine_cmd_geometry_get_bounds(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::GeometryGetBounds(result),
+                });
+            }
+            EngineCmd::CmdGeometrySetBudget(args) => {
+                let result = res::engine_cmd_geometry_set_budget(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::GeometrySetBudget(result),
+                });
+            }
             EngineCmd::CmdPrimitiveGeometryCreate(args) => {
                 let result = res::engine_cmd_primitive_geometry_create(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -628,6 +1020,55 @@ pub fn engine_process_batch(
                     response: CommandResponse::RenderGraphSet(result),
                 });
             }
+            EngineCmd::CmdRenderGraphGet(args) => {
+                let result = render::engine_cmd_render_graph_get(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::RenderGraphGet(result),
+                });
+            }
+            EngineCmd::CmdCustomPassCreate(args) => {
+                let result = render::engine_cmd_custom_pass_create(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::CustomPassCreate(result),
+                });
+            }
+            EngineCmd::CmdCustomPassUpdate(args) => {
+                let result = render::engine_cmd_custom_pass_update(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::CustomPassUpdate(result),
+                });
+            }
+            EngineCmd::CmdCustomPassDispose(args) => {
+                let result = render::engine_cmd_custom_pass_dispose(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::CustomPassDispose(result),
+                });
+            }
+            EngineCmd::CmdRenderSetDebugMode(args) => {
+                let result = render::engine_cmd_render_set_debug_mode(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::RenderSetDebugMode(result),
+                });
+            }
+            EngineCmd::CmdRenderWarmupPipelines(args) => {
+                let result = render::engine_cmd_render_warmup_pipelines(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::RenderWarmupPipelines(result),
+                });
+            }
+            EngineCmd::CmdFlush(args) => {
+                let result = render::engine_cmd_flush(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::Flush(result),
+                });
+            }
             EngineCmd::CmdModelList(args) => {
                 let result = res::engine_cmd_model_list(engine, &args);
                 engine.response_queue.push(CommandResponseEnvelope {
@@ -670,6 +1111,13 @@ pub fn engine_process_batch(
                     response: CommandResponse::CameraList(result),
                 });
             }
+            EngineCmd::CmdSceneReset(args) => {
+                let result = res::engine_cmd_scene_reset(engine, &args);
+                engine.response_queue.push(CommandResponseEnvelope {
+                    id: pack.id,
+                    response: CommandResponse::SceneReset(result),
+                });
+            }
             EngineCmd::CmdGizmoDrawLine(args) => {
                 for window_state in engine.window.states.values_mut() {
                     window_state
@@ -0,0 +1,37 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Wire format used for command, response and event batches.
+/// Selected once at `vulfram_init_with_codec`; MessagePack is the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u32)]
+pub enum Codec {
+    #[default]
+    MsgPack = 0,
+    /// Human-readable JSON, intended for debugging and hosts without MessagePack
+    Json = 1,
+}
+
+impl Codec {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Codec::MsgPack),
+            1 => Some(Codec::Json),
+            _ => None,
+        }
+    }
+
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            Codec::MsgPack => rmp_serde::to_vec_named(value).map_err(|e| e.to_string()),
+            Codec::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, data: &[u8]) -> Result<T, String> {
+        match self {
+            Codec::MsgPack => rmp_serde::from_slice(data).map_err(|e| e.to_string()),
+            Codec::Json => serde_json::from_slice(data).map_err(|e| e.to_string()),
+        }
+    }
+}
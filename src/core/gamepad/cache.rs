@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
+
 use crate::core::input::events::ElementState;
 
-/// Dead zone threshold for analog sticks and triggers
+/// Default dead zone threshold for analog sticks and triggers
 pub const GAMEPAD_AXIS_DEAD_ZONE: f32 = 0.1;
 
 /// Minimum change threshold to dispatch axis events
@@ -11,33 +13,73 @@ pub const GAMEPAD_AXIS_CHANGE_THRESHOLD: f32 = 0.01;
 /// Minimum change threshold to dispatch button analog value events
 pub const GAMEPAD_BUTTON_CHANGE_THRESHOLD: f32 = 0.05;
 
+/// How raw axis values are shaped before `OnAxis` is emitted
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GamepadAxisSettings {
+    /// Magnitudes at or below this report zero
+    pub inner: f32,
+    /// Magnitudes at or above this report full deflection
+    pub outer: f32,
+    /// Multiplier on the rescaled value, clamped to full deflection
+    pub sensitivity: f32,
+    /// Axes whose sign is flipped (e.g. `[1, 3]` for inverted stick Y)
+    pub inverted_axes: Vec<u32>,
+}
+
+impl Default for GamepadAxisSettings {
+    fn default() -> Self {
+        Self {
+            inner: GAMEPAD_AXIS_DEAD_ZONE,
+            outer: 1.0,
+            sensitivity: 1.0,
+            inverted_axes: Vec::new(),
+        }
+    }
+}
+
+impl GamepadAxisSettings {
+    /// Rescales `inner..outer` to `0..1` keeping the sign, then applies sensitivity and inversion
+    pub fn apply(&self, axis: u32, value: f32) -> f32 {
+        let magnitude = value.abs();
+        if magnitude <= self.inner {
+            return 0.0;
+        }
+        let range = (self.outer - self.inner).max(f32::EPSILON);
+        let scaled = ((magnitude - self.inner) / range).min(1.0);
+        let shaped = (scaled * self.sensitivity).min(1.0) * value.signum();
+        if self.inverted_axes.contains(&axis) {
+            -shaped
+        } else {
+            shaped
+        }
+    }
+}
+
 /// Cached state for a single gamepad
 #[derive(Debug, Clone, Default)]
 pub struct GamepadStateCache {
     pub axes: HashMap<u32, f32>,
     pub buttons: HashMap<u32, (ElementState, f32)>,
+    pub settings: GamepadAxisSettings,
 }
 
 impl GamepadStateCache {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(settings: GamepadAxisSettings) -> Self {
+        Self {
+            settings,
+            ..Self::default()
+        }
     }
 
-    /// Apply dead zone to axis value
-    pub fn apply_dead_zone(value: f32) -> f32 {
-        if value.abs() < GAMEPAD_AXIS_DEAD_ZONE {
-            0.0
-        } else {
-            // Rescale value to maintain smooth transition
-            let sign = value.signum();
-            let adjusted = (value.abs() - GAMEPAD_AXIS_DEAD_ZONE) / (1.0 - GAMEPAD_AXIS_DEAD_ZONE);
-            sign * adjusted
-        }
+    /// Apply dead zone, sensitivity and inversion to axis value
+    pub fn apply_dead_zone(&self, axis: u32, value: f32) -> f32 {
+        self.settings.apply(axis, value)
     }
 
     /// Check if axis value changed significantly
     pub fn axis_changed(&self, axis: u32, new_value: f32) -> bool {
-        let adjusted_value = Self::apply_dead_zone(new_value);
+        let adjusted_value = self.apply_dead_zone(axis, new_value);
 
         if let Some(&cached_value) = self.axes.get(&axis) {
             (cached_value - adjusted_value).abs() > GAMEPAD_AXIS_CHANGE_THRESHOLD
@@ -65,7 +107,7 @@ impl GamepadStateCache {
 
     /// Update axis cache
     pub fn update_axis(&mut self, axis: u32, value: f32) {
-        let adjusted_value = Self::apply_dead_zone(value);
+        let adjusted_value = self.apply_dead_zone(axis, value);
         self.axes.insert(axis, adjusted_value);
     }
 
@@ -79,18 +121,46 @@ impl GamepadStateCache {
 #[derive(Debug, Default)]
 pub struct GamepadCacheManager {
     pub gamepads: HashMap<u32, GamepadStateCache>,
+    /// Axis settings of pads without their own
+    pub default_settings: GamepadAxisSettings,
+    /// Per-pad axis settings; kept across disconnects so a reconnected pad keeps them
+    pub settings: HashMap<u32, GamepadAxisSettings>,
 }
 
 impl GamepadCacheManager {
     pub fn new() -> Self {
-        Self {
-            gamepads: HashMap::new(),
-        }
+        Self::default()
     }
 
     /// Create cache for a newly connected gamepad
     pub fn add_gamepad(&mut self, gamepad_id: u32) {
-        self.gamepads.insert(gamepad_id, GamepadStateCache::new());
+        let settings = self.settings_for(gamepad_id).clone();
+        self.gamepads
+            .insert(gamepad_id, GamepadStateCache::new(settings));
+    }
+
+    pub fn settings_for(&self, gamepad_id: u32) -> &GamepadAxisSettings {
+        self.settings
+            .get(&gamepad_id)
+            .unwrap_or(&self.default_settings)
+    }
+
+    /// Sets the axis settings of one pad, or the default of all pads without their own
+    pub fn set_settings(&mut self, gamepad_id: Option<u32>, settings: GamepadAxisSettings) {
+        match gamepad_id {
+            Some(id) => {
+                self.settings.insert(id, settings);
+            }
+            None => self.default_settings = settings,
+        }
+        for (&id, cache) in &mut self.gamepads {
+            let settings = self.settings.get(&id).unwrap_or(&self.default_settings);
+            if cache.settings != *settings {
+                cache.settings = settings.clone();
+                // Re-shape from zero so the next movement reports with the new settings
+                cache.axes.clear();
+            }
+        }
     }
 
     /// Remove cache for a disconnected gamepad
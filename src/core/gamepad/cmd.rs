@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::gamepad::cache::GamepadAxisSettings;
+use crate::core::state::EngineState;
+
+// MARK: - Set Deadzone
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdGamepadSetDeadzoneArgs {
+    /// Pad to configure; None sets the default of every pad without its own settings
+    pub gamepad_id: Option<u32>,
+    pub inner: Option<f32>,
+    pub outer: Option<f32>,
+    pub sensitivity: Option<f32>,
+    pub inverted_axes: Option<Vec<u32>>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultGamepadSetDeadzone {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_gamepad_set_deadzone(
+    engine: &mut EngineState,
+    args: &CmdGamepadSetDeadzoneArgs,
+) -> CmdResultGamepadSetDeadzone {
+    let manager = &mut engine.gamepad.cache;
+    let current = match args.gamepad_id {
+        Some(id) => manager.settings_for(id),
+        None => &manager.default_settings,
+    };
+    let settings = GamepadAxisSettings {
+        inner: args.inner.unwrap_or(current.inner),
+        outer: args.outer.unwrap_or(current.outer),
+        sensitivity: args.sensitivity.unwrap_or(current.sensitivity),
+        inverted_axes: args
+            .inverted_axes
+            .clone()
+            .unwrap_or_else(|| current.inverted_axes.clone()),
+    };
+
+    let valid_range = (0.0..1.0).contains(&settings.inner)
+        && settings.outer > settings.inner
+        && settings.outer <= 1.0;
+    if !valid_range {
+        return CmdResultGamepadSetDeadzone {
+            success: false,
+            message: "Gamepad deadzone must satisfy 0 <= inner < outer <= 1".into(),
+        };
+    }
+    if !(settings.sensitivity.is_finite() && settings.sensitivity > 0.0) {
+        return CmdResultGamepadSetDeadzone {
+            success: false,
+            message: "Gamepad sensitivity must be greater than 0".into(),
+        };
+    }
+
+    manager.set_settings(args.gamepad_id, settings);
+
+    CmdResultGamepadSetDeadzone {
+        success: true,
+        message: "Gamepad deadzone set successfully".into(),
+    }
+}
@@ -8,7 +8,18 @@ use crate::core::input::events::ElementState;
 pub enum GamepadEvent {
     /// Gamepad was connected
     #[serde(rename_all = "camelCase")]
-    OnConnect { gamepad_id: u32, name: String },
+    OnConnect {
+        gamepad_id: u32,
+        name: String,
+        /// SDL-style device GUID in hex (identifies the controller model), when the
+        /// platform reports one
+        uuid: Option<String>,
+        /// USB ids, for picking button prompts per controller family
+        vendor_id: Option<u16>,
+        product_id: Option<u16>,
+        /// The device supports force feedback
+        has_rumble: bool,
+    },
 
     /// Gamepad was disconnected
     #[serde(rename_all = "camelCase")]
@@ -1,4 +1,5 @@
 pub mod cache;
+pub mod cmd;
 #[cfg(not(feature = "wasm"))]
 pub mod converters;
 pub mod events;
@@ -19,25 +20,49 @@ use self::cache::GamepadCacheManager;
 /// Process a gilrs gamepad event and convert it to engine events
 #[cfg(not(feature = "wasm"))]
 pub fn process_gilrs_event(engine_state: &mut EngineState, event: GilrsEvent) {
-    let gamepad_id: u32 = usize::from(event.id) as u32;
+    let gilrs_id = usize::from(event.id);
+    let uuid = engine_state
+        .gamepad
+        .gilrs
+        .as_ref()
+        .map_or([0; 16], |gilrs| gilrs.gamepad(event.id).uuid());
+    let gamepad_id = match event.event {
+        GilrsEventType::Connected => engine_state.gamepad.connect_id(gilrs_id, uuid),
+        GilrsEventType::Disconnected => engine_state.gamepad.disconnect_id(gilrs_id, uuid),
+        _ => engine_state.gamepad.id(gilrs_id),
+    };
     let manager: &mut GamepadCacheManager = &mut engine_state.gamepad.cache;
 
     match event.event {
         GilrsEventType::Connected => {
-            let name: String = if let Some(gilrs) = &engine_state.gamepad.gilrs {
-                gilrs.gamepad(event.id).name().into()
-            } else {
-                "Unknown".into()
+            let connect_event = match &engine_state.gamepad.gilrs {
+                Some(gilrs) => {
+                    let gamepad = gilrs.gamepad(event.id);
+                    GamepadEvent::OnConnect {
+                        gamepad_id,
+                        name: gamepad.name().into(),
+                        uuid: (uuid != [0; 16])
+                            .then(|| uuid.iter().map(|byte| format!("{:02x}", byte)).collect()),
+                        vendor_id: gamepad.vendor_id(),
+                        product_id: gamepad.product_id(),
+                        has_rumble: gamepad.is_ff_supported(),
+                    }
+                }
+                None => GamepadEvent::OnConnect {
+                    gamepad_id,
+                    name: "Unknown".into(),
+                    uuid: None,
+                    vendor_id: None,
+                    product_id: None,
+                    has_rumble: false,
+                },
             };
 
             manager.add_gamepad(gamepad_id);
 
             engine_state
                 .event_queue
-                .push(EngineEvent::Gamepad(GamepadEvent::OnConnect {
-                    gamepad_id,
-                    name,
-                }));
+                .push(EngineEvent::Gamepad(connect_event));
         }
         GilrsEventType::Disconnected => {
             manager.remove_gamepad(gamepad_id);
@@ -122,8 +147,8 @@ pub fn process_gilrs_event(engine_state: &mut EngineState, event: GilrsEvent) {
                     return;
                 }
 
-                let adjusted_value = cache.get_axis_value(axis_mapped);
                 cache.update_axis(axis_mapped, value);
+                let adjusted_value = cache.get_axis_value(axis_mapped);
 
                 engine_state
                     .event_queue
@@ -171,6 +196,11 @@ pub fn process_web_gamepads(engine_state: &mut EngineState) {
                 .push(EngineEvent::Gamepad(GamepadEvent::OnConnect {
                     gamepad_id,
                     name: pad.id(),
+                    uuid: None,
+                    vendor_id: None,
+                    product_id: None,
+                    has_rumble: js_sys::Reflect::get(&pad, &"vibrationActuator".into())
+                        .is_ok_and(|actuator| !actuator.is_undefined() && !actuator.is_null()),
                 }));
         }
 
@@ -211,8 +241,8 @@ pub fn process_web_gamepads(engine_state: &mut EngineState) {
                 if !cache.axis_changed(axis_id, value) {
                     continue;
                 }
-                let adjusted_value = cache.get_axis_value(axis_id);
                 cache.update_axis(axis_id, value);
+                let adjusted_value = cache.get_axis_value(axis_id);
                 engine_state
                     .event_queue
                     .push(EngineEvent::Gamepad(GamepadEvent::OnAxis {
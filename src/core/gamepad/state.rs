@@ -1,3 +1,6 @@
+#[cfg(not(feature = "wasm"))]
+use std::collections::HashMap;
+
 use crate::core::gamepad::cache::GamepadCacheManager;
 #[cfg(not(feature = "wasm"))]
 use crate::core::platform::gilrs;
@@ -6,6 +9,14 @@ pub struct GamepadState {
     #[cfg(not(feature = "wasm"))]
     pub gilrs: Option<gilrs::Gilrs>,
     pub cache: GamepadCacheManager,
+    /// Engine gamepad id of each connected gilrs id
+    #[cfg(not(feature = "wasm"))]
+    ids: HashMap<usize, u32>,
+    /// Ids of disconnected pads by device GUID, handed back when that model reconnects
+    #[cfg(not(feature = "wasm"))]
+    released_ids: HashMap<[u8; 16], Vec<u32>>,
+    #[cfg(not(feature = "wasm"))]
+    next_id: u32,
 }
 
 impl GamepadState {
@@ -23,6 +34,49 @@ impl GamepadState {
             #[cfg(not(feature = "wasm"))]
             gilrs,
             cache: GamepadCacheManager::new(),
+            #[cfg(not(feature = "wasm"))]
+            ids: HashMap::new(),
+            #[cfg(not(feature = "wasm"))]
+            released_ids: HashMap::new(),
+            #[cfg(not(feature = "wasm"))]
+            next_id: 0,
+        }
+    }
+
+    /// Assigns the engine id of a newly connected pad. A pad whose GUID matches a
+    /// disconnected one gets that id back, so reconnecting keeps the player slot.
+    #[cfg(not(feature = "wasm"))]
+    pub fn connect_id(&mut self, gilrs_id: usize, uuid: [u8; 16]) -> u32 {
+        let reused = self
+            .released_ids
+            .get_mut(&uuid)
+            .filter(|_| uuid != [0; 16])
+            .and_then(|ids| ids.pop());
+        let id = reused.unwrap_or_else(|| {
+            let id = self.next_id;
+            self.next_id += 1;
+            id
+        });
+        self.ids.insert(gilrs_id, id);
+        id
+    }
+
+    /// Engine id of a connected pad (assigned on the spot if its connect was missed)
+    #[cfg(not(feature = "wasm"))]
+    pub fn id(&mut self, gilrs_id: usize) -> u32 {
+        match self.ids.get(&gilrs_id) {
+            Some(&id) => id,
+            None => self.connect_id(gilrs_id, [0; 16]),
+        }
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    pub fn disconnect_id(&mut self, gilrs_id: usize, uuid: [u8; 16]) -> u32 {
+        let id = self.id(gilrs_id);
+        self.ids.remove(&gilrs_id);
+        if uuid != [0; 16] {
+            self.released_ids.entry(uuid).or_default().push(id);
         }
+        id
     }
 }
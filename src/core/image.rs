@@ -1,6 +1,8 @@
+use glam::UVec2;
 use half::f16;
 use image::imageops::FilterType;
 use image::{GenericImageView, ImageFormat};
+use serde::{Deserialize, Serialize};
 
 /// Decoded image buffer in RGBA format
 #[derive(Debug, Clone)]
@@ -8,6 +10,23 @@ pub struct ImageBuffer {
     pub width: u32,
     pub height: u32,
     pub pixels: ImagePixels,
+    /// Additional mip levels (level 1..N), only provided by containers like KTX2
+    pub mips: Vec<ImagePixels>,
+    /// Color space declared by the container (KTX2); `None` when the data carries none
+    pub srgb: Option<bool>,
+}
+
+impl ImageBuffer {
+    /// Size in bytes of the base level plus all mip levels
+    pub fn byte_len(&self) -> usize {
+        self.pixels.byte_len() + self.mips.iter().map(ImagePixels::byte_len).sum::<usize>()
+    }
+
+    /// Whether 8-bit pixels upload as sRGB. The container's flag wins over `requested`,
+    /// which defaults to sRGB.
+    pub fn is_srgb(&self, requested: Option<bool>) -> bool {
+        self.srgb.or(requested).unwrap_or(true)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -16,6 +35,29 @@ pub enum ImagePixels {
     Rgba16F(Vec<u16>),
 }
 
+impl ImagePixels {
+    pub fn byte_len(&self) -> usize {
+        match self {
+            ImagePixels::Rgba8(data) => data.len(),
+            ImagePixels::Rgba16F(data) => data.len() * 2,
+        }
+    }
+}
+
+/// Encoding of an uploaded image buffer
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImageEncoding {
+    /// Detect the container from the file signature
+    #[default]
+    Auto,
+    /// Tightly packed RGBA8 pixels (requires explicit dimensions)
+    Raw,
+    Png,
+    Jpeg,
+    Ktx2,
+}
+
 /// Image decoder that automatically detects format and decodes
 pub struct ImageDecoder;
 
@@ -23,12 +65,65 @@ impl ImageDecoder {
     /// Try to decode image data. Returns Some(ImageBuffer) if successful, None otherwise.
     pub fn try_decode(data: &[u8]) -> Option<ImageBuffer> {
         let format = image::guess_format(data).ok();
-        Self::decode_with_image_crate(data, format)
+        Self::decode_with_image_crate(data, format, false).ok()
+    }
+
+    /// Decode image data with an explicit encoding. `size` is required for `Raw`.
+    pub fn decode(
+        data: &[u8],
+        encoding: ImageEncoding,
+        size: Option<UVec2>,
+    ) -> Result<ImageBuffer, String> {
+        match encoding {
+            ImageEncoding::Auto => {
+                if data.starts_with(&KTX2_IDENTIFIER) {
+                    return Self::decode_ktx2(data);
+                }
+                let format = image::guess_format(data)
+                    .map_err(|_| "Unrecognized image format".to_string())?;
+                Self::decode_with_image_crate(data, Some(format), false)
+            }
+            ImageEncoding::Raw => Self::decode_raw(data, size),
+            ImageEncoding::Png => Self::decode_with_image_crate(data, Some(ImageFormat::Png), true),
+            ImageEncoding::Jpeg => {
+                Self::decode_with_image_crate(data, Some(ImageFormat::Jpeg), true)
+            }
+            ImageEncoding::Ktx2 => Self::decode_ktx2(data),
+        }
+    }
+
+    fn decode_raw(data: &[u8], size: Option<UVec2>) -> Result<ImageBuffer, String> {
+        let size = size.ok_or_else(|| "Raw images require an explicit size".to_string())?;
+        let expected = size.x as usize * size.y as usize * 4;
+        if size.x == 0 || size.y == 0 || data.len() != expected {
+            return Err(format!(
+                "Raw RGBA8 image of {}x{} expects {} bytes, got {}",
+                size.x,
+                size.y,
+                expected,
+                data.len()
+            ));
+        }
+        Ok(ImageBuffer {
+            width: size.x,
+            height: size.y,
+            pixels: ImagePixels::Rgba8(data.to_vec()),
+            mips: Vec::new(),
+            srgb: None,
+        })
     }
 
     /// Decode PNG, JPEG, WebP, and AVIF using the image crate
-    fn decode_with_image_crate(data: &[u8], format: Option<ImageFormat>) -> Option<ImageBuffer> {
-        let mut img = image::load_from_memory(data).ok()?;
+    fn decode_with_image_crate(
+        data: &[u8],
+        format: Option<ImageFormat>,
+        strict: bool,
+    ) -> Result<ImageBuffer, String> {
+        let mut img = match format {
+            Some(format) if strict => image::load_from_memory_with_format(data, format),
+            _ => image::load_from_memory(data),
+        }
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
         let max_dim: u32 = 2048;
 
         if matches!(format, Some(ImageFormat::OpenExr | ImageFormat::Hdr)) {
@@ -46,18 +141,81 @@ impl ImageDecoder {
             for value in rgba.into_raw() {
                 out.push(f16::from_f32(value).to_bits());
             }
-            return Some(ImageBuffer {
+            return Ok(ImageBuffer {
                 width: img.width(),
                 height: img.height(),
                 pixels: ImagePixels::Rgba16F(out),
+                mips: Vec::new(),
+                srgb: None,
             });
         }
 
         let rgba = img.to_rgba8();
-        Some(ImageBuffer {
+        Ok(ImageBuffer {
             width: rgba.width(),
             height: rgba.height(),
             pixels: ImagePixels::Rgba8(rgba.into_raw()),
+            mips: Vec::new(),
+            srgb: None,
+        })
+    }
+
+    /// Decode uncompressed RGBA8 / RGBA16F KTX2 containers, including mip chains
+    #[cfg(feature = "ktx2")]
+    fn decode_ktx2(data: &[u8]) -> Result<ImageBuffer, String> {
+        let reader = ktx2::Reader::new(data).map_err(|e| format!("Invalid KTX2: {:?}", e))?;
+        let header = reader.header();
+
+        if header.supercompression_scheme.is_some() {
+            return Err("Supercompressed KTX2 textures are not supported".into());
+        }
+        if header.face_count != 1 || header.layer_count > 1 || header.pixel_depth > 1 {
+            return Err("Only 2D KTX2 textures are supported".into());
+        }
+
+        let (is_float, srgb) = match header.format {
+            Some(ktx2::Format::R8G8B8A8_UNORM) => (false, Some(false)),
+            Some(ktx2::Format::R8G8B8A8_SRGB) => (false, Some(true)),
+            Some(ktx2::Format::R16G16B16A16_SFLOAT) => (true, None),
+            other => return Err(format!("Unsupported KTX2 format: {:?}", other)),
+        };
+
+        let texel_bytes = if is_float { 8 } else { 4 };
+        let width = header.pixel_width;
+        let height = header.pixel_height.max(1);
+        let mut levels = Vec::new();
+        for (index, level) in reader.levels().enumerate() {
+            let level_width = (width >> index).max(1) as usize;
+            let level_height = (height >> index).max(1) as usize;
+            if level.data.len() != level_width * level_height * texel_bytes {
+                return Err(format!("KTX2 mip level {} has an unexpected size", index));
+            }
+            levels.push(if is_float {
+                ImagePixels::Rgba16F(bytemuck::pod_collect_to_vec(level.data))
+            } else {
+                ImagePixels::Rgba8(level.data.to_vec())
+            });
+        }
+        if levels.is_empty() {
+            return Err("KTX2 texture has no mip levels".into());
+        }
+        let pixels = levels.remove(0);
+
+        Ok(ImageBuffer {
+            width,
+            height,
+            pixels,
+            mips: levels,
+            srgb,
         })
     }
+
+    #[cfg(not(feature = "ktx2"))]
+    fn decode_ktx2(_data: &[u8]) -> Result<ImageBuffer, String> {
+        Err("KTX2 support requires the `ktx2` feature".into())
+    }
 }
+
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
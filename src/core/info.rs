@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+use super::VulframResult;
+
+/// Version of the exported function set and their signatures.
+/// Bump when a `vulfram_*` export is added, removed or changes signature.
+pub const ABI_VERSION: u32 = 8;
+
+/// Version of the MessagePack schema (`EngineCmd`, `CommandResponse`, `EngineEvent`).
+/// Bump whenever any of those enums or their payloads change shape.
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// Build information for host compatibility checks
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineInfo {
+    pub version: String,
+    pub abi_version: u32,
+    pub schema_version: u32,
+    pub features: Vec<String>,
+}
+
+impl EngineInfo {
+    pub fn current() -> Self {
+        let features = [
+            ("napi", cfg!(feature = "napi")),
+            ("ffi", cfg!(feature = "ffi")),
+            ("lua", cfg!(feature = "lua")),
+            ("python", cfg!(feature = "python")),
+            ("wasm", cfg!(feature = "wasm")),
+            ("gltf", cfg!(feature = "gltf")),
+            ("ktx2", cfg!(feature = "ktx2")),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            abi_version: ABI_VERSION,
+            schema_version: SCHEMA_VERSION,
+            features,
+        }
+    }
+}
+
+/// ABI version of this build (does not require `vulfram_init`)
+pub fn vulfram_abi_version() -> u32 {
+    ABI_VERSION
+}
+
+/// Build information as MessagePack (does not require `vulfram_init`)
+pub fn vulfram_get_info(out_ptr: *mut *const u8, out_length: *mut usize) -> VulframResult {
+    let serialized_data = match rmp_serde::to_vec_named(&EngineInfo::current()) {
+        Ok(data) => data,
+        Err(_) => return VulframResult::UnknownError,
+    };
+
+    let data_length = serialized_data.len();
+
+    // Transfer ownership via Box::into_raw (zero-copy)
+    let boxed = serialized_data.into_boxed_slice();
+    let ptr = Box::into_raw(boxed) as *mut u8;
+
+    unsafe {
+        *out_ptr = ptr;
+        *out_length = data_length;
+    }
+
+    VulframResult::Success
+}
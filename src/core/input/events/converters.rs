@@ -34,6 +34,20 @@ pub fn convert_key_location(location: winit::keyboard::KeyLocation) -> u32 {
     }
 }
 
+/// Convert a winit logical Key to the W3C `KeyboardEvent.key` value the browser reports:
+/// the produced character, or the key's name ("Enter", "ArrowLeft", "Control"...)
+pub fn convert_logical_key(key: &winit::keyboard::Key) -> String {
+    use winit::keyboard::{Key, NamedKey};
+
+    match key {
+        Key::Character(text) => text.to_string(),
+        Key::Named(NamedKey::Space) => " ".into(),
+        Key::Named(named) => format!("{:?}", named),
+        Key::Dead(_) => "Dead".into(),
+        Key::Unidentified(_) => "Unidentified".into(),
+    }
+}
+
 /// Convert winit PhysicalKey to u32
 pub fn convert_key_code(physical_key: &winit::keyboard::PhysicalKey) -> u32 {
     use winit::keyboard::KeyCode as WKeyCode;
@@ -10,14 +10,24 @@ pub enum KeyboardEvent {
     #[serde(rename_all = "camelCase")]
     OnInput {
         window_id: u32,
+        /// Physical key position, independent of the keyboard layout
         key_code: u32,
+        /// Layout-aware key as the W3C `KeyboardEvent.key` value ("s", "S", "Enter"...)
+        logical_key: String,
         state: ElementState,
         location: u32,
+        /// Auto-repeat of a held key; false on the initial press
         repeat: bool,
         text: Option<String>,
+        /// Modifiers held when the key changed
         modifiers: ModifiersState,
     },
 
+    /// Text typed into the window: layout-resolved characters (dead keys applied) from a
+    /// key press, or an IME commit. Use this for text fields instead of key codes.
+    #[serde(rename_all = "camelCase")]
+    OnText { window_id: u32, text: String },
+
     /// Modifiers changed
     #[serde(rename_all = "camelCase")]
     OnModifiersChange {
@@ -45,3 +55,19 @@ pub enum KeyboardEvent {
     #[serde(rename_all = "camelCase")]
     OnImeDisable { window_id: u32 },
 }
+
+impl KeyboardEvent {
+    /// Text entry for characters produced by a key press or IME commit. Control characters
+    /// are dropped, and so are presses made while Ctrl (without Alt, which AltGr reports
+    /// as) or Meta is held, since those are shortcuts rather than typing.
+    pub fn text_entry(window_id: u32, text: &str, modifiers: ModifiersState) -> Option<Self> {
+        if (modifiers.ctrl && !modifiers.alt) || modifiers.meta {
+            return None;
+        }
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        if text.is_empty() {
+            return None;
+        }
+        Some(Self::OnText { window_id, text })
+    }
+}
@@ -11,7 +11,8 @@ pub use common::ModifiersState;
 pub use common::TouchPhase;
 #[cfg(not(feature = "wasm"))]
 pub use converters::{
-    convert_key_code, convert_key_location, convert_mouse_button, convert_touch_phase,
+    convert_key_code, convert_key_location, convert_logical_key, convert_mouse_button,
+    convert_touch_phase,
 };
 pub use keyboard::KeyboardEvent;
 pub use pointer::PointerEvent;
@@ -10,7 +10,8 @@ pub use cache::InputCacheManager;
 pub use events::{ElementState, KeyboardEvent, ModifiersState, PointerEvent, ScrollDelta};
 #[cfg(not(feature = "wasm"))]
 pub use events::{
-    convert_key_code, convert_key_location, convert_mouse_button, convert_touch_phase,
+    convert_key_code, convert_key_location, convert_logical_key, convert_mouse_button,
+    convert_touch_phase,
 };
 #[cfg(not(feature = "wasm"))]
 pub use state::InputState;
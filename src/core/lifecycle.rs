@@ -2,12 +2,21 @@ use crate::core::platforms::DefaultPlatformProxy;
 use std::thread;
 
 use super::VulframResult;
+use super::codec::Codec;
 use super::singleton::{ENGINE_INSTANCE, EngineSingleton, MAIN_THREAD_ID};
 use super::state::EngineState;
 
 /// Initialize the engine (must be called from the main thread)
 pub fn vulfram_init() -> VulframResult {
-    let _ = env_logger::try_init();
+    vulfram_init_with_codec(Codec::MsgPack as u32)
+}
+
+/// Initialize the engine with the wire format used by the queue functions
+pub fn vulfram_init_with_codec(codec: u32) -> VulframResult {
+    let Some(codec) = Codec::from_u32(codec) else {
+        return VulframResult::UnsupportedCodec;
+    };
+    super::logging::install();
     #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
     if web_sys::window().is_none() {
         return VulframResult::NotInBrowser;
@@ -27,8 +36,9 @@ pub fn vulfram_init() -> VulframResult {
         } else {
             let platform = DefaultPlatformProxy::new();
             let mut state = EngineState::new();
+            state.codec = codec;
             if let Err(message) = state.audio.init() {
-                eprintln!("Audio init failed: {}", message);
+                log::error!("Audio init failed: {}", message);
             }
             *opt = Some(EngineSingleton { state, platform });
             return VulframResult::Success;
@@ -59,6 +69,7 @@ pub fn vulfram_dispose() -> VulframResult {
             // Clear all windows to drop GPU resources
             singleton.state.window.states.clear();
             singleton.state.window.window_id_map.clear();
+            singleton.state.shared.drop_all();
         }
 
         *opt = None;
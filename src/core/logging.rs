@@ -0,0 +1,175 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::cell::RefCell;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+
+use super::VulframResult;
+use super::singleton::{MAIN_THREAD_ID, validate_thread};
+
+/// Host log sink: receives `(level, message)` where level is 1 = error … 5 = trace.
+/// Always invoked on the main thread; records from worker threads are queued and
+/// delivered on the next main-thread log or `vulfram_tick`.
+pub type LogCallback = Box<dyn Fn(u32, &str)>;
+
+/// C signature for `vulfram_set_log_callback` (message is UTF-8, not NUL-terminated)
+pub type LogCallbackFn = extern "C" fn(level: u32, message_ptr: *const u8, message_length: usize);
+
+thread_local! {
+    static CALLBACK: RefCell<Option<LogCallback>> = RefCell::new(None);
+}
+
+static HAS_CALLBACK: AtomicBool = AtomicBool::new(false);
+/// `LevelFilter` of the host callback (defaults to info)
+static CALLBACK_LEVEL: AtomicUsize = AtomicUsize::new(3);
+/// `LevelFilter` parsed from `RUST_LOG` for the stderr fallback
+static ENV_LEVEL: AtomicUsize = AtomicUsize::new(1);
+static PENDING: Mutex<Vec<(u32, String)>> = Mutex::new(Vec::new());
+
+struct EngineLogger {
+    fallback: env_logger::Logger,
+}
+
+impl Log for EngineLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        if HAS_CALLBACK.load(Ordering::Relaxed) {
+            metadata.level() as usize <= CALLBACK_LEVEL.load(Ordering::Relaxed)
+        } else {
+            self.fallback.enabled(metadata)
+        }
+    }
+
+    fn log(&self, record: &Record) {
+        if !HAS_CALLBACK.load(Ordering::Relaxed) {
+            self.fallback.log(record);
+            return;
+        }
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let level = level_to_u32(record.level());
+        let message = format!("[{}] {}", record.target(), record.args());
+        if is_main_thread() {
+            flush_pending_logs();
+        }
+        if (!is_main_thread() || !dispatch(level, &message))
+            && let Ok(mut pending) = PENDING.lock()
+        {
+            pending.push((level, message));
+        }
+    }
+
+    fn flush(&self) {
+        self.fallback.flush();
+    }
+}
+
+fn level_to_u32(level: Level) -> u32 {
+    match level {
+        Level::Error => 1,
+        Level::Warn => 2,
+        Level::Info => 3,
+        Level::Debug => 4,
+        Level::Trace => 5,
+    }
+}
+
+fn level_filter_from_u32(level: u32) -> Option<LevelFilter> {
+    match level {
+        0 => Some(LevelFilter::Off),
+        1 => Some(LevelFilter::Error),
+        2 => Some(LevelFilter::Warn),
+        3 => Some(LevelFilter::Info),
+        4 => Some(LevelFilter::Debug),
+        5 => Some(LevelFilter::Trace),
+        _ => None,
+    }
+}
+
+fn update_max_level() {
+    let level = if HAS_CALLBACK.load(Ordering::Relaxed) {
+        CALLBACK_LEVEL.load(Ordering::Relaxed)
+    } else {
+        ENV_LEVEL.load(Ordering::Relaxed)
+    };
+    log::set_max_level(level_filter_from_u32(level as u32).unwrap_or(LevelFilter::Trace));
+}
+
+fn is_main_thread() -> bool {
+    MAIN_THREAD_ID.get() == Some(&thread::current().id())
+}
+
+/// Call the host callback; returns false when it is busy (re-entrant log) or unset
+fn dispatch(level: u32, message: &str) -> bool {
+    CALLBACK.with(|cell| match cell.try_borrow() {
+        Ok(callback) => match callback.as_ref() {
+            Some(callback) => {
+                callback(level, message);
+                true
+            }
+            None => false,
+        },
+        Err(_) => false,
+    })
+}
+
+/// Install the engine logger (idempotent). Defaults to `env_logger` on stderr.
+pub fn install() {
+    let fallback = env_logger::Builder::from_default_env().build();
+    let filter = fallback.filter();
+    if log::set_boxed_logger(Box::new(EngineLogger { fallback })).is_ok() {
+        ENV_LEVEL.store(filter as usize, Ordering::Relaxed);
+        update_max_level();
+    }
+}
+
+/// Deliver records queued from worker threads. Main thread only.
+pub fn flush_pending_logs() {
+    if !HAS_CALLBACK.load(Ordering::Relaxed) {
+        return;
+    }
+    let pending = match PENDING.lock() {
+        Ok(mut pending) if !pending.is_empty() => std::mem::take(&mut *pending),
+        _ => return,
+    };
+    for (level, message) in pending {
+        dispatch(level, &message);
+    }
+}
+
+/// Route engine logs to `callback` instead of stderr (`None` restores stderr)
+pub fn set_log_callback(callback: Option<LogCallback>) -> VulframResult {
+    if let Err(e) = validate_thread() {
+        return e;
+    }
+    install();
+    let enabled = callback.is_some();
+    CALLBACK.with(|cell| *cell.borrow_mut() = callback);
+    HAS_CALLBACK.store(enabled, Ordering::Relaxed);
+    update_max_level();
+    if !enabled && let Ok(mut pending) = PENDING.lock() {
+        pending.clear();
+    }
+    VulframResult::Success
+}
+
+/// C-ABI variant of `set_log_callback`
+pub fn vulfram_set_log_callback(callback: Option<LogCallbackFn>) -> VulframResult {
+    set_log_callback(callback.map(|callback| {
+        Box::new(move |level: u32, message: &str| callback(level, message.as_ptr(), message.len()))
+            as LogCallback
+    }))
+}
+
+/// Maximum level forwarded to the host callback: 0 = off, 1 = error … 5 = trace.
+/// The stderr fallback keeps following `RUST_LOG`.
+pub fn vulfram_set_log_level(level: u32) -> VulframResult {
+    let Some(filter) = level_filter_from_u32(level) else {
+        return VulframResult::UnknownError;
+    };
+    install();
+    CALLBACK_LEVEL.store(filter as usize, Ordering::Relaxed);
+    update_max_level();
+    VulframResult::Success
+}
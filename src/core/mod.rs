@@ -1,10 +1,15 @@
+#[cfg(feature = "gltf")]
+pub mod asset;
 pub mod audio;
 pub mod buffers;
 pub mod cmd;
+pub mod codec;
 pub mod gamepad;
 pub mod image;
+pub mod info;
 pub mod input;
 mod lifecycle;
+mod logging;
 pub mod platform;
 pub mod platforms;
 pub mod profiling;
@@ -15,19 +20,40 @@ mod singleton;
 mod state;
 pub mod system;
 mod tick;
+#[cfg(not(feature = "wasm"))]
+mod trace;
 pub mod window;
 
 // Re-exports for public API
+#[cfg(feature = "gltf")]
 #[allow(unused)]
-pub use buffers::vulfram_upload_buffer;
+pub use asset::vulfram_load_gltf;
 #[allow(unused)]
-pub use lifecycle::{vulfram_dispose, vulfram_init};
+pub use buffers::{
+    vulfram_download_buffer, vulfram_free_buffer, vulfram_update_buffer, vulfram_upload_buffer,
+};
+#[allow(unused)]
+pub use info::{vulfram_abi_version, vulfram_get_info};
+#[allow(unused)]
+pub use lifecycle::{vulfram_dispose, vulfram_init, vulfram_init_with_codec};
+#[allow(unused)]
+pub use logging::{
+    LogCallback, LogCallbackFn, set_log_callback, vulfram_set_log_callback, vulfram_set_log_level,
+};
 #[allow(unused)]
 pub use profiling::vulfram_get_profiling;
 #[allow(unused)]
-pub use queue::{vulfram_receive_events, vulfram_receive_queue, vulfram_send_queue};
+pub use queue::{
+    vulfram_pending_command_count, vulfram_receive_events, vulfram_receive_queue,
+    vulfram_send_queue,
+};
+#[allow(unused)]
+pub use system::vulfram_get_adapter_info;
+#[allow(unused)]
+pub use tick::{vulfram_flush, vulfram_tick};
+#[cfg(not(feature = "wasm"))]
 #[allow(unused)]
-pub use tick::vulfram_tick;
+pub use trace::{vulfram_record_start, vulfram_record_stop, vulfram_replay};
 
 #[derive(Debug, PartialEq, Eq)]
 #[repr(u32)]
@@ -43,4 +69,11 @@ pub enum VulframResult {
     BufferNotFound,
     BufferIdCollision,
     InvalidUploadType,
+    AssetImportError,
+    QueueFull,
+    UnsupportedCodec,
+    TraceIoError,
+    BufferOutOfRange,
+    GpuFlushFailed,
+    NoAdapter,
 }
@@ -28,23 +28,16 @@ pub fn attach_canvas_listeners(
 
         let _ = with_engine(|engine| {
             if let Some(window_state) = engine.window.states.get_mut(&window_id) {
-                window_state.config.width = width;
-                window_state.config.height = height;
-                if let Some(device) = engine.device.as_ref() {
-                    window_state.surface.configure(device, &window_state.config);
-                    window_state.render_state.on_resize(device, width, height);
-                }
+                window_state.request_resize(glam::UVec2::new(width, height));
                 window_state.inner_size = glam::UVec2::new(width, height);
                 window_state.outer_size = glam::UVec2::new(width, height);
-                window_state.is_dirty = true;
             }
-            engine
-                .event_queue
-                .push(EngineEvent::Window(WindowEvent::OnResize {
-                    window_id,
-                    width,
-                    height,
-                }));
+            WindowEvent::OnResize {
+                window_id,
+                width,
+                height,
+            }
+            .push_coalesced(&mut engine.event_queue);
         });
     }) as Box<dyn FnMut(Event)>);
     let _ =
@@ -53,6 +46,9 @@ pub fn attach_canvas_listeners(
 
     let focus_closure = Closure::wrap(Box::new(move |_event: Event| {
         let _ = with_engine(|engine| {
+            if let Some(window_state) = engine.window.states.get_mut(&window_id) {
+                window_state.focused = true;
+            }
             engine
                 .event_queue
                 .push(EngineEvent::Window(WindowEvent::OnFocus {
@@ -67,6 +63,9 @@ pub fn attach_canvas_listeners(
 
     let blur_closure = Closure::wrap(Box::new(move |_event: Event| {
         let _ = with_engine(|engine| {
+            if let Some(window_state) = engine.window.states.get_mut(&window_id) {
+                window_state.focused = false;
+            }
             engine
                 .event_queue
                 .push(EngineEvent::Window(WindowEvent::OnFocus {
@@ -94,7 +93,7 @@ pub fn attach_canvas_listeners(
             .key()
             .chars()
             .next()
-            .filter(|_| event.key().len() == 1)
+            .filter(|_| event.key().chars().count() == 1)
             .map(|_| event.key());
 
         let _ = with_engine(|engine| {
@@ -103,12 +102,20 @@ pub fn attach_canvas_listeners(
                 .push(EngineEvent::Keyboard(CoreKeyboardEvent::OnInput {
                     window_id,
                     key_code,
+                    logical_key: event.key(),
                     state: ElementState::Pressed,
                     location: event.location() as u32,
                     repeat: event.repeat(),
-                    text,
+                    text: text.clone(),
                     modifiers,
                 }));
+            // `key` already has the layout and dead keys applied ("Dead" while composing)
+            if let Some(text_event) = text
+                .as_deref()
+                .and_then(|text| CoreKeyboardEvent::text_entry(window_id, text, modifiers))
+            {
+                engine.event_queue.push(EngineEvent::Keyboard(text_event));
+            }
         });
     }) as Box<dyn FnMut(Event)>);
     let _ = window
@@ -131,7 +138,7 @@ pub fn attach_canvas_listeners(
             .key()
             .chars()
             .next()
-            .filter(|_| event.key().len() == 1)
+            .filter(|_| event.key().chars().count() == 1)
             .map(|_| event.key());
 
         let _ = with_engine(|engine| {
@@ -140,6 +147,7 @@ pub fn attach_canvas_listeners(
                 .push(EngineEvent::Keyboard(CoreKeyboardEvent::OnInput {
                     window_id,
                     key_code,
+                    logical_key: event.key(),
                     state: ElementState::Released,
                     location: event.location() as u32,
                     repeat: event.repeat(),
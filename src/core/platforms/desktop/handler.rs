@@ -5,17 +5,44 @@ use glam::{IVec2, UVec2, Vec2};
 
 use crate::core::input::{
     ElementState, KeyboardEvent, ModifiersState, PointerEvent, ScrollDelta, convert_key_code,
-    convert_key_location, convert_mouse_button, convert_touch_phase,
+    convert_key_location, convert_logical_key, convert_mouse_button, convert_touch_phase,
 };
 use crate::core::render::render_frames;
 use crate::core::system::SystemEvent;
-use crate::core::window::WindowEvent;
-use crate::core::window::engine_cmd_window_create;
+use crate::core::window::{CmdResultWindowSetCursorImage, engine_cmd_window_create};
+use crate::core::window::{EngineWindowState, WindowEvent, query_window_state};
 
 use crate::core::cmd::{CommandResponse, CommandResponseEnvelope, EngineEvent};
 use crate::core::singleton::EngineCustomEvents;
 use crate::core::state::EngineState;
 
+/// Emits `OnStateChange` when the window's state differs from the last one reported
+fn sync_window_state(engine: &mut EngineState, window_id: u32) {
+    let Some(window_state) = engine.window.states.get_mut(&window_id) else {
+        return;
+    };
+    let state = query_window_state(&window_state.window);
+    let minimized = state == EngineWindowState::Minimized;
+    if window_state.minimized && !minimized {
+        // Frames were skipped while minimized; draw the restored window right away
+        window_state.is_dirty = true;
+    }
+    window_state.minimized = minimized;
+
+    let cache = engine.window.cache.get_or_create(window_id);
+    if cache.state == state {
+        return;
+    }
+    cache.state = state;
+
+    engine
+        .event_queue
+        .push(EngineEvent::Window(WindowEvent::OnStateChange {
+            window_id,
+            state,
+        }));
+}
+
 impl ApplicationHandler<EngineCustomEvents> for EngineState {
     fn resumed(&mut self, _event_loop: &ActiveEventLoop) {
         self.event_queue
@@ -48,9 +75,39 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
             None => return,
         };
 
+        // Minimize, maximize and restore have no winit event of their own but arrive as
+        // one of these, so the state is read back whenever one shows up
+        if matches!(
+            event,
+            WinitWindowEvent::Resized(_)
+                | WinitWindowEvent::Moved(_)
+                | WinitWindowEvent::Focused(_)
+                | WinitWindowEvent::Occluded(_)
+        ) {
+            sync_window_state(self, window_id);
+        }
+
         match event {
             WinitWindowEvent::Resized(size) => {
-                let new_size = UVec2::new(size.width, size.height);
+                let mut new_size = UVec2::new(size.width, size.height);
+
+                // Enforce size constraints (aspect lock has no native winit support).
+                // Zero sizes mean minimized and are left alone.
+                if let Some(window_state) = self.window.states.get(&window_id)
+                    && new_size.cmpgt(UVec2::ZERO).all()
+                {
+                    let clamped = window_state.size_constraints.clamp(new_size);
+                    if clamped != new_size {
+                        let _ =
+                            window_state
+                                .window
+                                .request_inner_size(winit::dpi::PhysicalSize::new(
+                                    clamped.x, clamped.y,
+                                ));
+                        new_size = clamped;
+                    }
+                }
+
                 let cache = self.window.cache.get_or_create(window_id);
 
                 // Only dispatch event if size actually changed
@@ -62,37 +119,26 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
                 // Update cache
                 cache.inner_size = new_size;
 
-                // Update surface configuration with new size
+                // Queue the surface and camera target rebuild; it runs right before the
+                // window's next render so bursts of resize events reconfigure only once
                 if let Some(window_state) = self.window.states.get_mut(&window_id) {
-                    if size.width > 0 && size.height > 0 {
-                        window_state.config.width = size.width;
-                        window_state.config.height = size.height;
-                        // SAFETY: device is always Some after initialization
-                        let device = unsafe { self.device.as_ref().unwrap_unchecked() };
-                        window_state.surface.configure(device, &window_state.config);
-
-                        // Update camera targets and projections
-                        window_state
-                            .render_state
-                            .on_resize(device, size.width, size.height);
+                    if new_size.x > 0 && new_size.y > 0 {
+                        window_state.request_resize(new_size);
 
                         // Update size state
                         window_state.inner_size = new_size;
                         let outer_size = window_state.window.outer_size();
                         window_state.outer_size = UVec2::new(outer_size.width, outer_size.height);
                         cache.outer_size = UVec2::new(outer_size.width, outer_size.height);
-
-                        // Mark window as dirty to trigger redraw
-                        window_state.is_dirty = true;
                     }
                 }
 
-                self.event_queue
-                    .push(EngineEvent::Window(WindowEvent::OnResize {
-                        window_id,
-                        width: size.width,
-                        height: size.height,
-                    }));
+                WindowEvent::OnResize {
+                    window_id,
+                    width: new_size.x,
+                    height: new_size.y,
+                }
+                .push_coalesced(&mut self.event_queue);
             }
 
             WinitWindowEvent::Moved(position) => {
@@ -117,11 +163,11 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
                     }
                 }
 
-                self.event_queue
-                    .push(EngineEvent::Window(WindowEvent::OnMove {
-                        window_id,
-                        position: new_pos,
-                    }));
+                WindowEvent::OnMove {
+                    window_id,
+                    position: new_pos,
+                }
+                .push_coalesced(&mut self.event_queue);
             }
 
             WinitWindowEvent::CloseRequested => {
@@ -181,6 +227,9 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
             }
 
             WinitWindowEvent::Focused(focused) => {
+                if let Some(window_state) = self.window.states.get_mut(&window_id) {
+                    window_state.focused = focused;
+                }
                 let cache = self.window.cache.get_or_create(window_id);
 
                 // Only dispatch event if focus state actually changed
@@ -209,6 +258,7 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
                 }
 
                 let key_code = convert_key_code(&event.physical_key);
+                let logical_key = convert_logical_key(&event.logical_key);
                 let location = convert_key_location(event.location);
                 let state = if event.state.is_pressed() {
                     ElementState::Pressed
@@ -220,12 +270,21 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
                     .push(EngineEvent::Keyboard(KeyboardEvent::OnInput {
                         window_id,
                         key_code,
+                        logical_key,
                         state,
                         location,
                         repeat: event.repeat,
-                        text: event.text.map(|s| s.into()),
+                        text: event.text.as_ref().map(|s| s.to_string()),
                         modifiers: self.input.modifiers,
                     }));
+
+                if event.state.is_pressed()
+                    && let Some(text_event) = event.text.as_ref().and_then(|text| {
+                        KeyboardEvent::text_entry(window_id, text, self.input.modifiers)
+                    })
+                {
+                    self.event_queue.push(EngineEvent::Keyboard(text_event));
+                }
             }
 
             WinitWindowEvent::ModifiersChanged(modifiers) => {
@@ -262,6 +321,12 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
                         cursor_range: cursor,
                     },
                     winit::event::Ime::Commit(text) => {
+                        // Committed text is typed text too; modifiers do not apply to IME
+                        if let Some(text_event) =
+                            KeyboardEvent::text_entry(window_id, &text, ModifiersState::default())
+                        {
+                            self.event_queue.push(EngineEvent::Keyboard(text_event));
+                        }
                         KeyboardEvent::OnImeCommit { window_id, text }
                     }
                     winit::event::Ime::Disabled => KeyboardEvent::OnImeDisable { window_id },
@@ -405,7 +470,7 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
 
             WinitWindowEvent::ScaleFactorChanged {
                 scale_factor,
-                inner_size_writer: _,
+                mut inner_size_writer,
             } => {
                 let cache = self.window.cache.get_or_create(window_id);
 
@@ -414,17 +479,27 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
                     return;
                 }
 
-                // Update cache
+                let previous_scale = cache.scale_factor;
                 cache.scale_factor = scale_factor;
 
-                // Get the current window inner size for the event
+                // Keep the logical size across the change. The resulting Resized event
+                // reconfigures the surface and camera targets at the new physical size.
                 let (new_width, new_height) = self
                     .window
                     .states
                     .get(&window_id)
                     .map(|ws| {
                         let size = ws.window.inner_size();
-                        (size.width, size.height)
+                        let ratio = scale_factor / previous_scale.max(f64::EPSILON);
+                        let scaled = UVec2::new(
+                            (size.width as f64 * ratio).round() as u32,
+                            (size.height as f64 * ratio).round() as u32,
+                        );
+                        let clamped = ws.size_constraints.clamp(scaled);
+                        let _ = inner_size_writer.request_inner_size(
+                            winit::dpi::PhysicalSize::new(clamped.x, clamped.y),
+                        );
+                        (clamped.x, clamped.y)
                     })
                     .unwrap_or((0, 0));
 
@@ -457,6 +532,12 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
             }
 
             WinitWindowEvent::Occluded(occluded) => {
+                if let Some(window_state) = self.window.states.get_mut(&window_id) {
+                    if window_state.occluded && !occluded {
+                        window_state.is_dirty = true;
+                    }
+                    window_state.occluded = occluded;
+                }
                 let cache = self.window.cache.get_or_create(window_id);
 
                 // Only dispatch event if occluded state actually changed
@@ -504,7 +585,7 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
             EngineCustomEvents::CreateWindow(id, args) => {
                 let result = engine_cmd_window_create(self, event_loop, &args);
 
-                self.response_queue.push(CommandResponseEnvelope {
+                self.complete_async_cmd(CommandResponseEnvelope {
                     id,
                     response: CommandResponse::WindowCreate(result),
                 });
@@ -513,6 +594,28 @@ impl ApplicationHandler<EngineCustomEvents> for EngineState {
             EngineCustomEvents::NotificationInteraction(event) => {
                 self.event_queue.push(EngineEvent::System(event));
             }
+
+            EngineCustomEvents::SetCursorImage(id, window_id, source) => {
+                let result = match self.window.states.get(&window_id) {
+                    Some(window_state) => {
+                        let cursor = event_loop.create_custom_cursor(source);
+                        window_state.window.set_cursor(cursor);
+                        CmdResultWindowSetCursorImage {
+                            success: true,
+                            message: "Cursor image set successfully".into(),
+                        }
+                    }
+                    None => CmdResultWindowSetCursorImage {
+                        success: false,
+                        message: format!("Window with id {} not found", window_id),
+                    },
+                };
+
+                self.complete_async_cmd(CommandResponseEnvelope {
+                    id,
+                    response: CommandResponse::WindowSetCursorImage(result),
+                });
+            }
         }
 
         // Track time spent in custom events to exclude from profiling
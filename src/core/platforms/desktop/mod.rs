@@ -35,10 +35,13 @@ impl PlatformProxy for DesktopProxy {
         cmd_id: u64,
         args: &CmdWindowCreateArgs,
     ) -> Result<(), CmdResultWindowCreate> {
-        let _ = self
-            .proxy
-            .send_event(EngineCustomEvents::CreateWindow(cmd_id, args.clone()));
-        Ok(())
+        self.proxy
+            .send_event(EngineCustomEvents::CreateWindow(cmd_id, args.clone()))
+            .map_err(|_| CmdResultWindowCreate {
+                success: false,
+                message: "Event loop is closed".into(),
+                ..Default::default()
+            })
     }
 
     fn process_gamepads(&mut self, state: &mut EngineState) -> u64 {
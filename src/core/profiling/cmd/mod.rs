@@ -26,6 +26,14 @@ pub struct ProfilingData {
     pub window_fps: Vec<WindowFps>,
     pub total_events_dispatched: usize,
     pub total_events_cached: usize,
+    /// Bytes kept on the CPU by geometries created with `retainCpuCopy`
+    pub retained_geometry_bytes: u64,
+    /// Bytes kept on the CPU by textures created with `retainCpuCopy`
+    pub retained_texture_bytes: u64,
+    pub cmd_queue_depth: usize,
+    pub response_queue_depth: usize,
+    pub dropped_responses: usize,
+    pub dropped_events: usize,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -40,6 +48,9 @@ pub struct WindowFps {
 pub fn vulfram_get_profiling(out_ptr: *mut *const u8, out_length: *mut usize) -> VulframResult {
     match with_engine(|engine| {
         let mut window_fps = Vec::with_capacity(engine.window.states.len());
+        // Geometries and textures are shared by every window
+        let retained_geometry_bytes = engine.shared.vertex_retained_cpu_bytes();
+        let retained_texture_bytes = engine.shared.textures().borrow().retained_cpu_bytes();
         for (&window_id, window_state) in &engine.window.states {
             window_fps.push(WindowFps {
                 window_id,
@@ -71,6 +82,12 @@ pub fn vulfram_get_profiling(out_ptr: *mut *const u8, out_length: *mut usize) ->
             window_fps,
             total_events_dispatched: engine.profiling.total_events_dispatched,
             total_events_cached: engine.profiling.total_events_cached,
+            retained_geometry_bytes,
+            retained_texture_bytes,
+            cmd_queue_depth: engine.cmd_queue.len(),
+            response_queue_depth: engine.response_queue.len(),
+            dropped_responses: engine.profiling.dropped_responses,
+            dropped_events: engine.profiling.dropped_events,
         };
 
         // Serialize profiling data
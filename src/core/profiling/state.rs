@@ -11,6 +11,10 @@ pub struct TickProfiling {
     pub frame_delta_ns: u64,
     pub total_events_dispatched: usize,
     pub total_events_cached: usize,
+    /// Cumulative responses dropped by the queue limits
+    pub dropped_responses: usize,
+    /// Cumulative events dropped by the queue limits
+    pub dropped_events: usize,
     pub custom_events_ns: u64,
     pub gpu_shadow_ns: u64,
     pub gpu_light_cull_ns: u64,
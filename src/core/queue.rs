@@ -14,16 +14,21 @@ use super::singleton::with_engine;
 
 /// Send a batch of commands to the engine
 pub fn vulfram_send_queue(ptr: *const u8, length: usize) -> VulframResult {
-    let data = unsafe { std::slice::from_raw_parts(ptr, length).to_vec() };
-
-    let batch = match rmp_serde::from_slice::<EngineBatchCmds>(&data) {
-        Err(_) => {
-            return VulframResult::CmdInvalidMessagePackError;
-        }
-        Ok(batch) => batch,
-    };
+    let data = unsafe { std::slice::from_raw_parts(ptr, length) };
 
     match with_engine(|engine| {
+        let batch = match engine.codec.decode::<EngineBatchCmds>(data) {
+            Err(_) => {
+                return VulframResult::CmdInvalidMessagePackError;
+            }
+            Ok(batch) => batch,
+        };
+        if !engine
+            .queue_limits
+            .accepts_commands(engine.cmd_queue.len(), batch.len())
+        {
+            return VulframResult::QueueFull;
+        }
         engine.cmd_queue.extend(batch);
         VulframResult::Success
     }) {
@@ -32,6 +37,21 @@ pub fn vulfram_send_queue(ptr: *const u8, length: usize) -> VulframResult {
     }
 }
 
+/// Number of commands that have not produced a response yet.
+/// Includes queued commands and async ones in flight (e.g. `CmdWindowCreate`,
+/// `CmdWindowScreenshot`), plus texture decodes whose completion event has not been emitted.
+pub fn vulfram_pending_command_count(out_count: *mut u32) -> VulframResult {
+    match with_engine(|engine| {
+        unsafe {
+            *out_count = engine.pending_command_count() as u32;
+        }
+        VulframResult::Success
+    }) {
+        Err(e) => e,
+        Ok(r) => r,
+    }
+}
+
 /// Receive a batch of command responses from the engine
 pub fn vulfram_receive_queue(out_ptr: *mut *const u8, out_length: *mut usize) -> VulframResult {
     match with_engine(|engine| {
@@ -49,7 +69,7 @@ pub fn vulfram_receive_queue(out_ptr: *mut *const u8, out_length: *mut usize) ->
         let serialization_start = Instant::now();
         #[cfg(feature = "wasm")]
         let serialization_start = now_ns();
-        let serialized_data = match rmp_serde::to_vec_named(&engine.response_queue) {
+        let serialized_data = match engine.codec.encode(&engine.response_queue) {
             Ok(data) => data,
             Err(_) => return VulframResult::UnknownError,
         };
@@ -97,7 +117,7 @@ pub fn vulfram_receive_events(out_ptr: *mut *const u8, out_length: *mut usize) -
         let serialization_start = Instant::now();
         #[cfg(feature = "wasm")]
         let serialization_start = now_ns();
-        let serialized_data = match rmp_serde::to_vec_named(&engine.event_queue) {
+        let serialized_data = match engine.codec.encode(&engine.event_queue) {
             Ok(data) => data,
             Err(_) => return VulframResult::UnknownError,
         };
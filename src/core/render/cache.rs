@@ -1,9 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+/// Pipelines compiled on worker threads at once; later ones queue until a worker is free
+const MAX_COMPILE_WORKERS: usize = 2;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u64)]
 pub enum ShaderId {
     Compose = 0,
+    ComposeEasu,
+    ComposeRcas,
     Post,
     Outline,
     Ssao,
@@ -21,6 +27,17 @@ pub enum ShaderId {
     ForwardStandard,
     ForwardPbr,
     Gizmo,
+    DebugShadowAtlas,
+    DebugShadowPages,
+    IndirectCull,
+    OutlineEdge,
+    OutlineEdgeMsaa,
+    Fog,
+    FogMsaa,
+    Particle,
+    ParticleMsaa,
+    LightCluster,
+    Sprite,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -36,17 +53,39 @@ pub struct PipelineKey {
     pub depth_write_enabled: bool,
     pub depth_compare: wgpu::CompareFunction,
     pub blend: Option<wgpu::BlendState>,
+    /// Conservative rasterization; only set when the device has the feature
+    pub conservative: bool,
 }
 
 #[derive(Debug)]
 struct PipelineEntry {
     pipeline: wgpu::RenderPipeline,
     last_used_frame: u64,
+    /// Compiled by `get_or_compile`; kept until `clear` so it never has to compile again
+    pinned: bool,
+}
+
+/// Pipeline waiting for a free compile worker
+struct QueuedCompile {
+    key: PipelineKey,
+    create: Box<dyn FnOnce() -> wgpu::RenderPipeline + Send>,
+}
+
+impl std::fmt::Debug for QueuedCompile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueuedCompile")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
 }
 
 #[derive(Debug)]
 pub struct RenderCache {
     pipelines: HashMap<PipelineKey, PipelineEntry>,
+    /// Pipelines compiling on a worker thread
+    pending: HashMap<PipelineKey, Receiver<wgpu::RenderPipeline>>,
+    /// Pipelines waiting for a worker, in request order
+    queued: VecDeque<QueuedCompile>,
     compute_pipelines: HashMap<ComputePipelineKey, ComputePipelineEntry>,
     max_unused_frames: u64,
 }
@@ -67,6 +106,8 @@ impl RenderCache {
     pub fn new() -> Self {
         Self {
             pipelines: HashMap::new(),
+            pending: HashMap::new(),
+            queued: VecDeque::new(),
             compute_pipelines: HashMap::new(),
             max_unused_frames: 3,
         }
@@ -84,22 +125,130 @@ impl RenderCache {
         let entry = self.pipelines.entry(key).or_insert_with(|| PipelineEntry {
             pipeline: create(),
             last_used_frame: frame_index,
+            pinned: false,
         });
 
         entry.last_used_frame = frame_index;
         &entry.pipeline
     }
 
+    /// Like `get_or_create`, but a missing pipeline compiles on a worker thread and `None`
+    /// is returned until it is ready, so callers skip the draw instead of stalling the frame.
+    /// At most `MAX_COMPILE_WORKERS` compile at once; the rest wait in request order.
+    /// With `wait` (or without threads) it compiles inline like `get_or_create`.
+    pub fn get_or_compile<F>(
+        &mut self,
+        key: PipelineKey,
+        frame_index: u64,
+        wait: bool,
+        create: F,
+    ) -> Option<&wgpu::RenderPipeline>
+    where
+        F: FnOnce() -> wgpu::RenderPipeline + wgpu::WasmNotSend + 'static,
+    {
+        if wait {
+            if let Some(receiver) = self.pending.remove(&key)
+                && let Ok(pipeline) = receiver.recv()
+            {
+                self.insert_compiled(key, pipeline, frame_index);
+            }
+            // Compiled inline below instead
+            self.queued.retain(|job| job.key != key);
+        }
+        self.collect_compiled(frame_index);
+
+        if !self.pipelines.contains_key(&key) {
+            #[cfg(not(feature = "wasm"))]
+            if !wait {
+                let known =
+                    self.pending.contains_key(&key) || self.queued.iter().any(|job| job.key == key);
+                if !known {
+                    self.queued.push_back(QueuedCompile {
+                        key,
+                        create: Box::new(create),
+                    });
+                    self.dispatch_compiles();
+                }
+                return None;
+            }
+            self.pipelines.insert(
+                key,
+                PipelineEntry {
+                    pipeline: create(),
+                    last_used_frame: frame_index,
+                    pinned: true,
+                },
+            );
+        }
+
+        let entry = self.pipelines.get_mut(&key)?;
+        entry.last_used_frame = frame_index;
+        Some(&entry.pipeline)
+    }
+
+    /// Pipelines still compiling on worker threads or waiting for one
+    pub fn pending_count(&self) -> usize {
+        self.pending.len() + self.queued.len()
+    }
+
+    fn insert_compiled(&mut self, key: PipelineKey, pipeline: wgpu::RenderPipeline, frame: u64) {
+        self.pipelines.insert(
+            key,
+            PipelineEntry {
+                pipeline,
+                last_used_frame: frame,
+                pinned: true,
+            },
+        );
+    }
+
+    /// Keeps the pipelines workers finished and hands their slots to queued compiles
+    fn collect_compiled(&mut self, frame_index: u64) {
+        let mut finished = Vec::new();
+        for (key, receiver) in &self.pending {
+            match receiver.try_recv() {
+                Ok(pipeline) => finished.push((*key, Some(pipeline))),
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => finished.push((*key, None)),
+            }
+        }
+        for (key, pipeline) in finished {
+            self.pending.remove(&key);
+            if let Some(pipeline) = pipeline {
+                self.insert_compiled(key, pipeline, frame_index);
+            }
+        }
+        self.dispatch_compiles();
+    }
+
+    fn dispatch_compiles(&mut self) {
+        #[cfg(not(feature = "wasm"))]
+        while self.pending.len() < MAX_COMPILE_WORKERS {
+            let Some(job) = self.queued.pop_front() else {
+                break;
+            };
+            let (sender, receiver) = std::sync::mpsc::channel();
+            let create = job.create;
+            std::thread::spawn(move || {
+                let _ = sender.send(create());
+            });
+            self.pending.insert(job.key, receiver);
+        }
+    }
+
     pub fn gc(&mut self, frame_index: u64) {
         let max_unused = self.max_unused_frames;
-        self.pipelines
-            .retain(|_, entry| frame_index.saturating_sub(entry.last_used_frame) <= max_unused);
+        self.pipelines.retain(|_, entry| {
+            entry.pinned || frame_index.saturating_sub(entry.last_used_frame) <= max_unused
+        });
         self.compute_pipelines
             .retain(|_, entry| frame_index.saturating_sub(entry.last_used_frame) <= max_unused);
     }
 
     pub fn clear(&mut self) {
         self.pipelines.clear();
+        self.pending.clear();
+        self.queued.clear();
         self.compute_pipelines.clear();
     }
 
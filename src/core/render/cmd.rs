@@ -1,6 +1,17 @@
+pub mod custom_pass;
+pub mod flush;
+
+pub use custom_pass::*;
+pub use flush::*;
+
 use serde::{Deserialize, Serialize};
 
-use crate::core::render::graph::{RenderGraphApplyResult, RenderGraphDesc};
+use crate::core::render::RenderState;
+use crate::core::render::graph::{
+    LogicalId, RenderGraphApplyResult, RenderGraphDesc, RenderGraphEdge,
+};
+use crate::core::render::state::DebugRenderMode;
+use crate::core::resources::{OutlineMode, SkyboxMode};
 use crate::core::state::EngineState;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -55,3 +66,226 @@ pub fn engine_cmd_render_graph_set(
         },
     }
 }
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdRenderGraphGetArgs {
+    pub window_id: u32,
+}
+
+/// One node of the active graph as it will execute this frame
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderGraphPassInfo {
+    pub node_id: LogicalId,
+    pub pass_id: String,
+    /// Position in execution order
+    pub order: u32,
+    pub inputs: Vec<LogicalId>,
+    pub outputs: Vec<LogicalId>,
+    /// Whether the pass contributes to the frame with the current configuration
+    pub enabled: bool,
+    /// Why the pass is skipped, when `enabled` is false
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultRenderGraphGet {
+    pub success: bool,
+    pub message: String,
+    pub graph_id: Option<LogicalId>,
+    pub fallback_used: bool,
+    /// Nodes in execution order
+    pub passes: Vec<RenderGraphPassInfo>,
+    pub edges: Vec<RenderGraphEdge>,
+}
+
+/// Why `pass_id` does nothing this frame, mirroring the early outs of each pass
+fn pass_skip_reason(render_state: &RenderState, pass_id: &str) -> Option<String> {
+    let environment = &render_state.environment;
+    let post = &environment.post;
+    let needs_camera = !matches!(pass_id, "shadow" | "light-cull" | "compose");
+    if needs_camera && render_state.scene.cameras.is_empty() {
+        return Some("No cameras".into());
+    }
+
+    match pass_id {
+        "shadow" if render_state.shadow.is_none() => Some("Shadow manager not initialized".into()),
+        "skybox" if matches!(environment.skybox.mode, SkyboxMode::None) => {
+            Some("Skybox mode is none".into())
+        }
+        "fog" if !environment.fog.enabled => Some("Fog disabled".into()),
+        "fog" if environment.fog.density <= 0.0 => Some("Fog density is 0".into()),
+        "particles" if render_state.scene.particle_systems.is_empty() => {
+            Some("No particle systems".into())
+        }
+        "outline" if !post.outline_enabled => Some(match post.outline_mode {
+            OutlineMode::EdgeDetect => "Outline disabled".into(),
+            OutlineMode::Hull => {
+                "Outline disabled; the pass runs but post ignores its output".into()
+            }
+        }),
+        "ssao" | "ssao-blur" if !post.ssao_enabled => Some("SSAO disabled".into()),
+        "bloom" if !post.bloom_enabled => Some("Bloom disabled; the target is cleared".into()),
+        _ => None,
+    }
+}
+
+pub fn engine_cmd_render_graph_get(
+    engine: &mut EngineState,
+    args: &CmdRenderGraphGetArgs,
+) -> CmdResultRenderGraphGet {
+    let window_state = match engine.window.states.get(&args.window_id) {
+        Some(state) => state,
+        None => {
+            return CmdResultRenderGraphGet {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+                ..Default::default()
+            };
+        }
+    };
+
+    let render_state = &window_state.render_state;
+    let plan = render_state.render_graph.plan();
+    let passes = plan
+        .order
+        .iter()
+        .enumerate()
+        .map(|(order, &idx)| {
+            let node = &plan.nodes[idx];
+            let reason = pass_skip_reason(render_state, &node.pass_id);
+            RenderGraphPassInfo {
+                node_id: node.node_id.clone(),
+                pass_id: node.pass_id.clone(),
+                order: order as u32,
+                inputs: node.inputs.clone(),
+                outputs: node.outputs.clone(),
+                enabled: reason.is_none(),
+                reason,
+            }
+        })
+        .collect();
+
+    CmdResultRenderGraphGet {
+        success: true,
+        message: "Render graph retrieved".into(),
+        graph_id: Some(plan.graph_id.clone()),
+        fallback_used: render_state.render_graph.uses_fallback(),
+        passes,
+        edges: plan.edges.clone(),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdRenderSetDebugModeArgs {
+    pub window_id: u32,
+    pub mode: DebugRenderMode,
+    /// Shadow atlas layer shown by `shadow-atlas`
+    #[serde(default)]
+    pub layer: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultRenderSetDebugMode {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_render_set_debug_mode(
+    engine: &mut EngineState,
+    args: &CmdRenderSetDebugModeArgs,
+) -> CmdResultRenderSetDebugMode {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(state) => state,
+        None => {
+            return CmdResultRenderSetDebugMode {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    let render_state = &mut window_state.render_state;
+    if args.mode == DebugRenderMode::ShadowAtlas {
+        let layers = render_state
+            .shadow
+            .as_ref()
+            .map(|shadow| shadow.config.atlas_layers)
+            .unwrap_or(0);
+        if args.layer >= layers {
+            return CmdResultRenderSetDebugMode {
+                success: false,
+                message: format!(
+                    "Shadow atlas layer {} out of range ({} layers)",
+                    args.layer, layers
+                ),
+            };
+        }
+    }
+
+    render_state.debug.mode = args.mode;
+    render_state.debug.layer = args.layer;
+
+    CmdResultRenderSetDebugMode {
+        success: true,
+        message: "Debug render mode set".into(),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdRenderWarmupPipelinesArgs {
+    pub window_id: u32,
+    /// Compile on the calling tick instead of on worker threads
+    #[serde(default)]
+    pub wait: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultRenderWarmupPipelines {
+    pub success: bool,
+    pub message: String,
+    /// Pipelines still compiling on worker threads
+    pub pending: u32,
+}
+
+pub fn engine_cmd_render_warmup_pipelines(
+    engine: &mut EngineState,
+    args: &CmdRenderWarmupPipelinesArgs,
+) -> CmdResultRenderWarmupPipelines {
+    let Some(device) = engine.device.as_ref() else {
+        return CmdResultRenderWarmupPipelines {
+            success: false,
+            message: "Device not initialized".into(),
+            pending: 0,
+        };
+    };
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(state) => state,
+        None => {
+            return CmdResultRenderWarmupPipelines {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+                pending: 0,
+            };
+        }
+    };
+
+    let pending = crate::core::render::passes::warmup_forward_pipelines(
+        &mut window_state.render_state,
+        device,
+        engine.frame_index,
+        args.wait,
+    );
+
+    CmdResultRenderWarmupPipelines {
+        success: true,
+        message: "Pipeline warmup started".into(),
+        pending: pending as u32,
+    }
+}
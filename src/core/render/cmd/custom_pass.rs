@@ -0,0 +1,228 @@
+use glam::Vec4;
+use serde::{Deserialize, Serialize};
+
+use crate::core::buffers::state::UploadType;
+use crate::core::render::state::custom_pass::validate_custom_pass_source;
+use crate::core::render::state::{CustomPassInsertPoint, CustomPassRecord};
+use crate::core::state::EngineState;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdCustomPassCreateArgs {
+    pub window_id: u32,
+    pub custom_pass_id: u32,
+    pub label: Option<String>,
+    /// Upload buffer (`ShaderSource`) with WGSL defining `fs_main`
+    pub buffer_id: u64,
+    #[serde(default)]
+    pub insert_point: CustomPassInsertPoint,
+    #[serde(default)]
+    pub order: i32,
+    /// Values exposed to the shader as `pass_params.params`
+    #[serde(default)]
+    pub params: Vec4,
+    #[serde(default = "crate::core::resources::common::default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultCustomPassCreate {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_custom_pass_create(
+    engine: &mut EngineState,
+    args: &CmdCustomPassCreateArgs,
+) -> CmdResultCustomPassCreate {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(state) => state,
+        None => {
+            return CmdResultCustomPassCreate {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    let passes = &mut window_state.render_state.custom_passes.passes;
+    if passes.contains_key(&args.custom_pass_id) && !args.overwrite {
+        return CmdResultCustomPassCreate {
+            success: false,
+            message: format!("Custom pass with id {} already exists", args.custom_pass_id),
+        };
+    }
+
+    let buffer = match engine.buffers.uploads.get(&args.buffer_id) {
+        Some(buffer) => buffer,
+        None => {
+            return CmdResultCustomPassCreate {
+                success: false,
+                message: format!("Buffer with id {} not found", args.buffer_id),
+            };
+        }
+    };
+    if buffer.upload_type != UploadType::ShaderSource {
+        return CmdResultCustomPassCreate {
+            success: false,
+            message: format!(
+                "Invalid buffer type. Expected ShaderSource, got {:?}",
+                buffer.upload_type
+            ),
+        };
+    }
+    let source = match std::str::from_utf8(&buffer.data) {
+        Ok(source) => source.to_string(),
+        Err(_) => {
+            return CmdResultCustomPassCreate {
+                success: false,
+                message: "Shader source is not valid UTF-8".into(),
+            };
+        }
+    };
+    if let Err(err) = validate_custom_pass_source(&source) {
+        return CmdResultCustomPassCreate {
+            success: false,
+            message: format!("Invalid custom pass shader: {}", err),
+        };
+    }
+    // A rejected shader leaves the upload in place so the host can fix and retry
+    engine.buffers.remove_upload(args.buffer_id);
+
+    passes.insert(
+        args.custom_pass_id,
+        CustomPassRecord::new(
+            args.label.clone(),
+            args.insert_point,
+            args.order,
+            args.enabled,
+            args.params,
+            source,
+        ),
+    );
+
+    CmdResultCustomPassCreate {
+        success: true,
+        message: "Custom pass created successfully".into(),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdCustomPassUpdateArgs {
+    pub window_id: u32,
+    pub custom_pass_id: u32,
+    pub label: Option<String>,
+    pub insert_point: Option<CustomPassInsertPoint>,
+    pub order: Option<i32>,
+    pub params: Option<Vec4>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultCustomPassUpdate {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_custom_pass_update(
+    engine: &mut EngineState,
+    args: &CmdCustomPassUpdateArgs,
+) -> CmdResultCustomPassUpdate {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(state) => state,
+        None => {
+            return CmdResultCustomPassUpdate {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    let record = match window_state
+        .render_state
+        .custom_passes
+        .passes
+        .get_mut(&args.custom_pass_id)
+    {
+        Some(record) => record,
+        None => {
+            return CmdResultCustomPassUpdate {
+                success: false,
+                message: format!("Custom pass with id {} not found", args.custom_pass_id),
+            };
+        }
+    };
+
+    if args.label.is_some() {
+        record.label = args.label.clone();
+    }
+    if let Some(insert_point) = args.insert_point {
+        record.insert_point = insert_point;
+    }
+    if let Some(order) = args.order {
+        record.order = order;
+    }
+    if let Some(params) = args.params {
+        record.params = params;
+    }
+    if let Some(enabled) = args.enabled {
+        record.enabled = enabled;
+    }
+
+    CmdResultCustomPassUpdate {
+        success: true,
+        message: "Custom pass updated successfully".into(),
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdCustomPassDisposeArgs {
+    pub window_id: u32,
+    pub custom_pass_id: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultCustomPassDispose {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_custom_pass_dispose(
+    engine: &mut EngineState,
+    args: &CmdCustomPassDisposeArgs,
+) -> CmdResultCustomPassDispose {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(state) => state,
+        None => {
+            return CmdResultCustomPassDispose {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    if window_state
+        .render_state
+        .custom_passes
+        .passes
+        .remove(&args.custom_pass_id)
+        .is_some()
+    {
+        CmdResultCustomPassDispose {
+            success: true,
+            message: "Custom pass disposed successfully".into(),
+        }
+    } else {
+        CmdResultCustomPassDispose {
+            success: false,
+            message: format!("Custom pass with id {} not found", args.custom_pass_id),
+        }
+    }
+}
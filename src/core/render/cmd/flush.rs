@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::state::EngineState;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdFlushArgs {
+    pub window_id: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultFlush {
+    pub success: bool,
+    pub message: String,
+}
+
+/// Submits pending queue writes and blocks until the GPU finished all submitted work.
+/// Does nothing before the first window created the device.
+pub(crate) fn flush_device(engine: &EngineState) -> Result<(), String> {
+    let (Some(device), Some(queue)) = (engine.device.as_ref(), engine.queue.as_ref()) else {
+        return Ok(());
+    };
+    queue.submit(std::iter::empty());
+    device
+        .poll(wgpu::PollType::wait_indefinitely())
+        .map(|_| ())
+        .map_err(|e| format!("GPU flush failed: {}", e))
+}
+
+pub fn engine_cmd_flush(engine: &mut EngineState, args: &CmdFlushArgs) -> CmdResultFlush {
+    if !engine.window.states.contains_key(&args.window_id) {
+        return CmdResultFlush {
+            success: false,
+            message: format!("Window {} not found", args.window_id),
+        };
+    }
+
+    if let Err(message) = flush_device(engine) {
+        return CmdResultFlush {
+            success: false,
+            message,
+        };
+    }
+    // Readbacks completed by the wait are handed out without another tick
+    crate::core::render::screenshot::process_screenshots(engine);
+
+    CmdResultFlush {
+        success: true,
+        message: "GPU work completed".into(),
+    }
+}
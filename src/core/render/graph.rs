@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 
+mod fallback;
+
+pub use fallback::fallback_graph;
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum LogicalId {
@@ -138,7 +142,9 @@ pub struct RenderGraphDesc {
 
 #[derive(Debug, Clone)]
 pub struct RenderGraphPlan {
+    pub graph_id: LogicalId,
     pub nodes: Vec<RenderGraphNode>,
+    pub edges: Vec<RenderGraphEdge>,
     pub order: Vec<usize>,
 }
 
@@ -199,6 +205,10 @@ impl RenderGraphState {
     pub fn plan(&self) -> &RenderGraphPlan {
         &self.active
     }
+
+    pub fn uses_fallback(&self) -> bool {
+        self.uses_fallback
+    }
 }
 
 pub fn validate_graph(desc: &RenderGraphDesc) -> Result<RenderGraphPlan, String> {
@@ -245,7 +255,9 @@ pub fn validate_graph(desc: &RenderGraphDesc) -> Result<RenderGraphPlan, String>
     let order = topo_sort(&desc.nodes, &desc.edges)?;
 
     Ok(RenderGraphPlan {
+        graph_id: desc.graph_id.clone(),
         nodes: desc.nodes.clone(),
+        edges: desc.edges.clone(),
         order,
     })
 }
@@ -302,6 +314,8 @@ fn is_known_pass(pass_id: &str) -> bool {
             | "light-cull"
             | "skybox"
             | "forward"
+            | "fog"
+            | "particles"
             | "outline"
             | "ssao"
             | "ssao-blur"
@@ -310,189 +324,3 @@ fn is_known_pass(pass_id: &str) -> bool {
             | "compose"
     )
 }
-
-pub fn fallback_graph() -> RenderGraphDesc {
-    RenderGraphDesc {
-        graph_id: LogicalId::Str("fallback".into()),
-        nodes: vec![
-            RenderGraphNode {
-                node_id: LogicalId::Str("shadow_pass".into()),
-                pass_id: "shadow".into(),
-                inputs: Vec::new(),
-                outputs: vec![LogicalId::Str("shadow_atlas".into())],
-                params: HashMap::new(),
-            },
-            RenderGraphNode {
-                node_id: LogicalId::Str("light_cull_pass".into()),
-                pass_id: "light-cull".into(),
-                inputs: Vec::new(),
-                outputs: Vec::new(),
-                params: HashMap::new(),
-            },
-            RenderGraphNode {
-                node_id: LogicalId::Str("forward_pass".into()),
-                pass_id: "forward".into(),
-                inputs: vec![LogicalId::Str("shadow_atlas".into())],
-                outputs: vec![
-                    LogicalId::Str("hdr_color".into()),
-                    LogicalId::Str("depth".into()),
-                ],
-                params: HashMap::new(),
-            },
-            RenderGraphNode {
-                node_id: LogicalId::Str("outline_pass".into()),
-                pass_id: "outline".into(),
-                inputs: vec![LogicalId::Str("depth".into())],
-                outputs: vec![LogicalId::Str("outline_color".into())],
-                params: HashMap::new(),
-            },
-            RenderGraphNode {
-                node_id: LogicalId::Str("ssao_pass".into()),
-                pass_id: "ssao".into(),
-                inputs: vec![LogicalId::Str("depth".into())],
-                outputs: vec![LogicalId::Str("ssao_raw".into())],
-                params: HashMap::new(),
-            },
-            RenderGraphNode {
-                node_id: LogicalId::Str("ssao_blur_pass".into()),
-                pass_id: "ssao-blur".into(),
-                inputs: vec![
-                    LogicalId::Str("ssao_raw".into()),
-                    LogicalId::Str("depth".into()),
-                ],
-                outputs: vec![LogicalId::Str("ssao_blur".into())],
-                params: HashMap::new(),
-            },
-            RenderGraphNode {
-                node_id: LogicalId::Str("bloom_pass".into()),
-                pass_id: "bloom".into(),
-                inputs: vec![LogicalId::Str("hdr_color".into())],
-                outputs: vec![LogicalId::Str("bloom_color".into())],
-                params: HashMap::new(),
-            },
-            RenderGraphNode {
-                node_id: LogicalId::Str("post_pass".into()),
-                pass_id: "post".into(),
-                inputs: vec![
-                    LogicalId::Str("hdr_color".into()),
-                    LogicalId::Str("outline_color".into()),
-                    LogicalId::Str("ssao_blur".into()),
-                    LogicalId::Str("bloom_color".into()),
-                ],
-                outputs: vec![LogicalId::Str("post_color".into())],
-                params: HashMap::new(),
-            },
-            RenderGraphNode {
-                node_id: LogicalId::Str("compose_pass".into()),
-                pass_id: "compose".into(),
-                inputs: vec![LogicalId::Str("post_color".into())],
-                outputs: vec![LogicalId::Str("swapchain".into())],
-                params: HashMap::new(),
-            },
-        ],
-        edges: vec![
-            RenderGraphEdge {
-                from_node_id: LogicalId::Str("shadow_pass".into()),
-                to_node_id: LogicalId::Str("forward_pass".into()),
-                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
-            },
-            RenderGraphEdge {
-                from_node_id: LogicalId::Str("forward_pass".into()),
-                to_node_id: LogicalId::Str("outline_pass".into()),
-                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
-            },
-            RenderGraphEdge {
-                from_node_id: LogicalId::Str("forward_pass".into()),
-                to_node_id: LogicalId::Str("ssao_pass".into()),
-                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
-            },
-            RenderGraphEdge {
-                from_node_id: LogicalId::Str("ssao_pass".into()),
-                to_node_id: LogicalId::Str("ssao_blur_pass".into()),
-                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
-            },
-            RenderGraphEdge {
-                from_node_id: LogicalId::Str("ssao_blur_pass".into()),
-                to_node_id: LogicalId::Str("post_pass".into()),
-                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
-            },
-            RenderGraphEdge {
-                from_node_id: LogicalId::Str("forward_pass".into()),
-                to_node_id: LogicalId::Str("bloom_pass".into()),
-                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
-            },
-            RenderGraphEdge {
-                from_node_id: LogicalId::Str("bloom_pass".into()),
-                to_node_id: LogicalId::Str("post_pass".into()),
-                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
-            },
-            RenderGraphEdge {
-                from_node_id: LogicalId::Str("outline_pass".into()),
-                to_node_id: LogicalId::Str("post_pass".into()),
-                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
-            },
-            RenderGraphEdge {
-                from_node_id: LogicalId::Str("post_pass".into()),
-                to_node_id: LogicalId::Str("compose_pass".into()),
-                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
-            },
-        ],
-        resources: vec![
-            RenderGraphResource {
-                res_id: LogicalId::Str("shadow_atlas".into()),
-                kind: RenderGraphResourceKind::Texture,
-                lifetime: RenderGraphLifetime::Frame,
-                alias_group: None,
-            },
-            RenderGraphResource {
-                res_id: LogicalId::Str("hdr_color".into()),
-                kind: RenderGraphResourceKind::Texture,
-                lifetime: RenderGraphLifetime::Frame,
-                alias_group: None,
-            },
-            RenderGraphResource {
-                res_id: LogicalId::Str("depth".into()),
-                kind: RenderGraphResourceKind::Texture,
-                lifetime: RenderGraphLifetime::Frame,
-                alias_group: None,
-            },
-            RenderGraphResource {
-                res_id: LogicalId::Str("outline_color".into()),
-                kind: RenderGraphResourceKind::Texture,
-                lifetime: RenderGraphLifetime::Frame,
-                alias_group: None,
-            },
-            RenderGraphResource {
-                res_id: LogicalId::Str("ssao_raw".into()),
-                kind: RenderGraphResourceKind::Texture,
-                lifetime: RenderGraphLifetime::Frame,
-                alias_group: None,
-            },
-            RenderGraphResource {
-                res_id: LogicalId::Str("ssao_blur".into()),
-                kind: RenderGraphResourceKind::Texture,
-                lifetime: RenderGraphLifetime::Frame,
-                alias_group: None,
-            },
-            RenderGraphResource {
-                res_id: LogicalId::Str("bloom_color".into()),
-                kind: RenderGraphResourceKind::Texture,
-                lifetime: RenderGraphLifetime::Frame,
-                alias_group: None,
-            },
-            RenderGraphResource {
-                res_id: LogicalId::Str("post_color".into()),
-                kind: RenderGraphResourceKind::Texture,
-                lifetime: RenderGraphLifetime::Frame,
-                alias_group: None,
-            },
-            RenderGraphResource {
-                res_id: LogicalId::Str("swapchain".into()),
-                kind: RenderGraphResourceKind::Attachment,
-                lifetime: RenderGraphLifetime::Frame,
-                alias_group: None,
-            },
-        ],
-        fallback: true,
-    }
-}
@@ -3,12 +3,19 @@ pub mod cmd;
 pub mod gizmos;
 pub mod graph;
 mod passes;
+pub mod screenshot;
 pub mod state;
 
+use crate::core::cmd::EngineEvent;
 use crate::core::render::graph::RenderGraphPlan;
+use crate::core::render::state::CustomPassInsertPoint;
 use crate::core::state::EngineState;
+use crate::core::system::SystemEvent;
 pub use state::RenderState;
 
+/// Minimum number of frames between two `ShadowAtlasFull` events
+const SHADOW_OVERFLOW_WARNING_INTERVAL: u64 = 300;
+
 pub fn bloom_chain_size(base: u32, level: usize) -> u32 {
     passes::bloom_chain_size(base, level)
 }
@@ -31,6 +38,10 @@ pub fn render_frames(engine_state: &mut EngineState) {
     engine_state.profiling.gpu_compose_ns = 0;
     engine_state.profiling.gpu_total_ns = 0;
 
+    for window_state in engine_state.window.states.values_mut() {
+        window_state.render_state.collector.stats = Default::default();
+    }
+
     let device = match &engine_state.device {
         Some(device) => device,
         None => return,
@@ -45,7 +56,8 @@ pub fn render_frames(engine_state: &mut EngineState) {
         gpu_profiler.ensure_capacity(device, queue, engine_state.window.states.len());
     }
 
-    let time = engine_state.time as f32 / 1000.0;
+    let time_ms = engine_state.time;
+    let time = time_ms as f32 / 1000.0;
     let delta_time = engine_state.delta_time as f32 / 1000.0;
     let frame_index = engine_state.frame_index as u32;
     let frame_spec = crate::core::resources::FrameComponent::new(time, delta_time, frame_index);
@@ -56,6 +68,16 @@ pub fn render_frames(engine_state: &mut EngineState) {
     #[cfg(feature = "wasm")]
     let total_start = now_ns();
 
+    // Minimized, occluded and throttled windows skip the frame, shadows included
+    if !engine_state
+        .window
+        .states
+        .values()
+        .any(|window_state| window_state.should_render(time_ms))
+    {
+        return;
+    }
+
     // 1. Update Shadows (Global for all windows - using first window's state as proxy)
     let shadow_enabled = engine_state.window.states.values().any(|window_state| {
         window_state
@@ -66,7 +88,7 @@ pub fn render_frames(engine_state: &mut EngineState) {
     });
 
     if shadow_enabled {
-        if let Some((_, window_state)) = engine_state.window.states.iter_mut().next() {
+        if let Some((window_id, window_state)) = engine_state.window.states.iter_mut().next() {
             #[cfg(not(feature = "wasm"))]
             let shadow_start = std::time::Instant::now();
             #[cfg(feature = "wasm")]
@@ -104,8 +126,28 @@ pub fn render_frames(engine_state: &mut EngineState) {
 
             if let Some(shadow) = &mut window_state.render_state.shadow {
                 shadow.sync_table();
+
+                if shadow.should_warn_overflow(
+                    engine_state.frame_index,
+                    SHADOW_OVERFLOW_WARNING_INTERVAL,
+                ) {
+                    let overflow = shadow.overflow;
+                    log::warn!(
+                        "Shadow atlas full: {} pages reused, {} dropped",
+                        overflow.reused_pages,
+                        overflow.dropped_pages
+                    );
+                    engine_state.event_queue.push(EngineEvent::System(
+                        SystemEvent::ShadowAtlasFull {
+                            window_id: *window_id,
+                            reused_pages: overflow.reused_pages,
+                            dropped_pages: overflow.dropped_pages,
+                        },
+                    ));
+                }
             }
 
+            window_state.render_state.flush_uploads();
             queue.submit(Some(encoder.finish()));
             #[cfg(not(feature = "wasm"))]
             {
@@ -120,15 +162,38 @@ pub fn render_frames(engine_state: &mut EngineState) {
 
     // 2. Render all windows
     let mut windows_ns: u64 = 0;
-    for (window_index, (_window_id, window_state)) in
+    let mut window_costs: Vec<(u32, u64)> = Vec::new();
+    for (window_index, (window_id, window_state)) in
         engine_state.window.states.iter_mut().enumerate()
     {
         #[cfg(not(feature = "wasm"))]
         let window_start = std::time::Instant::now();
         #[cfg(feature = "wasm")]
         let window_start = now_ns();
+        if !window_state.should_render(time_ms) {
+            if window_state.is_hidden() {
+                window_state
+                    .render_state
+                    .screenshot
+                    .fail_request(format!("Window {} stopped rendering", window_id));
+            }
+            continue;
+        }
+        window_state.last_render_time = Some(time_ms);
+        // Rebuild the surface and camera targets before acquiring, so a frame never mixes sizes
+        window_state.apply_pending_resize(device);
+
         let surface_texture = match window_state.surface.get_current_texture() {
             Ok(texture) => texture,
+            Err(wgpu::SurfaceError::Outdated | wgpu::SurfaceError::Lost) => {
+                // The last presented frame stays on screen; render again at the new size
+                window_state.reconfigure_surface(device);
+                continue;
+            }
+            Err(wgpu::SurfaceError::Timeout) => {
+                log::warn!("Timed out acquiring surface texture, skipping frame");
+                continue;
+            }
             Err(e) => {
                 log::error!("Failed to get surface texture: {:?}", e);
                 continue;
@@ -160,11 +225,19 @@ pub fn render_frames(engine_state: &mut EngineState) {
             &surface_texture,
             &window_state.config,
             engine_state.frame_index,
+            frame_spec,
             engine_state.gpu_profiler.as_ref(),
             gpu_base,
         );
 
+        let keep_alpha = render_state.transparent_background;
+        render_state
+            .screenshot
+            .record_copy(device, &mut encoder, &surface_texture, keep_alpha);
+
+        render_state.flush_uploads();
         queue.submit(Some(encoder.finish()));
+        render_state.screenshot.begin_map();
         surface_texture.present();
         #[cfg(not(feature = "wasm"))]
         {
@@ -198,13 +271,11 @@ pub fn render_frames(engine_state: &mut EngineState) {
             };
         }
         #[cfg(not(feature = "wasm"))]
-        {
-            windows_ns = windows_ns.saturating_add(window_start.elapsed().as_nanos() as u64);
-        }
+        let window_ns = window_start.elapsed().as_nanos() as u64;
         #[cfg(feature = "wasm")]
-        {
-            windows_ns = windows_ns.saturating_add(now_ns().saturating_sub(window_start));
-        }
+        let window_ns = now_ns().saturating_sub(window_start);
+        windows_ns = windows_ns.saturating_add(window_ns);
+        window_costs.push((*window_id, window_ns));
     }
 
     if gpu_written {
@@ -233,6 +304,27 @@ pub fn render_frames(engine_state: &mut EngineState) {
         }
     }
     engine_state.profiling.render_windows_ns = windows_ns;
+
+    // 3. Dynamic resolution: GPU time when timestamps are available, else CPU time
+    for (window_id, window_ns) in window_costs {
+        let Some(window_state) = engine_state.window.states.get_mut(&window_id) else {
+            continue;
+        };
+        let frame_ns = if engine_state.profiling.gpu_total_ns > 0 {
+            engine_state.profiling.gpu_total_ns
+        } else {
+            window_ns
+        };
+        if window_state
+            .render_state
+            .dynamic_resolution
+            .record_frame(frame_ns)
+        {
+            let (width, height) = (window_state.config.width, window_state.config.height);
+            window_state.render_state.on_resize(device, width, height);
+            window_state.is_dirty = true;
+        }
+    }
     #[cfg(not(feature = "wasm"))]
     {
         engine_state.profiling.render_total_ns = total_start.elapsed().as_nanos() as u64;
@@ -252,6 +344,7 @@ fn execute_window_graph(
     surface_texture: &wgpu::SurfaceTexture,
     config: &wgpu::SurfaceConfiguration,
     frame_index: u64,
+    frame_spec: crate::core::resources::FrameComponent,
     gpu_profiler: Option<&crate::core::profiling::gpu::GpuProfiler>,
     gpu_base: Option<u32>,
 ) -> bool {
@@ -293,6 +386,20 @@ fn execute_window_graph(
                 if let Some(base) = gpu_base {
                     write_gpu_timestamp(encoder, gpu_profiler, base + 3, &mut gpu_written);
                 }
+                passes::pass_custom(
+                    render_state,
+                    device,
+                    queue,
+                    encoder,
+                    CustomPassInsertPoint::AfterForward,
+                    frame_spec,
+                );
+            }
+            "fog" => {
+                passes::pass_fog(render_state, device, queue, encoder, frame_index);
+            }
+            "particles" => {
+                passes::pass_particles(render_state, device, queue, encoder, frame_index);
             }
             "outline" => {
                 passes::pass_outline(render_state, device, queue, encoder, frame_index);
@@ -307,7 +414,23 @@ fn execute_window_graph(
                 passes::pass_bloom(render_state, device, queue, encoder, frame_index);
             }
             "post" => {
+                passes::pass_custom(
+                    render_state,
+                    device,
+                    queue,
+                    encoder,
+                    CustomPassInsertPoint::BeforePost,
+                    frame_spec,
+                );
                 passes::pass_post(render_state, device, queue, encoder, frame_index);
+                passes::pass_custom(
+                    render_state,
+                    device,
+                    queue,
+                    encoder,
+                    CustomPassInsertPoint::AfterPost,
+                    frame_spec,
+                );
             }
             "compose" => {
                 if let Some(base) = gpu_base {
@@ -330,6 +453,28 @@ fn execute_window_graph(
         }
     }
 
+    passes::pass_sprites(
+        render_state,
+        device,
+        queue,
+        encoder,
+        surface_texture,
+        config,
+        frame_index,
+    );
+
+    if render_state.debug.is_enabled() {
+        passes::pass_debug(
+            render_state,
+            device,
+            queue,
+            encoder,
+            surface_texture,
+            config,
+            frame_index,
+        );
+    }
+
     gpu_written
 }
 
@@ -2,6 +2,7 @@ use bytemuck::{Pod, Zeroable};
 
 use crate::core::render::RenderState;
 use crate::core::render::cache::{PipelineKey, ShaderId};
+use crate::core::resources::BloomSource;
 
 const BLOOM_DOWNSAMPLE_COUNT: usize = 4;
 
@@ -74,11 +75,11 @@ pub fn pass_bloom(
     sorted_cameras.sort_by_key(|(_, record)| record.order);
 
     for (_id, record) in sorted_cameras {
-        let input_target = match record
-            .emissive_target
-            .as_ref()
-            .or(record.render_target.as_ref())
-        {
+        let input_target = match post_config.bloom_source {
+            BloomSource::Emissive => record.emissive_target.as_ref(),
+            BloomSource::Scene => record.render_target.as_ref(),
+        };
+        let input_target = match input_target {
             Some(t) => t,
             None => continue,
         };
@@ -126,6 +127,7 @@ pub fn pass_bloom(
                 depth_write_enabled: false,
                 depth_compare: wgpu::CompareFunction::Always,
                 blend: None,
+                conservative: false,
             };
             let pipeline_h = render_state.cache.get_or_create(key_h, frame_index, || {
                 device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
@@ -228,6 +230,7 @@ pub fn pass_bloom(
                     depth_write_enabled: false,
                     depth_compare: wgpu::CompareFunction::Always,
                     blend: None,
+                    conservative: false,
                 };
 
                 let pipeline_v = render_state.cache.get_or_create(key_v, frame_index, || {
@@ -328,6 +331,7 @@ pub fn pass_bloom(
                 depth_write_enabled: false,
                 depth_compare: wgpu::CompareFunction::Always,
                 blend: None,
+                conservative: false,
             };
 
             let pipeline = render_state.cache.get_or_create(key, frame_index, || {
@@ -426,6 +430,7 @@ pub fn pass_bloom(
                 depth_write_enabled: false,
                 depth_compare: wgpu::CompareFunction::Always,
                 blend: None,
+                conservative: false,
             };
 
             let pipeline = render_state.cache.get_or_create(key, frame_index, || {
@@ -524,6 +529,7 @@ pub fn pass_bloom(
                 depth_write_enabled: false,
                 depth_compare: wgpu::CompareFunction::Always,
                 blend: None,
+                conservative: false,
             };
 
             let pipeline = render_state.cache.get_or_create(key, frame_index, || {
@@ -13,11 +13,201 @@ fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
     return out;
 }
 
+struct ComposeParams {
+    uv_scale: vec4<f32>, // xy: viewport UV to image UV around the center
+    bar_color: vec4<f32>,
+    upscale: vec4<f32>, // xy: EASU output size, z: RCAS sharpness
+}
+
 @group(0) @binding(0) var t_diffuse: texture_2d<f32>;
 @group(0) @binding(1) var s_diffuse: sampler;
-@group(0) @binding(2) var<uniform> _post: vec4<f32>;
+@group(0) @binding(2) var<uniform> compose: ComposeParams;
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(t_diffuse, s_diffuse, in.uv);
+    let uv = (in.uv - vec2<f32>(0.5)) * compose.uv_scale.xy + vec2<f32>(0.5);
+    let color = textureSample(t_diffuse, s_diffuse, clamp(uv, vec2<f32>(0.0), vec2<f32>(1.0)));
+    let outside = any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0));
+    return select(color, compose.bar_color, outside);
+}
+
+// MARK: - FSR
+
+// Luma approximation FSR steers by: r/2 + g + b/2
+fn fsr_luma(color: vec3<f32>) -> f32 {
+    return color.b * 0.5 + (color.r * 0.5 + color.g);
+}
+
+fn fsr_load(base: vec2<i32>, offset: vec2<i32>, size: vec2<i32>) -> vec3<f32> {
+    return textureLoad(t_diffuse, clamp(base + offset, vec2<i32>(0), size - 1), 0).rgb;
+}
+
+// Edge direction and length around one texel of the bilinear quad, weighted by `w`.
+// `left`, `center`, `right` run horizontally and `top`, `center`, `bottom` vertically.
+fn easu_set(
+    dir: ptr<function, vec2<f32>>,
+    len: ptr<function, f32>,
+    w: f32,
+    top: f32,
+    left: f32,
+    center: f32,
+    right: f32,
+    bottom: f32,
+) {
+    let dir_x = right - left;
+    let len_x = max(abs(right - center), abs(center - left));
+    let edge_x = saturate(abs(dir_x) / max(len_x, 1e-5));
+    let dir_y = bottom - top;
+    let len_y = max(abs(bottom - center), abs(center - top));
+    let edge_y = saturate(abs(dir_y) / max(len_y, 1e-5));
+    *dir = *dir + vec2<f32>(dir_x, dir_y) * w;
+    *len = *len + (edge_x * edge_x + edge_y * edge_y) * w;
+}
+
+// One tap of the approximated Lanczos-2 kernel, stretched along the edge direction
+fn easu_tap(
+    color: ptr<function, vec3<f32>>,
+    weight: ptr<function, f32>,
+    offset: vec2<f32>,
+    dir: vec2<f32>,
+    len: vec2<f32>,
+    lobe: f32,
+    clip: f32,
+    texel: vec3<f32>,
+) {
+    let v = vec2<f32>(dot(offset, dir), dot(offset, vec2<f32>(-dir.y, dir.x))) * len;
+    let d2 = min(dot(v, v), clip);
+    var wb = 0.4 * d2 - 1.0;
+    var wa = lobe * d2 - 1.0;
+    wb = wb * wb;
+    wa = wa * wa;
+    wb = 1.5625 * wb - 0.5625;
+    let w = wb * wa;
+    *color = *color + texel * w;
+    *weight = *weight + w;
+}
+
+// Edge-adaptive spatial upsampling of the camera image to `compose.upscale.xy`
+@fragment
+fn fs_easu(in: VertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<i32>(textureDimensions(t_diffuse));
+    let scale = vec2<f32>(size) / compose.upscale.xy;
+    var pp = in.position.xy * scale - vec2<f32>(0.5);
+    let fp = floor(pp);
+    pp = pp - fp;
+    let base = vec2<i32>(fp);
+
+    //    b c
+    //  e f g h
+    //  i j k l
+    //    n o
+    let b = fsr_load(base, vec2<i32>(0, -1), size);
+    let c = fsr_load(base, vec2<i32>(1, -1), size);
+    let e = fsr_load(base, vec2<i32>(-1, 0), size);
+    let f = fsr_load(base, vec2<i32>(0, 0), size);
+    let g = fsr_load(base, vec2<i32>(1, 0), size);
+    let h = fsr_load(base, vec2<i32>(2, 0), size);
+    let i = fsr_load(base, vec2<i32>(-1, 1), size);
+    let j = fsr_load(base, vec2<i32>(0, 1), size);
+    let k = fsr_load(base, vec2<i32>(1, 1), size);
+    let l = fsr_load(base, vec2<i32>(2, 1), size);
+    let n = fsr_load(base, vec2<i32>(0, 2), size);
+    let o = fsr_load(base, vec2<i32>(1, 2), size);
+
+    let bl = fsr_luma(b);
+    let cl = fsr_luma(c);
+    let el = fsr_luma(e);
+    let fl = fsr_luma(f);
+    let gl = fsr_luma(g);
+    let hl = fsr_luma(h);
+    let il = fsr_luma(i);
+    let jl = fsr_luma(j);
+    let kl = fsr_luma(k);
+    let ll = fsr_luma(l);
+    let nl = fsr_luma(n);
+    let ol = fsr_luma(o);
+
+    var dir = vec2<f32>(0.0);
+    var len = 0.0;
+    easu_set(&dir, &len, (1.0 - pp.x) * (1.0 - pp.y), bl, el, fl, gl, jl);
+    easu_set(&dir, &len, pp.x * (1.0 - pp.y), cl, fl, gl, hl, kl);
+    easu_set(&dir, &len, (1.0 - pp.x) * pp.y, fl, il, jl, kl, nl);
+    easu_set(&dir, &len, pp.x * pp.y, gl, jl, kl, ll, ol);
+
+    // Flat areas have no direction; fall back to an axis-aligned kernel
+    let dir_length2 = dot(dir, dir);
+    if (dir_length2 < 1.0 / 32768.0) {
+        dir = vec2<f32>(1.0, 0.0);
+    } else {
+        dir = dir * inverseSqrt(dir_length2);
+    }
+    len = len * 0.5;
+    len = len * len;
+    let stretch = dot(dir, dir) / max(abs(dir.x), abs(dir.y));
+    let len2 = vec2<f32>(1.0 + (stretch - 1.0) * len, 1.0 - 0.5 * len);
+    let lobe = 0.5 + ((1.0 / 4.0 - 0.04) - 0.5) * len;
+    let clip = 1.0 / lobe;
+
+    var color = vec3<f32>(0.0);
+    var weight = 0.0;
+    easu_tap(&color, &weight, vec2<f32>(0.0, -1.0) - pp, dir, len2, lobe, clip, b);
+    easu_tap(&color, &weight, vec2<f32>(1.0, -1.0) - pp, dir, len2, lobe, clip, c);
+    easu_tap(&color, &weight, vec2<f32>(-1.0, 1.0) - pp, dir, len2, lobe, clip, i);
+    easu_tap(&color, &weight, vec2<f32>(0.0, 1.0) - pp, dir, len2, lobe, clip, j);
+    easu_tap(&color, &weight, vec2<f32>(0.0, 0.0) - pp, dir, len2, lobe, clip, f);
+    easu_tap(&color, &weight, vec2<f32>(-1.0, 0.0) - pp, dir, len2, lobe, clip, e);
+    easu_tap(&color, &weight, vec2<f32>(1.0, 1.0) - pp, dir, len2, lobe, clip, k);
+    easu_tap(&color, &weight, vec2<f32>(2.0, 1.0) - pp, dir, len2, lobe, clip, l);
+    easu_tap(&color, &weight, vec2<f32>(2.0, 0.0) - pp, dir, len2, lobe, clip, h);
+    easu_tap(&color, &weight, vec2<f32>(1.0, 0.0) - pp, dir, len2, lobe, clip, g);
+    easu_tap(&color, &weight, vec2<f32>(1.0, 2.0) - pp, dir, len2, lobe, clip, o);
+    easu_tap(&color, &weight, vec2<f32>(0.0, 2.0) - pp, dir, len2, lobe, clip, n);
+
+    // Dering against the four texels of the bilinear quad
+    let quad_min = min(min(f, g), min(j, k));
+    let quad_max = max(max(f, g), max(j, k));
+    let rgb = clamp(color / weight, quad_min, quad_max);
+    let alpha = textureSampleLevel(t_diffuse, s_diffuse, in.uv, 0.0).a;
+    return vec4<f32>(rgb, alpha);
+}
+
+// Robust contrast-adaptive sharpening of the EASU output while it is composed
+@fragment
+fn fs_rcas(in: VertexOutput) -> @location(0) vec4<f32> {
+    let uv = (in.uv - vec2<f32>(0.5)) * compose.uv_scale.xy + vec2<f32>(0.5);
+    let outside = any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0));
+    let size = vec2<i32>(textureDimensions(t_diffuse));
+    let ip = clamp(vec2<i32>(floor(uv * vec2<f32>(size))), vec2<i32>(0), size - 1);
+
+    //    b
+    //  d e f
+    //    h
+    let center = textureLoad(t_diffuse, ip, 0);
+    let b = saturate(fsr_load(ip, vec2<i32>(0, -1), size));
+    let d = saturate(fsr_load(ip, vec2<i32>(-1, 0), size));
+    let e = saturate(center.rgb);
+    let f = saturate(fsr_load(ip, vec2<i32>(1, 0), size));
+    let h = saturate(fsr_load(ip, vec2<i32>(0, 1), size));
+
+    // Strongest negative lobe that keeps every channel of the result within 0..1
+    let ring_min = min(min(b, d), min(f, h));
+    let ring_max = max(max(b, d), max(f, h));
+    let hit_min = min(ring_min, e) / max(4.0 * ring_max, vec3<f32>(1e-5));
+    let hit_max = (vec3<f32>(1.0) - max(ring_max, e)) / min(4.0 * ring_min - 4.0, vec3<f32>(-1e-5));
+    let lobe_rgb = max(-hit_min, hit_max);
+    var lobe = max(-(0.25 - 1.0 / 16.0), min(max(lobe_rgb.r, max(lobe_rgb.g, lobe_rgb.b)), 0.0));
+    lobe = lobe * compose.upscale.z;
+
+    // Back off on isolated noise so grain is not amplified
+    let bl = fsr_luma(b);
+    let dl = fsr_luma(d);
+    let el = fsr_luma(e);
+    let fl = fsr_luma(f);
+    let hl = fsr_luma(h);
+    let luma_range = max(max(max(bl, dl), max(el, fl)), hl) - min(min(min(bl, dl), min(el, fl)), hl);
+    let noise = saturate(abs(0.25 * (bl + dl + fl + hl) - el) / max(luma_range, 1e-5));
+    lobe = lobe * (1.0 - 0.5 * noise);
+
+    let rgb = (lobe * (b + d + f + h) + e) / (4.0 * lobe + 1.0);
+    return select(vec4<f32>(rgb, center.a), compose.bar_color, outside);
 }
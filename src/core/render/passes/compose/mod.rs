@@ -1,7 +1,68 @@
+use bytemuck::{Pod, Zeroable};
+
 use crate::core::render::RenderState;
-use crate::core::render::cache::{PipelineKey, ShaderId};
-use crate::core::render::passes::update_post_uniform_buffer;
-use crate::core::render::state::ResourceLibrary;
+use crate::core::render::cache::{PipelineKey, RenderCache, ShaderId};
+use crate::core::render::state::{ResourceLibrary, UpscalerConfig};
+use crate::core::resources::{
+    CameraOutput, CameraRecord, FitMode, RenderTarget, TextureRecord, ensure_render_target,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct ComposeUniform {
+    uv_scale: [f32; 4],
+    bar_color: [f32; 4],
+    /// xy: output size of the EASU pass, z: RCAS sharpness
+    upscale: [f32; 4],
+}
+
+impl ComposeUniform {
+    /// Fits a camera image into a `width` x `height` area by the window fit mode.
+    /// Only cameras with a pinned aspect can differ from their area.
+    fn new(
+        record: &CameraRecord,
+        fit_mode: FitMode,
+        bar_color: glam::Vec4,
+        upscaler: &UpscalerConfig,
+        (width, height): (u32, u32),
+    ) -> Self {
+        let viewport = width.max(1) as f32 / height.max(1) as f32;
+        let scale = record.fixed_aspect.map_or(glam::Vec2::ONE, |content| {
+            fit_mode.uv_scale(content, viewport)
+        });
+        Self {
+            uv_scale: [scale.x, scale.y, 0.0, 0.0],
+            bar_color: bar_color.to_array(),
+            upscale: [width as f32, height as f32, upscaler.sharpness, 0.0],
+        }
+    }
+}
+
+/// Size of the area a camera is composed to: its window viewport or its output texture
+fn compose_area(
+    record: &CameraRecord,
+    textures: &std::collections::HashMap<u32, TextureRecord>,
+    config: &wgpu::SurfaceConfiguration,
+) -> (u32, u32) {
+    match record.output {
+        CameraOutput::Texture(texture_id) => textures.get(&texture_id).map_or((1, 1), |texture| {
+            (texture._size.width, texture._size.height)
+        }),
+        CameraOutput::Window => record
+            .view_position
+            .as_ref()
+            .map(|vp| vp.resolve_size(config.width, config.height))
+            .unwrap_or((config.width, config.height)),
+    }
+}
+
+/// Image compose reads for a camera: the post target, or the scene target without post
+fn camera_source(record: &CameraRecord) -> Option<&RenderTarget> {
+    record
+        .post_target
+        .as_ref()
+        .or(record.render_target.as_ref())
+}
 
 fn build_compose_bind_group(
     device: &wgpu::Device,
@@ -10,7 +71,8 @@ fn build_compose_bind_group(
     outline_view: &wgpu::TextureView,
     ssao_view: &wgpu::TextureView,
     bloom_view: &wgpu::TextureView,
-    uniform_buffer: &wgpu::Buffer,
+    sampler: &wgpu::Sampler,
+    uniform: wgpu::BufferBinding,
 ) -> wgpu::BindGroup {
     device.create_bind_group(&wgpu::BindGroupDescriptor {
         label: Some("Compose Bind Group"),
@@ -22,11 +84,11 @@ fn build_compose_bind_group(
             },
             wgpu::BindGroupEntry {
                 binding: 1,
-                resource: wgpu::BindingResource::Sampler(&library.samplers.point_clamp),
+                resource: wgpu::BindingResource::Sampler(sampler),
             },
             wgpu::BindGroupEntry {
                 binding: 2,
-                resource: uniform_buffer.as_entire_binding(),
+                resource: wgpu::BindingResource::Buffer(uniform),
             },
             wgpu::BindGroupEntry {
                 binding: 3,
@@ -44,6 +106,130 @@ fn build_compose_bind_group(
     })
 }
 
+/// Compose pipeline for one stage and camera blend; wgpu pipelines are cheap handle clones.
+/// `shader_id` picks the fragment entry: plain compose, the EASU upscale or the RCAS compose.
+fn compose_pipeline(
+    cache: &mut RenderCache,
+    device: &wgpu::Device,
+    library: &ResourceLibrary,
+    shader_id: ShaderId,
+    format: wgpu::TextureFormat,
+    blend: Option<wgpu::BlendState>,
+    frame_index: u64,
+) -> wgpu::RenderPipeline {
+    let entry_point = match shader_id {
+        ShaderId::ComposeEasu => "fs_easu",
+        ShaderId::ComposeRcas => "fs_rcas",
+        _ => "fs_main",
+    };
+    let key = PipelineKey {
+        shader_id: shader_id as u64,
+        color_format: format,
+        color_target_count: 1,
+        depth_format: None,
+        sample_count: 1,
+        topology: wgpu::PrimitiveTopology::TriangleList,
+        cull_mode: None,
+        front_face: wgpu::FrontFace::Ccw,
+        depth_write_enabled: false,
+        depth_compare: wgpu::CompareFunction::Always,
+        blend,
+        conservative: false,
+    };
+
+    cache
+        .get_or_create(key, frame_index, || {
+            let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Compose Pipeline Layout"),
+                bind_group_layouts: &[&library.layout_target],
+                ..Default::default()
+            });
+
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Compose Pipeline"),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &library.compose_shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &library.compose_shader,
+                    entry_point: Some(entry_point),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: key.blend,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: None,
+            })
+        })
+        .clone()
+}
+
+/// Bind group compose draws a camera with. Upscaled cameras read their EASU output,
+/// which already matches the area.
+fn camera_bind_group(
+    device: &wgpu::Device,
+    library: &ResourceLibrary,
+    record: &CameraRecord,
+    window_scale: f32,
+    uniform: wgpu::BufferBinding,
+) -> Option<wgpu::BindGroup> {
+    let target = match record.upscale_target.as_ref() {
+        Some(target) => target,
+        None => camera_source(record)?,
+    };
+    let outline_view = record
+        .outline_target
+        .as_ref()
+        .map(|target| &target.view)
+        .unwrap_or(&library.fallback_view);
+    let ssao_view = record
+        .ssao_blur_target
+        .as_ref()
+        .map(|target| &target.view)
+        .unwrap_or(&library.fallback_view);
+    let bloom_view = record
+        .bloom_target
+        .as_ref()
+        .map(|target| &target.view)
+        .unwrap_or(&library.fallback_view);
+    // Texel-exact copy at full resolution; scaled cameras are filtered up or down
+    let sampler = if record.render_scale * window_scale == 1.0 || record.upscale_target.is_some() {
+        &library.samplers.point_clamp
+    } else {
+        &library.samplers.linear_clamp
+    };
+
+    Some(build_compose_bind_group(
+        device,
+        library,
+        &target.view,
+        outline_view,
+        ssao_view,
+        bloom_view,
+        sampler,
+        uniform,
+    ))
+}
+
+/// Upscaled cameras are sharpened by RCAS while they are composed
+fn compose_shader(record: &CameraRecord) -> ShaderId {
+    if record.upscale_target.is_some() {
+        ShaderId::ComposeRcas
+    } else {
+        ShaderId::Compose
+    }
+}
+
 pub fn pass_compose(
     render_state: &mut RenderState,
     device: &wgpu::Device,
@@ -57,80 +243,210 @@ pub fn pass_compose(
         .texture
         .create_view(&wgpu::TextureViewDescriptor::default());
 
-    // 2. Get or Create Compose Pipeline
+    // 1. Keep an EASU target for every camera the FSR upscaler applies to
+    let textures = render_state.textures.clone();
+    let textures = textures.borrow();
+    let window_scale = render_state.dynamic_resolution.scale;
+    let upscaler = render_state.upscaler;
+    let scene = &mut render_state.scene;
+    for record in scene.cameras.values_mut() {
+        let format = camera_source(record).map(|target| target.format);
+        match format {
+            Some(format) if upscaler.uses_fsr(record.render_scale * window_scale) => {
+                let (width, height) = compose_area(record, &textures.records, config);
+                ensure_render_target(
+                    device,
+                    &mut record.upscale_target,
+                    width.max(1),
+                    height.max(1),
+                    format,
+                );
+            }
+            _ => record.upscale_target = None,
+        }
+    }
+
+    // 1a. Sort cameras by order; later cameras layer over earlier ones, ids break ties
+    let mut sorted_cameras: Vec<_> = render_state.scene.cameras.iter().collect();
+    sorted_cameras.sort_by_key(|(id, record)| (record.order, **id));
+
+    // 1b. Fit of every camera into the area it is composed to, one uniform slot each
+    let stride = (std::mem::size_of::<ComposeUniform>() as u64)
+        .next_multiple_of(device.limits().min_uniform_buffer_offset_alignment as u64);
+    let mut uniform_bytes = vec![0u8; stride as usize * sorted_cameras.len()];
+    for (index, (_id, record)) in sorted_cameras.iter().enumerate() {
+        let uniform = ComposeUniform::new(
+            record,
+            render_state.fit_mode,
+            render_state.fit_bar_color,
+            &upscaler,
+            compose_area(record, &textures.records, config),
+        );
+        let offset = index * stride as usize;
+        uniform_bytes[offset..offset + std::mem::size_of::<ComposeUniform>()]
+            .copy_from_slice(bytemuck::bytes_of(&uniform));
+    }
+    if uniform_bytes.is_empty() {
+        uniform_bytes.resize(stride as usize, 0);
+    }
+    if render_state
+        .compose_uniform_buffer
+        .as_ref()
+        .is_none_or(|buffer| buffer.size() < uniform_bytes.len() as u64)
+    {
+        render_state.compose_uniform_buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Compose Uniform Buffer"),
+            size: (uniform_bytes.len() as u64).next_power_of_two(),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+    }
+
+    // 2. Shared resources; pipelines are picked per camera blend
     let library = match render_state.library.as_ref() {
         Some(l) => l,
         None => return,
     };
-
-    let post_config = render_state.environment.post.clone();
-    let uniform_buffer = match render_state.post_uniform_buffer.as_ref() {
+    let uniform_buffer = match render_state.compose_uniform_buffer.as_ref() {
         Some(buffer) => buffer,
         None => return,
     };
-    update_post_uniform_buffer(&post_config, uniform_buffer, queue, frame_index);
-
-    // 1. Sort cameras by order
-    let mut sorted_cameras: Vec<_> = render_state.scene.cameras.iter().collect();
-    sorted_cameras.sort_by_key(|(_, record)| record.order);
+    queue.write_buffer(uniform_buffer, 0, &uniform_bytes);
+    let uniform_slot = |index: usize| wgpu::BufferBinding {
+        buffer: uniform_buffer,
+        offset: index as u64 * stride,
+        size: std::num::NonZeroU64::new(std::mem::size_of::<ComposeUniform>() as u64),
+    };
 
     let cache = &mut render_state.cache;
-    let key = PipelineKey {
-        shader_id: ShaderId::Compose as u64,
-        color_format: config.format,
-        color_target_count: 1,
-        depth_format: None,
-        sample_count: 1,
-        topology: wgpu::PrimitiveTopology::TriangleList,
-        cull_mode: None,
-        front_face: wgpu::FrontFace::Ccw,
-        depth_write_enabled: false,
-        depth_compare: wgpu::CompareFunction::Always,
-        blend: None,
-    };
 
-    let pipeline = cache.get_or_create(key, frame_index, || {
-        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Compose Pipeline Layout"),
-            bind_group_layouts: &[&library.layout_target],
-            ..Default::default()
+    // 2a. EASU upscale of FSR cameras into their area-size target
+    for (index, (_id, record)) in sorted_cameras.iter().enumerate() {
+        let (Some(upscale_target), Some(source)) =
+            (record.upscale_target.as_ref(), camera_source(record))
+        else {
+            continue;
+        };
+        let bind_group = build_compose_bind_group(
+            device,
+            library,
+            &source.view,
+            &library.fallback_view,
+            &library.fallback_view,
+            &library.fallback_view,
+            &library.samplers.linear_clamp,
+            uniform_slot(index),
+        );
+        let mut easu_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Compose EASU Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &upscale_target.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
         });
+        let pipeline = compose_pipeline(
+            cache,
+            device,
+            library,
+            ShaderId::ComposeEasu,
+            upscale_target.format,
+            None,
+            frame_index,
+        );
+        easu_pass.set_pipeline(&pipeline);
+        easu_pass.set_bind_group(0, &bind_group, &[]);
+        easu_pass.draw(0..3, 0..1);
+    }
 
-        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Compose Pipeline"),
-            layout: Some(&layout),
-            vertex: wgpu::VertexState {
-                module: &library.compose_shader,
-                entry_point: Some("vs_main"),
-                buffers: &[],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &library.compose_shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: key.blend,
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            primitive: wgpu::PrimitiveState::default(),
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
+    // 2b. Cameras that output to a texture; the first one per texture clears it
+    let mut cleared_textures = std::collections::HashSet::new();
+    for (index, (_id, record)) in sorted_cameras.iter().enumerate() {
+        let CameraOutput::Texture(texture_id) = record.output else {
+            continue;
+        };
+        let texture = match textures.records.get(&texture_id) {
+            Some(texture)
+                if texture
+                    ._texture
+                    .usage()
+                    .contains(wgpu::TextureUsages::RENDER_ATTACHMENT) =>
+            {
+                texture
+            }
+            _ => continue,
+        };
+        let Some(bind_group) =
+            camera_bind_group(device, library, record, window_scale, uniform_slot(index))
+        else {
+            continue;
+        };
+
+        let load = if cleared_textures.insert(texture_id) {
+            wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT)
+        } else {
+            wgpu::LoadOp::Load
+        };
+        let mut texture_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Compose Texture Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &texture.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
             multiview_mask: None,
-            cache: None,
-        })
-    });
+        });
+        let pipeline = compose_pipeline(
+            cache,
+            device,
+            library,
+            compose_shader(record),
+            texture._format,
+            record.compose_blend.blend_state(),
+            frame_index,
+        );
+        texture_pass.set_pipeline(&pipeline);
+        texture_pass.set_bind_group(0, &bind_group, &[]);
+        texture_pass.draw(0..3, 0..1);
+    }
 
     // 3. Begin compose pass
+    // Shows wherever no camera viewport covers the window
+    let clear = match (
+        render_state.clear_color,
+        render_state.transparent_background,
+    ) {
+        (Some(color), transparent) => wgpu::Color {
+            r: color.x as f64,
+            g: color.y as f64,
+            b: color.z as f64,
+            a: if transparent { color.w as f64 } else { 1.0 },
+        },
+        (None, true) => wgpu::Color::TRANSPARENT,
+        (None, false) => wgpu::Color::BLACK,
+    };
     let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
         label: Some("Compose Pass"),
         color_attachments: &[Some(wgpu::RenderPassColorAttachment {
             view: &view,
             resolve_target: None,
             ops: wgpu::Operations {
-                load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                load: wgpu::LoadOp::Clear(clear),
                 store: wgpu::StoreOp::Store,
             },
             depth_slice: None,
@@ -141,32 +457,15 @@ pub fn pass_compose(
         multiview_mask: None,
     });
 
-    render_pass.set_pipeline(pipeline);
-
-    for (_id, record) in sorted_cameras {
-        let target = match record
-            .post_target
-            .as_ref()
-            .or(record.render_target.as_ref())
-        {
-            Some(t) => t,
-            None => continue,
+    for (index, (_id, record)) in sorted_cameras.into_iter().enumerate() {
+        if record.output != CameraOutput::Window {
+            continue;
+        }
+        let Some(bind_group) =
+            camera_bind_group(device, library, record, window_scale, uniform_slot(index))
+        else {
+            continue;
         };
-        let outline_view = record
-            .outline_target
-            .as_ref()
-            .map(|target| &target.view)
-            .unwrap_or(&library.fallback_view);
-        let ssao_view = record
-            .ssao_blur_target
-            .as_ref()
-            .map(|target| &target.view)
-            .unwrap_or(&library.fallback_view);
-        let bloom_view = record
-            .bloom_target
-            .as_ref()
-            .map(|target| &target.view)
-            .unwrap_or(&library.fallback_view);
 
         // 4. Resolve viewport
         let (x, y) = record
@@ -183,17 +482,16 @@ pub fn pass_compose(
 
         render_pass.set_viewport(x as f32, y as f32, width as f32, height as f32, 0.0, 1.0);
 
-        // 5. Create Bind Group for this camera's target
-        let bind_group = build_compose_bind_group(
+        let pipeline = compose_pipeline(
+            cache,
             device,
             library,
-            &target.view,
-            outline_view,
-            ssao_view,
-            bloom_view,
-            uniform_buffer,
+            compose_shader(record),
+            config.format,
+            record.compose_blend.blend_state(),
+            frame_index,
         );
-
+        render_pass.set_pipeline(&pipeline);
         render_pass.set_bind_group(0, &bind_group, &[]);
         render_pass.draw(0..3, 0..1);
     }
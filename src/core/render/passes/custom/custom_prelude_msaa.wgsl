@@ -0,0 +1,48 @@
+// Prepended to every custom pass shader. The host source supplies
+// `@fragment fn fs_main(in: VertexOutput) -> @location(0) vec4<f32>`.
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+struct CustomPassParams {
+    inv_view_proj: mat4x4<f32>,
+    resolution: vec4<f32>, // xy: target size in pixels, zw: 1 / size
+    time: vec4<f32>,       // x: seconds, y: delta seconds, z: frame index
+    params: vec4<f32>,     // values set by the host
+}
+
+@group(0) @binding(0) var t_color: texture_2d<f32>;
+@group(0) @binding(1) var s_color: sampler;
+@group(0) @binding(2) var t_depth: texture_depth_multisampled_2d;
+@group(0) @binding(3) var<uniform> pass_params: CustomPassParams;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32((i32(vertex_index) << 1) & 2) * 2.0 - 1.0;
+    let y = f32(i32(vertex_index) & 2) * -2.0 + 1.0;
+    out.position = vec4<f32>(x, y, 0.0, 1.0);
+    out.uv = vec2<f32>(x * 0.5 + 0.5, 1.0 - (y * 0.5 + 0.5));
+    return out;
+}
+
+// Scene color at `uv` as it was before this pass
+fn sample_color(uv: vec2<f32>) -> vec4<f32> {
+    return textureSampleLevel(t_color, s_color, uv, 0.0);
+}
+
+// Reverse-Z device depth at `uv` (first sample); 0 is the far plane
+fn sample_depth(uv: vec2<f32>) -> f32 {
+    let dims = vec2<f32>(textureDimensions(t_depth));
+    let coord = clamp(uv * dims, vec2<f32>(0.0), dims - vec2<f32>(1.0));
+    return textureLoad(t_depth, vec2<i32>(coord), 0);
+}
+
+// World position reconstructed from `uv` and its depth
+fn world_position(uv: vec2<f32>) -> vec3<f32> {
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, sample_depth(uv), 1.0);
+    let world = pass_params.inv_view_proj * ndc;
+    return world.xyz / world.w;
+}
@@ -0,0 +1,185 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::core::render::RenderState;
+use crate::core::render::state::{CUSTOM_PASS_FORMAT, CustomPassInsertPoint};
+use crate::core::resources::{FrameComponent, ensure_render_target};
+
+/// Bytes between per-draw uniform slots, the largest offset alignment wgpu allows
+const CUSTOM_PASS_UNIFORM_SLOT_SIZE: u64 = 256;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct CustomPassUniform {
+    inv_view_proj: [[f32; 4]; 4],
+    resolution: [f32; 4],
+    time: [f32; 4],
+    params: [f32; 4],
+}
+
+/// Runs the host passes registered at `insert_point` over each camera's color. Every pass
+/// copies the target to a scratch texture first, so it can sample the color it overwrites.
+pub fn pass_custom(
+    render_state: &mut RenderState,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    encoder: &mut wgpu::CommandEncoder,
+    insert_point: CustomPassInsertPoint,
+    frame: FrameComponent,
+) {
+    let ids = render_state.custom_passes.ordered(insert_point);
+    if ids.is_empty() || render_state.scene.cameras.is_empty() {
+        return;
+    }
+
+    let library = match render_state.library.as_ref() {
+        Some(lib) => lib,
+        None => return,
+    };
+    let depth_target = match render_state.forward_depth_target.as_ref() {
+        Some(target) => target,
+        None => return,
+    };
+    let msaa = depth_target.sample_count > 1;
+    let system = &mut render_state.custom_passes;
+
+    let needed =
+        (ids.len() * render_state.scene.cameras.len()) as u64 * CUSTOM_PASS_UNIFORM_SLOT_SIZE;
+    if system
+        .uniform_buffer
+        .as_ref()
+        .is_none_or(|buffer| buffer.size() < needed)
+    {
+        system.uniform_buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Custom Pass Uniform Buffer"),
+            size: needed.next_power_of_two(),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+    }
+
+    let (bind_group_layout, pipeline_layout) = {
+        let (bind_group_layout, pipeline_layout) = system.layouts(device, msaa);
+        (bind_group_layout.clone(), pipeline_layout.clone())
+    };
+    for id in &ids {
+        if let Some(record) = system.passes.get_mut(id) {
+            record.ensure_pipeline(device, &pipeline_layout, msaa);
+        }
+    }
+
+    let mut sorted_cameras: Vec<_> = render_state.scene.cameras.iter().collect();
+    sorted_cameras.sort_by_key(|(_, record)| record.order);
+
+    let mut slot = 0u64;
+    for (camera_id, camera) in sorted_cameras {
+        let target = match insert_point {
+            CustomPassInsertPoint::AfterPost => camera.post_target.as_ref(),
+            _ => camera.render_target.as_ref(),
+        };
+        let target = match target {
+            Some(t) if t.format == CUSTOM_PASS_FORMAT => t,
+            _ => continue,
+        };
+
+        let size = target._texture.size();
+        ensure_render_target(
+            device,
+            &mut system.scratch,
+            size.width,
+            size.height,
+            CUSTOM_PASS_FORMAT,
+        );
+        let (scratch, uniform_buffer) =
+            match (system.scratch.as_ref(), system.uniform_buffer.as_ref()) {
+                (Some(scratch), Some(buffer)) => (scratch, buffer),
+                _ => return,
+            };
+
+        for id in &ids {
+            let record = match system.passes.get(id) {
+                Some(record) => record,
+                None => continue,
+            };
+            let pipeline = match record.pipeline(msaa) {
+                Some(pipeline) => pipeline,
+                None => continue,
+            };
+
+            let uniform = CustomPassUniform {
+                inv_view_proj: camera.data.view_projection.inverse().to_cols_array_2d(),
+                resolution: [
+                    size.width as f32,
+                    size.height as f32,
+                    1.0 / size.width.max(1) as f32,
+                    1.0 / size.height.max(1) as f32,
+                ],
+                time: [frame.time, frame.delta_time, frame.frame_index as f32, 0.0],
+                params: record.params.to_array(),
+            };
+            let offset = slot * CUSTOM_PASS_UNIFORM_SLOT_SIZE;
+            slot += 1;
+            queue.write_buffer(uniform_buffer, offset, bytemuck::bytes_of(&uniform));
+
+            encoder.copy_texture_to_texture(
+                target._texture.as_image_copy(),
+                scratch._texture.as_image_copy(),
+                size,
+            );
+
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Custom Pass Bind Group"),
+                layout: &bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&scratch.view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&library.samplers.linear_clamp),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(&depth_target.view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                            buffer: uniform_buffer,
+                            offset,
+                            size: wgpu::BufferSize::new(
+                                std::mem::size_of::<CustomPassUniform>() as u64
+                            ),
+                        }),
+                    },
+                ],
+            });
+
+            let label = format!(
+                "Custom Pass {} ({}) - Camera {}",
+                record.label.as_deref().unwrap_or(&id.to_string()),
+                insert_point.label(),
+                camera_id
+            );
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(&label),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &target.view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32((i32(vertex_index) << 1) & 2) * 2.0 - 1.0;
+    let y = f32(i32(vertex_index) & 2) * -2.0 + 1.0;
+    out.position = vec4<f32>(x, y, 0.0, 1.0);
+    out.uv = vec2<f32>(x * 0.5 + 0.5, 1.0 - (y * 0.5 + 0.5));
+    return out;
+}
+
+// MARK: - Atlas layer
+
+@group(0) @binding(0) var t_atlas: texture_depth_2d;
+
+@fragment
+fn fs_atlas(in: VertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(t_atlas));
+    let coord = vec2<i32>(clamp(in.uv * size, vec2<f32>(0.0), size - vec2<f32>(1.0)));
+    let depth = textureLoad(t_atlas, coord, 0);
+    // Reverse Z: near geometry is bright, cleared texels are black; sqrt lifts the far range
+    let value = sqrt(clamp(depth, 0.0, 1.0));
+    return vec4<f32>(vec3<f32>(value), 1.0);
+}
+
+// MARK: - Page allocations
+
+struct PageOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) local: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@vertex
+fn vs_page(
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) rect: vec4<f32>,
+    @location(1) color: vec4<f32>,
+) -> PageOutput {
+    var out: PageOutput;
+    // Two-triangle quad: (0,0) (1,0) (0,1) (0,1) (1,0) (1,1)
+    let corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let local = corners[vertex_index % 6u];
+    let uv = mix(rect.xy, rect.zw, local);
+    out.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.local = local;
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs_page(in: PageOutput) -> @location(0) vec4<f32> {
+    // Solid border one pixel wide, translucent fill
+    let edge = min(in.local, vec2<f32>(1.0) - in.local) / max(fwidth(in.local), vec2<f32>(1e-5));
+    let border = min(edge.x, edge.y) < 1.0;
+    let alpha = select(0.3, 1.0, border);
+    return vec4<f32>(in.color.rgb, in.color.a * alpha);
+}
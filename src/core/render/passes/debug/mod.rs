@@ -0,0 +1,246 @@
+use glam::Vec4;
+
+use crate::core::render::RenderState;
+use crate::core::render::cache::{PipelineKey, ShaderId};
+use crate::core::render::state::{DebugRenderMode, debug::DebugPageInstance};
+
+/// Fraction of the shorter surface side used by the overlay
+const OVERLAY_SCALE: f32 = 0.4;
+const OVERLAY_MARGIN_PX: f32 = 16.0;
+
+pub fn pass_debug(
+    render_state: &mut RenderState,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    encoder: &mut wgpu::CommandEncoder,
+    surface_texture: &wgpu::SurfaceTexture,
+    config: &wgpu::SurfaceConfiguration,
+    frame_index: u64,
+) {
+    match render_state.debug.mode {
+        DebugRenderMode::None => {}
+        DebugRenderMode::ShadowAtlas => pass_debug_shadow_atlas(
+            render_state,
+            device,
+            queue,
+            encoder,
+            surface_texture,
+            config,
+            frame_index,
+        ),
+    }
+}
+
+fn pass_debug_shadow_atlas(
+    render_state: &mut RenderState,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    encoder: &mut wgpu::CommandEncoder,
+    surface_texture: &wgpu::SurfaceTexture,
+    config: &wgpu::SurfaceConfiguration,
+    frame_index: u64,
+) {
+    let (library, shadow, debug, cache) = (
+        match render_state.library.as_ref() {
+            Some(l) => l,
+            None => return,
+        },
+        match render_state.shadow.as_ref() {
+            Some(s) => s,
+            None => return,
+        },
+        &mut render_state.debug,
+        &mut render_state.cache,
+    );
+
+    let layer = debug.layer;
+    let atlas_layer_view = match shadow.atlas.layer_view(layer) {
+        Some(view) => view,
+        None => return,
+    };
+
+    // 1. Collect page allocations living on the selected layer
+    debug.clear_pages();
+    for record in shadow.cache.values() {
+        let Some((scale_x, scale_y, bias_x, bias_y, page_layer)) =
+            shadow.atlas.get_uv_transform(record.atlas_handle)
+        else {
+            continue;
+        };
+        if page_layer != layer {
+            continue;
+        }
+        debug.add_page(
+            Vec4::new(bias_x, bias_y, bias_x + scale_x, bias_y + scale_y),
+            frame_index.saturating_sub(record.last_frame_used),
+            record.is_dirty,
+        );
+    }
+    debug.prepare(device, queue);
+
+    // 2. Place the overlay in the bottom-right corner, keeping the atlas aspect
+    let info = shadow.atlas.info();
+    let aspect = info.tiles_w as f32 / info.tiles_h.max(1) as f32;
+    let side = config.width.min(config.height) as f32 * OVERLAY_SCALE;
+    let (width, height) = if aspect >= 1.0 {
+        (side, side / aspect)
+    } else {
+        (side * aspect, side)
+    };
+    let x = (config.width as f32 - width - OVERLAY_MARGIN_PX).max(0.0);
+    let y = (config.height as f32 - height - OVERLAY_MARGIN_PX).max(0.0);
+
+    let view = surface_texture
+        .texture
+        .create_view(&wgpu::TextureViewDescriptor::default());
+
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Debug Shadow Atlas Bind Group"),
+        layout: &library.layout_debug,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::TextureView(atlas_layer_view),
+        }],
+    });
+
+    // 3. Atlas layer depth
+    {
+        let key = PipelineKey {
+            shader_id: ShaderId::DebugShadowAtlas as u64,
+            color_format: config.format,
+            color_target_count: 1,
+            depth_format: None,
+            sample_count: 1,
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            front_face: wgpu::FrontFace::Ccw,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            blend: None,
+            conservative: false,
+        };
+
+        let pipeline = cache.get_or_create(key, frame_index, || {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Debug Shadow Atlas Pipeline"),
+                layout: Some(&library.debug_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &library.debug_shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &library.debug_shader,
+                    entry_point: Some("fs_atlas"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: config.format,
+                        blend: key.blend,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: None,
+            })
+        });
+
+        let mut render_pass = begin_overlay_pass(encoder, &view, "Debug Shadow Atlas Pass");
+        render_pass.set_viewport(x, y, width, height, 0.0, 1.0);
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    // 4. Page allocation grid
+    let page_count = debug.page_count();
+    let page_buffer = match debug.buffer() {
+        Some(buffer) if page_count > 0 => buffer,
+        _ => return,
+    };
+
+    let key = PipelineKey {
+        shader_id: ShaderId::DebugShadowPages as u64,
+        color_format: config.format,
+        color_target_count: 1,
+        depth_format: None,
+        sample_count: 1,
+        topology: wgpu::PrimitiveTopology::TriangleList,
+        cull_mode: None,
+        front_face: wgpu::FrontFace::Ccw,
+        depth_write_enabled: false,
+        depth_compare: wgpu::CompareFunction::Always,
+        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+        conservative: false,
+    };
+
+    let pipeline = cache.get_or_create(key, frame_index, || {
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Debug Shadow Pages Pipeline Layout"),
+            bind_group_layouts: &[],
+            ..Default::default()
+        });
+
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Debug Shadow Pages Pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &library.debug_shader,
+                entry_point: Some("vs_page"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<DebugPageInstance>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x4, 1 => Float32x4],
+                }],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &library.debug_shader,
+                entry_point: Some("fs_page"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: key.blend,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: None,
+        })
+    });
+
+    let mut render_pass = begin_overlay_pass(encoder, &view, "Debug Shadow Pages Pass");
+    render_pass.set_viewport(x, y, width, height, 0.0, 1.0);
+    render_pass.set_pipeline(pipeline);
+    render_pass.set_vertex_buffer(0, page_buffer.slice(..));
+    render_pass.draw(0..6, 0..page_count);
+}
+
+fn begin_overlay_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+    label: &'a str,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Store,
+            },
+            depth_slice: None,
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        multiview_mask: None,
+    })
+}
@@ -0,0 +1,296 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+struct LightDrawParams {
+    camera_index: u32,
+    max_lights_per_camera: u32,
+};
+
+struct Light {
+    position: vec4<f32>,
+    direction: vec4<f32>,
+    color: vec4<f32>,
+    ground_color: vec4<f32>,
+    view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    view_projection: mat4x4<f32>,
+    intensity_range: vec2<f32>,
+    spot_inner_outer: vec2<f32>,
+    kind_flags: vec2<u32>,
+    shadow_index: u32,
+    units: u32,
+    area_right: vec4<f32>,
+    area_size: vec2<f32>,
+    volumetric: f32,
+    _padding: f32,
+}
+
+struct ShadowPageEntry {
+    scale_offset: vec4<f32>,
+    layer_index: u32,
+    _padding0: u32,
+    _padding1: u32,
+    _padding2: u32,
+}
+
+struct ShadowParams {
+    virtual_grid_size: f32,
+    pcf_range: i32,
+    table_capacity: u32,
+    bias_min: f32,
+    bias_slope: f32,
+    point_bias_min: f32,
+    point_bias_slope: f32,
+    normal_bias: f32,
+    pcss_enabled: u32,
+    pcss_light_size: f32,
+    pcss_search_radius: f32,
+}
+
+struct FogParams {
+    inv_view_proj: mat4x4<f32>,
+    color_density: vec4<f32>, // rgb: albedo, a: density
+    height: vec4<f32>,        // falloff, base height, scattering, anisotropy
+    march: vec4<f32>,         // max distance, steps, frame, unused
+}
+
+@group(0) @binding(2) var<uniform> light_params: LightDrawParams;
+@group(0) @binding(3) var<storage, read> lights: array<Light>;
+@group(0) @binding(4) var<storage, read> visible_indices: array<u32>;
+@group(0) @binding(5) var<storage, read> visible_counts: array<u32>;
+@group(0) @binding(6) var<uniform> shadow_params: ShadowParams;
+@group(0) @binding(7) var shadow_atlas: texture_depth_2d_array;
+@group(0) @binding(8) var<storage, read> shadow_page_table: array<ShadowPageEntry>;
+@group(0) @binding(9) var<storage, read> point_light_vp: array<mat4x4<f32>>;
+@group(0) @binding(14) var shadow_sampler: sampler_comparison;
+
+@group(1) @binding(0) var t_depth: texture_depth_2d;
+@group(1) @binding(1) var<uniform> fog: FogParams;
+
+const INVALID_SHADOW_INDEX: u32 = 0xFFFFFFFFu;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32((i32(vertex_index) << 1) & 2) * 2.0 - 1.0;
+    let y = f32(i32(vertex_index) & 2) * -2.0 + 1.0;
+    out.position = vec4<f32>(x, y, 0.0, 1.0);
+    out.uv = vec2<f32>(x * 0.5 + 0.5, 1.0 - (y * 0.5 + 0.5));
+    return out;
+}
+
+fn depth_at(uv: vec2<f32>) -> f32 {
+    let dims = vec2<f32>(textureDimensions(t_depth));
+    let coord = clamp(uv * dims, vec2<f32>(0.0), dims - vec2<f32>(1.0));
+    return textureLoad(t_depth, vec2<i32>(coord), 0);
+}
+
+fn world_from_ndc(ndc: vec3<f32>) -> vec3<f32> {
+    let world = fog.inv_view_proj * vec4<f32>(ndc, 1.0);
+    return world.xyz / world.w;
+}
+
+// Interleaved gradient noise, shifted every frame so the step jitter averages out
+fn step_jitter(pixel: vec2<f32>, frame: f32) -> f32 {
+    let p = pixel + vec2<f32>(5.588238 * (frame % 64.0));
+    return fract(52.9829189 * fract(dot(p, vec2<f32>(0.06711056, 0.00583715))));
+}
+
+fn fog_density(y: f32) -> f32 {
+    let height = max(y - fog.height.y, 0.0);
+    return fog.color_density.a * exp(-fog.height.x * height);
+}
+
+// Henyey-Greenstein, scaled by 4π so isotropic scattering is 1
+fn phase(cos_theta: f32) -> f32 {
+    let g = fog.height.w;
+    let g2 = g * g;
+    let denom = max(1.0 + g2 - 2.0 * g * cos_theta, 1e-4);
+    return (1.0 - g2) / (denom * sqrt(denom));
+}
+
+// -----------------------------------------------------------------------------
+// Shadows (single compare tap per sample; the march jitter does the filtering)
+// -----------------------------------------------------------------------------
+
+fn compute_table_id(light_base: u32, grid_x: u32, grid_y: u32, grid_size_u: u32) -> u32 {
+    let grid_area = grid_size_u * grid_size_u;
+    let linear_id = light_base * grid_area + grid_y * grid_size_u + grid_x;
+    return linear_id % shadow_params.table_capacity;
+}
+
+fn sample_shadow_page(light_base: u32, light_ndc: vec3<f32>, bias: f32) -> f32 {
+    let light_uv = vec2<f32>(light_ndc.x * 0.5 + 0.5, light_ndc.y * -0.5 + 0.5);
+    let light_depth = light_ndc.z;
+    let ndc_in_bounds = light_uv.x >= 0.0
+        && light_uv.x <= 1.0
+        && light_uv.y >= 0.0
+        && light_uv.y <= 1.0
+        && light_depth >= 0.0
+        && light_depth <= 1.0;
+
+    let grid_size_f = shadow_params.virtual_grid_size;
+    let grid_size_u = u32(grid_size_f);
+    let grid_x = u32(clamp(light_uv.x * grid_size_f, 0.0, grid_size_f - 1.0));
+    let grid_y = u32(clamp(light_uv.y * grid_size_f, 0.0, grid_size_f - 1.0));
+
+    let table_id = compute_table_id(light_base, grid_x, grid_y, grid_size_u);
+    let page = shadow_page_table[table_id];
+    let page_has_data = !(page.scale_offset.x == 0.0 && page.scale_offset.y == 0.0);
+
+    let page_origin = vec2<f32>(f32(grid_x), f32(grid_y)) / grid_size_f;
+    let page_uv = (light_uv - page_origin) * grid_size_f;
+    let atlas_uv = (page_uv * page.scale_offset.xy) + page.scale_offset.zw;
+
+    let dim = textureDimensions(shadow_atlas);
+    let guard = 1.5 / vec2<f32>(f32(dim.x), f32(dim.y));
+    let uv = clamp(
+        atlas_uv,
+        page.scale_offset.zw + guard,
+        page.scale_offset.zw + page.scale_offset.xy - guard
+    );
+
+    let shadow = textureSampleCompareLevel(
+        shadow_atlas,
+        shadow_sampler,
+        uv,
+        i32(page.layer_index),
+        saturate(light_depth + bias) // Reverse Z: add bias
+    );
+    return select(1.0, shadow, ndc_in_bounds && page_has_data);
+}
+
+fn point_face(dir: vec3<f32>) -> u32 {
+    let ad = abs(dir);
+    if (ad.x >= ad.y && ad.x >= ad.z) {
+        return select(1u, 0u, dir.x >= 0.0);
+    }
+    if (ad.y >= ad.z) {
+        return select(3u, 2u, dir.y >= 0.0);
+    }
+    return select(5u, 4u, dir.z >= 0.0);
+}
+
+fn light_shadow(light: Light, world_pos: vec3<f32>) -> f32 {
+    let casts = (light.kind_flags.y & 1u) != 0u && light.shadow_index != INVALID_SHADOW_INDEX;
+    if (!casts) {
+        return 1.0;
+    }
+    if (light.kind_flags.x == 1u) {
+        let face = point_face(world_pos - light.position.xyz);
+        let light_base = light.shadow_index * 6u + face;
+        let clip = point_light_vp[light_base] * vec4<f32>(world_pos, 1.0);
+        return sample_shadow_page(light_base, clip.xyz / clip.w, shadow_params.point_bias_min);
+    }
+    let clip = light.view_projection * vec4<f32>(world_pos, 1.0);
+    return sample_shadow_page(light.shadow_index * 6u, clip.xyz / clip.w, shadow_params.bias_min);
+}
+
+// -----------------------------------------------------------------------------
+// In-scattering
+// -----------------------------------------------------------------------------
+
+const LIGHT_UNITS_ARBITRARY: u32 = 0u;
+
+fn light_distance_attenuation(light: Light, dist: f32) -> f32 {
+    let range = max(light.intensity_range.y, 1e-5);
+    if (light.units == LIGHT_UNITS_ARBITRARY) {
+        return pow(clamp(1.0 - dist / range, 0.0, 1.0), 2.0);
+    }
+    let ratio = dist / range;
+    let ratio2 = ratio * ratio;
+    let window = clamp(1.0 - ratio2 * ratio2, 0.0, 1.0);
+    return window * window / max(dist * dist, 1e-4);
+}
+
+// Radiance scattered toward the camera at `world_pos`; `view_dir` points away from the camera
+fn in_scatter(world_pos: vec3<f32>, view_dir: vec3<f32>) -> vec3<f32> {
+    let cam = light_params.camera_index;
+    let base = cam * light_params.max_lights_per_camera;
+    let count = min(visible_counts[cam], light_params.max_lights_per_camera);
+
+    var radiance = vec3<f32>(0.0);
+    for (var i = 0u; i < count; i++) {
+        let light = lights[visible_indices[base + i]];
+        if (light.volumetric <= 0.0) {
+            continue;
+        }
+        let kind = light.kind_flags.x;
+        let energy = light.color.rgb * light.intensity_range.x * light.volumetric;
+
+        if (kind == 0u) {
+            let travel = normalize(light.direction.xyz);
+            let shadow = light_shadow(light, world_pos);
+            radiance += energy * phase(dot(travel, -view_dir)) * shadow;
+        } else if (kind == 1u || kind == 2u) {
+            let to_pos = world_pos - light.position.xyz;
+            let dist = length(to_pos);
+            if (dist <= 1e-5 || dist > light.intensity_range.y) {
+                continue;
+            }
+            let travel = to_pos / dist;
+            var cone = 1.0;
+            if (kind == 2u) {
+                let inner = cos(light.spot_inner_outer.x);
+                let outer = cos(light.spot_inner_outer.y);
+                let theta = dot(travel, normalize(light.direction.xyz));
+                cone = clamp((theta - outer) / max(inner - outer, 1e-5), 0.0, 1.0);
+                if (cone <= 0.0) {
+                    continue;
+                }
+            }
+            let attenuation = light_distance_attenuation(light, dist);
+            let shadow = light_shadow(light, world_pos);
+            radiance += energy * phase(dot(travel, -view_dir)) * attenuation * cone * shadow;
+        } else if (kind == 3u) {
+            radiance += energy;
+        } else if (kind == 4u) {
+            radiance += mix(light.ground_color.rgb, light.color.rgb, 0.5)
+                * light.intensity_range.x
+                * light.volumetric;
+        }
+    }
+    return radiance;
+}
+
+// rgb: light scattered toward the camera, a: transmittance of the scene behind the fog
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let ndc_xy = vec2<f32>(in.uv.x * 2.0 - 1.0, 1.0 - in.uv.y * 2.0);
+    let depth = depth_at(in.uv);
+    let max_distance = fog.march.x;
+
+    // Reverse Z: the near plane is at 1 and empty pixels keep the cleared 0
+    let ray_start = world_from_ndc(vec3<f32>(ndc_xy, 1.0));
+    let hit = depth > 0.0;
+    let toward = world_from_ndc(vec3<f32>(ndc_xy, select(0.5, depth, hit)));
+    let view_dir = normalize(toward - ray_start);
+    let ray_length = select(max_distance, min(distance(toward, ray_start), max_distance), hit);
+
+    let steps = max(u32(fog.march.y), 1u);
+    let step_length = ray_length / f32(steps);
+    let jitter = step_jitter(in.position.xy, fog.march.z);
+
+    var transmittance = 1.0;
+    var scattered = vec3<f32>(0.0);
+    for (var i = 0u; i < steps; i++) {
+        let t = (f32(i) + jitter) * step_length;
+        let pos = ray_start + view_dir * t;
+        let sigma = fog_density(pos.y);
+        if (sigma <= 0.0) {
+            continue;
+        }
+        let step_transmittance = exp(-sigma * step_length);
+        // Analytic integral of constant in-scattering over the step
+        scattered += transmittance * in_scatter(pos, view_dir) * (1.0 - step_transmittance);
+        transmittance *= step_transmittance;
+        if (transmittance < 0.001) {
+            break;
+        }
+    }
+
+    let scattering = fog.color_density.rgb * fog.height.z;
+    return vec4<f32>(scattered * scattering, transmittance);
+}
@@ -0,0 +1,225 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::core::render::RenderState;
+use crate::core::render::cache::{PipelineKey, ShaderId};
+use crate::core::resources::{CameraRecord, FogConfig};
+
+/// Bytes between per-camera uniform slots, the largest offset alignment wgpu allows
+pub(crate) const FOG_UNIFORM_SLOT_SIZE: u64 = 256;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct FogUniform {
+    inv_view_proj: [[f32; 4]; 4],
+    color_density: [f32; 4],
+    height: [f32; 4],
+    march: [f32; 4],
+}
+
+/// Blends `scene * transmittance + in_scattered`, with the shader writing
+/// in-scattered light to rgb and transmittance to alpha
+const FOG_BLEND: wgpu::BlendState = wgpu::BlendState {
+    color: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::SrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    },
+    alpha: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::Zero,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    },
+};
+
+fn fog_uniform(record: &CameraRecord, config: &FogConfig, frame_index: u64) -> FogUniform {
+    FogUniform {
+        inv_view_proj: record.data.view_projection.inverse().to_cols_array_2d(),
+        color_density: config.color.extend(config.density.max(0.0)).to_array(),
+        height: [
+            config.height_falloff.max(0.0),
+            config.base_height,
+            config.scattering.max(0.0),
+            config.anisotropy.clamp(-0.9, 0.9),
+        ],
+        march: [
+            config.max_distance.max(0.0),
+            config.steps.clamp(1, 256) as f32,
+            (frame_index % 64) as f32,
+            0.0,
+        ],
+    }
+}
+
+/// Ray-marches `EnvironmentConfig::fog` through each camera's depth and blends the result
+/// over its HDR color. Lights are the camera's culled lights, shadowed through the shadow atlas.
+pub fn pass_fog(
+    render_state: &mut RenderState,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    encoder: &mut wgpu::CommandEncoder,
+    frame_index: u64,
+) {
+    let fog_config = render_state.environment.fog.clone();
+    if !fog_config.enabled || fog_config.density <= 0.0 || render_state.scene.cameras.is_empty() {
+        return;
+    }
+
+    let needed = render_state.scene.cameras.len() as u64 * FOG_UNIFORM_SLOT_SIZE;
+    if render_state
+        .fog_uniform_buffer
+        .as_ref()
+        .is_some_and(|buffer| buffer.size() < needed)
+    {
+        render_state.fog_uniform_buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Fog Uniform Buffer"),
+            size: needed.next_power_of_two(),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+    }
+
+    let library = match render_state.library.as_ref() {
+        Some(lib) => lib,
+        None => return,
+    };
+    let depth_target = match render_state.forward_depth_target.as_ref() {
+        Some(target) => target,
+        None => return,
+    };
+    let fog_buffer = match render_state.fog_uniform_buffer.as_ref() {
+        Some(buffer) => buffer,
+        None => return,
+    };
+    let (bindings, light_system) = match (
+        render_state.bindings.as_ref(),
+        render_state.light_system.as_mut(),
+    ) {
+        (Some(bindings), Some(light_system)) => (bindings, light_system),
+        _ => return,
+    };
+    let shared_group = match bindings.shared_group() {
+        Some(group) => group,
+        None => return,
+    };
+
+    let (shader_id, label, shader, pipeline_layout, bind_group_layout) =
+        if depth_target.sample_count > 1 {
+            (
+                ShaderId::FogMsaa,
+                "Fog MSAA Pipeline",
+                &library.fog_msaa_shader,
+                &library.fog_msaa_pipeline_layout,
+                &library.layout_ssao_msaa,
+            )
+        } else {
+            (
+                ShaderId::Fog,
+                "Fog Pipeline",
+                &library.fog_shader,
+                &library.fog_pipeline_layout,
+                &library.layout_ssao,
+            )
+        };
+
+    let mut sorted_cameras: Vec<_> = render_state.scene.cameras.iter().collect();
+    sorted_cameras.sort_by_key(|(_, record)| record.order);
+
+    for (camera_index, (camera_id, record)) in sorted_cameras.into_iter().enumerate() {
+        let target = match &record.render_target {
+            Some(t) => t,
+            None => continue,
+        };
+
+        let slot_offset = camera_index as u64 * FOG_UNIFORM_SLOT_SIZE;
+        let uniform = fog_uniform(record, &fog_config, frame_index);
+        queue.write_buffer(fog_buffer, slot_offset, bytemuck::bytes_of(&uniform));
+        light_system.write_draw_params(camera_index as u32, light_system.max_lights_per_camera);
+
+        let key = PipelineKey {
+            shader_id: shader_id as u64,
+            color_format: target.format,
+            color_target_count: 1,
+            depth_format: None,
+            sample_count: 1,
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            front_face: wgpu::FrontFace::Ccw,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            blend: Some(FOG_BLEND),
+            conservative: false,
+        };
+        let pipeline = render_state.cache.get_or_create(key, frame_index, || {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: target.format,
+                        blend: key.blend,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: None,
+            })
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Fog Bind Group"),
+            layout: bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&depth_target.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: fog_buffer,
+                        offset: slot_offset,
+                        size: wgpu::BufferSize::new(std::mem::size_of::<FogUniform>() as u64),
+                    }),
+                },
+            ],
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&format!("Fog Pass - Camera {}", camera_id)),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &target.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+
+        let size = target._texture.size();
+        let camera_offset = bindings.camera_pool.get_offset(*camera_id) as u32;
+        let light_offset = light_system.draw_params_offset(camera_index as u32) as u32;
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_viewport(0.0, 0.0, size.width as f32, size.height as f32, 0.0, 1.0);
+        render_pass.set_bind_group(0, shared_group, &[camera_offset, light_offset]);
+        render_pass.set_bind_group(1, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
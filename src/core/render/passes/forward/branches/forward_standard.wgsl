@@ -6,7 +6,7 @@ struct Frame {
     time: f32,
     delta_time: f32,
     frame_index: u32,
-    _padding: u32,
+    mip_bias: f32,
 }
 
 struct Camera {
@@ -23,6 +23,13 @@ struct Camera {
 struct LightDrawParams {
     camera_index: u32,
     max_lights_per_camera: u32,
+    clustered: u32,
+    max_lights_per_cluster: u32,
+    cluster_dims: vec4<u32>, // w: froxels per camera
+    cluster_count_base: u32,
+    cluster_index_base: u32,
+    _padding0: u32,
+    _padding1: u32,
 };
 
 struct Light {
@@ -37,7 +44,11 @@ struct Light {
     spot_inner_outer: vec2<f32>,
     kind_flags: vec2<u32>,
     shadow_index: u32,
-    _padding: u32,
+    units: u32,
+    area_right: vec4<f32>,
+    area_size: vec2<f32>,
+    volumetric: f32,
+    _padding: f32,
 }
 
 struct ShadowPageEntry {
@@ -57,9 +68,9 @@ struct ShadowParams {
     point_bias_min: f32,
     point_bias_slope: f32,
     normal_bias: f32,
-    _padding0: f32,
-    _padding1: f32,
-    _padding2: f32,
+    pcss_enabled: u32,
+    pcss_light_size: f32,
+    pcss_search_radius: f32,
 }
 
 struct Model {
@@ -116,6 +127,10 @@ struct MaterialStandardParams {
 @group(1) @binding(10) var material_tex7: texture_2d<f32>;
 @group(1) @binding(11) var<storage, read> bones: array<mat4x4<f32>>;
 
+// Transparent pipelines only; `fs_transparent_msaa` reads the multisampled variant
+@group(2) @binding(0) var scene_depth: texture_depth_2d;
+@group(2) @binding(1) var scene_depth_msaa: texture_depth_multisampled_2d;
+
 const STANDARD_INVALID_SLOT: u32 = 0xFFFFFFFFu;
 const SURFACE_MASKED: u32 = 1u;
 const ALPHA_CUTOFF: f32 = 0.5;
@@ -124,7 +139,15 @@ const TEX_SPEC: u32 = 1u;
 const TEX_NORMAL: u32 = 2u;
 const TEX_TOON: u32 = 3u;
 const TEX_EMISSIVE: u32 = 4u;
+const TEX_LIGHTMAP: u32 = 5u;
+const INPUT_TOON: u32 = 4u; // x: bands, y: band softness, z: rim width, w: rim softness
+const INPUT_LIGHTMAP: u32 = 5u;
+const INPUT_VERTEX_COLOR: u32 = 6u;
+const TEX_DISPLACEMENT: u32 = 6u;
+const INPUT_SURFACE: u32 = 7u; // x: soft depth fade, y: displacement scale
+const INPUT_RIM_COLOR: u32 = 8u;
 const STANDARD_FLAG_SPECULAR: u32 = 1u;
+const STANDARD_FLAG_VERTEX_COLOR_REPLACE: u32 = 2u;
 const SAMPLER_POINT_CLAMP: u32 = 0u;
 const SAMPLER_LINEAR_CLAMP: u32 = 1u;
 const SAMPLER_POINT_REPEAT: u32 = 2u;
@@ -152,62 +175,62 @@ fn sample_color(tex_slot: u32, sampler_index: u32, uv: vec2<f32>) -> vec4<f32> {
         return vec4<f32>(1.0);
     }
     if (tex_slot == 0u) {
-        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSample(material_tex0, point_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSample(material_tex0, linear_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSample(material_tex0, point_repeat_sampler, uv); }
-        return textureSample(material_tex0, linear_repeat_sampler, uv);
+        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSampleBias(material_tex0, point_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSampleBias(material_tex0, linear_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSampleBias(material_tex0, point_repeat_sampler, uv, frame.mip_bias); }
+        return textureSampleBias(material_tex0, linear_repeat_sampler, uv, frame.mip_bias);
     }
     if (tex_slot == 1u) {
-        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSample(material_tex1, point_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSample(material_tex1, linear_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSample(material_tex1, point_repeat_sampler, uv); }
-        return textureSample(material_tex1, linear_repeat_sampler, uv);
+        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSampleBias(material_tex1, point_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSampleBias(material_tex1, linear_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSampleBias(material_tex1, point_repeat_sampler, uv, frame.mip_bias); }
+        return textureSampleBias(material_tex1, linear_repeat_sampler, uv, frame.mip_bias);
     }
     if (tex_slot == 2u) {
-        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSample(material_tex2, point_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSample(material_tex2, linear_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSample(material_tex2, point_repeat_sampler, uv); }
-        return textureSample(material_tex2, linear_repeat_sampler, uv);
+        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSampleBias(material_tex2, point_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSampleBias(material_tex2, linear_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSampleBias(material_tex2, point_repeat_sampler, uv, frame.mip_bias); }
+        return textureSampleBias(material_tex2, linear_repeat_sampler, uv, frame.mip_bias);
     }
     if (tex_slot == 3u) {
-        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSample(material_tex3, point_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSample(material_tex3, linear_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSample(material_tex3, point_repeat_sampler, uv); }
-        return textureSample(material_tex3, linear_repeat_sampler, uv);
+        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSampleBias(material_tex3, point_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSampleBias(material_tex3, linear_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSampleBias(material_tex3, point_repeat_sampler, uv, frame.mip_bias); }
+        return textureSampleBias(material_tex3, linear_repeat_sampler, uv, frame.mip_bias);
     }
     if (tex_slot == 4u) {
-        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSample(material_tex4, point_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSample(material_tex4, linear_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSample(material_tex4, point_repeat_sampler, uv); }
-        return textureSample(material_tex4, linear_repeat_sampler, uv);
+        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSampleBias(material_tex4, point_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSampleBias(material_tex4, linear_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSampleBias(material_tex4, point_repeat_sampler, uv, frame.mip_bias); }
+        return textureSampleBias(material_tex4, linear_repeat_sampler, uv, frame.mip_bias);
     }
     if (tex_slot == 5u) {
-        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSample(material_tex5, point_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSample(material_tex5, linear_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSample(material_tex5, point_repeat_sampler, uv); }
-        return textureSample(material_tex5, linear_repeat_sampler, uv);
+        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSampleBias(material_tex5, point_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSampleBias(material_tex5, linear_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSampleBias(material_tex5, point_repeat_sampler, uv, frame.mip_bias); }
+        return textureSampleBias(material_tex5, linear_repeat_sampler, uv, frame.mip_bias);
     }
     if (tex_slot == 6u) {
-        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSample(material_tex6, point_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSample(material_tex6, linear_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSample(material_tex6, point_repeat_sampler, uv); }
-        return textureSample(material_tex6, linear_repeat_sampler, uv);
+        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSampleBias(material_tex6, point_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSampleBias(material_tex6, linear_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSampleBias(material_tex6, point_repeat_sampler, uv, frame.mip_bias); }
+        return textureSampleBias(material_tex6, linear_repeat_sampler, uv, frame.mip_bias);
     }
     if (tex_slot == 7u) {
-        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSample(material_tex7, point_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSample(material_tex7, linear_clamp_sampler, uv); }
-        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSample(material_tex7, point_repeat_sampler, uv); }
-        return textureSample(material_tex7, linear_repeat_sampler, uv);
+        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSampleBias(material_tex7, point_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSampleBias(material_tex7, linear_clamp_sampler, uv, frame.mip_bias); }
+        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSampleBias(material_tex7, point_repeat_sampler, uv, frame.mip_bias); }
+        return textureSampleBias(material_tex7, linear_repeat_sampler, uv, frame.mip_bias);
     }
     return vec4<f32>(1.0);
 }
 
 fn sample_atlas(sampler_index: u32, uv: vec2<f32>, layer: u32) -> vec4<f32> {
     let layer_i = i32(layer);
-    if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSample(forward_atlas, point_clamp_sampler, uv, layer_i); }
-    if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSample(forward_atlas, linear_clamp_sampler, uv, layer_i); }
-    if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSample(forward_atlas, point_repeat_sampler, uv, layer_i); }
-    return textureSample(forward_atlas, linear_repeat_sampler, uv, layer_i);
+    if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSampleBias(forward_atlas, point_clamp_sampler, uv, layer_i, frame.mip_bias); }
+    if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSampleBias(forward_atlas, linear_clamp_sampler, uv, layer_i, frame.mip_bias); }
+    if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSampleBias(forward_atlas, point_repeat_sampler, uv, layer_i, frame.mip_bias); }
+    return textureSampleBias(forward_atlas, linear_repeat_sampler, uv, layer_i, frame.mip_bias);
 }
 
 fn sample_material(tex_slot: u32, sampler_index: u32, uv: vec2<f32>) -> vec4<f32> {
@@ -231,12 +254,36 @@ fn sample_material(tex_slot: u32, sampler_index: u32, uv: vec2<f32>) -> vec4<f32
 
 fn diffuse_term(ndotl: f32, toon_slot: u32, toon_sampler: u32) -> vec3<f32> {
     if (toon_slot == STANDARD_INVALID_SLOT) {
-        return vec3<f32>(ndotl);
+        let toon = input_at(INPUT_TOON);
+        let bands = floor(toon.x);
+        if (bands < 1.0) {
+            return vec3<f32>(ndotl);
+        }
+        // Each band steps up at its upper edge; softness widens the step into a ramp
+        let scaled = ndotl * bands;
+        let softness = clamp(toon.y, 0.001, 1.0);
+        let stepped = floor(scaled) + smoothstep(1.0 - softness, 1.0, fract(scaled));
+        return vec3<f32>(stepped / bands);
     }
     let ramp = sample_material(toon_slot, toon_sampler, vec2<f32>(ndotl, 0.5));
     return ramp.rgb;
 }
 
+// Toon rim light: a band along the silhouette, where the surface turns away from the view
+fn rim_term(normal: vec3<f32>, world_pos: vec3<f32>) -> vec3<f32> {
+    let toon = input_at(INPUT_TOON);
+    let width = clamp(toon.z, 0.0, 1.0);
+    if (width <= 0.0) {
+        return vec3<f32>(0.0);
+    }
+    let view_dir = normalize(camera.position.xyz - world_pos);
+    let facing = 1.0 - max(dot(normal, view_dir), 0.0);
+    let softness = clamp(toon.w, 0.001, 1.0);
+    let rim = smoothstep(1.0 - width, 1.0 - width + softness * width, facing);
+    let rim_color = input_at(INPUT_RIM_COLOR);
+    return rim_color.rgb * rim_color.a * rim;
+}
+
 fn apply_normal_map(
     normal: vec3<f32>,
     world_pos: vec3<f32>,
@@ -259,6 +306,103 @@ fn apply_normal_map(
     return normalize(tbn * map);
 }
 
+// Baked lighting from the lightmap slot, sampled with UV1 (zero without a lightmap)
+fn sample_lightmap(uv1: vec2<f32>) -> vec3<f32> {
+    let slot = get_slot(material.texture_slots, TEX_LIGHTMAP);
+    if (slot == STANDARD_INVALID_SLOT) {
+        return vec3<f32>(0.0);
+    }
+    let sampler_index = get_slot(material.sampler_indices, TEX_LIGHTMAP);
+    return sample_material(slot, sampler_index, uv1).rgb * input_at(INPUT_LIGHTMAP).x;
+}
+
+// -----------------------------------------------------------------------------
+// Displacement
+// -----------------------------------------------------------------------------
+
+// Height from the displacement slot's red channel at mip 0, so the vertex stage can read it
+fn sample_height(uv: vec2<f32>) -> f32 {
+    let slot = get_slot(material.texture_slots, TEX_DISPLACEMENT);
+    if (slot == STANDARD_INVALID_SLOT) {
+        return 0.0;
+    }
+    let sampler_index = get_slot(material.sampler_indices, TEX_DISPLACEMENT);
+    let source = get_slot(material.tex_sources, slot);
+    if (source == TEX_SOURCE_ATLAS) {
+        let scale_bias = material.atlas_scale_bias[slot];
+        let atlas_uv = uv * scale_bias.xy + scale_bias.zw;
+        let layer = i32(get_slot(material.atlas_layers, slot));
+        if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSampleLevel(forward_atlas, point_clamp_sampler, atlas_uv, layer, 0.0).r; }
+        if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSampleLevel(forward_atlas, linear_clamp_sampler, atlas_uv, layer, 0.0).r; }
+        if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSampleLevel(forward_atlas, point_repeat_sampler, atlas_uv, layer, 0.0).r; }
+        return textureSampleLevel(forward_atlas, linear_repeat_sampler, atlas_uv, layer, 0.0).r;
+    }
+    if (source == TEX_SOURCE_INVALID) {
+        return 0.0;
+    }
+    if (sampler_index == SAMPLER_POINT_CLAMP) { return textureSampleLevel(material_tex6, point_clamp_sampler, uv, 0.0).r; }
+    if (sampler_index == SAMPLER_LINEAR_CLAMP) { return textureSampleLevel(material_tex6, linear_clamp_sampler, uv, 0.0).r; }
+    if (sampler_index == SAMPLER_POINT_REPEAT) { return textureSampleLevel(material_tex6, point_repeat_sampler, uv, 0.0).r; }
+    return textureSampleLevel(material_tex6, linear_repeat_sampler, uv, 0.0).r;
+}
+
+// One texel of the displacement texture in UV units
+fn height_texel(slot: u32) -> vec2<f32> {
+    if (get_slot(material.tex_sources, slot) == TEX_SOURCE_ATLAS) {
+        let scale = material.atlas_scale_bias[slot].xy;
+        return 1.0 / (vec2<f32>(textureDimensions(forward_atlas)) * scale);
+    }
+    return 1.0 / vec2<f32>(textureDimensions(material_tex6));
+}
+
+fn displacement_enabled() -> bool {
+    return get_slot(material.texture_slots, TEX_DISPLACEMENT) != STANDARD_INVALID_SLOT
+        && input_at(INPUT_SURFACE).y != 0.0;
+}
+
+// Moves a world position along its normal by height * displacement scale
+fn displace_position(world_pos: vec3<f32>, world_normal: vec3<f32>, uv: vec2<f32>) -> vec3<f32> {
+    if (!displacement_enabled() || dot(world_normal, world_normal) == 0.0) {
+        return world_pos;
+    }
+    return world_pos + normalize(world_normal) * (sample_height(uv) * input_at(INPUT_SURFACE).y);
+}
+
+// Normal of the displaced surface: height differences across one texel, applied to the
+// undisplaced surface's UV derivatives (its position per UV unit)
+fn displaced_normal(normal: vec3<f32>, base_pos: vec3<f32>, uv: vec2<f32>) -> vec3<f32> {
+    if (!displacement_enabled()) {
+        return normal;
+    }
+    let dp1 = dpdx(base_pos);
+    let dp2 = dpdy(base_pos);
+    let duv1 = dpdx(uv);
+    let duv2 = dpdy(uv);
+    let det = duv1.x * duv2.y - duv1.y * duv2.x;
+    if (abs(det) < 1e-12) {
+        return normal;
+    }
+    let dp_du = (dp1 * duv2.y - dp2 * duv1.y) / det;
+    let dp_dv = (dp2 * duv1.x - dp1 * duv2.x) / det;
+
+    let texel = height_texel(get_slot(material.texture_slots, TEX_DISPLACEMENT));
+    let step_u = vec2<f32>(texel.x, 0.0);
+    let step_v = vec2<f32>(0.0, texel.y);
+    let dh_du = (sample_height(uv + step_u) - sample_height(uv - step_u)) / (2.0 * texel.x);
+    let dh_dv = (sample_height(uv + step_v) - sample_height(uv - step_v)) / (2.0 * texel.y);
+
+    let n = normalize(normal);
+    let scale = input_at(INPUT_SURFACE).y;
+    let ds_du = dp_du + n * (dh_du * scale);
+    let ds_dv = dp_dv + n * (dh_dv * scale);
+    let displaced = cross(ds_du, ds_dv);
+    if (dot(displaced, displaced) == 0.0) {
+        return normal;
+    }
+    // UV winding decides the cross product's side; keep the one the mesh normal faces
+    return normalize(displaced) * select(-1.0, 1.0, dot(displaced, n) >= 0.0);
+}
+
 // -----------------------------------------------------------------------------
 // Vertex I/O
 // -----------------------------------------------------------------------------
@@ -267,18 +411,21 @@ struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) normal: vec3<f32>,
     @location(4) uv0: vec2<f32>,
+    @location(5) uv1: vec2<f32>,
     @location(3) color0: vec4<f32>,
     @location(6) joints: vec4<u32>,
     @location(7) weights: vec4<f32>,
 }
 
 struct VertexOutput {
-    @builtin(position) clip_position: vec4<f32>,
+    @builtin(position) @invariant clip_position: vec4<f32>,
     @location(0) world_position: vec3<f32>,
     @location(1) normal: vec3<f32>,
     @location(2) uv0: vec2<f32>,
     @location(3) color0: vec4<f32>,
     @location(4) @interpolate(flat) instance_id: u32,
+    @location(5) uv1: vec2<f32>,
+    @location(6) base_position: vec3<f32>,
 }
 
 // -----------------------------------------------------------------------------
@@ -344,6 +491,65 @@ fn skin_normal(
     return normalize(n0 + n1 + n2 + n3);
 }
 
+// Percentage-closer soft shadows: the PCF radius grows with the distance
+// between the receiver and the average blocker found around it.
+fn sample_shadow_pcss(
+    layer: i32,
+    atlas_uv_center: vec2<f32>,
+    uv_min: vec2<f32>,
+    uv_max: vec2<f32>,
+    page_size: vec2<f32>,
+    receiver_depth: f32,
+    atlas_texel: vec2<f32>
+) -> f32 {
+    let dim = vec2<f32>(textureDimensions(shadow_atlas));
+    let search_radius = max(shadow_params.pcss_search_radius, 1.0);
+    let search_step = search_radius / 3.0;
+
+    // 1. Blocker search (Reverse Z: blockers have a larger depth)
+    var blocker_sum = 0.0;
+    var blocker_count = 0.0;
+    for (var oy = -3; oy <= 3; oy = oy + 1) {
+        for (var ox = -3; ox <= 3; ox = ox + 1) {
+            let offset = vec2<f32>(f32(ox), f32(oy)) * search_step * atlas_texel;
+            let uv = clamp(atlas_uv_center + offset, uv_min, uv_max);
+            let depth = textureLoad(shadow_atlas, vec2<i32>(uv * dim), layer, 0);
+            if (depth > receiver_depth) {
+                blocker_sum += depth;
+                blocker_count += 1.0;
+            }
+        }
+    }
+    if (blocker_count == 0.0) {
+        return 1.0;
+    }
+    let blocker_depth = blocker_sum / blocker_count;
+
+    // 2. Penumbra estimate, in page UV units (Reverse Z: (d_b - d_r) / d_r)
+    let penumbra = shadow_params.pcss_light_size
+        * (blocker_depth - receiver_depth) / max(receiver_depth, 1e-5);
+    let radius = clamp(penumbra * page_size, atlas_texel, atlas_texel * search_radius * 2.0);
+
+    // 3. Variable-radius PCF
+    var sum = 0.0;
+    var samples = 0.0;
+    for (var oy = -2; oy <= 2; oy = oy + 1) {
+        for (var ox = -2; ox <= 2; ox = ox + 1) {
+            let offset = vec2<f32>(f32(ox), f32(oy)) * 0.5 * radius;
+            let uv = clamp(atlas_uv_center + offset, uv_min, uv_max);
+            sum += textureSampleCompareLevel(
+                shadow_atlas,
+                shadow_sampler,
+                uv,
+                layer,
+                receiver_depth
+            );
+            samples += 1.0;
+        }
+    }
+    return sum / samples;
+}
+
 fn sample_shadow_page_at(
     light_base: u32,
     light_ndc: vec3<f32>,
@@ -389,7 +595,17 @@ fn sample_shadow_page_at(
     let uv_max = tile_max - guard;
 
     var shadow = 1.0;
-    if (shadow_params.pcf_range == 0) {
+    if (shadow_params.pcss_enabled != 0u) {
+        shadow = sample_shadow_pcss(
+            i32(page.layer_index),
+            atlas_uv_center,
+            uv_min,
+            uv_max,
+            page.scale_offset.xy,
+            saturate(light_depth + bias),
+            atlas_texel
+        );
+    } else if (shadow_params.pcf_range == 0) {
         let uv = clamp(atlas_uv_center, uv_min, uv_max);
         shadow = textureSampleCompare(
             shadow_atlas,
@@ -504,6 +720,76 @@ fn get_shadow_factor(
     return select(1.0, shadow, shadow_enabled);
 }
 
+// -----------------------------------------------------------------------------
+// Light lists
+// -----------------------------------------------------------------------------
+
+// Depth slice of view depth `depth`, the inverse of `slice_depth` in light_cluster.wgsl
+fn cluster_slice(depth: f32) -> u32 {
+    let near = max(camera.near_far.x, 1e-4);
+    let far = max(camera.near_far.y, near + 1e-4);
+    var t = (depth - near) / (far - near);
+    if (camera.kind_flags.x == 0u) {
+        t = log(max(depth, near) / near) / log(far / near);
+    }
+    let slices = light_params.cluster_dims.z;
+    return min(u32(max(t, 0.0) * f32(slices)), slices - 1u);
+}
+
+// Start and length of the lights to shade `world_pos` with: its froxel's list when
+// clustered culling ran, the camera's list otherwise
+fn light_list(world_pos: vec3<f32>) -> vec2<u32> {
+    let cam = light_params.camera_index;
+    if (light_params.clustered == 0u) {
+        let base = cam * light_params.max_lights_per_camera;
+        return vec2<u32>(base, min(visible_counts[cam], light_params.max_lights_per_camera));
+    }
+
+    let dims = light_params.cluster_dims.xyz;
+    let clip = camera.view_projection * vec4<f32>(world_pos, 1.0);
+    let ndc = clip.xy / clip.w;
+    let tile = vec2<u32>(clamp(
+        (ndc * 0.5 + 0.5) * vec2<f32>(dims.xy),
+        vec2<f32>(0.0),
+        vec2<f32>(dims.xy - 1u)
+    ));
+    let depth = -(camera.view * vec4<f32>(world_pos, 1.0)).z;
+    let cluster = tile.x + tile.y * dims.x + cluster_slice(depth) * dims.x * dims.y;
+
+    let max_lights = light_params.max_lights_per_cluster;
+    let count = min(visible_counts[light_params.cluster_count_base + cluster], max_lights);
+    return vec2<u32>(light_params.cluster_index_base + cluster * max_lights, count);
+}
+
+// -----------------------------------------------------------------------------
+// Light falloff
+// -----------------------------------------------------------------------------
+
+const LIGHT_UNITS_ARBITRARY: u32 = 0u;
+
+// Distance attenuation for point and spot lights. Arbitrary units keep the
+// (1 - d/r)^2 ramp; photometric units use inverse-square with a smooth window
+// that reaches zero at `range`, clamped at 1 cm to avoid the singularity.
+fn light_distance_attenuation(light: Light, dist: f32) -> f32 {
+    let range = max(light.intensity_range.y, 1e-5);
+    if (light.units == LIGHT_UNITS_ARBITRARY) {
+        return pow(clamp(1.0 - dist / range, 0.0, 1.0), 2.0);
+    }
+    let ratio = dist / range;
+    let ratio2 = ratio * ratio;
+    let window = clamp(1.0 - ratio2 * ratio2, 0.0, 1.0);
+    return window * window / max(dist * dist, 1e-4);
+}
+
+// Phong highlights from local lights were never attenuated in arbitrary units;
+// photometric lights fall off like their diffuse term.
+fn light_specular_falloff(light: Light, world_pos: vec3<f32>) -> f32 {
+    if (light.units == LIGHT_UNITS_ARBITRARY) {
+        return 1.0;
+    }
+    return light_distance_attenuation(light, distance(world_pos, light.position.xyz));
+}
+
 // -----------------------------------------------------------------------------
 // Lighting (Standard: Lambert + Phong)
 // -----------------------------------------------------------------------------
@@ -551,9 +837,8 @@ fn calculate_spot_light(
     let dist_valid = dist > 1e-5;
     let inv_dist = select(0.0, 1.0 / dist, dist_valid);
     let l = -light_to_pos * inv_dist;
-    let safe_range = max(range, 1e-5);
     let in_range = dist <= range;
-    let attenuation = pow(clamp(1.0 - dist / safe_range, 0.0, 1.0), 2.0);
+    let attenuation = light_distance_attenuation(light, dist);
     let theta = dot(l, normalize(-light.direction.xyz));
     let inner = cos(light.spot_inner_outer.x);
     let outer = cos(light.spot_inner_outer.y);
@@ -583,9 +868,8 @@ fn calculate_point_light(
     let dist_valid = dist > 1e-5;
     let inv_dist = select(0.0, 1.0 / dist, dist_valid);
     let l = -light_to_pos * inv_dist;
-    let safe_range = max(range, 1e-5);
     let in_range = dist <= range;
-    let attenuation = pow(clamp(1.0 - dist / safe_range, 0.0, 1.0), 2.0);
+    let attenuation = light_distance_attenuation(light, dist);
     let ndotl = max(dot(normal, l), 0.0);
     let ndotl_shadow = max(dot(shadow_normal, l), 0.0);
     let shadow = get_shadow_factor(light, world_pos, shadow_normal, ndotl_shadow, instance_id);
@@ -595,6 +879,111 @@ fn calculate_point_light(
     return select(vec3<f32>(0.0), lighting, enabled);
 }
 
+// -----------------------------------------------------------------------------
+// Area lights (linearly transformed cosines)
+// -----------------------------------------------------------------------------
+
+fn area_light_corners(light: Light) -> array<vec3<f32>, 4> {
+    let center = light.position.xyz;
+    let normal = normalize(light.direction.xyz);
+    var right = light.area_right.xyz - normal * dot(light.area_right.xyz, normal);
+    if (dot(right, right) < 1e-8) {
+        right = select(vec3<f32>(1.0, 0.0, 0.0), vec3<f32>(0.0, 0.0, 1.0), abs(normal.x) > 0.99);
+        right = right - normal * dot(right, normal);
+    }
+    right = normalize(right);
+    // Counter-clockwise seen from the lit side, so the integral is positive in front
+    let up = cross(right, normal) * (0.5 * light.area_size.y);
+    right = right * (0.5 * light.area_size.x);
+    return array<vec3<f32>, 4>(
+        center - right - up,
+        center + right - up,
+        center + right + up,
+        center - right + up,
+    );
+}
+
+fn ltc_integrate_edge(v1: vec3<f32>, v2: vec3<f32>) -> vec3<f32> {
+    // Fitted theta / sin(theta) / (2 * PI), accurate near 0 and pi
+    let x = dot(v1, v2);
+    let y = abs(x);
+    let a = 0.8543985 + (0.4965155 + 0.0145206 * y) * y;
+    let b = 3.4175940 + (4.1616724 + y) * y;
+    let v = a / b;
+    let theta_sintheta = select(0.5 * inverseSqrt(max(1.0 - x * x, 1e-7)) - v, v, x > 0.0);
+    return cross(v1, v2) * theta_sintheta;
+}
+
+// Integral of the clamped cosine distribution transformed by `minv` over the polygon.
+// With the identity transform this is the polygon's form factor.
+fn ltc_evaluate(
+    n: vec3<f32>,
+    v: vec3<f32>,
+    p: vec3<f32>,
+    minv: mat3x3<f32>,
+    corners: array<vec3<f32>, 4>,
+) -> f32 {
+    var t1 = v - n * dot(v, n);
+    if (dot(t1, t1) < 1e-8) {
+        t1 = select(vec3<f32>(1.0, 0.0, 0.0), vec3<f32>(0.0, 0.0, 1.0), abs(n.x) > 0.99);
+        t1 = t1 - n * dot(t1, n);
+    }
+    t1 = normalize(t1);
+    let t2 = cross(n, t1);
+    let m = minv * transpose(mat3x3<f32>(t1, t2, n));
+
+    var l: array<vec3<f32>, 4>;
+    for (var i = 0u; i < 4u; i++) {
+        l[i] = m * (corners[i] - p);
+    }
+
+    // Clip the quad against the horizon (z >= 0); at most 5 vertices remain
+    var clipped: array<vec3<f32>, 5>;
+    var count = 0u;
+    for (var i = 0u; i < 4u; i++) {
+        let a = l[i];
+        let b = l[(i + 1u) % 4u];
+        if (a.z >= 0.0) {
+            clipped[count] = a;
+            count += 1u;
+        }
+        if ((a.z >= 0.0) != (b.z >= 0.0)) {
+            clipped[count] = mix(a, b, a.z / (a.z - b.z));
+            count += 1u;
+        }
+    }
+    if (count < 3u) {
+        return 0.0;
+    }
+
+    var sum = vec3<f32>(0.0);
+    for (var i = 0u; i < count; i++) {
+        let a = normalize(clipped[i]);
+        let b = normalize(clipped[(i + 1u) % count]);
+        sum += ltc_integrate_edge(a, b);
+    }
+    // One-sided: surfaces behind the light see a negative winding
+    return max(sum.z, 0.0);
+}
+
+fn area_light_window(light: Light, world_pos: vec3<f32>) -> f32 {
+    let range = max(light.intensity_range.y, 1e-5);
+    let ratio = distance(world_pos, light.position.xyz) / range;
+    let falloff = clamp(1.0 - ratio * ratio * ratio * ratio, 0.0, 1.0);
+    return falloff * falloff;
+}
+
+fn calculate_area_light(light: Light, normal: vec3<f32>, world_pos: vec3<f32>) -> vec3<f32> {
+    let identity = mat3x3<f32>(
+        vec3<f32>(1.0, 0.0, 0.0),
+        vec3<f32>(0.0, 1.0, 0.0),
+        vec3<f32>(0.0, 0.0, 1.0),
+    );
+    // Diffuse only: the identity transform gives the polygon's form factor
+    let form_factor = ltc_evaluate(normal, normal, world_pos, identity, area_light_corners(light));
+    return light.color.rgb * light.intensity_range.x * form_factor * area_light_window(light, world_pos);
+}
+
 // -----------------------------------------------------------------------------
 // Vertex
 // -----------------------------------------------------------------------------
@@ -612,10 +1001,14 @@ fn vs_main(in: VertexInput, @builtin(instance_index) instance_id: u32) -> Vertex
         local_normal = skin_normal(in.normal, in.joints, in.weights, bone_offset, bone_count);
     }
     let world_pos = model.transform * vec4<f32>(local_pos, 1.0);
-    out.clip_position = camera.view_projection * world_pos;
-    out.world_position = world_pos.xyz;
-    out.normal = (model.transform * vec4<f32>(local_normal, 0.0)).xyz;
+    let world_normal = (model.transform * vec4<f32>(local_normal, 0.0)).xyz;
+    let displaced_pos = displace_position(world_pos.xyz, world_normal, in.uv0);
+    out.clip_position = camera.view_projection * vec4<f32>(displaced_pos, 1.0);
+    out.world_position = displaced_pos;
+    out.base_position = world_pos.xyz;
+    out.normal = world_normal;
     out.uv0 = in.uv0;
+    out.uv1 = in.uv1;
     out.color0 = in.color0;
     out.instance_id = instance_id;
     return out;
@@ -630,8 +1023,7 @@ struct FragmentOutput {
     @location(1) emissive: vec4<f32>,
 }
 
-@fragment
-fn fs_main(in: VertexOutput) -> FragmentOutput {
+fn shade_fragment(in: VertexOutput) -> FragmentOutput {
     let base_color = input_at(material.input_indices.x);
     let emissive_color = input_at(material.input_indices.w).rgb;
     let spec_enabled = (material.surface_flags.y & STANDARD_FLAG_SPECULAR) != 0u;
@@ -646,18 +1038,25 @@ fn fs_main(in: VertexOutput) -> FragmentOutput {
     let emissive_tex = sample_material(emissive_slot, emissive_sampler, in.uv0);
     let emissive = emissive_color * emissive_tex.rgb;
 
-    var color = base_color.rgb * base_tex.rgb * in.color0.rgb;
+    let vertex_color_factor = input_at(INPUT_VERTEX_COLOR).x;
+    var tint = base_color.rgb * mix(vec3<f32>(1.0), in.color0.rgb, vertex_color_factor);
+    if ((material.surface_flags.y & STANDARD_FLAG_VERTEX_COLOR_REPLACE) != 0u) {
+        tint = mix(base_color.rgb, in.color0.rgb, vertex_color_factor);
+    }
+    var color = tint * base_tex.rgb;
     let alpha = base_color.a * base_tex.a;
+    let baked = sample_lightmap(in.uv1);
 
-    let cam = light_params.camera_index;
-    let base = cam * light_params.max_lights_per_camera;
-    let count = min(visible_counts[cam], light_params.max_lights_per_camera);
+    let lights_range = light_list(in.world_position);
+    let base = lights_range.x;
+    let count = lights_range.y;
 
     if (count > 0u) {
         let normal_slot = get_slot(material.texture_slots, TEX_NORMAL);
         let normal_sampler = get_slot(material.sampler_indices, TEX_NORMAL);
-        let n_geom = normalize(in.normal);
-        let n = apply_normal_map(in.normal, in.world_position, in.uv0, normal_slot, normal_sampler);
+        let surface_normal = displaced_normal(in.normal, in.base_position, in.uv0);
+        let n_geom = normalize(surface_normal);
+        let n = apply_normal_map(surface_normal, in.world_position, in.uv0, normal_slot, normal_sampler);
         var spec_color_final = vec3<f32>(0.0);
         var spec_power = 0.0;
         var view_dir = vec3<f32>(0.0);
@@ -708,7 +1107,8 @@ fn fs_main(in: VertexOutput) -> FragmentOutput {
                     let light_dir = normalize(light.position.xyz - in.world_position);
                     let reflect_dir = reflect(-light_dir, n);
                     let spec = pow(max(dot(view_dir, reflect_dir), 0.0), spec_power);
-                    specular += spec_color_final * spec * light.intensity_range.x;
+                    let falloff = light_specular_falloff(light, in.world_position);
+                    specular += spec_color_final * spec * light.intensity_range.x * falloff;
                 }
             } else if (kind == 2u) {
                 let spot_light = calculate_spot_light(
@@ -725,18 +1125,22 @@ fn fs_main(in: VertexOutput) -> FragmentOutput {
                     let light_dir = normalize(light.position.xyz - in.world_position);
                     let reflect_dir = reflect(-light_dir, n);
                     let spec = pow(max(dot(view_dir, reflect_dir), 0.0), spec_power);
-                    specular += spec_color_final * spec * light.intensity_range.x;
+                    let falloff = light_specular_falloff(light, in.world_position);
+                    specular += spec_color_final * spec * light.intensity_range.x * falloff;
                 }
             } else if (kind == 3u) {
                 lighting += calculate_ambient_light(light);
             } else if (kind == 4u) {
                 lighting += calculate_hemisphere_light(light, n);
+            } else if (kind == 5u) {
+                lighting += calculate_area_light(light, n, in.world_position);
             }
         }
-        color *= (lighting + vec3<f32>(0.001));
+        color *= (lighting + baked + vec3<f32>(0.001));
         color += specular;
+        color += rim_term(n, in.world_position);
     } else {
-        color *= vec3<f32>(0.001);
+        color *= (baked + vec3<f32>(0.001));
     }
     color += emissive;
 
@@ -745,3 +1149,45 @@ fn fs_main(in: VertexOutput) -> FragmentOutput {
     }
     return FragmentOutput(vec4<f32>(color, alpha), vec4<f32>(emissive, alpha));
 }
+
+// View distance of a reverse-Z scene depth; the cleared far plane reads as infinitely far
+fn scene_view_depth(depth: f32) -> f32 {
+    if (depth <= 0.0) {
+        return 3.4e38;
+    }
+    let p = camera.projection;
+    let view_z = (p[3][2] - depth * p[3][3]) / (depth * p[2][3] - p[2][2]);
+    return -view_z;
+}
+
+// Fades alpha over `soft_depth_fade` view units in front of the opaque depth
+fn apply_soft_depth_fade(out: FragmentOutput, world_position: vec3<f32>, depth: f32) -> FragmentOutput {
+    let fade_distance = input_at(INPUT_SURFACE).x;
+    if (fade_distance <= 0.0) {
+        return out;
+    }
+    let view_depth = -(camera.view * vec4<f32>(world_position, 1.0)).z;
+    let fade = saturate((scene_view_depth(depth) - view_depth) / fade_distance);
+    return FragmentOutput(
+        vec4<f32>(out.color.rgb, out.color.a * fade),
+        vec4<f32>(out.emissive.rgb, out.emissive.a * fade),
+    );
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> FragmentOutput {
+    return shade_fragment(in);
+}
+
+@fragment
+fn fs_transparent(in: VertexOutput) -> FragmentOutput {
+    let depth = textureLoad(scene_depth, vec2<i32>(in.clip_position.xy), 0);
+    return apply_soft_depth_fade(shade_fragment(in), in.world_position, depth);
+}
+
+@fragment
+fn fs_transparent_msaa(in: VertexOutput) -> FragmentOutput {
+    // First sample only; the fade spans whole pixels anyway
+    let depth = textureLoad(scene_depth_msaa, vec2<i32>(in.clip_position.xy), 0);
+    return apply_soft_depth_fade(shade_fragment(in), in.world_position, depth);
+}
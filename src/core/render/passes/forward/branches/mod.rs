@@ -1,2 +1,13 @@
 pub mod pbr;
 pub mod standard;
+
+/// How a forward pipeline uses the depth buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForwardDepthMode {
+    /// Test and write depth while shading
+    Shade,
+    /// Depth-only prepass: vertex stage only, no color targets
+    Prepass,
+    /// Shade only the fragments whose depth the prepass wrote
+    ShadeEqual,
+}
@@ -1,7 +1,10 @@
+use super::ForwardDepthMode;
 use crate::core::render::cache::{PipelineKey, RenderCache, ShaderId};
 use crate::core::render::state::ResourceLibrary;
 use crate::core::resources::{SurfaceType, VertexStream};
 
+/// Forward Standard pipeline of a surface variant; `None` while it compiles on a worker
+/// unless `wait` is set
 pub fn get_pipeline<'a>(
     cache: &'a mut RenderCache,
     frame_index: u64,
@@ -9,7 +12,10 @@ pub fn get_pipeline<'a>(
     library: &ResourceLibrary,
     surface: SurfaceType,
     sample_count: u32,
-) -> &'a wgpu::RenderPipeline {
+    conservative: bool,
+    depth_mode: ForwardDepthMode,
+    wait: bool,
+) -> Option<&'a wgpu::RenderPipeline> {
     let (blend, depth_write, depth_compare, cull_mode) = match surface {
         SurfaceType::Transparent => (
             Some(wgpu::BlendState::ALPHA_BLENDING),
@@ -19,15 +25,36 @@ pub fn get_pipeline<'a>(
         ),
         _ => (
             None,
-            true,
-            wgpu::CompareFunction::Greater, // Reverse Z
+            depth_mode != ForwardDepthMode::ShadeEqual,
+            if depth_mode == ForwardDepthMode::ShadeEqual {
+                wgpu::CompareFunction::Equal
+            } else {
+                wgpu::CompareFunction::Greater // Reverse Z
+            },
             Some(wgpu::Face::Back),
         ),
     };
+    let color_target_count = if depth_mode == ForwardDepthMode::Prepass {
+        0
+    } else {
+        2
+    };
+    // Transparent surfaces read the opaque depth for `soft_depth_fade`
+    let (layout, fragment_entry) = match (surface, sample_count > 1) {
+        (SurfaceType::Transparent, false) => (
+            &library.forward_standard_transparent_pipeline_layout,
+            "fs_transparent",
+        ),
+        (SurfaceType::Transparent, true) => (
+            &library.forward_standard_transparent_msaa_pipeline_layout,
+            "fs_transparent_msaa",
+        ),
+        _ => (&library.forward_standard_pipeline_layout, "fs_main"),
+    };
     let key = PipelineKey {
         shader_id: ShaderId::ForwardStandard as u64,
         color_format: wgpu::TextureFormat::Rgba16Float,
-        color_target_count: 2,
+        color_target_count,
         depth_format: Some(wgpu::TextureFormat::Depth32Float), // Reverse Z
         sample_count,
         topology: wgpu::PrimitiveTopology::TriangleList,
@@ -36,14 +63,30 @@ pub fn get_pipeline<'a>(
         depth_write_enabled: depth_write,
         depth_compare,
         blend,
+        conservative,
     };
 
-    cache.get_or_create(key, frame_index, || {
+    let device = device.clone();
+    let layout = layout.clone();
+    let shader = library.forward_standard_shader.clone();
+    cache.get_or_compile(key, frame_index, wait, move || {
+        let color_targets = [
+            Some(wgpu::ColorTargetState {
+                format: key.color_format,
+                blend: key.blend,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+            Some(wgpu::ColorTargetState {
+                format: wgpu::TextureFormat::Rgba16Float,
+                blend: key.blend,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ];
         device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
             label: Some("Forward Standard Pipeline"),
-            layout: Some(&library.forward_standard_pipeline_layout),
+            layout: Some(&layout),
             vertex: wgpu::VertexState {
-                module: &library.forward_standard_shader,
+                module: &shader,
                 entry_point: Some("vs_main"),
                 buffers: &[
                     // 0: Position
@@ -129,21 +172,11 @@ pub fn get_pipeline<'a>(
                 ],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
             },
-            fragment: Some(wgpu::FragmentState {
-                module: &library.forward_standard_shader,
-                entry_point: Some("fs_main"),
-                targets: &[
-                    Some(wgpu::ColorTargetState {
-                        format: key.color_format,
-                        blend: key.blend,
-                        write_mask: wgpu::ColorWrites::ALL,
-                    }),
-                    Some(wgpu::ColorTargetState {
-                        format: wgpu::TextureFormat::Rgba16Float,
-                        blend: key.blend,
-                        write_mask: wgpu::ColorWrites::ALL,
-                    }),
-                ],
+            // The depth prepass runs the vertex stage only
+            fragment: (key.color_target_count > 0).then(|| wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some(fragment_entry),
+                targets: &color_targets,
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
             }),
             primitive: wgpu::PrimitiveState {
@@ -153,7 +186,7 @@ pub fn get_pipeline<'a>(
                 cull_mode: key.cull_mode,
                 unclipped_depth: false,
                 polygon_mode: wgpu::PolygonMode::Fill,
-                conservative: false,
+                conservative: key.conservative,
             },
             depth_stencil: key.depth_format.map(|format| wgpu::DepthStencilState {
                 format,
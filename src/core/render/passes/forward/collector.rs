@@ -6,6 +6,7 @@ pub(crate) fn collect_objects(
     scene: &crate::core::render::state::RenderScene,
     collector: &mut crate::core::render::state::DrawCollector,
     camera_record: &CameraRecord,
+    layer_mask: u32,
     vertex_sys: &crate::core::resources::VertexAllocatorSystem,
 ) -> u32 {
     let materials_standard = &scene.materials_standard;
@@ -15,7 +16,7 @@ pub(crate) fn collect_objects(
     let mut instance_cursor = 0;
 
     for (model_id, model_record) in &scene.models {
-        if (model_record.layer_mask & camera_record.layer_mask) == 0 {
+        if (model_record.layer_mask & layer_mask) == 0 {
             continue;
         }
 
@@ -31,6 +32,7 @@ pub(crate) fn collect_objects(
         if let Some(aabb) = vertex_sys.aabb(model_record.geometry_id) {
             let world_aabb = aabb.transform(&model_record.data.transform);
             if !frustum.intersects_aabb(world_aabb.min, world_aabb.max) {
+                collector.stats.models_culled += 1;
                 continue;
             }
         }
@@ -51,6 +53,7 @@ pub(crate) fn collect_objects(
                 model_id: *model_id,
                 geometry_id: model_record.geometry_id,
                 material_id,
+                sort_order: model_record.sort_order,
                 depth: model_depth,
                 instance_idx: 0,
             };
@@ -76,6 +79,7 @@ pub(crate) fn collect_objects(
             model_id: *model_id,
             geometry_id: model_record.geometry_id,
             material_id,
+            sort_order: model_record.sort_order,
             depth: model_depth,
             instance_idx: 0,
         };
@@ -113,29 +117,35 @@ pub(crate) fn collect_objects(
 }
 
 fn sort_collector(collector: &mut crate::core::render::state::DrawCollector) {
+    // `sort_order` is the primary key inside every bucket, so a higher value always
+    // draws later no matter how the model map happens to iterate.
     collector
         .pbr_opaque
-        .sort_by_key(|a| (a.material_id, a.geometry_id));
+        .sort_by_key(|a| (a.sort_order, a.material_id, a.geometry_id));
     collector
         .standard_opaque
-        .sort_by_key(|a| (a.material_id, a.geometry_id));
+        .sort_by_key(|a| (a.sort_order, a.material_id, a.geometry_id));
     collector
         .pbr_masked
-        .sort_by_key(|a| (a.material_id, a.geometry_id));
+        .sort_by_key(|a| (a.sort_order, a.material_id, a.geometry_id));
     collector
         .standard_masked
-        .sort_by_key(|a| (a.material_id, a.geometry_id));
+        .sort_by_key(|a| (a.sort_order, a.material_id, a.geometry_id));
 
     // Sort Far-to-Near (Painter's Algorithm)
     // With Reverse Z: Far is 0.0, Near is 1.0. So we sort Ascending.
     collector.standard_transparent.sort_by(|a, b| {
-        a.depth
-            .partial_cmp(&b.depth)
-            .unwrap_or(std::cmp::Ordering::Equal)
+        a.sort_order.cmp(&b.sort_order).then(
+            a.depth
+                .partial_cmp(&b.depth)
+                .unwrap_or(std::cmp::Ordering::Equal),
+        )
     });
     collector.pbr_transparent.sort_by(|a, b| {
-        a.depth
-            .partial_cmp(&b.depth)
-            .unwrap_or(std::cmp::Ordering::Equal)
+        a.sort_order.cmp(&b.sort_order).then(
+            a.depth
+                .partial_cmp(&b.depth)
+                .unwrap_or(std::cmp::Ordering::Equal),
+        )
     });
 }
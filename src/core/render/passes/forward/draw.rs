@@ -1,6 +1,61 @@
-use super::branches;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::branches::{self, ForwardDepthMode};
+use crate::core::render::state::{DrawStats, IndirectDrawSystem};
 use crate::core::resources::SurfaceType;
 
+/// Set once the unsupported-feature warning for `conservativeRaster` has been logged
+static CONSERVATIVE_UNSUPPORTED_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Draw groups in submission order: opaque, masked, then transparent
+pub(crate) fn draw_groups(
+    collector: &crate::core::render::state::DrawCollector,
+) -> [(&[crate::core::render::state::DrawItem], SurfaceType, bool); 6] {
+    [
+        (&collector.pbr_opaque, SurfaceType::Opaque, true),
+        (&collector.pbr_masked, SurfaceType::Masked, true),
+        (&collector.standard_opaque, SurfaceType::Opaque, false),
+        (&collector.standard_masked, SurfaceType::Masked, false),
+        (&collector.pbr_transparent, SurfaceType::Transparent, true),
+        (
+            &collector.standard_transparent,
+            SurfaceType::Transparent,
+            false,
+        ),
+    ]
+}
+
+/// Splits sorted items into runs sharing material and geometry (one draw each)
+pub(crate) fn batch_ranges(
+    items: &[crate::core::render::state::DrawItem],
+) -> impl Iterator<Item = std::ops::Range<usize>> + '_ {
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        if i >= items.len() {
+            return None;
+        }
+        let batch_start = i;
+        let mat_id = items[i].material_id;
+        let geom_id = items[i].geometry_id;
+        while i < items.len() && items[i].material_id == mat_id && items[i].geometry_id == geom_id {
+            i += 1;
+        }
+        Some(batch_start..i)
+    })
+}
+
+/// Instances one batch draws; the indirect templates start at the same `first_instance`
+pub(crate) fn batch_instances(
+    items: &[crate::core::render::state::DrawItem],
+    batch: &std::ops::Range<usize>,
+) -> std::ops::Range<u32> {
+    let first_instance = items[batch.start].instance_idx;
+    first_instance..(first_instance + batch.len() as u32)
+}
+
+/// Draws the opaque and masked groups, or only the transparent ones when `transparent` is set.
+/// `indirect` carries the args buffer and the next draw slot of this camera. With
+/// `depth_prepassed`, opaque groups only shade the fragments the prepass left in the depth buffer.
 pub(crate) fn draw_batches(
     render_pass: &mut wgpu::RenderPass,
     scene: &crate::core::render::state::RenderScene,
@@ -12,109 +67,99 @@ pub(crate) fn draw_batches(
     device: &wgpu::Device,
     cache: &mut crate::core::render::cache::RenderCache,
     sample_count: u32,
-) {
-    // 1. PBR Opaque
-    draw_group(
-        render_pass,
-        &collector.pbr_opaque,
-        SurfaceType::Opaque,
-        true, // is_pbr
-        scene,
-        bindings,
-        vertex_sys,
-        frame_index,
-        device,
-        cache,
-        library,
-        sample_count,
-    );
-
-    // 2. PBR Masked
-    draw_group(
-        render_pass,
-        &collector.pbr_masked,
-        SurfaceType::Masked,
-        true,
-        scene,
-        bindings,
-        vertex_sys,
-        frame_index,
-        device,
-        cache,
-        library,
-        sample_count,
-    );
-
-    // 3. Standard Opaque
-    draw_group(
-        render_pass,
-        &collector.standard_opaque,
-        SurfaceType::Opaque,
-        false, // is_pbr
-        scene,
-        bindings,
-        vertex_sys,
-        frame_index,
-        device,
-        cache,
-        library,
-        sample_count,
-    );
-
-    // 4. Standard Masked
-    draw_group(
-        render_pass,
-        &collector.standard_masked,
-        SurfaceType::Masked,
-        false,
-        scene,
-        bindings,
-        vertex_sys,
-        frame_index,
-        device,
-        cache,
-        library,
-        sample_count,
-    );
-
-    // 5. PBR Transparent
-    draw_group(
-        render_pass,
-        &collector.pbr_transparent,
-        SurfaceType::Transparent,
-        true,
-        scene,
-        bindings,
-        vertex_sys,
-        frame_index,
-        device,
-        cache,
-        library,
-        sample_count,
-    );
-
-    // 6. Standard Transparent
-    draw_group(
-        render_pass,
-        &collector.standard_transparent,
-        SurfaceType::Transparent,
-        false,
-        scene,
-        bindings,
-        vertex_sys,
-        frame_index,
-        device,
-        cache,
-        library,
-        sample_count,
-    );
+    indirect: &mut Option<(&wgpu::Buffer, u32)>,
+    transparent: bool,
+    depth_prepassed: bool,
+) -> DrawStats {
+    let mut stats = DrawStats::default();
+
+    for (items, surface_type, is_pbr) in draw_groups(collector) {
+        if (surface_type == SurfaceType::Transparent) != transparent {
+            continue;
+        }
+        let depth_mode = if depth_prepassed && surface_type == SurfaceType::Opaque {
+            ForwardDepthMode::ShadeEqual
+        } else {
+            ForwardDepthMode::Shade
+        };
+        draw_group(
+            &mut stats,
+            render_pass,
+            items,
+            surface_type,
+            is_pbr,
+            depth_mode,
+            scene,
+            bindings,
+            vertex_sys,
+            frame_index,
+            device,
+            cache,
+            library,
+            sample_count,
+            indirect,
+        );
+    }
+
+    stats
+}
+
+/// Writes the depth of the opaque groups without shading them. Masked groups keep their
+/// indirect slots untouched but still consume them, so slot numbering matches `draw_batches`.
+/// Takes the camera's first indirect slot by value and leaves the caller's counter alone.
+pub(crate) fn draw_depth_prepass(
+    render_pass: &mut wgpu::RenderPass,
+    scene: &crate::core::render::state::RenderScene,
+    library: &crate::core::render::state::ResourceLibrary,
+    collector: &crate::core::render::state::DrawCollector,
+    bindings: &crate::core::render::state::BindingSystem,
+    vertex_sys: &mut crate::core::resources::VertexAllocatorSystem,
+    frame_index: u64,
+    device: &wgpu::Device,
+    cache: &mut crate::core::render::cache::RenderCache,
+    sample_count: u32,
+    mut indirect: Option<(&wgpu::Buffer, u32)>,
+) -> DrawStats {
+    let mut stats = DrawStats::default();
+
+    for (items, surface_type, is_pbr) in draw_groups(collector) {
+        match surface_type {
+            SurfaceType::Opaque => draw_group(
+                &mut stats,
+                render_pass,
+                items,
+                surface_type,
+                is_pbr,
+                ForwardDepthMode::Prepass,
+                scene,
+                bindings,
+                vertex_sys,
+                frame_index,
+                device,
+                cache,
+                library,
+                sample_count,
+                &mut indirect,
+            ),
+            SurfaceType::Masked => {
+                if let Some((_, next_draw)) = indirect.as_mut() {
+                    *next_draw += batch_ranges(items).count() as u32;
+                }
+            }
+            SurfaceType::Transparent => {}
+        }
+    }
+
+    stats
 }
 
 fn draw_group(
+    stats: &mut DrawStats,
     render_pass: &mut wgpu::RenderPass,
     items: &[crate::core::render::state::DrawItem],
     surface_type: SurfaceType,
     is_pbr: bool,
+    depth_mode: ForwardDepthMode,
     scene: &crate::core::render::state::RenderScene,
     bindings: &crate::core::render::state::BindingSystem,
     vertex_sys: &mut crate::core::resources::VertexAllocatorSystem,
@@ -123,54 +168,99 @@ fn draw_group(
     cache: &mut crate::core::render::cache::RenderCache,
     library: &crate::core::render::state::ResourceLibrary,
     sample_count: u32,
+    indirect: &mut Option<(&wgpu::Buffer, u32)>,
 ) {
     if items.is_empty() {
         return;
     }
 
-    let pipeline = if is_pbr {
-        branches::pbr::get_pipeline(
-            cache,
-            frame_index,
-            device,
-            library,
-            surface_type,
-            sample_count,
-        )
-    } else {
-        branches::standard::get_pipeline(
-            cache,
-            frame_index,
-            device,
-            library,
-            surface_type,
-            sample_count,
-        )
-    };
-    render_pass.set_pipeline(pipeline);
+    let conservative_supported = device
+        .features()
+        .contains(wgpu::Features::CONSERVATIVE_RASTERIZATION);
+    let mut bound_conservative = None;
+    // Batches are skipped while their pipeline compiles on a worker
+    let mut pipeline_ready = false;
 
-    let mut i = 0;
-    while i < items.len() {
-        let batch_start = i;
-        let item = &items[i];
-        let mat_id = item.material_id;
-        let geom_id = item.geometry_id;
+    for batch in batch_ranges(items) {
+        let batch_start = batch.start;
+        let mat_id = items[batch_start].material_id;
+        let geom_id = items[batch_start].geometry_id;
+        let batch_count = batch.len() as u32;
 
-        while i < items.len() && items[i].material_id == mat_id && items[i].geometry_id == geom_id {
-            i += 1;
+        let wants_conservative = if is_pbr {
+            scene
+                .materials_pbr
+                .get(&mat_id)
+                .is_some_and(|material| material.conservative_raster)
+        } else {
+            scene
+                .materials_standard
+                .get(&mat_id)
+                .is_some_and(|material| material.conservative_raster)
+        };
+        if wants_conservative
+            && !conservative_supported
+            && !CONSERVATIVE_UNSUPPORTED_WARNED.swap(true, Ordering::Relaxed)
+        {
+            log::warn!("Conservative rasterization is not supported; `conservativeRaster` ignored");
+        }
+        let conservative = wants_conservative && conservative_supported;
+        if bound_conservative != Some(conservative) {
+            let pipeline = if is_pbr {
+                branches::pbr::get_pipeline(
+                    cache,
+                    frame_index,
+                    device,
+                    library,
+                    surface_type,
+                    sample_count,
+                    conservative,
+                    depth_mode,
+                    false,
+                )
+            } else {
+                branches::standard::get_pipeline(
+                    cache,
+                    frame_index,
+                    device,
+                    library,
+                    surface_type,
+                    sample_count,
+                    conservative,
+                    depth_mode,
+                    false,
+                )
+            };
+            if let Some(pipeline) = pipeline {
+                render_pass.set_pipeline(pipeline);
+            }
+            pipeline_ready = pipeline.is_some();
+            bound_conservative = Some(conservative);
+        }
+
+        // Every batch owns an args slot, drawn or not, to stay in step with the templates
+        let indirect_draw = indirect.as_mut().map(|(buffer, next_draw)| {
+            let draw_index = *next_draw;
+            *next_draw += 1;
+            (*buffer, draw_index)
+        });
+        if !pipeline_ready {
+            continue;
         }
-        let batch_count = (i - batch_start) as u32;
 
+        // Bindless materials share one group, so only the dynamic offset changes between them
         if is_pbr {
             if let Some(material) = scene.materials_pbr.get(&mat_id) {
-                if let Some(group) = material.bind_group.as_ref() {
+                let group = bindings.material_pbr_group.as_ref();
+                if let Some(group) = group.or(material.bind_group.as_ref()) {
                     let material_offset = bindings.material_pbr_pool.get_offset(mat_id) as u32;
                     render_pass.set_bind_group(1, group, &[material_offset]);
                 }
             }
         } else {
             if let Some(material) = scene.materials_standard.get(&mat_id) {
-                if let Some(group) = material.bind_group.as_ref() {
+                let group = bindings.material_standard_group.as_ref();
+                if let Some(group) = group.or(material.bind_group.as_ref()) {
                     let material_offset = bindings.material_standard_pool.get_offset(mat_id) as u32;
                     render_pass.set_bind_group(1, group, &[material_offset]);
                 }
@@ -179,12 +269,17 @@ fn draw_group(
 
         if let Ok(Some(index_info)) = vertex_sys.index_info(geom_id) {
             if vertex_sys.bind(render_pass, geom_id).is_ok() {
-                let first_instance = items[batch_start].instance_idx;
-                render_pass.draw_indexed(
-                    0..index_info.count,
-                    0,
-                    first_instance..(first_instance + batch_count),
-                );
+                if let Some((buffer, draw_index)) = indirect_draw {
+                    render_pass
+                        .draw_indexed_indirect(buffer, IndirectDrawSystem::args_offset(draw_index));
+                } else {
+                    render_pass.draw_indexed(
+                        0..index_info.count,
+                        0,
+                        batch_instances(items, &batch),
+                    );
+                }
+                stats.add_draw(index_info.count, batch_count);
             }
         }
     }
@@ -0,0 +1,218 @@
+use glam::{Vec3, Vec4};
+
+use super::draw::{batch_instances, batch_ranges, draw_groups};
+use crate::core::render::cache::{ComputePipelineKey, RenderCache, ShaderId};
+use crate::core::render::passes::light_cull::extract_frustum_planes;
+use crate::core::render::state::indirect::{IndirectCullParams, IndirectDrawArgs};
+use crate::core::render::state::{
+    DrawCollector, DrawItem, IndirectDrawSystem, IndirectDrawTemplate, RenderScene, ResourceLibrary,
+};
+use crate::core::resources::{CameraRecord, VertexAllocatorSystem};
+
+const INDIRECT_CULL_WORKGROUP_SIZE: u32 = 64;
+
+/// Draw slots written for one camera
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct IndirectDrawRange {
+    pub camera_index: u32,
+    pub first_draw: u32,
+    pub draw_count: u32,
+}
+
+/// Writes one template per forward batch of this camera, plus its frustum and cull params
+pub(crate) fn prepare_indirect_draws(
+    indirect: &mut IndirectDrawSystem,
+    scene: &RenderScene,
+    collector: &DrawCollector,
+    vertex_sys: &VertexAllocatorSystem,
+    camera_index: u32,
+    camera_record: &CameraRecord,
+) -> IndirectDrawRange {
+    let templates = build_templates(scene, collector, vertex_sys);
+    let range = IndirectDrawRange {
+        camera_index,
+        first_draw: indirect.draw_cursor,
+        draw_count: templates.len() as u32,
+    };
+    if range.draw_count == 0 {
+        return range;
+    }
+    indirect.draw_cursor += range.draw_count;
+
+    let planes = extract_frustum_planes(camera_record.data.view_projection);
+    indirect
+        .frustums
+        .write_slice(camera_index * planes.len() as u32, &planes);
+    indirect.templates.write_slice(range.first_draw, &templates);
+    indirect.args.reserve(range.first_draw + range.draw_count);
+    indirect.params.write(
+        camera_index,
+        &IndirectCullParams {
+            first_draw: range.first_draw,
+            draw_count: range.draw_count,
+            camera_index,
+            _padding: 0,
+        },
+    );
+    range
+}
+
+/// Records the compute cull that turns the camera's templates into indirect args
+pub(crate) fn dispatch_indirect_cull(
+    indirect: &mut IndirectDrawSystem,
+    device: &wgpu::Device,
+    library: &ResourceLibrary,
+    cache: &mut RenderCache,
+    encoder: &mut wgpu::CommandEncoder,
+    frame_index: u64,
+    range: IndirectDrawRange,
+) {
+    if range.draw_count == 0 {
+        return;
+    }
+
+    let versions = indirect.versions();
+    if indirect.bind_group.is_none() || indirect.bound_versions != versions {
+        indirect.bind_group = Some(build_bind_group(device, library, indirect));
+        indirect.bound_versions = versions;
+    }
+
+    let key = ComputePipelineKey {
+        shader_id: ShaderId::IndirectCull as u64,
+    };
+    let pipeline = cache.get_or_create_compute(key, frame_index, || {
+        device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Indirect Cull Pipeline"),
+            layout: Some(&library.indirect_cull_pipeline_layout),
+            module: &library.indirect_cull_shader,
+            entry_point: Some("cs_main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        })
+    });
+
+    let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: Some("Indirect Cull Pass"),
+        timestamp_writes: None,
+    });
+    cpass.set_pipeline(pipeline);
+    if let Some(group) = indirect.bind_group.as_ref() {
+        let params_offset = indirect.params.get_offset(range.camera_index) as u32;
+        cpass.set_bind_group(0, group, &[params_offset]);
+    }
+    cpass.dispatch_workgroups(
+        range.draw_count.div_ceil(INDIRECT_CULL_WORKGROUP_SIZE),
+        1,
+        1,
+    );
+}
+
+/// Same groups and batches, in the same order, as `draw::draw_batches`
+fn build_templates(
+    scene: &RenderScene,
+    collector: &DrawCollector,
+    vertex_sys: &VertexAllocatorSystem,
+) -> Vec<IndirectDrawTemplate> {
+    let mut templates = Vec::new();
+
+    for (items, _, _) in draw_groups(collector) {
+        for batch in batch_ranges(items) {
+            let first = &items[batch.start];
+            let index_count = vertex_sys
+                .index_info(first.geometry_id)
+                .ok()
+                .flatten()
+                .map(|info| info.count)
+                .unwrap_or(0);
+
+            let mut min = Vec3::splat(f32::INFINITY);
+            let mut max = Vec3::splat(f32::NEG_INFINITY);
+            let mut bounded = true;
+            for item in &items[batch.clone()] {
+                match (
+                    vertex_sys.aabb(item.geometry_id),
+                    scene.models.get(&item.model_id),
+                ) {
+                    (Some(aabb), Some(model)) => {
+                        let world = aabb.transform(&model.data.transform);
+                        min = min.min(world.min);
+                        max = max.max(world.max);
+                    }
+                    _ => bounded = false,
+                }
+            }
+
+            let bounds = if bounded && min.is_finite() && max.is_finite() && min.cmple(max).all() {
+                let center = (min + max) * 0.5;
+                center.extend((max - center).length())
+            } else {
+                Vec4::new(0.0, 0.0, 0.0, -1.0)
+            };
+
+            templates.push(IndirectDrawTemplate {
+                args: batch_args(items, &batch, index_count),
+                _padding: [0; 3],
+                bounds,
+            });
+        }
+    }
+
+    templates
+}
+
+/// Args matching the `draw_indexed` call `draw::draw_group` makes for the batch.
+/// Every batch after the first has a non-zero `first_instance`, which needs
+/// `INDIRECT_FIRST_INSTANCE`.
+fn batch_args(
+    items: &[DrawItem],
+    batch: &std::ops::Range<usize>,
+    index_count: u32,
+) -> IndirectDrawArgs {
+    let instances = batch_instances(items, batch);
+    IndirectDrawArgs {
+        index_count,
+        instance_count: instances.len() as u32,
+        first_index: 0,
+        base_vertex: 0,
+        first_instance: instances.start,
+    }
+}
+
+fn build_bind_group(
+    device: &wgpu::Device,
+    library: &ResourceLibrary,
+    indirect: &IndirectDrawSystem,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Indirect Cull Bind Group"),
+        layout: &library.layout_indirect_cull,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: indirect.templates.buffer().as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: indirect.args.buffer().as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: indirect.frustums.buffer().as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: indirect.params.buffer(),
+                    offset: 0,
+                    size: Some(
+                        std::num::NonZeroU64::new(std::mem::size_of::<IndirectCullParams>() as u64)
+                            .unwrap(),
+                    ),
+                }),
+            },
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests;
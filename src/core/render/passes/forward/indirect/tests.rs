@@ -0,0 +1,67 @@
+use super::super::draw::{batch_instances, batch_ranges, draw_groups};
+use super::batch_args;
+use crate::core::render::state::{DrawCollector, DrawItem};
+
+fn item(material_id: u32, geometry_id: u32, instance_idx: u32) -> DrawItem {
+    DrawItem {
+        model_id: instance_idx,
+        geometry_id,
+        material_id,
+        sort_order: 0,
+        depth: 0.0,
+        instance_idx,
+    }
+}
+
+/// Instance indices run across the groups in the order `collect_objects` assigns them
+fn collector() -> DrawCollector {
+    let mut collector = DrawCollector::default();
+    collector.pbr_opaque = vec![item(1, 1, 0), item(1, 1, 1), item(1, 2, 2), item(2, 2, 3)];
+    collector.standard_opaque = vec![item(3, 1, 4), item(3, 1, 5)];
+    collector.pbr_masked = vec![item(4, 3, 6)];
+    collector.pbr_transparent = vec![item(5, 1, 7), item(5, 1, 8), item(5, 1, 9)];
+    collector
+}
+
+#[test]
+fn indirect_args_match_direct_draws() {
+    let collector = collector();
+    let mut direct = Vec::new();
+    let mut indirect = Vec::new();
+
+    for (items, _, _) in draw_groups(&collector) {
+        for batch in batch_ranges(items) {
+            // What `draw_group` passes to `draw_indexed` for a geometry of 36 indices
+            direct.push((0..36u32, 0i32, batch_instances(items, &batch)));
+            let args = batch_args(items, &batch, 36);
+            indirect.push((
+                args.first_index..(args.first_index + args.index_count),
+                args.base_vertex,
+                args.first_instance..(args.first_instance + args.instance_count),
+            ));
+        }
+    }
+
+    assert_eq!(direct, indirect);
+    assert_eq!(indirect.len(), 6);
+    // Batches past the first start at a non-zero instance
+    assert!(
+        indirect[1..]
+            .iter()
+            .all(|(_, _, instances)| instances.start > 0)
+    );
+}
+
+#[test]
+fn batch_args_cover_every_instance_once() {
+    let collector = collector();
+    let mut covered = Vec::new();
+    for (items, _, _) in draw_groups(&collector) {
+        for batch in batch_ranges(items) {
+            let args = batch_args(items, &batch, 3);
+            covered.extend(args.first_instance..(args.first_instance + args.instance_count));
+        }
+    }
+    covered.sort_unstable();
+    assert_eq!(covered, (0..10).collect::<Vec<u32>>());
+}
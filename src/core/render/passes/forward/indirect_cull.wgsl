@@ -0,0 +1,59 @@
+struct DrawArgs {
+    index_count: u32,
+    instance_count: u32,
+    first_index: u32,
+    base_vertex: i32,
+    first_instance: u32,
+};
+
+struct DrawTemplate {
+    args: DrawArgs,
+    _padding0: u32,
+    _padding1: u32,
+    _padding2: u32,
+    bounds: vec4<f32>,
+};
+
+struct IndirectCullParams {
+    first_draw: u32,
+    draw_count: u32,
+    camera_index: u32,
+    _padding: u32,
+};
+
+@group(0) @binding(0) var<storage, read> templates: array<DrawTemplate>;
+@group(0) @binding(1) var<storage, read_write> draw_args: array<DrawArgs>;
+@group(0) @binding(2) var<storage, read> frustum_planes: array<vec4<f32>>;
+@group(0) @binding(3) var<uniform> params: IndirectCullParams;
+
+const PLANES_PER_CAMERA: u32 = 6u;
+
+fn batch_visible(bounds: vec4<f32>) -> bool {
+    if (bounds.w < 0.0) {
+        return true;
+    }
+
+    let base = params.camera_index * PLANES_PER_CAMERA;
+    for (var i = 0u; i < PLANES_PER_CAMERA; i = i + 1u) {
+        let plane = frustum_planes[base + i];
+        if (dot(plane.xyz, bounds.xyz) + plane.w < -bounds.w) {
+            return false;
+        }
+    }
+    return true;
+}
+
+@compute @workgroup_size(64)
+fn cs_main(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= params.draw_count) {
+        return;
+    }
+
+    let index = params.first_draw + gid.x;
+    let draw = templates[index];
+    var args = draw.args;
+    if (!batch_visible(draw.bounds)) {
+        args.instance_count = 0u;
+    }
+    draw_args[index] = args;
+}
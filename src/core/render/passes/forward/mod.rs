@@ -1,10 +1,17 @@
 mod branches;
 mod collector;
 mod draw;
+mod gizmos;
+mod indirect;
+mod warmup;
 
 use crate::core::render::RenderState;
 use crate::core::render::cache::{PipelineKey, ShaderId};
 
+use self::gizmos::draw_gizmos;
+
+pub use self::warmup::warmup_forward_pipelines;
+
 pub fn pass_forward(
     render_state: &mut RenderState,
     device: &wgpu::Device,
@@ -16,10 +23,17 @@ pub fn pass_forward(
     let scene = &render_state.scene;
 
     let sample_count = render_state.msaa_sample_count();
+    let clear_alpha = if render_state.transparent_background {
+        0.0
+    } else {
+        1.0
+    };
 
     // Split borrows
-    let (vertex_sys, bindings, library, light_system, collector, cache, gizmos) = (
-        render_state.vertex.as_mut().unwrap(),
+    let vertex_rc = render_state.vertex.clone().unwrap();
+    let mut vertex_guard = vertex_rc.borrow_mut();
+    let vertex_sys = &mut *vertex_guard;
+    let (bindings, library, light_system, collector, cache, gizmos) = (
         render_state.bindings.as_mut().unwrap(),
         render_state.library.as_ref().unwrap(),
         render_state.light_system.as_mut().unwrap(),
@@ -27,6 +41,9 @@ pub fn pass_forward(
         &mut render_state.cache,
         &mut render_state.gizmos,
     );
+    let mut indirect_system = render_state.indirect.as_mut();
+    let bindless = render_state.bindless.as_ref();
+    let layer_visibility = render_state.layer_visibility;
 
     // 0. Ensure Depth Target exists and matches size (Lazy)
     if let Some((_, camera)) = scene.cameras.iter().next() {
@@ -128,11 +145,29 @@ pub fn pass_forward(
             depth_write_enabled: false,
             depth_compare: wgpu::CompareFunction::Greater,
             blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+            conservative: false,
         })
     } else {
         None
     };
 
+    // Transparent surfaces sample the opaque depth while it stays attached read-only
+    let scene_depth_group = depth_target.map(|target| {
+        let (layout, binding) = if target.sample_count > 1 {
+            (&library.layout_scene_depth_msaa, 1)
+        } else {
+            (&library.layout_scene_depth, 0)
+        };
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Scene Depth Bind Group"),
+            layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding,
+                resource: wgpu::BindingResource::TextureView(&target.view),
+            }],
+        })
+    });
+
     // 1. Sort cameras by order
     let mut sorted_cameras: Vec<_> = scene.cameras.iter().collect();
     sorted_cameras.sort_by_key(|(_, record)| record.order);
@@ -164,9 +199,98 @@ pub fn pass_forward(
         collector.clear();
 
         // 3. Collection & Sorting
-        collector::collect_objects(scene, collector, camera_record, vertex_sys);
+        collector::collect_objects(
+            scene,
+            collector,
+            camera_record,
+            camera_record.layer_mask & layer_visibility,
+            vertex_sys,
+        );
+        collector.stats.visible_cameras += 1;
+
+        // 3b. GPU frustum cull of this camera's batches into indirect args
+        let indirect_first_draw = indirect_system.as_deref_mut().map(|indirect| {
+            let range = indirect::prepare_indirect_draws(
+                indirect,
+                scene,
+                collector,
+                vertex_sys,
+                camera_index as u32,
+                camera_record,
+            );
+            indirect::dispatch_indirect_cull(
+                indirect,
+                device,
+                library,
+                cache,
+                encoder,
+                frame_index,
+                range,
+            );
+            range.first_draw
+        });
+
+        let has_transparent =
+            !collector.pbr_transparent.is_empty() || !collector.standard_transparent.is_empty();
+        let transparent_depth_group = scene_depth_group.as_ref().filter(|_| has_transparent);
+        let mut indirect_draw = indirect_system
+            .as_deref()
+            .zip(indirect_first_draw)
+            .map(|(indirect, first_draw)| (indirect.args.buffer(), first_draw));
+        let camera_offset = bindings.camera_pool.get_offset(*camera_id) as u32;
+        let light_offset = light_system.draw_params_offset(camera_index as u32) as u32;
+
+        // Write instances
+        if !collector.instance_data.is_empty() {
+            bindings
+                .instance_pool
+                .write_slice(0, &collector.instance_data);
+        }
 
-        // 4. Begin render pass
+        // 4a. Depth prepass: opaque depth first, so the shading pass runs once per pixel
+        let depth_prepass = depth_target
+            .filter(|_| render_state.environment.depth_prepass)
+            .filter(|_| !collector.pbr_opaque.is_empty() || !collector.standard_opaque.is_empty());
+        if let Some(target) = depth_prepass {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(&format!("Forward Depth Prepass - Camera {}", camera_id)),
+                color_attachments: &[],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &target.view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(0.0), // Reverse Z
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            vertex_sys.begin_pass();
+
+            if let Some(shared_group) = bindings.shared_group() {
+                render_pass.set_bind_group(0, shared_group, &[camera_offset, light_offset]);
+            }
+            bind_bindless_groups(&mut render_pass, bindless, true);
+
+            let drawn = draw::draw_depth_prepass(
+                &mut render_pass,
+                scene,
+                library,
+                collector,
+                bindings,
+                vertex_sys,
+                frame_index,
+                device,
+                cache,
+                sample_count,
+                indirect_draw,
+            );
+            collector.stats.merge(&drawn);
+        }
+
+        // 4b. Opaque and masked pass
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some(&format!("Forward Pass - Camera {}", camera_id)),
@@ -175,12 +299,14 @@ pub fn pass_forward(
                         view: color_view,
                         resolve_target,
                         ops: wgpu::Operations {
-                            load: if clear_color {
+                            load: if camera_record.compose_blend.is_overlay() {
+                                wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT)
+                            } else if clear_color {
                                 wgpu::LoadOp::Clear(wgpu::Color {
                                     r: 0.0,
                                     g: 0.0,
                                     b: 0.0,
-                                    a: 1.0,
+                                    a: clear_alpha,
                                 })
                             } else {
                                 wgpu::LoadOp::Load
@@ -204,7 +330,11 @@ pub fn pass_forward(
                     wgpu::RenderPassDepthStencilAttachment {
                         view: &target.view,
                         depth_ops: Some(wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(0.0), // Reverse Z
+                            load: if depth_prepass.is_some() {
+                                wgpu::LoadOp::Load
+                            } else {
+                                wgpu::LoadOp::Clear(0.0) // Reverse Z
+                            },
                             store: wgpu::StoreOp::Store,
                         }),
                         stencil_ops: None,
@@ -217,21 +347,13 @@ pub fn pass_forward(
             vertex_sys.begin_pass();
 
             // 5. Bind Shared (Group 0: Frame + Camera + ModelPool)
-            if let Some(shared_group) = bindings.shared_group.as_ref() {
-                let camera_offset = bindings.camera_pool.get_offset(*camera_id) as u32;
-                let light_offset = light_system.draw_params_offset(camera_index as u32) as u32;
+            if let Some(shared_group) = bindings.shared_group() {
                 render_pass.set_bind_group(0, shared_group, &[camera_offset, light_offset]);
             }
-
-            // Write instances
-            if !collector.instance_data.is_empty() {
-                bindings
-                    .instance_pool
-                    .write_slice(0, &collector.instance_data);
-            }
+            bind_bindless_groups(&mut render_pass, bindless, true);
 
             // 6. Draw Batches
-            draw::draw_batches(
+            let drawn = draw::draw_batches(
                 &mut render_pass,
                 scene,
                 library,
@@ -242,76 +364,115 @@ pub fn pass_forward(
                 device,
                 cache,
                 sample_count,
+                &mut indirect_draw,
+                false,
+                depth_prepass.is_some(),
             );
+            collector.stats.merge(&drawn);
 
-            // 7. Draw Gizmos
-            if let Some(key) = gizmo_pipeline_key {
-                let pipeline = cache.get_or_create(key, frame_index, || {
-                    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                        label: Some("Gizmo Pipeline"),
-                        layout: Some(&library.gizmo_pipeline_layout),
-                        vertex: wgpu::VertexState {
-                            module: &library.gizmo_shader,
-                            entry_point: Some("vs_main"),
-                            buffers: &[wgpu::VertexBufferLayout {
-                                array_stride: std::mem::size_of::<
-                                    crate::core::render::gizmos::GizmoVertex,
-                                >() as u64,
-                                step_mode: wgpu::VertexStepMode::Vertex,
-                                attributes: &[
-                                    wgpu::VertexAttribute {
-                                        format: wgpu::VertexFormat::Float32x3,
-                                        offset: 0,
-                                        shader_location: 0,
-                                    },
-                                    wgpu::VertexAttribute {
-                                        format: wgpu::VertexFormat::Float32x4,
-                                        offset: 16,
-                                        shader_location: 1,
-                                    },
-                                ],
-                            }],
-                            compilation_options: wgpu::PipelineCompilationOptions::default(),
-                        },
-                        fragment: Some(wgpu::FragmentState {
-                            module: &library.gizmo_shader,
-                            entry_point: Some("fs_main"),
-                            targets: &[
-                                Some(wgpu::ColorTargetState {
-                                    format: wgpu::TextureFormat::Rgba16Float,
-                                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                                    write_mask: wgpu::ColorWrites::ALL,
-                                }),
-                                Some(wgpu::ColorTargetState {
-                                    format: wgpu::TextureFormat::Rgba16Float,
-                                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                                    write_mask: wgpu::ColorWrites::ALL,
-                                }),
-                            ],
-                            compilation_options: wgpu::PipelineCompilationOptions::default(),
-                        }),
-                        primitive: wgpu::PrimitiveState {
-                            topology: wgpu::PrimitiveTopology::LineList,
-                            ..Default::default()
+            // Gizmos go last, in the transparent pass when there is one
+            if let Some(key) = gizmo_pipeline_key.filter(|_| transparent_depth_group.is_none()) {
+                draw_gizmos(
+                    &mut render_pass,
+                    key,
+                    cache,
+                    device,
+                    library,
+                    gizmos,
+                    frame_index,
+                );
+            }
+        }
+
+        // 7. Transparent pass, with the depth attachment read-only so it can be sampled
+        if let Some(scene_depth_group) = transparent_depth_group {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(&format!("Forward Transparent Pass - Camera {}", camera_id)),
+                color_attachments: &[
+                    Some(wgpu::RenderPassColorAttachment {
+                        view: color_view,
+                        resolve_target,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
                         },
-                        depth_stencil: depth_target.map(|target| wgpu::DepthStencilState {
-                            format: target.format,
-                            depth_write_enabled: false,
-                            depth_compare: wgpu::CompareFunction::Greater,
-                            stencil: wgpu::StencilState::default(),
-                            bias: wgpu::DepthBiasState::default(),
-                        }),
-                        multisample: wgpu::MultisampleState {
-                            count: sample_count,
-                            ..Default::default()
+                        depth_slice: None,
+                    }),
+                    Some(wgpu::RenderPassColorAttachment {
+                        view: emissive_view,
+                        resolve_target: emissive_resolve,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
                         },
-                        multiview_mask: None,
-                        cache: None,
-                    })
-                });
-                render_pass.set_pipeline(pipeline);
-                gizmos.draw(&mut render_pass);
+                        depth_slice: None,
+                    }),
+                ],
+                depth_stencil_attachment: depth_target.map(|target| {
+                    wgpu::RenderPassDepthStencilAttachment {
+                        view: &target.view,
+                        depth_ops: None,
+                        stencil_ops: None,
+                    }
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            vertex_sys.begin_pass();
+
+            if let Some(shared_group) = bindings.shared_group() {
+                render_pass.set_bind_group(0, shared_group, &[camera_offset, light_offset]);
+            }
+            bind_bindless_groups(&mut render_pass, bindless, false);
+            render_pass.set_bind_group(2, scene_depth_group, &[]);
+
+            let drawn = draw::draw_batches(
+                &mut render_pass,
+                scene,
+                library,
+                collector,
+                bindings,
+                vertex_sys,
+                frame_index,
+                device,
+                cache,
+                sample_count,
+                &mut indirect_draw,
+                true,
+                false,
+            );
+            collector.stats.merge(&drawn);
+
+            if let Some(key) = gizmo_pipeline_key {
+                draw_gizmos(
+                    &mut render_pass,
+                    key,
+                    cache,
+                    device,
+                    library,
+                    gizmos,
+                    frame_index,
+                );
             }
         }
     }
 }
+
+/// Binds the global texture array of bindless pipelines at group 3.
+/// Opaque pipelines read no scene depth and take an empty group 2 instead.
+fn bind_bindless_groups(
+    render_pass: &mut wgpu::RenderPass,
+    bindless: Option<&crate::core::render::state::BindlessTextureSystem>,
+    opaque: bool,
+) {
+    let Some(bindless) = bindless else {
+        return;
+    };
+    if opaque {
+        render_pass.set_bind_group(2, &bindless.empty_group, &[]);
+    }
+    if let Some(textures_group) = bindless.bind_group.as_ref() {
+        render_pass.set_bind_group(3, textures_group, &[]);
+    }
+}
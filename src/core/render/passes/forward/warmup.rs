@@ -0,0 +1,48 @@
+use super::branches::{self, ForwardDepthMode};
+use crate::core::render::RenderState;
+use crate::core::resources::SurfaceType;
+
+/// Starts compiling every forward pipeline variant the current environment can draw with,
+/// so no material kind or surface type stalls the frame it first appears in. With `wait`
+/// the variants compile inline before returning. Returns how many are still compiling.
+pub fn warmup_forward_pipelines(
+    render_state: &mut RenderState,
+    device: &wgpu::Device,
+    frame_index: u64,
+    wait: bool,
+) -> usize {
+    let Some(library) = render_state.library.as_ref() else {
+        return 0;
+    };
+    let sample_count = render_state.msaa_sample_count();
+    let mut variants = vec![
+        (SurfaceType::Opaque, ForwardDepthMode::Shade),
+        (SurfaceType::Masked, ForwardDepthMode::Shade),
+        (SurfaceType::Transparent, ForwardDepthMode::Shade),
+    ];
+    if render_state.environment.depth_prepass {
+        variants.push((SurfaceType::Opaque, ForwardDepthMode::Prepass));
+        variants.push((SurfaceType::Opaque, ForwardDepthMode::ShadeEqual));
+    }
+
+    let cache = &mut render_state.cache;
+    for (surface, depth_mode) in variants {
+        for get_pipeline in [
+            branches::standard::get_pipeline,
+            branches::pbr::get_pipeline,
+        ] {
+            get_pipeline(
+                cache,
+                frame_index,
+                device,
+                library,
+                surface,
+                sample_count,
+                false,
+                depth_mode,
+                wait,
+            );
+        }
+    }
+    cache.pending_count()
+}
@@ -0,0 +1,145 @@
+struct Light {
+    position: vec4<f32>,
+    direction: vec4<f32>,
+    color: vec4<f32>,
+    ground_color: vec4<f32>,
+    view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    view_projection: mat4x4<f32>,
+    intensity_range: vec2<f32>,
+    spot_inner_outer: vec2<f32>,
+    kind_flags: vec2<u32>,
+    shadow_index: u32,
+    units: u32,
+    area_right: vec4<f32>,
+    area_size: vec2<f32>,
+    volumetric: f32,
+    _padding: f32,
+};
+
+struct Camera {
+    position: vec4<f32>,
+    direction: vec4<f32>,
+    up: vec4<f32>,
+    near_far: vec2<f32>,
+    kind_flags: vec2<u32>,
+    projection: mat4x4<f32>,
+    view: mat4x4<f32>,
+    view_projection: mat4x4<f32>,
+};
+
+struct LightClusterParams {
+    light_count: u32,
+    camera_count: u32,
+    max_lights_per_cluster: u32,
+    cluster_count_base: u32,
+    cluster_dims: vec4<u32>, // w: froxels per camera
+    cluster_index_base: u32,
+    _padding0: u32,
+    _padding1: u32,
+    _padding2: u32,
+};
+
+struct Aabb {
+    min: vec3<f32>,
+    max: vec3<f32>,
+};
+
+@group(0) @binding(0) var<storage, read> lights: array<Light>;
+@group(0) @binding(1) var<storage, read_write> visible_indices: array<u32>;
+@group(0) @binding(2) var<storage, read_write> visible_counts: array<u32>;
+@group(0) @binding(3) var<uniform> params: LightClusterParams;
+@group(0) @binding(4) var<storage, read> cameras: array<Camera>;
+
+// View depth where depth slice `slice` begins. Perspective slices grow exponentially so
+// froxels stay roughly cube-shaped; orthographic slices are even. Keep in sync with
+// `cluster_slice` in the forward shaders.
+fn slice_depth(camera: Camera, slice: u32) -> f32 {
+    let near = max(camera.near_far.x, 1e-4);
+    let far = max(camera.near_far.y, near + 1e-4);
+    let t = f32(slice) / f32(params.cluster_dims.z);
+    if (camera.kind_flags.x == 0u) {
+        return near * pow(far / near, t);
+    }
+    return mix(near, far, t);
+}
+
+// View-space point at NDC `ndc` and view depth `depth`
+fn view_point(camera: Camera, ndc: vec2<f32>, depth: f32) -> vec3<f32> {
+    let scale = vec2<f32>(camera.projection[0][0], camera.projection[1][1]);
+    if (camera.kind_flags.x == 0u) {
+        return vec3<f32>(ndc * depth / scale, -depth);
+    }
+    return vec3<f32>(ndc / scale, -depth);
+}
+
+fn cluster_bounds(camera: Camera, cell: vec3<u32>) -> Aabb {
+    let dims = vec2<f32>(params.cluster_dims.xy);
+    let ndc_min = vec2<f32>(cell.xy) / dims * 2.0 - 1.0;
+    let ndc_max = vec2<f32>(cell.xy + 1u) / dims * 2.0 - 1.0;
+    let depth_near = slice_depth(camera, cell.z);
+    let depth_far = slice_depth(camera, cell.z + 1u);
+
+    let a = view_point(camera, ndc_min, depth_near);
+    let b = view_point(camera, ndc_max, depth_near);
+    let c = view_point(camera, ndc_min, depth_far);
+    let d = view_point(camera, ndc_max, depth_far);
+
+    var bounds: Aabb;
+    bounds.min = min(min(a, b), min(c, d));
+    bounds.max = max(max(a, b), max(c, d));
+    return bounds;
+}
+
+fn light_in_cluster(light: Light, camera: Camera, bounds: Aabb) -> bool {
+    let kind = light.kind_flags.x;
+    if (kind == 0u || kind == 3u || kind == 4u) {
+        return true;
+    }
+
+    var radius = light.intensity_range.y;
+    if (kind == 5u) {
+        // Area light: range is measured from the rectangle, bound it by its half diagonal
+        radius = radius + 0.5 * length(light.area_size);
+    }
+    if (radius <= 0.0) {
+        return false;
+    }
+
+    let center = (camera.view * vec4<f32>(light.position.xyz, 1.0)).xyz;
+    let closest = clamp(center, bounds.min, bounds.max);
+    let offset = center - closest;
+    return dot(offset, offset) <= radius * radius;
+}
+
+@compute @workgroup_size(64)
+fn cs_main(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let clusters = params.cluster_dims.w;
+    let global_cluster = gid.x;
+    if (global_cluster >= clusters * params.camera_count) {
+        return;
+    }
+
+    let camera = cameras[global_cluster / clusters];
+    let cluster = global_cluster % clusters;
+    let dims = params.cluster_dims.xyz;
+    let cell = vec3<u32>(
+        cluster % dims.x,
+        (cluster / dims.x) % dims.y,
+        cluster / (dims.x * dims.y)
+    );
+    let bounds = cluster_bounds(camera, cell);
+
+    let base = params.cluster_index_base + global_cluster * params.max_lights_per_cluster;
+    var count = 0u;
+    for (var i = 0u; i < params.light_count; i = i + 1u) {
+        if (count >= params.max_lights_per_cluster) {
+            break;
+        }
+        if (light_in_cluster(lights[i], camera, bounds)) {
+            visible_indices[base + count] = i;
+            count = count + 1u;
+        }
+    }
+    visible_counts[params.cluster_count_base + global_cluster] = count;
+}
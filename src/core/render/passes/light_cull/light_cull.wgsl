@@ -10,7 +10,11 @@ struct Light {
     spot_inner_outer: vec2<f32>,
     kind_flags: vec2<u32>,
     shadow_index: u32,
-    _padding: u32,
+    units: u32,
+    area_right: vec4<f32>,
+    area_size: vec2<f32>,
+    volumetric: f32,
+    _padding: f32,
 };
 
 struct LightCullParams {
@@ -34,7 +38,11 @@ fn light_visible_for_camera(light: Light, camera_index: u32) -> bool {
         return true;
     }
 
-    let radius = light.intensity_range.y;
+    var radius = light.intensity_range.y;
+    if (kind == 5u) {
+        // Area light: range is measured from the rectangle, bound it by its half diagonal
+        radius = radius + 0.5 * length(light.area_size);
+    }
     if (radius <= 0.0) {
         return false;
     }
@@ -13,7 +13,21 @@ struct LightCullParams {
     _padding: u32,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct LightClusterParams {
+    light_count: u32,
+    camera_count: u32,
+    max_lights_per_cluster: u32,
+    cluster_count_base: u32,
+    /// Froxels across, down and in depth; `w` is the froxel count of one camera
+    cluster_dims: [u32; 4],
+    cluster_index_base: u32,
+    _padding: [u32; 3],
+}
+
 const LIGHT_CULL_WORKGROUP_SIZE: u32 = 64;
+const LIGHT_CLUSTER_WORKGROUP_SIZE: u32 = 64;
 const PLANES_PER_CAMERA: u32 = 6;
 
 fn normalize_plane(plane: glam::Vec4) -> glam::Vec4 {
@@ -22,7 +36,7 @@ fn normalize_plane(plane: glam::Vec4) -> glam::Vec4 {
     if len > 0.0 { plane / len } else { plane }
 }
 
-fn extract_frustum_planes(view_projection: glam::Mat4) -> [FrustumPlane; 6] {
+pub(crate) fn extract_frustum_planes(view_projection: glam::Mat4) -> [FrustumPlane; 6] {
     let m = view_projection.to_cols_array_2d();
     let row0 = glam::Vec4::new(m[0][0], m[1][0], m[2][0], m[3][0]);
     let row1 = glam::Vec4::new(m[0][1], m[1][1], m[2][1], m[3][1]);
@@ -109,6 +123,96 @@ fn build_light_cull_bind_group(
         ],
     })
 }
+fn build_light_cluster_bind_group(
+    device: &wgpu::Device,
+    library: &ResourceLibrary,
+    light_system: &LightCullingSystem,
+    params_buffer: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Light Cluster Bind Group"),
+        layout: &library.layout_light_cull,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: light_system.lights.buffer().as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: light_system.visible_indices.buffer().as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: light_system.visible_counts.buffer().as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: params_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 4,
+                resource: light_system.cluster_cameras.buffer().as_entire_binding(),
+            },
+        ],
+    })
+}
+
+/// Bins every light into the froxels of every camera. Each invocation owns one froxel and
+/// writes its list and count outright, so nothing has to be cleared between frames.
+fn dispatch_light_clusters(
+    light_system: &mut LightCullingSystem,
+    library: &ResourceLibrary,
+    cache: &mut crate::core::render::cache::RenderCache,
+    device: &wgpu::Device,
+    encoder: &mut wgpu::CommandEncoder,
+    frame_index: u64,
+) {
+    let Some(params_buffer) = light_system.cluster_params_buffer.as_ref() else {
+        return;
+    };
+
+    let dims = light_system.config.cluster_dims;
+    let clusters = light_system.config.clusters_per_camera();
+    let params = LightClusterParams {
+        light_count: light_system.light_count as u32,
+        camera_count: light_system.camera_count,
+        max_lights_per_cluster: light_system.config.max_lights_per_cluster,
+        cluster_count_base: light_system.cluster_count_base(),
+        cluster_dims: [dims.x, dims.y, dims.z, clusters],
+        cluster_index_base: light_system.cluster_index_base(),
+        _padding: [0; 3],
+    };
+    light_system
+        .queue
+        .write_buffer(params_buffer, 0, bytes_of(&params));
+
+    let key = ComputePipelineKey {
+        shader_id: ShaderId::LightCluster as u64,
+    };
+    let pipeline = cache.get_or_create_compute(key, frame_index, || {
+        device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Light Cluster Pipeline"),
+            layout: Some(&library.light_cull_pipeline_layout),
+            module: &library.light_cluster_shader,
+            entry_point: Some("cs_main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        })
+    });
+    let bind_group = build_light_cluster_bind_group(device, library, light_system, params_buffer);
+
+    let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: Some("Light Cluster Pass"),
+        timestamp_writes: None,
+    });
+    cpass.set_pipeline(pipeline);
+    cpass.set_bind_group(0, &bind_group, &[]);
+    let total = clusters * light_system.camera_count;
+    cpass.dispatch_workgroups(total.div_ceil(LIGHT_CLUSTER_WORKGROUP_SIZE), 1, 1);
+
+    light_system.clusters_ready = true;
+}
+
 pub fn pass_light_cull(
     render_state: &mut RenderState,
     device: &wgpu::Device,
@@ -132,6 +236,7 @@ pub fn pass_light_cull(
 
     light_system.camera_count = camera_count as u32;
     light_system.max_lights_per_camera = light_count;
+    light_system.clusters_ready = false;
 
     if light_count == 0 || camera_count == 0 {
         light_system.bind_group = None;
@@ -189,4 +294,7 @@ pub fn pass_light_cull(
 
     let dispatch_count = (light_count + LIGHT_CULL_WORKGROUP_SIZE - 1) / LIGHT_CULL_WORKGROUP_SIZE;
     cpass.dispatch_workgroups(dispatch_count, 1, 1);
+    drop(cpass);
+
+    dispatch_light_clusters(light_system, library, cache, device, encoder, frame_index);
 }
@@ -1,19 +1,29 @@
 mod bloom;
 mod compose;
+mod custom;
+mod debug;
+mod fog;
 mod forward;
 mod light_cull;
 mod outline;
+mod particles;
 mod post;
 mod shadow;
 mod skybox;
+mod sprites;
 mod ssao;
 
 pub use bloom::*;
 pub use compose::*;
+pub use custom::*;
+pub use debug::*;
+pub use fog::*;
 pub use forward::*;
 pub use light_cull::*;
 pub use outline::*;
+pub use particles::*;
 pub use post::*;
 pub use shadow::*;
 pub use skybox::*;
+pub use sprites::*;
 pub use ssao::*;
@@ -0,0 +1,181 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::core::render::RenderState;
+use crate::core::render::cache::{PipelineKey, ShaderId};
+use crate::core::resources::{CameraRecord, PostProcessConfig};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct OutlineEdgeUniform {
+    inv_proj: [[f32; 4]; 4],
+    color: [f32; 4],
+    params0: [f32; 4],
+}
+
+fn update_edge_uniform(
+    record: &CameraRecord,
+    config: &PostProcessConfig,
+    buffer: &wgpu::Buffer,
+    queue: &wgpu::Queue,
+) {
+    let size = record
+        .render_target
+        .as_ref()
+        .map(|target| target._texture.size())
+        .unwrap_or(wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        });
+
+    let uniform = OutlineEdgeUniform {
+        inv_proj: record.data.projection.inverse().to_cols_array_2d(),
+        color: config.outline_edge_color.extend(1.0).to_array(),
+        params0: [
+            1.0 / size.width.max(1) as f32,
+            1.0 / size.height.max(1) as f32,
+            config.outline_width,
+            0.0,
+        ],
+    };
+
+    queue.write_buffer(buffer, 0, bytemuck::bytes_of(&uniform));
+}
+
+/// `OutlineMode::EdgeDetect`: fills each camera's outline target from the forward depth
+/// instead of drawing hulls. Alpha carries the edge strength thresholded by `post`.
+pub(super) fn pass_outline_edge(
+    render_state: &mut RenderState,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    encoder: &mut wgpu::CommandEncoder,
+    frame_index: u64,
+) {
+    let post_config = &render_state.environment.post;
+    if !post_config.outline_enabled {
+        return;
+    }
+    let library = match render_state.library.as_ref() {
+        Some(lib) => lib,
+        None => return,
+    };
+    let depth_target = match render_state.forward_depth_target.as_ref() {
+        Some(target) => target,
+        None => return,
+    };
+    let edge_buffer = match render_state.outline_uniform_buffer.as_ref() {
+        Some(buffer) => buffer,
+        None => return,
+    };
+
+    let (shader_id, label, shader, pipeline_layout, bind_group_layout) =
+        if depth_target.sample_count > 1 {
+            (
+                ShaderId::OutlineEdgeMsaa,
+                "Outline Edge MSAA Pipeline",
+                &library.outline_edge_msaa_shader,
+                &library.ssao_msaa_pipeline_layout,
+                &library.layout_ssao_msaa,
+            )
+        } else {
+            (
+                ShaderId::OutlineEdge,
+                "Outline Edge Pipeline",
+                &library.outline_edge_shader,
+                &library.ssao_pipeline_layout,
+                &library.layout_ssao,
+            )
+        };
+
+    let mut sorted_cameras: Vec<_> = render_state.scene.cameras.iter().collect();
+    sorted_cameras.sort_by_key(|(_, record)| record.order);
+
+    for (camera_id, record) in sorted_cameras {
+        let target = match &record.outline_target {
+            Some(t) => t,
+            None => continue,
+        };
+
+        update_edge_uniform(record, post_config, edge_buffer, queue);
+
+        let key = PipelineKey {
+            shader_id: shader_id as u64,
+            color_format: target.format,
+            color_target_count: 1,
+            depth_format: None,
+            sample_count: 1,
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            front_face: wgpu::FrontFace::Ccw,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            blend: None,
+            conservative: false,
+        };
+        let pipeline = render_state.cache.get_or_create(key, frame_index, || {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: target.format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: None,
+            })
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Outline Edge Bind Group"),
+            layout: bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&depth_target.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: edge_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&format!("Outline Edge Pass - Camera {}", camera_id)),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &target.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+
+        let size = target._texture.size();
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_viewport(0.0, 0.0, size.width as f32, size.height as f32, 0.0, 1.0);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
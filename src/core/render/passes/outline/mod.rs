@@ -1,16 +1,24 @@
+mod edge;
+
 use crate::core::render::RenderState;
 use crate::core::render::cache::{PipelineKey, ShaderId};
-use crate::core::resources::VertexStream;
 use crate::core::resources::geometry::Frustum;
+use crate::core::resources::{OutlineMode, VertexStream};
 
 pub fn pass_outline(
     render_state: &mut RenderState,
     device: &wgpu::Device,
-    _queue: &wgpu::Queue,
+    queue: &wgpu::Queue,
     encoder: &mut wgpu::CommandEncoder,
     frame_index: u64,
 ) {
+    if render_state.environment.post.outline_mode == OutlineMode::EdgeDetect {
+        edge::pass_outline_edge(render_state, device, queue, encoder, frame_index);
+        return;
+    }
+
     let scene = &render_state.scene;
+    let layer_visibility = render_state.layer_visibility;
     if scene.cameras.is_empty() {
         return;
     }
@@ -20,19 +28,21 @@ pub fn pass_outline(
         None => return,
     };
 
-    let (bindings, vertex_sys, light_system, collector) = match (
+    let (bindings, vertex_rc, light_system, collector) = match (
         render_state.bindings.as_mut(),
-        render_state.vertex.as_mut(),
+        render_state.vertex.clone(),
         render_state.light_system.as_mut(),
     ) {
-        (Some(bindings), Some(vertex_sys), Some(light_system)) => (
+        (Some(bindings), Some(vertex_rc), Some(light_system)) => (
             bindings,
-            vertex_sys,
+            vertex_rc,
             light_system,
             &mut render_state.collector,
         ),
         _ => return,
     };
+    let mut vertex_guard = vertex_rc.borrow_mut();
+    let vertex_sys = &mut *vertex_guard;
 
     let mut sorted_cameras: Vec<_> = scene.cameras.iter().collect();
     sorted_cameras.sort_by_key(|(_, record)| record.order);
@@ -54,7 +64,7 @@ pub fn pass_outline(
             if !model_record.cast_outline {
                 continue;
             }
-            if (model_record.layer_mask & camera_record.layer_mask) == 0 {
+            if (model_record.layer_mask & camera_record.layer_mask & layer_visibility) == 0 {
                 continue;
             }
             if vertex_sys
@@ -101,6 +111,7 @@ pub fn pass_outline(
             depth_write_enabled: false,
             depth_compare: wgpu::CompareFunction::Always,
             blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+            conservative: false,
         };
 
         let pipeline = render_state.cache.get_or_create(key, frame_index, || {
@@ -226,7 +237,7 @@ pub fn pass_outline(
             multiview_mask: None,
         });
 
-        if let Some(shared_group) = bindings.shared_group.as_ref() {
+        if let Some(shared_group) = bindings.shared_group() {
             let camera_offset = bindings.camera_pool.get_offset(*camera_id) as u32;
             let light_offset = light_system.draw_params_offset(camera_index as u32) as u32;
             render_pass.set_bind_group(0, shared_group, &[camera_offset, light_offset]);
@@ -257,6 +268,7 @@ pub fn pass_outline(
                         0,
                         first_instance..(first_instance + batch_count),
                     );
+                    collector.stats.add_draw(index_info.count, batch_count);
                 }
             }
         }
@@ -0,0 +1,82 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+struct EdgeParams {
+    inv_proj: mat4x4<f32>,
+    color: vec4<f32>,
+    params0: vec4<f32>, // texel.x, texel.y, width, unused
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32((i32(vertex_index) << 1) & 2) * 2.0 - 1.0;
+    let y = f32(i32(vertex_index) & 2) * -2.0 + 1.0;
+    out.position = vec4<f32>(x, y, 0.0, 1.0);
+    out.uv = vec2<f32>(x * 0.5 + 0.5, 1.0 - (y * 0.5 + 0.5));
+    return out;
+}
+
+@group(0) @binding(0) var t_depth: texture_depth_2d;
+@group(0) @binding(1) var<uniform> edge: EdgeParams;
+
+fn depth_at(uv: vec2<f32>) -> f32 {
+    let dims = vec2<f32>(textureDimensions(t_depth));
+    let coord = clamp(uv * dims, vec2<f32>(0.0), dims - vec2<f32>(1.0));
+    return textureLoad(t_depth, vec2<i32>(coord), 0);
+}
+
+fn view_pos_from_depth(uv: vec2<f32>, depth: f32) -> vec3<f32> {
+    let ndc = vec4<f32>(uv * 2.0 - 1.0, depth, 1.0);
+    let view = edge.inv_proj * ndc;
+    return view.xyz / view.w;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let step = edge.params0.xy * clamp(edge.params0.z, 0.5, 8.0);
+
+    // 3x3 neighbourhood, row-major from the top-left
+    var pos: array<vec3<f32>, 9>;
+    var covered = 0u;
+    for (var i: i32 = 0; i < 9; i = i + 1) {
+        let offset = vec2<f32>(f32(i % 3 - 1), f32(i / 3 - 1)) * step;
+        let uv = in.uv + offset;
+        let depth = depth_at(uv);
+        // Reverse Z: 0 is the cleared far plane
+        if (depth > 0.0) {
+            covered = covered + 1u;
+        }
+        pos[i] = view_pos_from_depth(uv, depth);
+    }
+
+    if (covered == 0u) {
+        return vec4<f32>(edge.color.rgb, 0.0);
+    }
+    if (covered < 9u) {
+        // Silhouette against the background
+        return vec4<f32>(edge.color.rgb, 1.0);
+    }
+
+    // Sobel on linear depth, relative to the centre so distant edges keep their weight
+    let center = pos[4];
+    let gx = (pos[2].z + 2.0 * pos[5].z + pos[8].z) - (pos[0].z + 2.0 * pos[3].z + pos[6].z);
+    let gy = (pos[6].z + 2.0 * pos[7].z + pos[8].z) - (pos[0].z + 2.0 * pos[1].z + pos[2].z);
+    let depth_edge = length(vec2<f32>(gx, gy)) / max(abs(center.z), 0.0001);
+
+    // Normals of the four triangles around the centre; opposite pairs straddle any crease
+    let right = pos[5] - center;
+    let up = pos[1] - center;
+    let left = pos[3] - center;
+    let down = pos[7] - center;
+    let n0 = normalize(cross(right, up));
+    let n1 = normalize(cross(up, left));
+    let n2 = normalize(cross(left, down));
+    let n3 = normalize(cross(down, right));
+    let normal_edge = 1.0 - min(dot(n0, n2), dot(n1, n3));
+
+    let strength = clamp(max(depth_edge, normal_edge), 0.0, 1.0);
+    return vec4<f32>(edge.color.rgb, strength);
+}
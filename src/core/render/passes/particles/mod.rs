@@ -0,0 +1,360 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::core::render::RenderState;
+use crate::core::render::cache::{PipelineKey, ShaderId};
+use crate::core::resources::{ParticleBlendMode, ParticleInstance};
+
+/// Bytes between per-draw uniform slots, the largest offset alignment wgpu allows
+pub(crate) const PARTICLE_UNIFORM_SLOT_SIZE: u64 = 256;
+
+const FLAG_TEXTURED: u32 = 1;
+const FLAG_ADDITIVE: u32 = 2;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct ParticleUniform {
+    flags: u32,
+    soft_distance: f32,
+    _padding: [f32; 2],
+}
+
+/// Premultiplied alpha; additive particles write zero alpha so the destination is kept
+const PARTICLE_BLEND: wgpu::BlendState = wgpu::BlendState {
+    color: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    },
+    alpha: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    },
+};
+
+struct ParticleDraw {
+    camera_id: u32,
+    camera_index: u32,
+    texture_id: Option<u32>,
+    first_instance: u32,
+    instance_count: u32,
+}
+
+/// Draws every particle system as camera-facing billboards over each camera's HDR color.
+/// Additive systems go first; alpha systems follow with their particles sorted back to front.
+pub fn pass_particles(
+    render_state: &mut RenderState,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    encoder: &mut wgpu::CommandEncoder,
+    frame_index: u64,
+) {
+    if render_state.scene.particle_systems.is_empty() || render_state.scene.cameras.is_empty() {
+        return;
+    }
+
+    let mut sorted_cameras: Vec<_> = render_state.scene.cameras.iter().collect();
+    sorted_cameras.sort_by_key(|(_, record)| record.order);
+
+    // MARK: Build instances
+
+    let mut systems: Vec<_> = render_state.scene.particle_systems.iter().collect();
+    systems.sort_by_key(|(id, record)| (record.config.blend == ParticleBlendMode::Alpha, **id));
+
+    let mut instances: Vec<ParticleInstance> = Vec::new();
+    let mut uniforms: Vec<ParticleUniform> = Vec::new();
+    let mut draws: Vec<ParticleDraw> = Vec::new();
+    for (camera_index, (camera_id, camera)) in sorted_cameras.iter().enumerate() {
+        if camera.render_target.is_none() {
+            continue;
+        }
+        for (_, record) in &systems {
+            if record.particles.is_empty()
+                || (record.config.layer_mask & camera.layer_mask & render_state.layer_visibility)
+                    == 0
+            {
+                continue;
+            }
+
+            let first_instance = instances.len() as u32;
+            if record.config.blend == ParticleBlendMode::Alpha {
+                let view = camera.data.view;
+                let mut ordered: Vec<_> = record
+                    .particles
+                    .iter()
+                    .map(|particle| (view.transform_point3(particle.position).z, particle))
+                    .collect();
+                // View space looks down -Z, so the farthest particle has the lowest z
+                ordered.sort_by(|a, b| a.0.total_cmp(&b.0));
+                instances.extend(
+                    ordered
+                        .iter()
+                        .map(|(_, particle)| record.instance(particle)),
+                );
+            } else {
+                instances.extend(
+                    record
+                        .particles
+                        .iter()
+                        .map(|particle| record.instance(particle)),
+                );
+            }
+
+            let mut flags = 0;
+            if record.config.texture_id.is_some() {
+                flags |= FLAG_TEXTURED;
+            }
+            if record.config.blend == ParticleBlendMode::Additive {
+                flags |= FLAG_ADDITIVE;
+            }
+            uniforms.push(ParticleUniform {
+                flags,
+                soft_distance: record.config.soft_distance.max(0.0),
+                _padding: [0.0; 2],
+            });
+            draws.push(ParticleDraw {
+                camera_id: **camera_id,
+                camera_index: camera_index as u32,
+                texture_id: record.config.texture_id,
+                first_instance,
+                instance_count: instances.len() as u32 - first_instance,
+            });
+        }
+    }
+
+    if draws.is_empty() {
+        return;
+    }
+
+    // MARK: Upload
+
+    let instance_bytes: &[u8] = bytemuck::cast_slice(&instances);
+    if render_state
+        .particle_instance_buffer
+        .as_ref()
+        .is_some_and(|buffer| buffer.size() < instance_bytes.len() as u64)
+    {
+        render_state.particle_instance_buffer =
+            Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Particle Instance Buffer"),
+                size: (instance_bytes.len() as u64).next_power_of_two(),
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+    }
+    let uniform_needed = uniforms.len() as u64 * PARTICLE_UNIFORM_SLOT_SIZE;
+    if render_state
+        .particle_uniform_buffer
+        .as_ref()
+        .is_some_and(|buffer| buffer.size() < uniform_needed)
+    {
+        render_state.particle_uniform_buffer =
+            Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Particle Uniform Buffer"),
+                size: uniform_needed.next_power_of_two(),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+    }
+
+    let library = match render_state.library.as_ref() {
+        Some(lib) => lib,
+        None => return,
+    };
+    let depth_target = match render_state.forward_depth_target.as_ref() {
+        Some(target) => target,
+        None => return,
+    };
+    let (instance_buffer, uniform_buffer) = match (
+        render_state.particle_instance_buffer.as_ref(),
+        render_state.particle_uniform_buffer.as_ref(),
+    ) {
+        (Some(instance_buffer), Some(uniform_buffer)) => (instance_buffer, uniform_buffer),
+        _ => return,
+    };
+    let (bindings, light_system) = match (
+        render_state.bindings.as_ref(),
+        render_state.light_system.as_mut(),
+    ) {
+        (Some(bindings), Some(light_system)) => (bindings, light_system),
+        _ => return,
+    };
+    let shared_group = match bindings.shared_group() {
+        Some(group) => group,
+        None => return,
+    };
+
+    queue.write_buffer(instance_buffer, 0, instance_bytes);
+    for (slot, uniform) in uniforms.iter().enumerate() {
+        queue.write_buffer(
+            uniform_buffer,
+            slot as u64 * PARTICLE_UNIFORM_SLOT_SIZE,
+            bytemuck::bytes_of(uniform),
+        );
+    }
+
+    let (shader_id, label, shader, pipeline_layout, bind_group_layout) =
+        if depth_target.sample_count > 1 {
+            (
+                ShaderId::ParticleMsaa,
+                "Particle MSAA Pipeline",
+                &library.particle_msaa_shader,
+                &library.particle_msaa_pipeline_layout,
+                &library.layout_particles_msaa,
+            )
+        } else {
+            (
+                ShaderId::Particle,
+                "Particle Pipeline",
+                &library.particle_shader,
+                &library.particle_pipeline_layout,
+                &library.layout_particles,
+            )
+        };
+
+    // MARK: Draw
+
+    let mut draw_start = 0;
+    while draw_start < draws.len() {
+        let camera_id = draws[draw_start].camera_id;
+        let camera_index = draws[draw_start].camera_index;
+        let draw_end = draws[draw_start..]
+            .iter()
+            .position(|draw| draw.camera_id != camera_id)
+            .map_or(draws.len(), |len| draw_start + len);
+        let camera_draws = draw_start..draw_end;
+        draw_start = draw_end;
+
+        let target = match render_state
+            .scene
+            .cameras
+            .get(&camera_id)
+            .and_then(|record| record.render_target.as_ref())
+        {
+            Some(t) => t,
+            None => continue,
+        };
+
+        light_system.write_draw_params(camera_index, light_system.max_lights_per_camera);
+
+        let key = PipelineKey {
+            shader_id: shader_id as u64,
+            color_format: target.format,
+            color_target_count: 1,
+            depth_format: None,
+            sample_count: 1,
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            front_face: wgpu::FrontFace::Ccw,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            blend: Some(PARTICLE_BLEND),
+            conservative: false,
+        };
+        let pipeline = render_state.cache.get_or_create(key, frame_index, || {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: target.format,
+                        blend: key.blend,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: None,
+            })
+        });
+
+        let textures = render_state.textures.borrow();
+        let bind_groups: Vec<_> = camera_draws
+            .clone()
+            .map(|slot| {
+                let sprite_view = draws[slot]
+                    .texture_id
+                    .and_then(|id| textures.records.get(&id))
+                    .filter(|record| record.view_dimension == wgpu::TextureViewDimension::D2)
+                    .map_or(&library.fallback_view, |record| &record.view);
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("Particle Bind Group"),
+                    layout: bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: instance_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                buffer: uniform_buffer,
+                                offset: slot as u64 * PARTICLE_UNIFORM_SLOT_SIZE,
+                                size: wgpu::BufferSize::new(
+                                    std::mem::size_of::<ParticleUniform>() as u64
+                                ),
+                            }),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::TextureView(sprite_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 3,
+                            resource: wgpu::BindingResource::Sampler(
+                                &library.samplers.linear_clamp,
+                            ),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 4,
+                            resource: wgpu::BindingResource::TextureView(&depth_target.view),
+                        },
+                    ],
+                })
+            })
+            .collect();
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&format!("Particle Pass - Camera {}", camera_id)),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &target.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+
+        let size = target._texture.size();
+        let camera_offset = bindings.camera_pool.get_offset(camera_id) as u32;
+        let light_offset = light_system.draw_params_offset(camera_index) as u32;
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_viewport(0.0, 0.0, size.width as f32, size.height as f32, 0.0, 1.0);
+        render_pass.set_bind_group(0, shared_group, &[camera_offset, light_offset]);
+        for (slot, bind_group) in camera_draws.zip(&bind_groups) {
+            let draw = &draws[slot];
+            render_pass.set_bind_group(1, bind_group, &[]);
+            render_pass.draw(
+                0..6,
+                draw.first_instance..draw.first_instance + draw.instance_count,
+            );
+        }
+    }
+}
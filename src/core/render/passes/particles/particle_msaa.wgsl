@@ -0,0 +1,116 @@
+struct Camera {
+    position: vec4<f32>,
+    direction: vec4<f32>,
+    up: vec4<f32>,
+    near_far: vec2<f32>,
+    kind_flags: vec2<u32>,
+    projection: mat4x4<f32>,
+    view: mat4x4<f32>,
+    view_projection: mat4x4<f32>,
+}
+
+struct ParticleInstance {
+    position_size: vec4<f32>, // xyz: world position, w: size
+    color: vec4<f32>,
+    rotation: vec4<f32>,      // x: rotation in radians
+}
+
+struct ParticleParams {
+    flags: u32,               // 1: textured, 2: additive
+    soft_distance: f32,
+    _padding0: f32,
+    _padding1: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) view_depth: f32,
+}
+
+@group(0) @binding(1) var<uniform> camera: Camera;
+
+@group(1) @binding(0) var<storage, read> instances: array<ParticleInstance>;
+@group(1) @binding(1) var<uniform> params: ParticleParams;
+@group(1) @binding(2) var t_sprite: texture_2d<f32>;
+@group(1) @binding(3) var s_sprite: sampler;
+@group(1) @binding(4) var t_depth: texture_depth_multisampled_2d;
+
+const FLAG_TEXTURED: u32 = 1u;
+const FLAG_ADDITIVE: u32 = 2u;
+
+const CORNERS = array<vec2<f32>, 6>(
+    vec2<f32>(-1.0, -1.0),
+    vec2<f32>(1.0, -1.0),
+    vec2<f32>(1.0, 1.0),
+    vec2<f32>(-1.0, -1.0),
+    vec2<f32>(1.0, 1.0),
+    vec2<f32>(-1.0, 1.0),
+);
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    let instance = instances[instance_index];
+    let corner = CORNERS[vertex_index];
+
+    // Camera right and up are the first two rows of the view rotation
+    let right = vec3<f32>(camera.view[0].x, camera.view[1].x, camera.view[2].x);
+    let up = vec3<f32>(camera.view[0].y, camera.view[1].y, camera.view[2].y);
+    let c = cos(instance.rotation.x);
+    let s = sin(instance.rotation.x);
+    let offset = vec2<f32>(corner.x * c - corner.y * s, corner.x * s + corner.y * c)
+        * (instance.position_size.w * 0.5);
+    let world = instance.position_size.xyz + right * offset.x + up * offset.y;
+
+    var out: VertexOutput;
+    out.position = camera.view_projection * vec4<f32>(world, 1.0);
+    out.uv = vec2<f32>(corner.x * 0.5 + 0.5, 0.5 - corner.y * 0.5);
+    out.color = instance.color;
+    out.view_depth = -(camera.view * vec4<f32>(world, 1.0)).z;
+    return out;
+}
+
+// View distance of the scene depth (reverse-Z); the cleared far plane reads as infinitely far
+fn scene_view_depth(depth: f32) -> f32 {
+    if (depth <= 0.0) {
+        return 3.4e38;
+    }
+    let p = camera.projection;
+    let view_z = (p[3][2] - depth * p[3][3]) / (depth * p[2][3] - p[2][2]);
+    return -view_z;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = in.color;
+    let sprite = textureSample(t_sprite, s_sprite, in.uv);
+    if ((params.flags & FLAG_TEXTURED) != 0u) {
+        color *= sprite;
+    } else {
+        let r = length(in.uv * 2.0 - 1.0);
+        let falloff = saturate(1.0 - r);
+        color.a *= falloff * falloff;
+    }
+
+    if (params.soft_distance > 0.0) {
+        // First sample only; particles blend at pixel rate
+        let depth = textureLoad(t_depth, vec2<i32>(in.position.xy), 0);
+        let gap = scene_view_depth(depth) - in.view_depth;
+        color.a *= saturate(gap / params.soft_distance);
+    }
+
+    if (color.a <= 0.0) {
+        discard;
+    }
+
+    // Premultiplied output; additive particles leave the destination untouched
+    let rgb = color.rgb * color.a;
+    if ((params.flags & FLAG_ADDITIVE) != 0u) {
+        return vec4<f32>(rgb, 0.0);
+    }
+    return vec4<f32>(rgb, color.a);
+}
@@ -1,6 +1,6 @@
 use crate::core::render::RenderState;
 use crate::core::render::cache::{PipelineKey, ShaderId};
-use crate::core::resources::PostProcessConfig;
+use crate::core::resources::{ChromaticAberrationMode, OutlineMode, PostProcessConfig};
 use bytemuck::{Pod, Zeroable};
 
 #[repr(C)]
@@ -12,6 +12,8 @@ struct PostProcessUniform {
     params3: [f32; 4],
     params4: [f32; 4],
     params5: [f32; 4],
+    params6: [f32; 4],
+    params7: [f32; 4],
 }
 
 impl PostProcessUniform {
@@ -32,6 +34,18 @@ impl PostProcessUniform {
         if config.bloom_enabled {
             flags |= 1 << 4;
         }
+        if config.outline_mode == OutlineMode::EdgeDetect {
+            flags |= 1 << 5;
+        }
+        if config.dither_enabled {
+            flags |= 1 << 6;
+        }
+        if config.chromatic_aberration_mode == ChromaticAberrationMode::Uniform {
+            flags |= 1 << 7;
+        }
+        if config.grain_temporal {
+            flags |= 1 << 8;
+        }
 
         let outline_threshold = config.outline_threshold.clamp(0.0, 0.999);
         let outline_quality = config.outline_quality.clamp(0.0, 1.0);
@@ -56,7 +70,8 @@ impl PostProcessUniform {
                 flags as f32,
             ],
             params3: [
-                frame_index as f32,
+                // Wrapped so the grain seed stays exact in f32
+                (frame_index % 65536) as f32,
                 config.filter_sharpen,
                 config.outline_width,
                 outline_quality,
@@ -65,7 +80,7 @@ impl PostProcessUniform {
                 config.ssao_strength,
                 config.ssao_power,
                 config.filter_tonemap_mode as f32,
-                0.0,
+                config.grain_size.max(1.0),
             ],
             params5: [
                 config.bloom_threshold,
@@ -73,6 +88,18 @@ impl PostProcessUniform {
                 config.bloom_intensity,
                 config.bloom_scatter,
             ],
+            params6: [
+                config.vignette_color.x,
+                config.vignette_color.y,
+                config.vignette_color.z,
+                config.vignette_roundness.clamp(0.0, 1.0),
+            ],
+            params7: [
+                config.vignette_center.x,
+                config.vignette_center.y,
+                config.vignette_falloff.max(0.001),
+                config.chromatic_aberration_angle,
+            ],
         }
     }
 }
@@ -178,6 +205,7 @@ pub fn pass_post(
             depth_write_enabled: false,
             depth_compare: wgpu::CompareFunction::Always,
             blend: None,
+            conservative: false,
         };
 
         let pipeline = cache.get_or_create(key, frame_index, || {
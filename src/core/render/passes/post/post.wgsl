@@ -7,9 +7,11 @@ struct PostParams {
     params0: vec4<f32>, // exposure, gamma, saturation, contrast
     params1: vec4<f32>, // vignette, grain, chromatic_aberration, blur
     params2: vec4<f32>, // outline_strength, outline_threshold, posterize_steps, flags
-    params3: vec4<f32>, // time, sharpen, outline_width, outline_quality
-    params4: vec4<f32>, // ssao_strength, ssao_power, unused, unused
+    params3: vec4<f32>, // frame_index, sharpen, outline_width, outline_quality
+    params4: vec4<f32>, // ssao_strength, ssao_power, tonemap_mode, grain_size
     params5: vec4<f32>, // bloom_threshold, bloom_knee, bloom_intensity, bloom_scatter
+    params6: vec4<f32>, // vignette_color.rgb, vignette_roundness
+    params7: vec4<f32>, // vignette_center.xy, vignette_falloff, chromatic_aberration_angle
 }
 
 @vertex
@@ -33,9 +35,30 @@ fn luma(color: vec3<f32>) -> f32 {
     return dot(color, vec3<f32>(0.299, 0.587, 0.114));
 }
 
-fn rand(uv: vec2<f32>, time: f32) -> f32 {
-    let seed = dot(uv, vec2<f32>(12.9898, 78.233)) + time * 0.01;
-    return fract(sin(seed) * 43758.5453);
+// Uniform value in [0, 1] per grain cell and seed
+fn grain_hash(cell: vec2<i32>, seed: u32) -> f32 {
+    var h = (bitcast<u32>(cell.x) * 73856093u) ^ (bitcast<u32>(cell.y) * 19349663u) ^ (seed * 83492791u);
+    h = h ^ (h >> 16u);
+    h = h * 0x7feb352du;
+    h = h ^ (h >> 15u);
+    h = h * 0x846ca68bu;
+    h = h ^ (h >> 16u);
+    return f32(h) / 4294967295.0;
+}
+
+// Interleaved gradient noise: a fixed screen-space pattern with little low-frequency energy
+fn dither_noise(pixel: vec2<f32>) -> f32 {
+    return fract(52.9829189 * fract(dot(pixel, vec2<f32>(0.06711056, 0.00583715))));
+}
+
+fn linear_to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let c = max(color, vec3<f32>(0.0));
+    return select(1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055, c * 12.92, c <= vec3<f32>(0.0031308));
+}
+
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let c = max(color, vec3<f32>(0.0));
+    return select(pow((c + 0.055) / 1.055, vec3<f32>(2.4)), c / 12.92, c <= vec3<f32>(0.04045));
 }
 
 fn sample_color(uv: vec2<f32>) -> vec4<f32> {
@@ -54,6 +77,37 @@ fn sample_bloom(uv: vec2<f32>) -> vec3<f32> {
     return textureSample(t_bloom, s_diffuse, uv).rgb;
 }
 
+// Contrast-adaptive sharpening (after AMD FidelityFX CAS). The negative lobe shrinks where
+// the 3x3 neighborhood already has high contrast, so flat noise and hard edges get less
+// sharpening than soft detail, and the result is clamped to the neighborhood range so it
+// cannot ring.
+fn contrast_adaptive_sharpen(uv: vec2<f32>, center: vec3<f32>, texel: vec2<f32>, amount: f32) -> vec3<f32> {
+    let a = sample_color(uv + vec2<f32>(-texel.x, -texel.y)).rgb;
+    let b = sample_color(uv + vec2<f32>(0.0, -texel.y)).rgb;
+    let c = sample_color(uv + vec2<f32>(texel.x, -texel.y)).rgb;
+    let d = sample_color(uv + vec2<f32>(-texel.x, 0.0)).rgb;
+    let f = sample_color(uv + vec2<f32>(texel.x, 0.0)).rgb;
+    let g = sample_color(uv + vec2<f32>(-texel.x, texel.y)).rgb;
+    let h = sample_color(uv + vec2<f32>(0.0, texel.y)).rgb;
+    let i = sample_color(uv + vec2<f32>(texel.x, texel.y)).rgb;
+
+    let cross_min = min(center, min(min(b, d), min(f, h)));
+    let cross_max = max(center, max(max(b, d), max(f, h)));
+    let ring_min = min(cross_min, min(min(a, c), min(g, i)));
+    let ring_max = max(cross_max, max(max(a, c), max(g, i)));
+
+    // The input is HDR, so the contrast test runs on Reinhard-compressed values in [0, 1]
+    let one = vec3<f32>(1.0);
+    let mn = cross_min / (one + cross_min) + ring_min / (one + ring_min);
+    let mx = cross_max / (one + cross_max) + ring_max / (one + ring_max);
+    let amp = sqrt(clamp(min(mn, 2.0 * one - mx) / max(mx, vec3<f32>(1e-4)), vec3<f32>(0.0), one));
+    let peak = -1.0 / mix(8.0, 5.0, clamp(amount, 0.0, 1.0));
+    let w = amp * peak;
+
+    let sharpened = ((b + d + f + h) * w + center) / (one + 4.0 * w);
+    return clamp(sharpened, cross_min, cross_max);
+}
+
 fn tonemap(color: vec3<f32>, mode: u32) -> vec3<f32> {
     if (mode == 0u) {
         return color;
@@ -93,6 +147,10 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let outline_enabled = (flags & 4u) != 0u;
     let ssao_enabled = (flags & 8u) != 0u;
     let bloom_enabled = (flags & 16u) != 0u;
+    let outline_edge_mode = (flags & 32u) != 0u;
+    let dither_enabled = (flags & 64u) != 0u;
+    let chroma_uniform = (flags & 128u) != 0u;
+    let grain_temporal = (flags & 256u) != 0u;
     let ssao_strength = post.params4.x;
     let tone_mode = u32(post.params4.z + 0.5);
     let bloom_intensity = post.params5.z;
@@ -108,7 +166,13 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
     if (enabled) {
         if (chroma > 0.0001) {
-            let offset = (in.uv - vec2<f32>(0.5, 0.5)) * chroma * 2.0 * texel;
+            // Radial splits grow to `chroma` pixels at the screen edges; uniform splits are
+            // `chroma` pixels everywhere
+            var offset = (in.uv - vec2<f32>(0.5, 0.5)) * chroma * 2.0 * texel;
+            if (chroma_uniform) {
+                let angle = post.params7.w;
+                offset = vec2<f32>(cos(angle), -sin(angle)) * chroma * texel;
+            }
             let r = sample_color(in.uv + offset).r;
             let g = color.g;
             let b = sample_color(in.uv - offset).b;
@@ -126,22 +190,19 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         }
 
         if (sharpen > 0.0001) {
-            let t = texel;
-            let c = color.rgb;
-            let n = sample_color(in.uv + vec2<f32>(t.x, 0.0)).rgb;
-            let s = sample_color(in.uv - vec2<f32>(t.x, 0.0)).rgb;
-            let e = sample_color(in.uv + vec2<f32>(0.0, t.y)).rgb;
-            let w = sample_color(in.uv - vec2<f32>(0.0, t.y)).rgb;
-            let edge = (n + s + e + w) * 0.25;
-            let sharpened = mix(c, c + (c - edge), sharpen);
-            color = vec4<f32>(sharpened, color.a);
+            color = vec4<f32>(contrast_adaptive_sharpen(in.uv, color.rgb, texel, sharpen), color.a);
         }
     }
 
     var outline_mask = 0.0;
     var outline_rgb = vec3<f32>(0.0);
 
-    if (outline_enabled && outline_strength > 0.0001) {
+    if (outline_enabled && outline_strength > 0.0001 && outline_edge_mode) {
+        // Edge-detect writes the edge strength directly; no dilation needed
+        let edge = sample_outline(in.uv);
+        outline_mask = smoothstep(outline_thresh, outline_thresh + 0.15, edge.a);
+        outline_rgb = edge.rgb;
+    } else if (outline_enabled && outline_strength > 0.0001) {
         let step = texel * outline_width;
         let center_outline = sample_outline(in.uv);
         let center_alpha = center_outline.a;
@@ -220,15 +281,24 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         color = vec4<f32>(gam, color.a);
 
         if (vignette > 0.0001) {
-            let d = distance(in.uv, vec2<f32>(0.5, 0.5));
-            let v = smoothstep(0.7, 0.95, d);
-            let vcol = color.rgb * mix(1.0, 1.0 - vignette, v);
+            // Roundness widens x by the aspect ratio, turning the UV ellipse into a circle
+            let aspect = tex_size.x / tex_size.y;
+            let roundness = post.params6.w;
+            var delta = in.uv - post.params7.xy;
+            delta.x *= mix(1.0, aspect, roundness);
+            let d = length(delta);
+            let v = smoothstep(0.95 - post.params7.z, 0.95, d);
+            let vcol = mix(color.rgb, post.params6.rgb, v * vignette);
             color = vec4<f32>(vcol, color.a);
         }
 
         if (grain > 0.0001) {
-            let noise = rand(in.uv * 2048.0, post.params3.x) - 0.5;
-            let gcol = color.rgb + noise * grain;
+            let cell = vec2<i32>(floor(in.position.xy / post.params4.w));
+            let seed = select(0u, u32(post.params3.x), grain_temporal);
+            let noise = grain_hash(cell, seed) - 0.5;
+            // Film grain shows most in shadows and midtones and washes out in highlights
+            let response = mix(1.0, 0.25, smoothstep(0.5, 1.0, luma(color.rgb)));
+            let gcol = color.rgb + noise * grain * response;
             color = vec4<f32>(gcol, color.a);
         }
     }
@@ -238,5 +308,12 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         color = vec4<f32>(outlined, color.a);
     }
 
+    // The surface stores 8-bit sRGB, so the offset is half a step in that encoding
+    if (dither_enabled) {
+        let offset = (dither_noise(in.position.xy) - 0.5) / 255.0;
+        let encoded = linear_to_srgb(color.rgb) + vec3<f32>(offset);
+        color = vec4<f32>(srgb_to_linear(encoded), color.a);
+    }
+
     return vec4<f32>(color.rgb, color.a);
 }
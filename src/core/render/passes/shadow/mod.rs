@@ -24,6 +24,15 @@ pub fn pass_shadow_update(
         }
     }
 
+    shadow_manager.overflow = Default::default();
+
+    // Release pages that went out of view so new requests can still allocate
+    let max_page_age = shadow_manager.config.max_page_age;
+    let evicted = shadow_manager.evict_stale(frame_index, max_page_age);
+    if evicted > 0 {
+        log::trace!("Evicted {} stale shadow pages", evicted);
+    }
+
     let library = match render_state.library.as_ref() {
         Some(l) => l,
         None => return,
@@ -34,10 +43,12 @@ pub fn pass_shadow_update(
         None => return,
     };
 
-    let vertex_sys = match render_state.vertex.as_mut() {
+    let vertex_rc = match render_state.vertex.clone() {
         Some(v) => v,
         None => return,
     };
+    let mut vertex_guard = vertex_rc.borrow_mut();
+    let vertex_sys = &mut *vertex_guard;
 
     let cache = &mut render_state.cache;
 
@@ -62,7 +73,7 @@ pub fn pass_shadow_update(
             None => continue,
         };
 
-        if !light_record.cast_shadow {
+        if !light_record.casts_shadow() {
             continue;
         }
 
@@ -244,6 +255,7 @@ pub fn pass_shadow_update(
             depth_write_enabled: true,
             depth_compare: wgpu::CompareFunction::Greater, // Reverse Z
             blend: None,
+            conservative: false,
         };
 
         let shadow_pipeline = cache.get_or_create(shadow_pipeline_key, frame_index, || {
@@ -379,7 +391,7 @@ pub fn pass_shadow_update(
             rpass.set_viewport(vx, vy, vw, vh, 0.0, 1.0);
             rpass.set_scissor_rect(vx as u32, vy as u32, vw as u32, vh as u32);
 
-            if let Some(shared_group) = bindings.shared_group.as_ref() {
+            if let Some(shared_group) = bindings.shared_group() {
                 let camera_offset = bindings.camera_pool.get_offset(page.shadow_cam_id) as u32;
                 rpass.set_bind_group(0, shared_group, &[camera_offset, 0]);
             }
@@ -395,7 +407,9 @@ pub fn pass_shadow_update(
             }
 
             for (_model_id, model_record) in &render_state.scene.models {
-                if !model_record.cast_shadow {
+                if !model_record.cast_shadow
+                    || (model_record.layer_mask & render_state.layer_visibility) == 0
+                {
                     continue;
                 }
 
@@ -418,6 +432,7 @@ pub fn pass_shadow_update(
                         shadow_instance_cursor += 1;
 
                         rpass.draw_indexed(0..index_info.count, 0, inst_idx..(inst_idx + 1));
+                        collector.stats.add_draw(index_info.count, 1);
                     }
                 }
             }
@@ -69,6 +69,10 @@ pub fn pass_skybox(
     sorted_cameras.sort_by_key(|(_, record)| record.order);
 
     for (_camera_id, camera_record) in sorted_cameras {
+        // Overlay cameras keep a transparent background; forward clears them instead
+        if camera_record.compose_blend.is_overlay() {
+            continue;
+        }
         let target_view = match &camera_record.render_target {
             Some(target) => &target.view,
             None => continue,
@@ -101,6 +105,7 @@ pub fn pass_skybox(
             depth_write_enabled: false,
             depth_compare: wgpu::CompareFunction::Always,
             blend: None,
+            conservative: false,
         };
 
         let pipeline = render_state
@@ -144,6 +149,22 @@ pub fn pass_skybox(
             SkyboxMode::Cubemap => 2.0,
         };
 
+        // A 2D texture is read as an equirectangular panorama, a cube texture by direction
+        let textures = render_state.textures.borrow();
+        let skybox_texture = match (skybox.mode, skybox.cubemap_texture_id) {
+            (SkyboxMode::Cubemap, Some(id)) => textures.records.get(&id),
+            _ => None,
+        };
+        let (equirect_view, cube_view, cube_flag) = match skybox_texture {
+            Some(record) if record.view_dimension == wgpu::TextureViewDimension::Cube => {
+                (&library.fallback_view, &record.view, 1.0)
+            }
+            Some(record) if record.view_dimension == wgpu::TextureViewDimension::D2 => {
+                (&record.view, &library.fallback_cube_view, 0.0)
+            }
+            _ => (&library.fallback_view, &library.fallback_cube_view, 0.0),
+        };
+
         let uniform = SkyboxUniform {
             inv_view_proj: inv_view_proj.to_cols_array_2d(),
             camera_pos: [camera_pos.x, camera_pos.y, camera_pos.z, 1.0],
@@ -166,20 +187,10 @@ pub fn pass_skybox(
                 skybox.sky_color.z,
                 1.0,
             ],
-            params: [skybox.rotation, mode_value, 0.0, 0.0],
+            params: [skybox.rotation, mode_value, cube_flag, 0.0],
         };
         queue.write_buffer(uniform_buffer, 0, bytemuck::bytes_of(&uniform));
 
-        let skybox_view = match (skybox.mode, skybox.cubemap_texture_id) {
-            (SkyboxMode::Cubemap, Some(id)) => render_state
-                .scene
-                .textures
-                .get(&id)
-                .map(|record| &record.view)
-                .unwrap_or(&library.fallback_view),
-            _ => &library.fallback_view,
-        };
-
         let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Skybox Bind Group"),
             layout: &library.layout_skybox,
@@ -190,12 +201,16 @@ pub fn pass_skybox(
                 },
                 wgpu::BindGroupEntry {
                     binding: 1,
-                    resource: wgpu::BindingResource::TextureView(skybox_view),
+                    resource: wgpu::BindingResource::TextureView(equirect_view),
                 },
                 wgpu::BindGroupEntry {
                     binding: 2,
                     resource: wgpu::BindingResource::Sampler(&library.samplers.linear_clamp),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(cube_view),
+                },
             ],
         });
 
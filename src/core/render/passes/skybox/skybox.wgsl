@@ -11,6 +11,7 @@ struct SkyboxParams {
 @group(0) @binding(0) var<uniform> u_sky: SkyboxParams;
 @group(0) @binding(1) var t_sky: texture_2d<f32>;
 @group(0) @binding(2) var s_sky: sampler;
+@group(0) @binding(3) var t_sky_cube: texture_cube<f32>;
 
 struct VsOut {
     @builtin(position) pos: vec4<f32>,
@@ -72,6 +73,13 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
         return vec4<f32>(final_color, 1.0);
     }
 
+    // params.z: a cube texture is bound. Cube faces are authored left-handed,
+    // so z is flipped: the +Z face sits ahead of a camera looking down -Z.
+    if (u_sky.params.z > 0.5) {
+        let texel = textureSample(t_sky_cube, s_sky, vec3<f32>(dir.x, dir.y, -dir.z)).rgb;
+        return vec4<f32>(texel * intensity, 1.0);
+    }
+
     let uv = equirect_uv(dir);
     let texel = textureSample(t_sky, s_sky, uv).rgb;
     return vec4<f32>(texel * intensity, 1.0);
@@ -0,0 +1,222 @@
+use glam::{Vec2, Vec4};
+
+use crate::core::render::RenderState;
+use crate::core::render::cache::{PipelineKey, ShaderId};
+use crate::core::resources::SpriteInstance;
+
+/// Premultiplied alpha over whatever compose left in the surface
+const SPRITE_BLEND: wgpu::BlendState = wgpu::BlendState {
+    color: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    },
+    alpha: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    },
+};
+
+struct SpriteDraw {
+    texture_id: Option<u32>,
+    first_instance: u32,
+    instance_count: u32,
+}
+
+/// Draws every visible sprite batch over the composed surface in window pixels.
+/// Batches go in ascending `order` (ties by id), and consecutive batches sharing a texture
+/// collapse into one instanced draw.
+pub fn pass_sprites(
+    render_state: &mut RenderState,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    encoder: &mut wgpu::CommandEncoder,
+    surface_texture: &wgpu::SurfaceTexture,
+    config: &wgpu::SurfaceConfiguration,
+    frame_index: u64,
+) {
+    // MARK: Build instances
+
+    let mut batches: Vec<_> = render_state
+        .scene
+        .sprite_batches
+        .iter()
+        .filter(|(_, record)| record.visible && !record.sprites.is_empty())
+        .collect();
+    if batches.is_empty() {
+        return;
+    }
+    batches.sort_by_key(|(id, record)| (record.order, **id));
+
+    let textures = render_state.textures.borrow();
+    let mut instances: Vec<SpriteInstance> = Vec::new();
+    let mut draws: Vec<SpriteDraw> = Vec::new();
+    for (_, record) in &batches {
+        let texture_size = record
+            .texture_id
+            .and_then(|id| textures.records.get(&id))
+            .map_or(Vec2::ONE, |texture| {
+                Vec2::new(texture._size.width as f32, texture._size.height as f32)
+            });
+        let first_instance = instances.len() as u32;
+        for sprite in &record.sprites {
+            sprite.push_instances(texture_size, &mut instances);
+        }
+        let instance_count = instances.len() as u32 - first_instance;
+        match draws.last_mut() {
+            Some(draw) if draw.texture_id == record.texture_id => {
+                draw.instance_count += instance_count;
+            }
+            _ => draws.push(SpriteDraw {
+                texture_id: record.texture_id,
+                first_instance,
+                instance_count,
+            }),
+        }
+    }
+
+    // MARK: Upload
+
+    let instance_bytes: &[u8] = bytemuck::cast_slice(&instances);
+    if render_state
+        .sprite_instance_buffer
+        .as_ref()
+        .is_some_and(|buffer| buffer.size() < instance_bytes.len() as u64)
+    {
+        render_state.sprite_instance_buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Sprite Instance Buffer"),
+            size: (instance_bytes.len() as u64).next_power_of_two(),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+    }
+
+    let library = match render_state.library.as_ref() {
+        Some(lib) => lib,
+        None => return,
+    };
+    let (instance_buffer, uniform_buffer) = match (
+        render_state.sprite_instance_buffer.as_ref(),
+        render_state.sprite_uniform_buffer.as_ref(),
+    ) {
+        (Some(instance_buffer), Some(uniform_buffer)) => (instance_buffer, uniform_buffer),
+        _ => return,
+    };
+
+    queue.write_buffer(instance_buffer, 0, instance_bytes);
+    let viewport = Vec4::new(config.width as f32, config.height as f32, 0.0, 0.0);
+    queue.write_buffer(uniform_buffer, 0, bytemuck::bytes_of(&viewport));
+
+    let key = PipelineKey {
+        shader_id: ShaderId::Sprite as u64,
+        color_format: config.format,
+        color_target_count: 1,
+        depth_format: None,
+        sample_count: 1,
+        topology: wgpu::PrimitiveTopology::TriangleList,
+        cull_mode: None,
+        front_face: wgpu::FrontFace::Ccw,
+        depth_write_enabled: false,
+        depth_compare: wgpu::CompareFunction::Always,
+        blend: Some(SPRITE_BLEND),
+        conservative: false,
+    };
+    let pipeline = render_state.cache.get_or_create(key, frame_index, || {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Sprite Pipeline"),
+            layout: Some(&library.sprite_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &library.sprite_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &library.sprite_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: key.blend,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: None,
+        })
+    });
+
+    // MARK: Draw
+
+    let bind_groups: Vec<_> = draws
+        .iter()
+        .map(|draw| {
+            let sprite_view = draw
+                .texture_id
+                .and_then(|id| textures.records.get(&id))
+                .filter(|record| record.view_dimension == wgpu::TextureViewDimension::D2)
+                .map_or(&library.fallback_view, |record| &record.view);
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Sprite Bind Group"),
+                layout: &library.layout_sprites,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: instance_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: uniform_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(sprite_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::Sampler(
+                            library.samplers.get(render_state.default_sampler),
+                        ),
+                    },
+                ],
+            })
+        })
+        .collect();
+
+    let view = surface_texture
+        .texture
+        .create_view(&wgpu::TextureViewDescriptor::default());
+    let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Sprite Pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: &view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Store,
+            },
+            depth_slice: None,
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        multiview_mask: None,
+    });
+
+    render_pass.set_pipeline(pipeline);
+    for (draw, bind_group) in draws.iter().zip(&bind_groups) {
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(
+            0..6,
+            draw.first_instance..draw.first_instance + draw.instance_count,
+        );
+        render_state
+            .collector
+            .stats
+            .add_draw(6, draw.instance_count);
+    }
+}
@@ -0,0 +1,65 @@
+struct SpriteInstance {
+    position_size: vec4<f32>,  // xy: pixel position, zw: pixel size
+    uv_rect: vec4<f32>,        // u_min, v_min, u_max, v_max
+    color: vec4<f32>,
+    pivot_rotation: vec4<f32>, // xy: pivot, z: rotation in radians
+}
+
+struct SpriteParams {
+    viewport: vec4<f32>,       // xy: surface size in pixels
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@group(0) @binding(0) var<storage, read> instances: array<SpriteInstance>;
+@group(0) @binding(1) var<uniform> params: SpriteParams;
+@group(0) @binding(2) var t_sprite: texture_2d<f32>;
+@group(0) @binding(3) var s_sprite: sampler;
+
+const CORNERS = array<vec2<f32>, 6>(
+    vec2<f32>(0.0, 0.0),
+    vec2<f32>(1.0, 0.0),
+    vec2<f32>(1.0, 1.0),
+    vec2<f32>(0.0, 0.0),
+    vec2<f32>(1.0, 1.0),
+    vec2<f32>(0.0, 1.0),
+);
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    let instance = instances[instance_index];
+    let corner = CORNERS[vertex_index];
+
+    // Pixel space has y down, so a positive angle turns clockwise on screen
+    let local = (corner - instance.pivot_rotation.xy) * instance.position_size.zw;
+    let c = cos(instance.pivot_rotation.z);
+    let s = sin(instance.pivot_rotation.z);
+    let pixel = instance.position_size.xy
+        + vec2<f32>(local.x * c - local.y * s, local.x * s + local.y * c);
+
+    let ndc = pixel / params.viewport.xy * 2.0 - 1.0;
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(ndc.x, -ndc.y, 0.0, 1.0);
+    out.uv = mix(instance.uv_rect.xy, instance.uv_rect.zw, corner);
+    out.color = instance.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Untextured batches bind the white fallback
+    let color = in.color * textureSample(t_sprite, s_sprite, in.uv);
+    if (color.a <= 0.0) {
+        discard;
+    }
+    // Premultiplied output
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
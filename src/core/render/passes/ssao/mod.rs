@@ -158,6 +158,7 @@ pub fn pass_ssao(
                 depth_write_enabled: false,
                 depth_compare: wgpu::CompareFunction::Always,
                 blend: None,
+                conservative: false,
             };
             let pipeline = render_state.cache.get_or_create(key, frame_index, || {
                 device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
@@ -216,6 +217,7 @@ pub fn pass_ssao(
                 depth_write_enabled: false,
                 depth_compare: wgpu::CompareFunction::Always,
                 blend: None,
+                conservative: false,
             };
             let pipeline = render_state.cache.get_or_create(key, frame_index, || {
                 device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
@@ -343,6 +345,7 @@ pub fn pass_ssao_blur(
                 depth_write_enabled: false,
                 depth_compare: wgpu::CompareFunction::Always,
                 blend: None,
+                conservative: false,
             };
             let pipeline = render_state.cache.get_or_create(key, frame_index, || {
                 device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
@@ -405,6 +408,7 @@ pub fn pass_ssao_blur(
                 depth_write_enabled: false,
                 depth_compare: wgpu::CompareFunction::Always,
                 blend: None,
+                conservative: false,
             };
             let pipeline = render_state.cache.get_or_create(key, frame_index, || {
                 device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
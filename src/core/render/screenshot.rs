@@ -0,0 +1,479 @@
+use std::sync::mpsc::{Receiver, Sender, TryRecvError, channel};
+
+use half::f16;
+use image::ImageEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use serde::{Deserialize, Serialize};
+
+use crate::core::cmd::{CommandResponse, CommandResponseEnvelope};
+use crate::core::state::EngineState;
+use crate::core::window::CmdResultWindowScreenshot;
+
+pub const DEFAULT_JPEG_QUALITY: u8 = 90;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScreenshotFormat {
+    #[default]
+    Png,
+    Jpeg,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ScreenshotRequest {
+    /// Command whose response is deferred until the capture completes
+    cmd_id: u64,
+    /// Download buffer that receives the encoded image
+    buffer_id: u64,
+    format: ScreenshotFormat,
+    quality: u8,
+}
+
+struct ScreenshotReadback {
+    request: ScreenshotRequest,
+    buffer: wgpu::Buffer,
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    texture_format: wgpu::TextureFormat,
+    keep_alpha: bool,
+    mapped: Option<Receiver<Result<(), wgpu::BufferAsyncError>>>,
+}
+
+struct EncodedScreenshot {
+    request: ScreenshotRequest,
+    width: u32,
+    height: u32,
+    data: Result<Vec<u8>, String>,
+}
+
+impl EncodedScreenshot {
+    fn failed(request: ScreenshotRequest, message: String) -> Self {
+        Self {
+            request,
+            width: 0,
+            height: 0,
+            data: Err(message),
+        }
+    }
+}
+
+/// Per-window screenshot pipeline: request -> surface copy -> map -> encode off-thread
+pub struct ScreenshotCapture {
+    request: Option<ScreenshotRequest>,
+    readback: Option<ScreenshotReadback>,
+    /// Request whose encoded image (or failure) is still travelling through the channel
+    in_flight: Option<ScreenshotRequest>,
+    sender: Sender<EncodedScreenshot>,
+    receiver: Receiver<EncodedScreenshot>,
+}
+
+impl ScreenshotCapture {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            request: None,
+            readback: None,
+            in_flight: None,
+            sender,
+            receiver,
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.current().is_some()
+    }
+
+    /// Download buffer the capture in progress will fill
+    pub fn pending_buffer_id(&self) -> Option<u64> {
+        self.current().map(|request| request.buffer_id)
+    }
+
+    fn current(&self) -> Option<&ScreenshotRequest> {
+        self.request
+            .as_ref()
+            .or(self.readback.as_ref().map(|readback| &readback.request))
+            .or(self.in_flight.as_ref())
+    }
+
+    pub fn request(&mut self, cmd_id: u64, buffer_id: u64, format: ScreenshotFormat, quality: u8) {
+        self.request = Some(ScreenshotRequest {
+            cmd_id,
+            buffer_id,
+            format,
+            quality: quality.clamp(1, 100),
+        });
+    }
+
+    /// Fails a request that has not been copied yet (e.g. the window stopped rendering)
+    pub fn fail_request(&mut self, message: String) {
+        if let Some(request) = self.request.take() {
+            self.fail(request, message);
+        }
+    }
+
+    fn fail(&mut self, request: ScreenshotRequest, message: String) {
+        self.in_flight = Some(request);
+        let _ = self
+            .sender
+            .send(EncodedScreenshot::failed(request, message));
+    }
+
+    /// Drops the capture in progress and returns a failed result for it
+    fn abort(&mut self, message: String) -> Option<EncodedScreenshot> {
+        let request = *self.current()?;
+        self.request = None;
+        self.readback = None;
+        self.in_flight = None;
+        Some(EncodedScreenshot::failed(request, message))
+    }
+
+    /// Records a copy of the presented surface texture. Call before submitting `encoder`.
+    pub fn record_copy(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_texture: &wgpu::SurfaceTexture,
+        keep_alpha: bool,
+    ) {
+        let Some(request) = self.request.take() else {
+            return;
+        };
+        let texture = &surface_texture.texture;
+        let texture_format = texture.format();
+        let Some(bytes_per_pixel) = texture_format.block_copy_size(None) else {
+            self.fail(
+                request,
+                format!(
+                    "Screenshots are not supported for surface format {:?}",
+                    texture_format
+                ),
+            );
+            return;
+        };
+
+        let width = texture.width();
+        let height = texture.height();
+        let padded_bytes_per_row = (width * bytes_per_pixel)
+            .div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Screenshot Readback Buffer"),
+            size: padded_bytes_per_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+
+        self.readback = Some(ScreenshotReadback {
+            request,
+            buffer,
+            width,
+            height,
+            padded_bytes_per_row,
+            texture_format,
+            keep_alpha,
+            mapped: None,
+        });
+    }
+
+    /// Starts mapping the readback buffer. Call after the copy was submitted.
+    pub fn begin_map(&mut self) {
+        let Some(readback) = self.readback.as_mut() else {
+            return;
+        };
+        if readback.mapped.is_some() {
+            return;
+        }
+        let (sender, receiver) = channel();
+        readback
+            .buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let _ = sender.send(result);
+            });
+        readback.mapped = Some(receiver);
+    }
+
+    /// Hands a mapped readback to the encoder and returns finished screenshots
+    fn poll(&mut self) -> Vec<EncodedScreenshot> {
+        let map_state = self
+            .readback
+            .as_ref()
+            .and_then(|readback| readback.mapped.as_ref())
+            .map(|receiver| receiver.try_recv());
+
+        match map_state {
+            Some(Ok(Ok(()))) => {
+                if let Some(readback) = self.readback.take() {
+                    let pixels = unpad_rows(&readback);
+                    readback.buffer.unmap();
+                    self.in_flight = Some(readback.request);
+                    spawn_encode(readback, pixels, self.sender.clone());
+                }
+            }
+            Some(Ok(Err(e))) => {
+                if let Some(readback) = self.readback.take() {
+                    self.fail(
+                        readback.request,
+                        format!("Failed to map screenshot buffer: {}", e),
+                    );
+                }
+            }
+            Some(Err(TryRecvError::Disconnected)) => {
+                if let Some(readback) = self.readback.take() {
+                    self.fail(
+                        readback.request,
+                        "Screenshot buffer mapping was lost".into(),
+                    );
+                }
+            }
+            Some(Err(TryRecvError::Empty)) | None => {}
+        }
+
+        let mut finished = Vec::new();
+        while let Ok(result) = self.receiver.try_recv() {
+            self.in_flight = None;
+            finished.push(result);
+        }
+        finished
+    }
+}
+
+impl Default for ScreenshotCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Advances pending screenshots of every window and completes their commands
+pub fn process_screenshots(engine: &mut EngineState) {
+    let any_pending = engine
+        .window
+        .states
+        .values()
+        .any(|window_state| window_state.render_state.screenshot.is_busy());
+    if !any_pending {
+        return;
+    }
+
+    if let Some(device) = engine.device.as_ref() {
+        let _ = device.poll(wgpu::PollType::Poll);
+    }
+
+    let finished: Vec<EncodedScreenshot> = engine
+        .window
+        .states
+        .values_mut()
+        .flat_map(|window_state| window_state.render_state.screenshot.poll())
+        .collect();
+    for result in finished {
+        complete_screenshot(engine, result);
+    }
+}
+
+/// Fails the screenshot still pending on a window that is going away
+pub fn abort_screenshot(engine: &mut EngineState, window_id: u32) {
+    let aborted = engine
+        .window
+        .states
+        .get_mut(&window_id)
+        .and_then(|window_state| {
+            window_state
+                .render_state
+                .screenshot
+                .abort(format!("Window {} was closed", window_id))
+        });
+    if let Some(result) = aborted {
+        complete_screenshot(engine, result);
+    }
+}
+
+/// Stores the encoded image as a download buffer and sends the deferred response
+fn complete_screenshot(engine: &mut EngineState, result: EncodedScreenshot) {
+    let request = result.request;
+    let outcome = result.data.and_then(|data| {
+        let byte_length = data.len() as u64;
+        if engine.buffers.insert_download(request.buffer_id, data) {
+            Ok(byte_length)
+        } else {
+            Err(format!(
+                "Download buffer {} is already in use",
+                request.buffer_id
+            ))
+        }
+    });
+
+    let response = match outcome {
+        Ok(byte_length) => CmdResultWindowScreenshot {
+            success: true,
+            message: "Screenshot captured".into(),
+            format: request.format,
+            width: result.width,
+            height: result.height,
+            buffer_id: request.buffer_id,
+            byte_length,
+        },
+        Err(message) => CmdResultWindowScreenshot {
+            success: false,
+            message,
+            format: request.format,
+            buffer_id: request.buffer_id,
+            ..Default::default()
+        },
+    };
+    engine.complete_async_cmd(CommandResponseEnvelope {
+        id: request.cmd_id,
+        response: CommandResponse::WindowScreenshot(response),
+    });
+}
+
+// MARK: - Conversion
+
+fn unpad_rows(readback: &ScreenshotReadback) -> Vec<u8> {
+    let bytes_per_pixel = readback.texture_format.block_copy_size(None).unwrap_or(4) as usize;
+    let row_bytes = readback.width as usize * bytes_per_pixel;
+    let mapped = readback.buffer.slice(..).get_mapped_range();
+    let mut pixels = Vec::with_capacity(row_bytes * readback.height as usize);
+    for row in mapped.chunks(readback.padded_bytes_per_row as usize) {
+        pixels.extend_from_slice(&row[..row_bytes]);
+    }
+    pixels
+}
+
+/// Converts raw surface texels to sRGB-encoded RGBA8. 8-bit formats are kept as
+/// stored (that is what the display shows); float surfaces are linear and get encoded.
+fn to_rgba8(
+    pixels: &[u8],
+    format: wgpu::TextureFormat,
+    keep_alpha: bool,
+) -> Result<Vec<u8>, String> {
+    let mut rgba = match format {
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => pixels.to_vec(),
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => pixels
+            .chunks_exact(4)
+            .flat_map(|p| [p[2], p[1], p[0], p[3]])
+            .collect(),
+        wgpu::TextureFormat::Rgb10a2Unorm => pixels
+            .chunks_exact(4)
+            .flat_map(|p| {
+                let v = u32::from_le_bytes([p[0], p[1], p[2], p[3]]);
+                let channel = |shift: u32| ((v >> shift) & 0x3FF) as f32 / 1023.0;
+                [
+                    unorm8(channel(0)),
+                    unorm8(channel(10)),
+                    unorm8(channel(20)),
+                    ((v >> 30) * 85) as u8,
+                ]
+            })
+            .collect(),
+        wgpu::TextureFormat::Rgba16Float => pixels
+            .chunks_exact(8)
+            .flat_map(|p| {
+                let channel = |i: usize| f16::from_le_bytes([p[i * 2], p[i * 2 + 1]]).to_f32();
+                [
+                    unorm8(linear_to_srgb(channel(0))),
+                    unorm8(linear_to_srgb(channel(1))),
+                    unorm8(linear_to_srgb(channel(2))),
+                    unorm8(channel(3)),
+                ]
+            })
+            .collect(),
+        other => {
+            return Err(format!(
+                "Screenshots are not supported for surface format {:?}",
+                other
+            ));
+        }
+    };
+
+    if !keep_alpha {
+        for pixel in rgba.chunks_exact_mut(4) {
+            pixel[3] = 255;
+        }
+    }
+    Ok(rgba)
+}
+
+fn unorm8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+// MARK: - Encoding
+
+fn encode(
+    pixels: &[u8],
+    format: wgpu::TextureFormat,
+    request: ScreenshotRequest,
+    width: u32,
+    height: u32,
+    keep_alpha: bool,
+) -> Result<Vec<u8>, String> {
+    let rgba = to_rgba8(pixels, format, keep_alpha)?;
+    let mut out = Vec::new();
+    match request.format {
+        ScreenshotFormat::Png => PngEncoder::new(&mut out)
+            .write_image(&rgba, width, height, image::ExtendedColorType::Rgba8)
+            .map_err(|e| format!("PNG encoding failed: {}", e))?,
+        ScreenshotFormat::Jpeg => {
+            let rgb: Vec<u8> = rgba
+                .chunks_exact(4)
+                .flat_map(|p| [p[0], p[1], p[2]])
+                .collect();
+            JpegEncoder::new_with_quality(&mut out, request.quality)
+                .write_image(&rgb, width, height, image::ExtendedColorType::Rgb8)
+                .map_err(|e| format!("JPEG encoding failed: {}", e))?
+        }
+    }
+    Ok(out)
+}
+
+fn encode_readback(readback: &ScreenshotReadback, pixels: &[u8]) -> EncodedScreenshot {
+    EncodedScreenshot {
+        request: readback.request,
+        width: readback.width,
+        height: readback.height,
+        data: encode(
+            pixels,
+            readback.texture_format,
+            readback.request,
+            readback.width,
+            readback.height,
+            readback.keep_alpha,
+        ),
+    }
+}
+
+#[cfg(not(feature = "wasm"))]
+fn spawn_encode(readback: ScreenshotReadback, pixels: Vec<u8>, sender: Sender<EncodedScreenshot>) {
+    std::thread::spawn(move || {
+        let _ = sender.send(encode_readback(&readback, &pixels));
+    });
+}
+
+#[cfg(feature = "wasm")]
+fn spawn_encode(readback: ScreenshotReadback, pixels: Vec<u8>, sender: Sender<EncodedScreenshot>) {
+    let _ = sender.send(encode_readback(&readback, &pixels));
+}
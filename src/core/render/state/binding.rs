@@ -17,12 +17,40 @@ pub struct BindingSystem {
     pub material_pbr_pool: UniformBufferPool<MaterialPbrParams>,
     pub material_pbr_inputs: StorageBufferPool<glam::Vec4>,
     pub bones_pool: StorageBufferPool<glam::Mat4>,
-    pub shared_group: Option<wgpu::BindGroup>,
+    /// One shared group per camera pool copy, built together and picked by `shared_group`
+    pub shared_groups: Vec<wgpu::BindGroup>,
     pub model_bind_group: Option<wgpu::BindGroup>,
     pub outline_model_bind_group: Option<wgpu::BindGroup>,
     pub shadow_model_bind_group: Option<wgpu::BindGroup>,
+    /// Material groups shared by every material in bindless mode
+    pub material_standard_group: Option<wgpu::BindGroup>,
+    pub material_pbr_group: Option<wgpu::BindGroup>,
 
     // Version tracking for bind group invalidation
     pub pool_versions: HashMap<&'static str, u64>,
     pub last_with_shadows: bool,
+    /// `TextureStore::atlas_version` of the forward atlas view in `shared_groups`
+    pub last_atlas_version: u64,
+}
+
+impl BindingSystem {
+    /// Shared group bound to the camera pool copy of this frame
+    pub fn shared_group(&self) -> Option<&wgpu::BindGroup> {
+        self.shared_groups.get(self.camera_pool.current_copy())
+    }
+
+    /// Uploads writes batched by every pool
+    pub fn flush(&mut self) {
+        self.frame_pool.flush();
+        self.camera_pool.flush();
+        self.model_pool.flush();
+        self.instance_pool.flush();
+        self.outline_instance_pool.flush();
+        self.shadow_instance_pool.flush();
+        self.material_standard_pool.flush();
+        self.material_standard_inputs.flush();
+        self.material_pbr_pool.flush();
+        self.material_pbr_inputs.flush();
+        self.bones_pool.flush();
+    }
 }
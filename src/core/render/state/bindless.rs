@@ -0,0 +1,343 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::core::resources::TextureRecord;
+
+/// How materials bind their textures, chosen when the window is created
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MaterialBindingConfig {
+    /// Index one global texture array instead of binding textures per material.
+    /// Ignored when the adapter lacks `TEXTURE_BINDING_ARRAY`.
+    pub bindless: bool,
+    /// Size of the global texture array. Index 0 holds the fallback texture, which textures
+    /// past the limit sample instead.
+    pub max_textures: u32,
+}
+
+impl Default for MaterialBindingConfig {
+    fn default() -> Self {
+        Self {
+            bindless: true,
+            max_textures: 256,
+        }
+    }
+}
+
+impl MaterialBindingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(2..=16384).contains(&self.max_textures) {
+            return Err(format!(
+                "maxTextures must be within 2..=16384, got {}",
+                self.max_textures
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Global texture array of the forward pass, bound to group 3.
+/// Materials store the array index of each standalone texture in `atlas_layers`.
+pub struct BindlessTextureSystem {
+    pub layout: wgpu::BindGroupLayout,
+    /// Group 2 of opaque pipelines, which read no scene depth
+    pub empty_group: wgpu::BindGroup,
+    pub bind_group: Option<wgpu::BindGroup>,
+    pub capacity: u32,
+    /// Array index of every texture in the array
+    pub indices: HashMap<u32, u32>,
+    free: Vec<u32>,
+    next_index: u32,
+    /// `TextureStore::version` the array was built from
+    synced_version: Option<u64>,
+}
+
+impl BindlessTextureSystem {
+    /// Array index of a texture; 0 (the fallback) when it is not in the array
+    pub fn index_of(&self, texture_id: u32) -> u32 {
+        self.indices.get(&texture_id).copied().unwrap_or(0)
+    }
+
+    /// Assigns indices to new 2D textures, releases those of disposed ones and rebuilds the
+    /// bind group. Indices are stable, so materials of untouched textures keep theirs.
+    pub fn sync(
+        &mut self,
+        device: &wgpu::Device,
+        textures: &HashMap<u32, TextureRecord>,
+        textures_version: u64,
+        fallback_view: &wgpu::TextureView,
+    ) {
+        if self.synced_version == Some(textures_version) && self.bind_group.is_some() {
+            return;
+        }
+
+        let is_bindable =
+            |record: &TextureRecord| record.view_dimension == wgpu::TextureViewDimension::D2;
+        let free = &mut self.free;
+        self.indices.retain(|id, index| {
+            let keep = textures.get(id).is_some_and(is_bindable);
+            if !keep {
+                free.push(*index);
+            }
+            keep
+        });
+
+        let mut added: Vec<u32> = textures
+            .iter()
+            .filter(|(id, record)| is_bindable(record) && !self.indices.contains_key(id))
+            .map(|(id, _)| *id)
+            .collect();
+        added.sort_unstable();
+        let mut overflow = 0;
+        for id in added {
+            let index = match self.free.pop() {
+                Some(index) => index,
+                None if self.next_index < self.capacity => {
+                    self.next_index += 1;
+                    self.next_index - 1
+                }
+                None => {
+                    overflow += 1;
+                    continue;
+                }
+            };
+            self.indices.insert(id, index);
+        }
+        if overflow > 0 {
+            log::warn!(
+                "Bindless texture array is full ({} slots); {} textures sample the fallback",
+                self.capacity,
+                overflow
+            );
+        }
+
+        let mut views = vec![fallback_view; self.capacity as usize];
+        for (id, index) in &self.indices {
+            views[*index as usize] = &textures[id].view;
+        }
+        self.bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("BindGroup Bindless Textures"),
+            layout: &self.layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureViewArray(&views),
+            }],
+        }));
+        self.synced_version = Some(textures_version);
+    }
+}
+
+/// Bindless textures need binding arrays with room for `max_textures` in each shader stage
+pub fn bindless_supported(device: &wgpu::Device, config: &MaterialBindingConfig) -> bool {
+    config.bindless
+        && device
+            .features()
+            .contains(wgpu::Features::TEXTURE_BINDING_ARRAY)
+        && device.limits().max_binding_array_elements_per_shader_stage >= config.max_textures
+}
+
+/// Forward shader source with the eight material texture bindings replaced by lookups into
+/// the global array, indexed by the array index the material keeps in `atlas_layers`
+#[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+fn bindless_source(source: &str, max_textures: u32) -> String {
+    let mut output: String = source
+        .lines()
+        .filter(|line| !(line.starts_with("@group(1)") && line.contains("var material_tex")))
+        .flat_map(|line| [line, "\n"])
+        .collect();
+    for slot in 0..8 {
+        output = output.replace(
+            &format!("material_tex{}", slot),
+            &format!(
+                "bindless_textures[material.atlas_layers[{}][{}]]",
+                slot / 4,
+                slot % 4
+            ),
+        );
+    }
+    output.push_str(&format!(
+        "@group(3) @binding(0) var bindless_textures: binding_array<texture_2d<f32>, {}>;\n",
+        max_textures
+    ));
+    output
+}
+
+/// Group 1 of the bindless forward pipelines: the per-material layout minus its textures
+#[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+fn object_layout(
+    device: &wgpu::Device,
+    label: &str,
+    material_size: usize,
+) -> wgpu::BindGroupLayout {
+    let storage = |binding, visibility, min_binding_size| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage { read_only: true },
+            has_dynamic_offset: false,
+            min_binding_size,
+        },
+        count: None,
+    };
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some(label),
+        entries: &[
+            storage(
+                0,
+                wgpu::ShaderStages::VERTEX_FRAGMENT,
+                std::num::NonZeroU64::new(
+                    std::mem::size_of::<crate::core::resources::ModelComponent>() as u64,
+                ),
+            ),
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: std::num::NonZeroU64::new(material_size as u64),
+                },
+                count: None,
+            },
+            storage(2, wgpu::ShaderStages::VERTEX_FRAGMENT, None),
+            storage(
+                11,
+                wgpu::ShaderStages::VERTEX,
+                std::num::NonZeroU64::new(std::mem::size_of::<glam::Mat4>() as u64),
+            ),
+        ],
+    })
+}
+
+#[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+impl super::RenderState {
+    /// Switches forward materials to the global texture array when the device allows it.
+    /// Swaps the forward layouts and shaders of the library, so it must run after `init`.
+    pub(crate) fn init_bindless(&mut self, device: &wgpu::Device, config: &MaterialBindingConfig) {
+        self.bindless = None;
+        let Some(library) = self.library.as_mut() else {
+            return;
+        };
+        if !bindless_supported(device, config) {
+            if config.bindless {
+                log::info!("Bindless textures unavailable; binding textures per material");
+            }
+            return;
+        }
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("BindGroupLayout Bindless Textures"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: std::num::NonZeroU32::new(config.max_textures),
+            }],
+        });
+        let layout_empty = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("BindGroupLayout Empty"),
+            entries: &[],
+        });
+        let empty_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("BindGroup Empty"),
+            layout: &layout_empty,
+            entries: &[],
+        });
+        let object_standard = object_layout(
+            device,
+            "BindGroupLayout Object Standard (Bindless)",
+            std::mem::size_of::<crate::core::resources::MaterialStandardParams>(),
+        );
+        let object_pbr = object_layout(
+            device,
+            "BindGroupLayout Object PBR (Bindless)",
+            std::mem::size_of::<crate::core::resources::MaterialPbrParams>(),
+        );
+
+        let pipeline_layout = |label: &str, object, depth| {
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some(label),
+                bind_group_layouts: &[&library.layout_shared, object, depth, &layout],
+                immediate_size: 0,
+            })
+        };
+        library.forward_standard_pipeline_layout = pipeline_layout(
+            "Forward Standard Bindless Pipeline Layout",
+            &object_standard,
+            &layout_empty,
+        );
+        library.forward_pbr_pipeline_layout = pipeline_layout(
+            "Forward PBR Bindless Pipeline Layout",
+            &object_pbr,
+            &layout_empty,
+        );
+        library.forward_standard_transparent_pipeline_layout = pipeline_layout(
+            "Forward Standard Transparent Bindless Pipeline Layout",
+            &object_standard,
+            &library.layout_scene_depth,
+        );
+        library.forward_standard_transparent_msaa_pipeline_layout = pipeline_layout(
+            "Forward Standard Transparent MSAA Bindless Pipeline Layout",
+            &object_standard,
+            &library.layout_scene_depth_msaa,
+        );
+        library.forward_pbr_transparent_pipeline_layout = pipeline_layout(
+            "Forward PBR Transparent Bindless Pipeline Layout",
+            &object_pbr,
+            &library.layout_scene_depth,
+        );
+        library.forward_pbr_transparent_msaa_pipeline_layout = pipeline_layout(
+            "Forward PBR Transparent MSAA Bindless Pipeline Layout",
+            &object_pbr,
+            &library.layout_scene_depth_msaa,
+        );
+
+        library.forward_standard_shader =
+            device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("forward_standard_bindless.wgsl"),
+                source: wgpu::ShaderSource::Wgsl(
+                    bindless_source(
+                        include_str!("../passes/forward/branches/forward_standard.wgsl"),
+                        config.max_textures,
+                    )
+                    .into(),
+                ),
+            });
+        library.forward_pbr_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("forward_pbr_bindless.wgsl"),
+            source: wgpu::ShaderSource::Wgsl(
+                bindless_source(
+                    include_str!("../passes/forward/branches/forward_pbr.wgsl"),
+                    config.max_textures,
+                )
+                .into(),
+            ),
+        });
+        library.layout_object_standard = object_standard;
+        library.layout_object_pbr = object_pbr;
+
+        // Materials bound per material so far switch to the shared groups
+        for record in self.scene.materials_standard.values_mut() {
+            record.bind_group = None;
+        }
+        for record in self.scene.materials_pbr.values_mut() {
+            record.bind_group = None;
+        }
+
+        self.bindless = Some(BindlessTextureSystem {
+            layout,
+            empty_group,
+            bind_group: None,
+            capacity: config.max_textures,
+            // Index 0 is the fallback texture
+            indices: HashMap::new(),
+            free: Vec::new(),
+            next_index: 1,
+            synced_version: None,
+        });
+    }
+}
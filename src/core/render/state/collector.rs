@@ -5,10 +5,34 @@ pub struct DrawItem {
     pub model_id: u32,
     pub geometry_id: u32,
     pub material_id: u32,
+    pub sort_order: i32,
     pub depth: f32,
     pub instance_idx: u32,
 }
 
+/// Draw counters accumulated across the geometry passes of one frame
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DrawStats {
+    pub draw_calls: u32,
+    pub triangles: u64,
+    pub models_culled: u32,
+    pub visible_cameras: u32,
+}
+
+impl DrawStats {
+    pub fn add_draw(&mut self, index_count: u32, instance_count: u32) {
+        self.draw_calls += 1;
+        self.triangles += (index_count / 3) as u64 * instance_count as u64;
+    }
+
+    pub fn merge(&mut self, other: &DrawStats) {
+        self.draw_calls += other.draw_calls;
+        self.triangles += other.triangles;
+        self.models_culled += other.models_culled;
+        self.visible_cameras += other.visible_cameras;
+    }
+}
+
 /// Collection of vectors to be reused across frames for draw call preparation
 #[derive(Default)]
 pub struct DrawCollector {
@@ -22,6 +46,8 @@ pub struct DrawCollector {
     pub shadow_instance_data: Vec<ModelComponent>,
     pub outline_items: Vec<(u32, u32)>,
     pub outline_instance_data: Vec<ModelComponent>,
+    /// Reset once per `render_frames`, not by `clear`
+    pub stats: DrawStats,
 }
 
 impl DrawCollector {
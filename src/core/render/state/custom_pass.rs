@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use glam::Vec4;
+use serde::{Deserialize, Serialize};
+
+use crate::core::resources::RenderTarget;
+
+const PRELUDE: &str = include_str!("../passes/custom/custom_prelude.wgsl");
+const PRELUDE_MSAA: &str = include_str!("../passes/custom/custom_prelude_msaa.wgsl");
+
+/// Where in the render graph a custom pass runs
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum CustomPassInsertPoint {
+    /// Right after the `forward` node, over the HDR scene color
+    #[default]
+    AfterForward,
+    /// Right before the `post` node, over the HDR scene color
+    BeforePost,
+    /// Right after the `post` node, over the post-processed color
+    AfterPost,
+}
+
+impl CustomPassInsertPoint {
+    pub fn label(self) -> &'static str {
+        match self {
+            CustomPassInsertPoint::AfterForward => "after-forward",
+            CustomPassInsertPoint::BeforePost => "before-post",
+            CustomPassInsertPoint::AfterPost => "after-post",
+        }
+    }
+}
+
+pub struct CustomPassRecord {
+    pub label: Option<String>,
+    pub insert_point: CustomPassInsertPoint,
+    /// Passes at the same insert point run in ascending order, then by id
+    pub order: i32,
+    pub enabled: bool,
+    pub params: Vec4,
+    source: String,
+    /// Indexed by whether the depth input is multisampled
+    pipelines: [Option<wgpu::RenderPipeline>; 2],
+}
+
+impl CustomPassRecord {
+    pub fn new(
+        label: Option<String>,
+        insert_point: CustomPassInsertPoint,
+        order: i32,
+        enabled: bool,
+        params: Vec4,
+        source: String,
+    ) -> Self {
+        Self {
+            label,
+            insert_point,
+            order,
+            enabled,
+            params,
+            source,
+            pipelines: [None, None],
+        }
+    }
+
+    pub fn pipeline(&self, msaa: bool) -> Option<&wgpu::RenderPipeline> {
+        self.pipelines[msaa as usize].as_ref()
+    }
+
+    /// Compiles the pipeline for the given depth input on first use
+    pub fn ensure_pipeline(
+        &mut self,
+        device: &wgpu::Device,
+        layout: &wgpu::PipelineLayout,
+        msaa: bool,
+    ) {
+        if self.pipelines[msaa as usize].is_some() {
+            return;
+        }
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Custom Pass Shader"),
+            source: wgpu::ShaderSource::Wgsl(full_source(&self.source, msaa).into()),
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Custom Pass Pipeline"),
+            layout: Some(layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: CUSTOM_PASS_FORMAT,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: None,
+        });
+        self.pipelines[msaa as usize] = Some(pipeline);
+    }
+}
+
+/// Color format of the camera targets custom passes write to
+pub const CUSTOM_PASS_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+fn full_source(source: &str, msaa: bool) -> String {
+    let prelude = if msaa { PRELUDE_MSAA } else { PRELUDE };
+    format!("{}\n{}", prelude, source)
+}
+
+/// Parses and validates host WGSL against both preludes, so a bad shader is rejected by the
+/// command instead of failing pipeline creation mid-frame
+pub fn validate_custom_pass_source(source: &str) -> Result<(), String> {
+    for msaa in [false, true] {
+        let full = full_source(source, msaa);
+        let module =
+            naga::front::wgsl::parse_str(&full).map_err(|err| err.emit_to_string(&full))?;
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::default(),
+        )
+        .validate(&module)
+        .map_err(|err| format!("{:?}", err.into_inner()))?;
+
+        let has_fragment = module
+            .entry_points
+            .iter()
+            .any(|entry| entry.name == "fs_main" && entry.stage == naga::ShaderStage::Fragment);
+        if !has_fragment {
+            return Err("Shader must define `@fragment fn fs_main`".into());
+        }
+    }
+    Ok(())
+}
+
+struct CustomPassLayouts {
+    bind_group_layouts: [wgpu::BindGroupLayout; 2],
+    pipeline_layouts: [wgpu::PipelineLayout; 2],
+}
+
+/// Host-registered fullscreen passes of one window
+#[derive(Default)]
+pub struct CustomPassSystem {
+    pub passes: HashMap<u32, CustomPassRecord>,
+    pub uniform_buffer: Option<wgpu::Buffer>,
+    /// Copy of the color target the pass samples while writing over the original
+    pub scratch: Option<RenderTarget>,
+    layouts: Option<CustomPassLayouts>,
+}
+
+impl CustomPassSystem {
+    pub fn clear(&mut self) {
+        self.passes.clear();
+        self.uniform_buffer = None;
+        self.scratch = None;
+        self.layouts = None;
+    }
+
+    /// Enabled pass ids at `insert_point`, in execution order
+    pub fn ordered(&self, insert_point: CustomPassInsertPoint) -> Vec<u32> {
+        let mut ids: Vec<_> = self
+            .passes
+            .iter()
+            .filter(|(_, record)| record.enabled && record.insert_point == insert_point)
+            .map(|(id, record)| (record.order, *id))
+            .collect();
+        ids.sort_unstable();
+        ids.into_iter().map(|(_, id)| id).collect()
+    }
+
+    pub fn layouts(
+        &mut self,
+        device: &wgpu::Device,
+        msaa: bool,
+    ) -> (&wgpu::BindGroupLayout, &wgpu::PipelineLayout) {
+        let layouts = self.layouts.get_or_insert_with(|| {
+            let bind_group_layouts = [false, true].map(|multisampled| {
+                device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: Some("Custom Pass Bind Group Layout"),
+                    entries: &[
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Texture {
+                                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                            count: None,
+                        },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 2,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Texture {
+                                sample_type: wgpu::TextureSampleType::Depth,
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                multisampled,
+                            },
+                            count: None,
+                        },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 3,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                    ],
+                })
+            });
+            let pipeline_layouts = [0, 1].map(|index| {
+                device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: Some("Custom Pass Pipeline Layout"),
+                    bind_group_layouts: &[&bind_group_layouts[index]],
+                    immediate_size: 0,
+                })
+            });
+            CustomPassLayouts {
+                bind_group_layouts,
+                pipeline_layouts,
+            }
+        });
+        (
+            &layouts.bind_group_layouts[msaa as usize],
+            &layouts.pipeline_layouts[msaa as usize],
+        )
+    }
+}
@@ -0,0 +1,102 @@
+use bytemuck::{Pod, Zeroable};
+use glam::Vec4;
+use serde::{Deserialize, Serialize};
+
+/// Debug visualization drawn on top of the composed frame
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DebugRenderMode {
+    #[default]
+    None,
+    /// Shadow atlas layer with the virtual page allocations overlaid
+    ShadowAtlas,
+}
+
+/// Number of frames after which an unused shadow page is drawn fully "cold"
+const PAGE_AGE_RANGE: f32 = 120.0;
+
+/// One shadow page rectangle in atlas UV space
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct DebugPageInstance {
+    /// min.xy, max.xy
+    pub rect: Vec4,
+    pub color: Vec4,
+}
+
+pub struct DebugOverlay {
+    pub mode: DebugRenderMode,
+    pub layer: u32,
+    pages: Vec<DebugPageInstance>,
+    buffer: Option<wgpu::Buffer>,
+    capacity: usize,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        Self {
+            mode: DebugRenderMode::None,
+            layer: 0,
+            pages: Vec::new(),
+            buffer: None,
+            capacity: 0,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.mode != DebugRenderMode::None
+    }
+
+    pub fn clear_pages(&mut self) {
+        self.pages.clear();
+    }
+
+    /// Queue a page rect; color goes green (used this frame) → red (stale), dirty pages are magenta
+    pub fn add_page(&mut self, uv_rect: Vec4, age_frames: u64, is_dirty: bool) {
+        let color = if is_dirty {
+            Vec4::new(1.0, 0.0, 1.0, 1.0)
+        } else {
+            let t = (age_frames as f32 / PAGE_AGE_RANGE).min(1.0);
+            Vec4::new(t, 1.0 - t, 0.0, 1.0)
+        };
+        self.pages.push(DebugPageInstance {
+            rect: uv_rect,
+            color,
+        });
+    }
+
+    pub fn page_count(&self) -> u32 {
+        self.pages.len() as u32
+    }
+
+    pub fn prepare(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        if self.pages.is_empty() {
+            return;
+        }
+
+        if self.buffer.is_none() || self.capacity < self.pages.len() {
+            self.capacity = self.pages.len().next_power_of_two();
+            let size = self.capacity * std::mem::size_of::<DebugPageInstance>();
+            self.buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Debug Page Instance Buffer"),
+                size: size as u64,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+
+        if let Some(buffer) = &self.buffer {
+            queue.write_buffer(buffer, 0, bytemuck::cast_slice(&self.pages));
+        }
+    }
+
+    pub fn buffer(&self) -> Option<&wgpu::Buffer> {
+        self.buffer.as_ref()
+    }
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,123 @@
+use crate::core::render::state::{FrustumPlane, RenderState};
+use crate::core::resources::{StorageBufferPool, UniformBufferPool};
+
+/// Layout of `wgpu::util::DrawIndexedIndirectArgs`, written by the cull shader
+#[repr(C)]
+#[derive(Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct IndirectDrawArgs {
+    pub index_count: u32,
+    pub instance_count: u32,
+    pub first_index: u32,
+    pub base_vertex: i32,
+    pub first_instance: u32,
+}
+
+/// One forward batch before culling
+#[repr(C)]
+#[derive(Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct IndirectDrawTemplate {
+    pub args: IndirectDrawArgs,
+    pub _padding: [u32; 3],
+    /// World-space bounding sphere of every instance in the batch (xyz center, w radius).
+    /// A negative radius skips the test.
+    pub bounds: glam::Vec4,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct IndirectCullParams {
+    pub first_draw: u32,
+    pub draw_count: u32,
+    pub camera_index: u32,
+    pub _padding: u32,
+}
+
+/// Buffers for GPU-culled indirect forward draws.
+/// Draws of every camera in a frame are appended, so each camera keeps its own args.
+pub struct IndirectDrawSystem {
+    pub templates: StorageBufferPool<IndirectDrawTemplate>,
+    pub args: StorageBufferPool<IndirectDrawArgs>,
+    pub frustums: StorageBufferPool<FrustumPlane>,
+    pub params: UniformBufferPool<IndirectCullParams>,
+    pub bind_group: Option<wgpu::BindGroup>,
+    /// Pool versions `bind_group` was built with
+    pub bound_versions: [u64; 4],
+    /// Next free draw slot this frame
+    pub draw_cursor: u32,
+}
+
+impl IndirectDrawSystem {
+    #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let alignment = device.limits().min_uniform_buffer_offset_alignment as u64;
+        Self {
+            templates: StorageBufferPool::new(device, queue, Some(1024), 0),
+            args: StorageBufferPool::new_with_usage(
+                device,
+                queue,
+                Some(1024),
+                0,
+                wgpu::BufferUsages::INDIRECT,
+            ),
+            frustums: StorageBufferPool::new(device, queue, Some(48), 0),
+            params: UniformBufferPool::new(device, queue, Some(8), alignment),
+            bind_group: None,
+            bound_versions: [0; 4],
+            draw_cursor: 0,
+        }
+    }
+
+    pub fn versions(&self) -> [u64; 4] {
+        [
+            self.templates.version(),
+            self.args.version(),
+            self.frustums.version(),
+            self.params.version(),
+        ]
+    }
+
+    pub fn args_offset(draw_index: u32) -> u64 {
+        draw_index as u64 * std::mem::size_of::<IndirectDrawArgs>() as u64
+    }
+
+    pub fn begin_frame(&mut self, frame_index: u64) {
+        self.templates.begin_frame(frame_index);
+        self.args.begin_frame(frame_index);
+        self.frustums.begin_frame(frame_index);
+        self.params.begin_frame(frame_index);
+        self.draw_cursor = 0;
+    }
+
+    pub fn flush(&mut self) {
+        self.templates.flush();
+        self.frustums.flush();
+        self.params.flush();
+    }
+}
+
+/// Indirect draws need GPU-written args, a compute stage to write them, and a non-zero
+/// `first_instance` for every batch after the first
+#[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+pub fn indirect_draw_supported(adapter: &wgpu::Adapter, device: &wgpu::Device) -> bool {
+    let flags = adapter.get_downlevel_capabilities().flags;
+    flags.contains(wgpu::DownlevelFlags::INDIRECT_EXECUTION | wgpu::DownlevelFlags::COMPUTE_SHADERS)
+        && device
+            .features()
+            .contains(wgpu::Features::INDIRECT_FIRST_INSTANCE)
+        && device.limits().max_compute_workgroups_per_dimension > 0
+        && device.limits().max_storage_buffers_per_shader_stage >= 3
+}
+
+#[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+impl RenderState {
+    /// Switches forward draws to GPU-culled indirect args when the adapter allows it
+    pub(crate) fn init_indirect_draw(
+        &mut self,
+        adapter: &wgpu::Adapter,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
+        self.indirect = indirect_draw_supported(adapter, device)
+            .then(|| IndirectDrawSystem::new(device, queue));
+    }
+}
@@ -7,6 +7,8 @@ pub(crate) struct FallbackTextures {
     pub(crate) atlas_view: wgpu::TextureView,
     pub(crate) shadow_texture: wgpu::Texture,
     pub(crate) shadow_view: wgpu::TextureView,
+    pub(crate) cube_texture: wgpu::Texture,
+    pub(crate) cube_view: wgpu::TextureView,
 }
 
 impl RenderState {
@@ -120,6 +122,39 @@ impl RenderState {
                 usage: None,
             });
 
+        let cube_size = wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 6,
+        };
+        let fallback_cube_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Fallback Cube Texture 1x1"),
+            size: cube_size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        queue.write_texture(
+            fallback_cube_texture.as_image_copy(),
+            &white_pixel.repeat(6),
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4),
+                rows_per_image: Some(1),
+            },
+            cube_size,
+        );
+
+        let fallback_cube_view = fallback_cube_texture.create_view(&wgpu::TextureViewDescriptor {
+            label: Some("Fallback Cube View"),
+            dimension: Some(wgpu::TextureViewDimension::Cube),
+            ..Default::default()
+        });
+
         FallbackTextures {
             texture: fallback_texture,
             view: fallback_view,
@@ -127,6 +162,8 @@ impl RenderState {
             atlas_view: fallback_forward_atlas_view,
             shadow_texture: fallback_shadow_texture,
             shadow_view: fallback_shadow_view,
+            cube_texture: fallback_cube_texture,
+            cube_view: fallback_cube_view,
         }
     }
 }
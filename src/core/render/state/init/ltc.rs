@@ -0,0 +1,71 @@
+use super::super::RenderState;
+
+/// Resolution of the GGX LTC lookup tables (roughness × sqrt(1 - N·V))
+pub(crate) const LTC_LUT_SIZE: u32 = 64;
+
+/// Fitted GGX tables as RGBA16F: inverse LTC matrices, then (magnitude, fresnel, 0, 1)
+const LTC_GGX_TABLES: &[u8] = include_bytes!("ltc_ggx.bin");
+
+pub(crate) struct LtcTables {
+    pub(crate) matrix_texture: wgpu::Texture,
+    pub(crate) matrix_view: wgpu::TextureView,
+    pub(crate) amplitude_texture: wgpu::Texture,
+    pub(crate) amplitude_view: wgpu::TextureView,
+}
+
+impl RenderState {
+    pub(crate) fn init_ltc_tables(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> LtcTables {
+        let table_bytes = (LTC_LUT_SIZE * LTC_LUT_SIZE * 8) as usize;
+        let (matrix_data, amplitude_data) = LTC_GGX_TABLES.split_at(table_bytes);
+
+        let (matrix_texture, matrix_view) =
+            create_lut_texture(device, queue, "LTC GGX Matrix LUT", matrix_data);
+        let (amplitude_texture, amplitude_view) =
+            create_lut_texture(device, queue, "LTC GGX Amplitude LUT", amplitude_data);
+
+        LtcTables {
+            matrix_texture,
+            matrix_view,
+            amplitude_texture,
+            amplitude_view,
+        }
+    }
+}
+
+fn create_lut_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    label: &str,
+    data: &[u8],
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let size = wgpu::Extent3d {
+        width: LTC_LUT_SIZE,
+        height: LTC_LUT_SIZE,
+        depth_or_array_layers: 1,
+    };
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba16Float,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+
+    queue.write_texture(
+        texture.as_image_copy(),
+        data,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(LTC_LUT_SIZE * 8),
+            rows_per_image: Some(LTC_LUT_SIZE),
+        },
+        size,
+    );
+
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
@@ -3,10 +3,14 @@ mod fallbacks;
 #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
 mod library;
 #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+mod ltc;
+#[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
 mod systems;
 
 #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
-use crate::core::render::state::{RenderState, ResourceLibrary};
+use crate::core::render::state::{
+    LightCullingConfig, RenderState, ResourceLibrary, SharedResources,
+};
 
 #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
 impl RenderState {
@@ -15,9 +19,11 @@ impl RenderState {
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         _surface_format: wgpu::TextureFormat,
+        shared: &mut SharedResources,
+        light_culling: &LightCullingConfig,
     ) {
         // 1. Initialize core systems
-        self.init_core_systems(device, queue);
+        self.init_core_systems(device, queue, shared, light_culling);
 
         // 2. Initialize samplers
         let samplers = self.init_samplers(device);
@@ -27,6 +33,7 @@ impl RenderState {
 
         // 4. Initialize fallback textures
         let fallbacks = self.init_fallback_textures(device, queue);
+        let ltc = self.init_ltc_tables(device, queue);
 
         // 5. Initialize pipeline layouts
         let gizmo_pipeline_layout =
@@ -50,6 +57,46 @@ impl RenderState {
                 immediate_size: 0,
             });
 
+        let forward_standard_transparent_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Forward Standard Transparent Pipeline Layout"),
+                bind_group_layouts: &[
+                    &layouts.shared,
+                    &layouts.object_standard,
+                    &layouts.scene_depth,
+                ],
+                immediate_size: 0,
+            });
+
+        let forward_standard_transparent_msaa_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Forward Standard Transparent MSAA Pipeline Layout"),
+                bind_group_layouts: &[
+                    &layouts.shared,
+                    &layouts.object_standard,
+                    &layouts.scene_depth_msaa,
+                ],
+                immediate_size: 0,
+            });
+
+        let forward_pbr_transparent_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Forward PBR Transparent Pipeline Layout"),
+                bind_group_layouts: &[&layouts.shared, &layouts.object_pbr, &layouts.scene_depth],
+                immediate_size: 0,
+            });
+
+        let forward_pbr_transparent_msaa_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Forward PBR Transparent MSAA Pipeline Layout"),
+                bind_group_layouts: &[
+                    &layouts.shared,
+                    &layouts.object_pbr,
+                    &layouts.scene_depth_msaa,
+                ],
+                immediate_size: 0,
+            });
+
         let shadow_pipeline_layout =
             device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                 label: Some("Shadow Pipeline Layout"),
@@ -71,6 +118,13 @@ impl RenderState {
                 immediate_size: 0,
             });
 
+        let indirect_cull_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("IndirectCull Pipeline Layout"),
+                bind_group_layouts: &[&layouts.indirect_cull],
+                immediate_size: 0,
+            });
+
         let ssao_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("SSAO Pipeline Layout"),
             bind_group_layouts: &[&layouts.ssao],
@@ -98,6 +152,40 @@ impl RenderState {
                 immediate_size: 0,
             });
 
+        let fog_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Fog Pipeline Layout"),
+            bind_group_layouts: &[&layouts.shared, &layouts.ssao],
+            immediate_size: 0,
+        });
+
+        let fog_msaa_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Fog MSAA Pipeline Layout"),
+                bind_group_layouts: &[&layouts.shared, &layouts.ssao_msaa],
+                immediate_size: 0,
+            });
+
+        let particle_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Particle Pipeline Layout"),
+                bind_group_layouts: &[&layouts.shared, &layouts.particles],
+                immediate_size: 0,
+            });
+
+        let particle_msaa_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Particle MSAA Pipeline Layout"),
+                bind_group_layouts: &[&layouts.shared, &layouts.particles_msaa],
+                immediate_size: 0,
+            });
+
+        let sprite_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Sprite Pipeline Layout"),
+                bind_group_layouts: &[&layouts.sprites],
+                immediate_size: 0,
+            });
+
         let bloom_pipeline_layout =
             device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                 label: Some("Bloom Pipeline Layout"),
@@ -112,6 +200,13 @@ impl RenderState {
                 immediate_size: 0,
             });
 
+        let debug_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Debug Pipeline Layout"),
+                bind_group_layouts: &[&layouts.debug],
+                immediate_size: 0,
+            });
+
         // 6. Initialize shaders
         let forward_standard_shader = device.create_shader_module(wgpu::include_wgsl!(
             "../../passes/forward/branches/forward_standard.wgsl"
@@ -126,10 +221,22 @@ impl RenderState {
         let light_cull_shader = device.create_shader_module(wgpu::include_wgsl!(
             "../../passes/light_cull/light_cull.wgsl"
         ));
+        let light_cluster_shader = device.create_shader_module(wgpu::include_wgsl!(
+            "../../passes/light_cull/light_cluster.wgsl"
+        ));
+        let indirect_cull_shader = device.create_shader_module(wgpu::include_wgsl!(
+            "../../passes/forward/indirect_cull.wgsl"
+        ));
         let shadow_shader =
             device.create_shader_module(wgpu::include_wgsl!("../../passes/shadow/shadow.wgsl"));
         let outline_shader =
             device.create_shader_module(wgpu::include_wgsl!("../../passes/outline/outline.wgsl"));
+        let outline_edge_shader = device.create_shader_module(wgpu::include_wgsl!(
+            "../../passes/outline/outline_edge.wgsl"
+        ));
+        let outline_edge_msaa_shader = device.create_shader_module(wgpu::include_wgsl!(
+            "../../passes/outline/outline_edge_msaa.wgsl"
+        ));
         let ssao_shader =
             device.create_shader_module(wgpu::include_wgsl!("../../passes/ssao/ssao.wgsl"));
         let ssao_blur_shader =
@@ -138,15 +245,35 @@ impl RenderState {
             device.create_shader_module(wgpu::include_wgsl!("../../passes/ssao/ssao_msaa.wgsl"));
         let ssao_blur_msaa_shader = device
             .create_shader_module(wgpu::include_wgsl!("../../passes/ssao/ssao_blur_msaa.wgsl"));
+        let fog_shader =
+            device.create_shader_module(wgpu::include_wgsl!("../../passes/fog/fog.wgsl"));
+        let fog_msaa_shader =
+            device.create_shader_module(wgpu::include_wgsl!("../../passes/fog/fog_msaa.wgsl"));
+        let particle_shader = device
+            .create_shader_module(wgpu::include_wgsl!("../../passes/particles/particle.wgsl"));
+        let particle_msaa_shader = device.create_shader_module(wgpu::include_wgsl!(
+            "../../passes/particles/particle_msaa.wgsl"
+        ));
+        let sprite_shader =
+            device.create_shader_module(wgpu::include_wgsl!("../../passes/sprites/sprite.wgsl"));
         let bloom_shader =
             device.create_shader_module(wgpu::include_wgsl!("../../passes/bloom/bloom.wgsl"));
         let skybox_shader =
             device.create_shader_module(wgpu::include_wgsl!("../../passes/skybox/skybox.wgsl"));
         let gizmo_shader =
             device.create_shader_module(wgpu::include_wgsl!("../../gizmos/gizmo.wgsl"));
+        let debug_shader =
+            device.create_shader_module(wgpu::include_wgsl!("../../passes/debug/debug.wgsl"));
 
         let post_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("PostProcess Uniform Buffer"),
+            size: 128,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let outline_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Outline Edge Uniform Buffer"),
             size: 96,
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
@@ -166,6 +293,41 @@ impl RenderState {
             mapped_at_creation: false,
         });
 
+        let fog_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Fog Uniform Buffer"),
+            size: crate::core::render::passes::FOG_UNIFORM_SLOT_SIZE * 4,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let particle_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Uniform Buffer"),
+            size: crate::core::render::passes::PARTICLE_UNIFORM_SLOT_SIZE * 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let particle_instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Instance Buffer"),
+            size: std::mem::size_of::<crate::core::resources::ParticleInstance>() as u64 * 1024,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let sprite_instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Sprite Instance Buffer"),
+            size: std::mem::size_of::<crate::core::resources::SpriteInstance>() as u64 * 1024,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let sprite_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Sprite Uniform Buffer"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         let bloom_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Bloom Storage Buffer"),
             size: 32,
@@ -187,37 +349,65 @@ impl RenderState {
             layout_object_pbr: layouts.object_pbr,
             layout_target: layouts.target,
             layout_light_cull: layouts.light_cull,
+            layout_indirect_cull: layouts.indirect_cull,
             layout_ssao: layouts.ssao,
             layout_ssao_blur: layouts.ssao_blur,
             layout_ssao_msaa: layouts.ssao_msaa,
             layout_ssao_blur_msaa: layouts.ssao_blur_msaa,
             layout_bloom: layouts.bloom,
             layout_skybox: layouts.skybox,
+            layout_scene_depth: layouts.scene_depth,
+            layout_scene_depth_msaa: layouts.scene_depth_msaa,
+            layout_particles: layouts.particles,
+            layout_particles_msaa: layouts.particles_msaa,
+            layout_sprites: layouts.sprites,
+            layout_debug: layouts.debug,
             forward_standard_pipeline_layout,
             forward_pbr_pipeline_layout,
+            forward_standard_transparent_pipeline_layout,
+            forward_standard_transparent_msaa_pipeline_layout,
+            forward_pbr_transparent_pipeline_layout,
+            forward_pbr_transparent_msaa_pipeline_layout,
             shadow_pipeline_layout,
             outline_pipeline_layout,
             ssao_pipeline_layout,
             ssao_blur_pipeline_layout,
             ssao_msaa_pipeline_layout,
             ssao_blur_msaa_pipeline_layout,
+            fog_pipeline_layout,
+            fog_msaa_pipeline_layout,
+            particle_pipeline_layout,
+            particle_msaa_pipeline_layout,
+            sprite_pipeline_layout,
             bloom_pipeline_layout,
             skybox_pipeline_layout,
+            debug_pipeline_layout,
             forward_standard_shader,
             forward_pbr_shader,
             post_shader,
             compose_shader,
             outline_shader,
+            outline_edge_shader,
+            outline_edge_msaa_shader,
             ssao_shader,
             ssao_blur_shader,
             ssao_msaa_shader,
             ssao_blur_msaa_shader,
+            fog_shader,
+            fog_msaa_shader,
+            particle_shader,
+            particle_msaa_shader,
+            sprite_shader,
             bloom_shader,
             skybox_shader,
             light_cull_shader,
+            light_cluster_shader,
+            indirect_cull_shader,
             shadow_shader,
             gizmo_shader,
+            debug_shader,
             light_cull_pipeline_layout,
+            indirect_cull_pipeline_layout,
             gizmo_pipeline_layout,
             samplers,
             _fallback_texture: fallbacks.texture,
@@ -226,11 +416,23 @@ impl RenderState {
             fallback_forward_atlas_view: fallbacks.atlas_view,
             _fallback_shadow_texture: fallbacks.shadow_texture,
             fallback_shadow_view: fallbacks.shadow_view,
+            _fallback_cube_texture: fallbacks.cube_texture,
+            fallback_cube_view: fallbacks.cube_view,
+            _ltc_matrix_texture: ltc.matrix_texture,
+            ltc_matrix_view: ltc.matrix_view,
+            _ltc_amplitude_texture: ltc.amplitude_texture,
+            ltc_amplitude_view: ltc.amplitude_view,
         });
 
         self.post_uniform_buffer = Some(post_uniform_buffer);
+        self.outline_uniform_buffer = Some(outline_uniform_buffer);
         self.ssao_uniform_buffer = Some(ssao_uniform_buffer);
         self.ssao_blur_uniform_buffer = Some(ssao_blur_uniform_buffer);
+        self.fog_uniform_buffer = Some(fog_uniform_buffer);
+        self.particle_uniform_buffer = Some(particle_uniform_buffer);
+        self.particle_instance_buffer = Some(particle_instance_buffer);
+        self.sprite_instance_buffer = Some(sprite_instance_buffer);
+        self.sprite_uniform_buffer = Some(sprite_uniform_buffer);
         self.bloom_uniform_buffer = Some(bloom_uniform_buffer);
         self.skybox_uniform_buffer = Some(skybox_uniform_buffer);
     }
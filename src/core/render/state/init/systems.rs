@@ -1,17 +1,21 @@
-use super::super::{BindingSystem, LightCullingSystem, RenderState, SamplerSet};
-use crate::core::resources::shadow::ShadowManager;
-use crate::core::resources::{
-    StorageBufferPool, UniformBufferPool, VertexAllocatorConfig, VertexAllocatorSystem,
+use super::super::{
+    BindingSystem, LightCullingConfig, LightCullingMode, LightCullingSystem, RenderState,
+    SamplerSet, SharedResources,
 };
+use crate::core::resources::shadow::ShadowManager;
+use crate::core::resources::{StorageBufferPool, UNIFORM_POOL_FRAMES_IN_FLIGHT, UniformBufferPool};
 use std::collections::HashMap;
 
 impl RenderState {
-    pub(crate) fn init_core_systems(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
-        self.vertex = Some(VertexAllocatorSystem::new(
-            device,
-            queue,
-            VertexAllocatorConfig::default(),
-        ));
+    pub(crate) fn init_core_systems(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        shared: &mut SharedResources,
+        light_culling: &LightCullingConfig,
+    ) {
+        self.vertex = Some(shared.vertex_allocator(device, queue));
+        self.textures = shared.textures().clone();
 
         let alignment = device.limits().min_uniform_buffer_offset_alignment as u64;
         let storage_alignment = device.limits().min_storage_buffer_offset_alignment as u64;
@@ -19,7 +23,13 @@ impl RenderState {
         // Initialize bindings
         self.bindings = Some(BindingSystem {
             frame_pool: UniformBufferPool::new(device, queue, Some(1), alignment),
-            camera_pool: UniformBufferPool::new(device, queue, Some(128), alignment),
+            camera_pool: UniformBufferPool::new_buffered(
+                device,
+                queue,
+                Some(128),
+                alignment,
+                UNIFORM_POOL_FRAMES_IN_FLIGHT,
+            ),
             model_pool: UniformBufferPool::new(device, queue, Some(2048), alignment),
             instance_pool: StorageBufferPool::new(device, queue, Some(16384), 0),
             outline_instance_pool: StorageBufferPool::new(device, queue, Some(16384), 0),
@@ -29,12 +39,15 @@ impl RenderState {
             material_pbr_pool: UniformBufferPool::new(device, queue, Some(256), alignment),
             material_pbr_inputs: StorageBufferPool::new(device, queue, Some(256), 0),
             bones_pool: StorageBufferPool::new(device, queue, Some(256), 0),
-            shared_group: None,
+            shared_groups: Vec::new(),
             model_bind_group: None,
             outline_model_bind_group: None,
             shadow_model_bind_group: None,
+            material_standard_group: None,
+            material_pbr_group: None,
             pool_versions: HashMap::new(),
             last_with_shadows: false,
+            last_atlas_version: 0,
         });
 
         self.light_system = Some(LightCullingSystem {
@@ -53,6 +66,17 @@ impl RenderState {
             light_count: 0,
             camera_count: 0,
             max_lights_per_camera: 0,
+            config: light_culling.clone(),
+            cluster_cameras: StorageBufferPool::new(device, queue, Some(4), 0),
+            cluster_params_buffer: (light_culling.mode == LightCullingMode::Clustered).then(|| {
+                device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("LightCluster Params"),
+                    size: std::mem::size_of::<u32>() as u64 * 12,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                })
+            }),
+            clusters_ready: false,
             queue: queue.clone(),
         });
 
@@ -60,6 +84,7 @@ impl RenderState {
     }
 
     pub(crate) fn init_samplers(&mut self, device: &wgpu::Device) -> SamplerSet {
+        let lod = &self.sampler_lod;
         SamplerSet {
             point_clamp: device.create_sampler(&wgpu::SamplerDescriptor {
                 label: Some("Sampler Point Clamp"),
@@ -68,6 +93,8 @@ impl RenderState {
                 address_mode_w: wgpu::AddressMode::ClampToEdge,
                 mag_filter: wgpu::FilterMode::Nearest,
                 min_filter: wgpu::FilterMode::Nearest,
+                lod_min_clamp: lod.lod_min_clamp,
+                lod_max_clamp: lod.lod_max_clamp,
                 ..Default::default()
             }),
             linear_clamp: device.create_sampler(&wgpu::SamplerDescriptor {
@@ -77,6 +104,8 @@ impl RenderState {
                 address_mode_w: wgpu::AddressMode::ClampToEdge,
                 mag_filter: wgpu::FilterMode::Linear,
                 min_filter: wgpu::FilterMode::Linear,
+                lod_min_clamp: lod.lod_min_clamp,
+                lod_max_clamp: lod.lod_max_clamp,
                 ..Default::default()
             }),
             point_repeat: device.create_sampler(&wgpu::SamplerDescriptor {
@@ -86,6 +115,8 @@ impl RenderState {
                 address_mode_w: wgpu::AddressMode::Repeat,
                 mag_filter: wgpu::FilterMode::Nearest,
                 min_filter: wgpu::FilterMode::Nearest,
+                lod_min_clamp: lod.lod_min_clamp,
+                lod_max_clamp: lod.lod_max_clamp,
                 ..Default::default()
             }),
             linear_repeat: device.create_sampler(&wgpu::SamplerDescriptor {
@@ -95,6 +126,8 @@ impl RenderState {
                 address_mode_w: wgpu::AddressMode::Repeat,
                 mag_filter: wgpu::FilterMode::Linear,
                 min_filter: wgpu::FilterMode::Linear,
+                lod_min_clamp: lod.lod_min_clamp,
+                lod_max_clamp: lod.lod_max_clamp,
                 ..Default::default()
             }),
             comparison: device.create_sampler(&wgpu::SamplerDescriptor {
@@ -1,3 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// Mip level selection of the material samplers, chosen when the window is created
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SamplerLodConfig {
+    /// Most detailed mip level sampled
+    pub lod_min_clamp: f32,
+    /// Least detailed mip level sampled; keeps the smallest mips out of minified textures
+    pub lod_max_clamp: f32,
+    /// Added to the mip level of material textures; negative values sharpen
+    pub mip_lod_bias: f32,
+}
+
+impl Default for SamplerLodConfig {
+    fn default() -> Self {
+        Self {
+            lod_min_clamp: 0.0,
+            lod_max_clamp: 32.0,
+            mip_lod_bias: 0.0,
+        }
+    }
+}
+
+impl SamplerLodConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.lod_min_clamp >= 0.0 && self.lod_min_clamp <= self.lod_max_clamp) {
+            return Err(format!(
+                "lodMinClamp must be within 0..=lodMaxClamp, got {} and {}",
+                self.lod_min_clamp, self.lod_max_clamp
+            ));
+        }
+        if !(-16.0..=15.99).contains(&self.mip_lod_bias) {
+            return Err(format!(
+                "mipLodBias must be within -16..=15.99, got {}",
+                self.mip_lod_bias
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Collection of standard samplers for various rendering needs
 pub struct SamplerSet {
     pub point_clamp: wgpu::Sampler,
@@ -7,6 +49,17 @@ pub struct SamplerSet {
     pub comparison: wgpu::Sampler,
 }
 
+impl SamplerSet {
+    pub fn get(&self, sampler: crate::core::resources::MaterialSampler) -> &wgpu::Sampler {
+        match sampler {
+            crate::core::resources::MaterialSampler::PointClamp => &self.point_clamp,
+            crate::core::resources::MaterialSampler::LinearClamp => &self.linear_clamp,
+            crate::core::resources::MaterialSampler::PointRepeat => &self.point_repeat,
+            crate::core::resources::MaterialSampler::LinearRepeat => &self.linear_repeat,
+        }
+    }
+}
+
 /// Static GPU resources that are shared across the renderer
 pub struct ResourceLibrary {
     pub layout_shared: wgpu::BindGroupLayout,
@@ -15,37 +68,65 @@ pub struct ResourceLibrary {
     pub layout_object_pbr: wgpu::BindGroupLayout,
     pub layout_target: wgpu::BindGroupLayout,
     pub layout_light_cull: wgpu::BindGroupLayout,
+    pub layout_indirect_cull: wgpu::BindGroupLayout,
     pub layout_ssao: wgpu::BindGroupLayout,
     pub layout_ssao_blur: wgpu::BindGroupLayout,
     pub layout_ssao_msaa: wgpu::BindGroupLayout,
     pub layout_ssao_blur_msaa: wgpu::BindGroupLayout,
     pub layout_bloom: wgpu::BindGroupLayout,
     pub layout_skybox: wgpu::BindGroupLayout,
+    pub layout_scene_depth: wgpu::BindGroupLayout,
+    pub layout_scene_depth_msaa: wgpu::BindGroupLayout,
+    pub layout_particles: wgpu::BindGroupLayout,
+    pub layout_particles_msaa: wgpu::BindGroupLayout,
+    pub layout_sprites: wgpu::BindGroupLayout,
+    pub layout_debug: wgpu::BindGroupLayout,
     pub forward_standard_pipeline_layout: wgpu::PipelineLayout,
     pub forward_pbr_pipeline_layout: wgpu::PipelineLayout,
+    pub forward_standard_transparent_pipeline_layout: wgpu::PipelineLayout,
+    pub forward_standard_transparent_msaa_pipeline_layout: wgpu::PipelineLayout,
+    pub forward_pbr_transparent_pipeline_layout: wgpu::PipelineLayout,
+    pub forward_pbr_transparent_msaa_pipeline_layout: wgpu::PipelineLayout,
     pub shadow_pipeline_layout: wgpu::PipelineLayout,
     pub outline_pipeline_layout: wgpu::PipelineLayout,
     pub ssao_pipeline_layout: wgpu::PipelineLayout,
     pub ssao_blur_pipeline_layout: wgpu::PipelineLayout,
     pub ssao_msaa_pipeline_layout: wgpu::PipelineLayout,
     pub ssao_blur_msaa_pipeline_layout: wgpu::PipelineLayout,
+    pub fog_pipeline_layout: wgpu::PipelineLayout,
+    pub fog_msaa_pipeline_layout: wgpu::PipelineLayout,
+    pub particle_pipeline_layout: wgpu::PipelineLayout,
+    pub particle_msaa_pipeline_layout: wgpu::PipelineLayout,
+    pub sprite_pipeline_layout: wgpu::PipelineLayout,
     pub bloom_pipeline_layout: wgpu::PipelineLayout,
     pub skybox_pipeline_layout: wgpu::PipelineLayout,
+    pub debug_pipeline_layout: wgpu::PipelineLayout,
     pub forward_standard_shader: wgpu::ShaderModule,
     pub forward_pbr_shader: wgpu::ShaderModule,
     pub post_shader: wgpu::ShaderModule,
     pub compose_shader: wgpu::ShaderModule,
     pub outline_shader: wgpu::ShaderModule,
+    pub outline_edge_shader: wgpu::ShaderModule,
+    pub outline_edge_msaa_shader: wgpu::ShaderModule,
     pub ssao_shader: wgpu::ShaderModule,
     pub ssao_blur_shader: wgpu::ShaderModule,
     pub ssao_msaa_shader: wgpu::ShaderModule,
     pub ssao_blur_msaa_shader: wgpu::ShaderModule,
+    pub fog_shader: wgpu::ShaderModule,
+    pub fog_msaa_shader: wgpu::ShaderModule,
+    pub particle_shader: wgpu::ShaderModule,
+    pub particle_msaa_shader: wgpu::ShaderModule,
+    pub sprite_shader: wgpu::ShaderModule,
     pub bloom_shader: wgpu::ShaderModule,
     pub skybox_shader: wgpu::ShaderModule,
     pub light_cull_shader: wgpu::ShaderModule,
+    pub light_cluster_shader: wgpu::ShaderModule,
+    pub indirect_cull_shader: wgpu::ShaderModule,
     pub shadow_shader: wgpu::ShaderModule,
     pub gizmo_shader: wgpu::ShaderModule,
+    pub debug_shader: wgpu::ShaderModule,
     pub light_cull_pipeline_layout: wgpu::PipelineLayout,
+    pub indirect_cull_pipeline_layout: wgpu::PipelineLayout,
     pub gizmo_pipeline_layout: wgpu::PipelineLayout,
     pub samplers: SamplerSet,
     pub _fallback_texture: wgpu::Texture,
@@ -54,4 +135,10 @@ pub struct ResourceLibrary {
     pub fallback_forward_atlas_view: wgpu::TextureView,
     pub _fallback_shadow_texture: wgpu::Texture,
     pub fallback_shadow_view: wgpu::TextureView,
+    pub _fallback_cube_texture: wgpu::Texture,
+    pub fallback_cube_view: wgpu::TextureView,
+    pub _ltc_matrix_texture: wgpu::Texture,
+    pub ltc_matrix_view: wgpu::TextureView,
+    pub _ltc_amplitude_texture: wgpu::Texture,
+    pub ltc_amplitude_view: wgpu::TextureView,
 }
@@ -14,11 +14,6 @@ use crate::core::resources::{
 use std::collections::HashMap;
 
 impl RenderState {
-    const VERTEX_COMPACT_FRAME_INTERVAL: u64 = 120;
-    const VERTEX_COMPACT_THRESHOLD: f32 = 0.25;
-    const VERTEX_COMPACT_SLACK_RATIO: f32 = 0.3;
-    const VERTEX_COMPACT_MIN_DEAD_BYTES: u64 = 256 * 1024;
-
     /// Create a new RenderState with empty systems
     #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
     pub fn new(_surface_format: wgpu::TextureFormat) -> Self {
@@ -38,28 +33,49 @@ impl RenderState {
                 lights: HashMap::new(),
                 materials_standard,
                 materials_pbr: HashMap::new(),
-                textures: HashMap::new(),
-                forward_atlas_entries: HashMap::new(),
+                particle_systems: HashMap::new(),
+                sprite_batches: HashMap::new(),
             },
             bindings: None,
             library: None,
             vertex: None,
+            textures: Default::default(),
             light_system: None,
+            indirect: None,
+            bindless: None,
             gizmos: GizmoSystem::new(),
             shadow: None,
-            forward_atlas: None,
             cache: RenderCache::new(),
             forward_depth_target: None,
             forward_msaa_target: None,
             forward_emissive_msaa_target: None,
             post_uniform_buffer: None,
+            outline_uniform_buffer: None,
             ssao_uniform_buffer: None,
             ssao_blur_uniform_buffer: None,
+            fog_uniform_buffer: None,
+            particle_uniform_buffer: None,
+            particle_instance_buffer: None,
+            sprite_instance_buffer: None,
+            sprite_uniform_buffer: None,
+            compose_uniform_buffer: None,
             bloom_uniform_buffer: None,
             skybox_uniform_buffer: None,
             collector: DrawCollector::default(),
+            debug: crate::core::render::state::DebugOverlay::new(),
+            transparent_background: false,
+            clear_color: None,
+            layer_visibility: u32::MAX,
+            fit_mode: crate::core::resources::FitMode::default(),
+            fit_bar_color: glam::Vec4::W,
+            default_sampler: crate::core::resources::MaterialSampler::default(),
+            sampler_lod: crate::core::render::state::SamplerLodConfig::default(),
+            dynamic_resolution: crate::core::render::state::DynamicResolution::default(),
+            upscaler: crate::core::render::state::UpscalerConfig::default(),
+            screenshot: crate::core::render::screenshot::ScreenshotCapture::new(),
             skinning: crate::core::render::state::SkinningSystem::default(),
             render_graph: crate::core::render::graph::RenderGraphState::new(),
+            custom_passes: crate::core::render::state::CustomPassSystem::default(),
             environment: crate::core::resources::EnvironmentConfig::default(),
             environment_is_configured: false,
         }
@@ -79,41 +95,56 @@ impl RenderState {
             ),
         );
         self.scene.materials_pbr.clear();
-        self.scene.textures.clear();
-        self.scene.forward_atlas_entries.clear();
+        self.scene.particle_systems.clear();
+        self.scene.sprite_batches.clear();
         self.bindings = None;
         self.library = None;
         self.vertex = None;
+        self.textures = Default::default();
         self.light_system = None;
+        self.indirect = None;
+        self.bindless = None;
         self.shadow = None;
-        self.forward_atlas = None;
         self.cache.clear();
         self.forward_depth_target = None;
         self.forward_msaa_target = None;
         self.forward_emissive_msaa_target = None;
         self.post_uniform_buffer = None;
+        self.outline_uniform_buffer = None;
         self.ssao_uniform_buffer = None;
         self.ssao_blur_uniform_buffer = None;
+        self.fog_uniform_buffer = None;
+        self.particle_uniform_buffer = None;
+        self.particle_instance_buffer = None;
+        self.sprite_instance_buffer = None;
+        self.sprite_uniform_buffer = None;
+        self.compose_uniform_buffer = None;
         self.bloom_uniform_buffer = None;
         self.skybox_uniform_buffer = None;
         self.skinning.clear();
         self.render_graph.reset_to_fallback();
+        self.custom_passes.clear();
         self.environment = crate::core::resources::EnvironmentConfig::default();
         self.environment_is_configured = false;
     }
 
-    pub fn begin_frame(&mut self, frame_index: u64) {
-        if let Some(vertex) = self.vertex.as_mut() {
-            vertex.begin_frame(frame_index);
-            if frame_index % Self::VERTEX_COMPACT_FRAME_INTERVAL == 0 {
-                vertex.maybe_compact_all(
-                    frame_index,
-                    Self::VERTEX_COMPACT_THRESHOLD,
-                    Self::VERTEX_COMPACT_SLACK_RATIO,
-                    Self::VERTEX_COMPACT_MIN_DEAD_BYTES,
-                );
-            }
+    /// Uploads batched pool writes. Call right before submitting this state's work.
+    pub fn flush_uploads(&mut self) {
+        if let Some(bindings) = self.bindings.as_mut() {
+            bindings.flush();
+        }
+        if let Some(light_system) = self.light_system.as_mut() {
+            light_system.flush();
+        }
+        if let Some(shadow) = self.shadow.as_mut() {
+            shadow.flush();
+        }
+        if let Some(indirect) = self.indirect.as_mut() {
+            indirect.flush();
         }
+    }
+
+    pub fn begin_frame(&mut self, frame_index: u64) {
         if let Some(bindings) = self.bindings.as_mut() {
             bindings.frame_pool.begin_frame(frame_index);
             bindings.camera_pool.begin_frame(frame_index);
@@ -132,11 +163,17 @@ impl RenderState {
             light_system.visible_indices.begin_frame(frame_index);
             light_system.visible_counts.begin_frame(frame_index);
             light_system.camera_frustums.begin_frame(frame_index);
+            light_system.cluster_cameras.begin_frame(frame_index);
             light_system.light_params.begin_frame(frame_index);
         }
         if let Some(shadow) = self.shadow.as_mut() {
             shadow.begin_frame(frame_index);
         }
+        if let Some(indirect) = self.indirect.as_mut() {
+            indirect.begin_frame(frame_index);
+        }
+        // Windows that skipped rendering still upload, so pending writes stay bounded
+        self.flush_uploads();
         self.gizmos.clear();
         self.cache.gc(frame_index);
     }
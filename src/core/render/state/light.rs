@@ -1,10 +1,79 @@
-use crate::core::resources::{LightComponent, StorageBufferPool, UniformBufferPool};
+use glam::UVec3;
+use serde::{Deserialize, Serialize};
+
+use crate::core::resources::{
+    CameraComponent, LightComponent, StorageBufferPool, UniformBufferPool,
+};
 
 #[repr(C)]
 #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct LightDrawParams {
     pub camera_index: u32,
     pub max_lights_per_camera: u32,
+    /// 1 when the camera shades from its froxel lists, 0 for its per-camera list
+    pub clustered: u32,
+    pub max_lights_per_cluster: u32,
+    /// Froxels across, down and in depth; `w` is the froxel count of one camera
+    pub cluster_dims: [u32; 4],
+    /// Where this camera's froxel counts start in `visible_counts`
+    pub cluster_count_base: u32,
+    /// Where this camera's froxel lists start in `visible_indices`
+    pub cluster_index_base: u32,
+    pub _padding: [u32; 2],
+}
+
+/// How lights are binned for the forward pass, chosen when the window is created
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LightCullingMode {
+    /// One list per camera, culled against the camera frustum
+    #[default]
+    Frustum,
+    /// One list per froxel (screen tile and depth slice) on top of the per-camera lists
+    Clustered,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LightCullingConfig {
+    pub mode: LightCullingMode,
+    /// Froxels across, down and in depth. Depth slices grow exponentially from near to far.
+    pub cluster_dims: UVec3,
+    /// Lights one froxel keeps at most; further lights in it are dropped
+    pub max_lights_per_cluster: u32,
+}
+
+impl Default for LightCullingConfig {
+    fn default() -> Self {
+        Self {
+            mode: LightCullingMode::Frustum,
+            cluster_dims: UVec3::new(16, 9, 24),
+            max_lights_per_cluster: 64,
+        }
+    }
+}
+
+impl LightCullingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        let dims = self.cluster_dims;
+        if dims.x == 0 || dims.y == 0 || dims.z == 0 || dims.x > 64 || dims.y > 64 || dims.z > 128 {
+            return Err(format!(
+                "clusterDims must be within 1..=64 x 1..=64 x 1..=128, got {}x{}x{}",
+                dims.x, dims.y, dims.z
+            ));
+        }
+        if !(1..=256).contains(&self.max_lights_per_cluster) {
+            return Err(format!(
+                "maxLightsPerCluster must be within 1..=256, got {}",
+                self.max_lights_per_cluster
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn clusters_per_camera(&self) -> u32 {
+        self.cluster_dims.x * self.cluster_dims.y * self.cluster_dims.z
+    }
 }
 
 #[repr(C)]
@@ -25,19 +94,53 @@ pub struct LightCullingSystem {
     pub light_count: usize,
     pub camera_count: u32,
     pub max_lights_per_camera: u32,
+    pub config: LightCullingConfig,
+    /// Cameras the cluster pass builds froxels for, in camera order
+    pub cluster_cameras: StorageBufferPool<CameraComponent>,
+    pub cluster_params_buffer: Option<wgpu::Buffer>,
+    /// Set when the cluster pass filled the froxel lists this frame
+    pub clusters_ready: bool,
     pub queue: wgpu::Queue,
 }
 
 impl LightCullingSystem {
     pub fn write_draw_params(&mut self, camera_index: u32, max_lights_per_camera: u32) {
+        let clusters = self.config.clusters_per_camera();
+        let dims = self.config.cluster_dims;
         let params = LightDrawParams {
             camera_index,
             max_lights_per_camera,
+            clustered: self.clusters_ready as u32,
+            max_lights_per_cluster: self.config.max_lights_per_cluster,
+            cluster_dims: [dims.x, dims.y, dims.z, clusters],
+            cluster_count_base: self.cluster_count_base() + camera_index * clusters,
+            cluster_index_base: self.cluster_index_base()
+                + camera_index * clusters * self.config.max_lights_per_cluster,
+            _padding: [0; 2],
         };
         self.light_params.write(camera_index, &params);
     }
 
+    /// Froxel counts follow the per-camera counts in `visible_counts`
+    pub fn cluster_count_base(&self) -> u32 {
+        self.camera_count
+    }
+
+    /// Froxel lists follow the per-camera lists in `visible_indices`
+    pub fn cluster_index_base(&self) -> u32 {
+        self.camera_count * self.light_count as u32
+    }
+
     pub fn draw_params_offset(&self, camera_index: u32) -> u64 {
         self.light_params.get_offset(camera_index)
     }
+
+    pub fn flush(&mut self) {
+        self.lights.flush();
+        self.visible_indices.flush();
+        self.visible_counts.flush();
+        self.camera_frustums.flush();
+        self.cluster_cameras.flush();
+        self.light_params.flush();
+    }
 }
@@ -1,26 +1,39 @@
 pub mod binding;
+pub mod bindless;
 pub mod collector;
+pub mod custom_pass;
+pub mod debug;
+pub mod indirect;
 pub mod init;
 pub mod library;
 pub mod lifecycle;
 pub mod light;
 pub mod prepare;
+pub mod resolution;
 pub mod scene;
+pub mod shared;
 pub mod skinning;
 
 use crate::core::render::cache::RenderCache;
 use crate::core::render::gizmos::GizmoSystem;
 use crate::core::resources::EnvironmentConfig;
-use crate::core::resources::VertexAllocatorSystem;
 use crate::core::resources::shadow::ShadowManager;
 
 pub use self::binding::BindingSystem;
-pub use self::collector::{DrawCollector, DrawItem};
-pub use self::library::ResourceLibrary;
+pub use self::bindless::{BindlessTextureSystem, MaterialBindingConfig};
+pub use self::collector::{DrawCollector, DrawItem, DrawStats};
+pub use self::custom_pass::{
+    CUSTOM_PASS_FORMAT, CustomPassInsertPoint, CustomPassRecord, CustomPassSystem,
+};
+pub use self::debug::{DebugOverlay, DebugRenderMode};
+pub use self::indirect::{IndirectDrawSystem, IndirectDrawTemplate};
 #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
 pub use self::library::SamplerSet;
-pub use self::light::{FrustumPlane, LightCullingSystem};
+pub use self::library::{ResourceLibrary, SamplerLodConfig};
+pub use self::light::{FrustumPlane, LightCullingConfig, LightCullingMode, LightCullingSystem};
+pub use self::resolution::{DynamicResolution, DynamicResolutionConfig, UpscalerConfig};
 pub use self::scene::RenderScene;
+pub use self::shared::{SharedResources, SharedTextureStore, SharedVertexAllocator};
 pub use self::skinning::SkinningSystem;
 pub use crate::core::render::graph::RenderGraphState;
 
@@ -28,27 +41,76 @@ pub struct RenderState {
     pub scene: RenderScene,
     pub bindings: Option<BindingSystem>,
     pub library: Option<ResourceLibrary>,
-    pub vertex: Option<VertexAllocatorSystem>,
+    /// Handle to the engine's `SharedResources` allocator
+    pub vertex: Option<SharedVertexAllocator>,
+    /// Handle to the engine's `SharedResources` texture store
+    pub textures: SharedTextureStore,
     pub light_system: Option<LightCullingSystem>,
+    /// GPU-culled indirect forward draws; `None` when the adapter lacks indirect execution
+    pub indirect: Option<IndirectDrawSystem>,
+    /// Global texture array of forward materials; `None` binds textures per material
+    pub bindless: Option<BindlessTextureSystem>,
     pub gizmos: GizmoSystem,
     pub shadow: Option<ShadowManager>,
-    pub forward_atlas: Option<crate::core::resources::ForwardAtlasSystem>,
     pub cache: RenderCache,
     pub forward_depth_target: Option<crate::core::resources::RenderTarget>,
     pub forward_msaa_target: Option<crate::core::resources::RenderTarget>,
     pub forward_emissive_msaa_target: Option<crate::core::resources::RenderTarget>,
     pub post_uniform_buffer: Option<wgpu::Buffer>,
+    pub outline_uniform_buffer: Option<wgpu::Buffer>,
     pub ssao_uniform_buffer: Option<wgpu::Buffer>,
     pub ssao_blur_uniform_buffer: Option<wgpu::Buffer>,
+    pub fog_uniform_buffer: Option<wgpu::Buffer>,
+    pub particle_uniform_buffer: Option<wgpu::Buffer>,
+    pub particle_instance_buffer: Option<wgpu::Buffer>,
+    pub sprite_instance_buffer: Option<wgpu::Buffer>,
+    pub sprite_uniform_buffer: Option<wgpu::Buffer>,
+    /// One `ComposeUniform` per composed camera, at uniform offset alignment
+    pub compose_uniform_buffer: Option<wgpu::Buffer>,
     pub bloom_uniform_buffer: Option<wgpu::Buffer>,
     pub skybox_uniform_buffer: Option<wgpu::Buffer>,
     pub environment: EnvironmentConfig,
     pub environment_is_configured: bool,
     pub skinning: SkinningSystem,
     pub render_graph: RenderGraphState,
+    /// Host fullscreen passes run at fixed points of the render graph
+    pub custom_passes: CustomPassSystem,
 
     /// Per-frame collector for draw calls, reused to avoid allocations.
     pub collector: DrawCollector,
+
+    /// Optional debug visualization drawn after compose
+    pub debug: DebugOverlay,
+
+    /// Clear to alpha 0 so uncovered pixels show what is behind a transparent window
+    pub transparent_background: bool,
+
+    /// Window background behind all cameras; `None` clears to black (or alpha 0 when transparent)
+    pub clear_color: Option<glam::Vec4>,
+
+    /// Layers drawn in this window, ANDed into every camera's `layer_mask`
+    pub layer_visibility: u32,
+
+    /// How cameras with a pinned aspect fit their viewport
+    pub fit_mode: crate::core::resources::FitMode,
+
+    /// Color of the bars `FitMode::Contain` leaves around the image
+    pub fit_bar_color: glam::Vec4,
+
+    /// Sampler of material texture slots and sprites that do not pick one
+    pub default_sampler: crate::core::resources::MaterialSampler,
+
+    /// Mip clamps of the point/linear samplers and mip bias of material textures
+    pub sampler_lod: SamplerLodConfig,
+
+    /// Adaptive factor on every camera's `render_scale`
+    pub dynamic_resolution: DynamicResolution,
+
+    /// Filter of cameras rendered below their viewport resolution
+    pub upscaler: UpscalerConfig,
+
+    /// Pending `CmdWindowScreenshot` capture
+    pub screenshot: crate::core::render::screenshot::ScreenshotCapture,
 }
 
 impl RenderState {
@@ -68,82 +130,19 @@ impl RenderState {
         self.forward_emissive_msaa_target = None;
 
         let mut any_camera_dirty = false;
+        let window_scale = self.dynamic_resolution.scale;
         for record in self.scene.cameras.values_mut() {
-            let (target_width, target_height) = record
+            let area = record
                 .view_position
                 .as_ref()
                 .map(|vp| vp.resolve_size(width, height))
                 .unwrap_or((width, height));
+            let (target_width, target_height) = record.scaled_target_size(area, window_scale);
 
-            crate::core::resources::ensure_render_target(
-                device,
-                &mut record.render_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
-            );
-            crate::core::resources::ensure_render_target(
-                device,
-                &mut record.emissive_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
-            );
-            crate::core::resources::ensure_render_target(
-                device,
-                &mut record.post_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
-            );
-            crate::core::resources::ensure_render_target(
-                device,
-                &mut record.outline_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba8Unorm,
-            );
-            crate::core::resources::ensure_render_target(
-                device,
-                &mut record.ssao_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
-            );
-            crate::core::resources::ensure_render_target(
-                device,
-                &mut record.ssao_blur_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
-            );
-            crate::core::resources::ensure_render_target(
-                device,
-                &mut record.bloom_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
-            );
-            for (level, target) in record.bloom_chain.iter_mut().enumerate() {
-                let level_width = crate::core::render::bloom_chain_size(target_width, level);
-                let level_height = crate::core::render::bloom_chain_size(target_height, level);
-                crate::core::resources::ensure_render_target(
-                    device,
-                    target,
-                    level_width,
-                    level_height,
-                    wgpu::TextureFormat::Rgba16Float,
-                );
-            }
+            record.ensure_targets(device, target_width, target_height, &self.environment.post);
 
-            record.data.update(
-                None,
-                None,
-                None,
-                None,
-                (target_width, target_height),
-                record.ortho_scale,
-            );
+            let lens = record.lens(area);
+            record.data.update(None, None, None, None, lens);
             record.mark_dirty();
             any_camera_dirty = true;
         }
@@ -8,7 +8,8 @@ impl RenderState {
         let light_system = self.light_system.as_ref().unwrap();
         let shadow_manager = self.shadow.as_ref().unwrap();
 
-        let forward_atlas_view = match self.forward_atlas.as_ref() {
+        let textures = self.textures.borrow();
+        let forward_atlas_view = match textures.atlas.as_ref() {
             Some(atlas) => atlas.view(),
             None => &library.fallback_forward_atlas_view,
         };
@@ -19,141 +20,160 @@ impl RenderState {
             &library.fallback_shadow_view
         };
 
-        if bindings.shared_group.is_none() {
-            bindings.shared_group = Some(
-                device.create_bind_group(&wgpu::BindGroupDescriptor {
-                    label: Some(&format!(
-                        "BindGroup Shared (Consolidated, shadows={})",
-                        with_shadows
-                    )),
-                    layout: &library.layout_shared,
-                    entries: &[
-                        wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                                buffer: bindings.frame_pool.buffer(),
-                                offset: 0,
-                                size: Some(
-                                    std::num::NonZeroU64::new(
-                                        std::mem::size_of::<FrameComponent>() as u64,
-                                    )
-                                    .unwrap(),
+        // One group per camera pool copy; the frame picks the copy it writes
+        if bindings.shared_groups.is_empty() {
+            bindings.shared_groups = (0..bindings.camera_pool.copy_count())
+                .map(|copy| {
+                    device.create_bind_group(&wgpu::BindGroupDescriptor {
+                        label: Some(&format!(
+                            "BindGroup Shared (Consolidated, shadows={})",
+                            with_shadows
+                        )),
+                        layout: &library.layout_shared,
+                        entries: &[
+                            wgpu::BindGroupEntry {
+                                binding: 0,
+                                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                    buffer: bindings.frame_pool.buffer(),
+                                    offset: 0,
+                                    size: Some(
+                                        std::num::NonZeroU64::new(
+                                            std::mem::size_of::<FrameComponent>() as u64,
+                                        )
+                                        .unwrap(),
+                                    ),
+                                }),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 1,
+                                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                    buffer: bindings.camera_pool.copy_buffer(copy),
+                                    offset: 0,
+                                    size: Some(
+                                        std::num::NonZeroU64::new(
+                                            std::mem::size_of::<CameraComponent>() as u64,
+                                        )
+                                        .unwrap(),
+                                    ),
+                                }),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 2,
+                                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                    buffer: light_system.light_params.buffer(),
+                                    offset: 0,
+                                    size: Some(
+                                        std::num::NonZeroU64::new(std::mem::size_of::<
+                                            super::super::light::LightDrawParams,
+                                        >(
+                                        )
+                                            as u64)
+                                        .unwrap(),
+                                    ),
+                                }),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 3,
+                                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                    buffer: light_system.lights.buffer(),
+                                    offset: 0,
+                                    size: None,
+                                }),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 4,
+                                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                    buffer: light_system.visible_indices.buffer(),
+                                    offset: 0,
+                                    size: None,
+                                }),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 5,
+                                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                    buffer: light_system.visible_counts.buffer(),
+                                    offset: 0,
+                                    size: None,
+                                }),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 6,
+                                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                    buffer: shadow_manager.params_pool.buffer(),
+                                    offset: 0,
+                                    size: None,
+                                }),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 7,
+                                resource: wgpu::BindingResource::TextureView(shadow_atlas_view),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 8,
+                                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                    buffer: shadow_manager.page_table.buffer(),
+                                    offset: 0,
+                                    size: None,
+                                }),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 9,
+                                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                    buffer: shadow_manager.point_light_vp.buffer(),
+                                    offset: 0,
+                                    size: None,
+                                }),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 10,
+                                resource: wgpu::BindingResource::Sampler(
+                                    &library.samplers.point_clamp,
                                 ),
-                            }),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 1,
-                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                                buffer: bindings.camera_pool.buffer(),
-                                offset: 0,
-                                size: Some(
-                                    std::num::NonZeroU64::new(
-                                        std::mem::size_of::<CameraComponent>() as u64,
-                                    )
-                                    .unwrap(),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 11,
+                                resource: wgpu::BindingResource::Sampler(
+                                    &library.samplers.linear_clamp,
                                 ),
-                            }),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 2,
-                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                                buffer: light_system.light_params.buffer(),
-                                offset: 0,
-                                size: Some(
-                                    std::num::NonZeroU64::new(std::mem::size_of::<
-                                        super::super::light::LightDrawParams,
-                                    >(
-                                    )
-                                        as u64)
-                                    .unwrap(),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 12,
+                                resource: wgpu::BindingResource::Sampler(
+                                    &library.samplers.point_repeat,
                                 ),
-                            }),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 3,
-                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                                buffer: light_system.lights.buffer(),
-                                offset: 0,
-                                size: None,
-                            }),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 4,
-                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                                buffer: light_system.visible_indices.buffer(),
-                                offset: 0,
-                                size: None,
-                            }),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 5,
-                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                                buffer: light_system.visible_counts.buffer(),
-                                offset: 0,
-                                size: None,
-                            }),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 6,
-                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                                buffer: shadow_manager.params_pool.buffer(),
-                                offset: 0,
-                                size: None,
-                            }),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 7,
-                            resource: wgpu::BindingResource::TextureView(shadow_atlas_view),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 8,
-                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                                buffer: shadow_manager.page_table.buffer(),
-                                offset: 0,
-                                size: None,
-                            }),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 9,
-                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                                buffer: shadow_manager.point_light_vp.buffer(),
-                                offset: 0,
-                                size: None,
-                            }),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 10,
-                            resource: wgpu::BindingResource::Sampler(&library.samplers.point_clamp),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 11,
-                            resource: wgpu::BindingResource::Sampler(
-                                &library.samplers.linear_clamp,
-                            ),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 12,
-                            resource: wgpu::BindingResource::Sampler(
-                                &library.samplers.point_repeat,
-                            ),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 13,
-                            resource: wgpu::BindingResource::Sampler(
-                                &library.samplers.linear_repeat,
-                            ),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 14,
-                            resource: wgpu::BindingResource::Sampler(&library.samplers.comparison),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 15,
-                            resource: wgpu::BindingResource::TextureView(forward_atlas_view),
-                        },
-                    ],
-                }),
-            );
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 13,
+                                resource: wgpu::BindingResource::Sampler(
+                                    &library.samplers.linear_repeat,
+                                ),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 14,
+                                resource: wgpu::BindingResource::Sampler(
+                                    &library.samplers.comparison,
+                                ),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 15,
+                                resource: wgpu::BindingResource::TextureView(forward_atlas_view),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 16,
+                                resource: wgpu::BindingResource::TextureView(
+                                    &library.ltc_matrix_view,
+                                ),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 17,
+                                resource: wgpu::BindingResource::TextureView(
+                                    &library.ltc_amplitude_view,
+                                ),
+                            },
+                        ],
+                    })
+                })
+                .collect();
         }
 
         // 3. Create Model Bind Group (Group 1)
@@ -14,7 +14,7 @@ impl RenderState {
             let mut shadow_counter = 0u32;
             for (_, record) in sorted_lights {
                 let mut light_data = record.data;
-                if record.cast_shadow {
+                if record.casts_shadow() {
                     light_data.shadow_index = shadow_counter;
                     shadow_counter += 1;
                 } else {
@@ -67,6 +67,27 @@ impl RenderState {
         }
         light_system.camera_count = sorted_cameras.len() as u32;
 
+        if light_system.cluster_params_buffer.is_some() {
+            // Froxel lists are appended after the per-camera ones; grow both before the
+            // shared bind group is rebuilt for this frame
+            let clusters = light_system.camera_count * light_system.config.clusters_per_camera();
+            let u32_size = std::mem::size_of::<u32>() as u64;
+            light_system
+                .visible_counts
+                .reserve_bytes((light_system.cluster_count_base() + clusters) as u64 * u32_size);
+            light_system.visible_indices.reserve_bytes(
+                (light_system.cluster_index_base() as u64
+                    + clusters as u64 * light_system.config.max_lights_per_cluster as u64)
+                    * u32_size,
+            );
+
+            let cameras: Vec<_> = sorted_cameras
+                .iter()
+                .map(|(_, record)| record.data)
+                .collect();
+            light_system.cluster_cameras.write_slice(0, &cameras);
+        }
+
         let max_lights = 128; // TBD: make configurable or dynamic
         light_system.max_lights_per_camera = max_lights;
 
@@ -1,4 +1,4 @@
-use super::super::RenderState;
+use super::super::{BindingSystem, RenderState};
 use crate::core::resources::{
     MaterialPbrParams, MaterialStandardParams, PBR_INVALID_SLOT, PBR_TEXTURE_SLOTS,
     STANDARD_INVALID_SLOT, STANDARD_TEXTURE_SLOTS, TEX_SOURCE_ATLAS, TEX_SOURCE_INVALID,
@@ -9,6 +9,8 @@ impl RenderState {
     pub(crate) fn prepare_materials(&mut self, device: &wgpu::Device) {
         let bindings = self.bindings.as_mut().unwrap();
         let library = self.library.as_ref().unwrap();
+        let bindless = self.bindless.as_ref();
+        let textures = self.textures.borrow();
 
         for (id, record) in &mut self.scene.materials_standard {
             let mut atlas_changed = false;
@@ -32,12 +34,14 @@ impl RenderState {
                 let mut desired_scale_bias = glam::Vec4::new(1.0, 1.0, 0.0, 0.0);
 
                 if tex_id != STANDARD_INVALID_SLOT {
-                    if let Some(entry) = self.scene.forward_atlas_entries.get(&tex_id) {
+                    if let Some(entry) = textures.atlas_entries.get(&tex_id) {
                         desired_source = TEX_SOURCE_ATLAS;
                         desired_layer = entry.layer;
                         desired_scale_bias = entry.uv_scale_bias;
                     } else {
                         desired_source = TEX_SOURCE_STANDALONE;
+                        // Bindless materials keep the global array index in the layer lane
+                        desired_layer = bindless.map_or(0, |b| b.index_of(tex_id));
                     }
                 }
 
@@ -88,7 +92,7 @@ impl RenderState {
             }
 
             // Update Bind Group
-            if record.bind_group.is_none() {
+            if bindless.is_none() && record.bind_group.is_none() {
                 let mut entries = Vec::new();
                 entries.push(wgpu::BindGroupEntry {
                     binding: 0,
@@ -123,9 +127,10 @@ impl RenderState {
                 for slot in 0..STANDARD_TEXTURE_SLOTS {
                     let tex_id = record.texture_ids[slot];
                     let view = if tex_id != STANDARD_INVALID_SLOT {
-                        self.scene
-                            .textures
+                        textures
+                            .records
                             .get(&tex_id)
+                            .filter(|t| t.view_dimension == wgpu::TextureViewDimension::D2)
                             .map(|t| &t.view)
                             .unwrap_or(&library.fallback_view)
                     } else {
@@ -175,12 +180,13 @@ impl RenderState {
                 let mut desired_scale_bias = glam::Vec4::new(1.0, 1.0, 0.0, 0.0);
 
                 if tex_id != PBR_INVALID_SLOT {
-                    if let Some(entry) = self.scene.forward_atlas_entries.get(&tex_id) {
+                    if let Some(entry) = textures.atlas_entries.get(&tex_id) {
                         desired_source = TEX_SOURCE_ATLAS;
                         desired_layer = entry.layer;
                         desired_scale_bias = entry.uv_scale_bias;
                     } else {
                         desired_source = TEX_SOURCE_STANDALONE;
+                        desired_layer = bindless.map_or(0, |b| b.index_of(tex_id));
                     }
                 }
 
@@ -231,7 +237,7 @@ impl RenderState {
             }
 
             // Update Bind Group
-            if record.bind_group.is_none() {
+            if bindless.is_none() && record.bind_group.is_none() {
                 let mut entries = Vec::new();
                 entries.push(wgpu::BindGroupEntry {
                     binding: 0,
@@ -266,9 +272,10 @@ impl RenderState {
                 for slot in 0..PBR_TEXTURE_SLOTS {
                     let tex_id = record.texture_ids[slot];
                     let view = if tex_id != PBR_INVALID_SLOT {
-                        self.scene
-                            .textures
+                        textures
+                            .records
                             .get(&tex_id)
+                            .filter(|t| t.view_dimension == wgpu::TextureViewDimension::D2)
                             .map(|t| &t.view)
                             .unwrap_or(&library.fallback_view)
                     } else {
@@ -295,5 +302,70 @@ impl RenderState {
                 }));
             }
         }
+
+        // Bindless materials share one group per type and select their params by offset
+        if bindless.is_some() {
+            if bindings.material_standard_group.is_none() {
+                let group = shared_material_group(
+                    device,
+                    &library.layout_object_standard,
+                    "BindGroup Material Standard (Bindless)",
+                    bindings,
+                    bindings.material_standard_pool.buffer(),
+                    std::mem::size_of::<MaterialStandardParams>() as u64,
+                    bindings.material_standard_inputs.buffer(),
+                );
+                bindings.material_standard_group = Some(group);
+            }
+            if bindings.material_pbr_group.is_none() {
+                let group = shared_material_group(
+                    device,
+                    &library.layout_object_pbr,
+                    "BindGroup Material PBR (Bindless)",
+                    bindings,
+                    bindings.material_pbr_pool.buffer(),
+                    std::mem::size_of::<MaterialPbrParams>() as u64,
+                    bindings.material_pbr_inputs.buffer(),
+                );
+                bindings.material_pbr_group = Some(group);
+            }
+        }
     }
 }
+
+fn shared_material_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    label: &str,
+    bindings: &BindingSystem,
+    material_buffer: &wgpu::Buffer,
+    material_size: u64,
+    inputs_buffer: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some(label),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: bindings.instance_pool.buffer().as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: material_buffer,
+                    offset: 0,
+                    size: std::num::NonZeroU64::new(material_size),
+                }),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: inputs_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 11,
+                resource: bindings.bones_pool.buffer().as_entire_binding(),
+            },
+        ],
+    })
+}
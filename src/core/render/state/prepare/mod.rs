@@ -15,6 +15,13 @@ impl RenderState {
         // 0. Pre-prepare lights
         self.prepare_lights(device);
 
+        // Effect targets follow the environment, which may have changed since the last frame
+        for record in self.scene.cameras.values_mut() {
+            if let Some(size) = record.render_target.as_ref().map(|t| t._texture.size()) {
+                record.sync_effect_targets(device, size.width, size.height, &self.environment.post);
+            }
+        }
+
         let bindings = match self.bindings.as_mut() {
             Some(b) => b,
             None => return,
@@ -25,6 +32,10 @@ impl RenderState {
         }
 
         // 1. Upload global data
+        let frame_spec = FrameComponent {
+            mip_bias: self.sampler_lod.mip_lod_bias,
+            ..frame_spec
+        };
         bindings.frame_pool.write(0, &frame_spec);
 
         let mut any_pool_resized = false;
@@ -66,14 +77,23 @@ impl RenderState {
 
         if with_shadows != bindings.last_with_shadows {
             bindings.last_with_shadows = with_shadows;
-            bindings.shared_group = None;
+            bindings.shared_groups.clear();
+        }
+
+        // Any window may create or regrow the shared forward atlas
+        let atlas_version = self.textures.borrow().atlas_version;
+        if atlas_version != bindings.last_atlas_version {
+            bindings.last_atlas_version = atlas_version;
+            bindings.shared_groups.clear();
         }
 
         if any_pool_resized {
-            bindings.shared_group = None;
+            bindings.shared_groups.clear();
             bindings.model_bind_group = None;
             bindings.outline_model_bind_group = None;
             bindings.shadow_model_bind_group = None;
+            bindings.material_standard_group = None;
+            bindings.material_pbr_group = None;
             // Clear material bind groups
             for mat in self.scene.materials_standard.values_mut() {
                 mat.bind_group = None;
@@ -83,9 +103,11 @@ impl RenderState {
             }
         }
 
-        // 2. Upload camera data
+        // 2. Upload camera data. The camera pool rotates buffers every frame and writes only
+        // reach the current one, so every camera is written again.
+        let rewrite_cameras = bindings.camera_pool.is_buffered();
         for (id, record) in &mut self.scene.cameras {
-            if record.is_dirty {
+            if record.is_dirty || rewrite_cameras {
                 bindings.camera_pool.write(*id, &record.data);
                 record.clear_dirty();
             }
@@ -102,7 +124,16 @@ impl RenderState {
             }
         }
 
-        // 4. Update and upload materials
+        // 4. Update the bindless texture array, then upload materials indexing into it
+        if let (Some(bindless), Some(library)) = (self.bindless.as_mut(), self.library.as_ref()) {
+            let textures = self.textures.borrow();
+            bindless.sync(
+                device,
+                &textures.records,
+                textures.version,
+                &library.fallback_view,
+            );
+        }
         self.prepare_materials(device);
 
         // 5. Build/Update bind groups
@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+
+/// Frames averaged before the scale is reconsidered
+const SAMPLE_FRAMES: u32 = 30;
+/// Scale changes snap to this step so small timing noise does not rebuild targets
+const SCALE_STEP: f32 = 0.05;
+/// Frames under this fraction of the target are cheap enough to raise the scale
+const RAISE_THRESHOLD: f32 = 0.8;
+
+/// Adaptive render scale of a window, driven by the measured frame cost
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DynamicResolutionConfig {
+    pub enabled: bool,
+    /// Frame cost the scale aims for, in milliseconds
+    pub target_frame_time_ms: f32,
+    pub min_scale: f32,
+    pub max_scale: f32,
+}
+
+impl Default for DynamicResolutionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_frame_time_ms: 16.6,
+            min_scale: 0.5,
+            max_scale: 1.0,
+        }
+    }
+}
+
+impl DynamicResolutionConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.target_frame_time_ms.is_finite() && self.target_frame_time_ms > 0.0) {
+            return Err(format!(
+                "targetFrameTimeMs must be a positive number, got {}",
+                self.target_frame_time_ms
+            ));
+        }
+        if !(0.1..=2.0).contains(&self.min_scale) || !(0.1..=2.0).contains(&self.max_scale) {
+            return Err(format!(
+                "minScale and maxScale must be within 0.1..=2.0, got {} and {}",
+                self.min_scale, self.max_scale
+            ));
+        }
+        if self.min_scale > self.max_scale {
+            return Err(format!(
+                "minScale ({}) must not exceed maxScale ({})",
+                self.min_scale, self.max_scale
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Filter the compose pass uses to bring cameras rendered below their viewport resolution
+/// back up
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Upscaler {
+    /// One bilinear tap; portable and cheapest
+    #[default]
+    Bilinear,
+    /// FSR1-style edge-adaptive upsampling (EASU) followed by contrast-adaptive
+    /// sharpening (RCAS). Costs an extra target at viewport size per scaled camera.
+    Fsr,
+}
+
+/// How a window upscales cameras whose effective render scale is below 1
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[serde(default, rename_all = "camelCase")]
+pub struct UpscalerConfig {
+    pub upscaler: Upscaler,
+    /// RCAS strength of `Upscaler::Fsr`; 0 skips sharpening, 1 is the strongest
+    pub sharpness: f32,
+}
+
+impl Default for UpscalerConfig {
+    fn default() -> Self {
+        Self {
+            upscaler: Upscaler::Bilinear,
+            sharpness: 0.8,
+        }
+    }
+}
+
+impl UpscalerConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.sharpness) {
+            return Err(format!(
+                "sharpness must be within 0.0..=1.0, got {}",
+                self.sharpness
+            ));
+        }
+        Ok(())
+    }
+
+    /// FSR only pays off when upscaling; downscaled cameras keep the bilinear filter
+    pub fn uses_fsr(&self, effective_scale: f32) -> bool {
+        self.upscaler == Upscaler::Fsr && effective_scale < 1.0
+    }
+}
+
+/// Window-wide factor on every camera's `render_scale`
+#[derive(Debug, Clone)]
+pub struct DynamicResolution {
+    pub config: DynamicResolutionConfig,
+    pub scale: f32,
+    sampled_frames: u32,
+    sampled_ns: u64,
+}
+
+impl Default for DynamicResolution {
+    fn default() -> Self {
+        Self {
+            config: DynamicResolutionConfig::default(),
+            scale: 1.0,
+            sampled_frames: 0,
+            sampled_ns: 0,
+        }
+    }
+}
+
+impl DynamicResolution {
+    /// Applies a new config; returns true when the scale changed and targets must be rebuilt
+    pub fn set_config(&mut self, config: DynamicResolutionConfig) -> bool {
+        let previous = self.scale;
+        self.scale = if config.enabled {
+            self.scale.clamp(config.min_scale, config.max_scale)
+        } else {
+            1.0
+        };
+        self.config = config;
+        self.sampled_frames = 0;
+        self.sampled_ns = 0;
+        self.scale != previous
+    }
+
+    /// Feeds the cost of one frame. Every `SAMPLE_FRAMES` frames the average picks a new
+    /// scale, assuming cost grows with pixel count (the square of the scale).
+    /// Returns true when the scale changed and targets must be rebuilt.
+    pub fn record_frame(&mut self, frame_ns: u64) -> bool {
+        if !self.config.enabled || frame_ns == 0 {
+            return false;
+        }
+        self.sampled_frames += 1;
+        self.sampled_ns = self.sampled_ns.saturating_add(frame_ns);
+        if self.sampled_frames < SAMPLE_FRAMES {
+            return false;
+        }
+
+        let average_ms = self.sampled_ns as f32 / self.sampled_frames as f32 / 1_000_000.0;
+        self.sampled_frames = 0;
+        self.sampled_ns = 0;
+
+        let target_ms = self.config.target_frame_time_ms;
+        if average_ms <= target_ms && average_ms >= target_ms * RAISE_THRESHOLD {
+            return false;
+        }
+        // Snapping down keeps a raised scale under the target
+        let ideal = self.scale * (target_ms / average_ms).sqrt();
+        let next = ((ideal / SCALE_STEP).floor() * SCALE_STEP)
+            .clamp(self.config.min_scale, self.config.max_scale);
+        if (next - self.scale).abs() < SCALE_STEP * 0.5 {
+            return false;
+        }
+        self.scale = next;
+        true
+    }
+}
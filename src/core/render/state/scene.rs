@@ -1,6 +1,6 @@
 use crate::core::resources::{
-    CameraRecord, ForwardAtlasEntry, LightRecord, MaterialPbrRecord, MaterialStandardRecord,
-    ModelRecord, TextureRecord,
+    CameraRecord, LightRecord, MaterialPbrRecord, MaterialStandardRecord, ModelRecord,
+    ParticleSystemRecord, SpriteBatchRecord,
 };
 use std::collections::HashMap;
 
@@ -12,6 +12,6 @@ pub struct RenderScene {
     pub lights: HashMap<u32, LightRecord>,
     pub materials_standard: HashMap<u32, MaterialStandardRecord>,
     pub materials_pbr: HashMap<u32, MaterialPbrRecord>,
-    pub textures: HashMap<u32, TextureRecord>,
-    pub forward_atlas_entries: HashMap<u32, ForwardAtlasEntry>,
+    pub particle_systems: HashMap<u32, ParticleSystemRecord>,
+    pub sprite_batches: HashMap<u32, SpriteBatchRecord>,
 }
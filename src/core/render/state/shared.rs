@@ -0,0 +1,88 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+use crate::core::resources::VertexAllocatorConfig;
+use crate::core::resources::{TextureStore, VertexAllocatorSystem};
+
+/// One vertex allocator for every window on the device; each `RenderState` holds a handle
+pub type SharedVertexAllocator = Rc<RefCell<VertexAllocatorSystem>>;
+
+/// One texture store for every window on the device; each `RenderState` holds a handle
+pub type SharedTextureStore = Rc<RefCell<TextureStore>>;
+
+/// GPU resources owned by the engine rather than a window. Geometries and textures are
+/// uploaded once and can be drawn in every window, while materials and scene entities
+/// (models, cameras, lights) stay per window.
+#[derive(Default)]
+pub struct SharedResources {
+    vertex: Option<SharedVertexAllocator>,
+    textures: SharedTextureStore,
+}
+
+impl SharedResources {
+    const VERTEX_COMPACT_FRAME_INTERVAL: u64 = 120;
+    const VERTEX_COMPACT_THRESHOLD: f32 = 0.25;
+    const VERTEX_COMPACT_SLACK_RATIO: f32 = 0.3;
+    const VERTEX_COMPACT_MIN_DEAD_BYTES: u64 = 256 * 1024;
+
+    /// Handle to the shared vertex allocator, created on first use
+    #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+    pub fn vertex_allocator(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> SharedVertexAllocator {
+        self.vertex
+            .get_or_insert_with(|| {
+                Rc::new(RefCell::new(VertexAllocatorSystem::new(
+                    device,
+                    queue,
+                    VertexAllocatorConfig::default(),
+                )))
+            })
+            .clone()
+    }
+
+    /// Handle to the shared texture store
+    pub fn textures(&self) -> &SharedTextureStore {
+        &self.textures
+    }
+
+    /// Bytes of the live geometries; 0 before the first geometry upload
+    pub fn vertex_used_bytes(&self) -> u64 {
+        self.vertex
+            .as_ref()
+            .map_or(0, |vertex| vertex.borrow().used_bytes())
+    }
+
+    /// CPU bytes kept by geometries created with `retainCpuCopy`
+    pub fn vertex_retained_cpu_bytes(&self) -> u64 {
+        self.vertex
+            .as_ref()
+            .map_or(0, |vertex| vertex.borrow().retained_cpu_bytes())
+    }
+
+    /// Runs once per frame, before any window renders
+    pub fn begin_frame(&mut self, frame_index: u64) {
+        let Some(vertex) = self.vertex.as_ref() else {
+            return;
+        };
+        let mut vertex = vertex.borrow_mut();
+        vertex.begin_frame(frame_index);
+        if frame_index.is_multiple_of(Self::VERTEX_COMPACT_FRAME_INTERVAL) {
+            vertex.maybe_compact_all(
+                frame_index,
+                Self::VERTEX_COMPACT_THRESHOLD,
+                Self::VERTEX_COMPACT_SLACK_RATIO,
+                Self::VERTEX_COMPACT_MIN_DEAD_BYTES,
+            );
+        }
+    }
+
+    /// Releases the engine's handles; windows still holding one keep it alive until dropped
+    pub fn drop_all(&mut self) {
+        self.vertex = None;
+        self.textures = SharedTextureStore::default();
+    }
+}
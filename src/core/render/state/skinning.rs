@@ -81,3 +81,6 @@ impl SkinningSystem {
         self.free_list = merged;
     }
 }
+
+#[cfg(test)]
+mod tests;
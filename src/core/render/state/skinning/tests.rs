@@ -0,0 +1,57 @@
+use super::SkinningSystem;
+
+#[test]
+fn released_range_is_reused() {
+    let mut skinning = SkinningSystem::default();
+    assert_eq!(skinning.ensure_allocation(1, 16).offset, 0);
+    assert_eq!(skinning.ensure_allocation(2, 8).offset, 16);
+
+    skinning.release(1);
+    assert_eq!(skinning.ensure_allocation(3, 16).offset, 0);
+    // Nothing new was carved past the end
+    assert_eq!(skinning.ensure_allocation(4, 4).offset, 24);
+}
+
+#[test]
+fn overwritten_model_gets_its_range_back() {
+    // A model create with `overwrite` disposes the old record (releasing its bones)
+    // before the new one allocates under the same id
+    let mut skinning = SkinningSystem::default();
+    skinning.ensure_allocation(1, 32);
+    skinning.ensure_allocation(2, 32);
+
+    skinning.release(1);
+    let replaced = skinning.ensure_allocation(1, 32);
+    assert_eq!((replaced.offset, replaced.count), (0, 32));
+    assert_eq!(skinning.ensure_allocation(3, 1).offset, 64);
+}
+
+#[test]
+fn growing_an_allocation_frees_the_old_range() {
+    let mut skinning = SkinningSystem::default();
+    skinning.ensure_allocation(1, 4);
+    let grown = skinning.ensure_allocation(1, 8);
+    assert_eq!((grown.offset, grown.count), (4, 8));
+
+    assert_eq!(skinning.ensure_allocation(2, 4).offset, 0);
+}
+
+#[test]
+fn adjacent_released_ranges_merge() {
+    let mut skinning = SkinningSystem::default();
+    skinning.ensure_allocation(1, 4);
+    skinning.ensure_allocation(2, 4);
+    skinning.ensure_allocation(3, 4);
+
+    skinning.release(1);
+    skinning.release(2);
+    assert_eq!(skinning.ensure_allocation(4, 8).offset, 0);
+}
+
+#[test]
+fn release_of_unknown_model_is_a_no_op() {
+    let mut skinning = SkinningSystem::default();
+    skinning.ensure_allocation(1, 4);
+    skinning.release(9);
+    assert_eq!(skinning.ensure_allocation(2, 4).offset, 4);
+}
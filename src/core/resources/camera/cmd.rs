@@ -1,12 +1,20 @@
 use glam::{Mat4, Vec2};
 use serde::{Deserialize, Serialize};
 
-use crate::core::resources::common::default_layer_mask;
+use crate::core::resources::common::{claim_create_id, default_layer_mask};
 use crate::core::resources::{
-    CameraComponent, CameraKind, CameraRecord, ViewPosition, ensure_render_target,
+    CameraComponent, CameraController, CameraControllerMode, CameraControllerParams, CameraKind,
+    CameraLens, CameraOutput, CameraRecord, ComposeBlend, DEFAULT_FOV_Y, ViewPosition,
+    target_aspect_ratio,
 };
 use crate::core::state::EngineState;
 
+mod look_at;
+mod perspective;
+
+pub use look_at::*;
+pub use perspective::*;
+
 // MARK: - Create Camera
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -26,12 +34,44 @@ pub struct CmdCameraCreateArgs {
     pub view_position: Option<ViewPosition>,
     #[serde(default = "default_ortho_scale")]
     pub ortho_scale: f32,
+    #[serde(default = "default_auto_aspect")]
+    pub auto_aspect: bool,
+    pub aspect: Option<f32>,
+    #[serde(default)]
+    pub compose_blend: ComposeBlend,
+    #[serde(default)]
+    pub output: CameraOutput,
+    /// Fraction of the viewport resolution to render at (0.1..=2.0)
+    #[serde(default = "default_render_scale")]
+    pub render_scale: f32,
+    #[serde(default)]
+    pub overwrite: bool,
 }
 
 fn default_ortho_scale() -> f32 {
     10.0
 }
 
+fn default_auto_aspect() -> bool {
+    true
+}
+
+fn default_render_scale() -> f32 {
+    1.0
+}
+
+fn valid_render_scale(render_scale: f32) -> bool {
+    (0.1..=2.0).contains(&render_scale)
+}
+
+fn valid_aspect(aspect: Option<f32>) -> Option<f32> {
+    aspect.filter(|aspect| aspect.is_finite() && *aspect > 0.0)
+}
+
+fn valid_fov_y(fov_y: f32) -> bool {
+    fov_y.is_finite() && fov_y > 0.0 && fov_y < std::f32::consts::PI
+}
+
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
 #[serde(default, rename_all = "camelCase")]
 pub struct CmdResultCameraCreate {
@@ -43,22 +83,47 @@ pub fn engine_cmd_camera_create(
     engine: &mut EngineState,
     args: &CmdCameraCreateArgs,
 ) -> CmdResultCameraCreate {
-    let window_states = &mut engine.window.states;
+    if args.aspect.is_some() && valid_aspect(args.aspect).is_none() {
+        return CmdResultCameraCreate {
+            success: false,
+            message: "Camera aspect must be a positive number".into(),
+        };
+    }
+    if !valid_render_scale(args.render_scale) {
+        return CmdResultCameraCreate {
+            success: false,
+            message: "Camera renderScale must be within 0.1..=2.0".into(),
+        };
+    }
 
-    for (_, window_state) in window_states.iter_mut() {
-        if window_state
+    let exists = engine.window.states.values().any(|window_state| {
+        window_state
             .render_state
             .scene
             .cameras
             .contains_key(&args.camera_id)
-        {
+    });
+    match claim_create_id("Camera", args.camera_id, exists, args.overwrite) {
+        // The args are valid, so the old camera is only replaced once creation cannot fail
+        Ok(true) => {
+            let _ = engine_cmd_camera_dispose(
+                engine,
+                &CmdCameraDisposeArgs {
+                    camera_id: args.camera_id,
+                },
+            );
+        }
+        Ok(false) => {}
+        Err(message) => {
             return CmdResultCameraCreate {
                 success: false,
-                message: format!("Camera with id {} already exists", args.camera_id),
+                message,
             };
         }
     }
 
+    let window_states = &mut engine.window.states;
+
     for (_, window_state) in window_states.iter_mut() {
         let (target_width, target_height) = args
             .view_position
@@ -66,14 +131,21 @@ pub fn engine_cmd_camera_create(
             .map(|vp| vp.resolve_size(window_state.config.width, window_state.config.height))
             .unwrap_or((window_state.config.width, window_state.config.height));
 
-        let component = CameraComponent::new(
-            args.transform,
-            args.kind,
-            args.flags,
-            args.near_far,
-            (target_width, target_height),
-            args.ortho_scale,
-        );
+        // Without auto aspect the camera keeps the given aspect, or the one it was created with
+        let fixed_aspect = (!args.auto_aspect).then(|| {
+            args.aspect
+                .unwrap_or(target_aspect_ratio((target_width, target_height)))
+        });
+
+        let lens = CameraLens {
+            fov_y: DEFAULT_FOV_Y,
+            aspect_ratio: fixed_aspect
+                .unwrap_or_else(|| target_aspect_ratio((target_width, target_height))),
+            ortho_scale: args.ortho_scale,
+        };
+
+        let component =
+            CameraComponent::new(args.transform, args.kind, args.flags, args.near_far, lens);
         let mut record = CameraRecord::new(
             args.label.clone(),
             component,
@@ -81,68 +153,22 @@ pub fn engine_cmd_camera_create(
             args.order,
             args.view_position.clone(),
             args.ortho_scale,
+            fixed_aspect,
         );
+        record.compose_blend = args.compose_blend;
+        record.output = args.output;
+        record.render_scale = args.render_scale;
         if let Some(device) = engine.device.as_ref() {
-            ensure_render_target(
-                device,
-                &mut record.render_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
-            );
-            ensure_render_target(
-                device,
-                &mut record.emissive_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
-            );
-            ensure_render_target(
-                device,
-                &mut record.post_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
-            );
-            ensure_render_target(
-                device,
-                &mut record.outline_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba8Unorm,
-            );
-            ensure_render_target(
-                device,
-                &mut record.ssao_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
-            );
-            ensure_render_target(
-                device,
-                &mut record.ssao_blur_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
+            let (scaled_width, scaled_height) = record.scaled_target_size(
+                (target_width, target_height),
+                window_state.render_state.dynamic_resolution.scale,
             );
-            ensure_render_target(
+            record.ensure_targets(
                 device,
-                &mut record.bloom_target,
-                target_width,
-                target_height,
-                wgpu::TextureFormat::Rgba16Float,
+                scaled_width,
+                scaled_height,
+                &window_state.render_state.environment.post,
             );
-            for (level, target) in record.bloom_chain.iter_mut().enumerate() {
-                let level_width = crate::core::render::bloom_chain_size(target_width, level);
-                let level_height = crate::core::render::bloom_chain_size(target_height, level);
-                ensure_render_target(
-                    device,
-                    target,
-                    level_width,
-                    level_height,
-                    wgpu::TextureFormat::Rgba16Float,
-                );
-            }
         }
         window_state
             .render_state
@@ -176,6 +202,36 @@ pub struct CmdCameraUpdateArgs {
     pub order: Option<i32>,
     pub view_position: Option<ViewPosition>,
     pub ortho_scale: Option<f32>,
+    pub fov_y: Option<f32>,
+    pub auto_aspect: Option<bool>,
+    pub aspect: Option<f32>,
+    pub compose_blend: Option<ComposeBlend>,
+    pub output: Option<CameraOutput>,
+    pub render_scale: Option<f32>,
+}
+
+impl CmdCameraUpdateArgs {
+    /// Update that leaves every property unchanged
+    pub fn new(camera_id: u32) -> Self {
+        Self {
+            camera_id,
+            label: None,
+            transform: None,
+            kind: None,
+            flags: None,
+            near_far: None,
+            layer_mask: None,
+            order: None,
+            view_position: None,
+            ortho_scale: None,
+            fov_y: None,
+            auto_aspect: None,
+            aspect: None,
+            compose_blend: None,
+            output: None,
+            render_scale: None,
+        }
+    }
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -189,6 +245,28 @@ pub fn engine_cmd_camera_update(
     engine: &mut EngineState,
     args: &CmdCameraUpdateArgs,
 ) -> CmdResultCameraUpdate {
+    if args.aspect.is_some() && valid_aspect(args.aspect).is_none() {
+        return CmdResultCameraUpdate {
+            success: false,
+            message: "Camera aspect must be a positive number".into(),
+        };
+    }
+    if args.fov_y.is_some_and(|fov_y| !valid_fov_y(fov_y)) {
+        return CmdResultCameraUpdate {
+            success: false,
+            message: "Camera fovY must be between 0 and PI radians".into(),
+        };
+    }
+    if args
+        .render_scale
+        .is_some_and(|render_scale| !valid_render_scale(render_scale))
+    {
+        return CmdResultCameraUpdate {
+            success: false,
+            message: "Camera renderScale must be within 0.1..=2.0".into(),
+        };
+    }
+
     let window_states = &mut engine.window.states;
 
     let mut found = false;
@@ -220,7 +298,24 @@ pub fn engine_cmd_camera_update(
             if let Some(ortho_scale) = args.ortho_scale {
                 record.ortho_scale = ortho_scale;
             }
-            let ortho_scale = record.ortho_scale;
+            if let Some(fov_y) = args.fov_y {
+                record.fov_y = fov_y;
+            }
+            if let Some(render_scale) = args.render_scale {
+                record.render_scale = render_scale;
+            }
+
+            // `aspect` only applies while auto aspect is off; turning it off pins the
+            // aspect currently in use unless a new one is given
+            let auto_aspect = args.auto_aspect.unwrap_or(record.fixed_aspect.is_none());
+            record.fixed_aspect = if auto_aspect {
+                None
+            } else {
+                Some(
+                    args.aspect
+                        .unwrap_or(record.aspect_ratio((target_width, target_height))),
+                )
+            };
 
             // Update camera component
             record.data.update(
@@ -228,71 +323,20 @@ pub fn engine_cmd_camera_update(
                 args.kind,
                 args.flags,
                 args.near_far,
-                (target_width, target_height),
-                ortho_scale,
+                record.lens((target_width, target_height)),
             );
 
             if let Some(device) = engine.device.as_ref() {
-                ensure_render_target(
-                    device,
-                    &mut record.render_target,
-                    target_width,
-                    target_height,
-                    wgpu::TextureFormat::Rgba16Float,
-                );
-                ensure_render_target(
-                    device,
-                    &mut record.emissive_target,
-                    target_width,
-                    target_height,
-                    wgpu::TextureFormat::Rgba16Float,
-                );
-                ensure_render_target(
-                    device,
-                    &mut record.post_target,
-                    target_width,
-                    target_height,
-                    wgpu::TextureFormat::Rgba16Float,
+                let (scaled_width, scaled_height) = record.scaled_target_size(
+                    (target_width, target_height),
+                    window_state.render_state.dynamic_resolution.scale,
                 );
-                ensure_render_target(
+                record.ensure_targets(
                     device,
-                    &mut record.outline_target,
-                    target_width,
-                    target_height,
-                    wgpu::TextureFormat::Rgba8Unorm,
+                    scaled_width,
+                    scaled_height,
+                    &window_state.render_state.environment.post,
                 );
-                ensure_render_target(
-                    device,
-                    &mut record.ssao_target,
-                    target_width,
-                    target_height,
-                    wgpu::TextureFormat::Rgba16Float,
-                );
-                ensure_render_target(
-                    device,
-                    &mut record.ssao_blur_target,
-                    target_width,
-                    target_height,
-                    wgpu::TextureFormat::Rgba16Float,
-                );
-                ensure_render_target(
-                    device,
-                    &mut record.bloom_target,
-                    target_width,
-                    target_height,
-                    wgpu::TextureFormat::Rgba16Float,
-                );
-                for (level, target) in record.bloom_chain.iter_mut().enumerate() {
-                    let level_width = crate::core::render::bloom_chain_size(target_width, level);
-                    let level_height = crate::core::render::bloom_chain_size(target_height, level);
-                    ensure_render_target(
-                        device,
-                        target,
-                        level_width,
-                        level_height,
-                        wgpu::TextureFormat::Rgba16Float,
-                    );
-                }
             }
 
             if let Some(layer_mask) = args.layer_mask {
@@ -303,6 +347,14 @@ pub fn engine_cmd_camera_update(
                 record.order = order;
             }
 
+            if let Some(compose_blend) = args.compose_blend {
+                record.compose_blend = compose_blend;
+            }
+
+            if let Some(output) = args.output {
+                record.output = output;
+            }
+
             record.mark_dirty();
             if let Some(shadow) = window_state.render_state.shadow.as_mut() {
                 shadow.mark_dirty();
@@ -324,6 +376,66 @@ pub fn engine_cmd_camera_update(
     }
 }
 
+// MARK: - Set Controller
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdCameraSetControllerArgs {
+    pub camera_id: u32,
+    pub mode: CameraControllerMode,
+    pub params: CameraControllerParams,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultCameraSetController {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_camera_set_controller(
+    engine: &mut EngineState,
+    args: &CmdCameraSetControllerArgs,
+) -> CmdResultCameraSetController {
+    let placement = engine.window.states.values().find_map(|window_state| {
+        window_state
+            .render_state
+            .scene
+            .cameras
+            .get(&args.camera_id)
+            .map(|record| {
+                (
+                    record.data.position.truncate(),
+                    record.data.direction.truncate(),
+                )
+            })
+    });
+    let Some((position, direction)) = placement else {
+        return CmdResultCameraSetController {
+            success: false,
+            message: format!("Camera with id {} not found", args.camera_id),
+        };
+    };
+
+    if args.mode == CameraControllerMode::None {
+        engine.camera_controllers.remove(&args.camera_id);
+        return CmdResultCameraSetController {
+            success: true,
+            message: "Camera controller disabled".into(),
+        };
+    }
+
+    engine.camera_controllers.insert(
+        args.camera_id,
+        CameraController::new(args.mode, args.params, position, direction),
+    );
+
+    CmdResultCameraSetController {
+        success: true,
+        message: "Camera controller enabled".into(),
+    }
+}
+
 // MARK: - Dispose Camera
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -343,6 +455,8 @@ pub fn engine_cmd_camera_dispose(
     engine: &mut EngineState,
     args: &CmdCameraDisposeArgs,
 ) -> CmdResultCameraDispose {
+    engine.camera_controllers.remove(&args.camera_id);
+
     let window_states = &mut engine.window.states;
 
     let mut found = false;
@@ -0,0 +1,64 @@
+use glam::{Mat4, Vec3};
+use serde::{Deserialize, Serialize};
+
+use super::{CmdCameraUpdateArgs, engine_cmd_camera_update};
+use crate::core::state::EngineState;
+
+// MARK: - Look At
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdCameraLookAtArgs {
+    pub camera_id: u32,
+    pub eye: Vec3,
+    pub target: Vec3,
+    #[serde(default = "default_up")]
+    pub up: Vec3,
+}
+
+fn default_up() -> Vec3 {
+    Vec3::Y
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultCameraLookAt {
+    pub success: bool,
+    pub message: String,
+}
+
+/// Camera transform placing the camera at `eye` looking at `target` (right-handed, -Z forward).
+/// `None` when the eye and target coincide or `up` is parallel to the view direction.
+pub fn camera_look_at_transform(eye: Vec3, target: Vec3, up: Vec3) -> Option<Mat4> {
+    let forward = (target - eye).try_normalize()?;
+    let up = up.try_normalize()?;
+    if forward.cross(up).length_squared() < 1e-8 {
+        return None;
+    }
+    Some(Mat4::look_at_rh(eye, target, up).inverse())
+}
+
+pub fn engine_cmd_camera_look_at(
+    engine: &mut EngineState,
+    args: &CmdCameraLookAtArgs,
+) -> CmdResultCameraLookAt {
+    let Some(transform) = camera_look_at_transform(args.eye, args.target, args.up) else {
+        return CmdResultCameraLookAt {
+            success: false,
+            message: "Camera eye and target must differ and up must not be parallel to the view direction".into(),
+        };
+    };
+
+    let result = engine_cmd_camera_update(
+        engine,
+        &CmdCameraUpdateArgs {
+            transform: Some(transform),
+            ..CmdCameraUpdateArgs::new(args.camera_id)
+        },
+    );
+
+    CmdResultCameraLookAt {
+        success: result.success,
+        message: result.message,
+    }
+}
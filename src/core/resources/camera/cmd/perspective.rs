@@ -0,0 +1,59 @@
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use super::{CmdCameraUpdateArgs, engine_cmd_camera_update, valid_fov_y};
+use crate::core::resources::CameraKind;
+use crate::core::state::EngineState;
+
+// MARK: - Set Perspective
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdCameraSetPerspectiveArgs {
+    pub camera_id: u32,
+    /// Vertical field of view in radians
+    pub fov_y: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultCameraSetPerspective {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_camera_set_perspective(
+    engine: &mut EngineState,
+    args: &CmdCameraSetPerspectiveArgs,
+) -> CmdResultCameraSetPerspective {
+    if !valid_fov_y(args.fov_y) {
+        return CmdResultCameraSetPerspective {
+            success: false,
+            message: "Camera fovY must be between 0 and PI radians".into(),
+        };
+    }
+    if !(args.near > 0.0 && args.far > args.near && args.far.is_finite()) {
+        return CmdResultCameraSetPerspective {
+            success: false,
+            message: "Camera planes must satisfy 0 < near < far".into(),
+        };
+    }
+
+    // The projection stays reverse-Z; near/far are given in the natural order
+    let result = engine_cmd_camera_update(
+        engine,
+        &CmdCameraUpdateArgs {
+            kind: Some(CameraKind::Perspective),
+            near_far: Some(Vec2::new(args.near, args.far)),
+            fov_y: Some(args.fov_y),
+            ..CmdCameraUpdateArgs::new(args.camera_id)
+        },
+    );
+
+    CmdResultCameraSetPerspective {
+        success: result.success,
+        message: result.message,
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::HashSet;
+
+use glam::{Vec2, Vec3};
+use serde::{Deserialize, Serialize};
+
+use crate::core::cmd::EngineEvent;
+use crate::core::input::events::{ElementState, KeyboardEvent, PointerEvent, ScrollDelta};
+use crate::core::resources::{
+    CmdCameraUpdateArgs, camera_look_at_transform, engine_cmd_camera_update,
+};
+use crate::core::state::EngineState;
+use crate::core::window::WindowEvent;
+
+// Engine key codes (see `convert_key_code`) and mouse buttons (see `convert_mouse_button`)
+const KEY_A: u32 = 19;
+const KEY_D: u32 = 22;
+const KEY_E: u32 = 23;
+const KEY_Q: u32 = 35;
+const KEY_S: u32 = 37;
+const KEY_W: u32 = 41;
+const KEY_SHIFT_LEFT: u32 = 60;
+const KEY_SHIFT_RIGHT: u32 = 61;
+const BUTTON_LEFT: u32 = 0;
+const BUTTON_RIGHT: u32 = 1;
+const BUTTON_MIDDLE: u32 = 2;
+
+/// Pixel scroll (touchpads) counted as one wheel line
+const SCROLL_PIXELS_PER_LINE: f32 = 50.0;
+/// Pitch stays short of the poles so the view never flips
+const MAX_PITCH: f32 = 89.0 * std::f32::consts::PI / 180.0;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CameraControllerMode {
+    /// No controller; the host drives the camera
+    #[default]
+    None,
+    /// Left drag orbits around a target, right/middle drag pans, scroll dollies
+    Orbit,
+    /// Right drag looks around, WASD moves, Q/E go down/up, Shift boosts
+    Fly,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CameraControllerParams {
+    /// Orbit pivot. Defaults to the point `distance` units in front of the camera
+    pub target: Option<Vec3>,
+    /// Orbit distance used when `target` is omitted
+    pub distance: f32,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    /// Radians per dragged pixel
+    pub rotate_speed: f32,
+    /// Pan per dragged pixel, relative to the orbit distance
+    pub pan_speed: f32,
+    /// Fraction of the distance covered per scroll line
+    pub zoom_speed: f32,
+    /// Fly speed in units per second
+    pub move_speed: f32,
+    /// Fly speed multiplier while Shift is held
+    pub boost: f32,
+}
+
+impl Default for CameraControllerParams {
+    fn default() -> Self {
+        Self {
+            target: None,
+            distance: 10.0,
+            min_distance: 0.1,
+            max_distance: 1000.0,
+            rotate_speed: 0.005,
+            pan_speed: 0.001,
+            zoom_speed: 0.1,
+            move_speed: 5.0,
+            boost: 4.0,
+        }
+    }
+}
+
+/// Per-camera controller state, fed by this tick's input events
+#[derive(Debug, Clone)]
+pub struct CameraController {
+    pub mode: CameraControllerMode,
+    pub params: CameraControllerParams,
+    eye: Vec3,
+    target: Vec3,
+    yaw: f32,
+    pitch: f32,
+    distance: f32,
+    buttons: HashSet<u32>,
+    keys: HashSet<u32>,
+    last_pointer: Option<Vec2>,
+    rotate: Vec2,
+    pan: Vec2,
+    zoom: f32,
+    changed: bool,
+}
+
+impl CameraController {
+    /// Starts from the camera's current placement so enabling a controller never jumps
+    pub fn new(
+        mode: CameraControllerMode,
+        params: CameraControllerParams,
+        position: Vec3,
+        direction: Vec3,
+    ) -> Self {
+        let forward = direction.try_normalize().unwrap_or(Vec3::NEG_Z);
+        let yaw = forward.x.atan2(-forward.z);
+        let pitch = forward
+            .y
+            .clamp(-1.0, 1.0)
+            .asin()
+            .clamp(-MAX_PITCH, MAX_PITCH);
+
+        let target = params
+            .target
+            .unwrap_or(position + forward * params.distance.max(params.min_distance));
+        let distance = (position - target).length().clamp(
+            params.min_distance,
+            params.max_distance.max(params.min_distance),
+        );
+
+        let mut controller = Self {
+            mode,
+            params,
+            eye: position,
+            target,
+            yaw,
+            pitch,
+            distance,
+            buttons: HashSet::new(),
+            keys: HashSet::new(),
+            last_pointer: None,
+            rotate: Vec2::ZERO,
+            pan: Vec2::ZERO,
+            zoom: 0.0,
+            changed: true,
+        };
+
+        if mode == CameraControllerMode::Orbit {
+            // Look at the pivot from where the camera stands
+            if let Some(to_eye) = (position - target).try_normalize() {
+                let forward = -to_eye;
+                controller.yaw = forward.x.atan2(-forward.z);
+                controller.pitch = forward.y.asin().clamp(-MAX_PITCH, MAX_PITCH);
+            }
+        }
+        controller
+    }
+
+    fn forward(&self) -> Vec3 {
+        Vec3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            -self.pitch.cos() * self.yaw.cos(),
+        )
+    }
+
+    fn handle_event(&mut self, event: &EngineEvent) {
+        match event {
+            EngineEvent::Pointer(PointerEvent::OnButton {
+                button,
+                state,
+                position,
+                ..
+            }) => {
+                match state {
+                    ElementState::Pressed => self.buttons.insert(*button),
+                    ElementState::Released => self.buttons.remove(button),
+                };
+                self.last_pointer = Some(*position);
+            }
+            EngineEvent::Pointer(PointerEvent::OnMove { position, .. }) => {
+                let delta = self
+                    .last_pointer
+                    .map(|last| *position - last)
+                    .unwrap_or(Vec2::ZERO);
+                self.last_pointer = Some(*position);
+
+                let look_button = match self.mode {
+                    CameraControllerMode::Fly => BUTTON_RIGHT,
+                    _ => BUTTON_LEFT,
+                };
+                if self.buttons.contains(&look_button) {
+                    self.rotate += delta;
+                } else if self.mode == CameraControllerMode::Orbit
+                    && (self.buttons.contains(&BUTTON_RIGHT)
+                        || self.buttons.contains(&BUTTON_MIDDLE))
+                {
+                    self.pan += delta;
+                }
+            }
+            EngineEvent::Pointer(PointerEvent::OnLeave { .. }) => {
+                self.last_pointer = None;
+            }
+            EngineEvent::Pointer(PointerEvent::OnScroll { delta, .. }) => {
+                self.zoom += match delta {
+                    ScrollDelta::Line(lines) => lines.y,
+                    ScrollDelta::Pixel(pixels) => pixels.y / SCROLL_PIXELS_PER_LINE,
+                };
+            }
+            EngineEvent::Pointer(PointerEvent::OnPinchGesture { delta, .. }) => {
+                self.zoom += *delta as f32 / self.params.zoom_speed.max(f32::EPSILON);
+            }
+            EngineEvent::Keyboard(KeyboardEvent::OnInput {
+                key_code, state, ..
+            }) => match state {
+                ElementState::Pressed => {
+                    self.keys.insert(*key_code);
+                }
+                ElementState::Released => {
+                    self.keys.remove(key_code);
+                }
+            },
+            EngineEvent::Window(WindowEvent::OnFocus { focused: false, .. }) => {
+                // Releases are not delivered while unfocused
+                self.buttons.clear();
+                self.keys.clear();
+                self.last_pointer = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn axis(&self, positive: u32, negative: u32) -> f32 {
+        self.keys.contains(&positive) as i32 as f32 - self.keys.contains(&negative) as i32 as f32
+    }
+
+    /// Applies accumulated input; returns the new eye and target when the camera moved
+    fn integrate(&mut self, delta_seconds: f32) -> Option<(Vec3, Vec3)> {
+        let params = self.params;
+
+        if self.rotate != Vec2::ZERO {
+            self.yaw += self.rotate.x * params.rotate_speed;
+            self.pitch =
+                (self.pitch - self.rotate.y * params.rotate_speed).clamp(-MAX_PITCH, MAX_PITCH);
+            self.changed = true;
+        }
+
+        let forward = self.forward();
+        let right = forward.cross(Vec3::Y).normalize_or_zero();
+        let up = right.cross(forward);
+
+        match self.mode {
+            CameraControllerMode::Orbit => {
+                if self.pan != Vec2::ZERO {
+                    let scale = params.pan_speed * self.distance;
+                    self.target += (-right * self.pan.x + up * self.pan.y) * scale;
+                    self.changed = true;
+                }
+                if self.zoom != 0.0 {
+                    let factor = (1.0 - params.zoom_speed).max(0.01).powf(self.zoom);
+                    self.distance = (self.distance * factor).clamp(
+                        params.min_distance,
+                        params.max_distance.max(params.min_distance),
+                    );
+                    self.changed = true;
+                }
+                self.eye = self.target - forward * self.distance;
+            }
+            CameraControllerMode::Fly => {
+                let movement = forward * self.axis(KEY_W, KEY_S)
+                    + right * self.axis(KEY_D, KEY_A)
+                    + Vec3::Y * self.axis(KEY_E, KEY_Q);
+                if let Some(direction) = movement.try_normalize() {
+                    let boosted =
+                        self.keys.contains(&KEY_SHIFT_LEFT) || self.keys.contains(&KEY_SHIFT_RIGHT);
+                    let speed = params.move_speed * if boosted { params.boost } else { 1.0 };
+                    self.eye += direction * speed * delta_seconds;
+                    self.changed = true;
+                }
+                self.target = self.eye + forward;
+            }
+            CameraControllerMode::None => {}
+        }
+
+        self.rotate = Vec2::ZERO;
+        self.pan = Vec2::ZERO;
+        self.zoom = 0.0;
+
+        if std::mem::take(&mut self.changed) {
+            Some((self.eye, self.target))
+        } else {
+            None
+        }
+    }
+}
+
+/// Feeds the input events currently in the event queue to every controller.
+/// Hosts still receive the events unchanged.
+pub fn feed_camera_controllers(engine: &mut EngineState) {
+    if engine.camera_controllers.is_empty() {
+        return;
+    }
+    for event in &engine.event_queue {
+        for controller in engine.camera_controllers.values_mut() {
+            controller.handle_event(event);
+        }
+    }
+}
+
+/// Moves controlled cameras for this tick. Controllers of removed cameras are dropped.
+pub fn process_camera_controllers(engine: &mut EngineState) {
+    if engine.camera_controllers.is_empty() {
+        return;
+    }
+
+    let delta_seconds = engine.delta_time as f32 / 1000.0;
+    let moves: Vec<(u32, Vec3, Vec3)> = engine
+        .camera_controllers
+        .iter_mut()
+        .filter_map(|(camera_id, controller)| {
+            controller
+                .integrate(delta_seconds)
+                .map(|(eye, target)| (*camera_id, eye, target))
+        })
+        .collect();
+
+    for (camera_id, eye, target) in moves {
+        let Some(transform) = camera_look_at_transform(eye, target, Vec3::Y) else {
+            continue;
+        };
+        let result = engine_cmd_camera_update(
+            engine,
+            &CmdCameraUpdateArgs {
+                transform: Some(transform),
+                ..CmdCameraUpdateArgs::new(camera_id)
+            },
+        );
+        if !result.success {
+            engine.camera_controllers.remove(&camera_id);
+        }
+    }
+}
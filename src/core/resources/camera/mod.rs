@@ -1,5 +1,9 @@
 mod cmd;
+mod controller;
 mod spec;
+mod targets;
 
 pub use cmd::*;
+pub use controller::*;
 pub use spec::*;
+pub use targets::*;
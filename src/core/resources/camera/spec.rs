@@ -1,7 +1,11 @@
 use bytemuck::{Pod, Zeroable};
 use glam::{Mat4, Quat, UVec2, Vec2, Vec3, Vec4};
 use serde::{Deserialize, Serialize};
-use wgpu::Extent3d;
+
+use super::RenderTarget;
+
+/// Vertical field of view used until a camera sets its own (45 degrees)
+pub const DEFAULT_FOV_Y: f32 = std::f32::consts::FRAC_PI_4;
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -27,6 +31,17 @@ impl CameraKind {
     }
 }
 
+/// Projection parameters that are not part of the GPU camera data
+#[derive(Debug, Clone, Copy)]
+pub struct CameraLens {
+    /// Vertical field of view in radians (Perspective)
+    pub fov_y: f32,
+    /// Width / height
+    pub aspect_ratio: f32,
+    /// Vertical span of the view (Orthographic)
+    pub ortho_scale: f32,
+}
+
 #[derive(Debug, Clone, Copy, Pod, Zeroable, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 #[repr(C)]
@@ -45,15 +60,14 @@ pub struct CameraComponent {
 impl CameraComponent {
     /// Create from raw input data
     ///
-    /// For both Perspective and Orthographic cameras, uses `window_size` (width, height) to calculate aspect ratio.
-    /// For Orthographic cameras, `ortho_scale` defines the vertical span of the view.
+    /// Perspective cameras keep `lens.fov_y` and Orthographic cameras keep `lens.ortho_scale`
+    /// as the vertical span, so both widen with `lens.aspect_ratio`.
     pub fn new(
         transform: Mat4,
         kind: CameraKind,
         flags: u32,
         near_far: Vec2,
-        window_size: (u32, u32),
-        ortho_scale: f32,
+        lens: CameraLens,
     ) -> Self {
         let position = transform.w_axis.truncate();
         let rotation = Quat::from_mat4(&transform);
@@ -62,17 +76,14 @@ impl CameraComponent {
 
         let view = Mat4::look_to_rh(position, direction, up);
 
-        let aspect_ratio = window_size.0 as f32 / window_size.1 as f32;
-
         let projection = match kind {
             CameraKind::Perspective => {
-                let fov_y = 45.0_f32.to_radians();
                 // Reverse Z: swap near/far
-                Mat4::perspective_rh(fov_y, aspect_ratio, near_far.y, near_far.x)
+                Mat4::perspective_rh(lens.fov_y, lens.aspect_ratio, near_far.y, near_far.x)
             }
             CameraKind::Orthographic => {
-                let half_height = ortho_scale / 2.0;
-                let half_width = half_height * aspect_ratio;
+                let half_height = lens.ortho_scale / 2.0;
+                let half_width = half_height * lens.aspect_ratio;
                 // Reverse Z: swap near/far
                 Mat4::orthographic_rh(
                     -half_width,  // left
@@ -106,8 +117,7 @@ impl CameraComponent {
         kind: Option<CameraKind>,
         flags: Option<u32>,
         near_far: Option<Vec2>,
-        window_size: (u32, u32),
-        ortho_scale: f32,
+        lens: CameraLens,
     ) {
         let transform = transform.unwrap_or_else(|| {
             let pos = self.position.truncate();
@@ -123,10 +133,88 @@ impl CameraComponent {
         let flags = flags.unwrap_or(self.kind_flags.y);
         let near_far = near_far.unwrap_or(self.near_far);
 
-        *self = Self::new(transform, kind, flags, near_far, window_size, ortho_scale);
+        *self = Self::new(transform, kind, flags, near_far, lens);
+    }
+}
+
+/// How the compose pass layers a camera's image over the cameras before it in `order`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ComposeBlend {
+    /// Replaces everything below inside the viewport
+    #[default]
+    Overwrite,
+    /// Blends by the camera's alpha; its target clears transparent and skips the skybox
+    AlphaBlend,
+    /// Adds the camera's color on top; its target clears transparent and skips the skybox
+    Additive,
+}
+
+impl ComposeBlend {
+    pub fn blend_state(self) -> Option<wgpu::BlendState> {
+        match self {
+            ComposeBlend::Overwrite => None,
+            // Forward output over a transparent clear is premultiplied
+            ComposeBlend::AlphaBlend => Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+            ComposeBlend::Additive => Some(wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::One,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::Zero,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+            }),
+        }
+    }
+
+    /// Overlay cameras start from a transparent target so only what they draw is layered
+    pub fn is_overlay(self) -> bool {
+        self != ComposeBlend::Overwrite
+    }
+}
+
+/// How the compose pass fits a camera with a pinned aspect into its viewport
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FitMode {
+    /// Fills the viewport, distorting the image when the aspects differ
+    #[default]
+    Stretch,
+    /// Shows the whole image, with bars on the sides the image does not reach
+    Contain,
+    /// Fills the viewport, cropping the sides of the image that do not fit
+    Cover,
+}
+
+impl FitMode {
+    /// Scale around the center from viewport UV to image UV, for an image of aspect
+    /// `content` shown in a viewport of aspect `viewport`
+    pub fn uv_scale(self, content: f32, viewport: f32) -> glam::Vec2 {
+        let ratio = viewport / content;
+        match self {
+            FitMode::Stretch => glam::Vec2::ONE,
+            FitMode::Contain => glam::Vec2::new(ratio.max(1.0), (1.0 / ratio).max(1.0)),
+            FitMode::Cover => glam::Vec2::new(ratio.min(1.0), (1.0 / ratio).min(1.0)),
+        }
     }
 }
 
+/// Where the compose pass draws a camera's image
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "kebab-case")]
+pub enum CameraOutput {
+    /// Composed into the window inside `view_position`
+    #[default]
+    Window,
+    /// Drawn over the whole of a texture created with `render-target` usage, by texture id.
+    /// Nothing is drawn while the texture is missing or cannot be rendered to.
+    Texture(u32),
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 #[serde(tag = "type", content = "value", rename_all = "kebab-case")]
 pub enum ViewValue {
@@ -187,72 +275,9 @@ impl ViewPosition {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct RenderTarget {
-    pub _texture: wgpu::Texture,
-    pub view: wgpu::TextureView,
-    pub format: wgpu::TextureFormat,
-    pub sample_count: u32,
-}
-
-impl RenderTarget {
-    pub fn new(device: &wgpu::Device, size: Extent3d, format: wgpu::TextureFormat) -> Self {
-        Self::new_with_samples(device, size, format, 1)
-    }
-
-    pub fn new_with_samples(
-        device: &wgpu::Device,
-        size: Extent3d,
-        format: wgpu::TextureFormat,
-        sample_count: u32,
-    ) -> Self {
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Camera RenderTarget"),
-            size,
-            mip_level_count: 1,
-            sample_count,
-            dimension: wgpu::TextureDimension::D2,
-            format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
-                | wgpu::TextureUsages::COPY_SRC
-                | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-
-        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-
-        Self {
-            _texture: texture,
-            view,
-            format,
-            sample_count,
-        }
-    }
-}
-
-pub fn ensure_render_target(
-    device: &wgpu::Device,
-    target: &mut Option<RenderTarget>,
-    width: u32,
-    height: u32,
-    format: wgpu::TextureFormat,
-) {
-    let needs_target = match target.as_ref() {
-        Some(existing) => {
-            let size = existing._texture.size();
-            size.width != width || size.height != height || existing.format != format
-        }
-        None => true,
-    };
-
-    if needs_target {
-        let size = wgpu::Extent3d {
-            width,
-            height,
-            depth_or_array_layers: 1,
-        };
-        *target = Some(RenderTarget::new(device, size, format));
-    }
+/// Width / height of a render target; zero sizes count as 1
+pub fn target_aspect_ratio(target_size: (u32, u32)) -> f32 {
+    target_size.0.max(1) as f32 / target_size.1.max(1) as f32
 }
 
 #[derive(Debug, Clone)]
@@ -263,6 +288,12 @@ pub struct CameraRecord {
     pub order: i32,
     pub is_dirty: bool,
     pub ortho_scale: f32,
+    /// Vertical field of view in radians for perspective projection
+    pub fov_y: f32,
+    /// Pinned width / height; `None` follows the render target aspect
+    pub fixed_aspect: Option<f32>,
+    /// Fraction of the viewport resolution the camera renders at; compose scales it back
+    pub render_scale: f32,
     pub render_target: Option<RenderTarget>,
     pub emissive_target: Option<RenderTarget>,
     pub post_target: Option<RenderTarget>,
@@ -271,7 +302,11 @@ pub struct CameraRecord {
     pub ssao_blur_target: Option<RenderTarget>,
     pub bloom_target: Option<RenderTarget>,
     pub bloom_chain: [Option<RenderTarget>; 4],
+    /// Viewport-size EASU output of the FSR upscaler; `None` while the camera is not upscaled
+    pub upscale_target: Option<RenderTarget>,
     pub view_position: Option<ViewPosition>,
+    pub compose_blend: ComposeBlend,
+    pub output: CameraOutput,
 }
 
 impl CameraRecord {
@@ -282,6 +317,7 @@ impl CameraRecord {
         order: i32,
         view_position: Option<ViewPosition>,
         ortho_scale: f32,
+        fixed_aspect: Option<f32>,
     ) -> Self {
         Self {
             label,
@@ -290,6 +326,9 @@ impl CameraRecord {
             order,
             is_dirty: true,
             ortho_scale,
+            fov_y: DEFAULT_FOV_Y,
+            fixed_aspect,
+            render_scale: 1.0,
             render_target: None,
             emissive_target: None,
             post_target: None,
@@ -298,7 +337,10 @@ impl CameraRecord {
             ssao_blur_target: None,
             bloom_target: None,
             bloom_chain: [None, None, None, None],
+            upscale_target: None,
             view_position,
+            compose_blend: ComposeBlend::Overwrite,
+            output: CameraOutput::Window,
         }
     }
 
@@ -310,5 +352,33 @@ impl CameraRecord {
         self.is_dirty = false;
     }
 
+    /// Render target size for a viewport `area` (width, height), scaled by `render_scale`
+    /// and the window's dynamic resolution scale
+    pub fn scaled_target_size(&self, area: (u32, u32), window_scale: f32) -> (u32, u32) {
+        let scale = self.render_scale * window_scale;
+        if scale == 1.0 {
+            return area;
+        }
+        (
+            ((area.0 as f32 * scale).round() as u32).max(1),
+            ((area.1 as f32 * scale).round() as u32).max(1),
+        )
+    }
+
+    /// Projection aspect for a render target of `target_size` (width, height)
+    pub fn aspect_ratio(&self, target_size: (u32, u32)) -> f32 {
+        self.fixed_aspect
+            .unwrap_or_else(|| target_aspect_ratio(target_size))
+    }
+
+    /// Projection parameters for a render target of `target_size` (width, height)
+    pub fn lens(&self, target_size: (u32, u32)) -> CameraLens {
+        CameraLens {
+            fov_y: self.fov_y,
+            aspect_ratio: self.aspect_ratio(target_size),
+            ortho_scale: self.ortho_scale,
+        }
+    }
+
     // Render targets are managed via ensure_render_target helper.
 }
@@ -0,0 +1,172 @@
+use wgpu::Extent3d;
+
+use super::CameraRecord;
+use crate::core::resources::PostProcessConfig;
+
+#[derive(Debug, Clone)]
+pub struct RenderTarget {
+    pub _texture: wgpu::Texture,
+    pub view: wgpu::TextureView,
+    pub format: wgpu::TextureFormat,
+    pub sample_count: u32,
+}
+
+impl RenderTarget {
+    pub fn new(device: &wgpu::Device, size: Extent3d, format: wgpu::TextureFormat) -> Self {
+        Self::new_with_samples(device, size, format, 1)
+    }
+
+    pub fn new_with_samples(
+        device: &wgpu::Device,
+        size: Extent3d,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Camera RenderTarget"),
+            size,
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self {
+            _texture: texture,
+            view,
+            format,
+            sample_count,
+        }
+    }
+}
+
+pub fn ensure_render_target(
+    device: &wgpu::Device,
+    target: &mut Option<RenderTarget>,
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+) {
+    let needs_target = match target.as_ref() {
+        Some(existing) => {
+            let size = existing._texture.size();
+            size.width != width || size.height != height || existing.format != format
+        }
+        None => true,
+    };
+
+    if needs_target {
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        *target = Some(RenderTarget::new(device, size, format));
+    }
+}
+
+impl CameraRecord {
+    /// Allocates the targets every camera renders into, plus the effect targets
+    /// enabled in `post`
+    pub fn ensure_targets(
+        &mut self,
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        post: &PostProcessConfig,
+    ) {
+        ensure_render_target(
+            device,
+            &mut self.render_target,
+            width,
+            height,
+            wgpu::TextureFormat::Rgba16Float,
+        );
+        ensure_render_target(
+            device,
+            &mut self.emissive_target,
+            width,
+            height,
+            wgpu::TextureFormat::Rgba16Float,
+        );
+        ensure_render_target(
+            device,
+            &mut self.post_target,
+            width,
+            height,
+            wgpu::TextureFormat::Rgba16Float,
+        );
+        self.sync_effect_targets(device, width, height, post);
+    }
+
+    /// Allocates outline, SSAO and bloom targets only while their effect is enabled
+    /// and frees them otherwise. Passes skip cameras without the target and `post`
+    /// samples a fallback instead.
+    pub fn sync_effect_targets(
+        &mut self,
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        post: &PostProcessConfig,
+    ) {
+        if post.outline_enabled {
+            ensure_render_target(
+                device,
+                &mut self.outline_target,
+                width,
+                height,
+                wgpu::TextureFormat::Rgba8Unorm,
+            );
+        } else {
+            self.outline_target = None;
+        }
+
+        if post.ssao_enabled {
+            ensure_render_target(
+                device,
+                &mut self.ssao_target,
+                width,
+                height,
+                wgpu::TextureFormat::Rgba16Float,
+            );
+            ensure_render_target(
+                device,
+                &mut self.ssao_blur_target,
+                width,
+                height,
+                wgpu::TextureFormat::Rgba16Float,
+            );
+        } else {
+            self.ssao_target = None;
+            self.ssao_blur_target = None;
+        }
+
+        if post.bloom_enabled {
+            ensure_render_target(
+                device,
+                &mut self.bloom_target,
+                width,
+                height,
+                wgpu::TextureFormat::Rgba16Float,
+            );
+            for (level, target) in self.bloom_chain.iter_mut().enumerate() {
+                ensure_render_target(
+                    device,
+                    target,
+                    crate::core::render::bloom_chain_size(width, level),
+                    crate::core::render::bloom_chain_size(height, level),
+                    wgpu::TextureFormat::Rgba16Float,
+                );
+            }
+        } else {
+            self.bloom_target = None;
+            self.bloom_chain = [None, None, None, None];
+        }
+    }
+}
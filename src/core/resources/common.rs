@@ -9,3 +9,36 @@ pub fn default_true() -> bool {
 pub fn default_vec4_zero() -> glam::Vec4 {
     glam::Vec4::ZERO
 }
+
+/// Checks a create against an id that may already be taken. A taken id is rejected unless
+/// `overwrite` is set; returns whether the create replaces an existing resource.
+pub fn claim_create_id(kind: &str, id: u32, exists: bool, overwrite: bool) -> Result<bool, String> {
+    if exists && !overwrite {
+        return Err(format!("{} with id {} already exists", kind, id));
+    }
+    Ok(exists)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::claim_create_id;
+
+    #[test]
+    fn claim_create_id_accepts_a_free_id() {
+        assert_eq!(claim_create_id("Model", 1, false, false), Ok(false));
+        assert_eq!(claim_create_id("Model", 1, false, true), Ok(false));
+    }
+
+    #[test]
+    fn claim_create_id_rejects_a_taken_id() {
+        assert_eq!(
+            claim_create_id("Model", 7, true, false),
+            Err("Model with id 7 already exists".into())
+        );
+    }
+
+    #[test]
+    fn claim_create_id_replaces_a_taken_id_on_overwrite() {
+        assert_eq!(claim_create_id("Model", 7, true, true), Ok(true));
+    }
+}
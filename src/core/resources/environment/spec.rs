@@ -1,4 +1,4 @@
-use glam::Vec3;
+use glam::{Vec2, Vec3};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
@@ -9,6 +9,41 @@ pub enum SkyboxMode {
     Cubemap,
 }
 
+/// How the `outline` pass builds the outline mask
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutlineMode {
+    /// Expanded back-faces of models with `cast_outline`, in each model's `outline_color`
+    #[default]
+    Hull,
+    /// Screen-space Sobel over depth and depth-derived normals; outlines the whole scene,
+    /// including interior creases, in `outline_edge_color`
+    EdgeDetect,
+}
+
+/// How `filter_chromatic_aberration` offsets the red and blue channels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChromaticAberrationMode {
+    /// Channels split away from the screen center, growing toward the edges like a lens
+    #[default]
+    Radial,
+    /// Channels split by the same amount everywhere, along `chromatic_aberration_angle`
+    Uniform,
+}
+
+/// Which image the bloom pass extracts bright areas from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BloomSource {
+    /// Only the emissive output of materials, so glow is set per material and bright
+    /// lit surfaces never bloom
+    #[default]
+    Emissive,
+    /// The whole lit scene; anything above `bloom_threshold` blooms
+    Scene,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MsaaConfig {
@@ -58,6 +93,11 @@ pub struct EnvironmentConfig {
     pub msaa: MsaaConfig,
     pub skybox: SkyboxConfig,
     pub post: PostProcessConfig,
+    #[serde(default)]
+    pub fog: FogConfig,
+    /// Lay down opaque depth before shading, so each opaque pixel is shaded once
+    #[serde(default)]
+    pub depth_prepass: bool,
 }
 
 impl Default for EnvironmentConfig {
@@ -66,6 +106,48 @@ impl Default for EnvironmentConfig {
             msaa: MsaaConfig::default(),
             skybox: SkyboxConfig::default(),
             post: PostProcessConfig::default(),
+            fog: FogConfig::default(),
+            depth_prepass: false,
+        }
+    }
+}
+
+/// Ray-marched height fog, drawn by the `fog` pass over each camera's HDR color.
+/// Lights scatter into it through their shadow maps, which produces light shafts.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FogConfig {
+    pub enabled: bool,
+    /// Albedo of the fog; tints the light it scatters
+    pub color: Vec3,
+    /// Extinction per world unit at `base_height`
+    pub density: f32,
+    /// Exponential density falloff per world unit above `base_height`
+    pub height_falloff: f32,
+    pub base_height: f32,
+    /// Multiplier on light scattered toward the camera
+    pub scattering: f32,
+    /// Henyey-Greenstein phase asymmetry in [-0.9, 0.9]; positive values brighten
+    /// shafts when looking toward the light
+    pub anisotropy: f32,
+    /// Distance the march stops at when the view ray hits nothing
+    pub max_distance: f32,
+    /// Samples along each view ray
+    pub steps: u32,
+}
+
+impl Default for FogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color: Vec3::ONE,
+            density: 0.02,
+            height_falloff: 0.1,
+            base_height: 0.0,
+            scattering: 1.0,
+            anisotropy: 0.6,
+            max_distance: 100.0,
+            steps: 32,
         }
     }
 }
@@ -79,8 +161,31 @@ pub struct PostProcessConfig {
     pub filter_saturation: f32,
     pub filter_contrast: f32,
     pub filter_vignette: f32,
+    /// Color the vignette fades toward
+    #[serde(default)]
+    pub vignette_color: Vec3,
+    /// 0 stretches the vignette with the screen aspect, 1 keeps it circular
+    #[serde(default)]
+    pub vignette_roundness: f32,
+    /// UV point the vignette is centered on
+    #[serde(default = "default_vignette_center")]
+    pub vignette_center: Vec2,
+    /// Width of the fade, in UV units ending at distance 0.95 from the center
+    #[serde(default = "default_vignette_falloff")]
+    pub vignette_falloff: f32,
     pub filter_grain: f32,
+    /// Edge of one grain cell in pixels
+    #[serde(default = "default_grain_size")]
+    pub grain_size: f32,
+    /// Reseeds the grain every frame; static grain keeps one pattern on screen
+    #[serde(default = "crate::core::resources::common::default_true")]
+    pub grain_temporal: bool,
     pub filter_chromatic_aberration: f32,
+    #[serde(default)]
+    pub chromatic_aberration_mode: ChromaticAberrationMode,
+    /// Direction red shifts in `uniform` mode, in radians; 0 points right
+    #[serde(default)]
+    pub chromatic_aberration_angle: f32,
     pub filter_blur: f32,
     pub filter_sharpen: f32,
     pub filter_tonemap_mode: u32,
@@ -89,6 +194,10 @@ pub struct PostProcessConfig {
     pub outline_threshold: f32,
     pub outline_width: f32,
     pub outline_quality: f32,
+    #[serde(default)]
+    pub outline_mode: OutlineMode,
+    #[serde(default)]
+    pub outline_edge_color: Vec3,
     pub filter_posterize_steps: f32,
     pub cell_shading: bool,
     pub ssao_enabled: bool,
@@ -99,10 +208,15 @@ pub struct PostProcessConfig {
     pub ssao_blur_radius: f32,
     pub ssao_blur_depth_threshold: f32,
     pub bloom_enabled: bool,
+    #[serde(default)]
+    pub bloom_source: BloomSource,
     pub bloom_threshold: f32,
     pub bloom_knee: f32,
     pub bloom_intensity: f32,
     pub bloom_scatter: f32,
+    /// Dithers the post output by half an 8-bit sRGB step to break up gradient banding
+    #[serde(default)]
+    pub dither_enabled: bool,
 }
 
 impl Default for PostProcessConfig {
@@ -114,8 +228,16 @@ impl Default for PostProcessConfig {
             filter_saturation: 1.0,
             filter_contrast: 1.0,
             filter_vignette: 0.0,
+            vignette_color: Vec3::ZERO,
+            vignette_roundness: 0.0,
+            vignette_center: default_vignette_center(),
+            vignette_falloff: default_vignette_falloff(),
             filter_grain: 0.0,
+            grain_size: default_grain_size(),
+            grain_temporal: true,
             filter_chromatic_aberration: 0.0,
+            chromatic_aberration_mode: ChromaticAberrationMode::Radial,
+            chromatic_aberration_angle: 0.0,
             filter_blur: 0.0,
             filter_sharpen: 0.0,
             filter_tonemap_mode: 1,
@@ -124,6 +246,8 @@ impl Default for PostProcessConfig {
             outline_threshold: 0.2,
             outline_width: 1.0,
             outline_quality: 1.0,
+            outline_mode: OutlineMode::Hull,
+            outline_edge_color: Vec3::ZERO,
             filter_posterize_steps: 0.0,
             cell_shading: false,
             ssao_enabled: false,
@@ -134,10 +258,24 @@ impl Default for PostProcessConfig {
             ssao_blur_radius: 2.0,
             ssao_blur_depth_threshold: 0.02,
             bloom_enabled: false,
+            bloom_source: BloomSource::Emissive,
             bloom_threshold: 1.0,
             bloom_knee: 0.5,
             bloom_intensity: 0.8,
             bloom_scatter: 0.7,
+            dither_enabled: false,
         }
     }
 }
+
+fn default_vignette_center() -> Vec2 {
+    Vec2::splat(0.5)
+}
+
+fn default_vignette_falloff() -> f32 {
+    0.25
+}
+
+fn default_grain_size() -> f32 {
+    1.0
+}
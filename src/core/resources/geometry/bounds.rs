@@ -0,0 +1,68 @@
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::core::state::EngineState;
+
+// -----------------------------------------------------------------------------
+// Get Bounds
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdGeometryGetBoundsArgs {
+    pub window_id: u32,
+    pub geometry_id: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultGeometryGetBounds {
+    pub success: bool,
+    pub message: String,
+    pub min: Vec3,
+    pub max: Vec3,
+    pub center: Vec3,
+    /// Half size along each axis
+    pub extents: Vec3,
+}
+
+pub fn engine_cmd_geometry_get_bounds(
+    engine: &mut EngineState,
+    args: &CmdGeometryGetBoundsArgs,
+) -> CmdResultGeometryGetBounds {
+    let window_state = match engine.window.states.get(&args.window_id) {
+        Some(ws) => ws,
+        None => {
+            return CmdResultGeometryGetBounds {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+                ..Default::default()
+            };
+        }
+    };
+
+    let aabb = match window_state
+        .render_state
+        .vertex
+        .as_ref()
+        .and_then(|va| va.borrow().aabb(args.geometry_id))
+    {
+        Some(aabb) => aabb,
+        None => {
+            return CmdResultGeometryGetBounds {
+                success: false,
+                message: format!("Geometry {} not found", args.geometry_id),
+                ..Default::default()
+            };
+        }
+    };
+
+    CmdResultGeometryGetBounds {
+        success: true,
+        message: "Geometry bounds retrieved successfully".into(),
+        min: aabb.min,
+        max: aabb.max,
+        center: (aabb.min + aabb.max) * 0.5,
+        extents: (aabb.max - aabb.min) * 0.5,
+    }
+}
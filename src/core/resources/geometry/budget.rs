@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::cmd::{EngineBatchEvents, EngineEvent};
+use crate::core::resources::vertex::{VertexAllocatorSystem, VertexBudgetPolicy};
+use crate::core::state::EngineState;
+use crate::core::system::SystemEvent;
+
+// -----------------------------------------------------------------------------
+// Budget
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdGeometrySetBudgetArgs {
+    pub window_id: u32,
+    /// `None` removes the limit
+    pub max_total_bytes: Option<u64>,
+    pub policy: VertexBudgetPolicy,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultGeometrySetBudget {
+    pub success: bool,
+    pub message: String,
+    /// Bytes of the live geometries when the budget was set
+    pub used_bytes: u64,
+}
+
+pub fn engine_cmd_geometry_set_budget(
+    engine: &mut EngineState,
+    args: &CmdGeometrySetBudgetArgs,
+) -> CmdResultGeometrySetBudget {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(ws) => ws,
+        None => {
+            return CmdResultGeometrySetBudget {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+                ..Default::default()
+            };
+        }
+    };
+
+    let vertex_rc = match window_state.render_state.vertex.clone() {
+        Some(va) => va,
+        None => {
+            return CmdResultGeometrySetBudget {
+                success: false,
+                message: format!(
+                    "Vertex allocator not initialized for window {}",
+                    args.window_id
+                ),
+                ..Default::default()
+            };
+        }
+    };
+    let mut vertex_guard = vertex_rc.borrow_mut();
+    let vertex_allocator = &mut *vertex_guard;
+
+    vertex_allocator.set_budget(args.max_total_bytes, args.policy);
+
+    CmdResultGeometrySetBudget {
+        success: true,
+        message: "Geometry budget updated successfully".into(),
+        used_bytes: vertex_allocator.used_bytes(),
+    }
+}
+
+/// Reports geometries evicted by the last `create_geometry` as `GeometryEvicted` events
+pub(crate) fn push_evicted_geometry_events(
+    events: &mut EngineBatchEvents,
+    vertex_allocator: &mut VertexAllocatorSystem,
+) {
+    for evicted in vertex_allocator.take_evicted() {
+        events.push(EngineEvent::System(SystemEvent::GeometryEvicted {
+            window_id: evicted.window_id,
+            geometry_id: evicted.geometry_id,
+            bytes: evicted.bytes,
+        }));
+    }
+}
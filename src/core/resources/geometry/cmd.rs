@@ -1,8 +1,13 @@
 use serde::{Deserialize, Serialize};
 
-use crate::core::resources::vertex::GeometryPrimitiveType;
+use crate::core::resources::common::claim_create_id;
+use crate::core::resources::vertex::{
+    GeometryCreateOptions, GeometryPrimitiveType, InterleavedLayout, deinterleave,
+};
 use crate::core::state::EngineState;
 
+use super::push_evicted_geometry_events;
+
 // -----------------------------------------------------------------------------
 // GeometryPrimitiveEntry
 // -----------------------------------------------------------------------------
@@ -14,6 +19,14 @@ pub struct GeometryPrimitiveEntry {
     pub buffer_id: u64,
 }
 
+/// Uploaded buffer holding whole vertices, split into streams on create
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeometryInterleavedEntry {
+    pub buffer_id: u64,
+    pub layout: InterleavedLayout,
+}
+
 // -----------------------------------------------------------------------------
 // Create
 // -----------------------------------------------------------------------------
@@ -24,7 +37,18 @@ pub struct CmdGeometryCreateArgs {
     pub window_id: u32,
     pub geometry_id: u32,
     pub label: Option<String>,
+    #[serde(default)]
     pub entries: Vec<GeometryPrimitiveEntry>,
+    #[serde(default)]
+    pub interleaved: Option<GeometryInterleavedEntry>,
+    #[serde(default)]
+    pub overwrite: bool,
+    #[serde(default)]
+    pub compute_normals: bool,
+    #[serde(default)]
+    pub compute_tangents: bool,
+    #[serde(default)]
+    pub retain_cpu_copy: bool,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -50,7 +74,7 @@ pub fn engine_cmd_geometry_create(
     };
 
     // 2. Validar que temos vertex_allocator
-    let vertex_allocator = match window_state.render_state.vertex.as_mut() {
+    let vertex_rc = match window_state.render_state.vertex.clone() {
         Some(va) => va,
         None => {
             return CmdResultGeometryCreate {
@@ -62,22 +86,49 @@ pub fn engine_cmd_geometry_create(
             };
         }
     };
+    let mut vertex_guard = vertex_rc.borrow_mut();
+    let vertex_allocator = &mut *vertex_guard;
+
+    // With `overwrite`, create_geometry replaces the old geometry only once the new one
+    // is allocated, so a failed create leaves it untouched
+    let exists = vertex_allocator.contains(args.geometry_id);
+    if let Err(message) = claim_create_id("Geometry", args.geometry_id, exists, args.overwrite) {
+        return CmdResultGeometryCreate {
+            success: false,
+            message,
+        };
+    }
 
     // 3. Validar buffers existem
-    for entry in &args.entries {
-        if !engine.buffers.uploads.contains_key(&entry.buffer_id) {
+    let buffer_ids = args
+        .entries
+        .iter()
+        .map(|e| e.buffer_id)
+        .chain(args.interleaved.as_ref().map(|i| i.buffer_id));
+    for buffer_id in buffer_ids.clone() {
+        if !engine.buffers.uploads.contains_key(&buffer_id) {
             return CmdResultGeometryCreate {
                 success: false,
-                message: format!("Buffer {} not found", entry.buffer_id),
+                message: format!("Buffer {} not found", buffer_id),
             };
         }
     }
 
     // 4. Validar tipos primitivos
-    let has_position = args
+    let primitive_types: Vec<GeometryPrimitiveType> = args
         .entries
         .iter()
-        .any(|e| matches!(e.primitive_type, GeometryPrimitiveType::Position));
+        .map(|e| e.primitive_type)
+        .chain(
+            args.interleaved
+                .iter()
+                .flat_map(|i| i.layout.attributes.iter().map(|a| a.primitive_type)),
+        )
+        .collect();
+
+    let has_position = primitive_types
+        .iter()
+        .any(|t| matches!(t, GeometryPrimitiveType::Position));
 
     if !has_position {
         return CmdResultGeometryCreate {
@@ -86,10 +137,9 @@ pub fn engine_cmd_geometry_create(
         };
     }
 
-    let uv_count = args
-        .entries
+    let uv_count = primitive_types
         .iter()
-        .filter(|e| matches!(e.primitive_type, GeometryPrimitiveType::UV))
+        .filter(|t| matches!(t, GeometryPrimitiveType::UV))
         .count();
 
     if uv_count > 2 {
@@ -101,12 +151,12 @@ pub fn engine_cmd_geometry_create(
 
     // Verificar duplicatas (exceto UV)
     let mut seen_types = std::collections::HashSet::new();
-    for entry in &args.entries {
-        if !matches!(entry.primitive_type, GeometryPrimitiveType::UV) {
-            if !seen_types.insert(entry.primitive_type) {
+    for primitive_type in &primitive_types {
+        if !matches!(primitive_type, GeometryPrimitiveType::UV) {
+            if !seen_types.insert(*primitive_type) {
                 return CmdResultGeometryCreate {
                     success: false,
-                    message: format!("Duplicate primitive type: {:?}", entry.primitive_type),
+                    message: format!("Duplicate primitive type: {:?}", primitive_type),
                 };
             }
         }
@@ -127,14 +177,47 @@ pub fn engine_cmd_geometry_create(
         geometry_data.push((entry.primitive_type, buffer.data.clone()));
     }
 
+    if let Some(interleaved) = &args.interleaved {
+        let buffer = match engine.buffers.uploads.get(&interleaved.buffer_id) {
+            Some(buffer) => buffer,
+            None => {
+                return CmdResultGeometryCreate {
+                    success: false,
+                    message: format!("Buffer {} not found", interleaved.buffer_id),
+                };
+            }
+        };
+        match deinterleave(&buffer.data, &interleaved.layout) {
+            Ok(streams) => geometry_data.extend(streams),
+            Err(e) => {
+                return CmdResultGeometryCreate {
+                    success: false,
+                    message: format!("Vertex allocator error: {:?}", e),
+                };
+            }
+        }
+    }
+
     // 6. Criar geometria
-    match vertex_allocator.create_geometry(args.geometry_id, args.label.clone(), geometry_data) {
+    let options = GeometryCreateOptions {
+        compute_normals: args.compute_normals,
+        compute_tangents: args.compute_tangents,
+        retain_cpu_copy: args.retain_cpu_copy,
+    };
+    match vertex_allocator.create_geometry(
+        args.geometry_id,
+        args.window_id,
+        args.label.clone(),
+        geometry_data,
+        options,
+    ) {
         Ok(_) => {
             // 7. Limpar buffers apenas em caso de sucesso
-            for entry in &args.entries {
-                engine.buffers.uploads.remove(&entry.buffer_id);
+            for buffer_id in buffer_ids {
+                engine.buffers.uploads.remove(&buffer_id);
             }
 
+            push_evicted_geometry_events(&mut engine.event_queue, vertex_allocator);
             window_state.is_dirty = true;
 
             CmdResultGeometryCreate {
@@ -188,7 +271,7 @@ pub fn engine_cmd_geometry_update(
     };
 
     // 2. Validar que temos vertex_allocator
-    let vertex_allocator = match window_state.render_state.vertex.as_mut() {
+    let vertex_rc = match window_state.render_state.vertex.clone() {
         Some(va) => va,
         None => {
             return CmdResultGeometryUpdate {
@@ -200,6 +283,8 @@ pub fn engine_cmd_geometry_update(
             };
         }
     };
+    let mut vertex_guard = vertex_rc.borrow_mut();
+    let vertex_allocator = &mut *vertex_guard;
 
     // 3. Se não houver novos dados, apenas atualizar o label se fornecido
     if args.entries.is_none() {
@@ -288,13 +373,28 @@ pub fn engine_cmd_geometry_update(
     }
 
     // 7. Atualizar geometria (create_geometry já trata replace)
-    match vertex_allocator.create_geometry(args.geometry_id, args.label.clone(), geometry_data) {
+    // The new streams keep a CPU copy when the previous ones had one
+    let retain_cpu_copy = vertex_allocator
+        .records()
+        .get(&args.geometry_id)
+        .is_some_and(|record| record.alive && record.cpu_copy.is_some());
+    match vertex_allocator.create_geometry(
+        args.geometry_id,
+        args.window_id,
+        args.label.clone(),
+        geometry_data,
+        GeometryCreateOptions {
+            retain_cpu_copy,
+            ..Default::default()
+        },
+    ) {
         Ok(_) => {
             // 8. Limpar buffers apenas em caso de sucesso
             for entry in entries {
                 engine.buffers.uploads.remove(&entry.buffer_id);
             }
 
+            push_evicted_geometry_events(&mut engine.event_queue, vertex_allocator);
             window_state.is_dirty = true;
 
             CmdResultGeometryUpdate {
@@ -346,7 +446,7 @@ pub fn engine_cmd_geometry_dispose(
     };
 
     // 2. Validar que temos vertex_allocator
-    let vertex_allocator = match window_state.render_state.vertex.as_mut() {
+    let vertex_rc = match window_state.render_state.vertex.clone() {
         Some(va) => va,
         None => {
             return CmdResultGeometryDispose {
@@ -358,6 +458,8 @@ pub fn engine_cmd_geometry_dispose(
             };
         }
     };
+    let mut vertex_guard = vertex_rc.borrow_mut();
+    let vertex_allocator = &mut *vertex_guard;
 
     // 3. Destruir geometria
     match vertex_allocator.destroy_geometry(args.geometry_id) {
@@ -0,0 +1,98 @@
+use bytemuck;
+use glam::{Vec2, Vec3};
+
+use crate::core::resources::geometry::primitives::CapsuleOptions;
+use crate::core::resources::vertex::GeometryPrimitiveType;
+
+use super::compute_tangents;
+
+pub fn generate_capsule(options: &CapsuleOptions) -> Vec<(GeometryPrimitiveType, Vec<u8>)> {
+    let radius = options.radius;
+    let sectors = options.sectors.max(3);
+    let rings = options.rings.max(1);
+    // `height` is the total height, so the cylindrical section shrinks to fit the caps.
+    let half_body = (options.height * 0.5 - radius).max(0.0);
+
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut indices = Vec::new();
+
+    let profile_length = std::f32::consts::PI * radius + half_body * 2.0;
+    let v_scale = if profile_length > 0.0 {
+        1.0 / profile_length
+    } else {
+        0.0
+    };
+
+    // Rows run from the top pole to the bottom pole; the two equator rows
+    // (one per hemisphere) bound the cylindrical section.
+    let mut rows = Vec::with_capacity(((rings + 1) * 2) as usize);
+    for k in 0..=rings {
+        let phi = std::f32::consts::FRAC_PI_2 * k as f32 / rings as f32;
+        rows.push((phi, half_body, radius * phi));
+    }
+    // Without a cylindrical section the equator rows coincide, so share one.
+    let first_bottom = if half_body > 0.0 { 0 } else { 1 };
+    for k in first_bottom..=rings {
+        let phi = std::f32::consts::FRAC_PI_2 * (1.0 + k as f32 / rings as f32);
+        rows.push((phi, -half_body, radius * phi + half_body * 2.0));
+    }
+
+    for (phi, offset, arc) in &rows {
+        let ring_radius = radius * phi.sin();
+        let y = offset + radius * phi.cos();
+        for j in 0..=sectors {
+            let sector_angle = std::f32::consts::PI * 2.0 / sectors as f32 * j as f32;
+            let (sin, cos) = sector_angle.sin_cos();
+            positions.push(Vec3::new(ring_radius * cos, y, ring_radius * sin));
+            normals
+                .push(Vec3::new(phi.sin() * cos, phi.cos(), phi.sin() * sin).normalize_or_zero());
+            uvs.push(Vec2::new(j as f32 / sectors as f32, arc * v_scale));
+        }
+    }
+
+    let stacks = rows.len() as u32 - 1;
+    for i in 0..stacks {
+        for j in 0..sectors {
+            let k1 = i * (sectors + 1) + j;
+            let k2 = k1 + sectors + 1;
+
+            if i != 0 {
+                indices.push(k1);
+                indices.push(k1 + 1);
+                indices.push(k2);
+            }
+
+            if i != (stacks - 1) {
+                indices.push(k1 + 1);
+                indices.push(k2 + 1);
+                indices.push(k2);
+            }
+        }
+    }
+    let tangents = compute_tangents(&positions, &normals, &uvs, &indices);
+
+    vec![
+        (
+            GeometryPrimitiveType::Position,
+            bytemuck::cast_slice(&positions).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::Normal,
+            bytemuck::cast_slice(&normals).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::UV,
+            bytemuck::cast_slice(&uvs).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::Tangent,
+            bytemuck::cast_slice(&tangents).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::Index,
+            bytemuck::cast_slice(&indices).to_vec(),
+        ),
+    ]
+}
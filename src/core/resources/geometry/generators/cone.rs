@@ -0,0 +1,99 @@
+use bytemuck;
+use glam::{Vec2, Vec3};
+
+use crate::core::resources::geometry::primitives::ConeOptions;
+use crate::core::resources::vertex::GeometryPrimitiveType;
+
+use super::compute_tangents;
+
+pub fn generate_cone(options: &ConeOptions) -> Vec<(GeometryPrimitiveType, Vec<u8>)> {
+    let radius = options.radius;
+    let height = options.height;
+    let sectors = options.sectors.max(3);
+
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut indices = Vec::new();
+
+    let side_normal = |angle: f32| {
+        Vec3::new(angle.cos() * height, radius, angle.sin() * height).normalize_or_zero()
+    };
+
+    // Side (one apex vertex per sector so normals stay smooth around the slope)
+    for i in 0..=sectors {
+        let angle = std::f32::consts::PI * 2.0 / sectors as f32 * i as f32;
+        let mid_angle = std::f32::consts::PI * 2.0 / sectors as f32 * (i as f32 + 0.5);
+
+        positions.push(Vec3::new(0.0, height / 2.0, 0.0));
+        normals.push(side_normal(mid_angle));
+        uvs.push(Vec2::new((i as f32 + 0.5) / sectors as f32, 1.0));
+
+        positions.push(Vec3::new(
+            radius * angle.cos(),
+            -height / 2.0,
+            radius * angle.sin(),
+        ));
+        normals.push(side_normal(angle));
+        uvs.push(Vec2::new(i as f32 / sectors as f32, 0.0));
+    }
+
+    for i in 0..sectors {
+        let apex = i * 2;
+        let base = apex + 1;
+        let next_base = (i + 1) * 2 + 1;
+        indices.push(base);
+        indices.push(apex);
+        indices.push(next_base);
+    }
+
+    let uv_scale = if radius.abs() > 0.0 {
+        1.0 / (2.0 * radius)
+    } else {
+        0.0
+    };
+
+    // Bottom cap
+    let bottom_center_index = positions.len() as u32;
+    positions.push(Vec3::new(0.0, -height / 2.0, 0.0));
+    normals.push(Vec3::new(0.0, -1.0, 0.0));
+    uvs.push(Vec2::new(0.5, 0.5));
+    for i in 0..=sectors {
+        let angle = std::f32::consts::PI * 2.0 / sectors as f32 * i as f32;
+        let x = radius * angle.cos();
+        let z = radius * angle.sin();
+        positions.push(Vec3::new(x, -height / 2.0, z));
+        normals.push(Vec3::new(0.0, -1.0, 0.0));
+        uvs.push(Vec2::new(x * uv_scale + 0.5, z * uv_scale + 0.5));
+    }
+
+    for i in 0..sectors {
+        indices.push(bottom_center_index);
+        indices.push(bottom_center_index + i + 1);
+        indices.push(bottom_center_index + i + 2);
+    }
+    let tangents = compute_tangents(&positions, &normals, &uvs, &indices);
+
+    vec![
+        (
+            GeometryPrimitiveType::Position,
+            bytemuck::cast_slice(&positions).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::Normal,
+            bytemuck::cast_slice(&normals).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::UV,
+            bytemuck::cast_slice(&uvs).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::Tangent,
+            bytemuck::cast_slice(&tangents).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::Index,
+            bytemuck::cast_slice(&indices).to_vec(),
+        ),
+    ]
+}
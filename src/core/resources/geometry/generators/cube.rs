@@ -80,6 +80,7 @@ pub fn generate_cube(options: &CubeOptions) -> Vec<(GeometryPrimitiveType, Vec<u
             half_u,
             half_v,
             subdivisions,
+            subdivisions,
         );
     }
     let tangents = compute_tangents(&positions, &normals, &uvs, &indices);
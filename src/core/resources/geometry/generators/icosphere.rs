@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use bytemuck;
+use glam::{Vec2, Vec3};
+
+use crate::core::resources::geometry::primitives::IcosphereOptions;
+use crate::core::resources::vertex::GeometryPrimitiveType;
+
+use super::compute_tangents;
+
+pub fn generate_icosphere(options: &IcosphereOptions) -> Vec<(GeometryPrimitiveType, Vec<u8>)> {
+    let radius = options.radius;
+    let subdivisions = options.subdivisions.min(IcosphereOptions::MAX_SUBDIVISIONS);
+
+    let t = (1.0 + 5.0_f32.sqrt()) / 2.0;
+    let mut points: Vec<Vec3> = [
+        Vec3::new(-1.0, t, 0.0),
+        Vec3::new(1.0, t, 0.0),
+        Vec3::new(-1.0, -t, 0.0),
+        Vec3::new(1.0, -t, 0.0),
+        Vec3::new(0.0, -1.0, t),
+        Vec3::new(0.0, 1.0, t),
+        Vec3::new(0.0, -1.0, -t),
+        Vec3::new(0.0, 1.0, -t),
+        Vec3::new(t, 0.0, -1.0),
+        Vec3::new(t, 0.0, 1.0),
+        Vec3::new(-t, 0.0, -1.0),
+        Vec3::new(-t, 0.0, 1.0),
+    ]
+    .iter()
+    .map(|p| p.normalize())
+    .collect();
+
+    let mut faces: Vec<[u32; 3]> = vec![
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        let mut midpoints: HashMap<(u32, u32), u32> = HashMap::new();
+        let mut midpoint = |a: u32, b: u32, points: &mut Vec<Vec3>| -> u32 {
+            let key = (a.min(b), a.max(b));
+            *midpoints.entry(key).or_insert_with(|| {
+                points.push(((points[a as usize] + points[b as usize]) * 0.5).normalize());
+                points.len() as u32 - 1
+            })
+        };
+
+        let mut next = Vec::with_capacity(faces.len() * 4);
+        for [a, b, c] in faces {
+            let ab = midpoint(a, b, &mut points);
+            let bc = midpoint(b, c, &mut points);
+            let ca = midpoint(c, a, &mut points);
+            next.push([a, ab, ca]);
+            next.push([b, bc, ab]);
+            next.push([c, ca, bc]);
+            next.push([ab, bc, ca]);
+        }
+        faces = next;
+    }
+
+    // Triangles get their own vertices so UVs can be unwrapped across the seam
+    // and at the poles without distorting neighbouring faces.
+    let mut positions = Vec::with_capacity(faces.len() * 3);
+    let mut normals = Vec::with_capacity(faces.len() * 3);
+    let mut uvs = Vec::with_capacity(faces.len() * 3);
+    let mut indices = Vec::with_capacity(faces.len() * 3);
+
+    for face in faces {
+        let dirs = face.map(|i| points[i as usize]);
+        let mut face_uvs = dirs.map(|d| {
+            let u = d.z.atan2(d.x) / (std::f32::consts::PI * 2.0);
+            let u = if u < 0.0 { u + 1.0 } else { u };
+            Vec2::new(u, d.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI)
+        });
+
+        let max_u = face_uvs.iter().map(|uv| uv.x).fold(f32::MIN, f32::max);
+        let min_u = face_uvs.iter().map(|uv| uv.x).fold(f32::MAX, f32::min);
+        if max_u - min_u > 0.5 {
+            for uv in &mut face_uvs {
+                if uv.x < 0.5 {
+                    uv.x += 1.0;
+                }
+            }
+        }
+
+        for k in 0..3 {
+            if dirs[k].y.abs() > 0.9999 {
+                face_uvs[k].x = (face_uvs[(k + 1) % 3].x + face_uvs[(k + 2) % 3].x) * 0.5;
+            }
+        }
+
+        for k in 0..3 {
+            indices.push(positions.len() as u32);
+            positions.push(dirs[k] * radius);
+            normals.push(dirs[k]);
+            uvs.push(face_uvs[k]);
+        }
+    }
+    let tangents = compute_tangents(&positions, &normals, &uvs, &indices);
+
+    vec![
+        (
+            GeometryPrimitiveType::Position,
+            bytemuck::cast_slice(&positions).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::Normal,
+            bytemuck::cast_slice(&normals).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::UV,
+            bytemuck::cast_slice(&uvs).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::Tangent,
+            bytemuck::cast_slice(&tangents).to_vec(),
+        ),
+        (
+            GeometryPrimitiveType::Index,
+            bytemuck::cast_slice(&indices).to_vec(),
+        ),
+    ]
+}
@@ -1,12 +1,20 @@
+mod capsule;
+mod cone;
 mod cube;
 mod cylinder;
+mod icosphere;
 mod plane;
 mod pyramid;
 mod sphere;
+#[cfg(test)]
+mod tests;
 mod torus;
 
+pub use capsule::generate_capsule;
+pub use cone::generate_cone;
 pub use cube::generate_cube;
 pub use cylinder::generate_cylinder;
+pub use icosphere::generate_icosphere;
 pub use plane::generate_plane;
 pub use pyramid::generate_pyramid;
 pub use sphere::generate_sphere;
@@ -24,18 +32,20 @@ pub(crate) fn push_face_grid(
     axis_v: Vec3,
     half_u: f32,
     half_v: f32,
-    subdivisions: u32,
+    subdivisions_u: u32,
+    subdivisions_v: u32,
 ) {
-    let subdivisions = subdivisions.max(1);
-    let n = subdivisions as usize;
+    let subdivisions_u = subdivisions_u.max(1);
+    let subdivisions_v = subdivisions_v.max(1);
+    let (nu, nv) = (subdivisions_u as usize, subdivisions_v as usize);
     let normal = axis_u.cross(axis_v).normalize();
 
     let base_index = positions.len() as u32;
 
-    for y in 0..=n {
-        let v = y as f32 / subdivisions as f32;
-        for x in 0..=n {
-            let u = x as f32 / subdivisions as f32;
+    for y in 0..=nv {
+        let v = y as f32 / subdivisions_v as f32;
+        for x in 0..=nu {
+            let u = x as f32 / subdivisions_u as f32;
             let pos =
                 center + axis_u * ((u * 2.0 - 1.0) * half_u) + axis_v * ((v * 2.0 - 1.0) * half_v);
 
@@ -45,9 +55,9 @@ pub(crate) fn push_face_grid(
         }
     }
 
-    let row_stride = (n + 1) as u32;
-    for y in 0..n {
-        for x in 0..n {
+    let row_stride = (nu + 1) as u32;
+    for y in 0..nv {
+        for x in 0..nu {
             let i0 = base_index + y as u32 * row_stride + x as u32;
             let i1 = i0 + 1;
             let i2 = i0 + row_stride;
@@ -130,6 +140,34 @@ pub(crate) fn push_triangle_grid(
     }
 }
 
+/// Smooth per-vertex normals, area-weighted across the triangles sharing each index
+pub(crate) fn compute_normals(positions: &[Vec3], indices: &[u32]) -> Vec<Vec3> {
+    let count = positions.len();
+    let mut normals = vec![Vec3::ZERO; count];
+
+    for tri in indices.chunks(3) {
+        if tri.len() < 3 {
+            continue;
+        }
+        let i0 = tri[0] as usize;
+        let i1 = tri[1] as usize;
+        let i2 = tri[2] as usize;
+        if i0 >= count || i1 >= count || i2 >= count {
+            continue;
+        }
+
+        let face = (positions[i1] - positions[i0]).cross(positions[i2] - positions[i0]);
+        normals[i0] += face;
+        normals[i1] += face;
+        normals[i2] += face;
+    }
+
+    normals
+        .into_iter()
+        .map(|n| n.normalize_or(Vec3::Y))
+        .collect()
+}
+
 pub(crate) fn compute_tangents(
     positions: &[Vec3],
     normals: &[Vec3],
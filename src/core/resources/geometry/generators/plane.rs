@@ -10,7 +10,14 @@ pub fn generate_plane(options: &PlaneOptions) -> Vec<(GeometryPrimitiveType, Vec
     let half_x = options.size.x / 2.0;
     let half_y = options.size.y / 2.0;
     let _half_z = options.size.z / 2.0;
-    let subdivisions = options.subdivisions.max(1);
+    let subdivisions_x = options
+        .subdivisions_x
+        .unwrap_or(options.subdivisions)
+        .max(1);
+    let subdivisions_y = options
+        .subdivisions_y
+        .unwrap_or(options.subdivisions)
+        .max(1);
 
     let mut positions = Vec::new();
     let mut normals = Vec::new();
@@ -27,7 +34,8 @@ pub fn generate_plane(options: &PlaneOptions) -> Vec<(GeometryPrimitiveType, Vec
         Vec3::Y,
         half_x,
         half_y,
-        subdivisions,
+        subdivisions_x,
+        subdivisions_y,
     );
     let tangents = compute_tangents(&positions, &normals, &uvs, &indices);
 
@@ -29,6 +29,7 @@ pub fn generate_pyramid(options: &PyramidOptions) -> Vec<(GeometryPrimitiveType,
         half_x,
         half_z,
         subdivisions,
+        subdivisions,
     );
 
     let apex = Vec3::new(0.0, half_y, 0.0);
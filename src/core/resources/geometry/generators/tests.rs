@@ -0,0 +1,236 @@
+use std::collections::{HashMap, HashSet};
+
+use glam::Vec3;
+
+use super::{
+    generate_capsule, generate_cone, generate_cube, generate_cylinder, generate_icosphere,
+    generate_plane, generate_sphere, generate_torus,
+};
+use crate::core::resources::geometry::aabb::Aabb;
+use crate::core::resources::geometry::primitives::{
+    CapsuleOptions, ConeOptions, CubeOptions, CylinderOptions, IcosphereOptions, PlaneOptions,
+    SphereOptions, TorusOptions,
+};
+use crate::core::resources::vertex::GeometryPrimitiveType;
+
+fn stream(streams: &[(GeometryPrimitiveType, Vec<u8>)], kind: GeometryPrimitiveType) -> &[u8] {
+    streams
+        .iter()
+        .find(|(primitive, _)| *primitive == kind)
+        .map(|(_, bytes)| bytes.as_slice())
+        .expect("generator emits the stream")
+}
+
+/// Checks index bounds and collects the directed edges of the triangles, matched by
+/// position. Returns the edges and the signed volume they enclose.
+fn check_triangles(streams: &[(GeometryPrimitiveType, Vec<u8>)]) -> (HashSet<(usize, usize)>, f32) {
+    let positions: &[Vec3] = bytemuck::cast_slice(stream(streams, GeometryPrimitiveType::Position));
+    let indices: &[u32] = bytemuck::cast_slice(stream(streams, GeometryPrimitiveType::Index));
+
+    assert!(!indices.is_empty());
+    assert_eq!(indices.len() % 3, 0);
+    assert!(indices.iter().all(|&i| (i as usize) < positions.len()));
+    assert!(positions.iter().all(|p| p.is_finite()));
+
+    // Seams and caps duplicate vertices, so edges are matched by position
+    let mut welded = HashMap::new();
+    let ids: Vec<usize> = positions
+        .iter()
+        .map(|p| {
+            let key = (p * 1.0e4).round().as_ivec3();
+            let next = welded.len();
+            *welded.entry(key).or_insert(next)
+        })
+        .collect();
+
+    let mut edges = HashSet::new();
+    let mut volume = 0.0;
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|k| ids[triangle[k] as usize]);
+        // Rows that collapse into a pole leave zero-area triangles behind
+        if a == b || b == c || c == a {
+            continue;
+        }
+        for edge in [(a, b), (b, c), (c, a)] {
+            assert!(edges.insert(edge), "edge {:?} is used twice", edge);
+        }
+        let [pa, pb, pc] = [0, 1, 2].map(|k| positions[triangle[k] as usize]);
+        volume += pa.dot(pb.cross(pc)) / 6.0;
+    }
+    (edges, volume)
+}
+
+fn positions_aabb(streams: &[(GeometryPrimitiveType, Vec<u8>)]) -> Aabb {
+    let aabb = Aabb::from_bytes(stream(streams, GeometryPrimitiveType::Position));
+    assert!(aabb.min.cmple(aabb.max).all());
+    aabb
+}
+
+/// Checks that the triangles close a mesh wound counter-clockwise seen from outside.
+/// Returns the AABB of the positions.
+fn check_closed_mesh(streams: &[(GeometryPrimitiveType, Vec<u8>)]) -> Aabb {
+    let (edges, volume) = check_triangles(streams);
+    for &(a, b) in &edges {
+        assert!(edges.contains(&(b, a)), "edge {:?} is open", (a, b));
+    }
+    assert!(volume > 0.0, "triangles face inward");
+    positions_aabb(streams)
+}
+
+fn assert_aabb(aabb: Aabb, min: Vec3, max: Vec3) {
+    assert!(
+        aabb.min.abs_diff_eq(min, 1.0e-5),
+        "{:?} != {:?}",
+        aabb.min,
+        min
+    );
+    assert!(
+        aabb.max.abs_diff_eq(max, 1.0e-5),
+        "{:?} != {:?}",
+        aabb.max,
+        max
+    );
+}
+
+#[test]
+fn cone_is_closed() {
+    for sectors in [3, 32] {
+        let aabb = check_closed_mesh(&generate_cone(&ConeOptions {
+            radius: 0.5,
+            height: 2.0,
+            sectors,
+        }));
+        assert_eq!(aabb.min.y, -1.0);
+        assert_eq!(aabb.max.y, 1.0);
+    }
+    let aabb = check_closed_mesh(&generate_cone(&ConeOptions::default()));
+    assert_aabb(aabb, Vec3::new(-0.5, -0.5, -0.5), Vec3::new(0.5, 0.5, 0.5));
+}
+
+#[test]
+fn capsule_is_closed() {
+    let aabb = check_closed_mesh(&generate_capsule(&CapsuleOptions::default()));
+    assert_aabb(
+        aabb,
+        Vec3::new(-0.25, -0.5, -0.25),
+        Vec3::new(0.25, 0.5, 0.25),
+    );
+
+    // No cylindrical section: the hemispheres share the equator row
+    let aabb = check_closed_mesh(&generate_capsule(&CapsuleOptions {
+        radius: 0.5,
+        height: 1.0,
+        sectors: 16,
+        rings: 1,
+    }));
+    assert_aabb(aabb, Vec3::splat(-0.5), Vec3::splat(0.5));
+}
+
+#[test]
+fn icosphere_is_closed() {
+    for subdivisions in 0..=3 {
+        let streams = generate_icosphere(&IcosphereOptions {
+            radius: 2.0,
+            subdivisions,
+        });
+        let aabb = check_closed_mesh(&streams);
+        assert!(aabb.min.cmpge(Vec3::splat(-2.0 - 1.0e-5)).all());
+        assert!(aabb.max.cmple(Vec3::splat(2.0 + 1.0e-5)).all());
+
+        let positions: &[Vec3] =
+            bytemuck::cast_slice(stream(&streams, GeometryPrimitiveType::Position));
+        assert!(positions.iter().all(|p| (p.length() - 2.0).abs() < 1.0e-4));
+    }
+}
+
+#[test]
+fn sphere_is_closed() {
+    for (sectors, stacks) in [(3, 2), (32, 16)] {
+        let aabb = check_closed_mesh(&generate_sphere(&SphereOptions {
+            radius: 2.0,
+            sectors,
+            stacks,
+        }));
+        assert!(aabb.min.cmpge(Vec3::splat(-2.0 - 1.0e-5)).all());
+        assert!(aabb.max.cmple(Vec3::splat(2.0 + 1.0e-5)).all());
+        assert_eq!(aabb.min.y, -2.0);
+        assert_eq!(aabb.max.y, 2.0);
+    }
+    let aabb = check_closed_mesh(&generate_sphere(&SphereOptions::default()));
+    assert_aabb(aabb, Vec3::splat(-0.5), Vec3::splat(0.5));
+}
+
+#[test]
+fn cylinder_is_closed() {
+    for sectors in [3, 32] {
+        let aabb = check_closed_mesh(&generate_cylinder(&CylinderOptions {
+            radius: 0.5,
+            height: 3.0,
+            sectors,
+        }));
+        assert_eq!(aabb.min.y, -1.5);
+        assert_eq!(aabb.max.y, 1.5);
+    }
+    let aabb = check_closed_mesh(&generate_cylinder(&CylinderOptions::default()));
+    assert_aabb(aabb, Vec3::splat(-0.5), Vec3::splat(0.5));
+}
+
+#[test]
+fn torus_is_closed() {
+    for (major_segments, minor_segments) in [(3, 3), (32, 16)] {
+        check_closed_mesh(&generate_torus(&TorusOptions {
+            major_radius: 1.0,
+            minor_radius: 0.25,
+            major_segments,
+            minor_segments,
+        }));
+    }
+    let aabb = check_closed_mesh(&generate_torus(&TorusOptions::default()));
+    assert_aabb(aabb, Vec3::new(-0.5, -0.1, -0.5), Vec3::new(0.5, 0.1, 0.5));
+}
+
+#[test]
+fn cube_is_closed() {
+    for subdivisions in [1, 3] {
+        let aabb = check_closed_mesh(&generate_cube(&CubeOptions {
+            size: Vec3::new(2.0, 4.0, 6.0),
+            subdivisions,
+        }));
+        assert_aabb(aabb, Vec3::new(-1.0, -2.0, -3.0), Vec3::new(1.0, 2.0, 3.0));
+    }
+}
+
+#[test]
+fn plane_is_a_single_sheet_facing_its_normal() {
+    for (x, y) in [(1, 1), (4, 2)] {
+        let streams = generate_plane(&PlaneOptions {
+            size: Vec3::new(2.0, 1.0, 1.0),
+            subdivisions: 1,
+            subdivisions_x: Some(x),
+            subdivisions_y: Some(y),
+        });
+        let (edges, volume) = check_triangles(&streams);
+        assert_eq!(volume, 0.0);
+
+        // Only the outline is open: one edge per quad side along the border
+        let open = edges.iter().filter(|&&(a, b)| !edges.contains(&(b, a)));
+        assert_eq!(open.count() as u32, 2 * (x + y));
+
+        let positions: &[Vec3] =
+            bytemuck::cast_slice(stream(&streams, GeometryPrimitiveType::Position));
+        let normals: &[Vec3] =
+            bytemuck::cast_slice(stream(&streams, GeometryPrimitiveType::Normal));
+        let indices: &[u32] = bytemuck::cast_slice(stream(&streams, GeometryPrimitiveType::Index));
+        for triangle in indices.chunks_exact(3) {
+            let [pa, pb, pc] = [0, 1, 2].map(|k| positions[triangle[k] as usize]);
+            let face = (pb - pa).cross(pc - pa);
+            assert!(
+                face.dot(normals[triangle[0] as usize]) > 0.0,
+                "triangle faces away"
+            );
+        }
+
+        let aabb = positions_aabb(&streams);
+        assert_aabb(aabb, Vec3::new(-1.0, -0.5, 0.0), Vec3::new(1.0, 0.5, 0.0));
+    }
+}
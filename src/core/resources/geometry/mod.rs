@@ -1,10 +1,14 @@
 pub mod aabb;
+pub mod bounds;
+pub mod budget;
 pub mod cmd;
 pub mod frustum;
 pub mod generators;
 pub mod primitives;
 
 pub use aabb::*;
+pub use bounds::*;
+pub use budget::*;
 pub use cmd::*;
 pub use frustum::*;
 pub use primitives::*;
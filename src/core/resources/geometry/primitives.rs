@@ -1,4 +1,6 @@
-use crate::core::resources::geometry::generators;
+use crate::core::resources::common::claim_create_id;
+use crate::core::resources::geometry::{generators, push_evicted_geometry_events};
+use crate::core::resources::vertex::GeometryCreateOptions;
 use crate::core::state::EngineState;
 use glam::Vec3;
 use serde::{Deserialize, Serialize};
@@ -12,6 +14,9 @@ pub enum PrimitiveShape {
     Cylinder,
     Torus,
     Pyramid,
+    Cone,
+    Capsule,
+    Icosphere,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -34,12 +39,24 @@ impl Default for CubeOptions {
 pub struct PlaneOptions {
     pub size: Vec3,
     pub subdivisions: u32,
+    /// Quads along X, overriding `subdivisions` (e.g. long strips of water or terrain)
+    #[serde(default)]
+    pub subdivisions_x: Option<u32>,
+    /// Quads along Y, overriding `subdivisions`
+    #[serde(default)]
+    pub subdivisions_y: Option<u32>,
+}
+impl PlaneOptions {
+    /// Quads per axis; a full grid stays far below the `u32` index range
+    pub const MAX_SUBDIVISIONS: u32 = 4096;
 }
 impl Default for PlaneOptions {
     fn default() -> Self {
         Self {
             size: Vec3::new(1.0, 1.0, 1.0),
             subdivisions: 1,
+            subdivisions_x: None,
+            subdivisions_y: None,
         }
     }
 }
@@ -112,6 +129,62 @@ impl Default for PyramidOptions {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConeOptions {
+    pub radius: f32,
+    pub height: f32,
+    pub sectors: u32,
+}
+impl Default for ConeOptions {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            height: 1.0,
+            sectors: 32,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CapsuleOptions {
+    pub radius: f32,
+    /// Total height including both hemispherical caps
+    pub height: f32,
+    pub sectors: u32,
+    /// Rings per hemisphere
+    pub rings: u32,
+}
+impl Default for CapsuleOptions {
+    fn default() -> Self {
+        Self {
+            radius: 0.25,
+            height: 1.0,
+            sectors: 32,
+            rings: 8,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IcosphereOptions {
+    pub radius: f32,
+    pub subdivisions: u32,
+}
+impl IcosphereOptions {
+    pub const MAX_SUBDIVISIONS: u32 = 6;
+}
+impl Default for IcosphereOptions {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            subdivisions: 3,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(tag = "type", content = "content", rename_all = "kebab-case")]
 pub enum PrimitiveOptions {
@@ -121,6 +194,9 @@ pub enum PrimitiveOptions {
     Cylinder(CylinderOptions),
     Torus(TorusOptions),
     Pyramid(PyramidOptions),
+    Cone(ConeOptions),
+    Capsule(CapsuleOptions),
+    Icosphere(IcosphereOptions),
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -132,6 +208,8 @@ pub struct CmdPrimitiveGeometryCreateArgs {
     pub shape: PrimitiveShape,
     #[serde(default)]
     pub options: Option<PrimitiveOptions>,
+    #[serde(default)]
+    pub overwrite: bool,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -170,6 +248,20 @@ pub fn engine_cmd_primitive_geometry_create(
             PrimitiveOptions::Pyramid(opts.clone())
         }
         (PrimitiveShape::Pyramid, None) => PrimitiveOptions::Pyramid(PyramidOptions::default()),
+        (PrimitiveShape::Cone, Some(PrimitiveOptions::Cone(opts))) => {
+            PrimitiveOptions::Cone(opts.clone())
+        }
+        (PrimitiveShape::Cone, None) => PrimitiveOptions::Cone(ConeOptions::default()),
+        (PrimitiveShape::Capsule, Some(PrimitiveOptions::Capsule(opts))) => {
+            PrimitiveOptions::Capsule(opts.clone())
+        }
+        (PrimitiveShape::Capsule, None) => PrimitiveOptions::Capsule(CapsuleOptions::default()),
+        (PrimitiveShape::Icosphere, Some(PrimitiveOptions::Icosphere(opts))) => {
+            PrimitiveOptions::Icosphere(opts.clone())
+        }
+        (PrimitiveShape::Icosphere, None) => {
+            PrimitiveOptions::Icosphere(IcosphereOptions::default())
+        }
         (shape, Some(_)) => {
             return CmdResultPrimitiveGeometryCreate {
                 success: false,
@@ -197,7 +289,7 @@ pub fn engine_cmd_primitive_geometry_create(
     };
 
     // 2. Get vertex allocator
-    let vertex_allocator = match window_state.render_state.vertex.as_mut() {
+    let vertex_rc = match window_state.render_state.vertex.clone() {
         Some(va) => va,
         None => {
             return CmdResultPrimitiveGeometryCreate {
@@ -209,6 +301,18 @@ pub fn engine_cmd_primitive_geometry_create(
             };
         }
     };
+    let mut vertex_guard = vertex_rc.borrow_mut();
+    let vertex_allocator = &mut *vertex_guard;
+
+    // With `overwrite`, create_geometry replaces the old geometry only once the new one
+    // is allocated, so a failed create leaves it untouched
+    let exists = vertex_allocator.contains(args.geometry_id);
+    if let Err(message) = claim_create_id("Geometry", args.geometry_id, exists, args.overwrite) {
+        return CmdResultPrimitiveGeometryCreate {
+            success: false,
+            message,
+        };
+    }
 
     // 3. Generate data based on shape
     let geometry_data = match options {
@@ -218,11 +322,21 @@ pub fn engine_cmd_primitive_geometry_create(
         PrimitiveOptions::Cylinder(opts) => generators::generate_cylinder(&opts),
         PrimitiveOptions::Torus(opts) => generators::generate_torus(&opts),
         PrimitiveOptions::Pyramid(opts) => generators::generate_pyramid(&opts),
+        PrimitiveOptions::Cone(opts) => generators::generate_cone(&opts),
+        PrimitiveOptions::Capsule(opts) => generators::generate_capsule(&opts),
+        PrimitiveOptions::Icosphere(opts) => generators::generate_icosphere(&opts),
     };
 
     // 4. Create geometry using the vertex allocator
-    match vertex_allocator.create_geometry(args.geometry_id, args.label.clone(), geometry_data) {
+    match vertex_allocator.create_geometry(
+        args.geometry_id,
+        args.window_id,
+        args.label.clone(),
+        geometry_data,
+        GeometryCreateOptions::default(),
+    ) {
         Ok(_) => {
+            push_evicted_geometry_events(&mut engine.event_queue, vertex_allocator);
             window_state.is_dirty = true;
             CmdResultPrimitiveGeometryCreate {
                 success: true,
@@ -242,11 +356,26 @@ fn validate_options(options: &PrimitiveOptions) -> Result<(), String> {
             if opts.subdivisions == 0 {
                 return Err("Cube subdivisions must be >= 1".to_string());
             }
+            check_grid_vertices("Cube", 6, opts.subdivisions, opts.subdivisions)?;
         }
         PrimitiveOptions::Plane(opts) => {
             if opts.subdivisions == 0 {
                 return Err("Plane subdivisions must be >= 1".to_string());
             }
+            if opts.subdivisions_x == Some(0) || opts.subdivisions_y == Some(0) {
+                return Err("Plane subdivisions_x and subdivisions_y must be >= 1".to_string());
+            }
+            let subdivisions_x = opts.subdivisions_x.unwrap_or(opts.subdivisions);
+            let subdivisions_y = opts.subdivisions_y.unwrap_or(opts.subdivisions);
+            if subdivisions_x > PlaneOptions::MAX_SUBDIVISIONS
+                || subdivisions_y > PlaneOptions::MAX_SUBDIVISIONS
+            {
+                return Err(format!(
+                    "Plane subdivisions_x and subdivisions_y must be <= {}",
+                    PlaneOptions::MAX_SUBDIVISIONS
+                ));
+            }
+            check_grid_vertices("Plane", 1, subdivisions_x, subdivisions_y)?;
         }
         PrimitiveOptions::Sphere(opts) => {
             if opts.sectors < 3 {
@@ -273,8 +402,49 @@ fn validate_options(options: &PrimitiveOptions) -> Result<(), String> {
             if opts.subdivisions == 0 {
                 return Err("Pyramid subdivisions must be >= 1".to_string());
             }
+            // The base grid plus four triangle grids, each smaller than the base
+            check_grid_vertices("Pyramid", 5, opts.subdivisions, opts.subdivisions)?;
+        }
+        PrimitiveOptions::Cone(opts) => {
+            if opts.sectors < 3 {
+                return Err("Cone sectors must be >= 3".to_string());
+            }
+        }
+        PrimitiveOptions::Capsule(opts) => {
+            if opts.sectors < 3 {
+                return Err("Capsule sectors must be >= 3".to_string());
+            }
+            if opts.rings == 0 {
+                return Err("Capsule rings must be >= 1".to_string());
+            }
+        }
+        PrimitiveOptions::Icosphere(opts) => {
+            if opts.subdivisions > IcosphereOptions::MAX_SUBDIVISIONS {
+                return Err(format!(
+                    "Icosphere subdivisions must be <= {}",
+                    IcosphereOptions::MAX_SUBDIVISIONS
+                ));
+            }
         }
     }
 
     Ok(())
 }
+
+/// Rejects `faces` grids of `subdivisions_u` by `subdivisions_v` quads that `u32` indices
+/// cannot address
+fn check_grid_vertices(
+    kind: &str,
+    faces: u64,
+    subdivisions_u: u32,
+    subdivisions_v: u32,
+) -> Result<(), String> {
+    let vertices = faces * (subdivisions_u as u64 + 1) * (subdivisions_v as u64 + 1);
+    if vertices > u32::MAX as u64 {
+        return Err(format!(
+            "{} needs {} vertices, more than u32 indices can address",
+            kind, vertices
+        ));
+    }
+    Ok(())
+}
@@ -1,8 +1,8 @@
 use glam::{Vec2, Vec4};
 use serde::{Deserialize, Serialize};
 
-use crate::core::resources::common::default_layer_mask;
-use crate::core::resources::{LightComponent, LightKind, LightRecord};
+use crate::core::resources::common::{claim_create_id, default_layer_mask};
+use crate::core::resources::{LightComponent, LightKind, LightRecord, LightUnits};
 use crate::core::state::EngineState;
 
 // MARK: - Create Light
@@ -25,14 +25,28 @@ pub struct CmdLightCreateArgs {
     pub ground_color: Option<Vec4>,
     #[serde(default)]
     pub intensity: Option<f32>,
+    /// Unit of `intensity` (default: arbitrary)
+    #[serde(default)]
+    pub units: Option<LightUnits>,
     #[serde(default)]
     pub range: Option<f32>,
     #[serde(default)]
     pub spot_inner_outer: Option<Vec2>,
+    /// Width and height of an area light
+    #[serde(default)]
+    pub area_size: Option<Vec2>,
+    /// Right axis of an area light (xyz)
+    #[serde(default)]
+    pub area_right: Option<Vec4>,
+    /// Scale of the light's in-scattering in volumetric fog (default: 1)
+    #[serde(default)]
+    pub volumetric: Option<f32>,
     #[serde(default = "default_layer_mask")]
     pub layer_mask: u32,
     #[serde(default = "crate::core::resources::common::default_true")]
     pub cast_shadow: bool,
+    #[serde(default)]
+    pub overwrite: bool,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -46,8 +60,8 @@ pub fn engine_cmd_light_create(
     engine: &mut EngineState,
     args: &CmdLightCreateArgs,
 ) -> CmdResultLightCreate {
-    let window_state = match engine.window.states.get_mut(&args.window_id) {
-        Some(ws) => ws,
+    let exists = match engine.window.states.get(&args.window_id) {
+        Some(ws) => ws.render_state.scene.lights.contains_key(&args.light_id),
         None => {
             return CmdResultLightCreate {
                 success: false,
@@ -55,18 +69,32 @@ pub fn engine_cmd_light_create(
             };
         }
     };
+    let replace = match claim_create_id("Light", args.light_id, exists, args.overwrite) {
+        Ok(replace) => replace,
+        Err(message) => {
+            return CmdResultLightCreate {
+                success: false,
+                message,
+            };
+        }
+    };
+    if replace {
+        // Nothing past this point fails, so the old light is not lost on error
+        let _ = engine_cmd_light_dispose(
+            engine,
+            &CmdLightDisposeArgs {
+                window_id: args.window_id,
+                light_id: args.light_id,
+            },
+        );
+    }
 
-    if window_state
-        .render_state
-        .scene
-        .lights
-        .contains_key(&args.light_id)
-    {
+    let Some(window_state) = engine.window.states.get_mut(&args.window_id) else {
         return CmdResultLightCreate {
             success: false,
-            message: format!("Light with id {} already exists", args.light_id),
+            message: format!("Window {} not found", args.window_id),
         };
-    }
+    };
 
     let kind = args.kind.unwrap_or(LightKind::Point);
     let position = args.position.unwrap_or(Vec4::new(0.0, 1.0, 0.0, 1.0));
@@ -77,7 +105,7 @@ pub fn engine_cmd_light_create(
     let range = args.range.unwrap_or(10.0);
     let spot_inner_outer = args.spot_inner_outer.unwrap_or(Vec2::new(0.5, 0.8));
 
-    let component = LightComponent::new(
+    let mut component = LightComponent::new(
         position,
         direction,
         color,
@@ -88,6 +116,18 @@ pub fn engine_cmd_light_create(
         kind,
         args.cast_shadow,
     );
+    if let Some(units) = args.units {
+        component.units = units.to_u32();
+    }
+    if let Some(area_size) = args.area_size {
+        component.area_size = area_size;
+    }
+    if let Some(area_right) = args.area_right {
+        component.area_right = area_right;
+    }
+    if let Some(volumetric) = args.volumetric {
+        component.volumetric = volumetric.max(0.0);
+    }
 
     let record = LightRecord::new(
         args.label.clone(),
@@ -125,8 +165,12 @@ pub struct CmdLightUpdateArgs {
     pub color: Option<Vec4>,
     pub ground_color: Option<Vec4>,
     pub intensity: Option<f32>,
+    pub units: Option<LightUnits>,
     pub range: Option<f32>,
     pub spot_inner_outer: Option<Vec2>,
+    pub area_size: Option<Vec2>,
+    pub area_right: Option<Vec4>,
+    pub volumetric: Option<f32>,
     pub layer_mask: Option<u32>,
     pub cast_shadow: Option<bool>,
 }
@@ -204,6 +248,10 @@ pub fn engine_cmd_light_update(
         record.data.intensity_range.x = intensity;
     }
 
+    if let Some(units) = args.units {
+        record.data.units = units.to_u32();
+    }
+
     if let Some(range) = args.range {
         record.data.intensity_range.y = range;
     }
@@ -212,6 +260,18 @@ pub fn engine_cmd_light_update(
         record.data.spot_inner_outer = spot_inner_outer;
     }
 
+    if let Some(area_size) = args.area_size {
+        record.data.area_size = area_size;
+    }
+
+    if let Some(area_right) = args.area_right {
+        record.data.area_right = area_right;
+    }
+
+    if let Some(volumetric) = args.volumetric {
+        record.data.volumetric = volumetric.max(0.0);
+    }
+
     if let Some(layer_mask) = args.layer_mask {
         record.layer_mask = layer_mask;
     }
@@ -10,6 +10,8 @@ pub enum LightKind {
     Spot,
     Ambient,
     Hemisphere,
+    /// One-sided rectangle centered at `position`, emitting along `direction`
+    Area,
 }
 
 impl LightKind {
@@ -20,6 +22,30 @@ impl LightKind {
             LightKind::Spot => 2,
             LightKind::Ambient => 3,
             LightKind::Hemisphere => 4,
+            LightKind::Area => 5,
+        }
+    }
+}
+
+/// How `intensity` is interpreted
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LightUnits {
+    /// Unitless multiplier with the legacy `(1 - d/range)^2` falloff
+    #[default]
+    Arbitrary = 0,
+    /// Luminous intensity (cd) for point and spot lights, with inverse-square falloff
+    Candela,
+    /// Illuminance (lx) for directional, ambient and hemisphere lights
+    Lux,
+}
+
+impl LightUnits {
+    pub fn to_u32(self) -> u32 {
+        match self {
+            LightUnits::Arbitrary => 0,
+            LightUnits::Candela => 1,
+            LightUnits::Lux => 2,
         }
     }
 }
@@ -39,7 +65,15 @@ pub struct LightComponent {
     pub spot_inner_outer: Vec2,
     pub kind_flags: UVec2, // x: kind, y: flags (bit 0: cast_shadow)
     pub shadow_index: u32,
-    pub _padding: u32,
+    /// `LightUnits` of `intensity_range.x`
+    pub units: u32,
+    /// Area lights: xyz = right axis of the rectangle (orthonormalized against `direction`)
+    pub area_right: Vec4,
+    /// Area lights: width and height in world units
+    pub area_size: Vec2,
+    /// Scale of this light's in-scattering in volumetric fog (0 keeps it out of the fog)
+    pub volumetric: f32,
+    pub _padding: f32,
 }
 
 impl LightComponent {
@@ -76,7 +110,11 @@ impl LightComponent {
             spot_inner_outer,
             kind_flags: UVec2::new(kind.to_u32(), flags),
             shadow_index: 0xFFFFFFFF,
-            _padding: 0,
+            units: LightUnits::Arbitrary.to_u32(),
+            area_right: Vec4::X,
+            area_size: Vec2::ONE,
+            volumetric: 1.0,
+            _padding: 0.0,
         }
     }
 }
@@ -105,6 +143,11 @@ impl LightRecord {
         }
     }
 
+    /// Area lights are never shadowed, whatever `cast_shadow` says
+    pub fn casts_shadow(&self) -> bool {
+        self.cast_shadow && self.data.kind_flags.x != LightKind::Area.to_u32()
+    }
+
     pub fn mark_dirty(&mut self) {
         self.is_dirty = true;
     }
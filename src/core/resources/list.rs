@@ -137,27 +137,26 @@ pub fn engine_cmd_texture_list(
     engine: &mut EngineState,
     args: &CmdTextureListArgs,
 ) -> CmdResultTextureList {
-    let window_state = match engine.window.states.get(&args.window_id) {
-        Some(ws) => ws,
-        None => {
-            return CmdResultTextureList {
-                success: false,
-                message: format!("Window {} not found", args.window_id),
-                ..Default::default()
-            };
-        }
-    };
+    if !engine.window.states.contains_key(&args.window_id) {
+        return CmdResultTextureList {
+            success: false,
+            message: format!("Window {} not found", args.window_id),
+            ..Default::default()
+        };
+    }
 
+    // Textures are shared by every window, so any window lists all of them
+    let store = engine.shared.textures().borrow();
     let mut textures = Vec::new();
 
-    for (&id, rec) in &window_state.render_state.scene.textures {
+    for (&id, rec) in &store.records {
         textures.push(ResourceEntry {
             id,
             label: rec.label.clone(),
         });
     }
 
-    for (&id, entry) in &window_state.render_state.scene.forward_atlas_entries {
+    for (&id, entry) in &store.atlas_entries {
         textures.push(ResourceEntry {
             id,
             label: entry.label.clone(),
@@ -205,7 +204,8 @@ pub fn engine_cmd_geometry_list(
     };
 
     let geometries = if let Some(va) = &window_state.render_state.vertex {
-        va.records()
+        va.borrow()
+            .records()
             .iter()
             .map(|(&id, rec)| ResourceEntry {
                 id,
@@ -1,5 +1,6 @@
 use super::types::*;
 use super::utils::{pack_pbr_material, pack_standard_material};
+use crate::core::resources::common::claim_create_id;
 use crate::core::resources::{
     MATERIAL_FALLBACK_ID, MaterialPbrParams, MaterialPbrRecord, MaterialStandardParams,
     MaterialStandardRecord,
@@ -10,8 +11,12 @@ pub fn engine_cmd_material_create(
     engine: &mut EngineState,
     args: &CmdMaterialCreateArgs,
 ) -> CmdResultMaterialCreate {
-    let window_state = match engine.window.states.get_mut(&args.window_id) {
-        Some(ws) => ws,
+    let exists = match engine.window.states.get(&args.window_id) {
+        Some(ws) => {
+            let scene = &ws.render_state.scene;
+            scene.materials_standard.contains_key(&args.material_id)
+                || scene.materials_pbr.contains_key(&args.material_id)
+        }
         None => {
             return CmdResultMaterialCreate {
                 success: false,
@@ -20,30 +25,42 @@ pub fn engine_cmd_material_create(
         }
     };
 
-    if window_state
-        .render_state
-        .scene
-        .materials_standard
-        .contains_key(&args.material_id)
-        || window_state
-            .render_state
-            .scene
-            .materials_pbr
-            .contains_key(&args.material_id)
-    {
+    let replace = match claim_create_id("Material", args.material_id, exists, args.overwrite) {
+        Ok(replace) => replace,
+        Err(message) => {
+            return CmdResultMaterialCreate {
+                success: false,
+                message,
+            };
+        }
+    };
+
+    if args.kind != MaterialKind::Standard && args.kind != MaterialKind::Pbr {
         return CmdResultMaterialCreate {
             success: false,
-            message: format!("Material with id {} already exists", args.material_id),
+            message: "Unsupported material kind".into(),
         };
     }
 
-    if args.kind != MaterialKind::Standard && args.kind != MaterialKind::Pbr {
+    // Validated, so the old material is only replaced once creation cannot fail
+    if replace {
+        let _ = engine_cmd_material_dispose(
+            engine,
+            &CmdMaterialDisposeArgs {
+                window_id: args.window_id,
+                material_id: args.material_id,
+            },
+        );
+    }
+
+    let Some(window_state) = engine.window.states.get_mut(&args.window_id) else {
         return CmdResultMaterialCreate {
             success: false,
-            message: "Unsupported material kind".into(),
+            message: format!("Window {} not found", args.window_id),
         };
-    }
+    };
 
+    let default_sampler = window_state.render_state.default_sampler;
     match args.kind {
         MaterialKind::Standard => {
             let opts = match &args.options {
@@ -53,7 +70,7 @@ pub fn engine_cmd_material_create(
             };
             let mut record =
                 MaterialStandardRecord::new(args.label.clone(), MaterialStandardParams::default());
-            pack_standard_material(args.material_id, &opts, &mut record);
+            pack_standard_material(args.material_id, &opts, default_sampler, &mut record);
             record.bind_group = None;
             window_state
                 .render_state
@@ -69,7 +86,7 @@ pub fn engine_cmd_material_create(
             };
             let mut record =
                 MaterialPbrRecord::new(args.label.clone(), MaterialPbrParams::default());
-            pack_pbr_material(args.material_id, &opts, &mut record);
+            pack_pbr_material(args.material_id, &opts, default_sampler, &mut record);
             record.bind_group = None;
             window_state
                 .render_state
@@ -152,6 +169,7 @@ pub fn engine_cmd_material_update(
     }
 
     if let Some(opts) = &args.options {
+        let default_sampler = window_state.render_state.default_sampler;
         match opts {
             MaterialOptions::Standard(opts) => {
                 if let Some(record) = window_state
@@ -160,7 +178,7 @@ pub fn engine_cmd_material_update(
                     .materials_standard
                     .get_mut(&args.material_id)
                 {
-                    pack_standard_material(args.material_id, &opts, record);
+                    pack_standard_material(args.material_id, &opts, default_sampler, record);
                     record.mark_dirty();
                 }
             }
@@ -171,7 +189,7 @@ pub fn engine_cmd_material_update(
                     .materials_pbr
                     .get_mut(&args.material_id)
                 {
-                    pack_pbr_material(args.material_id, &opts, record);
+                    pack_pbr_material(args.material_id, &opts, default_sampler, record);
                     record.mark_dirty();
                 }
             }
@@ -9,16 +9,28 @@ pub enum MaterialKind {
     Pbr,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 #[repr(u32)]
 pub enum MaterialSampler {
     PointClamp = 0,
+    #[default]
     LinearClamp = 1,
     PointRepeat = 2,
     LinearRepeat = 3,
 }
 
+/// How the `Color0` stream combines with the base color of a standard material
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum VertexColorMode {
+    /// Vertex color tints the base color
+    #[default]
+    Multiply,
+    /// Vertex color takes the place of the base color; textures still apply
+    Replace,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct StandardOptions {
@@ -26,6 +38,8 @@ pub struct StandardOptions {
     pub surface_type: SurfaceType,
     #[serde(default)]
     pub emissive_color: Vec4,
+    /// Multiplier on `emissive_color`, so glow can exceed 1 without changing its hue
+    pub emissive_intensity: Option<f32>,
     pub spec_color: Option<Vec4>,
     pub spec_power: Option<f32>,
     pub base_tex_id: Option<u32>,
@@ -38,8 +52,34 @@ pub struct StandardOptions {
     pub toon_ramp_sampler: Option<MaterialSampler>,
     pub emissive_tex_id: Option<u32>,
     pub emissive_sampler: Option<MaterialSampler>,
+    /// Baked lighting sampled with the second UV set (UV1)
+    pub lightmap_tex_id: Option<u32>,
+    pub lightmap_sampler: Option<MaterialSampler>,
+    pub lightmap_intensity: Option<f32>,
+    /// Blend from no vertex color (0) to the full `vertex_color_mode` effect (1)
+    pub vertex_color_factor: Option<f32>,
+    #[serde(default)]
+    pub vertex_color_mode: VertexColorMode,
+    /// Height map (red channel) pushing vertices along their normal by `displacement_scale`
+    pub displacement_tex_id: Option<u32>,
+    pub displacement_sampler: Option<MaterialSampler>,
+    /// World units of displacement at height 1 (0: off)
+    #[serde(default)]
+    pub displacement_scale: f32,
+    /// View distance over which transparent surfaces fade out in front of opaque geometry,
+    /// hiding hard intersection lines (0: off)
+    #[serde(default)]
+    pub soft_depth_fade: f32,
+    /// Rasterize every pixel a triangle touches, so thin geometry never drops coverage.
+    /// Ignored on devices without conservative rasterization.
+    #[serde(default)]
+    pub conservative_raster: bool,
     pub flags: u32,
+    /// Toon lighting: x = diffuse bands (0: smooth), y = band edge softness,
+    /// z = rim width, w = rim edge softness. A toon ramp texture replaces the bands.
     pub toon_params: Option<Vec4>,
+    /// Rim light color (rgb) and intensity (a), used when `toon_params.z` > 0
+    pub rim_color: Option<Vec4>,
 }
 
 impl Default for StandardOptions {
@@ -48,6 +88,7 @@ impl Default for StandardOptions {
             base_color: Vec4::ONE,
             surface_type: SurfaceType::Opaque,
             emissive_color: Vec4::ZERO,
+            emissive_intensity: None,
             spec_color: None,
             spec_power: None,
             base_tex_id: None,
@@ -60,8 +101,19 @@ impl Default for StandardOptions {
             toon_ramp_sampler: None,
             emissive_tex_id: None,
             emissive_sampler: None,
+            lightmap_tex_id: None,
+            lightmap_sampler: None,
+            lightmap_intensity: None,
+            vertex_color_factor: None,
+            vertex_color_mode: VertexColorMode::Multiply,
+            displacement_tex_id: None,
+            displacement_sampler: None,
+            displacement_scale: 0.0,
+            soft_depth_fade: 0.0,
+            conservative_raster: false,
             flags: 0,
             toon_params: None,
+            rim_color: None,
         }
     }
 }
@@ -72,6 +124,8 @@ pub struct PbrOptions {
     pub base_color: Vec4,
     pub surface_type: SurfaceType,
     pub emissive_color: Vec4,
+    /// See `StandardOptions::emissive_intensity`
+    pub emissive_intensity: Option<f32>,
     pub metallic: f32,
     pub roughness: f32,
     pub ao: f32,
@@ -86,6 +140,21 @@ pub struct PbrOptions {
     pub emissive_sampler: Option<MaterialSampler>,
     pub ao_tex_id: Option<u32>,
     pub ao_sampler: Option<MaterialSampler>,
+    /// Baked diffuse lighting sampled with the second UV set (UV1)
+    pub lightmap_tex_id: Option<u32>,
+    pub lightmap_sampler: Option<MaterialSampler>,
+    pub lightmap_intensity: Option<f32>,
+    /// See `StandardOptions::displacement_tex_id`
+    pub displacement_tex_id: Option<u32>,
+    pub displacement_sampler: Option<MaterialSampler>,
+    #[serde(default)]
+    pub displacement_scale: f32,
+    /// See `StandardOptions::soft_depth_fade`
+    #[serde(default)]
+    pub soft_depth_fade: f32,
+    /// See `StandardOptions::conservative_raster`
+    #[serde(default)]
+    pub conservative_raster: bool,
     pub flags: u32,
 }
 
@@ -95,6 +164,7 @@ impl Default for PbrOptions {
             base_color: Vec4::ONE,
             surface_type: SurfaceType::Opaque,
             emissive_color: Vec4::ZERO,
+            emissive_intensity: None,
             metallic: 0.0,
             roughness: 0.5,
             ao: 1.0,
@@ -109,6 +179,14 @@ impl Default for PbrOptions {
             emissive_sampler: None,
             ao_tex_id: None,
             ao_sampler: None,
+            lightmap_tex_id: None,
+            lightmap_sampler: None,
+            lightmap_intensity: None,
+            displacement_tex_id: None,
+            displacement_sampler: None,
+            displacement_scale: 0.0,
+            soft_depth_fade: 0.0,
+            conservative_raster: false,
             flags: 0,
         }
     }
@@ -132,6 +210,8 @@ pub struct CmdMaterialCreateArgs {
     pub kind: MaterialKind,
     #[serde(default)]
     pub options: Option<MaterialOptions>,
+    #[serde(default)]
+    pub overwrite: bool,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
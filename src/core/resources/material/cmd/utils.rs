@@ -1,4 +1,4 @@
-use super::types::{MaterialSampler, PbrOptions, StandardOptions};
+use super::types::{MaterialSampler, PbrOptions, StandardOptions, VertexColorMode};
 use crate::core::resources::{
     MaterialPbrParams, MaterialPbrRecord, MaterialStandardParams, MaterialStandardRecord,
     PBR_INPUTS_PER_MATERIAL, PBR_INVALID_SLOT, PBR_TEXTURE_SLOTS, STANDARD_INPUTS_PER_MATERIAL,
@@ -9,6 +9,7 @@ use glam::Vec4;
 pub(crate) fn pack_standard_material(
     material_id: u32,
     opts: &StandardOptions,
+    default_sampler: MaterialSampler,
     record: &mut MaterialStandardRecord,
 ) {
     let previous_texture_ids = record.texture_ids;
@@ -20,6 +21,9 @@ pub(crate) fn pack_standard_material(
     if opts.spec_color.is_some() || opts.spec_power.is_some() || opts.spec_tex_id.is_some() {
         flags |= 1;
     }
+    if opts.vertex_color_mode == VertexColorMode::Replace {
+        flags |= 1 << 1;
+    }
     record.data.surface_flags = glam::UVec2::new(opts.surface_type as u32, flags);
 
     let mut texture_slots = [glam::UVec4::splat(STANDARD_INVALID_SLOT); 2];
@@ -64,7 +68,7 @@ pub(crate) fn pack_standard_material(
             assign_sampler(
                 &mut sampler_indices,
                 0,
-                opts.base_sampler.unwrap_or(MaterialSampler::LinearClamp) as u32,
+                opts.base_sampler.unwrap_or(default_sampler) as u32,
             );
         }
     }
@@ -76,7 +80,7 @@ pub(crate) fn pack_standard_material(
             assign_sampler(
                 &mut sampler_indices,
                 1,
-                opts.spec_sampler.unwrap_or(MaterialSampler::LinearClamp) as u32,
+                opts.spec_sampler.unwrap_or(default_sampler) as u32,
             );
         }
     }
@@ -88,7 +92,7 @@ pub(crate) fn pack_standard_material(
             assign_sampler(
                 &mut sampler_indices,
                 2,
-                opts.normal_sampler.unwrap_or(MaterialSampler::LinearClamp) as u32,
+                opts.normal_sampler.unwrap_or(default_sampler) as u32,
             );
         }
     }
@@ -100,8 +104,7 @@ pub(crate) fn pack_standard_material(
             assign_sampler(
                 &mut sampler_indices,
                 3,
-                opts.toon_ramp_sampler
-                    .unwrap_or(MaterialSampler::LinearClamp) as u32,
+                opts.toon_ramp_sampler.unwrap_or(default_sampler) as u32,
             );
         }
     }
@@ -113,8 +116,31 @@ pub(crate) fn pack_standard_material(
             assign_sampler(
                 &mut sampler_indices,
                 4,
-                opts.emissive_sampler
-                    .unwrap_or(MaterialSampler::LinearClamp) as u32,
+                opts.emissive_sampler.unwrap_or(default_sampler) as u32,
+            );
+        }
+    }
+    if let Some(tex_id) = opts.lightmap_tex_id {
+        let slot = 5;
+        if slot < STANDARD_TEXTURE_SLOTS {
+            record.texture_ids[slot] = tex_id;
+            assign_slot(&mut texture_slots, 5, slot as u32);
+            assign_sampler(
+                &mut sampler_indices,
+                5,
+                opts.lightmap_sampler.unwrap_or(default_sampler) as u32,
+            );
+        }
+    }
+    if let Some(tex_id) = opts.displacement_tex_id {
+        let slot = 6;
+        if slot < STANDARD_TEXTURE_SLOTS {
+            record.texture_ids[slot] = tex_id;
+            assign_slot(&mut texture_slots, 6, slot as u32);
+            assign_sampler(
+                &mut sampler_indices,
+                6,
+                opts.displacement_sampler.unwrap_or(default_sampler) as u32,
             );
         }
     }
@@ -126,6 +152,7 @@ pub(crate) fn pack_standard_material(
     record.data.atlas_scale_bias = atlas_scale_bias;
 
     record.surface_type = opts.surface_type;
+    record.conservative_raster = opts.conservative_raster;
     if record.texture_ids != previous_texture_ids {
         record.bind_group = None;
     }
@@ -135,17 +162,23 @@ pub(crate) fn pack_standard_material(
     record.inputs[0] = opts.base_color;
     record.inputs[1] = opts.spec_color.unwrap_or(Vec4::ONE);
     record.inputs[2] = Vec4::new(opts.spec_power.unwrap_or(32.0), 0.0, 0.0, 0.0);
-    record.inputs[3] = opts.emissive_color;
-    if let Some(toon_params) = opts.toon_params {
-        if record.inputs.len() > 4 {
-            record.inputs[4] = toon_params;
-        }
-    }
+    record.inputs[3] = emissive_input(opts.emissive_color, opts.emissive_intensity);
+    record.inputs[4] = opts.toon_params.unwrap_or(Vec4::ZERO);
+    record.inputs[5] = Vec4::new(opts.lightmap_intensity.unwrap_or(1.0), 0.0, 0.0, 0.0);
+    record.inputs[6] = Vec4::new(opts.vertex_color_factor.unwrap_or(1.0), 0.0, 0.0, 0.0);
+    record.inputs[7] = Vec4::new(
+        opts.soft_depth_fade.max(0.0),
+        opts.displacement_scale,
+        0.0,
+        0.0,
+    );
+    record.inputs[8] = opts.rim_color.unwrap_or(Vec4::ONE);
 }
 
 pub(crate) fn pack_pbr_material(
     material_id: u32,
     opts: &PbrOptions,
+    default_sampler: MaterialSampler,
     record: &mut MaterialPbrRecord,
 ) {
     let previous_texture_ids = record.texture_ids;
@@ -197,7 +230,7 @@ pub(crate) fn pack_pbr_material(
             assign_sampler(
                 &mut sampler_indices,
                 0,
-                opts.base_sampler.unwrap_or(MaterialSampler::LinearClamp) as u32,
+                opts.base_sampler.unwrap_or(default_sampler) as u32,
             );
         }
     }
@@ -209,7 +242,7 @@ pub(crate) fn pack_pbr_material(
             assign_sampler(
                 &mut sampler_indices,
                 1,
-                opts.normal_sampler.unwrap_or(MaterialSampler::LinearClamp) as u32,
+                opts.normal_sampler.unwrap_or(default_sampler) as u32,
             );
         }
     }
@@ -221,8 +254,7 @@ pub(crate) fn pack_pbr_material(
             assign_sampler(
                 &mut sampler_indices,
                 2,
-                opts.metallic_roughness_sampler
-                    .unwrap_or(MaterialSampler::LinearClamp) as u32,
+                opts.metallic_roughness_sampler.unwrap_or(default_sampler) as u32,
             );
         }
     }
@@ -234,8 +266,7 @@ pub(crate) fn pack_pbr_material(
             assign_sampler(
                 &mut sampler_indices,
                 3,
-                opts.emissive_sampler
-                    .unwrap_or(MaterialSampler::LinearClamp) as u32,
+                opts.emissive_sampler.unwrap_or(default_sampler) as u32,
             );
         }
     }
@@ -247,7 +278,31 @@ pub(crate) fn pack_pbr_material(
             assign_sampler(
                 &mut sampler_indices,
                 4,
-                opts.ao_sampler.unwrap_or(MaterialSampler::LinearClamp) as u32,
+                opts.ao_sampler.unwrap_or(default_sampler) as u32,
+            );
+        }
+    }
+    if let Some(tex_id) = opts.lightmap_tex_id {
+        let slot = 5;
+        if slot < PBR_TEXTURE_SLOTS {
+            record.texture_ids[slot] = tex_id;
+            assign_slot(&mut texture_slots, 5, slot as u32);
+            assign_sampler(
+                &mut sampler_indices,
+                5,
+                opts.lightmap_sampler.unwrap_or(default_sampler) as u32,
+            );
+        }
+    }
+    if let Some(tex_id) = opts.displacement_tex_id {
+        let slot = 6;
+        if slot < PBR_TEXTURE_SLOTS {
+            record.texture_ids[slot] = tex_id;
+            assign_slot(&mut texture_slots, 6, slot as u32);
+            assign_sampler(
+                &mut sampler_indices,
+                6,
+                opts.displacement_sampler.unwrap_or(default_sampler) as u32,
             );
         }
     }
@@ -259,6 +314,7 @@ pub(crate) fn pack_pbr_material(
     record.data.atlas_scale_bias = atlas_scale_bias;
 
     record.surface_type = opts.surface_type;
+    record.conservative_raster = opts.conservative_raster;
     if record.texture_ids != previous_texture_ids {
         record.bind_group = None;
     }
@@ -266,7 +322,25 @@ pub(crate) fn pack_pbr_material(
         record.inputs = vec![Vec4::ZERO; PBR_INPUTS_PER_MATERIAL as usize];
     }
     record.inputs[0] = opts.base_color;
-    record.inputs[1] = opts.emissive_color;
+    record.inputs[1] = emissive_input(opts.emissive_color, opts.emissive_intensity);
     record.inputs[2] = Vec4::new(opts.metallic, opts.roughness, opts.ao, 0.0);
     record.inputs[3] = Vec4::new(opts.normal_scale, 0.0, 0.0, 0.0);
+    record.inputs[5] = Vec4::new(opts.lightmap_intensity.unwrap_or(1.0), 0.0, 0.0, 0.0);
+    record.inputs[7] = Vec4::new(
+        opts.soft_depth_fade.max(0.0),
+        opts.displacement_scale,
+        0.0,
+        0.0,
+    );
+}
+
+/// Emissive color with its intensity folded into rgb; alpha is kept as given
+fn emissive_input(color: Vec4, intensity: Option<f32>) -> Vec4 {
+    let intensity = intensity.unwrap_or(1.0).max(0.0);
+    Vec4::new(
+        color.x * intensity,
+        color.y * intensity,
+        color.z * intensity,
+        color.w,
+    )
 }
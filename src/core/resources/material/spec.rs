@@ -12,7 +12,7 @@ pub enum SurfaceType {
 }
 
 pub const MATERIAL_FALLBACK_ID: u32 = 0;
-pub const STANDARD_INPUTS_PER_MATERIAL: u32 = 8;
+pub const STANDARD_INPUTS_PER_MATERIAL: u32 = 9;
 pub const STANDARD_TEXTURE_SLOTS: usize = 8;
 pub const STANDARD_INVALID_SLOT: u32 = u32::MAX;
 pub const PBR_INPUTS_PER_MATERIAL: u32 = 8;
@@ -87,6 +87,8 @@ pub struct MaterialStandardRecord {
     pub inputs: Vec<Vec4>,
     pub texture_ids: [u32; STANDARD_TEXTURE_SLOTS],
     pub surface_type: SurfaceType,
+    /// Drawn with conservative rasterization when the device supports it
+    pub conservative_raster: bool,
     pub is_dirty: bool,
     pub bind_group: Option<wgpu::BindGroup>,
 }
@@ -97,12 +99,14 @@ impl MaterialStandardRecord {
         inputs[0] = Vec4::ONE;
         inputs[1] = Vec4::ONE;
         inputs[2] = Vec4::new(32.0, 0.0, 0.0, 0.0);
+        inputs[6] = Vec4::new(1.0, 0.0, 0.0, 0.0);
         Self {
             label,
             data,
             inputs,
             texture_ids: [STANDARD_INVALID_SLOT; STANDARD_TEXTURE_SLOTS],
             surface_type: SurfaceType::Opaque,
+            conservative_raster: false,
             is_dirty: true,
             bind_group: None,
         }
@@ -124,6 +128,8 @@ pub struct MaterialPbrRecord {
     pub inputs: Vec<Vec4>,
     pub texture_ids: [u32; PBR_TEXTURE_SLOTS],
     pub surface_type: SurfaceType,
+    /// Drawn with conservative rasterization when the device supports it
+    pub conservative_raster: bool,
     pub is_dirty: bool,
     pub bind_group: Option<wgpu::BindGroup>,
 }
@@ -141,6 +147,7 @@ impl MaterialPbrRecord {
             inputs,
             texture_ids: [PBR_INVALID_SLOT; PBR_TEXTURE_SLOTS],
             surface_type: SurfaceType::Opaque,
+            conservative_raster: false,
             is_dirty: true,
             bind_group: None,
         }
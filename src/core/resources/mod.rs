@@ -6,8 +6,12 @@ pub mod geometry;
 mod light;
 mod material;
 mod model;
+mod particle;
+mod scene;
 pub mod shadow;
 mod spec;
+mod sprite;
+mod staging;
 mod storage;
 mod texture;
 mod uniform;
@@ -22,7 +26,10 @@ pub use light::*;
 pub use list::*;
 pub use material::*;
 pub use model::*;
+pub use particle::*;
+pub use scene::*;
 pub use spec::*;
+pub use sprite::*;
 pub use storage::*;
 pub use texture::*;
 pub use uniform::*;
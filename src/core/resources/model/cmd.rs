@@ -2,10 +2,16 @@ use glam::Mat4;
 use serde::{Deserialize, Serialize};
 
 use crate::core::render::state::SkinningSystem;
-use crate::core::resources::common::default_layer_mask;
+use crate::core::resources::common::{claim_create_id, default_layer_mask};
 use crate::core::resources::{ModelComponent, ModelRecord};
 use crate::core::state::EngineState;
 
+mod parent;
+mod transform;
+
+pub use parent::*;
+pub use transform::*;
+
 // MARK: - Create Model
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -28,6 +34,11 @@ pub struct CmdModelCreateArgs {
     pub cast_outline: bool,
     #[serde(default = "crate::core::resources::common::default_vec4_zero")]
     pub outline_color: glam::Vec4,
+    /// Draw order within the model's surface bucket (default: 0)
+    #[serde(default)]
+    pub sort_order: i32,
+    #[serde(default)]
+    pub overwrite: bool,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -41,8 +52,8 @@ pub fn engine_cmd_model_create(
     engine: &mut EngineState,
     args: &CmdModelCreateArgs,
 ) -> CmdResultModelCreate {
-    let window_state = match engine.window.states.get_mut(&args.window_id) {
-        Some(ws) => ws,
+    let exists = match engine.window.states.get(&args.window_id) {
+        Some(ws) => ws.render_state.scene.models.contains_key(&args.model_id),
         None => {
             return CmdResultModelCreate {
                 success: false,
@@ -50,21 +61,35 @@ pub fn engine_cmd_model_create(
             };
         }
     };
+    let replace = match claim_create_id("Model", args.model_id, exists, args.overwrite) {
+        Ok(replace) => replace,
+        Err(message) => {
+            return CmdResultModelCreate {
+                success: false,
+                message,
+            };
+        }
+    };
+    if replace {
+        // Nothing past this point fails, so the old model is not lost on error
+        let _ = engine_cmd_model_dispose(
+            engine,
+            &CmdModelDisposeArgs {
+                window_id: args.window_id,
+                model_id: args.model_id,
+            },
+        );
+    }
 
-    if window_state
-        .render_state
-        .scene
-        .models
-        .contains_key(&args.model_id)
-    {
+    let Some(window_state) = engine.window.states.get_mut(&args.window_id) else {
         return CmdResultModelCreate {
             success: false,
-            message: format!("Model with id {} already exists", args.model_id),
+            message: format!("Window {} not found", args.window_id),
         };
-    }
+    };
 
     let component = ModelComponent::new(args.transform, args.receive_shadow, args.outline_color);
-    let record = ModelRecord::new(
+    let mut record = ModelRecord::new(
         args.label.clone(),
         component,
         args.geometry_id,
@@ -74,6 +99,7 @@ pub fn engine_cmd_model_create(
         args.receive_shadow,
         args.cast_outline,
     );
+    record.sort_order = args.sort_order;
     window_state
         .render_state
         .scene
@@ -107,6 +133,7 @@ pub struct CmdModelUpdateArgs {
     pub receive_shadow: Option<bool>,
     pub cast_outline: Option<bool>,
     pub outline_color: Option<glam::Vec4>,
+    pub sort_order: Option<i32>,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -169,14 +196,23 @@ pub fn engine_cmd_model_update(
         record.cast_outline = cast_outline;
     }
 
+    // Parented models resolve their world transform from the hierarchy before rendering
+    if let Some(transform) = args.transform {
+        record.set_local_transform(transform);
+    }
+    let world_transform = args.transform.filter(|_| record.parent_id.is_none());
     record
         .data
-        .update(args.transform, args.receive_shadow, args.outline_color);
+        .update(world_transform, args.receive_shadow, args.outline_color);
 
     if let Some(layer_mask) = args.layer_mask {
         record.layer_mask = layer_mask;
     }
 
+    if let Some(sort_order) = args.sort_order {
+        record.sort_order = sort_order;
+    }
+
     record.mark_dirty();
     if let Some(shadow) = window_state.render_state.shadow.as_mut() {
         shadow.mark_dirty();
@@ -265,12 +301,14 @@ pub fn engine_cmd_pose_update(
         }
     };
 
-    let vertex_allocator = match window_state.render_state.vertex.as_ref() {
+    let vertex_rc = match window_state.render_state.vertex.clone() {
         Some(va) => va,
         None => {
             return fail(engine, "Vertex allocator not initialized".into());
         }
     };
+    let vertex_guard = vertex_rc.borrow();
+    let vertex_allocator = &*vertex_guard;
 
     let has_skin_streams = vertex_allocator
         .geometry_has_streams(
@@ -366,6 +404,13 @@ pub fn engine_cmd_model_dispose(
         .is_some()
     {
         window_state.render_state.skinning.release(args.model_id);
+        // Children stay where they are, now as roots
+        for record in window_state.render_state.scene.models.values_mut() {
+            if record.parent_id == Some(args.model_id) {
+                record.parent_id = None;
+                record.set_local_transform(record.data.transform);
+            }
+        }
         if let Some(shadow) = window_state.render_state.shadow.as_mut() {
             shadow.mark_dirty();
         }
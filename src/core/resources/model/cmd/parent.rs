@@ -0,0 +1,94 @@
+use glam::Mat4;
+use serde::{Deserialize, Serialize};
+
+use crate::core::resources::model::hierarchy::{creates_cycle, resolve_model_transforms};
+use crate::core::state::EngineState;
+
+// MARK: - Set Parent
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdModelSetParentArgs {
+    pub window_id: u32,
+    /// The child model
+    pub model_id: u32,
+    /// New parent in the same window (None: detach and become a root)
+    pub parent_id: Option<u32>,
+    /// Keep the child's current world placement by recomputing its local transform;
+    /// otherwise its transform is reinterpreted relative to the new parent
+    pub keep_world_transform: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultModelSetParent {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_model_set_parent(
+    engine: &mut EngineState,
+    args: &CmdModelSetParentArgs,
+) -> CmdResultModelSetParent {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(ws) => ws,
+        None => {
+            return CmdResultModelSetParent {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    let models = &mut window_state.render_state.scene.models;
+    // Bring pending parent moves in so keep_world_transform reads current placements
+    resolve_model_transforms(models);
+    if !models.contains_key(&args.model_id) {
+        return CmdResultModelSetParent {
+            success: false,
+            message: format!("Model with id {} not found", args.model_id),
+        };
+    }
+
+    let parent_world = match args.parent_id {
+        Some(parent_id) => {
+            let Some(parent) = models.get(&parent_id) else {
+                return CmdResultModelSetParent {
+                    success: false,
+                    message: format!("Parent model with id {} not found", parent_id),
+                };
+            };
+            if creates_cycle(models, args.model_id, parent_id) {
+                return CmdResultModelSetParent {
+                    success: false,
+                    message: format!(
+                        "Model {} cannot be parented to {}: it would create a cycle",
+                        args.model_id, parent_id
+                    ),
+                };
+            }
+            parent.data.transform
+        }
+        None => Mat4::IDENTITY,
+    };
+
+    if let Some(record) = models.get_mut(&args.model_id) {
+        if args.keep_world_transform {
+            let local = parent_world.inverse() * record.data.transform;
+            if local.is_finite() {
+                record.local_transform = local;
+            }
+        }
+        record.parent_id = args.parent_id;
+        record.transform_dirty = true;
+    }
+    if let Some(shadow) = window_state.render_state.shadow.as_mut() {
+        shadow.mark_dirty();
+    }
+    window_state.is_dirty = true;
+
+    CmdResultModelSetParent {
+        success: true,
+        message: "Model parent set successfully".into(),
+    }
+}
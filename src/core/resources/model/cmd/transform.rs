@@ -0,0 +1,80 @@
+use glam::{Mat4, Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+use super::{CmdModelUpdateArgs, engine_cmd_model_update};
+use crate::core::state::EngineState;
+
+// MARK: - Set Transform
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdModelSetTransformArgs {
+    pub window_id: u32,
+    pub model_id: u32,
+    #[serde(default)]
+    pub translation: Vec3,
+    /// Rotation quaternion `[x, y, z, w]`; normalized before use
+    #[serde(default)]
+    pub rotation_quat: Quat,
+    #[serde(default = "default_scale")]
+    pub scale: Vec3,
+}
+
+fn default_scale() -> Vec3 {
+    Vec3::ONE
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultModelSetTransform {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_model_set_transform(
+    engine: &mut EngineState,
+    args: &CmdModelSetTransformArgs,
+) -> CmdResultModelSetTransform {
+    if !(args.translation.is_finite() && args.scale.is_finite()) {
+        return CmdResultModelSetTransform {
+            success: false,
+            message: "Model translation and scale must be finite".into(),
+        };
+    }
+    let rotation_length = args.rotation_quat.length();
+    if !(rotation_length.is_finite() && rotation_length > 1e-6) {
+        return CmdResultModelSetTransform {
+            success: false,
+            message: "Model rotationQuat must be a finite, non-zero quaternion".into(),
+        };
+    }
+
+    // T * R * S, the same matrix as composing Mat4::from_* by hand
+    let transform = Mat4::from_scale_rotation_translation(
+        args.scale,
+        args.rotation_quat / rotation_length,
+        args.translation,
+    );
+    let result = engine_cmd_model_update(
+        engine,
+        &CmdModelUpdateArgs {
+            window_id: args.window_id,
+            model_id: args.model_id,
+            label: None,
+            geometry_id: None,
+            material_id: None,
+            transform: Some(transform),
+            layer_mask: None,
+            cast_shadow: None,
+            receive_shadow: None,
+            cast_outline: None,
+            outline_color: None,
+            sort_order: None,
+        },
+    );
+
+    CmdResultModelSetTransform {
+        success: result.success,
+        message: result.message,
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use glam::Mat4;
+
+use super::ModelRecord;
+use crate::core::state::EngineState;
+
+/// Whether making `parent_id` the parent of `model_id` would close a loop
+pub fn creates_cycle(models: &HashMap<u32, ModelRecord>, model_id: u32, parent_id: u32) -> bool {
+    let mut current = Some(parent_id);
+    // A valid chain visits each model at most once, so a longer walk is a loop already
+    for _ in 0..=models.len() {
+        match current {
+            Some(id) if id == model_id => return true,
+            Some(id) => current = models.get(&id).and_then(|record| record.parent_id),
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Recomputes world transforms of dirty models and everything below them.
+/// Models whose parent no longer exists resolve as roots.
+pub fn resolve_model_transforms(models: &mut HashMap<u32, ModelRecord>) {
+    if !models.values().any(|record| record.transform_dirty) {
+        return;
+    }
+
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    let mut stack: Vec<(u32, Option<Mat4>, bool)> = Vec::new();
+    for (&id, record) in models.iter() {
+        match record
+            .parent_id
+            .filter(|parent| models.contains_key(parent))
+        {
+            Some(parent) => children.entry(parent).or_default().push(id),
+            None => stack.push((id, None, false)),
+        }
+    }
+
+    while let Some((id, parent_world, parent_changed)) = stack.pop() {
+        let Some(record) = models.get_mut(&id) else {
+            continue;
+        };
+        let changed = parent_changed || record.transform_dirty;
+        if changed {
+            let world = parent_world.map_or(record.local_transform, |parent| {
+                parent * record.local_transform
+            });
+            record.data.update(Some(world), None, None);
+            record.transform_dirty = false;
+            record.mark_dirty();
+        }
+        let world = record.data.transform;
+        if let Some(ids) = children.get(&id) {
+            stack.extend(ids.iter().map(|&child| (child, Some(world), changed)));
+        }
+    }
+}
+
+/// Resolves the model hierarchy of every window before the frame is rendered
+pub fn process_model_hierarchy(engine: &mut EngineState) {
+    for window_state in engine.window.states.values_mut() {
+        resolve_model_transforms(&mut window_state.render_state.scene.models);
+    }
+}
@@ -1,5 +1,7 @@
 mod cmd;
+mod hierarchy;
 mod spec;
 
 pub use cmd::*;
+pub use hierarchy::*;
 pub use spec::*;
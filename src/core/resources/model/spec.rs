@@ -83,6 +83,14 @@ pub struct ModelRecord {
     pub cast_shadow: bool,
     pub receive_shadow: bool,
     pub cast_outline: bool,
+    /// Draw order within a surface bucket, independent of `layer_mask` (higher draws later)
+    pub sort_order: i32,
+    /// Transform relative to the parent (world-space without one)
+    pub local_transform: Mat4,
+    /// Parent model in the same window; `data.transform` is resolved as `parent * local`
+    pub parent_id: Option<u32>,
+    /// The world transform must be recomputed, along with every descendant's
+    pub transform_dirty: bool,
     pub is_dirty: bool,
 }
 
@@ -99,6 +107,7 @@ impl ModelRecord {
     ) -> Self {
         Self {
             label,
+            local_transform: data.transform,
             data,
             geometry_id,
             material_id,
@@ -106,10 +115,18 @@ impl ModelRecord {
             cast_shadow,
             receive_shadow,
             cast_outline,
+            sort_order: 0,
+            parent_id: None,
+            transform_dirty: true,
             is_dirty: true,
         }
     }
 
+    pub fn set_local_transform(&mut self, transform: Mat4) {
+        self.local_transform = transform;
+        self.transform_dirty = true;
+    }
+
     pub fn mark_dirty(&mut self) {
         self.is_dirty = true;
     }
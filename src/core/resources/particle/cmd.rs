@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::resources::{ParticleSystemConfig, ParticleSystemRecord};
+use crate::core::state::EngineState;
+
+// MARK: - Create Particle System
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdParticleSystemCreateArgs {
+    pub window_id: u32,
+    pub particle_system_id: u32,
+    pub label: Option<String>,
+    #[serde(default)]
+    pub config: ParticleSystemConfig,
+    /// Particles spawned on the first tick, on top of `spawn_rate`
+    #[serde(default)]
+    pub burst: u32,
+    /// Start with continuous spawning on (default: true)
+    #[serde(default = "crate::core::resources::common::default_true")]
+    pub emitting: bool,
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultParticleSystemCreate {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_particle_system_create(
+    engine: &mut EngineState,
+    args: &CmdParticleSystemCreateArgs,
+) -> CmdResultParticleSystemCreate {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(ws) => ws,
+        None => {
+            return CmdResultParticleSystemCreate {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    let systems = &mut window_state.render_state.scene.particle_systems;
+    if systems.contains_key(&args.particle_system_id) && !args.overwrite {
+        return CmdResultParticleSystemCreate {
+            success: false,
+            message: format!(
+                "Particle system with id {} already exists",
+                args.particle_system_id
+            ),
+        };
+    }
+
+    let mut record = ParticleSystemRecord::new(args.label.clone(), args.config.clone());
+    record.pending_burst = args.burst;
+    record.emitting = args.emitting;
+    systems.insert(args.particle_system_id, record);
+    window_state.is_dirty = true;
+
+    CmdResultParticleSystemCreate {
+        success: true,
+        message: "Particle system created successfully".into(),
+    }
+}
+
+// MARK: - Update Particle System
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdParticleSystemUpdateArgs {
+    pub window_id: u32,
+    pub particle_system_id: u32,
+    pub label: Option<String>,
+    /// Replaces the whole config; live particles keep their state
+    pub config: Option<ParticleSystemConfig>,
+    /// Particles to spawn on the next tick
+    pub burst: Option<u32>,
+    pub emitting: Option<bool>,
+    /// Removes every live particle before spawning the burst
+    #[serde(default)]
+    pub clear: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultParticleSystemUpdate {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_particle_system_update(
+    engine: &mut EngineState,
+    args: &CmdParticleSystemUpdateArgs,
+) -> CmdResultParticleSystemUpdate {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(ws) => ws,
+        None => {
+            return CmdResultParticleSystemUpdate {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    let record = match window_state
+        .render_state
+        .scene
+        .particle_systems
+        .get_mut(&args.particle_system_id)
+    {
+        Some(r) => r,
+        None => {
+            return CmdResultParticleSystemUpdate {
+                success: false,
+                message: format!(
+                    "Particle system with id {} not found",
+                    args.particle_system_id
+                ),
+            };
+        }
+    };
+
+    if args.label.is_some() {
+        record.label = args.label.clone();
+    }
+
+    if let Some(config) = &args.config {
+        if config.seed != record.config.seed {
+            record.rng_state = config.seed.max(1);
+        }
+        record.config = config.clone();
+        let max_particles = record.config.max_particles as usize;
+        record.particles.truncate(max_particles);
+    }
+
+    if args.clear {
+        record.particles.clear();
+        record.spawn_accumulator = 0.0;
+    }
+
+    if let Some(burst) = args.burst {
+        record.pending_burst = record.pending_burst.saturating_add(burst);
+    }
+
+    if let Some(emitting) = args.emitting {
+        record.emitting = emitting;
+    }
+
+    window_state.is_dirty = true;
+
+    CmdResultParticleSystemUpdate {
+        success: true,
+        message: "Particle system updated successfully".into(),
+    }
+}
+
+// MARK: - Dispose Particle System
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdParticleSystemDisposeArgs {
+    pub window_id: u32,
+    pub particle_system_id: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultParticleSystemDispose {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_particle_system_dispose(
+    engine: &mut EngineState,
+    args: &CmdParticleSystemDisposeArgs,
+) -> CmdResultParticleSystemDispose {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(ws) => ws,
+        None => {
+            return CmdResultParticleSystemDispose {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    if window_state
+        .render_state
+        .scene
+        .particle_systems
+        .remove(&args.particle_system_id)
+        .is_some()
+    {
+        window_state.is_dirty = true;
+        CmdResultParticleSystemDispose {
+            success: true,
+            message: "Particle system disposed successfully".into(),
+        }
+    } else {
+        CmdResultParticleSystemDispose {
+            success: false,
+            message: format!(
+                "Particle system with id {} not found",
+                args.particle_system_id
+            ),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod cmd;
+mod sim;
+mod spec;
+
+pub use cmd::*;
+pub use sim::*;
+pub use spec::*;
@@ -0,0 +1,108 @@
+use glam::Vec3;
+
+use super::{Particle, ParticleSystemRecord};
+use crate::core::state::EngineState;
+
+/// Longest step simulated at once, so a stalled frame does not fling particles
+const MAX_STEP_SECONDS: f32 = 0.1;
+
+impl ParticleSystemRecord {
+    /// Advances every particle by `delta_seconds`, then retires and spawns
+    pub fn step(&mut self, delta_seconds: f32) {
+        let dt = delta_seconds.clamp(0.0, MAX_STEP_SECONDS);
+        let gravity = self.config.gravity;
+        let damping = 1.0 / (1.0 + self.config.drag.max(0.0) * dt);
+
+        for particle in &mut self.particles {
+            particle.velocity = (particle.velocity + gravity * dt) * damping;
+            particle.position += particle.velocity * dt;
+            particle.rotation += particle.spin * dt;
+            particle.age += dt;
+        }
+        self.particles
+            .retain(|particle| particle.age < particle.lifetime);
+
+        let mut spawn = std::mem::take(&mut self.pending_burst);
+        if self.emitting && self.config.spawn_rate > 0.0 {
+            self.spawn_accumulator += self.config.spawn_rate * dt;
+            let whole = self.spawn_accumulator.floor();
+            self.spawn_accumulator -= whole;
+            spawn = spawn.saturating_add(whole as u32);
+        }
+
+        let room = (self.config.max_particles as usize).saturating_sub(self.particles.len());
+        for _ in 0..(spawn as usize).min(room) {
+            let particle = self.spawn_particle();
+            self.particles.push(particle);
+        }
+    }
+
+    fn spawn_particle(&mut self) -> Particle {
+        let config = &self.config;
+        let (lifetime_min, lifetime_max) =
+            (config.lifetime.x, config.lifetime.y.max(config.lifetime.x));
+        let (origin, spawn_radius) = (config.position, config.spawn_radius.max(0.0));
+        let (velocity, spread) = (config.velocity, config.velocity_spread.max(0.0));
+        let rotation_speed = config.rotation_speed;
+
+        let offset = self.random_in_sphere() * spawn_radius;
+        let jitter = self.random_in_sphere() * spread;
+        let lifetime = lifetime_min + (lifetime_max - lifetime_min) * self.random();
+        let rotation = self.random() * std::f32::consts::TAU;
+        let spin = (self.random() * 2.0 - 1.0) * rotation_speed;
+
+        Particle {
+            position: origin + offset,
+            velocity: velocity + jitter,
+            age: 0.0,
+            lifetime: lifetime.max(1e-3),
+            rotation,
+            spin,
+        }
+    }
+
+    /// xorshift32 in [0, 1)
+    fn random(&mut self) -> f32 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.rng_state = x;
+        (x >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    fn random_in_sphere(&mut self) -> Vec3 {
+        loop {
+            let v = Vec3::new(self.random(), self.random(), self.random()) * 2.0 - Vec3::ONE;
+            if v.length_squared() <= 1.0 {
+                return v;
+            }
+        }
+    }
+}
+
+/// Simulates every particle system for this tick. Windows with live particles redraw.
+/// With fixed stepping on, systems advance by the tick's fixed steps instead of its delta.
+pub fn process_particle_systems(engine: &mut EngineState) {
+    let (steps, delta_seconds) = if engine.fixed_step.enabled() {
+        (engine.fixed_step.steps, engine.fixed_step.step_ms / 1000.0)
+    } else {
+        (1, engine.delta_time as f32 / 1000.0)
+    };
+    for window_state in engine.window.states.values_mut() {
+        let systems = &mut window_state.render_state.scene.particle_systems;
+        if systems.is_empty() {
+            continue;
+        }
+        let mut alive = false;
+        for record in systems.values_mut() {
+            for _ in 0..steps {
+                record.step(delta_seconds);
+            }
+            alive |= !record.particles.is_empty();
+        }
+        if alive {
+            window_state.is_dirty = true;
+        }
+    }
+}
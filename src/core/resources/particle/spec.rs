@@ -0,0 +1,136 @@
+use bytemuck::{Pod, Zeroable};
+use glam::{Vec2, Vec3, Vec4};
+use serde::{Deserialize, Serialize};
+
+/// How particles combine with the color behind them
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ParticleBlendMode {
+    /// Adds color weighted by alpha; order independent
+    #[default]
+    Additive,
+    /// Alpha-blended over the scene, sorted back to front per camera
+    Alpha,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ParticleSystemConfig {
+    /// Live particles are capped here; spawns past the cap are dropped
+    pub max_particles: u32,
+    /// Particles spawned per second while emitting (0: bursts only)
+    pub spawn_rate: f32,
+    /// Lifetime range in seconds (min, max)
+    pub lifetime: Vec2,
+    /// World-space emitter origin
+    pub position: Vec3,
+    /// Particles spawn uniformly inside a sphere of this radius around `position`
+    pub spawn_radius: f32,
+    pub velocity: Vec3,
+    /// Random velocity added in a sphere of this radius
+    pub velocity_spread: f32,
+    pub gravity: Vec3,
+    /// Linear velocity damping per second
+    pub drag: f32,
+    /// Billboard size in world units at birth and at death
+    pub size_start: f32,
+    pub size_end: f32,
+    pub color_start: Vec4,
+    pub color_end: Vec4,
+    /// Random spin range in radians per second (-value..value)
+    pub rotation_speed: f32,
+    pub blend: ParticleBlendMode,
+    /// View distance over which particles fade out where they meet geometry (0: hard edge)
+    pub soft_distance: f32,
+    /// Sprite texture; particles without one are soft round dots
+    pub texture_id: Option<u32>,
+    pub layer_mask: u32,
+    /// Seed of the spawn randomness, so replays emit the same particles
+    pub seed: u32,
+}
+
+impl Default for ParticleSystemConfig {
+    fn default() -> Self {
+        Self {
+            max_particles: 1024,
+            spawn_rate: 32.0,
+            lifetime: Vec2::new(1.0, 2.0),
+            position: Vec3::ZERO,
+            spawn_radius: 0.1,
+            velocity: Vec3::new(0.0, 1.0, 0.0),
+            velocity_spread: 0.5,
+            gravity: Vec3::ZERO,
+            drag: 0.0,
+            size_start: 0.2,
+            size_end: 0.05,
+            color_start: Vec4::ONE,
+            color_end: Vec4::new(1.0, 1.0, 1.0, 0.0),
+            rotation_speed: 0.0,
+            blend: ParticleBlendMode::Additive,
+            soft_distance: 0.5,
+            texture_id: None,
+            layer_mask: 0xFFFFFFFF,
+            seed: 1,
+        }
+    }
+}
+
+/// One simulated particle
+#[derive(Debug, Clone, Copy)]
+pub struct Particle {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub age: f32,
+    pub lifetime: f32,
+    pub rotation: f32,
+    pub spin: f32,
+}
+
+/// Per-particle data read by the `particles` pass
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Pod, Zeroable)]
+pub struct ParticleInstance {
+    /// xyz: world position, w: size
+    pub position_size: Vec4,
+    pub color: Vec4,
+    /// x: rotation in radians
+    pub rotation: Vec4,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParticleSystemRecord {
+    pub label: Option<String>,
+    pub config: ParticleSystemConfig,
+    pub particles: Vec<Particle>,
+    /// Continuous spawning; bursts still spawn while stopped
+    pub emitting: bool,
+    pub pending_burst: u32,
+    pub spawn_accumulator: f32,
+    pub rng_state: u32,
+}
+
+impl ParticleSystemRecord {
+    pub fn new(label: Option<String>, config: ParticleSystemConfig) -> Self {
+        let rng_state = config.seed.max(1);
+        Self {
+            label,
+            config,
+            particles: Vec::new(),
+            emitting: true,
+            pending_burst: 0,
+            spawn_accumulator: 0.0,
+            rng_state,
+        }
+    }
+
+    pub fn instance(&self, particle: &Particle) -> ParticleInstance {
+        let t = (particle.age / particle.lifetime.max(1e-5)).clamp(0.0, 1.0);
+        let config = &self.config;
+        let size = config.size_start + (config.size_end - config.size_start) * t;
+        ParticleInstance {
+            position_size: particle.position.extend(size.max(0.0)),
+            color: config.color_start.lerp(config.color_end, t),
+            rotation: Vec4::new(particle.rotation, 0.0, 0.0, 0.0),
+        }
+    }
+}
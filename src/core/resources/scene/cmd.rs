@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::resources::MATERIAL_FALLBACK_ID;
+use crate::core::resources::texture::utils::mark_texture_users_dirty;
+use crate::core::state::EngineState;
+
+// MARK: - Reset Scene
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdSceneResetArgs {
+    pub window_id: u32,
+    /// Keep geometries, materials and textures alive (only the scene graph is cleared).
+    #[serde(default)]
+    pub keep_shared_resources: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultSceneReset {
+    pub success: bool,
+    pub message: String,
+    pub models_freed: u32,
+    pub cameras_freed: u32,
+    pub lights_freed: u32,
+    pub particle_systems_freed: u32,
+    pub sprite_batches_freed: u32,
+    pub geometries_freed: u32,
+    pub materials_freed: u32,
+    pub textures_freed: u32,
+}
+
+pub fn engine_cmd_scene_reset(
+    engine: &mut EngineState,
+    args: &CmdSceneResetArgs,
+) -> CmdResultSceneReset {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(ws) => ws,
+        None => {
+            return CmdResultSceneReset {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+                ..Default::default()
+            };
+        }
+    };
+
+    let render_state = &mut window_state.render_state;
+    let scene = &mut render_state.scene;
+    let mut freed_textures = Vec::new();
+    let mut result = CmdResultSceneReset {
+        success: true,
+        message: "Scene reset successfully".into(),
+        ..Default::default()
+    };
+
+    result.models_freed = scene.models.len() as u32;
+    scene.models.clear();
+    render_state.skinning.clear();
+
+    result.cameras_freed = scene.cameras.len() as u32;
+    for (camera_id, _) in scene.cameras.drain() {
+        engine.camera_controllers.remove(&camera_id);
+    }
+
+    let light_ids: Vec<u32> = scene.lights.drain().map(|(id, _)| id).collect();
+    result.lights_freed = light_ids.len() as u32;
+    if let Some(shadow) = render_state.shadow.as_mut() {
+        for light_id in light_ids {
+            shadow.free_light(light_id);
+        }
+        shadow.mark_dirty();
+    }
+
+    result.particle_systems_freed = scene.particle_systems.len() as u32;
+    scene.particle_systems.clear();
+
+    result.sprite_batches_freed = scene.sprite_batches.len() as u32;
+    scene.sprite_batches.clear();
+
+    if !args.keep_shared_resources {
+        // Geometries are shared by every window; only those this window created are freed
+        if let Some(vertex_rc) = render_state.vertex.as_ref() {
+            let mut vertex = vertex_rc.borrow_mut();
+            let geometry_ids: Vec<u32> = vertex
+                .records()
+                .iter()
+                .filter(|(_, record)| record.alive && record.window_id == args.window_id)
+                .map(|(id, _)| *id)
+                .collect();
+            for geometry_id in geometry_ids {
+                if vertex.destroy_geometry(geometry_id).is_ok() {
+                    result.geometries_freed += 1;
+                }
+            }
+        }
+
+        let standard_before = scene.materials_standard.len();
+        let pbr_before = scene.materials_pbr.len();
+        scene
+            .materials_standard
+            .retain(|id, _| *id == MATERIAL_FALLBACK_ID);
+        scene
+            .materials_pbr
+            .retain(|id, _| *id == MATERIAL_FALLBACK_ID);
+        result.materials_freed = (standard_before - scene.materials_standard.len() + pbr_before
+            - scene.materials_pbr.len()) as u32;
+
+        // Textures are shared too; only those this window created are freed
+        let mut textures = engine.shared.textures().borrow_mut();
+        freed_textures = textures.owned_by(args.window_id);
+        for &texture_id in &freed_textures {
+            textures.remove(texture_id);
+        }
+        result.textures_freed = freed_textures.len() as u32;
+        engine.texture_async.cancel_window(args.window_id);
+
+        for record in scene.materials_standard.values_mut() {
+            record.mark_dirty();
+        }
+    }
+
+    window_state.is_dirty = true;
+    // Other windows' materials may have sampled the freed textures
+    mark_texture_users_dirty(&mut engine.window, &freed_textures);
+    result
+}
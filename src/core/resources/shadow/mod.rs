@@ -9,9 +9,21 @@ use wgpu::{Device, TextureFormat, TextureUsages};
 
 mod atlas;
 pub mod cmd;
+mod pages;
 pub use atlas::{ShadowAtlasDesc, ShadowAtlasHandle, ShadowAtlasRelocation, ShadowAtlasSystem};
 pub use cmd::*;
 
+/// What to do when a new shadow page does not fit in the atlas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShadowOverflowMode {
+    /// Skip the page; the area it covers renders unshadowed this frame
+    Drop,
+    /// Take over the least-recently-used page that is not visible this frame
+    #[default]
+    Degrade,
+}
+
 /// Configuration for the Shadow Manager
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 #[serde(default, rename_all = "camelCase")]
@@ -23,6 +35,25 @@ pub struct ShadowConfig {
     pub virtual_grid_size: u32,
     pub smoothing: u32,
     pub normal_bias: f32,
+    /// Minimum depth bias for directional/spot lights
+    pub bias_min: f32,
+    /// Slope-scaled depth bias for directional/spot lights
+    pub bias_slope: f32,
+    /// Minimum depth bias for point lights
+    pub point_bias_min: f32,
+    /// Slope-scaled depth bias for point lights
+    pub point_bias_slope: f32,
+    /// Percentage-closer soft shadows (penumbra grows with blocker distance).
+    /// Replaces the fixed `smoothing` kernel when enabled.
+    pub soft_shadows: bool,
+    /// Light size for soft shadows, as a fraction of a shadow page
+    pub light_size: f32,
+    /// Blocker search radius for soft shadows, in atlas texels
+    pub blocker_search_radius: f32,
+    /// Frames a page may stay unused before its atlas tile is freed (0 = never evict)
+    pub max_page_age: u32,
+    /// Behavior when the atlas is full
+    pub overflow_mode: ShadowOverflowMode,
 }
 
 impl Default for ShadowConfig {
@@ -35,6 +66,16 @@ impl Default for ShadowConfig {
             virtual_grid_size: 1,
             smoothing: 2,
             normal_bias: 0.01,
+            // Very small defaults, tuned for Reverse Z + Float32 depth
+            bias_min: 0.00001,
+            bias_slope: 0.0001,
+            point_bias_min: 0.0001,
+            point_bias_slope: 0.0005,
+            soft_shadows: false,
+            light_size: 0.05,
+            blocker_search_radius: 8.0,
+            max_page_age: 120,
+            overflow_mode: ShadowOverflowMode::Degrade,
         }
     }
 }
@@ -51,7 +92,27 @@ pub struct ShadowParams {
     pub point_bias_min: f32,
     pub point_bias_slope: f32,
     pub normal_bias: f32,
-    pub _padding: [f32; 3],
+    pub pcss_enabled: u32,
+    pub pcss_light_size: f32,
+    pub pcss_search_radius: f32,
+}
+
+impl ShadowParams {
+    pub fn new(config: &ShadowConfig, table_capacity: u32) -> Self {
+        Self {
+            virtual_grid_size: config.virtual_grid_size as f32,
+            pcf_range: config.smoothing as i32,
+            table_capacity,
+            bias_min: config.bias_min,
+            bias_slope: config.bias_slope,
+            point_bias_min: config.point_bias_min,
+            point_bias_slope: config.point_bias_slope,
+            normal_bias: config.normal_bias,
+            pcss_enabled: config.soft_shadows as u32,
+            pcss_light_size: config.light_size,
+            pcss_search_radius: config.blocker_search_radius,
+        }
+    }
 }
 
 /// Unique identifier for a virtual shadow page
@@ -71,6 +132,19 @@ pub struct ShadowPageRecord {
     pub is_dirty: bool,
 }
 
+/// Pages that did not fit in the atlas during the current shadow update
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShadowOverflowStats {
+    pub dropped_pages: u32,
+    pub reused_pages: u32,
+}
+
+impl ShadowOverflowStats {
+    pub fn is_empty(&self) -> bool {
+        self.dropped_pages == 0 && self.reused_pages == 0
+    }
+}
+
 /// Entry in the GPU page table for Virtual Shadow Maps
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -106,6 +180,11 @@ pub struct ShadowManager {
     pub cache: HashMap<ShadowPageKey, ShadowPageRecord>,
 
     pub config: ShadowConfig,
+
+    /// Atlas overflow counters, reset at the start of every shadow update
+    pub overflow: ShadowOverflowStats,
+    /// Frame of the last overflow warning event (rate limiting)
+    pub last_overflow_warning: Option<u64>,
 }
 
 impl ShadowManager {
@@ -131,20 +210,7 @@ impl ShadowManager {
         let point_light_vp = StorageBufferPool::new(device, queue, Some(128), storage_alignment);
         let mut params_pool = UniformBufferPool::new(device, queue, Some(1), alignment);
 
-        params_pool.write(
-            0,
-            &ShadowParams {
-                virtual_grid_size: config.virtual_grid_size as f32,
-                pcf_range: config.smoothing as i32,
-                table_capacity,
-                bias_min: 0.00001, // Very small default for Reverse Z + Float32
-                bias_slope: 0.0001,
-                point_bias_min: 0.0001,
-                point_bias_slope: 0.0005,
-                normal_bias: config.normal_bias,
-                _padding: [0.0; 3],
-            },
-        );
+        params_pool.write(0, &ShadowParams::new(&config, table_capacity));
 
         Self {
             atlas,
@@ -155,6 +221,8 @@ impl ShadowManager {
             cache: HashMap::new(),
             config,
             is_dirty: true,
+            overflow: ShadowOverflowStats::default(),
+            last_overflow_warning: None,
         }
     }
 
@@ -170,20 +238,9 @@ impl ShadowManager {
 
         self.config = config;
 
-        self.params_pool.write(
-            0,
-            &ShadowParams {
-                virtual_grid_size: config.virtual_grid_size as f32,
-                pcf_range: config.smoothing as i32,
-                table_capacity: self.table_capacity,
-                bias_min: 0.00001,
-                bias_slope: 0.0001,
-                point_bias_min: 0.0001,
-                point_bias_slope: 0.0005,
-                normal_bias: config.normal_bias,
-                _padding: [0.0; 3],
-            },
-        );
+        // Biases only affect sampling, so they never invalidate cached pages
+        self.params_pool
+            .write(0, &ShadowParams::new(&config, self.table_capacity));
 
         if needs_atlas_rebuild {
             let atlas_desc = ShadowAtlasDesc {
@@ -344,60 +401,6 @@ impl ShadowManager {
         custom_proj * light_proj * light_view
     }
 
-    /// Requests a tile for a specific virtual page.
-
-    /// If the page is already cached, returns its handle.
-    /// If not, tries to allocate a new one.
-    pub fn request_page(
-        &mut self,
-        light_id: u32,
-        face: u32,
-        x: u32,
-        y: u32,
-        frame_index: u64,
-    ) -> Option<ShadowAtlasHandle> {
-        let key = ShadowPageKey {
-            light_id,
-            face,
-            x,
-            y,
-        };
-
-        if let Some(record) = self.cache.get_mut(&key) {
-            record.last_frame_used = frame_index;
-            return Some(record.atlas_handle);
-        }
-
-        // New page needed
-        if let Some((handle, relocations)) = self.atlas.alloc(1, 1) {
-            // Handle relocations if repack happened
-            for relocation in relocations {
-                self.update_cache_after_relocation(relocation);
-            }
-
-            self.cache.insert(
-                key,
-                ShadowPageRecord {
-                    atlas_handle: handle,
-                    last_frame_used: frame_index,
-                    is_dirty: true,
-                },
-            );
-
-            return Some(handle);
-        }
-
-        None
-    }
-
-    fn update_cache_after_relocation(&mut self, relocation: ShadowAtlasRelocation) {
-        for record in self.cache.values_mut() {
-            if record.atlas_handle == relocation.handle {
-                record.is_dirty = true; // Must re-render since it moved
-            }
-        }
-    }
-
     /// Synchronizes the GPU page table with the current cache state
     pub fn sync_table(&mut self) {
         let mut entries = vec![ShadowPageEntry::default(); self.table_capacity as usize];
@@ -422,6 +425,12 @@ impl ShadowManager {
         self.page_table.write_slice(0, &entries);
     }
 
+    pub fn flush(&mut self) {
+        self.page_table.flush();
+        self.point_light_vp.flush();
+        self.params_pool.flush();
+    }
+
     pub fn begin_frame(&mut self, frame_index: u64) {
         self.page_table.begin_frame(frame_index);
         self.point_light_vp.begin_frame(frame_index);
@@ -432,27 +441,6 @@ impl ShadowManager {
         self.is_dirty = true;
     }
 
-    pub fn free_light(&mut self, light_id: u32) {
-        let mut to_remove: Vec<(ShadowPageKey, ShadowAtlasHandle)> = Vec::new();
-
-        for (key, record) in &self.cache {
-            if key.light_id == light_id {
-                to_remove.push((*key, record.atlas_handle));
-            }
-        }
-
-        if to_remove.is_empty() {
-            return;
-        }
-
-        for (key, handle) in to_remove {
-            self.cache.remove(&key);
-            self.atlas.free(handle);
-        }
-
-        self.is_dirty = true;
-    }
-
     pub fn clear_dirty(&mut self) {
         self.is_dirty = false;
     }
@@ -0,0 +1,153 @@
+use super::{
+    ShadowAtlasHandle, ShadowAtlasRelocation, ShadowManager, ShadowOverflowMode, ShadowPageKey,
+    ShadowPageRecord,
+};
+
+impl ShadowManager {
+    /// Requests a tile for a specific virtual page.
+
+    /// If the page is already cached, returns its handle.
+    /// If not, tries to allocate a new one.
+    pub fn request_page(
+        &mut self,
+        light_id: u32,
+        face: u32,
+        x: u32,
+        y: u32,
+        frame_index: u64,
+    ) -> Option<ShadowAtlasHandle> {
+        let key = ShadowPageKey {
+            light_id,
+            face,
+            x,
+            y,
+        };
+
+        if let Some(record) = self.cache.get_mut(&key) {
+            record.last_frame_used = frame_index;
+            return Some(record.atlas_handle);
+        }
+
+        // New page needed
+        if let Some((handle, relocations)) = self.atlas.alloc(1, 1) {
+            // Handle relocations if repack happened
+            for relocation in relocations {
+                self.update_cache_after_relocation(relocation);
+            }
+
+            self.cache.insert(
+                key,
+                ShadowPageRecord {
+                    atlas_handle: handle,
+                    last_frame_used: frame_index,
+                    is_dirty: true,
+                },
+            );
+
+            return Some(handle);
+        }
+
+        // Atlas full
+        if self.config.overflow_mode == ShadowOverflowMode::Drop {
+            self.overflow.dropped_pages += 1;
+            return None;
+        }
+
+        match self.take_lru_page(light_id, frame_index) {
+            Some(handle) => {
+                self.cache.insert(
+                    key,
+                    ShadowPageRecord {
+                        atlas_handle: handle,
+                        last_frame_used: frame_index,
+                        is_dirty: true,
+                    },
+                );
+                self.overflow.reused_pages += 1;
+                Some(handle)
+            }
+            None => {
+                self.overflow.dropped_pages += 1;
+                None
+            }
+        }
+    }
+
+    /// Removes the least-recently-used page not requested this frame, preferring
+    /// pages of `light_id`, and returns its atlas handle for reuse.
+    fn take_lru_page(&mut self, light_id: u32, frame_index: u64) -> Option<ShadowAtlasHandle> {
+        let key = self
+            .cache
+            .iter()
+            .filter(|(_, record)| record.last_frame_used < frame_index)
+            .min_by_key(|(key, record)| (key.light_id != light_id, record.last_frame_used))
+            .map(|(key, _)| *key)?;
+
+        self.cache.remove(&key).map(|record| record.atlas_handle)
+    }
+
+    /// Returns true when an overflow warning should be emitted for `frame_index`.
+    /// Warnings are limited to one per `interval` frames.
+    pub fn should_warn_overflow(&mut self, frame_index: u64, interval: u64) -> bool {
+        if self.overflow.is_empty() {
+            return false;
+        }
+        if let Some(last) = self.last_overflow_warning
+            && frame_index.saturating_sub(last) < interval
+        {
+            return false;
+        }
+        self.last_overflow_warning = Some(frame_index);
+        true
+    }
+
+    fn update_cache_after_relocation(&mut self, relocation: ShadowAtlasRelocation) {
+        for record in self.cache.values_mut() {
+            if record.atlas_handle == relocation.handle {
+                record.is_dirty = true; // Must re-render since it moved
+            }
+        }
+    }
+
+    pub fn free_light(&mut self, light_id: u32) {
+        let mut to_remove: Vec<(ShadowPageKey, ShadowAtlasHandle)> = Vec::new();
+
+        for (key, record) in &self.cache {
+            if key.light_id == light_id {
+                to_remove.push((*key, record.atlas_handle));
+            }
+        }
+
+        if to_remove.is_empty() {
+            return;
+        }
+
+        for (key, handle) in to_remove {
+            self.cache.remove(&key);
+            self.atlas.free(handle);
+        }
+
+        self.is_dirty = true;
+    }
+
+    /// Frees atlas tiles of pages not requested within `max_age` frames.
+    /// Returns the number of evicted pages.
+    pub fn evict_stale(&mut self, frame_index: u64, max_age: u32) -> usize {
+        if max_age == 0 {
+            return 0;
+        }
+
+        let mut evicted = 0;
+        let atlas = &mut self.atlas;
+        self.cache.retain(|_, record| {
+            if frame_index.saturating_sub(record.last_frame_used) <= max_age as u64 {
+                return true;
+            }
+            atlas.free(record.atlas_handle);
+            evicted += 1;
+            false
+        });
+
+        evicted
+    }
+}
@@ -8,7 +8,8 @@ pub struct FrameComponent {
     pub time: f32,
     pub delta_time: f32,
     pub frame_index: u32,
-    _padding: u32,
+    /// Mip bias of material textures in the forward pass (`SamplerLodConfig::mip_lod_bias`)
+    pub mip_bias: f32,
 }
 
 impl FrameComponent {
@@ -17,7 +18,7 @@ impl FrameComponent {
             time,
             delta_time,
             frame_index,
-            _padding: 0,
+            mip_bias: 0.0,
         }
     }
 }
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::resources::{Sprite, SpriteBatchRecord};
+use crate::core::state::EngineState;
+
+// MARK: - Create Sprite Batch
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdSpriteBatchCreateArgs {
+    pub window_id: u32,
+    pub sprite_batch_id: u32,
+    pub label: Option<String>,
+    pub texture_id: Option<u32>,
+    #[serde(default)]
+    pub order: i32,
+    #[serde(default)]
+    pub sprites: Vec<Sprite>,
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultSpriteBatchCreate {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_sprite_batch_create(
+    engine: &mut EngineState,
+    args: &CmdSpriteBatchCreateArgs,
+) -> CmdResultSpriteBatchCreate {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(ws) => ws,
+        None => {
+            return CmdResultSpriteBatchCreate {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    let batches = &mut window_state.render_state.scene.sprite_batches;
+    if batches.contains_key(&args.sprite_batch_id) && !args.overwrite {
+        return CmdResultSpriteBatchCreate {
+            success: false,
+            message: format!(
+                "Sprite batch with id {} already exists",
+                args.sprite_batch_id
+            ),
+        };
+    }
+
+    batches.insert(
+        args.sprite_batch_id,
+        SpriteBatchRecord {
+            label: args.label.clone(),
+            texture_id: args.texture_id,
+            order: args.order,
+            visible: true,
+            sprites: args.sprites.clone(),
+        },
+    );
+    window_state.is_dirty = true;
+
+    CmdResultSpriteBatchCreate {
+        success: true,
+        message: "Sprite batch created successfully".into(),
+    }
+}
+
+// MARK: - Update Sprite Batch
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdSpriteBatchUpdateArgs {
+    pub window_id: u32,
+    pub sprite_batch_id: u32,
+    pub label: Option<String>,
+    pub texture_id: Option<u32>,
+    /// Draws the batch with solid-color sprites again
+    #[serde(default)]
+    pub clear_texture: bool,
+    pub order: Option<i32>,
+    pub visible: Option<bool>,
+    /// Replaces every sprite of the batch
+    pub sprites: Option<Vec<Sprite>>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultSpriteBatchUpdate {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_sprite_batch_update(
+    engine: &mut EngineState,
+    args: &CmdSpriteBatchUpdateArgs,
+) -> CmdResultSpriteBatchUpdate {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(ws) => ws,
+        None => {
+            return CmdResultSpriteBatchUpdate {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    let record = match window_state
+        .render_state
+        .scene
+        .sprite_batches
+        .get_mut(&args.sprite_batch_id)
+    {
+        Some(r) => r,
+        None => {
+            return CmdResultSpriteBatchUpdate {
+                success: false,
+                message: format!("Sprite batch with id {} not found", args.sprite_batch_id),
+            };
+        }
+    };
+
+    if args.label.is_some() {
+        record.label = args.label.clone();
+    }
+
+    if args.clear_texture {
+        record.texture_id = None;
+    } else if args.texture_id.is_some() {
+        record.texture_id = args.texture_id;
+    }
+
+    if let Some(order) = args.order {
+        record.order = order;
+    }
+
+    if let Some(visible) = args.visible {
+        record.visible = visible;
+    }
+
+    if let Some(sprites) = &args.sprites {
+        record.sprites.clone_from(sprites);
+    }
+
+    window_state.is_dirty = true;
+
+    CmdResultSpriteBatchUpdate {
+        success: true,
+        message: "Sprite batch updated successfully".into(),
+    }
+}
+
+// MARK: - Dispose Sprite Batch
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdSpriteBatchDisposeArgs {
+    pub window_id: u32,
+    pub sprite_batch_id: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultSpriteBatchDispose {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_sprite_batch_dispose(
+    engine: &mut EngineState,
+    args: &CmdSpriteBatchDisposeArgs,
+) -> CmdResultSpriteBatchDispose {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(ws) => ws,
+        None => {
+            return CmdResultSpriteBatchDispose {
+                success: false,
+                message: format!("Window {} not found", args.window_id),
+            };
+        }
+    };
+
+    if window_state
+        .render_state
+        .scene
+        .sprite_batches
+        .remove(&args.sprite_batch_id)
+        .is_some()
+    {
+        window_state.is_dirty = true;
+        CmdResultSpriteBatchDispose {
+            success: true,
+            message: "Sprite batch disposed successfully".into(),
+        }
+    } else {
+        CmdResultSpriteBatchDispose {
+            success: false,
+            message: format!("Sprite batch with id {} not found", args.sprite_batch_id),
+        }
+    }
+}
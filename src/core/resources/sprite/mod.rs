@@ -0,0 +1,5 @@
+mod cmd;
+mod spec;
+
+pub use cmd::*;
+pub use spec::*;
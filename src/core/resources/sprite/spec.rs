@@ -0,0 +1,157 @@
+use bytemuck::{Pod, Zeroable};
+use glam::{Vec2, Vec4};
+use serde::{Deserialize, Serialize};
+
+/// One textured quad in window pixels, origin at the top-left corner
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Sprite {
+    /// Pixel position of the pivot
+    pub position: Vec2,
+    /// Quad size in pixels
+    pub size: Vec2,
+    /// Clockwise rotation around the pivot, in radians
+    pub rotation: f32,
+    /// Point of the quad placed at `position` and rotated around (0,0: top-left, 1,1: bottom-right)
+    pub pivot: Vec2,
+    /// Texture region as (u_min, v_min, u_max, v_max)
+    pub uv_rect: Vec4,
+    /// Linear RGBA multiplied with the texture
+    pub color: Vec4,
+    /// Draws the quad as nine slices so its corners keep their size when stretched
+    pub nine_slice: Option<NineSlice>,
+}
+
+/// Border insets of a nine-slice sprite. Corners keep their size, edges stretch along one
+/// axis and the center stretches along both.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NineSlice {
+    /// Border widths in texture pixels as (left, top, right, bottom)
+    pub insets: Vec4,
+    /// Screen pixels per texture pixel of the borders
+    pub scale: f32,
+}
+
+impl Default for NineSlice {
+    fn default() -> Self {
+        Self {
+            insets: Vec4::ZERO,
+            scale: 1.0,
+        }
+    }
+}
+
+impl Default for Sprite {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            size: Vec2::ONE,
+            rotation: 0.0,
+            pivot: Vec2::splat(0.5),
+            uv_rect: Vec4::new(0.0, 0.0, 1.0, 1.0),
+            color: Vec4::ONE,
+            nine_slice: None,
+        }
+    }
+}
+
+impl Sprite {
+    /// Appends the instances drawing this sprite: one quad, or up to nine for a nine-slice.
+    /// `texture_size` converts the nine-slice insets to UVs.
+    pub fn push_instances(&self, texture_size: Vec2, instances: &mut Vec<SpriteInstance>) {
+        let Some(slice) = self.nine_slice else {
+            instances.push(SpriteInstance::from(self));
+            return;
+        };
+
+        // Screen borders shrink together when they would overlap
+        let size = self.size.max(Vec2::ZERO);
+        let insets = slice.insets.max(Vec4::ZERO);
+        let border_min = Vec2::new(insets.x, insets.y) * slice.scale.max(0.0);
+        let border_max = Vec2::new(insets.z, insets.w) * slice.scale.max(0.0);
+        let fit = (size / (border_min + border_max).max(Vec2::splat(1e-6))).min(Vec2::ONE);
+        let (border_min, border_max) = (border_min * fit, border_max * fit);
+        let xs = [0.0, border_min.x, size.x - border_max.x, size.x];
+        let ys = [0.0, border_min.y, size.y - border_max.y, size.y];
+
+        let texture_size = texture_size.max(Vec2::ONE);
+        let uv_min = Vec2::new(insets.x, insets.y) / texture_size;
+        let uv_max = Vec2::new(insets.z, insets.w) / texture_size;
+        let us = [
+            self.uv_rect.x,
+            self.uv_rect.x + uv_min.x,
+            self.uv_rect.z - uv_max.x,
+            self.uv_rect.z,
+        ];
+        let vs = [
+            self.uv_rect.y,
+            self.uv_rect.y + uv_min.y,
+            self.uv_rect.w - uv_max.y,
+            self.uv_rect.w,
+        ];
+
+        // Every slice turns around the pivot of the whole sprite
+        let pivot = self.pivot * size;
+        for row in 0..3 {
+            for column in 0..3 {
+                let cell_min = Vec2::new(xs[column], ys[row]);
+                let cell_size = Vec2::new(xs[column + 1], ys[row + 1]) - cell_min;
+                if cell_size.x <= 0.0 || cell_size.y <= 0.0 {
+                    continue;
+                }
+                let cell_pivot = (pivot - cell_min) / cell_size;
+                instances.push(SpriteInstance {
+                    position_size: Vec4::new(
+                        self.position.x,
+                        self.position.y,
+                        cell_size.x,
+                        cell_size.y,
+                    ),
+                    uv_rect: Vec4::new(us[column], vs[row], us[column + 1], vs[row + 1]),
+                    color: self.color,
+                    pivot_rotation: Vec4::new(cell_pivot.x, cell_pivot.y, self.rotation, 0.0),
+                });
+            }
+        }
+    }
+}
+
+/// Per-sprite data read by the sprite pass
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Pod, Zeroable)]
+pub struct SpriteInstance {
+    /// xy: pixel position, zw: pixel size
+    pub position_size: Vec4,
+    pub uv_rect: Vec4,
+    pub color: Vec4,
+    /// xy: pivot, z: rotation in radians
+    pub pivot_rotation: Vec4,
+}
+
+impl From<&Sprite> for SpriteInstance {
+    fn from(sprite: &Sprite) -> Self {
+        Self {
+            position_size: Vec4::new(
+                sprite.position.x,
+                sprite.position.y,
+                sprite.size.x,
+                sprite.size.y,
+            ),
+            uv_rect: sprite.uv_rect,
+            color: sprite.color,
+            pivot_rotation: Vec4::new(sprite.pivot.x, sprite.pivot.y, sprite.rotation, 0.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpriteBatchRecord {
+    pub label: Option<String>,
+    /// Texture sampled by every sprite; without one sprites are solid color
+    pub texture_id: Option<u32>,
+    /// Batches draw in ascending order; sprites within a batch in submission order
+    pub order: i32,
+    pub visible: bool,
+    pub sprites: Vec<Sprite>,
+}
@@ -0,0 +1,97 @@
+// MARK: - Upload Batch
+
+// -----------------------------------------------------------------------------
+// Internal types
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy)]
+struct PendingWrite {
+    offset: u64,
+    start: usize,
+    len: usize,
+}
+
+// -----------------------------------------------------------------------------
+// UploadBatch
+// -----------------------------------------------------------------------------
+
+/// Collects small writes aimed at one buffer and flushes them as one
+/// `queue.write_buffer` per contiguous run.
+///
+/// The byte storage is reused between flushes, so steady-state frames do not
+/// allocate. Flushing must happen before the next submit that reads the buffer;
+/// overlapping writes resolve in recording order, exactly like direct writes.
+#[derive(Debug, Default)]
+pub(crate) struct UploadBatch {
+    bytes: Vec<u8>,
+    writes: Vec<PendingWrite>,
+    run: Vec<u8>,
+}
+
+impl UploadBatch {
+    pub fn push(&mut self, offset: u64, data: &[u8]) {
+        self.push_padded(offset, data, data.len());
+    }
+
+    /// Records `data` followed by zeros up to `size` bytes, so neighbouring
+    /// items of an aligned pool merge into one run
+    pub fn push_padded(&mut self, offset: u64, data: &[u8], size: usize) {
+        let len = size.max(data.len());
+        if len == 0 {
+            return;
+        }
+
+        let start = self.bytes.len();
+        self.bytes.extend_from_slice(data);
+        self.bytes.resize(start + len, 0);
+        self.writes.push(PendingWrite { offset, start, len });
+    }
+
+    pub fn flush(&mut self, queue: &wgpu::Queue, buffer: &wgpu::Buffer) {
+        if self.writes.is_empty() {
+            return;
+        }
+
+        // Stable: writes to the same offset keep their recording order
+        self.writes.sort_by_key(|write| write.offset);
+
+        let mut first = 0;
+        while first < self.writes.len() {
+            let run_offset = self.writes[first].offset;
+            let mut run_end = run_offset + self.writes[first].len as u64;
+            let mut last = first + 1;
+            while last < self.writes.len() && self.writes[last].offset <= run_end {
+                let write = self.writes[last];
+                run_end = run_end.max(write.offset + write.len as u64);
+                last += 1;
+            }
+
+            if last - first == 1 {
+                let write = self.writes[first];
+                queue.write_buffer(
+                    buffer,
+                    write.offset,
+                    &self.bytes[write.start..write.start + write.len],
+                );
+            } else {
+                // Replay in recording order so the latest write wins on overlap
+                let run = &mut self.writes[first..last];
+                run.sort_by_key(|write| write.start);
+
+                self.run.clear();
+                self.run.resize((run_end - run_offset) as usize, 0);
+                for write in run.iter() {
+                    let at = (write.offset - run_offset) as usize;
+                    self.run[at..at + write.len]
+                        .copy_from_slice(&self.bytes[write.start..write.start + write.len]);
+                }
+                queue.write_buffer(buffer, run_offset, &self.run);
+            }
+
+            first = last;
+        }
+
+        self.bytes.clear();
+        self.writes.clear();
+    }
+}
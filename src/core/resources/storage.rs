@@ -3,6 +3,8 @@
 use bytemuck::{Pod, bytes_of, cast_slice};
 use std::marker::PhantomData;
 
+use super::staging::UploadBatch;
+
 // -----------------------------------------------------------------------------
 // Internal types
 // -----------------------------------------------------------------------------
@@ -20,11 +22,15 @@ struct GarbageEntry {
 #[derive(Debug)]
 pub struct StorageBufferPool<T: Pod> {
     buffer: wgpu::Buffer,
+    usage: wgpu::BufferUsages,
     capacity: u32,
     item_size: u64,
     device: wgpu::Device,
     queue: wgpu::Queue,
 
+    // Writes are batched and flushed before the next submit
+    pending: UploadBatch,
+
     // Deferred drop
     garbage: Vec<GarbageEntry>,
     keep_frames: u64,
@@ -41,6 +47,24 @@ impl<T: Pod> StorageBufferPool<T> {
         queue: &wgpu::Queue,
         initial_capacity: Option<u32>,
         alignment: u64,
+    ) -> Self {
+        Self::new_with_usage(
+            device,
+            queue,
+            initial_capacity,
+            alignment,
+            wgpu::BufferUsages::empty(),
+        )
+    }
+
+    /// Pool whose buffers also carry `extra_usage` (e.g. `INDIRECT` for draw args)
+    #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+    pub fn new_with_usage(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        initial_capacity: Option<u32>,
+        alignment: u64,
+        extra_usage: wgpu::BufferUsages,
     ) -> Self {
         let capacity = initial_capacity.unwrap_or(4);
         let raw_item_size = std::mem::size_of::<T>() as u64;
@@ -53,22 +77,26 @@ impl<T: Pod> StorageBufferPool<T> {
             raw_item_size
         };
 
+        let usage = wgpu::BufferUsages::COPY_SRC
+            | wgpu::BufferUsages::COPY_DST
+            | wgpu::BufferUsages::STORAGE
+            | extra_usage;
         let buffer_size = capacity as u64 * item_size;
         let buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("StorageBufferPool"),
             size: buffer_size,
-            usage: wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST
-                | wgpu::BufferUsages::STORAGE,
+            usage,
             mapped_at_creation: false,
         });
 
         Self {
             buffer,
+            usage,
             capacity,
             item_size,
             device: device.clone(),
             queue: queue.clone(),
+            pending: UploadBatch::default(),
             garbage: Vec::new(),
             keep_frames: 3,
             version: 0,
@@ -91,7 +119,7 @@ impl<T: Pod> StorageBufferPool<T> {
         }
 
         let offset = index as u64 * self.item_size;
-        self.queue.write_buffer(&self.buffer, offset, data);
+        self.pending.push(offset, data);
     }
 
     pub fn write(&mut self, index: u32, value: &T) {
@@ -111,13 +139,30 @@ impl<T: Pod> StorageBufferPool<T> {
 
         let offset = start_index as u64 * self.item_size;
         let bytes = cast_slice(values);
-        self.queue.write_buffer(&self.buffer, offset, bytes);
+        self.pending.push(offset, bytes);
+    }
+
+    /// Grows the pool to hold at least `capacity` items without writing them,
+    /// for buffers filled on the GPU
+    pub fn reserve(&mut self, capacity: u32) {
+        self.scale_to_capacity(capacity);
+    }
+
+    /// Grows the pool to hold at least `size` bytes, for GPU-filled arrays indexed densely
+    /// rather than by item
+    pub fn reserve_bytes(&mut self, size: u64) {
+        self.scale_to_capacity(size.div_ceil(self.item_size) as u32);
     }
 
     pub fn buffer(&self) -> &wgpu::Buffer {
         &self.buffer
     }
 
+    /// Uploads pending writes. Call before submitting work that reads this pool.
+    pub fn flush(&mut self) {
+        self.pending.flush(&self.queue, &self.buffer);
+    }
+
     // -------------------------------------------------------------------------
     // Frame lifecycle / deferred drop
     // -------------------------------------------------------------------------
@@ -141,15 +186,16 @@ impl<T: Pod> StorageBufferPool<T> {
             return;
         }
 
+        // Pending writes target the old buffer; land them before it is copied
+        self.flush();
+
         let new_capacity = self.calculate_next_capacity(required_capacity);
         let new_size = new_capacity as u64 * self.item_size;
 
         let new_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("StorageBufferPool (resized)"),
             size: new_size,
-            usage: wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST
-                | wgpu::BufferUsages::STORAGE,
+            usage: self.usage,
             mapped_at_creation: false,
         });
 
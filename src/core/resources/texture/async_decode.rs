@@ -1,8 +1,56 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::mpsc::{Receiver, Sender, channel};
 
-use crate::core::image::{ImageBuffer, ImageDecoder};
-use crate::core::resources::texture::{ForwardAtlasOptions, TextureCreateMode};
+use glam::UVec2;
+use serde::{Deserialize, Serialize};
+
+use crate::core::image::{ImageBuffer, ImageDecoder, ImageEncoding};
+use crate::core::resources::texture::cmd::layered::{decode_layers, upload_layered_texture};
+use crate::core::resources::texture::cmd::utils::upload_standalone_texture;
+use crate::core::resources::texture::{ForwardAtlasOptions, TextureCreateMode, TextureRecord};
+
+/// Background workers of `CmdTextureCreateFromBuffer`, `CmdTextureCreateCubemap` and
+/// `CmdTextureCreateArray`
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TextureUploadConfig {
+    /// Decodes running at once; further jobs wait for a free worker
+    pub max_workers: u32,
+    /// Standalone textures are also created and written on the worker, so the tick only
+    /// inserts a texture the GPU already holds. Atlas textures always upload on the tick.
+    pub gpu_upload: bool,
+}
+
+impl Default for TextureUploadConfig {
+    fn default() -> Self {
+        Self {
+            max_workers: 2,
+            gpu_upload: true,
+        }
+    }
+}
+
+/// Encoded pixels of a decode job
+#[derive(Debug, Clone)]
+pub enum TextureDecodeSource {
+    /// One image for a standalone or forward atlas texture
+    Image(Vec<u8>),
+    /// Cube faces or array layers, uploaded into one standalone texture
+    Layers {
+        buffers: Vec<Vec<u8>>,
+        view_dimension: wgpu::TextureViewDimension,
+    },
+}
+
+/// Pixels a job decoded but did not upload
+#[derive(Debug)]
+pub enum DecodedTexture {
+    Image(ImageBuffer),
+    Layers {
+        images: Vec<ImageBuffer>,
+        view_dimension: wgpu::TextureViewDimension,
+    },
+}
 
 #[derive(Debug, Clone)]
 pub struct TextureDecodeJob {
@@ -12,7 +60,14 @@ pub struct TextureDecodeJob {
     pub srgb: Option<bool>,
     pub mode: TextureCreateMode,
     pub atlas_options: Option<ForwardAtlasOptions>,
-    pub bytes: Vec<u8>,
+    /// Replace an existing texture once the decode succeeds
+    pub overwrite: bool,
+    pub retain_cpu_copy: bool,
+    pub encoding: ImageEncoding,
+    pub size: Option<UVec2>,
+    pub source: TextureDecodeSource,
+    /// Device and queue the worker uploads to; `None` leaves the upload to the tick
+    pub gpu: Option<(wgpu::Device, wgpu::Queue)>,
 }
 
 #[derive(Debug)]
@@ -23,15 +78,22 @@ pub struct TextureDecodeResult {
     pub srgb: Option<bool>,
     pub mode: TextureCreateMode,
     pub atlas_options: Option<ForwardAtlasOptions>,
-    pub image: Option<ImageBuffer>,
+    pub overwrite: bool,
+    pub retain_cpu_copy: bool,
+    pub decoded: Option<DecodedTexture>,
+    /// Texture uploaded by the worker; its writes finished on the GPU before it was sent
+    pub record: Option<TextureRecord>,
     pub message: String,
 }
 
 pub struct TextureAsyncManager {
     sender: Sender<TextureDecodeResult>,
     receiver: Receiver<TextureDecodeResult>,
-    pending: HashSet<u32>,
+    pending: HashMap<u32, u32>,
     canceled: HashSet<u32>,
+    queued: VecDeque<TextureDecodeJob>,
+    active_workers: usize,
+    config: TextureUploadConfig,
 }
 
 impl TextureAsyncManager {
@@ -40,30 +102,79 @@ impl TextureAsyncManager {
         Self {
             sender,
             receiver,
-            pending: HashSet::new(),
+            pending: HashMap::new(),
             canceled: HashSet::new(),
+            queued: VecDeque::new(),
+            active_workers: 0,
+            config: TextureUploadConfig::default(),
         }
     }
 
+    /// Whether any job is queued or running
+    pub fn is_busy(&self) -> bool {
+        self.job_count() > 0
+    }
+
+    /// Jobs queued or running, canceled ones included, until their result is drained
+    pub fn job_count(&self) -> usize {
+        self.active_workers + self.queued.len()
+    }
+
     pub fn is_pending(&self, texture_id: u32) -> bool {
-        self.pending.contains(&texture_id)
+        self.pending.contains_key(&texture_id)
     }
 
     pub fn enqueue(&mut self, job: TextureDecodeJob) -> Result<(), String> {
-        if !self.pending.insert(job.texture_id) {
+        if self.pending.contains_key(&job.texture_id) {
             return Err(format!("Texture {} is already pending", job.texture_id));
         }
+        self.pending.insert(job.texture_id, job.window_id);
         self.canceled.remove(&job.texture_id);
-        let sender = self.sender.clone();
-        spawn_decode(job, sender);
+        self.queued.push_back(job);
+        self.dispatch();
         Ok(())
     }
 
+    pub fn config(&self) -> TextureUploadConfig {
+        self.config
+    }
+
+    pub fn set_config(&mut self, config: TextureUploadConfig) {
+        self.config = config;
+        self.dispatch();
+    }
+
+    /// Starts queued jobs while workers are free
+    fn dispatch(&mut self) {
+        let max_workers = self.config.max_workers.max(1) as usize;
+        while self.active_workers < max_workers {
+            let Some(job) = self.queued.pop_front() else {
+                break;
+            };
+            self.active_workers += 1;
+            spawn_decode(job, self.sender.clone());
+        }
+    }
+
     pub fn cancel(&mut self, texture_id: u32) {
         self.pending.remove(&texture_id);
         self.canceled.insert(texture_id);
     }
 
+    /// Cancels every pending decode that targets `window_id`.
+    pub fn cancel_window(&mut self, window_id: u32) -> usize {
+        let ids: Vec<u32> = self
+            .pending
+            .iter()
+            .filter(|(_, owner)| **owner == window_id)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in &ids {
+            self.cancel(*id);
+        }
+        ids.len()
+    }
+
     pub fn was_canceled(&mut self, texture_id: u32) -> bool {
         self.canceled.remove(&texture_id)
     }
@@ -71,53 +182,102 @@ impl TextureAsyncManager {
     pub fn drain_results(&mut self) -> Vec<TextureDecodeResult> {
         let mut results = Vec::new();
         while let Ok(result) = self.receiver.try_recv() {
+            self.active_workers = self.active_workers.saturating_sub(1);
             self.pending.remove(&result.texture_id);
             results.push(result);
         }
+        self.dispatch();
         results
     }
 }
 
+/// Decodes a job and, when it carries a device, uploads the texture as well.
+/// Uploads are flushed with an empty submit and reported once the GPU has run them,
+/// so the tick that receives the result never waits on the copy.
+fn run_job(job: TextureDecodeJob, sender: Sender<TextureDecodeResult>) {
+    let decoded = match job.source {
+        TextureDecodeSource::Image(bytes) => {
+            ImageDecoder::decode(&bytes, job.encoding, job.size).map(DecodedTexture::Image)
+        }
+        TextureDecodeSource::Layers {
+            buffers,
+            view_dimension,
+        } => decode_layers(&buffers, job.encoding, job.size, view_dimension).map(|images| {
+            DecodedTexture::Layers {
+                images,
+                view_dimension,
+            }
+        }),
+    };
+    let (decoded, message) = match decoded {
+        Ok(decoded) => (Some(decoded), "Texture decoded".to_string()),
+        Err(message) => (None, message),
+    };
+    let mut result = TextureDecodeResult {
+        window_id: job.window_id,
+        texture_id: job.texture_id,
+        label: job.label,
+        srgb: job.srgb,
+        mode: job.mode,
+        atlas_options: job.atlas_options,
+        overwrite: job.overwrite,
+        retain_cpu_copy: job.retain_cpu_copy,
+        decoded,
+        record: None,
+        message,
+    };
+
+    match (job.gpu, result.decoded.take()) {
+        (Some((device, queue)), Some(decoded)) => {
+            let record = match decoded {
+                DecodedTexture::Image(image) => upload_standalone_texture(
+                    &device,
+                    &queue,
+                    result.label.clone(),
+                    result.srgb,
+                    image,
+                    result.retain_cpu_copy,
+                ),
+                DecodedTexture::Layers {
+                    images,
+                    view_dimension,
+                } => upload_layered_texture(
+                    &device,
+                    &queue,
+                    result.label.clone(),
+                    result.srgb,
+                    &images,
+                    view_dimension,
+                ),
+            };
+            match record {
+                Ok(record) => {
+                    result.record = Some(record);
+                    result.message = "Texture uploaded".into();
+                    queue.submit(std::iter::empty());
+                    queue.on_submitted_work_done(move || {
+                        let _ = sender.send(result);
+                    });
+                }
+                Err(message) => {
+                    result.message = message;
+                    let _ = sender.send(result);
+                }
+            }
+        }
+        (_, decoded) => {
+            result.decoded = decoded;
+            let _ = sender.send(result);
+        }
+    }
+}
+
 #[cfg(not(feature = "wasm"))]
 fn spawn_decode(job: TextureDecodeJob, sender: Sender<TextureDecodeResult>) {
-    std::thread::spawn(move || {
-        let image = ImageDecoder::try_decode(&job.bytes);
-        let message = if image.is_some() {
-            "Texture decoded".to_string()
-        } else {
-            "Failed to decode image".to_string()
-        };
-        let _ = sender.send(TextureDecodeResult {
-            window_id: job.window_id,
-            texture_id: job.texture_id,
-            label: job.label,
-            srgb: job.srgb,
-            mode: job.mode,
-            atlas_options: job.atlas_options,
-            image,
-            message,
-        });
-    });
+    std::thread::spawn(move || run_job(job, sender));
 }
 
 #[cfg(feature = "wasm")]
 fn spawn_decode(job: TextureDecodeJob, sender: Sender<TextureDecodeResult>) {
-    wasm_bindgen_futures::spawn_local(async move {
-        let image = ImageDecoder::try_decode(&job.bytes);
-        let message = if image.is_some() {
-            "Texture decoded".to_string()
-        } else {
-            "Failed to decode image".to_string()
-        };
-        let _ = sender.send(TextureDecodeResult {
-            window_id: job.window_id,
-            texture_id: job.texture_id,
-            label: job.label,
-            srgb: job.srgb,
-            mode: job.mode,
-            atlas_options: job.atlas_options,
-            image,
-            message,
-        });
-    });
+    wasm_bindgen_futures::spawn_local(async move { run_job(job, sender) });
 }
@@ -1,6 +1,10 @@
+pub mod from_buffer;
 pub mod handler;
+pub mod layered;
 pub mod types;
 pub mod utils;
 
+pub use from_buffer::*;
 pub use handler::*;
+pub use layered::*;
 pub use types::*;
@@ -0,0 +1,417 @@
+use super::types::*;
+use super::utils::*;
+use crate::core::buffers::state::UploadType;
+use crate::core::image::{ImageBuffer, ImageEncoding, ImagePixels};
+use crate::core::resources::common::claim_create_id;
+use crate::core::resources::texture::cmd::layered::upload_layered_texture;
+use crate::core::resources::texture::{
+    DecodedTexture, TextureDecodeJob, TextureDecodeSource, TextureRecord,
+};
+use crate::core::state::EngineState;
+use crate::core::system::{SystemEvent, check_texture_headroom, texture_footprint};
+use glam::UVec2;
+
+pub fn engine_cmd_texture_create_from_buffer(
+    engine: &mut EngineState,
+    args: &CmdTextureCreateFromBufferArgs,
+) -> CmdResultTextureCreateFromBuffer {
+    if !engine.window.states.contains_key(&args.window_id) {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message: format!("Window {} not found", args.window_id),
+            pending: false,
+        };
+    }
+
+    // The old texture stays until the decode succeeds; a pending decode is never replaced
+    let exists = engine.shared.textures().borrow().contains(args.texture_id);
+    if let Err(message) = claim_create_id("Texture", args.texture_id, exists, args.overwrite) {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message,
+            pending: false,
+        };
+    }
+    if engine.texture_async.is_pending(args.texture_id) {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message: format!("Texture with id {} is already pending", args.texture_id),
+            pending: false,
+        };
+    }
+
+    if args.encoding == ImageEncoding::Raw && args.size.is_none() {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message: "Raw image encoding requires a size".into(),
+            pending: false,
+        };
+    }
+
+    match engine.buffers.uploads.get(&args.buffer_id) {
+        Some(buffer) if buffer.upload_type == UploadType::ImageData => {}
+        Some(buffer) => {
+            return CmdResultTextureCreateFromBuffer {
+                success: false,
+                message: format!(
+                    "Invalid buffer type. Expected ImageData, got {:?}",
+                    buffer.upload_type
+                ),
+                pending: false,
+            };
+        }
+        None => {
+            return CmdResultTextureCreateFromBuffer {
+                success: false,
+                message: format!("Buffer with id {} not found", args.buffer_id),
+                pending: false,
+            };
+        }
+    }
+    let Some(buffer) = engine.buffers.remove_upload(args.buffer_id) else {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message: format!("Buffer with id {} not found", args.buffer_id),
+            pending: false,
+        };
+    };
+
+    let job = TextureDecodeJob {
+        window_id: args.window_id,
+        texture_id: args.texture_id,
+        label: args.label.clone(),
+        srgb: args.srgb,
+        mode: args.mode,
+        atlas_options: args.atlas_options.clone(),
+        overwrite: args.overwrite,
+        retain_cpu_copy: args.retain_cpu_copy,
+        encoding: args.encoding,
+        size: args.size,
+        source: TextureDecodeSource::Image(buffer.data),
+        gpu: match (&args.mode, engine.device.as_ref(), engine.queue.as_ref()) {
+            (TextureCreateMode::Standalone, Some(device), Some(queue))
+                if engine.texture_async.config().gpu_upload =>
+            {
+                Some((device.clone(), queue.clone()))
+            }
+            _ => None,
+        },
+    };
+
+    if let Err(message) = engine.texture_async.enqueue(job) {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message,
+            pending: false,
+        };
+    }
+
+    CmdResultTextureCreateFromBuffer {
+        success: true,
+        message: "Texture decode queued".into(),
+        pending: true,
+    }
+}
+
+fn create_texture_from_image(
+    engine: &mut EngineState,
+    args: &CmdTextureCreateFromBufferArgs,
+    image: ImageBuffer,
+) -> CmdResultTextureCreateFromBuffer {
+    if matches!(args.mode, TextureCreateMode::Standalone)
+        && let Err(message) = check_texture_headroom(
+            engine.memory_budget.texture_headroom(&engine.shared),
+            standalone_texture_bytes(&image, args.srgb),
+        )
+    {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message,
+            pending: false,
+        };
+    }
+
+    if !engine.window.states.contains_key(&args.window_id) {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message: format!("Window {} not found", args.window_id),
+            pending: false,
+        };
+    }
+
+    let exists = engine.shared.textures().borrow().contains(args.texture_id);
+    if let Err(message) = claim_create_id("Texture", args.texture_id, exists, args.overwrite) {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message,
+            pending: false,
+        };
+    }
+
+    let device = match engine.device.as_ref() {
+        Some(d) => d,
+        None => {
+            return CmdResultTextureCreateFromBuffer {
+                success: false,
+                message: "Device not initialized".into(),
+                pending: false,
+            };
+        }
+    };
+
+    let queue = match engine.queue.as_ref() {
+        Some(q) => q,
+        None => {
+            return CmdResultTextureCreateFromBuffer {
+                success: false,
+                message: "Queue not initialized".into(),
+                pending: false,
+            };
+        }
+    };
+
+    match args.mode {
+        TextureCreateMode::Standalone => {
+            let record = match upload_standalone_texture(
+                device,
+                queue,
+                args.label.clone(),
+                args.srgb,
+                image,
+                args.retain_cpu_copy,
+            ) {
+                Ok(record) => record,
+                Err(message) => {
+                    return CmdResultTextureCreateFromBuffer {
+                        success: false,
+                        message,
+                        pending: false,
+                    };
+                }
+            };
+            engine.shared.textures().borrow_mut().insert_record(
+                args.texture_id,
+                args.window_id,
+                record,
+            );
+        }
+        TextureCreateMode::ForwardAtlas => {
+            let cpu_copy = args.retain_cpu_copy.then(|| image.clone());
+            let format = if image.is_srgb(args.srgb) {
+                wgpu::TextureFormat::Rgba8UnormSrgb
+            } else {
+                wgpu::TextureFormat::Rgba8Unorm
+            };
+            let pixel_data = match image.pixels {
+                ImagePixels::Rgba8(data) => data,
+                ImagePixels::Rgba16F(_) => {
+                    return CmdResultTextureCreateFromBuffer {
+                        success: false,
+                        message: "Float textures are not supported in forward atlas".into(),
+                        pending: false,
+                    };
+                }
+            };
+            match insert_forward_atlas_texture(
+                &mut engine.shared.textures().borrow_mut(),
+                args.window_id,
+                device,
+                queue,
+                args.texture_id,
+                args.label.clone(),
+                &pixel_data,
+                UVec2::new(image.width, image.height),
+                format,
+                &args.atlas_options.clone().unwrap_or_default(),
+                cpu_copy,
+            ) {
+                Ok(relocated) => mark_texture_users_dirty(&mut engine.window, &relocated),
+                Err(message) => {
+                    return CmdResultTextureCreateFromBuffer {
+                        success: false,
+                        message,
+                        pending: false,
+                    };
+                }
+            }
+        }
+    }
+
+    mark_texture_users_dirty(&mut engine.window, &[args.texture_id]);
+
+    CmdResultTextureCreateFromBuffer {
+        success: true,
+        message: "Texture created successfully".into(),
+        pending: false,
+    }
+}
+
+/// Adds a texture a decode worker already created and wrote, or a layered texture
+/// uploaded on the tick
+fn insert_uploaded_texture(
+    engine: &mut EngineState,
+    args: &CmdTextureCreateFromBufferArgs,
+    record: TextureRecord,
+) -> CmdResultTextureCreateFromBuffer {
+    // Workers cannot see the budget, so an over-budget upload is dropped here
+    if let Err(message) = check_texture_headroom(
+        engine.memory_budget.texture_headroom(&engine.shared),
+        texture_footprint(
+            record._format,
+            record._size,
+            record._texture.mip_level_count(),
+        ),
+    ) {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message,
+            pending: false,
+        };
+    }
+
+    if !engine.window.states.contains_key(&args.window_id) {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message: format!("Window {} not found", args.window_id),
+            pending: false,
+        };
+    }
+
+    let exists = engine.shared.textures().borrow().contains(args.texture_id);
+    if let Err(message) = claim_create_id("Texture", args.texture_id, exists, args.overwrite) {
+        return CmdResultTextureCreateFromBuffer {
+            success: false,
+            message,
+            pending: false,
+        };
+    }
+
+    engine
+        .shared
+        .textures()
+        .borrow_mut()
+        .insert_record(args.texture_id, args.window_id, record);
+    mark_texture_users_dirty(&mut engine.window, &[args.texture_id]);
+
+    CmdResultTextureCreateFromBuffer {
+        success: true,
+        message: "Texture created successfully".into(),
+        pending: false,
+    }
+}
+
+pub fn process_async_texture_results(engine: &mut EngineState) {
+    // Worker uploads report back from the queue's completion callbacks
+    if engine.texture_async.is_busy()
+        && let Some(device) = engine.device.as_ref()
+    {
+        let _ = device.poll(wgpu::PollType::Poll);
+    }
+
+    let results = engine.texture_async.drain_results();
+    for result in results {
+        if engine.texture_async.was_canceled(result.texture_id) {
+            engine
+                .event_queue
+                .push(crate::core::cmd::EngineEvent::System(
+                    SystemEvent::TextureReady {
+                        window_id: result.window_id,
+                        texture_id: result.texture_id,
+                        success: false,
+                        message: "Texture decode canceled".into(),
+                    },
+                ));
+            continue;
+        }
+        let args = CmdTextureCreateFromBufferArgs {
+            window_id: result.window_id,
+            texture_id: result.texture_id,
+            label: result.label.clone(),
+            buffer_id: 0,
+            encoding: ImageEncoding::Auto,
+            size: None,
+            srgb: result.srgb,
+            mode: result.mode,
+            atlas_options: result.atlas_options.clone(),
+            overwrite: result.overwrite,
+            retain_cpu_copy: result.retain_cpu_copy,
+        };
+
+        let response = match (result.record, result.decoded) {
+            (Some(record), _) => insert_uploaded_texture(engine, &args, record),
+            (None, Some(DecodedTexture::Image(image))) => {
+                create_texture_from_image(engine, &args, image)
+            }
+            (
+                None,
+                Some(DecodedTexture::Layers {
+                    images,
+                    view_dimension,
+                }),
+            ) => {
+                let record = match (engine.device.as_ref(), engine.queue.as_ref()) {
+                    (Some(device), Some(queue)) => upload_layered_texture(
+                        device,
+                        queue,
+                        args.label.clone(),
+                        args.srgb,
+                        &images,
+                        view_dimension,
+                    ),
+                    _ => Err("Device not initialized".into()),
+                };
+                match record {
+                    Ok(record) => insert_uploaded_texture(engine, &args, record),
+                    Err(message) => CmdResultTextureCreateFromBuffer {
+                        success: false,
+                        message,
+                        pending: false,
+                    },
+                }
+            }
+            (None, None) => CmdResultTextureCreateFromBuffer {
+                success: false,
+                message: result.message.clone(),
+                pending: false,
+            },
+        };
+
+        engine
+            .event_queue
+            .push(crate::core::cmd::EngineEvent::System(
+                SystemEvent::TextureReady {
+                    window_id: result.window_id,
+                    texture_id: result.texture_id,
+                    success: response.success,
+                    message: response.message,
+                },
+            ));
+    }
+}
+
+pub fn engine_cmd_texture_upload_configure(
+    engine: &mut EngineState,
+    args: &CmdTextureUploadConfigureArgs,
+) -> CmdResultTextureUploadConfigure {
+    let mut config = engine.texture_async.config();
+    if let Some(max_workers) = args.max_workers {
+        if !(1..=16).contains(&max_workers) {
+            return CmdResultTextureUploadConfigure {
+                success: false,
+                message: format!("maxWorkers must be within 1..=16, got {}", max_workers),
+                config: Some(config),
+            };
+        }
+        config.max_workers = max_workers;
+    }
+    if let Some(gpu_upload) = args.gpu_upload {
+        config.gpu_upload = gpu_upload;
+    }
+    engine.texture_async.set_config(config);
+
+    CmdResultTextureUploadConfigure {
+        success: true,
+        message: "Texture upload settings updated".into(),
+        config: Some(config),
+    }
+}
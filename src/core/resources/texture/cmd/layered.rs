@@ -0,0 +1,284 @@
+use super::types::*;
+use super::utils::*;
+use crate::core::buffers::state::UploadType;
+use crate::core::image::{ImageBuffer, ImageDecoder, ImageEncoding, ImagePixels};
+use crate::core::resources::common::claim_create_id;
+use crate::core::resources::texture::{TextureDecodeJob, TextureDecodeSource, TextureRecord};
+use crate::core::state::EngineState;
+use glam::UVec2;
+
+pub fn engine_cmd_texture_create_cubemap(
+    engine: &mut EngineState,
+    args: &CmdTextureCreateCubemapArgs,
+) -> CmdResultTextureCreateCubemap {
+    let result = queue_layered_texture(
+        engine,
+        &LayeredTextureDesc {
+            window_id: args.window_id,
+            texture_id: args.texture_id,
+            label: args.label.as_deref(),
+            buffer_ids: &args.faces,
+            encoding: args.encoding,
+            size: args.size,
+            srgb: args.srgb,
+            overwrite: args.overwrite,
+            view_dimension: wgpu::TextureViewDimension::Cube,
+        },
+    );
+    match result {
+        Ok(()) => CmdResultTextureCreateCubemap {
+            success: true,
+            message: "Cubemap decode queued".into(),
+            pending: true,
+        },
+        Err(message) => CmdResultTextureCreateCubemap {
+            success: false,
+            message,
+            pending: false,
+        },
+    }
+}
+
+pub fn engine_cmd_texture_create_array(
+    engine: &mut EngineState,
+    args: &CmdTextureCreateArrayArgs,
+) -> CmdResultTextureCreateArray {
+    let result = queue_layered_texture(
+        engine,
+        &LayeredTextureDesc {
+            window_id: args.window_id,
+            texture_id: args.texture_id,
+            label: args.label.as_deref(),
+            buffer_ids: &args.layers,
+            encoding: args.encoding,
+            size: args.size,
+            srgb: args.srgb,
+            overwrite: args.overwrite,
+            view_dimension: wgpu::TextureViewDimension::D2Array,
+        },
+    );
+    match result {
+        Ok(()) => CmdResultTextureCreateArray {
+            success: true,
+            message: "Texture array decode queued".into(),
+            pending: true,
+        },
+        Err(message) => CmdResultTextureCreateArray {
+            success: false,
+            message,
+            pending: false,
+        },
+    }
+}
+
+struct LayeredTextureDesc<'a> {
+    window_id: u32,
+    texture_id: u32,
+    label: Option<&'a str>,
+    buffer_ids: &'a [u64],
+    encoding: ImageEncoding,
+    size: Option<UVec2>,
+    srgb: Option<bool>,
+    overwrite: bool,
+    view_dimension: wgpu::TextureViewDimension,
+}
+
+/// Checks the layer uploads and queues their decode; the texture arrives with `TextureReady`
+fn queue_layered_texture(
+    engine: &mut EngineState,
+    desc: &LayeredTextureDesc,
+) -> Result<(), String> {
+    if !engine.window.states.contains_key(&desc.window_id) {
+        return Err(format!("Window {} not found", desc.window_id));
+    }
+
+    // The old texture stays until the decode succeeds; a pending decode is never replaced
+    let exists = engine.shared.textures().borrow().contains(desc.texture_id);
+    claim_create_id("Texture", desc.texture_id, exists, desc.overwrite)?;
+    if engine.texture_async.is_pending(desc.texture_id) {
+        return Err(format!(
+            "Texture with id {} is already pending",
+            desc.texture_id
+        ));
+    }
+
+    let device = engine.device.as_ref().ok_or("Device not initialized")?;
+    let queue = engine.queue.as_ref().ok_or("Queue not initialized")?;
+
+    let max_layers = device.limits().max_texture_array_layers as usize;
+    if desc.buffer_ids.is_empty() || desc.buffer_ids.len() > max_layers {
+        return Err(format!(
+            "Texture arrays need between 1 and {} layers, got {}",
+            max_layers,
+            desc.buffer_ids.len()
+        ));
+    }
+    if desc.encoding == ImageEncoding::Raw && desc.size.is_none() {
+        return Err("Raw image encoding requires a size".into());
+    }
+    for buffer_id in desc.buffer_ids {
+        match engine.buffers.uploads.get(buffer_id) {
+            Some(buffer) if buffer.upload_type == UploadType::ImageData => {}
+            Some(buffer) => {
+                return Err(format!(
+                    "Invalid buffer type for buffer {}. Expected ImageData, got {:?}",
+                    buffer_id, buffer.upload_type
+                ));
+            }
+            None => return Err(format!("Buffer with id {} not found", buffer_id)),
+        }
+    }
+
+    let gpu = engine
+        .texture_async
+        .config()
+        .gpu_upload
+        .then(|| (device.clone(), queue.clone()));
+    let buffers = desc
+        .buffer_ids
+        .iter()
+        .filter_map(|buffer_id| engine.buffers.remove_upload(*buffer_id))
+        .map(|buffer| buffer.data)
+        .collect();
+
+    engine.texture_async.enqueue(TextureDecodeJob {
+        window_id: desc.window_id,
+        texture_id: desc.texture_id,
+        label: desc.label.map(str::to_string),
+        srgb: desc.srgb,
+        mode: TextureCreateMode::Standalone,
+        atlas_options: None,
+        overwrite: desc.overwrite,
+        retain_cpu_copy: false,
+        encoding: desc.encoding,
+        size: desc.size,
+        source: TextureDecodeSource::Layers {
+            buffers,
+            view_dimension: desc.view_dimension,
+        },
+        gpu,
+    })
+}
+
+/// Decodes every layer and checks they can share one texture.
+/// Only the base level is kept; KTX2 mips are dropped.
+pub(crate) fn decode_layers(
+    buffers: &[Vec<u8>],
+    encoding: ImageEncoding,
+    size: Option<UVec2>,
+    view_dimension: wgpu::TextureViewDimension,
+) -> Result<Vec<ImageBuffer>, String> {
+    let mut images = Vec::with_capacity(buffers.len());
+    for (layer, bytes) in buffers.iter().enumerate() {
+        let mut image = ImageDecoder::decode(bytes, encoding, size)
+            .map_err(|message| format!("Layer {}: {}", layer, message))?;
+        image.mips.clear();
+        images.push(image);
+    }
+
+    let first = images
+        .first()
+        .ok_or("Layered textures need at least one layer")?;
+    let float = matches!(first.pixels, ImagePixels::Rgba16F(_));
+    for image in &images[1..] {
+        if image.width != first.width
+            || image.height != first.height
+            || matches!(image.pixels, ImagePixels::Rgba16F(_)) != float
+            || image.srgb != first.srgb
+        {
+            return Err("All layers must decode to the same size and pixel format".into());
+        }
+    }
+    if view_dimension == wgpu::TextureViewDimension::Cube && first.width != first.height {
+        return Err(format!(
+            "Cubemap faces must be square, got {}x{}",
+            first.width, first.height
+        ));
+    }
+
+    Ok(images)
+}
+
+/// Creates one texture from layers `decode_layers` accepted and queues the writes of every
+/// layer. Only touches the device and queue, so the async decode workers call it too.
+pub(crate) fn upload_layered_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    label: Option<String>,
+    srgb: Option<bool>,
+    images: &[ImageBuffer],
+    view_dimension: wgpu::TextureViewDimension,
+) -> Result<TextureRecord, String> {
+    let first = images
+        .first()
+        .ok_or("Layered textures need at least one layer")?;
+    let (format, texel_bytes) = match first.pixels {
+        ImagePixels::Rgba16F(_) => (wgpu::TextureFormat::Rgba16Float, 8),
+        ImagePixels::Rgba8(_) if first.is_srgb(srgb) => (wgpu::TextureFormat::Rgba8UnormSrgb, 4),
+        ImagePixels::Rgba8(_) => (wgpu::TextureFormat::Rgba8Unorm, 4),
+    };
+
+    let size = wgpu::Extent3d {
+        width: first.width,
+        height: first.height,
+        depth_or_array_layers: images.len() as u32,
+    };
+    let texture = try_create_texture(
+        device,
+        &wgpu::TextureDescriptor {
+            label: label.as_deref().or(Some("Layered Texture")),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        },
+    )?;
+
+    for (layer, image) in images.iter().enumerate() {
+        let pixel_data: &[u8] = match &image.pixels {
+            ImagePixels::Rgba8(data) => data.as_slice(),
+            ImagePixels::Rgba16F(data) => bytemuck::cast_slice(data),
+        };
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: 0,
+                    y: 0,
+                    z: layer as u32,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            pixel_data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(texel_bytes * image.width),
+                rows_per_image: Some(image.height),
+            },
+            wgpu::Extent3d {
+                width: image.width,
+                height: image.height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    let view = texture.create_view(&wgpu::TextureViewDescriptor {
+        dimension: Some(view_dimension),
+        ..Default::default()
+    });
+
+    Ok(TextureRecord {
+        label,
+        _texture: texture,
+        view,
+        view_dimension,
+        _size: size,
+        _format: format,
+        cpu_copy: None,
+    })
+}
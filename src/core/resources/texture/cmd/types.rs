@@ -1,4 +1,6 @@
-use glam::Vec4;
+use crate::core::image::ImageEncoding;
+use crate::core::resources::texture::TextureUploadConfig;
+use glam::{UVec2, Vec4};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -9,11 +11,21 @@ pub struct CmdTextureCreateFromBufferArgs {
     pub label: Option<String>,
     pub buffer_id: u64,
     #[serde(default)]
+    pub encoding: ImageEncoding,
+    /// Pixel dimensions, required when `encoding` is `raw`
+    #[serde(default)]
+    pub size: Option<UVec2>,
+    #[serde(default)]
     pub srgb: Option<bool>,
     #[serde(default)]
     pub mode: TextureCreateMode,
     #[serde(default)]
     pub atlas_options: Option<ForwardAtlasOptions>,
+    #[serde(default)]
+    pub overwrite: bool,
+    /// Keep the decoded pixels on the CPU for re-upload after device loss
+    #[serde(default)]
+    pub retain_cpu_copy: bool,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -66,6 +78,11 @@ pub struct CmdTextureCreateSolidColorArgs {
     pub mode: TextureCreateMode,
     #[serde(default)]
     pub atlas_options: Option<ForwardAtlasOptions>,
+    #[serde(default)]
+    pub overwrite: bool,
+    /// Keep the decoded pixels on the CPU for re-upload after device loss
+    #[serde(default)]
+    pub retain_cpu_copy: bool,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -88,3 +105,189 @@ pub struct CmdResultTextureDispose {
     pub success: bool,
     pub message: String,
 }
+
+/// Formats accepted by `CmdTextureCreate`; all of them sample as float
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TexturePixelFormat {
+    R8Unorm,
+    Rg8Unorm,
+    Rgba8Unorm,
+    #[default]
+    Rgba8UnormSrgb,
+    Bgra8Unorm,
+    Bgra8UnormSrgb,
+    Rgb10a2Unorm,
+    Rg11b10Ufloat,
+    R16Float,
+    Rg16Float,
+    Rgba16Float,
+    R32Float,
+    Rg32Float,
+    Rgba32Float,
+}
+
+impl TexturePixelFormat {
+    pub fn to_wgpu(self) -> wgpu::TextureFormat {
+        match self {
+            Self::R8Unorm => wgpu::TextureFormat::R8Unorm,
+            Self::Rg8Unorm => wgpu::TextureFormat::Rg8Unorm,
+            Self::Rgba8Unorm => wgpu::TextureFormat::Rgba8Unorm,
+            Self::Rgba8UnormSrgb => wgpu::TextureFormat::Rgba8UnormSrgb,
+            Self::Bgra8Unorm => wgpu::TextureFormat::Bgra8Unorm,
+            Self::Bgra8UnormSrgb => wgpu::TextureFormat::Bgra8UnormSrgb,
+            Self::Rgb10a2Unorm => wgpu::TextureFormat::Rgb10a2Unorm,
+            Self::Rg11b10Ufloat => wgpu::TextureFormat::Rg11b10Ufloat,
+            Self::R16Float => wgpu::TextureFormat::R16Float,
+            Self::Rg16Float => wgpu::TextureFormat::Rg16Float,
+            Self::Rgba16Float => wgpu::TextureFormat::Rgba16Float,
+            Self::R32Float => wgpu::TextureFormat::R32Float,
+            Self::Rg32Float => wgpu::TextureFormat::Rg32Float,
+            Self::Rgba32Float => wgpu::TextureFormat::Rgba32Float,
+        }
+    }
+
+    /// Inverse of `to_wgpu`; `None` for formats the engine does not name
+    pub fn from_wgpu(format: wgpu::TextureFormat) -> Option<Self> {
+        match format {
+            wgpu::TextureFormat::R8Unorm => Some(Self::R8Unorm),
+            wgpu::TextureFormat::Rg8Unorm => Some(Self::Rg8Unorm),
+            wgpu::TextureFormat::Rgba8Unorm => Some(Self::Rgba8Unorm),
+            wgpu::TextureFormat::Rgba8UnormSrgb => Some(Self::Rgba8UnormSrgb),
+            wgpu::TextureFormat::Bgra8Unorm => Some(Self::Bgra8Unorm),
+            wgpu::TextureFormat::Bgra8UnormSrgb => Some(Self::Bgra8UnormSrgb),
+            wgpu::TextureFormat::Rgb10a2Unorm => Some(Self::Rgb10a2Unorm),
+            wgpu::TextureFormat::Rg11b10Ufloat => Some(Self::Rg11b10Ufloat),
+            wgpu::TextureFormat::R16Float => Some(Self::R16Float),
+            wgpu::TextureFormat::Rg16Float => Some(Self::Rg16Float),
+            wgpu::TextureFormat::Rgba16Float => Some(Self::Rgba16Float),
+            wgpu::TextureFormat::R32Float => Some(Self::R32Float),
+            wgpu::TextureFormat::Rg32Float => Some(Self::Rg32Float),
+            wgpu::TextureFormat::Rgba32Float => Some(Self::Rgba32Float),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TextureUsage {
+    /// Bound by materials
+    Sampled,
+    /// Drawn into by a camera with `output: texture`
+    RenderTarget,
+    Storage,
+    CopySrc,
+    CopyDst,
+}
+
+impl TextureUsage {
+    pub fn to_wgpu(self) -> wgpu::TextureUsages {
+        match self {
+            Self::Sampled => wgpu::TextureUsages::TEXTURE_BINDING,
+            Self::RenderTarget => wgpu::TextureUsages::RENDER_ATTACHMENT,
+            Self::Storage => wgpu::TextureUsages::STORAGE_BINDING,
+            Self::CopySrc => wgpu::TextureUsages::COPY_SRC,
+            Self::CopyDst => wgpu::TextureUsages::COPY_DST,
+        }
+    }
+}
+
+fn default_texture_usage() -> Vec<TextureUsage> {
+    vec![TextureUsage::Sampled, TextureUsage::CopyDst]
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdTextureCreateArgs {
+    pub window_id: u32,
+    pub texture_id: u32,
+    pub label: Option<String>,
+    pub size: UVec2,
+    #[serde(default)]
+    pub format: TexturePixelFormat,
+    #[serde(default = "default_texture_usage")]
+    pub usage: Vec<TextureUsage>,
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultTextureCreate {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdTextureCreateCubemapArgs {
+    pub window_id: u32,
+    pub texture_id: u32,
+    pub label: Option<String>,
+    /// Upload buffers for the +X, -X, +Y, -Y, +Z, -Z faces of a standard left-handed set
+    /// (px, nx, py, ny, pz, nz). The skybox shows +Z ahead of a camera looking down -Z.
+    pub faces: [u64; 6],
+    #[serde(default)]
+    pub encoding: ImageEncoding,
+    /// Face dimensions, required when `encoding` is `raw`
+    #[serde(default)]
+    pub size: Option<UVec2>,
+    #[serde(default)]
+    pub srgb: Option<bool>,
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultTextureCreateCubemap {
+    pub success: bool,
+    pub message: String,
+    /// The layers decode in the background; the texture is ready with `TextureReady`
+    pub pending: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdTextureCreateArrayArgs {
+    pub window_id: u32,
+    pub texture_id: u32,
+    pub label: Option<String>,
+    /// One upload buffer per layer, in layer order
+    pub layers: Vec<u64>,
+    #[serde(default)]
+    pub encoding: ImageEncoding,
+    /// Layer dimensions, required when `encoding` is `raw`
+    #[serde(default)]
+    pub size: Option<UVec2>,
+    #[serde(default)]
+    pub srgb: Option<bool>,
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultTextureCreateArray {
+    pub success: bool,
+    pub message: String,
+    /// The layers decode in the background; the texture is ready with `TextureReady`
+    pub pending: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdTextureUploadConfigureArgs {
+    pub max_workers: Option<u32>,
+    pub gpu_upload: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultTextureUploadConfigure {
+    pub success: bool,
+    pub message: String,
+    /// Settings in effect after the update
+    pub config: Option<TextureUploadConfig>,
+}
@@ -1,47 +1,271 @@
-use crate::core::resources::texture::{ForwardAtlasDesc, ForwardAtlasSystem};
+use glam::{UVec2, Vec4};
+
+use crate::core::image::{ImageBuffer, ImagePixels};
+use crate::core::resources::texture::{
+    ForwardAtlasDesc, ForwardAtlasEntry, ForwardAtlasOptions, ForwardAtlasSystem, TextureRecord,
+    TextureStore,
+};
+use crate::core::system::texture_footprint;
+use crate::core::window::WindowManager;
 
 pub(crate) fn ensure_forward_atlas<'a>(
-    render_state: &'a mut crate::core::render::state::RenderState,
+    textures: &'a mut TextureStore,
     device: &wgpu::Device,
     queue: &wgpu::Queue,
     desc: &ForwardAtlasDesc,
 ) -> Result<&'a mut ForwardAtlasSystem, String> {
-    if render_state.forward_atlas.is_none() {
-        render_state.forward_atlas = Some(ForwardAtlasSystem::new(device, desc.clone()));
+    if textures.atlas.is_none() {
+        textures.atlas = Some(ForwardAtlasSystem::new(device, desc.clone()));
+        textures.atlas_version += 1;
     }
 
-    let atlas = render_state
-        .forward_atlas
-        .as_mut()
-        .expect("atlas just created");
+    let atlas = textures.atlas.as_mut().expect("atlas just created");
     let info = atlas.info();
     let config_matches = info.0 == desc.tile_px && info.5 == desc.format;
 
     if config_matches {
         let desired_layers = desc.layers.min(device.limits().max_texture_array_layers);
-        if desired_layers > info.4 {
-            let _ = atlas.grow_layers(device, queue, desired_layers);
+        if desired_layers > info.4 && atlas.grow_layers(device, queue, desired_layers) {
+            textures.atlas_version += 1;
         }
-        Ok(atlas)
+        Ok(textures.atlas.as_mut().expect("atlas just created"))
     } else {
         Err("Forward atlas already initialized with different config".into())
     }
 }
 
-pub(crate) fn mark_materials_dirty(
-    scene: &mut crate::core::render::state::RenderScene,
-    texture_id: u32,
-) {
-    for record in scene.materials_standard.values_mut() {
-        if record.texture_ids.iter().any(|id| *id == texture_id) {
-            record.bind_group = None;
-            record.mark_dirty();
+/// Drops the bind groups of every window's materials sampling one of `texture_ids`, so they
+/// pick up the new view or atlas placement. Every window redraws, since sprites, particles,
+/// skyboxes and camera outputs of any window may use the textures too.
+pub(crate) fn mark_texture_users_dirty(windows: &mut WindowManager, texture_ids: &[u32]) {
+    for window_state in windows.states.values_mut() {
+        let scene = &mut window_state.render_state.scene;
+        for record in scene.materials_standard.values_mut() {
+            if record.texture_ids.iter().any(|id| texture_ids.contains(id)) {
+                record.bind_group = None;
+                record.mark_dirty();
+            }
         }
+        for record in scene.materials_pbr.values_mut() {
+            if record.texture_ids.iter().any(|id| texture_ids.contains(id)) {
+                record.bind_group = None;
+                record.mark_dirty();
+            }
+        }
+        window_state.is_dirty = true;
     }
-    for record in scene.materials_pbr.values_mut() {
-        if record.texture_ids.iter().any(|id| *id == texture_id) {
-            record.bind_group = None;
-            record.mark_dirty();
+}
+
+/// Creates a texture, reporting an out-of-memory failure as an error instead of leaving it
+/// to the device's uncaptured error handler, which panics by default
+pub(crate) fn try_create_texture(
+    device: &wgpu::Device,
+    desc: &wgpu::TextureDescriptor,
+) -> Result<wgpu::Texture, String> {
+    // Browser error scopes resolve asynchronously and cannot be awaited here
+    #[cfg(not(feature = "wasm"))]
+    {
+        let scope = device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        let texture = device.create_texture(desc);
+        if let Some(error) = pollster::block_on(scope.pop()) {
+            return Err(format!("Out of GPU memory creating texture: {}", error));
         }
+        Ok(texture)
     }
+    #[cfg(feature = "wasm")]
+    Ok(device.create_texture(desc))
+}
+
+/// Format and bytes per texel of a standalone texture holding `image`
+fn standalone_format(image: &ImageBuffer, srgb: Option<bool>) -> (wgpu::TextureFormat, u32) {
+    match image.pixels {
+        ImagePixels::Rgba8(_) if image.is_srgb(srgb) => (wgpu::TextureFormat::Rgba8UnormSrgb, 4),
+        ImagePixels::Rgba8(_) => (wgpu::TextureFormat::Rgba8Unorm, 4),
+        ImagePixels::Rgba16F(_) => (wgpu::TextureFormat::Rgba16Float, 8),
+    }
+}
+
+/// GPU bytes `upload_standalone_texture` allocates for `image`
+pub(crate) fn standalone_texture_bytes(image: &ImageBuffer, srgb: Option<bool>) -> u64 {
+    let (format, _) = standalone_format(image, srgb);
+    texture_footprint(
+        format,
+        wgpu::Extent3d {
+            width: image.width,
+            height: image.height,
+            depth_or_array_layers: 1,
+        },
+        1 + image.mips.len() as u32,
+    )
+}
+
+/// Creates a standalone 2D texture from decoded pixels and queues the writes of every mip.
+/// Only touches the device and queue, so the async decode workers call it too.
+pub(crate) fn upload_standalone_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    label: Option<String>,
+    srgb: Option<bool>,
+    image: ImageBuffer,
+    retain_cpu_copy: bool,
+) -> Result<TextureRecord, String> {
+    let (format, texel_bytes) = standalone_format(&image, srgb);
+    let size = wgpu::Extent3d {
+        width: image.width,
+        height: image.height,
+        depth_or_array_layers: 1,
+    };
+
+    let texture = try_create_texture(
+        device,
+        &wgpu::TextureDescriptor {
+            label: label.as_deref().or(Some("Texture From Buffer")),
+            size,
+            mip_level_count: 1 + image.mips.len() as u32,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        },
+    )?;
+    let cpu_copy = retain_cpu_copy.then(|| image.clone());
+
+    for (level, pixels) in std::iter::once(&image.pixels)
+        .chain(&image.mips)
+        .enumerate()
+    {
+        let level = level as u32;
+        let mip_width = (image.width >> level).max(1);
+        let mip_height = (image.height >> level).max(1);
+        let data: &[u8] = match pixels {
+            ImagePixels::Rgba8(data) => data.as_slice(),
+            ImagePixels::Rgba16F(data) => bytemuck::cast_slice(data),
+        };
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: level,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(texel_bytes * mip_width),
+                rows_per_image: Some(mip_height),
+            },
+            wgpu::Extent3d {
+                width: mip_width,
+                height: mip_height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    Ok(TextureRecord {
+        label,
+        _texture: texture,
+        view,
+        view_dimension: wgpu::TextureViewDimension::D2,
+        _size: size,
+        _format: format,
+        cpu_copy,
+    })
+}
+
+/// Packs RGBA8 pixels into the shared forward atlas as `texture_id`. An existing texture
+/// with that id is replaced only once the tiles are allocated. Returns the ids of the other
+/// textures whose tiles moved to make room.
+pub(crate) fn insert_forward_atlas_texture(
+    textures: &mut TextureStore,
+    window_id: u32,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture_id: u32,
+    label: Option<String>,
+    pixels: &[u8],
+    size: UVec2,
+    format: wgpu::TextureFormat,
+    options: &ForwardAtlasOptions,
+    cpu_copy: Option<ImageBuffer>,
+) -> Result<Vec<u32>, String> {
+    let atlas_desc = ForwardAtlasDesc {
+        label: Some("Forward Atlas"),
+        format,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_DST
+            | wgpu::TextureUsages::COPY_SRC,
+        tile_px: options.tile_px,
+        layers: options.layers,
+    };
+    let (handle, transform, relocation_transforms) = {
+        let atlas = ensure_forward_atlas(textures, device, queue, &atlas_desc)?;
+        let tiles_x = size.x.div_ceil(options.tile_px);
+        let tiles_y = size.y.div_ceil(options.tile_px);
+        let (handle, relocations) = atlas
+            .alloc(tiles_x, tiles_y)
+            .ok_or("Forward atlas allocation failed")?;
+        let (x, y, _, _, layer) = atlas
+            .get_copy_rect(handle)
+            .ok_or("Forward atlas allocation invalid")?;
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: atlas.texture(),
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: layer },
+                aspect: wgpu::TextureAspect::All,
+            },
+            pixels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * size.x),
+                rows_per_image: Some(size.y),
+            },
+            wgpu::Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+        );
+        let transform = atlas
+            .get_uv_transform(handle)
+            .ok_or("Forward atlas transform missing")?;
+        let relocation_transforms: Vec<_> = relocations
+            .iter()
+            .map(|r| (r.handle, atlas.get_uv_transform(r.handle)))
+            .collect();
+        (handle, transform, relocation_transforms)
+    };
+
+    let mut relocated = Vec::new();
+    for (handle, transform) in relocation_transforms {
+        if let Some(transform) = transform {
+            for (tex_id, entry) in textures.atlas_entries.iter_mut() {
+                if entry.handle == handle {
+                    entry.uv_scale_bias =
+                        Vec4::new(transform.0, transform.1, transform.2, transform.3);
+                    entry.layer = transform.4;
+                    relocated.push(*tex_id);
+                }
+            }
+        }
+    }
+
+    textures.insert_atlas_entry(
+        texture_id,
+        window_id,
+        ForwardAtlasEntry {
+            label,
+            handle,
+            _size: size,
+            uv_scale_bias: Vec4::new(transform.0, transform.1, transform.2, transform.3),
+            layer: transform.4,
+            _format: format,
+            cpu_copy,
+        },
+    );
+    Ok(relocated)
 }
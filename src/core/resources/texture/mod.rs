@@ -2,8 +2,10 @@ mod async_decode;
 mod cmd;
 mod forward_atlas;
 mod spec;
+mod store;
 
 pub use async_decode::*;
 pub use cmd::*;
 pub use forward_atlas::*;
 pub use spec::*;
+pub use store::*;
@@ -1,5 +1,6 @@
 use glam::{UVec2, Vec4};
 
+use crate::core::image::ImageBuffer;
 use crate::core::resources::texture::ForwardAtlasHandle;
 
 #[derive(Debug)]
@@ -9,6 +10,10 @@ pub struct TextureRecord {
     pub _format: wgpu::TextureFormat,
     pub _texture: wgpu::Texture,
     pub view: wgpu::TextureView,
+    /// `D2` for plain textures; only those can be bound by materials
+    pub view_dimension: wgpu::TextureViewDimension,
+    /// Decoded pixels kept for re-upload after device loss (`retain_cpu_copy`)
+    pub cpu_copy: Option<ImageBuffer>,
 }
 
 #[derive(Debug)]
@@ -19,4 +24,5 @@ pub struct ForwardAtlasEntry {
     pub uv_scale_bias: Vec4,
     pub layer: u32,
     pub _format: wgpu::TextureFormat,
+    pub cpu_copy: Option<ImageBuffer>,
 }
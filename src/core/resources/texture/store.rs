@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use crate::core::resources::texture::{ForwardAtlasEntry, ForwardAtlasSystem, TextureRecord};
+
+/// Textures of every window on the device: standalone textures, forward atlas entries and
+/// the atlas they are packed into. Materials stay per window and bind from here.
+#[derive(Default)]
+pub struct TextureStore {
+    pub records: HashMap<u32, TextureRecord>,
+    pub atlas_entries: HashMap<u32, ForwardAtlasEntry>,
+    /// Tile atlas the entries are packed into, created by the first atlas texture
+    pub atlas: Option<ForwardAtlasSystem>,
+    /// Bumped whenever a texture is created, replaced or disposed
+    pub version: u64,
+    /// Bumped whenever the atlas texture is created or regrown, so its view changes
+    pub atlas_version: u64,
+    /// Window whose command created (or last replaced) each texture
+    owners: HashMap<u32, u32>,
+}
+
+impl TextureStore {
+    pub fn contains(&self, texture_id: u32) -> bool {
+        self.records.contains_key(&texture_id) || self.atlas_entries.contains_key(&texture_id)
+    }
+
+    /// Stores a standalone texture, replacing any texture with the same id
+    pub fn insert_record(&mut self, texture_id: u32, window_id: u32, record: TextureRecord) {
+        self.remove(texture_id);
+        self.records.insert(texture_id, record);
+        self.owners.insert(texture_id, window_id);
+    }
+
+    /// Stores a forward atlas entry whose tiles are already allocated, replacing any texture
+    /// with the same id
+    pub fn insert_atlas_entry(
+        &mut self,
+        texture_id: u32,
+        window_id: u32,
+        entry: ForwardAtlasEntry,
+    ) {
+        self.remove(texture_id);
+        self.atlas_entries.insert(texture_id, entry);
+        self.owners.insert(texture_id, window_id);
+    }
+
+    /// Drops a standalone texture or forward atlas entry, freeing its atlas tiles, and bumps
+    /// `version`. Returns whether the texture existed.
+    pub fn remove(&mut self, texture_id: u32) -> bool {
+        self.owners.remove(&texture_id);
+        let removed = if self.records.remove(&texture_id).is_some() {
+            true
+        } else if let Some(entry) = self.atlas_entries.remove(&texture_id) {
+            if let Some(atlas) = self.atlas.as_mut() {
+                let _ = atlas.free(entry.handle);
+            }
+            true
+        } else {
+            false
+        };
+        self.version += 1;
+        removed
+    }
+
+    /// Ids of the textures created by commands naming `window_id`
+    pub fn owned_by(&self, window_id: u32) -> Vec<u32> {
+        self.owners
+            .iter()
+            .filter(|&(_, owner)| *owner == window_id)
+            .map(|(&texture_id, _)| texture_id)
+            .collect()
+    }
+
+    /// CPU bytes kept by textures created with `retainCpuCopy`
+    pub fn retained_cpu_bytes(&self) -> u64 {
+        self.records
+            .values()
+            .filter_map(|record| record.cpu_copy.as_ref())
+            .chain(
+                self.atlas_entries
+                    .values()
+                    .filter_map(|entry| entry.cpu_copy.as_ref()),
+            )
+            .map(|image| image.byte_len() as u64)
+            .sum()
+    }
+}
@@ -3,6 +3,11 @@
 use bytemuck::{Pod, bytes_of};
 use std::marker::PhantomData;
 
+use super::staging::UploadBatch;
+
+/// Copies kept by pools rewritten every frame; matches the deepest swapchain queue
+pub const UNIFORM_POOL_FRAMES_IN_FLIGHT: usize = 3;
+
 // -----------------------------------------------------------------------------
 // Internal types
 // -----------------------------------------------------------------------------
@@ -19,12 +24,16 @@ struct GarbageEntry {
 
 #[derive(Debug)]
 pub struct UniformBufferPool<T: Pod> {
-    buffer: wgpu::Buffer,
+    copies: Vec<wgpu::Buffer>,
+    current: usize,
     capacity: u32,
     item_size: u64,
     device: wgpu::Device,
     queue: wgpu::Queue,
 
+    // Writes are batched and flushed into the current copy before the next submit
+    pending: UploadBatch,
+
     // Deferred drop
     garbage: Vec<GarbageEntry>,
     keep_frames: u64,
@@ -41,6 +50,20 @@ impl<T: Pod> UniformBufferPool<T> {
         queue: &wgpu::Queue,
         initial_capacity: Option<u32>,
         alignment: u64,
+    ) -> Self {
+        Self::new_buffered(device, queue, initial_capacity, alignment, 1)
+    }
+
+    /// Pool cycling through `frames` buffers (`frame_index % frames`), so this
+    /// frame's writes never land in a buffer the GPU may still be reading.
+    /// Writes only reach the current copy; callers rewrite every slot they read each frame.
+    #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+    pub fn new_buffered(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        initial_capacity: Option<u32>,
+        alignment: u64,
+        frames: usize,
     ) -> Self {
         let capacity = initial_capacity.unwrap_or(4);
         let raw_item_size = std::mem::size_of::<T>() as u64;
@@ -55,21 +78,18 @@ impl<T: Pod> UniformBufferPool<T> {
         };
 
         let buffer_size = capacity as u64 * item_size;
-        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("UniformBufferPool"),
-            size: buffer_size,
-            usage: wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST
-                | wgpu::BufferUsages::UNIFORM,
-            mapped_at_creation: false,
-        });
+        let copies = (0..frames.max(1))
+            .map(|_| Self::create_buffer(device, "UniformBufferPool", buffer_size))
+            .collect();
 
         Self {
-            buffer,
+            copies,
+            current: 0,
             capacity,
             item_size,
             device: device.clone(),
             queue: queue.clone(),
+            pending: UploadBatch::default(),
             garbage: Vec::new(),
             keep_frames: 3,
             version: 0,
@@ -81,6 +101,23 @@ impl<T: Pod> UniformBufferPool<T> {
         self.version
     }
 
+    /// Number of buffers the pool cycles through
+    pub fn copy_count(&self) -> usize {
+        self.copies.len()
+    }
+
+    /// Index of the buffer returned by `buffer()` this frame
+    pub fn current_copy(&self) -> usize {
+        self.current
+    }
+
+    /// Whether the pool cycles through several buffers
+    pub fn is_buffered(&self) -> bool {
+        self.copies.len() > 1
+    }
+
+    /// Writes a whole slot; bytes past `data` are zeroed so consecutive slots
+    /// flush as one write. Buffers start zeroed and padding is never read.
     pub fn write_bytes(&mut self, index: u32, data: &[u8]) {
         assert!(
             data.len() as u64 <= self.item_size,
@@ -92,7 +129,8 @@ impl<T: Pod> UniformBufferPool<T> {
         }
 
         let offset = index as u64 * self.item_size;
-        self.queue.write_buffer(&self.buffer, offset, data);
+        self.pending
+            .push_padded(offset, data, self.item_size as usize);
     }
 
     pub fn write(&mut self, index: u32, value: &T) {
@@ -101,7 +139,17 @@ impl<T: Pod> UniformBufferPool<T> {
     }
 
     pub fn buffer(&self) -> &wgpu::Buffer {
-        &self.buffer
+        &self.copies[self.current]
+    }
+
+    /// Buffer of one copy, for bind groups built once per copy
+    pub fn copy_buffer(&self, copy: usize) -> &wgpu::Buffer {
+        &self.copies[copy]
+    }
+
+    /// Uploads pending writes. Call before submitting work that reads this pool.
+    pub fn flush(&mut self) {
+        self.pending.flush(&self.queue, &self.copies[self.current]);
     }
 
     pub fn get_offset(&self, index: u32) -> u64 {
@@ -113,7 +161,10 @@ impl<T: Pod> UniformBufferPool<T> {
     // -------------------------------------------------------------------------
 
     /// Call once per frame to release old buffers that are safe to drop.
+    /// Buffered pools also move on to the next copy here.
     pub fn begin_frame(&mut self, frame_index: u64) {
+        self.current = (frame_index % self.copies.len() as u64) as usize;
+
         // Set retire frame for new garbage entries
         for g in &mut self.garbage {
             if g.retire_after_frame == 0 {
@@ -129,6 +180,17 @@ impl<T: Pod> UniformBufferPool<T> {
     // Resize
     // -------------------------------------------------------------------------
 
+    fn create_buffer(device: &wgpu::Device, label: &str, size: u64) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size,
+            usage: wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST
+                | wgpu::BufferUsages::UNIFORM,
+            mapped_at_creation: false,
+        })
+    }
+
     fn scale_to_capacity(&mut self, required_capacity: u32) {
         if required_capacity <= self.capacity {
             return;
@@ -137,38 +199,42 @@ impl<T: Pod> UniformBufferPool<T> {
         let new_capacity = self.calculate_next_capacity(required_capacity);
         let new_size = new_capacity as u64 * self.item_size;
 
-        let new_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("UniformBufferPool (resized)"),
-            size: new_size,
-            usage: wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST
-                | wgpu::BufferUsages::UNIFORM,
-            mapped_at_creation: false,
-        });
-
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
                 label: Some("UniformBufferPool resize encoder"),
             });
 
-        encoder.copy_buffer_to_buffer(
-            &self.buffer,
-            0,
-            &new_buffer,
-            0,
-            self.capacity as u64 * self.item_size,
-        );
+        // Pending writes target the old buffer; land them before it is copied
+        self.flush();
+
+        // Other copies are rewritten before they become current again
+        let mut old_buffers = Vec::with_capacity(self.copies.len());
+        for (index, buffer) in self.copies.iter_mut().enumerate() {
+            let new_buffer =
+                Self::create_buffer(&self.device, "UniformBufferPool (resized)", new_size);
+            if index == self.current {
+                encoder.copy_buffer_to_buffer(
+                    buffer,
+                    0,
+                    &new_buffer,
+                    0,
+                    self.capacity as u64 * self.item_size,
+                );
+            }
+            old_buffers.push(std::mem::replace(buffer, new_buffer));
+        }
 
         self.queue.submit(Some(encoder.finish()));
 
-        // Deferred drop: keep old buffer alive for keep_frames
-        // This ensures GPU commands referencing it have completed
-        let old = std::mem::replace(&mut self.buffer, new_buffer);
-        self.garbage.push(GarbageEntry {
-            _buffer: old,
-            retire_after_frame: 0, // Will be set by first begin_frame call
-        });
+        // Deferred drop: keep old buffers alive for keep_frames
+        // This ensures GPU commands referencing them have completed
+        for old in old_buffers {
+            self.garbage.push(GarbageEntry {
+                _buffer: old,
+                retire_after_frame: 0, // Will be set by first begin_frame call
+            });
+        }
 
         self.capacity = new_capacity;
         self.version += 1;
@@ -1,18 +1,24 @@
 use super::{
-    AllocHandle, GeometryPrimitiveType, GeometryRecord, GeometryStorage, IndexAlloc, IndexInfo,
-    VertexAllocError, VertexAllocatorSystem, VertexStream, align4, all_streams, pad_to_4,
+    AllocHandle, GeometryCpuCopy, GeometryCreateOptions, GeometryPrimitiveType, GeometryRecord,
+    GeometryStorage, IndexAlloc, IndexInfo, VertexAllocError, VertexAllocatorSystem, VertexStream,
+    align4, all_streams, pad_to_4,
 };
 use crate::core::resources::geometry::Aabb;
+use crate::core::resources::geometry::generators::{compute_normals, compute_tangents};
+use glam::{Vec2, Vec3};
 use std::collections::HashSet;
 use std::ops::Range;
 use wgpu::BufferDescriptor;
 
 impl VertexAllocatorSystem {
+    /// Creates or replaces geometry `id` on behalf of window `window_id`
     pub fn create_geometry(
         &mut self,
         id: u32,
+        window_id: u32,
         label: Option<String>,
         mut input: Vec<(GeometryPrimitiveType, Vec<u8>)>,
+        options: GeometryCreateOptions,
     ) -> Result<(), VertexAllocError> {
         let mut index_bytes: Option<Vec<u8>> = None;
         let mut stream_bytes: [Option<Vec<u8>>; 8] = [(); 8].map(|_| None);
@@ -135,6 +141,13 @@ impl VertexAllocatorSystem {
             None
         };
 
+        derive_missing_streams(
+            &mut stream_bytes,
+            index_info.as_ref().map(|(b, _)| b.as_slice()),
+            vertex_count,
+            options,
+        );
+
         let mut total_aligned: u64 = 0;
         if let Some((b, _)) = index_info.as_ref() {
             total_aligned += align4(b.len() as u64);
@@ -145,11 +158,19 @@ impl VertexAllocatorSystem {
             }
         }
 
+        self.check_budget(id, total_aligned)?;
+
+        let cpu_copy = options.retain_cpu_copy.then(|| GeometryCpuCopy {
+            index: index_info.as_ref().map(|(b, _)| b.clone()),
+            streams: stream_bytes.clone(),
+        });
+
         let storage = if total_aligned > self.cfg.dedicated_threshold_bytes {
             self.create_dedicated(vertex_count, index_info, stream_bytes)?
         } else {
             self.create_pooled(vertex_count, index_info, stream_bytes)?
         };
+        self.evict_over_budget(id, total_aligned);
 
         // CRITICAL: If ID already exists, we must free old allocations before replacing
         if let Some(rec) = self.records.get_mut(&id) {
@@ -172,17 +193,25 @@ impl VertexAllocatorSystem {
                 }
             }
             rec.alive = true;
+            rec.window_id = window_id;
             rec.label = label;
             rec.storage = storage;
             rec.aabb = aabb;
+            rec.cpu_copy = cpu_copy;
+            rec.gpu_bytes = total_aligned;
+            rec.last_used_frame = self.frame_index;
         } else {
             self.records.insert(
                 id,
                 GeometryRecord {
+                    window_id,
                     label,
                     alive: true,
                     storage,
                     aabb,
+                    cpu_copy,
+                    gpu_bytes: total_aligned,
+                    last_used_frame: self.frame_index,
                 },
             );
         }
@@ -259,7 +288,9 @@ impl VertexAllocatorSystem {
         let buffer = self.device.create_buffer(&BufferDescriptor {
             label: Some("Geometry(Dedicated)"),
             size: total_size,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::INDEX,
+            usage: wgpu::BufferUsages::VERTEX
+                | wgpu::BufferUsages::INDEX
+                | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
         });
 
@@ -331,3 +362,50 @@ impl VertexAllocatorSystem {
         Ok(())
     }
 }
+
+/// Fill in Normal/Tangent streams requested through `GeometryCreateOptions`.
+/// Streams supplied by the host are never replaced.
+fn derive_missing_streams(
+    stream_bytes: &mut [Option<Vec<u8>>; 8],
+    index_bytes: Option<&[u8]>,
+    vertex_count: u32,
+    options: GeometryCreateOptions,
+) {
+    let wants_normals =
+        options.compute_normals && stream_bytes[VertexStream::Normal as usize].is_none();
+    let wants_tangents = options.compute_tangents
+        && stream_bytes[VertexStream::Tangent as usize].is_none()
+        && stream_bytes[VertexStream::UV0 as usize].is_some();
+    if !wants_normals && !wants_tangents {
+        return;
+    }
+
+    let positions: Vec<Vec3> = stream_bytes[VertexStream::Position as usize]
+        .as_deref()
+        .map(bytemuck::pod_collect_to_vec)
+        .unwrap_or_default();
+    let indices: Vec<u32> = match index_bytes {
+        Some(bytes) => bytemuck::pod_collect_to_vec(bytes),
+        None => (0..vertex_count).collect(),
+    };
+
+    // Tangents need a normal frame even when the normals themselves are not stored
+    let normals: Vec<Vec3> = match stream_bytes[VertexStream::Normal as usize].as_deref() {
+        Some(bytes) => bytemuck::pod_collect_to_vec(bytes),
+        None => compute_normals(&positions, &indices),
+    };
+
+    if wants_tangents {
+        let uvs: Vec<Vec2> = stream_bytes[VertexStream::UV0 as usize]
+            .as_deref()
+            .map(bytemuck::pod_collect_to_vec)
+            .unwrap_or_default();
+        let tangents = compute_tangents(&positions, &normals, &uvs, &indices);
+        stream_bytes[VertexStream::Tangent as usize] =
+            Some(bytemuck::cast_slice(&tangents).to_vec());
+    }
+
+    if wants_normals {
+        stream_bytes[VertexStream::Normal as usize] = Some(bytemuck::cast_slice(&normals).to_vec());
+    }
+}
@@ -279,6 +279,19 @@ impl ArenaAllocator {
         self.queue.write_buffer(&self.buffer, slice.offset, data);
     }
 
+    /// Write raw bytes at `offset` inside an existing allocation.
+    pub fn write_bytes_at(&self, handle: AllocHandle, offset: u64, data: &[u8]) {
+        let slice = self.slice(handle);
+        assert!(
+            offset
+                .checked_add(data.len() as u64)
+                .is_some_and(|end| end <= slice.size),
+            "data does not fit allocation"
+        );
+        self.queue
+            .write_buffer(&self.buffer, slice.offset + offset, data);
+    }
+
     /// Allocate and write raw bytes. Input length must already satisfy alignment.
     pub fn allocate_and_write(&mut self, data: &[u8]) -> AllocHandle {
         assert!(!data.is_empty(), "cannot allocate empty data");
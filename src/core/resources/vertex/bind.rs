@@ -91,6 +91,10 @@ impl VertexAllocatorSystem {
             self.set_vertex_cached(pass, slot, buf, range);
         }
 
+        if let Some(rec) = self.records.get_mut(&id) {
+            rec.last_used_frame = self.frame_index;
+        }
+
         Ok(())
     }
 
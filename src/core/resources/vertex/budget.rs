@@ -0,0 +1,109 @@
+use super::{VertexAllocError, VertexAllocatorSystem, VertexBudgetPolicy};
+
+/// A geometry destroyed by `VertexBudgetPolicy::EvictLru`
+#[derive(Debug, Clone, Copy)]
+pub struct EvictedGeometry {
+    pub geometry_id: u32,
+    /// Window that created the geometry
+    pub window_id: u32,
+    pub bytes: u64,
+}
+
+impl VertexAllocatorSystem {
+    pub fn set_budget(&mut self, max_total_bytes: Option<u64>, policy: VertexBudgetPolicy) {
+        self.cfg.max_total_bytes = max_total_bytes;
+        self.cfg.budget_policy = policy;
+    }
+
+    /// Bytes of all live geometries, as counted against `max_total_bytes`
+    pub fn used_bytes(&self) -> u64 {
+        self.records
+            .values()
+            .filter(|record| record.alive)
+            .map(|record| record.gpu_bytes)
+            .sum()
+    }
+
+    /// Geometries evicted since the last call
+    pub fn take_evicted(&mut self) -> Vec<EvictedGeometry> {
+        std::mem::take(&mut self.evicted)
+    }
+
+    /// Checks that `requested` bytes can fit under geometry `id`, counting what `EvictLru`
+    /// may free. The geometry being replaced is never evicted and its current bytes do not
+    /// count. Nothing is evicted here; `evict_over_budget` does it once the storage exists.
+    pub(super) fn check_budget(&self, id: u32, requested: u64) -> Result<(), VertexAllocError> {
+        let Some(budget) = self.cfg.max_total_bytes else {
+            return Ok(());
+        };
+
+        let used = self.used_bytes_except(id);
+        if used + requested <= budget {
+            return Ok(());
+        }
+
+        if requested > budget || self.cfg.budget_policy == VertexBudgetPolicy::Fail {
+            return Err(VertexAllocError::BudgetExceeded {
+                requested,
+                used,
+                budget,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Evicts the least recently used geometries until `requested` bytes fit under
+    /// geometry `id`. Runs after `check_budget` and the allocation both succeeded, so a
+    /// failed create never costs other geometries.
+    pub(super) fn evict_over_budget(&mut self, id: u32, requested: u64) {
+        let Some(budget) = self.cfg.max_total_bytes else {
+            return;
+        };
+        let used = self.used_bytes_except(id);
+        if used + requested <= budget {
+            return;
+        }
+
+        // Oldest first; ids break ties so eviction order is deterministic
+        let mut candidates: Vec<(u64, u32, u64, u32)> = self
+            .records
+            .iter()
+            .filter(|(geometry_id, record)| record.alive && **geometry_id != id)
+            .map(|(geometry_id, record)| {
+                (
+                    record.last_used_frame,
+                    *geometry_id,
+                    record.gpu_bytes,
+                    record.window_id,
+                )
+            })
+            .collect();
+        candidates.sort_unstable();
+
+        let mut remaining = used;
+        for (_, geometry_id, bytes, window_id) in candidates {
+            if remaining + requested <= budget {
+                break;
+            }
+            if self.destroy_geometry(geometry_id).is_ok() {
+                remaining -= bytes;
+                self.evicted.push(EvictedGeometry {
+                    geometry_id,
+                    window_id,
+                    bytes,
+                });
+            }
+        }
+    }
+
+    /// Live bytes, leaving out geometry `id` since it is about to be replaced
+    fn used_bytes_except(&self, id: u32) -> u64 {
+        let replaced = self
+            .records
+            .get(&id)
+            .filter(|record| record.alive)
+            .map_or(0, |record| record.gpu_bytes);
+        self.used_bytes() - replaced
+    }
+}
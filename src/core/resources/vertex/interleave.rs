@@ -0,0 +1,69 @@
+use super::{
+    GeometryPrimitiveType, InterleavedAttribute, InterleavedLayout, VertexAllocError, VertexStream,
+};
+
+/// Splits an interleaved vertex buffer into one byte stream per attribute, in
+/// layout order, ready for `create_geometry`. Two `uv` attributes become UV0
+/// and UV1 in the order they are listed.
+pub fn deinterleave(
+    bytes: &[u8],
+    layout: &InterleavedLayout,
+) -> Result<Vec<(GeometryPrimitiveType, Vec<u8>)>, VertexAllocError> {
+    let stride = layout.stride as usize;
+    if stride == 0 {
+        return Err(VertexAllocError::InvalidInterleavedLayout {
+            reason: "stride must be greater than 0",
+        });
+    }
+    if layout.attributes.is_empty() {
+        return Err(VertexAllocError::InvalidInterleavedLayout {
+            reason: "no attributes",
+        });
+    }
+    if !bytes.len().is_multiple_of(stride) {
+        return Err(VertexAllocError::InvalidInterleavedLayout {
+            reason: "buffer length must be a multiple of the stride",
+        });
+    }
+
+    let vertex_count = bytes.len() / stride;
+    let mut out = Vec::with_capacity(layout.attributes.len());
+
+    for attribute in &layout.attributes {
+        let size = attribute_size(attribute)?;
+        let offset = attribute.offset as usize;
+        if offset + size > stride {
+            return Err(VertexAllocError::InvalidInterleavedLayout {
+                reason: "attribute extends past the stride",
+            });
+        }
+
+        let mut stream = Vec::with_capacity(vertex_count * size);
+        for vertex in bytes.chunks_exact(stride) {
+            stream.extend_from_slice(&vertex[offset..offset + size]);
+        }
+        out.push((attribute.primitive_type, stream));
+    }
+
+    Ok(out)
+}
+
+/// Bytes the attribute occupies in each vertex, in its stream's fixed format
+fn attribute_size(attribute: &InterleavedAttribute) -> Result<usize, VertexAllocError> {
+    let stream = match attribute.primitive_type {
+        GeometryPrimitiveType::Index => {
+            return Err(VertexAllocError::InvalidInterleavedLayout {
+                reason: "index data cannot be interleaved",
+            });
+        }
+        GeometryPrimitiveType::Position => VertexStream::Position,
+        GeometryPrimitiveType::Normal => VertexStream::Normal,
+        GeometryPrimitiveType::Tangent => VertexStream::Tangent,
+        GeometryPrimitiveType::Color => VertexStream::Color0,
+        // UV0 and UV1 share a format
+        GeometryPrimitiveType::UV => VertexStream::UV0,
+        GeometryPrimitiveType::SkinJoints => VertexStream::Joints,
+        GeometryPrimitiveType::SkinWeights => VertexStream::Weights,
+    };
+    Ok(stream.stride_bytes() as usize)
+}
@@ -6,9 +6,12 @@ mod alloc;
 mod arena;
 mod bind;
 mod bind_cache;
+mod budget;
 mod defaults;
+mod interleave;
 mod storage;
 mod types;
+mod write;
 
 use arena::*;
 use bind_cache::*;
@@ -18,8 +21,11 @@ use types::*;
 
 use crate::core::resources::geometry::Aabb;
 
+pub use budget::EvictedGeometry;
+pub use interleave::deinterleave;
 pub use types::{
-    GeometryPrimitiveType, IndexInfo, STREAM_COUNT, VertexAllocError, VertexAllocatorConfig,
+    GeometryCreateOptions, GeometryPrimitiveType, IndexInfo, InterleavedAttribute,
+    InterleavedLayout, STREAM_COUNT, VertexAllocError, VertexAllocatorConfig, VertexBudgetPolicy,
     VertexStream,
 };
 
@@ -46,6 +52,10 @@ pub struct VertexAllocatorSystem {
     records: HashMap<u32, GeometryRecord>,
 
     bind_cache: BindCache,
+
+    frame_index: u64,
+    /// Geometries dropped to stay within `max_total_bytes`, drained by the commands
+    evicted: Vec<EvictedGeometry>,
 }
 
 impl VertexAllocatorSystem {
@@ -57,6 +67,13 @@ impl VertexAllocatorSystem {
         &mut self.records
     }
 
+    pub fn contains(&self, geometry_id: u32) -> bool {
+        self.records
+            .get(&geometry_id)
+            .map(|record| record.alive)
+            .unwrap_or(false)
+    }
+
     pub fn geometry_has_streams(&self, geometry_id: u32, streams: &[VertexStream]) -> Option<bool> {
         let record = self.records.get(&geometry_id)?;
         let has_stream = |stream: VertexStream| match &record.storage {
@@ -158,6 +175,8 @@ impl VertexAllocatorSystem {
             default_weights: DefaultStreamBuffer::new(device, cfg, VertexStream::Weights),
             records: HashMap::new(),
             bind_cache: BindCache::default(),
+            frame_index: 0,
+            evicted: Vec::new(),
         };
 
         sys.initialize_default_buffers();
@@ -217,6 +236,7 @@ impl VertexAllocatorSystem {
     }
 
     pub fn begin_frame(&mut self, frame_index: u64) {
+        self.frame_index = frame_index;
         self.index_u32.begin_frame(frame_index);
         for p in &mut self.streams {
             p.begin_frame(frame_index);
@@ -247,6 +267,16 @@ impl VertexAllocatorSystem {
         self.records.get(&id).filter(|r| r.alive).map(|r| r.aabb)
     }
 
+    /// Total bytes held by geometries created with `retain_cpu_copy`
+    pub fn retained_cpu_bytes(&self) -> u64 {
+        self.records
+            .values()
+            .filter(|r| r.alive)
+            .filter_map(|r| r.cpu_copy.as_ref())
+            .map(|copy| copy.byte_len() as u64)
+            .sum()
+    }
+
     pub fn maybe_compact_all(
         &mut self,
         frame_index: u64,
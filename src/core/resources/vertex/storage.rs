@@ -22,8 +22,29 @@ pub enum GeometryStorage {
 
 #[derive(Debug)]
 pub struct GeometryRecord {
+    /// Window of the command that created (or last replaced) the geometry
+    pub window_id: u32,
     pub label: Option<String>,
     pub alive: bool,
     pub storage: GeometryStorage,
     pub aabb: Aabb,
+    pub cpu_copy: Option<GeometryCpuCopy>,
+    /// 4-byte aligned size of the index buffer and streams, counted against the budget
+    pub gpu_bytes: u64,
+    /// Last frame the geometry was bound for drawing (or created)
+    pub last_used_frame: u64,
+}
+
+/// Source bytes kept on the CPU so the geometry can be re-uploaded after a device reset
+#[derive(Debug, Clone)]
+pub struct GeometryCpuCopy {
+    pub index: Option<Vec<u8>>,
+    pub streams: [Option<Vec<u8>>; STREAM_COUNT],
+}
+
+impl GeometryCpuCopy {
+    pub fn byte_len(&self) -> usize {
+        self.index.as_ref().map_or(0, Vec::len)
+            + self.streams.iter().flatten().map(Vec::len).sum::<usize>()
+    }
 }
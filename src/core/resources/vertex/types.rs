@@ -63,6 +63,9 @@ pub struct VertexAllocatorConfig {
     pub min_pool_bytes: u64,            // >= 2MB
     pub dedicated_threshold_bytes: u64, // > 16MB => Dedicated
     pub keep_frames: u64,               // deferred drop window for arena resizes/compactions
+    /// Upper bound for the bytes of all live geometries; `None` = unlimited
+    pub max_total_bytes: Option<u64>,
+    pub budget_policy: VertexBudgetPolicy,
 }
 
 #[cfg(all(feature = "wasm", not(target_arch = "wasm32")))]
@@ -70,6 +73,8 @@ pub struct VertexAllocatorConfig {
 pub struct VertexAllocatorConfig {
     pub min_pool_bytes: u64,            // >= 2MB
     pub dedicated_threshold_bytes: u64, // > 16MB => Dedicated
+    pub max_total_bytes: Option<u64>,
+    pub budget_policy: VertexBudgetPolicy,
 }
 
 #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
@@ -79,6 +84,8 @@ impl Default for VertexAllocatorConfig {
             min_pool_bytes: 2 * 1024 * 1024,
             dedicated_threshold_bytes: 16 * 1024 * 1024,
             keep_frames: 3,
+            max_total_bytes: None,
+            budget_policy: VertexBudgetPolicy::Fail,
         }
     }
 }
@@ -89,10 +96,23 @@ impl Default for VertexAllocatorConfig {
         Self {
             min_pool_bytes: 2 * 1024 * 1024,
             dedicated_threshold_bytes: 16 * 1024 * 1024,
+            max_total_bytes: None,
+            budget_policy: VertexBudgetPolicy::Fail,
         }
     }
 }
 
+/// What `create_geometry` does when the new geometry would exceed `max_total_bytes`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VertexBudgetPolicy {
+    /// Reject the geometry with `VertexAllocError::BudgetExceeded`
+    #[default]
+    Fail,
+    /// Destroy the least-recently-drawn geometries until the new one fits
+    EvictLru,
+}
+
 // -----------------------------------------------------------------------------
 // Geometry Primitive Type (input format)
 // -----------------------------------------------------------------------------
@@ -123,6 +143,42 @@ pub struct IndexAlloc {
     pub info: IndexInfo,
 }
 
+// -----------------------------------------------------------------------------
+// Create options (derived streams)
+// -----------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GeometryCreateOptions {
+    /// Derive smooth per-vertex normals from the index topology when no Normal stream is given
+    pub compute_normals: bool,
+    /// Derive tangents from UV0 when no Tangent stream is given (requires UV0)
+    pub compute_tangents: bool,
+    /// Keep the final stream bytes on the CPU for re-upload after device loss
+    pub retain_cpu_copy: bool,
+}
+
+// -----------------------------------------------------------------------------
+// Interleaved input
+// -----------------------------------------------------------------------------
+
+/// One attribute inside an interleaved vertex. Its size is the stride of the
+/// matching stream (e.g. 12 bytes for `position`, 8 for `uv`).
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterleavedAttribute {
+    pub primitive_type: GeometryPrimitiveType,
+    /// Byte offset from the start of the vertex
+    pub offset: u32,
+}
+
+/// Layout of a buffer holding whole vertices back to back
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterleavedLayout {
+    /// Bytes per vertex, including padding
+    pub stride: u32,
+    pub attributes: Vec<InterleavedAttribute>,
+}
+
 // -----------------------------------------------------------------------------
 // Errors
 // -----------------------------------------------------------------------------
@@ -147,6 +203,27 @@ pub enum VertexAllocError {
         stream: crate::core::resources::vertex::VertexStream,
     },
     GeometryNotFound,
+    /// The geometry has no index buffer or stream for the primitive being written
+    StreamNotFound(GeometryPrimitiveType),
+    /// Partial writes must start and end on 4-byte boundaries
+    UnalignedRange {
+        offset: u64,
+        len: u64,
+    },
+    RangeOutOfBounds {
+        offset: u64,
+        len: u64,
+        size: u64,
+    },
+    InvalidInterleavedLayout {
+        reason: &'static str,
+    },
+    /// The geometry does not fit in `max_total_bytes`, even after eviction
+    BudgetExceeded {
+        requested: u64,
+        used: u64,
+        budget: u64,
+    },
 }
 
 impl std::fmt::Display for VertexAllocError {
@@ -181,6 +258,31 @@ impl std::fmt::Display for VertexAllocError {
                 stream, got, expected
             ),
             GeometryNotFound => write!(f, "geometry id not found"),
+            StreamNotFound(t) => write!(f, "geometry has no {:?} data to update", t),
+            UnalignedRange { offset, len } => write!(
+                f,
+                "range offset {} and length {} must be multiples of 4",
+                offset, len
+            ),
+            RangeOutOfBounds { offset, len, size } => write!(
+                f,
+                "range {}..{} exceeds data size {}",
+                offset,
+                offset + len,
+                size
+            ),
+            InvalidInterleavedLayout { reason } => {
+                write!(f, "invalid interleaved layout: {}", reason)
+            }
+            BudgetExceeded {
+                requested,
+                used,
+                budget,
+            } => write!(
+                f,
+                "geometry needs {} bytes but {} of the {} byte budget are in use",
+                requested, used, budget
+            ),
         }
     }
 }
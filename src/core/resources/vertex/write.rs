@@ -0,0 +1,142 @@
+use super::{
+    GeometryPrimitiveType, GeometryStorage, VertexAllocError, VertexAllocatorSystem, VertexStream,
+};
+use crate::core::resources::geometry::Aabb;
+
+impl VertexAllocatorSystem {
+    /// Overwrites part of one stream (or the index buffer) in place. The geometry keeps its
+    /// allocation, vertex count and index count; `byte_offset` is relative to the stream start.
+    /// `uv_set` selects UV0/UV1 for `GeometryPrimitiveType::UV`.
+    pub fn write_range(
+        &mut self,
+        id: u32,
+        primitive: GeometryPrimitiveType,
+        uv_set: u32,
+        byte_offset: u64,
+        data: &[u8],
+    ) -> Result<(), VertexAllocError> {
+        let len = data.len() as u64;
+        if !byte_offset.is_multiple_of(4) || !len.is_multiple_of(4) {
+            return Err(VertexAllocError::UnalignedRange {
+                offset: byte_offset,
+                len,
+            });
+        }
+
+        let record = self
+            .records
+            .get_mut(&id)
+            .filter(|record| record.alive)
+            .ok_or(VertexAllocError::GeometryNotFound)?;
+
+        let stream = match primitive {
+            GeometryPrimitiveType::Index => None,
+            GeometryPrimitiveType::Position => Some(VertexStream::Position),
+            GeometryPrimitiveType::Normal => Some(VertexStream::Normal),
+            GeometryPrimitiveType::Tangent => Some(VertexStream::Tangent),
+            GeometryPrimitiveType::Color => Some(VertexStream::Color0),
+            GeometryPrimitiveType::UV if uv_set == 0 => Some(VertexStream::UV0),
+            GeometryPrimitiveType::UV => Some(VertexStream::UV1),
+            GeometryPrimitiveType::SkinJoints => Some(VertexStream::Joints),
+            GeometryPrimitiveType::SkinWeights => Some(VertexStream::Weights),
+        };
+
+        // Logical size, excluding allocation padding
+        let (index_count, vertex_count, has_stream) = match &record.storage {
+            GeometryStorage::Pooled {
+                index,
+                streams,
+                vertex_count,
+            } => (
+                index.map(|ix| ix.info.count),
+                *vertex_count,
+                stream.is_some_and(|s| streams[s as usize].is_some()),
+            ),
+            GeometryStorage::Dedicated {
+                index,
+                streams,
+                vertex_count,
+                ..
+            } => (
+                index.as_ref().map(|(_, info)| info.count),
+                *vertex_count,
+                stream.is_some_and(|s| streams[s as usize].is_some()),
+            ),
+        };
+        let size = match stream {
+            None => index_count.map(|count| count as u64 * 4),
+            Some(stream) => has_stream.then(|| vertex_count as u64 * stream.stride_bytes()),
+        }
+        .ok_or(VertexAllocError::StreamNotFound(primitive))?;
+
+        if byte_offset.checked_add(len).is_none_or(|end| end > size) {
+            return Err(VertexAllocError::RangeOutOfBounds {
+                offset: byte_offset,
+                len,
+                size,
+            });
+        }
+
+        match (&record.storage, stream) {
+            (GeometryStorage::Pooled { index, .. }, None) => {
+                if let Some(ix) = index {
+                    self.index_u32.write_bytes_at(ix.handle, byte_offset, data);
+                }
+            }
+            (GeometryStorage::Pooled { streams, .. }, Some(stream)) => {
+                if let Some(handle) = streams[stream as usize] {
+                    self.streams[stream as usize].write_bytes_at(handle, byte_offset, data);
+                }
+            }
+            (GeometryStorage::Dedicated { buffer, index, .. }, None) => {
+                if let Some((range, _)) = index {
+                    self.queue
+                        .write_buffer(buffer, range.start + byte_offset, data);
+                }
+            }
+            (
+                GeometryStorage::Dedicated {
+                    buffer, streams, ..
+                },
+                Some(stream),
+            ) => {
+                if let Some(range) = &streams[stream as usize] {
+                    self.queue
+                        .write_buffer(buffer, range.start + byte_offset, data);
+                }
+            }
+        }
+
+        let start = byte_offset as usize;
+        let end = start + data.len();
+        if let Some(cpu_copy) = record.cpu_copy.as_mut() {
+            let target = match stream {
+                None => cpu_copy.index.as_mut(),
+                Some(stream) => cpu_copy.streams[stream as usize].as_mut(),
+            };
+            if let Some(bytes) = target.filter(|bytes| bytes.len() >= end) {
+                bytes[start..end].copy_from_slice(data);
+            }
+        }
+
+        if stream == Some(VertexStream::Position) {
+            // Exact bounds when the positions are retained, otherwise grow to cover the new ones
+            let retained = record
+                .cpu_copy
+                .as_ref()
+                .and_then(|cpu_copy| cpu_copy.streams[VertexStream::Position as usize].as_ref());
+            record.aabb = match retained {
+                Some(positions) => Aabb::from_bytes(positions),
+                None => {
+                    let written = Aabb::from_bytes(data);
+                    Aabb {
+                        min: record.aabb.min.min(written.min),
+                        max: record.aabb.max.max(written.max),
+                    }
+                }
+            };
+        }
+
+        Ok(())
+    }
+}
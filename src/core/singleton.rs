@@ -13,6 +13,12 @@ use super::window::CmdWindowCreateArgs;
 pub enum EngineCustomEvents {
     CreateWindow(u64, CmdWindowCreateArgs),
     NotificationInteraction(super::system::SystemEvent),
+    /// Custom cursors are created by the active event loop: (cmd id, window id, image)
+    SetCursorImage(
+        u64,
+        u32,
+        crate::core::platform::winit::window::CustomCursorSource,
+    ),
 }
 
 #[cfg(feature = "wasm")]
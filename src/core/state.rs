@@ -4,13 +4,20 @@ use crate::core::audio::KiraAudioProxy;
 use crate::core::audio::WebAudioProxy;
 use crate::core::audio::{AudioListenerBinding, AudioSourceParams, AudioStreamState};
 use crate::core::buffers::state::BufferStorage;
-use crate::core::cmd::{EngineBatchCmds, EngineBatchEvents, EngineBatchResponses};
+use crate::core::cmd::{
+    CommandResponseEnvelope, EngineBatchCmds, EngineBatchEvents, EngineBatchResponses,
+};
+use crate::core::codec::Codec;
 use crate::core::gamepad::state::GamepadState;
 #[cfg(not(feature = "wasm"))]
 use crate::core::input::InputState;
 use crate::core::profiling::TickProfiling;
 use crate::core::profiling::gpu::GpuProfiler;
-use crate::core::resources::TextureAsyncManager;
+use crate::core::render::state::SharedResources;
+use crate::core::resources::{CameraController, TextureAsyncManager};
+use crate::core::system::{FixedStepState, MemoryBudgetState, QueueLimits};
+#[cfg(not(feature = "wasm"))]
+use crate::core::trace::TraceRecorder;
 use crate::core::window::WindowManager;
 use std::collections::HashMap;
 
@@ -22,20 +29,37 @@ pub struct EngineState {
     pub wgpu: wgpu::Instance,
     #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
     pub caps: Option<wgpu::SurfaceCapabilities>,
+    /// Adapter the device was created from
+    #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+    pub adapter: Option<wgpu::Adapter>,
     pub device: Option<wgpu::Device>,
     pub queue: Option<wgpu::Queue>,
 
     pub buffers: BufferStorage,
+    /// GPU resources shared by all windows
+    pub shared: SharedResources,
     pub texture_async: TextureAsyncManager,
     pub audio: Box<dyn crate::core::audio::AudioProxy>,
     pub audio_listener_binding: Option<AudioListenerBinding>,
     pub audio_source_bindings: HashMap<u32, AudioListenerBinding>,
     pub audio_source_params: HashMap<u32, AudioSourceParams>,
     pub audio_streams: HashMap<u32, AudioStreamState>,
+    pub camera_controllers: HashMap<u32, CameraController>,
 
     pub cmd_queue: EngineBatchCmds,
     pub event_queue: EngineBatchEvents,
     pub response_queue: EngineBatchResponses,
+    /// Commands accepted by `engine_process_batch` whose response is produced later
+    pub(crate) pending_async_cmds: usize,
+    pub(crate) queue_limits: QueueLimits,
+    pub(crate) codec: Codec,
+    pub(crate) frame_stats_enabled: bool,
+    /// `EVENT_CATEGORY_*` bits of the events delivered to the host
+    pub(crate) event_subscription: u32,
+    pub(crate) fixed_step: FixedStepState,
+    pub(crate) memory_budget: MemoryBudgetState,
+    #[cfg(not(feature = "wasm"))]
+    pub(crate) recorder: Option<TraceRecorder>,
 
     pub(crate) time: u64,
     pub(crate) delta_time: u32,
@@ -78,9 +102,12 @@ impl EngineState {
             wgpu: wgpu::Instance::new(&wgpu_descriptor),
             #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
             caps: None,
+            #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+            adapter: None,
             device: None,
             queue: None,
             buffers: BufferStorage::new(),
+            shared: SharedResources::default(),
             texture_async: TextureAsyncManager::new(),
             #[cfg(not(feature = "wasm"))]
             audio: Box::new(KiraAudioProxy::default()),
@@ -90,9 +117,19 @@ impl EngineState {
             audio_source_bindings: HashMap::new(),
             audio_source_params: HashMap::new(),
             audio_streams: HashMap::new(),
+            camera_controllers: HashMap::new(),
             cmd_queue: Vec::new(),
             event_queue: Vec::new(),
             response_queue: Vec::new(),
+            pending_async_cmds: 0,
+            queue_limits: QueueLimits::default(),
+            codec: Codec::default(),
+            frame_stats_enabled: false,
+            event_subscription: crate::core::system::EVENT_CATEGORY_ALL,
+            fixed_step: FixedStepState::default(),
+            memory_budget: MemoryBudgetState::default(),
+            #[cfg(not(feature = "wasm"))]
+            recorder: None,
             time: 0,
             delta_time: 0,
             frame_index: 0,
@@ -104,7 +141,21 @@ impl EngineState {
         }
     }
 
+    /// Commands that have not produced a response yet (queued + in flight, screenshots
+    /// included), plus the texture decodes that still owe their completion event
+    pub fn pending_command_count(&self) -> usize {
+        self.cmd_queue.len() + self.pending_async_cmds + self.texture_async.job_count()
+    }
+
+    /// Push the response of a command that completed outside `engine_process_batch`
+    pub(crate) fn complete_async_cmd(&mut self, envelope: CommandResponseEnvelope) {
+        self.pending_async_cmds = self.pending_async_cmds.saturating_sub(1);
+        self.response_queue.push(envelope);
+    }
+
     pub fn cleanup_window(&mut self, window_id: u32) -> bool {
+        crate::core::render::screenshot::abort_screenshot(self, window_id);
+
         #[cfg(feature = "wasm")]
         return self.window.cleanup_window(window_id);
 
@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::VulframResult;
+use crate::core::singleton::with_engine;
+use crate::core::state::EngineState;
+
+// MARK: - Info
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AdapterDeviceType {
+    #[default]
+    Other,
+    IntegratedGpu,
+    DiscreteGpu,
+    VirtualGpu,
+    Cpu,
+}
+
+impl From<wgpu::DeviceType> for AdapterDeviceType {
+    fn from(device_type: wgpu::DeviceType) -> Self {
+        match device_type {
+            wgpu::DeviceType::Other => Self::Other,
+            wgpu::DeviceType::IntegratedGpu => Self::IntegratedGpu,
+            wgpu::DeviceType::DiscreteGpu => Self::DiscreteGpu,
+            wgpu::DeviceType::VirtualGpu => Self::VirtualGpu,
+            wgpu::DeviceType::Cpu => Self::Cpu,
+        }
+    }
+}
+
+/// Limits of the engine's device. They can be lower than the adapter's, since the engine
+/// requests the default limits.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AdapterLimits {
+    pub max_texture_dimension_2d: u32,
+    pub max_texture_dimension_3d: u32,
+    pub max_texture_array_layers: u32,
+    pub max_bind_groups: u32,
+    pub max_color_attachments: u32,
+    pub max_storage_buffer_binding_size: u32,
+    pub max_buffer_size: u64,
+    pub max_compute_invocations_per_workgroup: u32,
+    pub max_binding_array_elements_per_shader_stage: u32,
+}
+
+impl From<wgpu::Limits> for AdapterLimits {
+    fn from(limits: wgpu::Limits) -> Self {
+        Self {
+            max_texture_dimension_2d: limits.max_texture_dimension_2d,
+            max_texture_dimension_3d: limits.max_texture_dimension_3d,
+            max_texture_array_layers: limits.max_texture_array_layers,
+            max_bind_groups: limits.max_bind_groups,
+            max_color_attachments: limits.max_color_attachments,
+            max_storage_buffer_binding_size: limits.max_storage_buffer_binding_size,
+            max_buffer_size: limits.max_buffer_size,
+            max_compute_invocations_per_workgroup: limits.max_compute_invocations_per_workgroup,
+            max_binding_array_elements_per_shader_stage: limits
+                .max_binding_array_elements_per_shader_stage,
+        }
+    }
+}
+
+/// Capabilities the engine can use on this adapter
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AdapterFeatures {
+    /// Sample counts `MsaaConfig.sampleCount` may use; 1 means no MSAA
+    pub msaa_sample_counts: Vec<u32>,
+    /// GPU pass timings are reported by `vulfram_get_profiling`
+    pub timestamp_query: bool,
+    /// Materials may enable `conservativeRaster`
+    pub conservative_rasterization: bool,
+    /// Windows may bind forward textures through one global array (`materialBinding`)
+    pub bindless: bool,
+    /// Forward draws can be culled on the GPU with indirect args
+    pub indirect_draw: bool,
+    pub texture_compression_bc: bool,
+    pub texture_compression_etc2: bool,
+    pub texture_compression_astc: bool,
+}
+
+/// GPU the engine renders with
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AdapterInfo {
+    pub name: String,
+    /// Backend-specific vendor id, usually the PCI vendor id
+    pub vendor: u32,
+    /// Backend-specific device id, usually the PCI device id
+    pub device: u32,
+    pub device_type: AdapterDeviceType,
+    /// "vulkan", "metal", "dx12", "gl" or "webgpu"
+    pub backend: String,
+    pub driver: String,
+    pub driver_info: String,
+    pub limits: AdapterLimits,
+    pub features: AdapterFeatures,
+}
+
+impl AdapterInfo {
+    #[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+    pub fn new(adapter: &wgpu::Adapter, device: &wgpu::Device) -> Self {
+        let info = adapter.get_info();
+        let adapter_features = adapter.features();
+        let device_features = device.features();
+
+        // The forward pass renders to Rgba16Float with a Depth32Float depth buffer
+        let color_samples = adapter
+            .get_texture_format_features(wgpu::TextureFormat::Rgba16Float)
+            .flags;
+        let depth_samples = adapter
+            .get_texture_format_features(wgpu::TextureFormat::Depth32Float)
+            .flags;
+        let msaa_sample_counts = [1, 2, 4, 8, 16]
+            .into_iter()
+            .filter(|&count| {
+                color_samples.sample_count_supported(count)
+                    && depth_samples.sample_count_supported(count)
+            })
+            .collect();
+
+        Self {
+            name: info.name,
+            vendor: info.vendor,
+            device: info.device,
+            device_type: info.device_type.into(),
+            backend: info.backend.to_str().into(),
+            driver: info.driver,
+            driver_info: info.driver_info,
+            limits: device.limits().into(),
+            features: AdapterFeatures {
+                msaa_sample_counts,
+                timestamp_query: device_features.contains(
+                    wgpu::Features::TIMESTAMP_QUERY
+                        | wgpu::Features::TIMESTAMP_QUERY_INSIDE_ENCODERS,
+                ),
+                conservative_rasterization: device_features
+                    .contains(wgpu::Features::CONSERVATIVE_RASTERIZATION),
+                bindless: device_features.contains(wgpu::Features::TEXTURE_BINDING_ARRAY)
+                    && device.limits().max_binding_array_elements_per_shader_stage >= 2,
+                indirect_draw: crate::core::render::state::indirect::indirect_draw_supported(
+                    adapter, device,
+                ),
+                texture_compression_bc: adapter_features
+                    .contains(wgpu::Features::TEXTURE_COMPRESSION_BC),
+                texture_compression_etc2: adapter_features
+                    .contains(wgpu::Features::TEXTURE_COMPRESSION_ETC2),
+                texture_compression_astc: adapter_features
+                    .contains(wgpu::Features::TEXTURE_COMPRESSION_ASTC),
+            },
+        }
+    }
+}
+
+/// Info of the adapter chosen for the first window; `None` before any window exists
+#[cfg(any(not(feature = "wasm"), target_arch = "wasm32"))]
+fn current_adapter_info(engine: &EngineState) -> Option<AdapterInfo> {
+    match (engine.adapter.as_ref(), engine.device.as_ref()) {
+        (Some(adapter), Some(device)) => Some(AdapterInfo::new(adapter, device)),
+        _ => None,
+    }
+}
+
+#[cfg(all(feature = "wasm", not(target_arch = "wasm32")))]
+fn current_adapter_info(_engine: &EngineState) -> Option<AdapterInfo> {
+    None
+}
+
+// MARK: - Get Info
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdAdapterGetInfoArgs {}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultAdapterGetInfo {
+    pub success: bool,
+    pub message: String,
+    pub info: Option<AdapterInfo>,
+}
+
+pub fn engine_cmd_adapter_get_info(
+    engine: &mut EngineState,
+    _args: &CmdAdapterGetInfoArgs,
+) -> CmdResultAdapterGetInfo {
+    match current_adapter_info(engine) {
+        Some(info) => CmdResultAdapterGetInfo {
+            success: true,
+            message: "Adapter info retrieved".into(),
+            info: Some(info),
+        },
+        None => CmdResultAdapterGetInfo {
+            success: false,
+            message: "No GPU adapter yet; it is chosen when the first window is created".into(),
+            info: None,
+        },
+    }
+}
+
+/// Adapter info as MessagePack. Fails with `NoAdapter` before the first window exists.
+pub fn vulfram_get_adapter_info(out_ptr: *mut *const u8, out_length: *mut usize) -> VulframResult {
+    match with_engine(|engine| {
+        let Some(info) = current_adapter_info(engine) else {
+            return VulframResult::NoAdapter;
+        };
+
+        let serialized_data = match rmp_serde::to_vec_named(&info) {
+            Ok(data) => data,
+            Err(_) => return VulframResult::UnknownError,
+        };
+
+        let data_length = serialized_data.len();
+
+        // Transfer ownership via Box::into_raw (zero-copy)
+        let boxed = serialized_data.into_boxed_slice();
+        let ptr = Box::into_raw(boxed) as *mut u8;
+
+        unsafe {
+            *out_ptr = ptr;
+            *out_length = data_length;
+        }
+
+        VulframResult::Success
+    }) {
+        Err(e) => e,
+        Ok(result) => result,
+    }
+}
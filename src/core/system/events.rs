@@ -44,4 +44,61 @@ pub enum SystemEvent {
         total_bytes: u64,
         complete: bool,
     },
+
+    /// Per-tick render statistics, emitted when enabled via `CmdFrameStatsConfigure`.
+    /// Counters are summed across windows and cameras.
+    #[serde(rename_all = "camelCase")]
+    FrameStats {
+        frame_index: u64,
+        /// Wall-clock time spent inside `vulfram_tick`
+        cpu_ms: f32,
+        /// GPU time of the last resolved frame (0 without timestamp queries)
+        gpu_ms: f32,
+        draw_calls: u32,
+        triangles: u64,
+        models_culled: u32,
+        visible_cameras: u32,
+    },
+
+    /// Fixed simulation steps taken this tick, emitted when enabled via `CmdFixedStepConfigure`.
+    /// Run `steps` logic updates of `step_ms` each, then render interpolated by `alpha`.
+    #[serde(rename_all = "camelCase")]
+    FixedStep {
+        steps: u32,
+        step_ms: f32,
+        /// Leftover fraction of a step, in [0, 1)
+        alpha: f32,
+        /// Time discarded because more than `maxSteps` steps were due
+        dropped_ms: f32,
+    },
+
+    /// Shadow pages did not fit in the atlas. Rate-limited to one event every 300 frames.
+    #[serde(rename_all = "camelCase")]
+    ShadowAtlasFull {
+        window_id: u32,
+        /// Pages that took over the tile of a least-recently-used page
+        reused_pages: u32,
+        /// Pages rendered without shadows this frame
+        dropped_pages: u32,
+    },
+
+    /// A geometry was destroyed to keep the vertex allocator within its budget
+    /// (`CmdGeometrySetBudget` with `evict-lru`). Recreate it to draw it again.
+    #[serde(rename_all = "camelCase")]
+    GeometryEvicted {
+        /// Window whose command created the geometry
+        window_id: u32,
+        geometry_id: u32,
+        bytes: u64,
+    },
+
+    /// Texture and geometry memory reached `pressureThresholdBytes` of
+    /// `CmdMemoryBudgetConfigure`. Fires once per crossing; unload assets to get back under.
+    #[serde(rename_all = "camelCase")]
+    MemoryPressure {
+        used_bytes: u64,
+        threshold_bytes: u64,
+        texture_bytes: u64,
+        geometry_bytes: u64,
+    },
 }
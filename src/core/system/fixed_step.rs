@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::cmd::EngineEvent;
+use crate::core::state::EngineState;
+use crate::core::system::SystemEvent;
+
+const DEFAULT_MAX_STEPS: u32 = 5;
+
+// MARK: - State
+
+/// Accumulates tick deltas and splits them into fixed simulation steps
+#[derive(Debug, Clone)]
+pub(crate) struct FixedStepState {
+    /// Step length in milliseconds; 0 keeps simulation on the variable tick delta
+    pub step_ms: f32,
+    /// Steps taken by one tick at most; time beyond that is dropped
+    pub max_steps: u32,
+    accumulator_ms: f64,
+    /// Steps taken by the current tick
+    pub steps: u32,
+}
+
+impl Default for FixedStepState {
+    fn default() -> Self {
+        Self {
+            step_ms: 0.0,
+            max_steps: DEFAULT_MAX_STEPS,
+            accumulator_ms: 0.0,
+            steps: 0,
+        }
+    }
+}
+
+impl FixedStepState {
+    pub fn enabled(&self) -> bool {
+        self.step_ms > 0.0
+    }
+
+    /// Adds `delta_ms` and returns `(steps, dropped_ms)`. When more than `max_steps` fit, the
+    /// extra whole steps are discarded so a slow frame cannot snowball into slower ones.
+    pub fn advance(&mut self, delta_ms: u32) -> (u32, f32) {
+        if !self.enabled() {
+            self.steps = 0;
+            return (0, 0.0);
+        }
+
+        let step = self.step_ms as f64;
+        self.accumulator_ms += delta_ms as f64;
+        let available = (self.accumulator_ms / step).floor();
+        let steps = available.min(self.max_steps as f64);
+        self.accumulator_ms -= steps * step;
+
+        let mut dropped_ms = 0.0;
+        if available > steps {
+            let excess = (available - steps) * step;
+            self.accumulator_ms -= excess;
+            dropped_ms = excess as f32;
+        }
+
+        self.steps = steps as u32;
+        (self.steps, dropped_ms)
+    }
+
+    /// Fraction of a step left in the accumulator, for interpolating between steps
+    pub fn alpha(&self) -> f32 {
+        if !self.enabled() {
+            return 0.0;
+        }
+        (self.accumulator_ms / self.step_ms as f64).clamp(0.0, 1.0) as f32
+    }
+}
+
+/// Splits this tick's delta into fixed steps and reports them with `SystemEvent::FixedStep`
+pub(crate) fn process_fixed_step(engine: &mut EngineState) {
+    let (steps, dropped_ms) = engine.fixed_step.advance(engine.delta_time);
+    if !engine.fixed_step.enabled() {
+        return;
+    }
+
+    engine
+        .event_queue
+        .push(EngineEvent::System(SystemEvent::FixedStep {
+            steps,
+            step_ms: engine.fixed_step.step_ms,
+            alpha: engine.fixed_step.alpha(),
+            dropped_ms,
+        }));
+}
+
+// MARK: - Configure
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdFixedStepConfigureArgs {
+    /// Step length in milliseconds (e.g. 16.667 for 60 Hz); 0 disables fixed stepping
+    pub step_ms: f32,
+    /// Steps one tick may take at most
+    pub max_steps: u32,
+}
+
+impl Default for CmdFixedStepConfigureArgs {
+    fn default() -> Self {
+        Self {
+            step_ms: 0.0,
+            max_steps: DEFAULT_MAX_STEPS,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultFixedStepConfigure {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_fixed_step_configure(
+    engine: &mut EngineState,
+    args: &CmdFixedStepConfigureArgs,
+) -> CmdResultFixedStepConfigure {
+    if !args.step_ms.is_finite() || args.step_ms < 0.0 {
+        return CmdResultFixedStepConfigure {
+            success: false,
+            message: format!("Invalid stepMs {}", args.step_ms),
+        };
+    }
+    if args.max_steps == 0 {
+        return CmdResultFixedStepConfigure {
+            success: false,
+            message: "maxSteps must be at least 1".into(),
+        };
+    }
+
+    // Restart from an empty accumulator so a new rate does not inherit leftover time
+    engine.fixed_step = FixedStepState {
+        step_ms: args.step_ms,
+        max_steps: args.max_steps,
+        ..FixedStepState::default()
+    };
+
+    CmdResultFixedStepConfigure {
+        success: true,
+        message: if engine.fixed_step.enabled() {
+            format!(
+                "Fixed step set to {} ms (max {} steps per tick)",
+                args.step_ms, args.max_steps
+            )
+        } else {
+            "Fixed step disabled".into()
+        },
+    }
+}
@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::cmd::EngineEvent;
+use crate::core::render::state::SharedResources;
+use crate::core::resources::TextureStore;
+use crate::core::state::EngineState;
+use crate::core::system::SystemEvent;
+
+// MARK: - State
+
+/// Limits on the GPU memory of asset textures and geometries
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MemoryBudgetConfig {
+    /// Texture plus geometry bytes that raise `MemoryPressure`; `None` disables the event
+    pub pressure_threshold_bytes: Option<u64>,
+    /// Texture bytes new textures may not push usage past; `None` removes the limit
+    pub max_texture_bytes: Option<u64>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct MemoryBudgetState {
+    pub config: MemoryBudgetConfig,
+    /// Usage was at or above the threshold on the last check, so the event already fired
+    under_pressure: bool,
+}
+
+impl MemoryBudgetState {
+    pub fn set_config(&mut self, config: MemoryBudgetConfig) {
+        self.config = config;
+        // A new threshold is judged on its own, even if the old one had fired
+        self.under_pressure = false;
+    }
+
+    /// Bytes textures may still allocate under `max_texture_bytes`; `None` without a limit
+    pub fn texture_headroom(&self, shared: &SharedResources) -> Option<u64> {
+        self.config
+            .max_texture_bytes
+            .map(|budget| budget.saturating_sub(texture_bytes(&shared.textures().borrow())))
+    }
+}
+
+/// Fails with a budget error when `requested` bytes do not fit in `headroom`
+pub(crate) fn check_texture_headroom(headroom: Option<u64>, requested: u64) -> Result<(), String> {
+    match headroom {
+        Some(headroom) if requested > headroom => Err(format!(
+            "Texture memory budget exceeded: {} bytes requested, {} bytes left",
+            requested, headroom
+        )),
+        _ => Ok(()),
+    }
+}
+
+// MARK: - Usage
+
+/// Theoretical size of a 2D texture with all its mips and layers; drivers may pad it
+pub fn texture_footprint(format: wgpu::TextureFormat, size: wgpu::Extent3d, mips: u32) -> u64 {
+    (0..mips)
+        .map(|level| {
+            format.theoretical_memory_footprint(
+                size.mip_level_size(level, wgpu::TextureDimension::D2),
+            )
+        })
+        .sum()
+}
+
+fn texture_gpu_bytes(texture: &wgpu::Texture) -> u64 {
+    texture_footprint(texture.format(), texture.size(), texture.mip_level_count())
+}
+
+/// Bytes of the shared textures and forward atlas. Render targets are not counted.
+pub(crate) fn texture_bytes(textures: &TextureStore) -> u64 {
+    let standalone: u64 = textures
+        .records
+        .values()
+        .map(|record| texture_gpu_bytes(&record._texture))
+        .sum();
+    let atlas = textures
+        .atlas
+        .as_ref()
+        .map_or(0, |atlas| texture_gpu_bytes(atlas.texture()));
+    standalone + atlas
+}
+
+/// Emits `MemoryPressure` when usage reaches the threshold. It fires once per crossing and
+/// re-arms after usage drops back below the threshold.
+pub(crate) fn process_memory_budget(engine: &mut EngineState) {
+    let Some(threshold) = engine.memory_budget.config.pressure_threshold_bytes else {
+        return;
+    };
+
+    let texture_bytes = texture_bytes(&engine.shared.textures().borrow());
+    let geometry_bytes = engine.shared.vertex_used_bytes();
+    let used_bytes = texture_bytes + geometry_bytes;
+    let under_pressure = used_bytes >= threshold;
+    if under_pressure && !engine.memory_budget.under_pressure {
+        log::warn!(
+            "GPU memory pressure: {} bytes in use, threshold {}",
+            used_bytes,
+            threshold
+        );
+        engine
+            .event_queue
+            .push(EngineEvent::System(SystemEvent::MemoryPressure {
+                used_bytes,
+                threshold_bytes: threshold,
+                texture_bytes,
+                geometry_bytes,
+            }));
+    }
+    engine.memory_budget.under_pressure = under_pressure;
+}
+
+// MARK: - Configure
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdMemoryBudgetConfigureArgs {
+    #[serde(flatten)]
+    pub config: MemoryBudgetConfig,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultMemoryBudgetConfigure {
+    pub success: bool,
+    pub message: String,
+    /// Bytes of the textures when the budget was set
+    pub texture_bytes: u64,
+    /// Bytes of the live geometries when the budget was set
+    pub geometry_bytes: u64,
+}
+
+pub fn engine_cmd_memory_budget_configure(
+    engine: &mut EngineState,
+    args: &CmdMemoryBudgetConfigureArgs,
+) -> CmdResultMemoryBudgetConfigure {
+    if args.config.pressure_threshold_bytes == Some(0) {
+        return CmdResultMemoryBudgetConfigure {
+            success: false,
+            message: "pressureThresholdBytes must be greater than 0".into(),
+            ..Default::default()
+        };
+    }
+
+    engine.memory_budget.set_config(args.config);
+
+    CmdResultMemoryBudgetConfigure {
+        success: true,
+        message: "Memory budget updated".into(),
+        texture_bytes: texture_bytes(&engine.shared.textures().borrow()),
+        geometry_bytes: engine.shared.vertex_used_bytes(),
+    }
+}
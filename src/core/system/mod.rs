@@ -1,5 +1,17 @@
+pub mod adapter;
 pub mod events;
+pub mod fixed_step;
+pub mod memory;
 pub mod notification;
+pub mod queue;
+pub mod stats;
+pub mod subscription;
 
+pub use adapter::*;
 pub use events::SystemEvent;
+pub use fixed_step::*;
+pub use memory::*;
 pub use notification::*;
+pub use queue::*;
+pub use stats::*;
+pub use subscription::*;
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::state::EngineState;
+
+// MARK: - Queue Limits
+
+/// Maximum depth of the host-facing queues. `0` disables the bound.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[serde(default, rename_all = "camelCase")]
+pub struct QueueLimits {
+    /// Commands waiting for the next tick; `vulfram_send_queue` fails with
+    /// `QueueFull` when a batch would exceed it
+    pub max_commands: u32,
+    /// Responses not yet read by the host; the oldest are dropped when exceeded
+    pub max_responses: u32,
+    /// Events produced in a single tick; the oldest are dropped when exceeded
+    pub max_events: u32,
+}
+
+impl Default for QueueLimits {
+    fn default() -> Self {
+        Self {
+            max_commands: 65_536,
+            max_responses: 65_536,
+            max_events: 16_384,
+        }
+    }
+}
+
+impl QueueLimits {
+    pub fn accepts_commands(&self, queued: usize, incoming: usize) -> bool {
+        self.max_commands == 0 || queued + incoming <= self.max_commands as usize
+    }
+}
+
+/// Drops the oldest responses/events above the configured limits
+pub fn enforce_queue_limits(engine: &mut EngineState) {
+    let limits = engine.queue_limits;
+
+    let dropped = trim_oldest(&mut engine.response_queue, limits.max_responses);
+    if dropped > 0 {
+        log::warn!(
+            "Response queue over limit ({}), dropped {} oldest responses",
+            limits.max_responses,
+            dropped
+        );
+        engine.profiling.dropped_responses += dropped;
+    }
+
+    let dropped = trim_oldest(&mut engine.event_queue, limits.max_events);
+    if dropped > 0 {
+        log::warn!(
+            "Event queue over limit ({}), dropped {} oldest events",
+            limits.max_events,
+            dropped
+        );
+        engine.profiling.dropped_events += dropped;
+    }
+}
+
+fn trim_oldest<T>(queue: &mut Vec<T>, max: u32) -> usize {
+    let max = max as usize;
+    if max == 0 || queue.len() <= max {
+        return 0;
+    }
+    let excess = queue.len() - max;
+    queue.drain(..excess);
+    excess
+}
+
+// MARK: - Configure
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdQueueConfigureArgs {
+    pub max_commands: Option<u32>,
+    pub max_responses: Option<u32>,
+    pub max_events: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultQueueConfigure {
+    pub success: bool,
+    pub message: String,
+    /// Limits in effect after the update
+    pub limits: Option<QueueLimits>,
+}
+
+pub fn engine_cmd_queue_configure(
+    engine: &mut EngineState,
+    args: &CmdQueueConfigureArgs,
+) -> CmdResultQueueConfigure {
+    let limits = &mut engine.queue_limits;
+    if let Some(max) = args.max_commands {
+        limits.max_commands = max;
+    }
+    if let Some(max) = args.max_responses {
+        limits.max_responses = max;
+    }
+    if let Some(max) = args.max_events {
+        limits.max_events = max;
+    }
+
+    CmdResultQueueConfigure {
+        success: true,
+        message: "Queue limits updated".into(),
+        limits: Some(*limits),
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::cmd::EngineEvent;
+use crate::core::render::state::DrawStats;
+use crate::core::state::EngineState;
+use crate::core::system::SystemEvent;
+
+// MARK: - Emission
+
+/// Push a `FrameStats` event summarizing the tick that just rendered
+pub(crate) fn emit_frame_stats(engine: &mut EngineState, cpu_ns: u64) {
+    if !engine.frame_stats_enabled {
+        return;
+    }
+
+    let mut stats = DrawStats::default();
+    for window_state in engine.window.states.values() {
+        stats.merge(&window_state.render_state.collector.stats);
+    }
+
+    engine
+        .event_queue
+        .push(EngineEvent::System(SystemEvent::FrameStats {
+            frame_index: engine.frame_index,
+            cpu_ms: cpu_ns as f32 / 1_000_000.0,
+            gpu_ms: engine.profiling.gpu_total_ns as f32 / 1_000_000.0,
+            draw_calls: stats.draw_calls,
+            triangles: stats.triangles,
+            models_culled: stats.models_culled,
+            visible_cameras: stats.visible_cameras,
+        }));
+}
+
+// MARK: - Configure
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdFrameStatsConfigureArgs {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultFrameStatsConfigure {
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn engine_cmd_frame_stats_configure(
+    engine: &mut EngineState,
+    args: &CmdFrameStatsConfigureArgs,
+) -> CmdResultFrameStatsConfigure {
+    engine.frame_stats_enabled = args.enabled;
+
+    CmdResultFrameStatsConfigure {
+        success: true,
+        message: if args.enabled {
+            "Frame stats enabled".into()
+        } else {
+            "Frame stats disabled".into()
+        },
+    }
+}
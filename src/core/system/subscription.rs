@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::cmd::EngineEvent;
+use crate::core::state::EngineState;
+
+// MARK: - Categories
+
+/// Event category bits for `CmdEventSubscriptionSet::mask`
+pub const EVENT_CATEGORY_WINDOW: u32 = 1 << 0;
+pub const EVENT_CATEGORY_POINTER: u32 = 1 << 1;
+pub const EVENT_CATEGORY_KEYBOARD: u32 = 1 << 2;
+pub const EVENT_CATEGORY_GAMEPAD: u32 = 1 << 3;
+pub const EVENT_CATEGORY_SYSTEM: u32 = 1 << 4;
+pub const EVENT_CATEGORY_ALL: u32 = EVENT_CATEGORY_WINDOW
+    | EVENT_CATEGORY_POINTER
+    | EVENT_CATEGORY_KEYBOARD
+    | EVENT_CATEGORY_GAMEPAD
+    | EVENT_CATEGORY_SYSTEM;
+
+fn event_category(event: &EngineEvent) -> u32 {
+    match event {
+        EngineEvent::Window(_) => EVENT_CATEGORY_WINDOW,
+        EngineEvent::Pointer(_) => EVENT_CATEGORY_POINTER,
+        EngineEvent::Keyboard(_) => EVENT_CATEGORY_KEYBOARD,
+        EngineEvent::Gamepad(_) => EVENT_CATEGORY_GAMEPAD,
+        EngineEvent::System(_) => EVENT_CATEGORY_SYSTEM,
+    }
+}
+
+/// Drops events of categories the host did not subscribe to, before they are queued for
+/// `vulfram_receive_events`
+pub(crate) fn apply_event_subscription(engine: &mut EngineState) {
+    let mask = engine.event_subscription;
+    if mask & EVENT_CATEGORY_ALL == EVENT_CATEGORY_ALL {
+        return;
+    }
+    engine
+        .event_queue
+        .retain(|event| mask & event_category(event) != 0);
+}
+
+// MARK: - Configure
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdEventSubscriptionSetArgs {
+    /// Bitwise OR of `EVENT_CATEGORY_*`; categories left out are never emitted
+    pub mask: u32,
+}
+
+impl Default for CmdEventSubscriptionSetArgs {
+    fn default() -> Self {
+        Self {
+            mask: EVENT_CATEGORY_ALL,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultEventSubscriptionSet {
+    pub success: bool,
+    pub message: String,
+    /// Mask in effect, with unknown bits cleared
+    pub mask: u32,
+}
+
+pub fn engine_cmd_event_subscription_set(
+    engine: &mut EngineState,
+    args: &CmdEventSubscriptionSetArgs,
+) -> CmdResultEventSubscriptionSet {
+    let mask = args.mask & EVENT_CATEGORY_ALL;
+    engine.event_subscription = mask;
+
+    CmdResultEventSubscriptionSet {
+        success: true,
+        message: "Event subscription set".into(),
+        mask,
+    }
+}
@@ -13,9 +13,27 @@ use super::singleton::with_engine_singleton;
 /// Main engine tick - processes events and updates state
 pub fn vulfram_tick(time: u64, delta_time: u32) -> VulframResult {
     match with_engine_singleton(|engine| {
+        #[cfg(not(feature = "wasm"))]
+        let tick_start = Instant::now();
+        #[cfg(feature = "wasm")]
+        let tick_start = (Date::now() * 1_000_000.0) as u64;
+
         engine.state.time = time;
         engine.state.delta_time = delta_time;
+        // Browser input arrives between ticks, so it is consumed before the queue resets
+        #[cfg(feature = "wasm")]
+        crate::core::resources::feed_camera_controllers(&mut engine.state);
         engine.state.event_queue.clear();
+        crate::core::logging::flush_pending_logs();
+
+        #[cfg(not(feature = "wasm"))]
+        if let Some(recorder) = engine.state.recorder.as_mut() {
+            recorder.write(&crate::core::trace::TraceEntry::Tick {
+                time,
+                delta_time,
+                cmds: engine.state.cmd_queue.clone(),
+            });
+        }
 
         // Reset profiling counters
         engine.state.profiling.command_processing_ns = 0;
@@ -59,6 +77,8 @@ pub fn vulfram_tick(time: u64, delta_time: u32) -> VulframResult {
         process_audio_listener_binding(&mut engine.state);
         process_audio_source_bindings(&mut engine.state);
         crate::core::resources::process_async_texture_results(&mut engine.state);
+        crate::core::system::process_memory_budget(&mut engine.state);
+        crate::core::render::screenshot::process_screenshots(&mut engine.state);
         let audio_events = engine.state.audio.drain_events();
         for event in audio_events {
             engine
@@ -85,18 +105,59 @@ pub fn vulfram_tick(time: u64, delta_time: u32) -> VulframResult {
         let events_after = engine.state.event_queue.len();
         engine.state.profiling.total_events_dispatched = events_after - events_before;
 
+        // MARK: Fixed Step
+        crate::core::system::process_fixed_step(&mut engine.state);
+
+        // MARK: Camera Controllers
+        #[cfg(not(feature = "wasm"))]
+        crate::core::resources::feed_camera_controllers(&mut engine.state);
+        crate::core::resources::process_camera_controllers(&mut engine.state);
+
+        // MARK: Model Hierarchy
+        crate::core::resources::process_model_hierarchy(&mut engine.state);
+
+        // MARK: Particles
+        crate::core::resources::process_particle_systems(&mut engine.state);
+
         // MARK: Render Frame Lifecycle
         engine.state.frame_index = engine.state.frame_index.wrapping_add(1);
         let frame_index = engine.state.frame_index;
+        engine.state.shared.begin_frame(frame_index);
         for window_state in engine.state.window.states.values_mut() {
             window_state.render_state.begin_frame(frame_index);
         }
 
         // MARK: Request Redraw
         engine.state.profiling.request_redraw_ns = engine.platform.render(&mut engine.state);
+
+        #[cfg(not(feature = "wasm"))]
+        let tick_ns = tick_start.elapsed().as_nanos() as u64;
+        #[cfg(feature = "wasm")]
+        let tick_ns = ((Date::now() * 1_000_000.0) as u64).saturating_sub(tick_start);
+        crate::core::system::emit_frame_stats(&mut engine.state, tick_ns);
+
+        crate::core::system::apply_event_subscription(&mut engine.state);
+        crate::core::system::enforce_queue_limits(&mut engine.state);
         VulframResult::Success
     }) {
         Err(e) => e,
         Ok(result) => result,
     }
 }
+
+/// Blocks until the GPU finished all submitted work. Stalls the pipeline, so hosts call it
+/// only before readbacks or teardown.
+pub fn vulfram_flush() -> VulframResult {
+    match with_engine_singleton(|engine| {
+        match crate::core::render::cmd::flush_device(&engine.state) {
+            Ok(()) => VulframResult::Success,
+            Err(message) => {
+                log::error!("{}", message);
+                VulframResult::GpuFlushFailed
+            }
+        }
+    }) {
+        Err(e) => e,
+        Ok(result) => result,
+    }
+}
@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Write};
+
+use super::VulframResult;
+use super::buffers::state::UploadType;
+use super::cmd::EngineBatchCmds;
+use super::singleton::with_engine;
+
+/// One entry of a command trace, in the order the host produced it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", content = "content", rename_all = "kebab-case")]
+pub enum TraceEntry {
+    Upload {
+        id: u64,
+        upload_type: UploadType,
+        data: Vec<u8>,
+    },
+    Update {
+        id: u64,
+        offset: usize,
+        data: Vec<u8>,
+    },
+    Tick {
+        time: u64,
+        delta_time: u32,
+        cmds: EngineBatchCmds,
+    },
+}
+
+/// Writes every upload and tick to a MessagePack stream
+#[derive(Debug)]
+pub struct TraceRecorder {
+    writer: BufWriter<File>,
+}
+
+impl TraceRecorder {
+    pub fn create(path: &str) -> std::io::Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+        })
+    }
+
+    pub fn write(&mut self, entry: &TraceEntry) {
+        if let Err(e) = rmp_serde::encode::write_named(&mut self.writer, entry) {
+            log::warn!("Failed to write trace entry: {}", e);
+        }
+    }
+
+    pub fn finish(mut self) {
+        if let Err(e) = self.writer.flush() {
+            log::warn!("Failed to flush trace: {}", e);
+        }
+    }
+}
+
+fn read_path<'a>(ptr: *const u8, length: usize) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    std::str::from_utf8(unsafe { std::slice::from_raw_parts(ptr, length) }).ok()
+}
+
+/// Start recording uploads and command batches to `path` (UTF-8).
+/// Call right after `vulfram_init` to capture a complete session.
+pub fn vulfram_record_start(path_ptr: *const u8, path_length: usize) -> VulframResult {
+    let Some(path) = read_path(path_ptr, path_length) else {
+        return VulframResult::TraceIoError;
+    };
+
+    match with_engine(|engine| {
+        let recorder = match TraceRecorder::create(path) {
+            Ok(recorder) => recorder,
+            Err(e) => {
+                log::error!("Failed to create trace {}: {}", path, e);
+                return VulframResult::TraceIoError;
+            }
+        };
+        if let Some(previous) = engine.recorder.replace(recorder) {
+            previous.finish();
+        }
+        VulframResult::Success
+    }) {
+        Err(e) => e,
+        Ok(result) => result,
+    }
+}
+
+/// Stop the active recording and flush it to disk
+pub fn vulfram_record_stop() -> VulframResult {
+    match with_engine(|engine| {
+        if let Some(recorder) = engine.recorder.take() {
+            recorder.finish();
+        }
+        VulframResult::Success
+    }) {
+        Err(e) => e,
+        Ok(result) => result,
+    }
+}
+
+/// Replay a recorded trace: re-uploads buffers and runs every tick with its
+/// recorded `time`/`delta_time`. Responses and events are discarded.
+pub fn vulfram_replay(path_ptr: *const u8, path_length: usize) -> VulframResult {
+    let Some(path) = read_path(path_ptr, path_length) else {
+        return VulframResult::TraceIoError;
+    };
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) => {
+            log::error!("Failed to open trace {}: {}", path, e);
+            return VulframResult::TraceIoError;
+        }
+    };
+    let mut reader = BufReader::new(file);
+
+    loop {
+        let entry = match rmp_serde::from_read::<_, TraceEntry>(&mut reader) {
+            Ok(entry) => entry,
+            Err(rmp_serde::decode::Error::InvalidMarkerRead(e))
+                if e.kind() == ErrorKind::UnexpectedEof =>
+            {
+                return VulframResult::Success;
+            }
+            Err(e) => {
+                log::error!("Invalid trace entry in {}: {}", path, e);
+                return VulframResult::TraceIoError;
+            }
+        };
+
+        let result = match entry {
+            TraceEntry::Upload {
+                id,
+                upload_type,
+                data,
+            } => super::vulfram_upload_buffer(id, upload_type as u32, data.as_ptr(), data.len()),
+            TraceEntry::Update { id, offset, data } => {
+                super::vulfram_update_buffer(id, offset, data.as_ptr(), data.len())
+            }
+            TraceEntry::Tick {
+                time,
+                delta_time,
+                cmds,
+            } => {
+                if let Err(e) = with_engine(|engine| engine.cmd_queue.extend(cmds)) {
+                    return e;
+                }
+                let result = super::vulfram_tick(time, delta_time);
+                if let Err(e) = with_engine(|engine| engine.response_queue.clear()) {
+                    return e;
+                }
+                result
+            }
+        };
+        // Collisions overwrite the buffer, exactly as they did while recording
+        if !matches!(
+            result,
+            VulframResult::Success | VulframResult::BufferIdCollision
+        ) {
+            return result;
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use glam::{IVec2, UVec2};
 use std::collections::HashMap;
 
+use super::EngineWindowState;
+
 /// Cached window state to detect actual changes
 #[derive(Debug, Clone)]
 pub struct WindowStateCache {
@@ -12,6 +14,7 @@ pub struct WindowStateCache {
     pub focused: bool,
     pub occluded: bool,
     pub dark_mode: bool,
+    pub state: EngineWindowState,
 }
 
 impl WindowStateCache {
@@ -25,6 +28,7 @@ impl WindowStateCache {
             focused: false,
             occluded: false,
             dark_mode: false,
+            state: EngineWindowState::Windowed,
         }
     }
 
@@ -26,11 +26,17 @@ use wasm_bindgen_futures::spawn_local;
 #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
 use web_sys::HtmlCanvasElement;
 
+#[cfg(not(feature = "wasm"))]
+use super::apply_window_state;
 use super::{EngineWindowState, window_size_default};
 use crate::core::profiling::gpu::GpuProfiler;
+use crate::core::render::state::{LightCullingConfig, MaterialBindingConfig, SamplerLodConfig};
+use crate::core::resources::{MaterialSampler, TexturePixelFormat};
 use crate::core::state::EngineState;
+#[cfg(not(feature = "wasm"))]
+use crate::core::window::WindowSizeConstraints;
 #[cfg(any(not(feature = "wasm"), all(feature = "wasm", target_arch = "wasm32")))]
-use crate::core::window::WindowState;
+use crate::core::window::{BackgroundRenderConfig, WindowState};
 
 // MARK: - Create Window
 
@@ -54,6 +60,22 @@ pub struct CmdWindowCreateArgs {
     pub transparent: bool,
     #[serde(default)]
     pub initial_state: EngineWindowState,
+    /// Light binning of the window's renderer; fixed for the window's lifetime
+    #[serde(default)]
+    pub light_culling: LightCullingConfig,
+    /// Material texture binding of the window's renderer; fixed for the window's lifetime
+    #[serde(default)]
+    pub material_binding: MaterialBindingConfig,
+    /// Sampler of material texture slots and sprites that do not pick one
+    #[serde(default)]
+    pub default_sampler: MaterialSampler,
+    /// Mip clamps and mip bias of the material samplers; fixed for the window's lifetime
+    #[serde(default)]
+    pub sampler_lod: SamplerLodConfig,
+    /// Surface formats in order of preference; the first one the surface supports is used.
+    /// Without a supported one, the first sRGB format of the surface is picked.
+    #[serde(default)]
+    pub surface_formats: Vec<TexturePixelFormat>,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -61,6 +83,36 @@ pub struct CmdWindowCreateArgs {
 pub struct CmdResultWindowCreate {
     pub success: bool,
     pub message: String,
+    /// Format the surface was configured with; `None` on failure or for a format
+    /// `TexturePixelFormat` does not name
+    pub surface_format: Option<TexturePixelFormat>,
+}
+
+/// First of `preferred` the surface supports, else its first sRGB format
+#[cfg(any(not(feature = "wasm"), all(feature = "wasm", target_arch = "wasm32")))]
+fn pick_surface_format(
+    supported: &[wgpu::TextureFormat],
+    preferred: &[TexturePixelFormat],
+) -> wgpu::TextureFormat {
+    if let Some(format) = preferred
+        .iter()
+        .map(|format| format.to_wgpu())
+        .find(|format| supported.contains(format))
+    {
+        return format;
+    }
+    if !preferred.is_empty() {
+        log::warn!(
+            "None of the surface formats {:?} is supported (supported: {:?}); using the default",
+            preferred,
+            supported
+        );
+    }
+    supported
+        .iter()
+        .copied()
+        .find(|format| format.is_srgb())
+        .unwrap_or(supported[0])
 }
 
 #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
@@ -68,12 +120,26 @@ pub fn engine_cmd_window_create_async(
     args: &CmdWindowCreateArgs,
     cmd_id: u64,
 ) -> Result<(), CmdResultWindowCreate> {
+    if let Err(message) = args
+        .light_culling
+        .validate()
+        .and_then(|_| args.material_binding.validate())
+        .and_then(|_| args.sampler_lod.validate())
+    {
+        return Err(CmdResultWindowCreate {
+            success: false,
+            message,
+            ..Default::default()
+        });
+    }
+
     let canvas_id = match &args.canvas_id {
         Some(id) => id,
         None => {
             return Err(CmdResultWindowCreate {
                 success: false,
                 message: "canvasId is required in wasm mode".into(),
+                ..Default::default()
             });
         }
     };
@@ -84,6 +150,7 @@ pub fn engine_cmd_window_create_async(
             return Err(CmdResultWindowCreate {
                 success: false,
                 message: "Web window not available".into(),
+                ..Default::default()
             });
         }
     };
@@ -93,6 +160,7 @@ pub fn engine_cmd_window_create_async(
             return Err(CmdResultWindowCreate {
                 success: false,
                 message: "Document not available".into(),
+                ..Default::default()
             });
         }
     };
@@ -102,6 +170,7 @@ pub fn engine_cmd_window_create_async(
             return Err(CmdResultWindowCreate {
                 success: false,
                 message: format!("Canvas with id '{}' not found", canvas_id),
+                ..Default::default()
             });
         }
     };
@@ -111,6 +180,7 @@ pub fn engine_cmd_window_create_async(
             return Err(CmdResultWindowCreate {
                 success: false,
                 message: format!("Element '{}' is not a canvas", canvas_id),
+                ..Default::default()
             });
         }
     };
@@ -121,6 +191,11 @@ pub fn engine_cmd_window_create_async(
     canvas.set_height(window_height);
 
     let win_id = args.window_id;
+    let light_culling = args.light_culling.clone();
+    let material_binding = args.material_binding.clone();
+    let default_sampler = args.default_sampler;
+    let sampler_lod = args.sampler_lod.clone();
+    let surface_formats = args.surface_formats.clone();
     let canvas_clone = canvas.clone();
     spawn_local(async move {
         let instance_descriptor = wgpu::InstanceDescriptor {
@@ -135,11 +210,12 @@ pub fn engine_cmd_window_create_async(
                 Ok(surface) => surface,
                 Err(e) => {
                     let _ = with_engine_singleton(|engine| {
-                        engine.state.response_queue.push(CommandResponseEnvelope {
+                        engine.state.complete_async_cmd(CommandResponseEnvelope {
                             id: cmd_id,
                             response: CommandResponse::WindowCreate(CmdResultWindowCreate {
                                 success: false,
                                 message: format!("WGPU create surface error: {}", e),
+                                ..Default::default()
                             }),
                         });
                     });
@@ -158,11 +234,12 @@ pub fn engine_cmd_window_create_async(
             Ok(adapter) => adapter,
             Err(_) => {
                 let _ = with_engine_singleton(|engine| {
-                    engine.state.response_queue.push(CommandResponseEnvelope {
+                    engine.state.complete_async_cmd(CommandResponseEnvelope {
                         id: cmd_id,
                         response: CommandResponse::WindowCreate(CmdResultWindowCreate {
                             success: false,
                             message: "WGPU adapter request error".into(),
+                            ..Default::default()
                         }),
                     });
                 });
@@ -179,6 +256,8 @@ pub fn engine_cmd_window_create_async(
             required_features |=
                 wgpu::Features::TIMESTAMP_QUERY | wgpu::Features::TIMESTAMP_QUERY_INSIDE_ENCODERS;
         }
+        // Optional: materials with `conservativeRaster` fall back to regular rasterization
+        required_features |= adapter_features & wgpu::Features::CONSERVATIVE_RASTERIZATION;
 
         let required_limits = wgpu::Limits::downlevel_webgl2_defaults();
         let (device, queue) = match adapter
@@ -194,11 +273,12 @@ pub fn engine_cmd_window_create_async(
             Ok((device, queue)) => (device, queue),
             Err(e) => {
                 let _ = with_engine_singleton(|engine| {
-                    engine.state.response_queue.push(CommandResponseEnvelope {
+                    engine.state.complete_async_cmd(CommandResponseEnvelope {
                         id: cmd_id,
                         response: CommandResponse::WindowCreate(CmdResultWindowCreate {
                             success: false,
                             message: format!("WGPU device request error: {}", e),
+                            ..Default::default()
                         }),
                     });
                 });
@@ -207,12 +287,7 @@ pub fn engine_cmd_window_create_async(
         };
 
         let caps = surface.get_capabilities(&adapter);
-        let format = caps
-            .formats
-            .iter()
-            .copied()
-            .find(|f| f.is_srgb())
-            .unwrap_or(caps.formats[0]);
+        let format = pick_surface_format(&caps.formats, &surface_formats);
 
         let config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
@@ -228,7 +303,21 @@ pub fn engine_cmd_window_create_async(
         surface.configure(&device, &config);
 
         let mut render_state = crate::core::render::RenderState::new(format);
-        render_state.init(&device, &queue, format);
+        render_state.sampler_lod = sampler_lod;
+        let _ = with_engine_singleton(|engine| {
+            // Each canvas brings its own device, so buffers from an earlier one cannot be shared
+            engine.state.shared.drop_all();
+            render_state.init(
+                &device,
+                &queue,
+                format,
+                &mut engine.state.shared,
+                &light_culling,
+            );
+        });
+        render_state.init_indirect_draw(&adapter, &device, &queue);
+        render_state.init_bindless(&device, &material_binding);
+        render_state.default_sampler = default_sampler;
         render_state.on_resize(&device, window_width, window_height);
 
         let listeners =
@@ -238,6 +327,7 @@ pub fn engine_cmd_window_create_async(
         let _ = with_engine_singleton(|engine| {
             engine.state.wgpu = instance;
             engine.state.caps = Some(caps);
+            engine.state.adapter = Some(adapter);
             engine.state.device = Some(device);
             engine.state.queue = Some(queue);
             engine.state.window.map_window(window_handle.id(), win_id);
@@ -248,6 +338,12 @@ pub fn engine_cmd_window_create_async(
                     surface,
                     config: config.clone(),
                     #[cfg(not(feature = "wasm"))]
+                    size_constraints: WindowSizeConstraints::default(),
+                    #[cfg(not(feature = "wasm"))]
+                    alpha_modes: Vec::new(),
+                    #[cfg(not(feature = "wasm"))]
+                    created_transparent: false,
+                    #[cfg(not(feature = "wasm"))]
                     inner_position: IVec2::ZERO,
                     #[cfg(not(feature = "wasm"))]
                     outer_position: IVec2::ZERO,
@@ -255,6 +351,12 @@ pub fn engine_cmd_window_create_async(
                     outer_size: UVec2::new(window_width, window_height),
                     render_state,
                     is_dirty: true,
+                    minimized: false,
+                    occluded: false,
+                    focused: true,
+                    background: BackgroundRenderConfig::default(),
+                    last_render_time: None,
+                    pending_resize: None,
                     #[cfg(not(feature = "wasm"))]
                     last_present_instant: None,
                     #[cfg(feature = "wasm")]
@@ -271,11 +373,12 @@ pub fn engine_cmd_window_create_async(
                 .push(EngineEvent::Window(WindowEvent::OnCreate {
                     window_id: win_id,
                 }));
-            engine.state.response_queue.push(CommandResponseEnvelope {
+            engine.state.complete_async_cmd(CommandResponseEnvelope {
                 id: cmd_id,
                 response: CommandResponse::WindowCreate(CmdResultWindowCreate {
                     success: true,
                     message: "Canvas window created successfully".into(),
+                    surface_format: TexturePixelFormat::from_wgpu(format),
                 }),
             });
             if gpu_profiling_supported && engine.state.gpu_profiler.is_none() {
@@ -304,6 +407,7 @@ pub fn engine_cmd_window_create(
     CmdResultWindowCreate {
         success: false,
         message: "wasm feature requires the wasm32-unknown-unknown target".into(),
+        ..Default::default()
     }
 }
 
@@ -313,6 +417,19 @@ pub fn engine_cmd_window_create(
     event_loop: &ActiveEventLoop,
     args: &CmdWindowCreateArgs,
 ) -> CmdResultWindowCreate {
+    if let Err(message) = args
+        .light_culling
+        .validate()
+        .and_then(|_| args.material_binding.validate())
+        .and_then(|_| args.sampler_lod.validate())
+    {
+        return CmdResultWindowCreate {
+            success: false,
+            message,
+            ..Default::default()
+        };
+    }
+
     // Ensure minimum valid size
     let window_width = args.size.x.max(100);
     let window_height = args.size.y.max(100);
@@ -337,10 +454,11 @@ pub fn engine_cmd_window_create(
     let window = match event_loop.create_window(win_attrs) {
         Ok(window) => Arc::new(window),
         Err(e) => {
-            println!("Failed to create window: {}", e);
+            log::error!("Failed to create window: {}", e);
             return CmdResultWindowCreate {
                 success: false,
                 message: format!("Winit create window error: {}", e),
+                ..Default::default()
             };
         }
     };
@@ -354,6 +472,7 @@ pub fn engine_cmd_window_create(
             return CmdResultWindowCreate {
                 success: false,
                 message: format!("WGPU create surface error: {}", e),
+                ..Default::default()
             };
         }
     };
@@ -373,6 +492,7 @@ pub fn engine_cmd_window_create(
                     return CmdResultWindowCreate {
                         success: false,
                         message: "WGPU adapter request error".into(),
+                        ..Default::default()
                     };
                 }
             };
@@ -386,12 +506,30 @@ pub fn engine_cmd_window_create(
             required_features |=
                 wgpu::Features::TIMESTAMP_QUERY | wgpu::Features::TIMESTAMP_QUERY_INSIDE_ENCODERS;
         }
+        // Optional: materials with `conservativeRaster` fall back to regular rasterization
+        required_features |= adapter_features & wgpu::Features::CONSERVATIVE_RASTERIZATION;
+        // Optional: the device is shared by every window, so enable bindless textures whenever
+        // the adapter has them; windows opt out through `materialBinding`
+        required_features |= adapter_features & wgpu::Features::TEXTURE_BINDING_ARRAY;
+        // Optional: indirect forward draws start batches at non-zero instances; without it
+        // forward draws stay on `draw_indexed`
+        required_features |= adapter_features & wgpu::Features::INDIRECT_FIRST_INSTANCE;
+        let required_limits = wgpu::Limits {
+            max_binding_array_elements_per_shader_stage: if required_features
+                .contains(wgpu::Features::TEXTURE_BINDING_ARRAY)
+            {
+                adapter.limits().max_binding_array_elements_per_shader_stage
+            } else {
+                0
+            },
+            ..wgpu::Limits::default()
+        };
 
         let (device, queue) = match adapter
             .request_device(&wgpu::DeviceDescriptor {
                 label: None,
                 required_features,
-                required_limits: wgpu::Limits::default(),
+                required_limits,
                 memory_hints: wgpu::MemoryHints::default(),
                 ..Default::default()
             })
@@ -402,6 +540,7 @@ pub fn engine_cmd_window_create(
                 return CmdResultWindowCreate {
                     success: false,
                     message: format!("WGPU device request error: {}", e),
+                    ..Default::default()
                 };
             }
         };
@@ -409,6 +548,7 @@ pub fn engine_cmd_window_create(
         engine.caps = Some(surface.get_capabilities(&adapter));
         engine.device = Some(device);
         engine.queue = Some(queue);
+        engine.adapter = Some(adapter.clone());
         (adapter, true)
     } else {
         // Subsequent windows - validate surface compatibility with existing adapter
@@ -424,6 +564,7 @@ pub fn engine_cmd_window_create(
                 return CmdResultWindowCreate {
                         success: false,
                         message: "Surface is not compatible with existing WGPU adapter. Cannot create window.".into(),
+                        ..Default::default()
                     };
             }
         };
@@ -438,6 +579,7 @@ pub fn engine_cmd_window_create(
                 return CmdResultWindowCreate {
                     success: false,
                     message: "Surface capabilities not initialized".into(),
+                    ..Default::default()
                 };
             }
         }
@@ -451,24 +593,32 @@ pub fn engine_cmd_window_create(
                 return CmdResultWindowCreate {
                     success: false,
                     message: "Surface capabilities not initialized".into(),
+                    ..Default::default()
                 };
             }
         }
     };
 
-    let format = caps
-        .formats
-        .iter()
-        .copied()
-        .find(|f| f.is_srgb())
-        .unwrap_or(caps.formats[0]);
+    let format = pick_surface_format(&caps.formats, &args.surface_formats);
 
-    // Use Opaque alpha mode to prevent window transparency
-    // This ensures the clear color is rendered correctly
-    let alpha_mode = wgpu::CompositeAlphaMode::Auto;
+    // Opaque unless the window asked for transparency and the surface can blend with
+    // the desktop; otherwise the cleared alpha would show up as garbage
+    let transparent_mode = args
+        .transparent
+        .then(|| transparent_alpha_mode(&caps.alpha_modes))
+        .flatten();
+    let alpha_mode = transparent_mode.unwrap_or(wgpu::CompositeAlphaMode::Auto);
+    let alpha_modes = caps.alpha_modes.clone();
+
+    // Copyable surfaces let CmdWindowScreenshot read back the presented frame
+    let usage = if caps.usages.contains(wgpu::TextureUsages::COPY_SRC) {
+        wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC
+    } else {
+        wgpu::TextureUsages::RENDER_ATTACHMENT
+    };
 
     let config = wgpu::SurfaceConfiguration {
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        usage,
         width: window_width,
         height: window_height,
         present_mode: if caps.present_modes.contains(&wgpu::PresentMode::Mailbox) {
@@ -489,6 +639,7 @@ pub fn engine_cmd_window_create(
             return CmdResultWindowCreate {
                 success: false,
                 message: "Graphics device not initialized".into(),
+                ..Default::default()
             };
         }
     };
@@ -499,17 +650,29 @@ pub fn engine_cmd_window_create(
     let outer_position = window.outer_position().unwrap_or_default();
     let inner_size = window.inner_size();
     let outer_size = window.outer_size();
+    let scale_factor = window.scale_factor();
 
     // Create render state and initialize blit resources
     let mut render_state = crate::core::render::RenderState::new(format);
+    render_state.sampler_lod = args.sampler_lod.clone();
     if let Some(device) = &engine.device {
         if let Some(queue) = &engine.queue {
-            render_state.init(device, queue, format);
+            render_state.init(
+                device,
+                queue,
+                format,
+                &mut engine.shared,
+                &args.light_culling,
+            );
+            render_state.init_indirect_draw(&adapter, device, queue);
+            render_state.init_bindless(device, &args.material_binding);
 
             // Initialize size-dependent resources (like depth buffer)
             render_state.on_resize(device, window_width, window_height);
         }
     }
+    render_state.transparent_background = transparent_mode.is_some();
+    render_state.default_sampler = args.default_sampler;
 
     engine.window.insert_state(
         win_id,
@@ -517,12 +680,21 @@ pub fn engine_cmd_window_create(
             window,
             surface,
             config: config.clone(),
+            size_constraints: WindowSizeConstraints::default(),
+            alpha_modes,
+            created_transparent: args.transparent,
             inner_position: IVec2::new(inner_position.x, inner_position.y),
             outer_position: IVec2::new(outer_position.x, outer_position.y),
             inner_size: UVec2::new(inner_size.width, inner_size.height),
             outer_size: UVec2::new(outer_size.width, outer_size.height),
             render_state,
             is_dirty: true,
+            minimized: false,
+            occluded: false,
+            focused: true,
+            background: BackgroundRenderConfig::default(),
+            last_render_time: None,
+            pending_resize: None,
             last_present_instant: None,
             last_frame_delta_ns: 0,
             fps_instant: 0.0,
@@ -541,17 +713,40 @@ pub fn engine_cmd_window_create(
     cache.outer_position = IVec2::new(outer_position.x, outer_position.y);
     cache.inner_size = UVec2::new(inner_size.width, inner_size.height);
     cache.outer_size = UVec2::new(outer_size.width, outer_size.height);
-    cache.scale_factor = 1.0; // Will be updated on first scale factor change event
+    cache.scale_factor = scale_factor;
     cache.focused = false;
     cache.occluded = false;
     cache.dark_mode = false;
+    cache.state = args.initial_state;
+
+    if let Some(window_state) = engine.window.states.get_mut(&win_id) {
+        if let Err(message) = apply_window_state(&window_state.window, args.initial_state) {
+            log::warn!("Initial window state not applied: {}", message);
+        }
+        window_state.minimized = args.initial_state == EngineWindowState::Minimized;
+    }
 
     CmdResultWindowCreate {
         success: true,
         message: "Window created successfully".into(),
+        surface_format: TexturePixelFormat::from_wgpu(format),
     }
 }
 
+/// Composite alpha mode that lets zero-alpha pixels show the desktop, if any.
+/// Premultiplied is preferred since the renderer clears to transparent black.
+#[cfg(not(feature = "wasm"))]
+pub(crate) fn transparent_alpha_mode(
+    modes: &[wgpu::CompositeAlphaMode],
+) -> Option<wgpu::CompositeAlphaMode> {
+    [
+        wgpu::CompositeAlphaMode::PreMultiplied,
+        wgpu::CompositeAlphaMode::PostMultiplied,
+    ]
+    .into_iter()
+    .find(|mode| modes.contains(mode))
+}
+
 // MARK: - Close Window
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
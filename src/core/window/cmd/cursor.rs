@@ -1,3 +1,4 @@
+use glam::Vec2;
 use serde::{Deserialize, Serialize};
 
 #[cfg(not(feature = "wasm"))]
@@ -152,6 +153,72 @@ pub fn engine_cmd_window_set_cursor_grab(
     }
 }
 
+// MARK: - Set Cursor Position
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetCursorPositionArgs {
+    pub window_id: u32,
+    /// Physical pixels from the top-left of the window's client area, like pointer events
+    pub position: Vec2,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetCursorPosition {
+    success: bool,
+    message: String,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_set_cursor_position(
+    engine: &mut EngineState,
+    args: &CmdWindowSetCursorPositionArgs,
+) -> CmdResultWindowSetCursorPosition {
+    if !args.position.is_finite() {
+        return CmdResultWindowSetCursorPosition {
+            success: false,
+            message: "Cursor position must be finite".into(),
+        };
+    }
+
+    match engine.window.states.get(&args.window_id) {
+        Some(window_state) => {
+            let position =
+                winit::dpi::PhysicalPosition::new(args.position.x as f64, args.position.y as f64);
+            // Wayland and mobile platforms refuse to warp the cursor
+            match window_state.window.set_cursor_position(position) {
+                Ok(_) => CmdResultWindowSetCursorPosition {
+                    success: true,
+                    message: "Cursor position set successfully".into(),
+                },
+                Err(e) => CmdResultWindowSetCursorPosition {
+                    success: false,
+                    message: format!("Failed to set cursor position: {:?}", e),
+                },
+            }
+        }
+        None => CmdResultWindowSetCursorPosition {
+            success: false,
+            message: format!("Window with id {} not found", args.window_id),
+        },
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_set_cursor_position(
+    _engine: &mut EngineState,
+    args: &CmdWindowSetCursorPositionArgs,
+) -> CmdResultWindowSetCursorPosition {
+    CmdResultWindowSetCursorPosition {
+        success: false,
+        message: format!(
+            "Cursor position is not supported in wasm (window_id={})",
+            args.window_id
+        ),
+    }
+}
+
 // MARK: - Set Cursor Icon
 
 /// Cursor icon types
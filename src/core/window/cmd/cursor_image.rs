@@ -0,0 +1,147 @@
+use glam::UVec2;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "wasm"))]
+use crate::core::buffers::state::UploadType;
+#[cfg(not(feature = "wasm"))]
+use crate::core::image::ImageDecoder;
+use crate::core::platform::EventLoopProxy;
+#[cfg(not(feature = "wasm"))]
+use crate::core::platform::winit;
+use crate::core::singleton::EngineCustomEvents;
+use crate::core::state::EngineState;
+
+// MARK: - Set Cursor Image
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetCursorImageArgs {
+    pub window_id: u32,
+    /// `ImageData` upload (PNG, JPEG...), or a `Raw` upload of tightly packed RGBA8 pixels
+    pub buffer_id: u64,
+    /// Pixel size of a `Raw` upload; optional for `ImageData`, where it must match the image
+    pub size: Option<UVec2>,
+    /// Pixel inside the image that sits at the pointer position
+    pub hotspot: UVec2,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetCursorImage {
+    pub success: bool,
+    pub message: String,
+}
+
+/// Validates the image and hands it to the event loop, which creates the cursor and
+/// answers later. Returns the response right away when the command fails early.
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_set_cursor_image(
+    engine: &mut EngineState,
+    loop_proxy: &EventLoopProxy<EngineCustomEvents>,
+    cmd_id: u64,
+    args: &CmdWindowSetCursorImageArgs,
+) -> Result<(), CmdResultWindowSetCursorImage> {
+    let fail = |message: String| CmdResultWindowSetCursorImage {
+        success: false,
+        message,
+    };
+
+    if !engine.window.states.contains_key(&args.window_id) {
+        return Err(fail(format!("Window with id {} not found", args.window_id)));
+    }
+
+    // Get and remove buffer (one-shot consumption)
+    let buffer = engine
+        .buffers
+        .remove_upload(args.buffer_id)
+        .ok_or_else(|| fail(format!("Buffer with id {} not found", args.buffer_id)))?;
+
+    let (rgba, size) = match buffer.upload_type {
+        UploadType::Raw => {
+            let size = args
+                .size
+                .ok_or_else(|| fail("Raw cursor pixels require a size".into()))?;
+            let expected = size.x as usize * size.y as usize * 4;
+            if buffer.data.len() != expected {
+                return Err(fail(format!(
+                    "Raw cursor pixels must be {} bytes for {}x{} RGBA8, got {}",
+                    expected,
+                    size.x,
+                    size.y,
+                    buffer.data.len()
+                )));
+            }
+            (buffer.data, size)
+        }
+        UploadType::ImageData => {
+            let image = ImageDecoder::try_decode(&buffer.data).ok_or_else(|| {
+                fail(
+                    "Failed to decode image. Supported formats: PNG, JPEG, WebP, AVIF, EXR, HDR"
+                        .into(),
+                )
+            })?;
+            let size = UVec2::new(image.width, image.height);
+            if args.size.is_some_and(|expected| expected != size) {
+                return Err(fail(format!(
+                    "Cursor image is {}x{}, not the requested size",
+                    size.x, size.y
+                )));
+            }
+            match image.pixels {
+                crate::core::image::ImagePixels::Rgba8(data) => (data, size),
+                crate::core::image::ImagePixels::Rgba16F(_) => {
+                    return Err(fail("Cursor image requires RGBA8 image data".into()));
+                }
+            }
+        }
+        other => {
+            return Err(fail(format!(
+                "Invalid buffer type. Expected ImageData or Raw, got {:?}",
+                other
+            )));
+        }
+    };
+
+    if args.hotspot.cmpge(size).any() {
+        return Err(fail(format!(
+            "Cursor hotspot ({}, {}) is outside the {}x{} image",
+            args.hotspot.x, args.hotspot.y, size.x, size.y
+        )));
+    }
+    let narrow = |value: u32| u16::try_from(value).ok();
+    let (Some(width), Some(height)) = (narrow(size.x), narrow(size.y)) else {
+        return Err(fail("Cursor image is too large".into()));
+    };
+    let source = winit::window::CustomCursor::from_rgba(
+        rgba,
+        width,
+        height,
+        args.hotspot.x as u16,
+        args.hotspot.y as u16,
+    )
+    .map_err(|e| fail(format!("Failed to create cursor: {}", e)))?;
+
+    loop_proxy
+        .send_event(EngineCustomEvents::SetCursorImage(
+            cmd_id,
+            args.window_id,
+            source,
+        ))
+        .map_err(|_| fail("Event loop is closed".into()))
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_set_cursor_image(
+    _engine: &mut EngineState,
+    _loop_proxy: &EventLoopProxy<EngineCustomEvents>,
+    _cmd_id: u64,
+    args: &CmdWindowSetCursorImageArgs,
+) -> Result<(), CmdResultWindowSetCursorImage> {
+    Err(CmdResultWindowSetCursorImage {
+        success: false,
+        message: format!(
+            "Cursor image is not supported in wasm (window_id={})",
+            args.window_id
+        ),
+    })
+}
@@ -207,3 +207,221 @@ pub fn engine_cmd_window_is_resizable(
         content: false,
     }
 }
+
+// MARK: - Set Window Level
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EngineWindowLevel {
+    AlwaysOnBottom,
+    #[default]
+    Normal,
+    AlwaysOnTop,
+}
+
+#[cfg(not(feature = "wasm"))]
+impl From<EngineWindowLevel> for crate::core::platform::winit::window::WindowLevel {
+    fn from(level: EngineWindowLevel) -> Self {
+        match level {
+            EngineWindowLevel::AlwaysOnBottom => Self::AlwaysOnBottom,
+            EngineWindowLevel::Normal => Self::Normal,
+            EngineWindowLevel::AlwaysOnTop => Self::AlwaysOnTop,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetWindowLevelArgs {
+    pub window_id: u32,
+    pub level: EngineWindowLevel,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetWindowLevel {
+    success: bool,
+    message: String,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_set_window_level(
+    engine: &mut EngineState,
+    args: &CmdWindowSetWindowLevelArgs,
+) -> CmdResultWindowSetWindowLevel {
+    match engine.window.states.get(&args.window_id) {
+        Some(window_state) => {
+            window_state.window.set_window_level(args.level.into());
+            CmdResultWindowSetWindowLevel {
+                success: true,
+                message: "Window level set successfully".into(),
+            }
+        }
+        None => CmdResultWindowSetWindowLevel {
+            success: false,
+            message: format!("Window with id {} not found", args.window_id),
+        },
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_set_window_level(
+    _engine: &mut EngineState,
+    args: &CmdWindowSetWindowLevelArgs,
+) -> CmdResultWindowSetWindowLevel {
+    CmdResultWindowSetWindowLevel {
+        success: false,
+        message: format!(
+            "Window level is not supported in wasm (window_id={})",
+            args.window_id
+        ),
+    }
+}
+
+// MARK: - Set Transparent
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetTransparentArgs {
+    pub window_id: u32,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetTransparent {
+    success: bool,
+    message: String,
+    /// Whether the surface now composites with the desktop
+    content: bool,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_set_transparent(
+    engine: &mut EngineState,
+    args: &CmdWindowSetTransparentArgs,
+) -> CmdResultWindowSetTransparent {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => window_state,
+        None => {
+            return CmdResultWindowSetTransparent {
+                success: false,
+                message: format!("Window with id {} not found", args.window_id),
+                content: false,
+            };
+        }
+    };
+    let device = match engine.device.as_ref() {
+        Some(device) => device,
+        None => {
+            return CmdResultWindowSetTransparent {
+                success: false,
+                message: "Graphics device not initialized".into(),
+                content: false,
+            };
+        }
+    };
+
+    let alpha_mode = if args.enabled {
+        match super::create::transparent_alpha_mode(&window_state.alpha_modes) {
+            Some(mode) => mode,
+            None => {
+                return CmdResultWindowSetTransparent {
+                    success: false,
+                    message: "Surface does not support alpha compositing on this platform".into(),
+                    content: false,
+                };
+            }
+        }
+    } else {
+        wgpu::CompositeAlphaMode::Auto
+    };
+
+    window_state.window.set_transparent(args.enabled);
+    window_state.config.alpha_mode = alpha_mode;
+    window_state.surface.configure(device, &window_state.config);
+    window_state.render_state.transparent_background = args.enabled;
+    window_state.is_dirty = true;
+
+    // Most platforms pick the window visual at creation; toggling later only
+    // changes what the surface writes
+    let message = if args.enabled && !window_state.created_transparent {
+        "Transparency enabled; the window was not created with `transparent`, so some platforms keep it opaque".into()
+    } else {
+        "Window transparency set successfully".into()
+    };
+
+    CmdResultWindowSetTransparent {
+        success: true,
+        message,
+        content: args.enabled,
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_set_transparent(
+    _engine: &mut EngineState,
+    args: &CmdWindowSetTransparentArgs,
+) -> CmdResultWindowSetTransparent {
+    CmdResultWindowSetTransparent {
+        success: false,
+        message: format!(
+            "Window transparency is not supported in wasm (window_id={})",
+            args.window_id
+        ),
+        content: false,
+    }
+}
+
+// MARK: - Set Click Through
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetClickThroughArgs {
+    pub window_id: u32,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetClickThrough {
+    success: bool,
+    message: String,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_set_click_through(
+    engine: &mut EngineState,
+    args: &CmdWindowSetClickThroughArgs,
+) -> CmdResultWindowSetClickThrough {
+    match engine.window.states.get(&args.window_id) {
+        Some(window_state) => match window_state.window.set_cursor_hittest(!args.enabled) {
+            Ok(()) => CmdResultWindowSetClickThrough {
+                success: true,
+                message: "Window click-through set successfully".into(),
+            },
+            Err(e) => CmdResultWindowSetClickThrough {
+                success: false,
+                message: format!("Click-through is not supported on this platform: {}", e),
+            },
+        },
+        None => CmdResultWindowSetClickThrough {
+            success: false,
+            message: format!("Window with id {} not found", args.window_id),
+        },
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_set_click_through(
+    _engine: &mut EngineState,
+    args: &CmdWindowSetClickThroughArgs,
+) -> CmdResultWindowSetClickThrough {
+    CmdResultWindowSetClickThrough {
+        success: false,
+        message: format!(
+            "Window click-through is not supported in wasm (window_id={})",
+            args.window_id
+        ),
+    }
+}
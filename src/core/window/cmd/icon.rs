@@ -0,0 +1,163 @@
+#[cfg(not(feature = "wasm"))]
+use crate::core::platform::winit;
+use glam::UVec2;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "wasm"))]
+use crate::core::buffers::state::UploadType;
+#[cfg(not(feature = "wasm"))]
+use crate::core::image::ImageDecoder;
+use crate::core::image::ImageEncoding;
+use crate::core::state::EngineState;
+
+// MARK: - Set Icon
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetIconArgs {
+    pub window_id: u32,
+    pub buffer_id: u64,
+    pub encoding: ImageEncoding,
+    /// Pixel dimensions, required when `encoding` is `raw`
+    pub size: Option<UVec2>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetIcon {
+    success: bool,
+    message: String,
+}
+
+/// Why `window` cannot show a custom icon, or `None` when it can. These platforms take the
+/// icon from the application bundle or desktop entry instead.
+#[cfg(not(feature = "wasm"))]
+fn window_icon_unsupported(window: &winit::window::Window) -> Option<String> {
+    use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+    if cfg!(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "android"
+    )) {
+        return Some(format!(
+            "Window icon is not supported on {}; it comes from the application bundle",
+            std::env::consts::OS
+        ));
+    }
+    if window
+        .window_handle()
+        .is_ok_and(|handle| matches!(handle.as_raw(), RawWindowHandle::Wayland(_)))
+    {
+        return Some(
+            "Window icon is not supported on Wayland; it comes from the desktop entry".into(),
+        );
+    }
+    None
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_set_icon(
+    engine: &mut EngineState,
+    args: &CmdWindowSetIconArgs,
+) -> CmdResultWindowSetIcon {
+    let window = match engine.window.states.get(&args.window_id) {
+        Some(window_state) => window_state.window.clone(),
+        None => {
+            return CmdResultWindowSetIcon {
+                success: false,
+                message: format!("Window with id {} not found", args.window_id),
+            };
+        }
+    };
+
+    // Checked before the buffer is consumed, so the host can still discard it
+    if let Some(message) = window_icon_unsupported(&window) {
+        return CmdResultWindowSetIcon {
+            success: false,
+            message,
+        };
+    }
+
+    // Get and remove buffer (one-shot consumption)
+    let buffer = match engine.buffers.remove_upload(args.buffer_id) {
+        Some(b) => b,
+        None => {
+            return CmdResultWindowSetIcon {
+                success: false,
+                message: format!("Buffer with id {} not found", args.buffer_id),
+            };
+        }
+    };
+
+    // Validate buffer type
+    if buffer.upload_type != UploadType::ImageData {
+        return CmdResultWindowSetIcon {
+            success: false,
+            message: format!(
+                "Invalid buffer type. Expected ImageData, got {:?}",
+                buffer.upload_type
+            ),
+        };
+    }
+
+    let image_buffer = match ImageDecoder::decode(&buffer.data, args.encoding, args.size) {
+        Ok(img) => img,
+        Err(message) => {
+            return CmdResultWindowSetIcon {
+                success: false,
+                message: format!("Failed to decode icon: {}", message),
+            };
+        }
+    };
+
+    let image_data = match image_buffer.pixels {
+        crate::core::image::ImagePixels::Rgba8(data) => data,
+        crate::core::image::ImagePixels::Rgba16F(_) => {
+            return CmdResultWindowSetIcon {
+                success: false,
+                message: "Window icon requires RGBA8 image data".into(),
+            };
+        }
+    };
+
+    // Create winit icon (requires RGBA8 format)
+    let icon =
+        match winit::window::Icon::from_rgba(image_data, image_buffer.width, image_buffer.height) {
+            Ok(icon) => icon,
+            Err(e) => {
+                return CmdResultWindowSetIcon {
+                    success: false,
+                    message: format!("Failed to create icon: {:?}", e),
+                };
+            }
+        };
+
+    // Windows keeps separate title bar (ICON_SMALL) and taskbar (ICON_BIG) icons;
+    // X11 uses the one icon for both
+    #[cfg(target_os = "windows")]
+    {
+        use winit::platform::windows::WindowExtWindows;
+        window.set_taskbar_icon(Some(icon.clone()));
+    }
+    window.set_window_icon(Some(icon));
+
+    CmdResultWindowSetIcon {
+        success: true,
+        message: "Icon set successfully".into(),
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_set_icon(
+    _engine: &mut EngineState,
+    args: &CmdWindowSetIconArgs,
+) -> CmdResultWindowSetIcon {
+    CmdResultWindowSetIcon {
+        success: false,
+        message: format!(
+            "Window icon is not supported in wasm (window_id={})",
+            args.window_id
+        ),
+    }
+}
@@ -3,17 +3,27 @@ use serde::{Deserialize, Serialize};
 mod attention;
 mod create;
 mod cursor;
+mod cursor_image;
 mod decorations;
+mod icon;
 mod properties;
+mod rendering;
+mod screenshot;
+mod size;
 
 pub use attention::*;
 pub use create::*;
 pub use cursor::*;
+pub use cursor_image::*;
 pub use decorations::*;
+pub use icon::*;
 pub use properties::*;
+pub use rendering::*;
+pub use screenshot::*;
+pub use size::*;
 
 // Shared types
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum EngineWindowState {
     Minimized = 0,
@@ -0,0 +1,390 @@
+use glam::{UVec2, Vec4};
+use serde::{Deserialize, Serialize};
+
+use crate::core::render::state::{DynamicResolutionConfig, UpscalerConfig};
+use crate::core::resources::FitMode;
+use crate::core::resources::common::default_layer_mask;
+use crate::core::state::EngineState;
+use crate::core::window::BackgroundRenderConfig;
+
+// MARK: - Set Frame Latency
+
+/// Upper bound for queued frames; swapchains rarely honour more than 3
+#[cfg(not(feature = "wasm"))]
+const MAX_FRAME_LATENCY: u32 = 3;
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetFrameLatencyArgs {
+    pub window_id: u32,
+    pub frames: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetFrameLatency {
+    success: bool,
+    message: String,
+    /// Frame latency applied to the surface
+    content: u32,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_set_frame_latency(
+    engine: &mut EngineState,
+    args: &CmdWindowSetFrameLatencyArgs,
+) -> CmdResultWindowSetFrameLatency {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => window_state,
+        None => {
+            return CmdResultWindowSetFrameLatency {
+                success: false,
+                message: format!("Window with id {} not found", args.window_id),
+                content: 0,
+            };
+        }
+    };
+    let device = match engine.device.as_ref() {
+        Some(device) => device,
+        None => {
+            return CmdResultWindowSetFrameLatency {
+                success: false,
+                message: "Graphics device not initialized".into(),
+                content: 0,
+            };
+        }
+    };
+
+    if args.frames == 0 {
+        return CmdResultWindowSetFrameLatency {
+            success: false,
+            message: "Frame latency must be at least 1".into(),
+            content: window_state.config.desired_maximum_frame_latency,
+        };
+    }
+
+    let frames = args.frames.min(MAX_FRAME_LATENCY);
+    window_state.config.desired_maximum_frame_latency = frames;
+    window_state.surface.configure(device, &window_state.config);
+    window_state.is_dirty = true;
+
+    CmdResultWindowSetFrameLatency {
+        success: true,
+        message: if frames == args.frames {
+            "Frame latency set successfully".into()
+        } else {
+            format!("Frame latency clamped to {}", frames)
+        },
+        content: frames,
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_set_frame_latency(
+    _engine: &mut EngineState,
+    args: &CmdWindowSetFrameLatencyArgs,
+) -> CmdResultWindowSetFrameLatency {
+    CmdResultWindowSetFrameLatency {
+        success: false,
+        message: format!(
+            "Frame latency is not supported in wasm (window_id={})",
+            args.window_id
+        ),
+        content: 0,
+    }
+}
+
+// MARK: - Set Clear Color
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetClearColorArgs {
+    pub window_id: u32,
+    /// Linear RGBA; `None` restores the default background
+    pub color: Option<Vec4>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetClearColor {
+    success: bool,
+    message: String,
+}
+
+pub fn engine_cmd_window_set_clear_color(
+    engine: &mut EngineState,
+    args: &CmdWindowSetClearColorArgs,
+) -> CmdResultWindowSetClearColor {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => window_state,
+        None => {
+            return CmdResultWindowSetClearColor {
+                success: false,
+                message: format!("Window with id {} not found", args.window_id),
+            };
+        }
+    };
+
+    if let Some(color) = args.color
+        && !color.is_finite()
+    {
+        return CmdResultWindowSetClearColor {
+            success: false,
+            message: "Clear color components must be finite".into(),
+        };
+    }
+
+    window_state.render_state.clear_color =
+        args.color.map(|color| color.clamp(Vec4::ZERO, Vec4::ONE));
+    window_state.is_dirty = true;
+
+    CmdResultWindowSetClearColor {
+        success: true,
+        message: "Window clear color set successfully".into(),
+    }
+}
+
+// MARK: - Set Layer Visibility
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmdWindowSetLayerVisibilityArgs {
+    pub window_id: u32,
+    /// Layers drawn in the window; a cleared bit hides that layer from every camera
+    #[serde(default = "default_layer_mask")]
+    pub mask: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetLayerVisibility {
+    success: bool,
+    message: String,
+}
+
+pub fn engine_cmd_window_set_layer_visibility(
+    engine: &mut EngineState,
+    args: &CmdWindowSetLayerVisibilityArgs,
+) -> CmdResultWindowSetLayerVisibility {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => window_state,
+        None => {
+            return CmdResultWindowSetLayerVisibility {
+                success: false,
+                message: format!("Window with id {} not found", args.window_id),
+            };
+        }
+    };
+
+    window_state.render_state.layer_visibility = args.mask;
+    window_state.is_dirty = true;
+
+    CmdResultWindowSetLayerVisibility {
+        success: true,
+        message: "Window layer visibility set successfully".into(),
+    }
+}
+
+// MARK: - Set Fit Mode
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetFitModeArgs {
+    pub window_id: u32,
+    pub fit_mode: FitMode,
+    /// Linear RGBA of the `contain` bars; `None` uses opaque black
+    pub bar_color: Option<Vec4>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetFitMode {
+    success: bool,
+    message: String,
+}
+
+pub fn engine_cmd_window_set_fit_mode(
+    engine: &mut EngineState,
+    args: &CmdWindowSetFitModeArgs,
+) -> CmdResultWindowSetFitMode {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => window_state,
+        None => {
+            return CmdResultWindowSetFitMode {
+                success: false,
+                message: format!("Window with id {} not found", args.window_id),
+            };
+        }
+    };
+
+    if let Some(color) = args.bar_color
+        && !color.is_finite()
+    {
+        return CmdResultWindowSetFitMode {
+            success: false,
+            message: "Bar color components must be finite".into(),
+        };
+    }
+
+    window_state.render_state.fit_mode = args.fit_mode;
+    window_state.render_state.fit_bar_color = args
+        .bar_color
+        .map_or(Vec4::W, |color| color.clamp(Vec4::ZERO, Vec4::ONE));
+    window_state.is_dirty = true;
+
+    CmdResultWindowSetFitMode {
+        success: true,
+        message: "Window fit mode set successfully".into(),
+    }
+}
+
+// MARK: - Set Dynamic Resolution
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetDynamicResolutionArgs {
+    pub window_id: u32,
+    #[serde(flatten)]
+    pub config: DynamicResolutionConfig,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetDynamicResolution {
+    success: bool,
+    message: String,
+    /// Window scale in effect after the change
+    scale: f32,
+}
+
+pub fn engine_cmd_window_set_dynamic_resolution(
+    engine: &mut EngineState,
+    args: &CmdWindowSetDynamicResolutionArgs,
+) -> CmdResultWindowSetDynamicResolution {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => window_state,
+        None => {
+            return CmdResultWindowSetDynamicResolution {
+                success: false,
+                message: format!("Window with id {} not found", args.window_id),
+                scale: 0.0,
+            };
+        }
+    };
+
+    if let Err(message) = args.config.validate() {
+        return CmdResultWindowSetDynamicResolution {
+            success: false,
+            message,
+            scale: window_state.render_state.dynamic_resolution.scale,
+        };
+    }
+
+    let dynamic_resolution = &mut window_state.render_state.dynamic_resolution;
+    if dynamic_resolution.set_config(args.config.clone()) && window_state.pending_resize.is_none() {
+        // Camera targets are rebuilt at the new scale before the next frame
+        let size = UVec2::new(window_state.config.width, window_state.config.height);
+        window_state.request_resize(size);
+    }
+    window_state.is_dirty = true;
+
+    CmdResultWindowSetDynamicResolution {
+        success: true,
+        message: "Window dynamic resolution set successfully".into(),
+        scale: window_state.render_state.dynamic_resolution.scale,
+    }
+}
+
+// MARK: - Set Upscaler
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetUpscalerArgs {
+    pub window_id: u32,
+    #[serde(flatten)]
+    pub config: UpscalerConfig,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetUpscaler {
+    success: bool,
+    message: String,
+}
+
+pub fn engine_cmd_window_set_upscaler(
+    engine: &mut EngineState,
+    args: &CmdWindowSetUpscalerArgs,
+) -> CmdResultWindowSetUpscaler {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => window_state,
+        None => {
+            return CmdResultWindowSetUpscaler {
+                success: false,
+                message: format!("Window with id {} not found", args.window_id),
+            };
+        }
+    };
+
+    if let Err(message) = args.config.validate() {
+        return CmdResultWindowSetUpscaler {
+            success: false,
+            message,
+        };
+    }
+
+    window_state.render_state.upscaler = args.config;
+    window_state.is_dirty = true;
+
+    CmdResultWindowSetUpscaler {
+        success: true,
+        message: "Window upscaler set successfully".into(),
+    }
+}
+
+// MARK: - Set Background Rendering
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetBackgroundRenderingArgs {
+    pub window_id: u32,
+    #[serde(flatten)]
+    pub config: BackgroundRenderConfig,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetBackgroundRendering {
+    success: bool,
+    message: String,
+}
+
+pub fn engine_cmd_window_set_background_rendering(
+    engine: &mut EngineState,
+    args: &CmdWindowSetBackgroundRenderingArgs,
+) -> CmdResultWindowSetBackgroundRendering {
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => window_state,
+        None => {
+            return CmdResultWindowSetBackgroundRendering {
+                success: false,
+                message: format!("Window with id {} not found", args.window_id),
+            };
+        }
+    };
+
+    if let Err(message) = args.config.validate() {
+        return CmdResultWindowSetBackgroundRendering {
+            success: false,
+            message,
+        };
+    }
+
+    window_state.background = args.config;
+    window_state.is_dirty = true;
+
+    CmdResultWindowSetBackgroundRendering {
+        success: true,
+        message: "Window background rendering set successfully".into(),
+    }
+}
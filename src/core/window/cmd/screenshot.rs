@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "wasm"))]
+use crate::core::render::screenshot::DEFAULT_JPEG_QUALITY;
+use crate::core::render::screenshot::ScreenshotFormat;
+use crate::core::state::EngineState;
+
+// MARK: - Screenshot
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowScreenshotArgs {
+    pub window_id: u32,
+    /// Download buffer that receives the encoded image (see `vulfram_download_buffer`)
+    pub buffer_id: u64,
+    pub format: ScreenshotFormat,
+    /// JPEG quality from 1 to 100 (default: 90)
+    pub quality: Option<u8>,
+}
+
+/// Sent once the capture completes (or fails); the image waits in download buffer `buffer_id`
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowScreenshot {
+    pub success: bool,
+    pub message: String,
+    pub format: ScreenshotFormat,
+    pub width: u32,
+    pub height: u32,
+    pub buffer_id: u64,
+    /// Size of the encoded file in the download buffer (0 on failure)
+    pub byte_length: u64,
+}
+
+impl CmdResultWindowScreenshot {
+    fn rejected(args: &CmdWindowScreenshotArgs, message: String) -> Self {
+        Self {
+            success: false,
+            message,
+            format: args.format,
+            buffer_id: args.buffer_id,
+            ..Default::default()
+        }
+    }
+}
+
+/// Queues a capture of the next presented frame. `Ok` defers the response until the image
+/// is encoded; `Err` is the immediate rejection.
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_screenshot(
+    engine: &mut EngineState,
+    cmd_id: u64,
+    args: &CmdWindowScreenshotArgs,
+) -> Result<(), CmdResultWindowScreenshot> {
+    let buffer_taken = engine.buffers.downloads.contains_key(&args.buffer_id)
+        || engine.window.states.values().any(|window_state| {
+            window_state.render_state.screenshot.pending_buffer_id() == Some(args.buffer_id)
+        });
+    if buffer_taken {
+        return Err(CmdResultWindowScreenshot::rejected(
+            args,
+            format!("Download buffer {} is already in use", args.buffer_id),
+        ));
+    }
+
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => window_state,
+        None => {
+            return Err(CmdResultWindowScreenshot::rejected(
+                args,
+                format!("Window with id {} not found", args.window_id),
+            ));
+        }
+    };
+
+    if !window_state
+        .config
+        .usage
+        .contains(wgpu::TextureUsages::COPY_SRC)
+    {
+        return Err(CmdResultWindowScreenshot::rejected(
+            args,
+            "Window surface cannot be copied on this platform".into(),
+        ));
+    }
+
+    if window_state.is_hidden() {
+        return Err(CmdResultWindowScreenshot::rejected(
+            args,
+            format!(
+                "Window {} is minimized or occluded and does not render",
+                args.window_id
+            ),
+        ));
+    }
+
+    let screenshot = &mut window_state.render_state.screenshot;
+    if screenshot.is_busy() {
+        return Err(CmdResultWindowScreenshot::rejected(
+            args,
+            format!(
+                "A screenshot is already pending for window {}",
+                args.window_id
+            ),
+        ));
+    }
+
+    screenshot.request(
+        cmd_id,
+        args.buffer_id,
+        args.format,
+        args.quality.unwrap_or(DEFAULT_JPEG_QUALITY),
+    );
+    window_state.is_dirty = true;
+    Ok(())
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_screenshot(
+    _engine: &mut EngineState,
+    _cmd_id: u64,
+    args: &CmdWindowScreenshotArgs,
+) -> Result<(), CmdResultWindowScreenshot> {
+    Err(CmdResultWindowScreenshot::rejected(
+        args,
+        format!(
+            "Window screenshots are not supported in wasm (window_id={})",
+            args.window_id
+        ),
+    ))
+}
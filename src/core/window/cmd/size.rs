@@ -0,0 +1,386 @@
+#[cfg(not(feature = "wasm"))]
+use crate::core::platform::winit::dpi::PhysicalSize;
+use glam::UVec2;
+use serde::{Deserialize, Serialize};
+
+use crate::core::state::EngineState;
+
+// MARK: - Set Size
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetSizeArgs {
+    pub window_id: u32,
+    pub size: UVec2,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetSize {
+    success: bool,
+    message: String,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_set_size(
+    engine: &mut EngineState,
+    args: &CmdWindowSetSizeArgs,
+) -> CmdResultWindowSetSize {
+    match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => {
+            let clamped = window_state.size_constraints.clamp(args.size);
+            let size = PhysicalSize::new(clamped.x, clamped.y);
+
+            // Some platforms apply the size synchronously and only report it later;
+            // queue the resize so the surface and camera targets follow either way
+            if let Some(applied) = window_state.window.request_inner_size(size) {
+                window_state.request_resize(UVec2::new(applied.width, applied.height));
+            }
+
+            CmdResultWindowSetSize {
+                success: true,
+                message: "Size set successfully".into(),
+            }
+        }
+        None => CmdResultWindowSetSize {
+            success: false,
+            message: format!("Window with id {} not found", args.window_id),
+        },
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_set_size(
+    _engine: &mut EngineState,
+    args: &CmdWindowSetSizeArgs,
+) -> CmdResultWindowSetSize {
+    CmdResultWindowSetSize {
+        success: false,
+        message: format!(
+            "Window size is not supported in browser (window_id={})",
+            args.window_id
+        ),
+    }
+}
+
+// MARK: - Set Size Constraints
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowSetSizeConstraintsArgs {
+    pub window_id: u32,
+    pub min: Option<UVec2>,
+    pub max: Option<UVec2>,
+    /// Width / height ratio to lock the inner size to
+    pub aspect: Option<f32>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowSetSizeConstraints {
+    success: bool,
+    message: String,
+    /// Inner size after applying the constraints
+    content: UVec2,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_set_size_constraints(
+    engine: &mut EngineState,
+    args: &CmdWindowSetSizeConstraintsArgs,
+) -> CmdResultWindowSetSizeConstraints {
+    if args.min.is_some_and(|min| min.cmpeq(UVec2::ZERO).any())
+        || args.max.is_some_and(|max| max.cmpeq(UVec2::ZERO).any())
+    {
+        return CmdResultWindowSetSizeConstraints {
+            success: false,
+            message: "Size constraints must be at least 1x1".into(),
+            content: UVec2::ZERO,
+        };
+    }
+    if let (Some(min), Some(max)) = (args.min, args.max)
+        && min.cmpgt(max).any()
+    {
+        return CmdResultWindowSetSizeConstraints {
+            success: false,
+            message: format!("Minimum size {} exceeds maximum size {}", min, max),
+            content: UVec2::ZERO,
+        };
+    }
+    if args
+        .aspect
+        .is_some_and(|aspect| !aspect.is_finite() || aspect <= 0.0)
+    {
+        return CmdResultWindowSetSizeConstraints {
+            success: false,
+            message: "Aspect ratio must be a positive number".into(),
+            content: UVec2::ZERO,
+        };
+    }
+
+    let window_state = match engine.window.states.get_mut(&args.window_id) {
+        Some(window_state) => window_state,
+        None => {
+            return CmdResultWindowSetSizeConstraints {
+                success: false,
+                message: format!("Window with id {} not found", args.window_id),
+                content: UVec2::ZERO,
+            };
+        }
+    };
+
+    window_state.size_constraints = crate::core::window::WindowSizeConstraints {
+        min: args.min,
+        max: args.max,
+        aspect: args.aspect,
+    };
+    window_state
+        .window
+        .set_min_inner_size(args.min.map(|min| PhysicalSize::new(min.x, min.y)));
+    window_state
+        .window
+        .set_max_inner_size(args.max.map(|max| PhysicalSize::new(max.x, max.y)));
+
+    // Bring the current size inside the new bounds; the resulting resize event
+    // reconfigures the surface
+    let size = window_state.window.inner_size();
+    let current = UVec2::new(size.width, size.height);
+    let clamped = if current.cmpgt(UVec2::ZERO).all() {
+        window_state.size_constraints.clamp(current)
+    } else {
+        current
+    };
+    if clamped != current {
+        let _ = window_state
+            .window
+            .request_inner_size(PhysicalSize::new(clamped.x, clamped.y));
+    }
+
+    CmdResultWindowSetSizeConstraints {
+        success: true,
+        message: "Size constraints set successfully".into(),
+        content: clamped,
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_set_size_constraints(
+    _engine: &mut EngineState,
+    args: &CmdWindowSetSizeConstraintsArgs,
+) -> CmdResultWindowSetSizeConstraints {
+    CmdResultWindowSetSizeConstraints {
+        success: false,
+        message: format!(
+            "Window size constraints are not supported in browser (window_id={})",
+            args.window_id
+        ),
+        content: UVec2::ZERO,
+    }
+}
+
+// MARK: - Get Size
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowGetSizeArgs {
+    pub window_id: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowGetSize {
+    success: bool,
+    message: String,
+    content: UVec2,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_get_size(
+    engine: &EngineState,
+    args: &CmdWindowGetSizeArgs,
+) -> CmdResultWindowGetSize {
+    match engine.window.states.get(&args.window_id) {
+        Some(window_state) => {
+            let size = window_state.window.inner_size();
+            CmdResultWindowGetSize {
+                success: true,
+                message: "Size retrieved successfully".into(),
+                content: UVec2::new(size.width, size.height),
+            }
+        }
+        None => CmdResultWindowGetSize {
+            success: false,
+            message: format!("Window with id {} not found", args.window_id),
+            content: UVec2::new(0, 0),
+        },
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_get_size(
+    _engine: &EngineState,
+    args: &CmdWindowGetSizeArgs,
+) -> CmdResultWindowGetSize {
+    CmdResultWindowGetSize {
+        success: false,
+        message: format!(
+            "Window size is not supported in wasm (window_id={})",
+            args.window_id
+        ),
+        content: UVec2::new(0, 0),
+    }
+}
+
+// MARK: - Get Scale Factor
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowGetScaleFactorArgs {
+    pub window_id: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowGetScaleFactor {
+    success: bool,
+    message: String,
+    /// Physical pixels per logical pixel (UI `pixels_per_point`)
+    content: f64,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_get_scale_factor(
+    engine: &EngineState,
+    args: &CmdWindowGetScaleFactorArgs,
+) -> CmdResultWindowGetScaleFactor {
+    match engine.window.states.get(&args.window_id) {
+        Some(window_state) => CmdResultWindowGetScaleFactor {
+            success: true,
+            message: "Scale factor retrieved successfully".into(),
+            content: window_state.window.scale_factor(),
+        },
+        None => CmdResultWindowGetScaleFactor {
+            success: false,
+            message: format!("Window with id {} not found", args.window_id),
+            content: 1.0,
+        },
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_get_scale_factor(
+    _engine: &EngineState,
+    args: &CmdWindowGetScaleFactorArgs,
+) -> CmdResultWindowGetScaleFactor {
+    CmdResultWindowGetScaleFactor {
+        success: false,
+        message: format!(
+            "Window scale factor is not supported in wasm (window_id={})",
+            args.window_id
+        ),
+        content: 1.0,
+    }
+}
+
+// MARK: - Get Outer Size
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowGetOuterSizeArgs {
+    pub window_id: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowGetOuterSize {
+    success: bool,
+    message: String,
+    content: UVec2,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_get_outer_size(
+    engine: &EngineState,
+    args: &CmdWindowGetOuterSizeArgs,
+) -> CmdResultWindowGetOuterSize {
+    match engine.window.states.get(&args.window_id) {
+        Some(window_state) => {
+            let size = window_state.window.outer_size();
+            CmdResultWindowGetOuterSize {
+                success: true,
+                message: "Outer size retrieved successfully".into(),
+                content: UVec2::new(size.width, size.height),
+            }
+        }
+        None => CmdResultWindowGetOuterSize {
+            success: false,
+            message: format!("Window with id {} not found", args.window_id),
+            content: UVec2::new(0, 0),
+        },
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_get_outer_size(
+    _engine: &EngineState,
+    args: &CmdWindowGetOuterSizeArgs,
+) -> CmdResultWindowGetOuterSize {
+    CmdResultWindowGetOuterSize {
+        success: false,
+        message: format!(
+            "Window outer size is not supported in wasm (window_id={})",
+            args.window_id
+        ),
+        content: UVec2::new(0, 0),
+    }
+}
+
+// MARK: - Get Surface Size
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdWindowGetSurfaceSizeArgs {
+    pub window_id: u32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CmdResultWindowGetSurfaceSize {
+    success: bool,
+    message: String,
+    content: UVec2,
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn engine_cmd_window_get_surface_size(
+    engine: &EngineState,
+    args: &CmdWindowGetSurfaceSizeArgs,
+) -> CmdResultWindowGetSurfaceSize {
+    match engine.window.states.get(&args.window_id) {
+        Some(window_state) => CmdResultWindowGetSurfaceSize {
+            success: true,
+            message: "Surface size retrieved successfully".into(),
+            content: UVec2::new(window_state.config.width, window_state.config.height),
+        },
+        None => CmdResultWindowGetSurfaceSize {
+            success: false,
+            message: format!("Window with id {} not found", args.window_id),
+            content: UVec2::new(0, 0),
+        },
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn engine_cmd_window_get_surface_size(
+    _engine: &EngineState,
+    args: &CmdWindowGetSurfaceSizeArgs,
+) -> CmdResultWindowGetSurfaceSize {
+    CmdResultWindowGetSurfaceSize {
+        success: false,
+        message: format!(
+            "Window surface size is not supported in wasm (window_id={})",
+            args.window_id
+        ),
+        content: UVec2::new(0, 0),
+    }
+}
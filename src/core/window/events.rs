@@ -1,6 +1,8 @@
 use glam::{IVec2, Vec2};
 use serde::{Deserialize, Serialize};
 
+use super::EngineWindowState;
+
 /// Window-related events
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "event", content = "data", rename_all = "kebab-case")]
@@ -46,6 +48,14 @@ pub enum WindowEvent {
     #[serde(rename_all = "camelCase")]
     OnOcclude { window_id: u32, occluded: bool },
 
+    /// Window was minimized, maximized, restored or changed fullscreen mode, by a command
+    /// or by the user
+    #[serde(rename_all = "camelCase")]
+    OnStateChange {
+        window_id: u32,
+        state: EngineWindowState,
+    },
+
     /// Window redraw was requested
     #[serde(rename_all = "camelCase")]
     OnRedrawRequest { window_id: u32 },
@@ -74,3 +84,27 @@ pub enum WindowEvent {
     #[serde(rename_all = "camelCase")]
     OnThemeChange { window_id: u32, dark_mode: bool },
 }
+
+impl WindowEvent {
+    /// Queues a resize or move, replacing the one already queued this tick for the same
+    /// window so drags deliver only the final size/position. Other events are pushed as-is.
+    pub(crate) fn push_coalesced(self, queue: &mut crate::core::cmd::EngineBatchEvents) {
+        use crate::core::cmd::EngineEvent;
+
+        let superseded = |queued: &EngineEvent| match (queued, &self) {
+            (
+                EngineEvent::Window(WindowEvent::OnResize { window_id: a, .. }),
+                WindowEvent::OnResize { window_id: b, .. },
+            )
+            | (
+                EngineEvent::Window(WindowEvent::OnMove { window_id: a, .. }),
+                WindowEvent::OnMove { window_id: b, .. },
+            ) => a == b,
+            _ => false,
+        };
+        if let Some(index) = queue.iter().rposition(superseded) {
+            queue.remove(index);
+        }
+        queue.push(EngineEvent::Window(self));
+    }
+}
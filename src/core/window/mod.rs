@@ -6,5 +6,6 @@ mod state;
 
 pub use cmd::*;
 pub use events::WindowEvent;
-pub use state::WindowManager;
-pub use state::WindowState;
+#[cfg(not(feature = "wasm"))]
+pub use state::WindowSizeConstraints;
+pub use state::{BackgroundRenderConfig, WindowManager, WindowState};
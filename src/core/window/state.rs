@@ -2,6 +2,7 @@ use crate::core::platform::{Window, WindowId};
 #[cfg(not(feature = "wasm"))]
 use glam::IVec2;
 use glam::{UVec2, Vec2};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 #[cfg(not(feature = "wasm"))]
@@ -26,12 +27,29 @@ pub struct WindowState {
     pub config: wgpu::SurfaceConfiguration,
     pub render_state: RenderState,
     #[cfg(not(feature = "wasm"))]
+    pub(crate) size_constraints: WindowSizeConstraints,
+    /// Composite alpha modes the surface supports, captured at creation
+    #[cfg(not(feature = "wasm"))]
+    pub(crate) alpha_modes: Vec<wgpu::CompositeAlphaMode>,
+    /// Whether the native window was created with a transparent visual
+    #[cfg(not(feature = "wasm"))]
+    pub(crate) created_transparent: bool,
+    #[cfg(not(feature = "wasm"))]
     pub inner_position: IVec2,
     #[cfg(not(feature = "wasm"))]
     pub outer_position: IVec2,
     pub inner_size: UVec2,
     pub outer_size: UVec2,
     pub(crate) is_dirty: bool,
+    /// Minimized windows keep processing events but are not rendered
+    pub(crate) minimized: bool,
+    pub(crate) occluded: bool,
+    pub(crate) focused: bool,
+    pub(crate) background: BackgroundRenderConfig,
+    /// Engine time (ms) of the last frame rendered, for the unfocused frame cap
+    pub(crate) last_render_time: Option<u64>,
+    /// Latest surface size requested by a resize, applied right before the next render
+    pub(crate) pending_resize: Option<UVec2>,
     #[cfg(not(feature = "wasm"))]
     pub(crate) last_present_instant: Option<Instant>,
     #[cfg(feature = "wasm")]
@@ -42,6 +60,125 @@ pub struct WindowState {
     pub _web_listeners: Vec<Closure<dyn FnMut(Event)>>,
 }
 
+impl WindowState {
+    /// Queues a surface resize. Rapid resizes collapse into the latest size, so the
+    /// surface and camera targets are rebuilt once per frame instead of once per event.
+    pub(crate) fn request_resize(&mut self, size: UVec2) {
+        self.pending_resize = Some(size);
+        self.is_dirty = true;
+    }
+
+    /// Reconfigures the surface and reallocates every camera target for a queued resize.
+    /// Zero sizes (minimized windows) stay queued until the window is restored.
+    pub(crate) fn apply_pending_resize(&mut self, device: &wgpu::Device) -> bool {
+        let Some(size) = self.pending_resize else {
+            return false;
+        };
+        if size.x == 0 || size.y == 0 {
+            return false;
+        }
+        self.pending_resize = None;
+
+        self.config.width = size.x;
+        self.config.height = size.y;
+        self.surface.configure(device, &self.config);
+        self.render_state.on_resize(device, size.x, size.y);
+        self.is_dirty = true;
+        true
+    }
+
+    /// Minimized, or occluded with `skip_occluded`: the window does not render at all
+    pub(crate) fn is_hidden(&self) -> bool {
+        self.minimized || (self.occluded && self.background.skip_occluded)
+    }
+
+    /// Whether the window renders this frame. Skipped windows still process commands and
+    /// events.
+    pub(crate) fn should_render(&self, time_ms: u64) -> bool {
+        if self.is_hidden() {
+            return false;
+        }
+        if self.focused || self.background.unfocused_fps <= 0.0 {
+            return true;
+        }
+        let interval_ms = 1000.0 / self.background.unfocused_fps;
+        self.last_render_time
+            .is_none_or(|last| time_ms.saturating_sub(last) as f32 >= interval_ms)
+    }
+
+    /// Rebuilds an outdated or lost surface at its current size
+    pub(crate) fn reconfigure_surface(&mut self, device: &wgpu::Device) {
+        self.surface.configure(device, &self.config);
+        self.is_dirty = true;
+    }
+}
+
+/// How a window renders while the user is not looking at it
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BackgroundRenderConfig {
+    /// Skip frames while the window is fully covered or on another workspace
+    pub skip_occluded: bool,
+    /// Frame cap while the window is unfocused; 0 renders every tick
+    pub unfocused_fps: f32,
+}
+
+impl Default for BackgroundRenderConfig {
+    fn default() -> Self {
+        Self {
+            skip_occluded: true,
+            unfocused_fps: 0.0,
+        }
+    }
+}
+
+impl BackgroundRenderConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.unfocused_fps.is_finite() && self.unfocused_fps >= 0.0) {
+            return Err(format!(
+                "unfocusedFps must be 0 or a positive number, got {}",
+                self.unfocused_fps
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Inner-size bounds and aspect lock applied to resizes (physical pixels)
+#[cfg(not(feature = "wasm"))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WindowSizeConstraints {
+    pub min: Option<UVec2>,
+    pub max: Option<UVec2>,
+    /// Width / height
+    pub aspect: Option<f32>,
+}
+
+#[cfg(not(feature = "wasm"))]
+impl WindowSizeConstraints {
+    /// Fits `size` inside the bounds, then locks the aspect ratio while staying within them.
+    /// The result is never smaller than 1x1.
+    pub fn clamp(&self, size: UVec2) -> UVec2 {
+        let min = self.min.unwrap_or(UVec2::ONE).max(UVec2::ONE);
+        let max = self.max.unwrap_or(UVec2::MAX).max(min);
+        let mut clamped = size.clamp(min, max);
+
+        if let Some(aspect) = self.aspect {
+            // Keep the width the user dragged, derive the height, then fall back to
+            // deriving the width when the height leaves its bounds
+            let height = (clamped.x as f32 / aspect).round() as u32;
+            if (min.y..=max.y).contains(&height) {
+                clamped.y = height;
+            } else {
+                clamped.y = height.clamp(min.y, max.y);
+                clamped.x = ((clamped.y as f32 * aspect).round() as u32).clamp(min.x, max.x);
+            }
+        }
+
+        clamped
+    }
+}
+
 /// Aggregates window state, IDs and caches
 pub struct WindowManager {
     pub states: HashMap<u32, WindowState>,
@@ -27,11 +27,77 @@ mod wasm_exports {
         }
     }
 
+    #[wasm_bindgen]
+    pub struct CountResult {
+        count: u32,
+        result: u32,
+    }
+
+    #[wasm_bindgen]
+    impl CountResult {
+        #[wasm_bindgen(getter)]
+        pub fn count(&self) -> u32 {
+            self.count
+        }
+
+        #[wasm_bindgen(getter)]
+        pub fn result(&self) -> u32 {
+            self.result
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn vulfram_abi_version() -> u32 {
+        core::vulfram_abi_version()
+    }
+
+    #[wasm_bindgen]
+    pub fn vulfram_get_info() -> BufferResult {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_get_info(ptr_ptr, length_ptr) as u32;
+        if result != 0 || length == 0 {
+            return BufferResult {
+                buffer: Vec::new(),
+                result,
+            };
+        }
+
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        BufferResult {
+            buffer: boxed.into_vec(),
+            result,
+        }
+    }
+
     #[wasm_bindgen]
     pub fn vulfram_init() -> u32 {
         core::vulfram_init() as u32
     }
 
+    #[wasm_bindgen]
+    pub fn vulfram_init_with_codec(codec: u32) -> u32 {
+        core::vulfram_init_with_codec(codec) as u32
+    }
+
+    #[wasm_bindgen]
+    pub fn vulfram_set_log_callback(callback: Option<js_sys::Function>) -> u32 {
+        core::set_log_callback(callback.map(|callback| {
+            Box::new(move |level: u32, message: &str| {
+                let _ = callback.call2(&JsValue::NULL, &level.into(), &message.into());
+            }) as core::LogCallback
+        })) as u32
+    }
+
+    #[wasm_bindgen]
+    pub fn vulfram_set_log_level(level: u32) -> u32 {
+        core::vulfram_set_log_level(level) as u32
+    }
+
     #[wasm_bindgen]
     pub fn vulfram_dispose() -> u32 {
         core::vulfram_dispose() as u32
@@ -42,6 +108,13 @@ mod wasm_exports {
         core::vulfram_send_queue(data.as_ptr(), data.len()) as u32
     }
 
+    #[wasm_bindgen]
+    pub fn vulfram_pending_command_count() -> CountResult {
+        let mut count: u32 = 0;
+        let result = core::vulfram_pending_command_count(&mut count) as u32;
+        CountResult { count, result }
+    }
+
     #[wasm_bindgen]
     pub fn vulfram_receive_queue() -> BufferResult {
         let mut length: usize = 0;
@@ -93,11 +166,44 @@ mod wasm_exports {
         core::vulfram_upload_buffer(id, upload_type, data.as_ptr(), data.len()) as u32
     }
 
+    #[wasm_bindgen]
+    pub fn vulfram_update_buffer(id: u64, offset: usize, data: &[u8]) -> u32 {
+        core::vulfram_update_buffer(id, offset, data.as_ptr(), data.len()) as u32
+    }
+
+    #[wasm_bindgen]
+    pub fn vulfram_download_buffer(id: u64) -> BufferResult {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_download_buffer(id, ptr_ptr, length_ptr) as u32;
+        if result != 0 || length == 0 {
+            return BufferResult {
+                buffer: Vec::new(),
+                result,
+            };
+        }
+
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        BufferResult {
+            buffer: boxed.into_vec(),
+            result,
+        }
+    }
+
     #[wasm_bindgen]
     pub fn vulfram_tick(time_ms: f64, delta_ms: u32) -> u32 {
         core::vulfram_tick(time_ms as u64, delta_ms) as u32
     }
 
+    #[wasm_bindgen]
+    pub fn vulfram_flush() -> u32 {
+        core::vulfram_flush() as u32
+    }
+
     #[wasm_bindgen]
     pub fn vulfram_get_profiling() -> BufferResult {
         let mut length: usize = 0;
@@ -120,6 +226,29 @@ mod wasm_exports {
             result,
         }
     }
+
+    #[wasm_bindgen]
+    pub fn vulfram_get_adapter_info() -> BufferResult {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_get_adapter_info(ptr_ptr, length_ptr) as u32;
+        if result != 0 || length == 0 {
+            return BufferResult {
+                buffer: Vec::new(),
+                result,
+            };
+        }
+
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        BufferResult {
+            buffer: boxed.into_vec(),
+            result,
+        }
+    }
 }
 
 // ============================================================================
@@ -138,11 +267,79 @@ mod napi_exports {
         pub result: u32,
     }
 
+    #[napi(object)]
+    pub struct CountResult {
+        pub count: u32,
+        pub result: u32,
+    }
+
+    #[napi]
+    pub fn vulfram_abi_version() -> u32 {
+        core::vulfram_abi_version()
+    }
+
+    #[napi]
+    pub fn vulfram_get_info() -> Result<BufferResult> {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_get_info(ptr_ptr, length_ptr) as u32;
+
+        if result != 0 || length == 0 {
+            return Ok(BufferResult {
+                buffer: Buffer::from(vec![]),
+                result,
+            });
+        }
+
+        // Reconstruct Box<[u8]> and convert to Vec (zero-copy)
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        let vec = boxed.into_vec();
+        let buffer = Buffer::from(vec);
+
+        Ok(BufferResult { buffer, result })
+    }
+
     #[napi]
     pub fn vulfram_init() -> u32 {
         core::vulfram_init() as u32
     }
 
+    #[napi]
+    pub fn vulfram_init_with_codec(codec: u32) -> u32 {
+        core::vulfram_init_with_codec(codec) as u32
+    }
+
+    type LogThreadsafeFunction = napi::threadsafe_function::ThreadsafeFunction<
+        (u32, String),
+        (),
+        (u32, String),
+        Status,
+        false,
+        true,
+    >;
+
+    /// Logs are delivered asynchronously on the JS event loop
+    #[napi]
+    pub fn vulfram_set_log_callback(callback: Option<LogThreadsafeFunction>) -> u32 {
+        core::set_log_callback(callback.map(|callback| {
+            Box::new(move |level: u32, message: &str| {
+                callback.call(
+                    (level, message.to_string()),
+                    napi::threadsafe_function::ThreadsafeFunctionCallMode::NonBlocking,
+                );
+            }) as core::LogCallback
+        })) as u32
+    }
+
+    #[napi]
+    pub fn vulfram_set_log_level(level: u32) -> u32 {
+        core::vulfram_set_log_level(level) as u32
+    }
+
     #[napi]
     pub fn vulfram_dispose() -> u32 {
         core::vulfram_dispose() as u32
@@ -155,6 +352,13 @@ mod napi_exports {
         core::vulfram_send_queue(ptr, length) as u32
     }
 
+    #[napi]
+    pub fn vulfram_pending_command_count() -> CountResult {
+        let mut count: u32 = 0;
+        let result = core::vulfram_pending_command_count(&mut count) as u32;
+        CountResult { count, result }
+    }
+
     #[napi]
     pub fn vulfram_receive_queue() -> Result<BufferResult> {
         let mut length: usize = 0;
@@ -212,11 +416,96 @@ mod napi_exports {
         core::vulfram_upload_buffer(id as u64, upload_type, ptr, length) as u32
     }
 
+    #[napi]
+    pub fn vulfram_update_buffer(id: i64, offset: u32, data: Buffer) -> u32 {
+        let ptr = data.as_ptr();
+        let length = data.len();
+        core::vulfram_update_buffer(id as u64, offset as usize, ptr, length) as u32
+    }
+
+    #[napi]
+    pub fn vulfram_download_buffer(id: i64) -> Result<BufferResult> {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_download_buffer(id as u64, ptr_ptr, length_ptr) as u32;
+
+        if result != 0 || length == 0 {
+            return Ok(BufferResult {
+                buffer: Buffer::from(vec![]),
+                result,
+            });
+        }
+
+        // Reconstruct Box<[u8]> and convert to Vec (zero-copy)
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        let vec = boxed.into_vec();
+        let buffer = Buffer::from(vec);
+
+        Ok(BufferResult { buffer, result })
+    }
+
+    #[cfg(feature = "gltf")]
+    #[napi]
+    pub fn vulfram_load_gltf(data: Buffer, options: Buffer) -> Result<BufferResult> {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_load_gltf(
+            data.as_ptr(),
+            data.len(),
+            options.as_ptr(),
+            options.len(),
+            ptr_ptr,
+            length_ptr,
+        ) as u32;
+
+        if result != 0 || length == 0 {
+            return Ok(BufferResult {
+                buffer: Buffer::from(vec![]),
+                result,
+            });
+        }
+
+        // Reconstruct Box<[u8]> and convert to Vec (zero-copy)
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        let vec = boxed.into_vec();
+        let buffer = Buffer::from(vec);
+
+        Ok(BufferResult { buffer, result })
+    }
+
     #[napi]
     pub fn vulfram_tick(time: i64, delta_time: u32) -> u32 {
         core::vulfram_tick(time as u64, delta_time) as u32
     }
 
+    #[napi]
+    pub fn vulfram_flush() -> u32 {
+        core::vulfram_flush() as u32
+    }
+
+    #[napi]
+    pub fn vulfram_record_start(path: String) -> u32 {
+        core::vulfram_record_start(path.as_ptr(), path.len()) as u32
+    }
+
+    #[napi]
+    pub fn vulfram_record_stop() -> u32 {
+        core::vulfram_record_stop() as u32
+    }
+
+    #[napi]
+    pub fn vulfram_replay(path: String) -> u32 {
+        core::vulfram_replay(path.as_ptr(), path.len()) as u32
+    }
+
     #[napi]
     pub fn vulfram_get_profiling() -> Result<BufferResult> {
         let mut length: usize = 0;
@@ -241,6 +530,31 @@ mod napi_exports {
 
         Ok(BufferResult { buffer, result })
     }
+
+    #[napi]
+    pub fn vulfram_get_adapter_info() -> Result<BufferResult> {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_get_adapter_info(ptr_ptr, length_ptr) as u32;
+
+        if result != 0 || length == 0 {
+            return Ok(BufferResult {
+                buffer: Buffer::from(vec![]),
+                result,
+            });
+        }
+
+        // Reconstruct Box<[u8]> and convert to Vec (zero-copy)
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        let vec = boxed.into_vec();
+        let buffer = Buffer::from(vec);
+
+        Ok(BufferResult { buffer, result })
+    }
 }
 
 // ============================================================================
@@ -252,10 +566,50 @@ mod lua_exports {
     use super::core;
     use mlua::prelude::*;
 
+    fn vulfram_abi_version(_: &Lua, _: ()) -> LuaResult<u32> {
+        Ok(core::vulfram_abi_version())
+    }
+
+    fn vulfram_get_info(lua: &Lua, _: ()) -> LuaResult<(LuaString, u32)> {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_get_info(ptr_ptr, length_ptr) as u32;
+
+        if result != 0 || length == 0 {
+            return Ok((lua.create_string(&[])?, result));
+        }
+
+        // Reconstruct Box<[u8]> and let Lua copy (unavoidable)
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        let lua_string = lua.create_string(&boxed)?;
+
+        Ok((lua_string, result))
+    }
+
     fn vulfram_init(_: &Lua, _: ()) -> LuaResult<u32> {
         Ok(core::vulfram_init() as u32)
     }
 
+    fn vulfram_init_with_codec(_: &Lua, codec: u32) -> LuaResult<u32> {
+        Ok(core::vulfram_init_with_codec(codec) as u32)
+    }
+
+    fn vulfram_set_log_callback(_: &Lua, callback: Option<LuaFunction>) -> LuaResult<u32> {
+        Ok(core::set_log_callback(callback.map(|callback| {
+            Box::new(move |level: u32, message: &str| {
+                let _ = callback.call::<()>((level, message));
+            }) as core::LogCallback
+        })) as u32)
+    }
+
+    fn vulfram_set_log_level(_: &Lua, level: u32) -> LuaResult<u32> {
+        Ok(core::vulfram_set_log_level(level) as u32)
+    }
+
     fn vulfram_dispose(_: &Lua, _: ()) -> LuaResult<u32> {
         Ok(core::vulfram_dispose() as u32)
     }
@@ -265,6 +619,12 @@ mod lua_exports {
         Ok(core::vulfram_send_queue(bytes.as_ptr(), bytes.len()) as u32)
     }
 
+    fn vulfram_pending_command_count(_: &Lua, _: ()) -> LuaResult<(u32, u32)> {
+        let mut count: u32 = 0;
+        let result = core::vulfram_pending_command_count(&mut count) as u32;
+        Ok((count, result))
+    }
+
     fn vulfram_receive_queue(lua: &Lua, _: ()) -> LuaResult<(LuaString, u32)> {
         let mut length: usize = 0;
         let mut ptr: *const u8 = std::ptr::null();
@@ -313,10 +673,54 @@ mod lua_exports {
         Ok(core::vulfram_upload_buffer(id as u64, upload_type, bytes.as_ptr(), bytes.len()) as u32)
     }
 
+    fn vulfram_update_buffer(
+        _: &Lua,
+        (id, offset, data): (i64, usize, LuaString),
+    ) -> LuaResult<u32> {
+        let bytes = data.as_bytes();
+        Ok(core::vulfram_update_buffer(id as u64, offset, bytes.as_ptr(), bytes.len()) as u32)
+    }
+
+    fn vulfram_download_buffer(lua: &Lua, id: i64) -> LuaResult<(LuaString, u32)> {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_download_buffer(id as u64, ptr_ptr, length_ptr) as u32;
+
+        if result != 0 || length == 0 {
+            return Ok((lua.create_string(&[])?, result));
+        }
+
+        // Reconstruct Box<[u8]> and let Lua copy (unavoidable)
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        let lua_string = lua.create_string(&boxed)?;
+
+        Ok((lua_string, result))
+    }
+
     fn vulfram_tick(_: &Lua, (time, delta_time): (i64, u32)) -> LuaResult<u32> {
         Ok(core::vulfram_tick(time as u64, delta_time) as u32)
     }
 
+    fn vulfram_flush(_: &Lua, _: ()) -> LuaResult<u32> {
+        Ok(core::vulfram_flush() as u32)
+    }
+
+    fn vulfram_record_start(_: &Lua, path: String) -> LuaResult<u32> {
+        Ok(core::vulfram_record_start(path.as_ptr(), path.len()) as u32)
+    }
+
+    fn vulfram_record_stop(_: &Lua, _: ()) -> LuaResult<u32> {
+        Ok(core::vulfram_record_stop() as u32)
+    }
+
+    fn vulfram_replay(_: &Lua, path: String) -> LuaResult<u32> {
+        Ok(core::vulfram_replay(path.as_ptr(), path.len()) as u32)
+    }
+
     fn vulfram_get_profiling(lua: &Lua, _: ()) -> LuaResult<(LuaString, u32)> {
         let mut length: usize = 0;
         let mut ptr: *const u8 = std::ptr::null();
@@ -337,20 +741,68 @@ mod lua_exports {
         Ok((lua_string, result))
     }
 
+    fn vulfram_get_adapter_info(lua: &Lua, _: ()) -> LuaResult<(LuaString, u32)> {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_get_adapter_info(ptr_ptr, length_ptr) as u32;
+
+        if result != 0 || length == 0 {
+            return Ok((lua.create_string(&[])?, result));
+        }
+
+        // Reconstruct Box<[u8]> and let Lua copy (unavoidable)
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        let lua_string = lua.create_string(&boxed)?;
+
+        Ok((lua_string, result))
+    }
+
     #[mlua::lua_module]
     pub fn vulfram(lua: &Lua) -> LuaResult<LuaTable> {
         let exports = lua.create_table()?;
+        exports.set("abi_version", lua.create_function(vulfram_abi_version)?)?;
+        exports.set("get_info", lua.create_function(vulfram_get_info)?)?;
         exports.set("init", lua.create_function(vulfram_init)?)?;
+        exports.set(
+            "init_with_codec",
+            lua.create_function(vulfram_init_with_codec)?,
+        )?;
         exports.set("dispose", lua.create_function(vulfram_dispose)?)?;
+        exports.set(
+            "set_log_callback",
+            lua.create_function(vulfram_set_log_callback)?,
+        )?;
+        exports.set("set_log_level", lua.create_function(vulfram_set_log_level)?)?;
         exports.set("send_queue", lua.create_function(vulfram_send_queue)?)?;
+        exports.set(
+            "pending_command_count",
+            lua.create_function(vulfram_pending_command_count)?,
+        )?;
         exports.set("receive_queue", lua.create_function(vulfram_receive_queue)?)?;
         exports.set(
             "receive_events",
             lua.create_function(vulfram_receive_events)?,
         )?;
         exports.set("upload_buffer", lua.create_function(vulfram_upload_buffer)?)?;
+        exports.set("update_buffer", lua.create_function(vulfram_update_buffer)?)?;
+        exports.set(
+            "download_buffer",
+            lua.create_function(vulfram_download_buffer)?,
+        )?;
         exports.set("tick", lua.create_function(vulfram_tick)?)?;
+        exports.set("flush", lua.create_function(vulfram_flush)?)?;
+        exports.set("record_start", lua.create_function(vulfram_record_start)?)?;
+        exports.set("record_stop", lua.create_function(vulfram_record_stop)?)?;
+        exports.set("replay", lua.create_function(vulfram_replay)?)?;
         exports.set("get_profiling", lua.create_function(vulfram_get_profiling)?)?;
+        exports.set(
+            "get_adapter_info",
+            lua.create_function(vulfram_get_adapter_info)?,
+        )?;
         Ok(exports)
     }
 }
@@ -364,12 +816,118 @@ mod python_exports {
     use super::core;
     use pyo3::prelude::*;
     use pyo3::types::PyBytes;
+    use std::os::raw::{c_int, c_void};
+
+    /// Core-allocated buffer exposed through the buffer protocol (no copy).
+    /// Accepted by `memoryview`, `msgpack.unpackb` and friends.
+    #[pyclass(frozen)]
+    struct CoreBuffer {
+        data: Box<[u8]>,
+    }
+
+    #[pymethods]
+    impl CoreBuffer {
+        fn __len__(&self) -> usize {
+            self.data.len()
+        }
+
+        unsafe fn __getbuffer__(
+            slf: Bound<'_, Self>,
+            view: *mut pyo3::ffi::Py_buffer,
+            flags: c_int,
+        ) -> PyResult<()> {
+            let data = &slf.get().data;
+            let filled = unsafe {
+                pyo3::ffi::PyBuffer_FillInfo(
+                    view,
+                    slf.as_ptr(),
+                    data.as_ptr() as *mut c_void,
+                    data.len() as pyo3::ffi::Py_ssize_t,
+                    1,
+                    flags,
+                )
+            };
+            if filled == -1 {
+                return Err(PyErr::fetch(slf.py()));
+            }
+            Ok(())
+        }
+
+        unsafe fn __releasebuffer__(&self, _view: *mut pyo3::ffi::Py_buffer) {}
+    }
+
+    fn take_core_buffer(
+        receive: fn(*mut *const u8, *mut usize) -> core::VulframResult,
+    ) -> (CoreBuffer, u32) {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+
+        let result = receive(&mut ptr, &mut length) as u32;
+
+        let data = if result != 0 || length == 0 {
+            Box::default()
+        } else {
+            // Take ownership of the core allocation; freed when Python drops the object
+            unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr as *mut u8, length)) }
+        };
+        (CoreBuffer { data }, result)
+    }
+
+    #[pyfunction]
+    fn vulfram_abi_version() -> u32 {
+        core::vulfram_abi_version()
+    }
+
+    #[pyfunction]
+    fn vulfram_get_info(py: Python) -> PyResult<(Py<PyBytes>, u32)> {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_get_info(ptr_ptr, length_ptr) as u32;
+
+        if result != 0 || length == 0 {
+            return Ok((PyBytes::new(py, &[]).into(), result));
+        }
+
+        // Reconstruct Box<[u8]> and let Python copy (unavoidable)
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        let py_bytes = PyBytes::new(py, &boxed).into();
+
+        Ok((py_bytes, result))
+    }
 
     #[pyfunction]
     fn vulfram_init() -> u32 {
         core::vulfram_init() as u32
     }
 
+    #[pyfunction]
+    fn vulfram_init_with_codec(codec: u32) -> u32 {
+        core::vulfram_init_with_codec(codec) as u32
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (callback=None))]
+    fn vulfram_set_log_callback(callback: Option<Py<PyAny>>) -> u32 {
+        core::set_log_callback(callback.map(|callback| {
+            Box::new(move |level: u32, message: &str| {
+                Python::attach(|py| {
+                    if let Err(e) = callback.call1(py, (level, message)) {
+                        e.print(py);
+                    }
+                });
+            }) as core::LogCallback
+        })) as u32
+    }
+
+    #[pyfunction]
+    fn vulfram_set_log_level(level: u32) -> u32 {
+        core::vulfram_set_log_level(level) as u32
+    }
+
     #[pyfunction]
     fn vulfram_dispose() -> u32 {
         core::vulfram_dispose() as u32
@@ -380,6 +938,13 @@ mod python_exports {
         core::vulfram_send_queue(data.as_ptr(), data.len()) as u32
     }
 
+    #[pyfunction]
+    fn vulfram_pending_command_count() -> (u32, u32) {
+        let mut count: u32 = 0;
+        let result = core::vulfram_pending_command_count(&mut count) as u32;
+        (count, result)
+    }
+
     #[pyfunction]
     fn vulfram_receive_queue(py: Python) -> PyResult<(Py<PyBytes>, u32)> {
         let mut length: usize = 0;
@@ -422,16 +987,74 @@ mod python_exports {
         Ok((py_bytes, result))
     }
 
+    /// Zero-copy variant of `vulfram_receive_queue`
+    #[pyfunction]
+    fn vulfram_receive_queue_buffer() -> (CoreBuffer, u32) {
+        take_core_buffer(core::vulfram_receive_queue)
+    }
+
+    /// Zero-copy variant of `vulfram_receive_events`
+    #[pyfunction]
+    fn vulfram_receive_events_buffer() -> (CoreBuffer, u32) {
+        take_core_buffer(core::vulfram_receive_events)
+    }
+
     #[pyfunction]
     fn vulfram_upload_buffer(id: i64, upload_type: u32, data: &[u8]) -> u32 {
         core::vulfram_upload_buffer(id as u64, upload_type, data.as_ptr(), data.len()) as u32
     }
 
+    #[pyfunction]
+    fn vulfram_update_buffer(id: i64, offset: usize, data: &[u8]) -> u32 {
+        core::vulfram_update_buffer(id as u64, offset, data.as_ptr(), data.len()) as u32
+    }
+
+    #[pyfunction]
+    fn vulfram_download_buffer(py: Python, id: i64) -> PyResult<(Py<PyBytes>, u32)> {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_download_buffer(id as u64, ptr_ptr, length_ptr) as u32;
+
+        if result != 0 || length == 0 {
+            return Ok((PyBytes::new(py, &[]).into(), result));
+        }
+
+        // Reconstruct Box<[u8]> and let Python copy (unavoidable)
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        let py_bytes = PyBytes::new(py, &boxed).into();
+
+        Ok((py_bytes, result))
+    }
+
     #[pyfunction]
     fn vulfram_tick(time: i64, delta_time: u32) -> u32 {
         core::vulfram_tick(time as u64, delta_time) as u32
     }
 
+    #[pyfunction]
+    fn vulfram_flush() -> u32 {
+        core::vulfram_flush() as u32
+    }
+
+    #[pyfunction]
+    fn vulfram_record_start(path: &str) -> u32 {
+        core::vulfram_record_start(path.as_ptr(), path.len()) as u32
+    }
+
+    #[pyfunction]
+    fn vulfram_record_stop() -> u32 {
+        core::vulfram_record_stop() as u32
+    }
+
+    #[pyfunction]
+    fn vulfram_replay(path: &str) -> u32 {
+        core::vulfram_replay(path.as_ptr(), path.len()) as u32
+    }
+
     #[pyfunction]
     fn vulfram_get_profiling(py: Python) -> PyResult<(Py<PyBytes>, u32)> {
         let mut length: usize = 0;
@@ -453,16 +1076,53 @@ mod python_exports {
         Ok((py_bytes, result))
     }
 
+    #[pyfunction]
+    fn vulfram_get_adapter_info(py: Python) -> PyResult<(Py<PyBytes>, u32)> {
+        let mut length: usize = 0;
+        let mut ptr: *const u8 = std::ptr::null();
+        let length_ptr = &mut length as *mut usize;
+        let ptr_ptr = &mut ptr as *mut *const u8;
+
+        let result = core::vulfram_get_adapter_info(ptr_ptr, length_ptr) as u32;
+
+        if result != 0 || length == 0 {
+            return Ok((PyBytes::new(py, &[]).into(), result));
+        }
+
+        // Reconstruct Box<[u8]> and let Python copy (unavoidable)
+        let boxed =
+            unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, length)) };
+        let py_bytes = PyBytes::new(py, &boxed).into();
+
+        Ok((py_bytes, result))
+    }
+
     #[pymodule]
     fn vulfram(module: &Bound<'_, PyModule>) -> PyResult<()> {
+        module.add_class::<CoreBuffer>()?;
+        module.add_function(wrap_pyfunction!(vulfram_abi_version, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_get_info, module)?)?;
         module.add_function(wrap_pyfunction!(vulfram_init, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_init_with_codec, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_set_log_callback, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_set_log_level, module)?)?;
         module.add_function(wrap_pyfunction!(vulfram_dispose, module)?)?;
         module.add_function(wrap_pyfunction!(vulfram_send_queue, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_pending_command_count, module)?)?;
         module.add_function(wrap_pyfunction!(vulfram_receive_queue, module)?)?;
         module.add_function(wrap_pyfunction!(vulfram_receive_events, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_receive_queue_buffer, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_receive_events_buffer, module)?)?;
         module.add_function(wrap_pyfunction!(vulfram_upload_buffer, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_update_buffer, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_download_buffer, module)?)?;
         module.add_function(wrap_pyfunction!(vulfram_tick, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_flush, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_record_start, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_record_stop, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_replay, module)?)?;
         module.add_function(wrap_pyfunction!(vulfram_get_profiling, module)?)?;
+        module.add_function(wrap_pyfunction!(vulfram_get_adapter_info, module)?)?;
         Ok(())
     }
 }
@@ -475,11 +1135,36 @@ mod python_exports {
 mod ffi_exports {
     use super::core;
 
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_abi_version() -> u32 {
+        core::vulfram_abi_version()
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_get_info(out_ptr: *mut *const u8, out_length: *mut usize) -> u32 {
+        core::vulfram_get_info(out_ptr, out_length) as u32
+    }
+
     #[unsafe(no_mangle)]
     pub extern "C" fn vulfram_init() -> u32 {
         core::vulfram_init() as u32
     }
 
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_init_with_codec(codec: u32) -> u32 {
+        core::vulfram_init_with_codec(codec) as u32
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_set_log_callback(callback: Option<core::LogCallbackFn>) -> u32 {
+        core::vulfram_set_log_callback(callback) as u32
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_set_log_level(level: u32) -> u32 {
+        core::vulfram_set_log_level(level) as u32
+    }
+
     #[unsafe(no_mangle)]
     pub extern "C" fn vulfram_dispose() -> u32 {
         core::vulfram_dispose() as u32
@@ -490,6 +1175,11 @@ mod ffi_exports {
         core::vulfram_send_queue(ptr, length) as u32
     }
 
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_pending_command_count(out_count: *mut u32) -> u32 {
+        core::vulfram_pending_command_count(out_count) as u32
+    }
+
     #[unsafe(no_mangle)]
     pub extern "C" fn vulfram_receive_queue(
         out_ptr: *mut *const u8,
@@ -506,6 +1196,11 @@ mod ffi_exports {
         core::vulfram_receive_events(out_ptr, out_length) as u32
     }
 
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_free_buffer(ptr: *const u8, length: usize) -> u32 {
+        core::vulfram_free_buffer(ptr, length) as u32
+    }
+
     #[unsafe(no_mangle)]
     pub extern "C" fn vulfram_upload_buffer(
         bfr_id: u64,
@@ -516,11 +1211,70 @@ mod ffi_exports {
         core::vulfram_upload_buffer(bfr_id, upload_type, bfr_ptr, bfr_length) as u32
     }
 
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_update_buffer(
+        bfr_id: u64,
+        offset: usize,
+        bfr_ptr: *const u8,
+        bfr_length: usize,
+    ) -> u32 {
+        core::vulfram_update_buffer(bfr_id, offset, bfr_ptr, bfr_length) as u32
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_download_buffer(
+        bfr_id: u64,
+        out_ptr: *mut *const u8,
+        out_length: *mut usize,
+    ) -> u32 {
+        core::vulfram_download_buffer(bfr_id, out_ptr, out_length) as u32
+    }
+
+    #[cfg(feature = "gltf")]
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_load_gltf(
+        bytes_ptr: *const u8,
+        bytes_length: usize,
+        options_ptr: *const u8,
+        options_length: usize,
+        out_ptr: *mut *const u8,
+        out_length: *mut usize,
+    ) -> u32 {
+        core::vulfram_load_gltf(
+            bytes_ptr,
+            bytes_length,
+            options_ptr,
+            options_length,
+            out_ptr,
+            out_length,
+        ) as u32
+    }
+
     #[unsafe(no_mangle)]
     pub extern "C" fn vulfram_tick(time: u64, delta_time: u32) -> u32 {
         core::vulfram_tick(time, delta_time) as u32
     }
 
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_flush() -> u32 {
+        core::vulfram_flush() as u32
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_record_start(path_ptr: *const u8, path_length: usize) -> u32 {
+        core::vulfram_record_start(path_ptr, path_length) as u32
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_record_stop() -> u32 {
+        core::vulfram_record_stop() as u32
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_replay(path_ptr: *const u8, path_length: usize) -> u32 {
+        core::vulfram_replay(path_ptr, path_length) as u32
+    }
+
     #[unsafe(no_mangle)]
     pub extern "C" fn vulfram_get_profiling(
         out_ptr: *mut *const u8,
@@ -528,4 +1282,12 @@ mod ffi_exports {
     ) -> u32 {
         core::vulfram_get_profiling(out_ptr, out_length) as u32
     }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn vulfram_get_adapter_info(
+        out_ptr: *mut *const u8,
+        out_length: *mut usize,
+    ) -> u32 {
+        core::vulfram_get_adapter_info(out_ptr, out_length) as u32
+    }
 }
@@ -10,6 +10,7 @@ use crate::core::buffers::state::UploadType;
 use crate::core::cmd::{
     CommandResponse, CommandResponseEnvelope, EngineCmd, EngineCmdEnvelope, EngineEvent,
 };
+use crate::core::image::ImageEncoding;
 use crate::core::input::events::{ElementState, KeyboardEvent};
 use crate::core::render::cmd::CmdRenderGraphSetArgs;
 use crate::core::render::gizmos::{CmdGizmoDrawAabbArgs, CmdGizmoDrawLineArgs};
@@ -19,12 +20,15 @@ use crate::core::render::graph::{
 };
 use crate::core::resources::shadow::{CmdShadowConfigureArgs, ShadowConfig};
 use crate::core::resources::{
-    CameraKind, CmdCameraCreateArgs, CmdCameraUpdateArgs, CmdEnvironmentUpdateArgs,
+    CameraKind, CameraOutput, CmdCameraCreateArgs, CmdCameraLookAtArgs, CmdEnvironmentUpdateArgs,
     CmdGeometryCreateArgs, CmdLightCreateArgs, CmdMaterialCreateArgs, CmdModelCreateArgs,
-    CmdModelUpdateArgs, CmdPoseUpdateArgs, CmdPrimitiveGeometryCreateArgs,
-    CmdTextureCreateFromBufferArgs, EnvironmentConfig, GeometryPrimitiveEntry, LightKind,
-    MaterialKind, MaterialOptions, MaterialSampler, MsaaConfig, PostProcessConfig, PrimitiveShape,
-    SkyboxConfig, SkyboxMode, StandardOptions, TextureCreateMode,
+    CmdModelSetTransformArgs, CmdModelUpdateArgs, CmdParticleSystemCreateArgs, CmdPoseUpdateArgs,
+    CmdPrimitiveGeometryCreateArgs, CmdTextureCreateFromBufferArgs, ComposeBlend, CubeOptions,
+    EnvironmentConfig, FogConfig, GeometryInterleavedEntry, GeometryPrimitiveEntry,
+    GeometryPrimitiveType, InterleavedAttribute, InterleavedLayout, LightKind, MaterialKind,
+    MaterialOptions, MaterialSampler, MsaaConfig, OutlineMode, ParticleBlendMode,
+    ParticleSystemConfig, PostProcessConfig, PrimitiveShape, SkyboxConfig, SkyboxMode,
+    StandardOptions, TextureCreateMode,
 };
 use crate::core::system::events::SystemEvent;
 use crate::core::window::{CmdWindowCloseArgs, CmdWindowCreateArgs, WindowEvent};
@@ -130,13 +134,46 @@ fn create_window(window_id: u32, title: &str) {
 
 fn demo_001(window_id: u32) -> bool {
     let geometry_cube: u32 = 1;
+    let geometry_interleaved_cube: u32 = 2;
     let camera_id: u32 = 1;
     let material_instance: u32 = 10;
     let texture_test: u32 = 20;
     let texture_buffer: u64 = 1;
+    let interleaved_vertex_buffer: u64 = 2;
+    let interleaved_index_buffer: u64 = 3;
+    let geometry_lightmap_quad: u32 = 3;
+    let material_lightmapped: u32 = 11;
+    let quad_buffers: [u64; 5] = [4, 5, 6, 7, 8];
 
     upload_texture("assets/colo_test_texture.png", texture_buffer);
 
+    // Same cube as the primitive one, uploaded as whole vertices: the floor and
+    // the animated cubes must look identical
+    let (cube_vertices, cube_indices) = build_interleaved_cube();
+    upload_buffer(
+        interleaved_vertex_buffer,
+        UploadType::VertexData,
+        &cube_vertices,
+    );
+    upload_buffer(
+        interleaved_index_buffer,
+        UploadType::IndexData,
+        &cube_indices,
+    );
+
+    // Quad whose UV1 covers only the top-left quarter of the lightmap: base
+    // texture and lightmap must show at different scales
+    let quad = build_lightmapped_quad();
+    upload_buffer(quad_buffers[0], UploadType::VertexData, &quad.positions);
+    upload_buffer(quad_buffers[1], UploadType::VertexData, &quad.normals);
+    upload_buffer(quad_buffers[2], UploadType::VertexData, &quad.uv0);
+    upload_buffer(quad_buffers[3], UploadType::VertexData, &quad.uv1);
+    upload_buffer(quad_buffers[4], UploadType::IndexData, &quad.indices);
+    let quad_entry = |primitive_type, buffer_id| GeometryPrimitiveEntry {
+        primitive_type,
+        buffer_id,
+    };
+
     let mut setup_cmds = vec![
         EngineCmd::CmdPrimitiveGeometryCreate(CmdPrimitiveGeometryCreateArgs {
             window_id,
@@ -144,6 +181,41 @@ fn demo_001(window_id: u32) -> bool {
             label: Some("Default Cube".to_string()),
             shape: PrimitiveShape::Cube,
             options: None,
+            overwrite: false,
+        }),
+        EngineCmd::CmdGeometryCreate(CmdGeometryCreateArgs {
+            window_id,
+            geometry_id: geometry_interleaved_cube,
+            label: Some("Interleaved Cube".to_string()),
+            entries: vec![GeometryPrimitiveEntry {
+                primitive_type: GeometryPrimitiveType::Index,
+                buffer_id: interleaved_index_buffer,
+            }],
+            interleaved: Some(GeometryInterleavedEntry {
+                buffer_id: interleaved_vertex_buffer,
+                layout: DemoVertex::layout(),
+            }),
+            overwrite: false,
+            compute_normals: false,
+            compute_tangents: false,
+            retain_cpu_copy: false,
+        }),
+        EngineCmd::CmdGeometryCreate(CmdGeometryCreateArgs {
+            window_id,
+            geometry_id: geometry_lightmap_quad,
+            label: Some("Lightmapped Quad".to_string()),
+            entries: vec![
+                quad_entry(GeometryPrimitiveType::Position, quad_buffers[0]),
+                quad_entry(GeometryPrimitiveType::Normal, quad_buffers[1]),
+                quad_entry(GeometryPrimitiveType::UV, quad_buffers[2]),
+                quad_entry(GeometryPrimitiveType::UV, quad_buffers[3]),
+                quad_entry(GeometryPrimitiveType::Index, quad_buffers[4]),
+            ],
+            interleaved: None,
+            overwrite: false,
+            compute_normals: false,
+            compute_tangents: false,
+            retain_cpu_copy: false,
         }),
         create_camera_cmd(camera_id, "Main Camera", default_camera_transform()),
         create_point_light_cmd(window_id, 2, Vec4::new(0.0, 8.0, 0.0, 1.0)),
@@ -156,6 +228,34 @@ fn demo_001(window_id: u32) -> bool {
             Some(texture_test),
             None,
         ),
+        EngineCmd::CmdMaterialCreate(CmdMaterialCreateArgs {
+            window_id,
+            material_id: material_lightmapped,
+            label: Some("Lightmapped Material".to_string()),
+            kind: MaterialKind::Standard,
+            options: Some(MaterialOptions::Standard(StandardOptions {
+                base_tex_id: Some(texture_test),
+                lightmap_tex_id: Some(texture_test),
+                lightmap_intensity: Some(2.0),
+                ..Default::default()
+            })),
+            overwrite: false,
+        }),
+        EngineCmd::CmdModelCreate(CmdModelCreateArgs {
+            window_id,
+            model_id: 2001,
+            label: Some("Lightmapped Quad".to_string()),
+            geometry_id: geometry_lightmap_quad,
+            material_id: Some(material_lightmapped),
+            transform: Mat4::from_translation(Vec3::new(0.0, 0.0, -7.0)),
+            layer_mask: 0xFFFFFFFF,
+            cast_shadow: false,
+            receive_shadow: false,
+            cast_outline: false,
+            outline_color: Vec4::ZERO,
+            overwrite: false,
+            sort_order: 0,
+        }),
     ];
 
     setup_cmds.push(create_floor_cmd(
@@ -164,7 +264,7 @@ fn demo_001(window_id: u32) -> bool {
         material_instance,
     ));
     let (mut cubes, cube_cmds) =
-        create_instanced_cubes(window_id, geometry_cube, material_instance);
+        create_instanced_cubes(window_id, geometry_interleaved_cube, material_instance);
     setup_cmds.extend(cube_cmds);
     setup_cmds.push(create_shadow_config_cmd(window_id));
 
@@ -201,6 +301,7 @@ fn demo_001(window_id: u32) -> bool {
                 receive_shadow: None,
                 cast_outline: None,
                 outline_color: None,
+                sort_order: None,
             }));
         }
 
@@ -253,11 +354,13 @@ fn demo_002(window_id: u32) -> bool {
                     filter_blur: 0.0,
                     filter_sharpen: 0.1,
                     filter_tonemap_mode: 1,
-                    outline_enabled: false,
-                    outline_strength: 0.0,
+                    outline_enabled: true,
+                    outline_strength: 1.0,
                     outline_threshold: 0.2,
                     outline_width: 1.0,
                     outline_quality: 0.0,
+                    outline_mode: OutlineMode::EdgeDetect,
+                    outline_edge_color: Vec3::new(0.02, 0.02, 0.03),
                     filter_posterize_steps: 0.0,
                     cell_shading: false,
                     ssao_enabled: false,
@@ -272,7 +375,11 @@ fn demo_002(window_id: u32) -> bool {
                     bloom_knee: 0.5,
                     bloom_intensity: 0.8,
                     bloom_scatter: 0.7,
+                    dither_enabled: true,
+                    ..PostProcessConfig::default()
                 },
+                fog: FogConfig::default(),
+                depth_prepass: false,
             },
         }),
         create_camera_cmd(
@@ -312,6 +419,7 @@ fn demo_002(window_id: u32) -> bool {
                 label: Some(label.clone()),
                 shape: *shape,
                 options: None,
+                overwrite: false,
             },
         ));
 
@@ -337,6 +445,8 @@ fn demo_002(window_id: u32) -> bool {
             receive_shadow: true,
             cast_outline: false,
             outline_color: Vec4::ZERO,
+            overwrite: false,
+            sort_order: 0,
         }));
 
         primitive_models.push((model_id, position));
@@ -352,22 +462,12 @@ fn demo_002(window_id: u32) -> bool {
 
         for (index, (model_id, position)) in primitive_models.iter().enumerate() {
             let rotation = time_f * 0.6 + index as f32 * 0.3;
-            frame_cmds.push(EngineCmd::CmdModelUpdate(CmdModelUpdateArgs {
+            frame_cmds.push(EngineCmd::CmdModelSetTransform(CmdModelSetTransformArgs {
                 window_id,
                 model_id: *model_id,
-                label: None,
-                geometry_id: None,
-                material_id: None,
-                transform: Some(
-                    Mat4::from_translation(*position)
-                        * Mat4::from_euler(glam::EulerRot::XYZ, rotation * 0.4, rotation, 0.0)
-                        * Mat4::from_scale(Vec3::splat(1.2)),
-                ),
-                layer_mask: None,
-                cast_shadow: None,
-                receive_shadow: None,
-                cast_outline: None,
-                outline_color: None,
+                translation: *position,
+                rotation_quat: Quat::from_euler(glam::EulerRot::XYZ, rotation * 0.4, rotation, 0.0),
+                scale: Vec3::splat(1.2),
             }));
         }
 
@@ -423,6 +523,11 @@ fn demo_003(window_id: u32) -> bool {
                     buffer_id: 2004,
                 },
             ],
+            interleaved: None,
+            overwrite: false,
+            compute_normals: false,
+            compute_tangents: false,
+            retain_cpu_copy: false,
         }),
         create_camera_cmd(
             camera_id,
@@ -451,6 +556,8 @@ fn demo_003(window_id: u32) -> bool {
             receive_shadow: true,
             cast_outline: false,
             outline_color: Vec4::ZERO,
+            overwrite: false,
+            sort_order: 0,
         }),
         create_shadow_config_cmd(window_id),
     ];
@@ -551,6 +658,27 @@ fn demo_004(window_id: u32) -> bool {
                 ],
                 params: HashMap::new(),
             },
+            RenderGraphNode {
+                node_id: LogicalId::Str("fog_pass".into()),
+                pass_id: "fog".into(),
+                inputs: vec![
+                    LogicalId::Str("hdr_color".into()),
+                    LogicalId::Str("depth".into()),
+                    LogicalId::Str("shadow_atlas".into()),
+                ],
+                outputs: vec![LogicalId::Str("hdr_color".into())],
+                params: HashMap::new(),
+            },
+            RenderGraphNode {
+                node_id: LogicalId::Str("particles_pass".into()),
+                pass_id: "particles".into(),
+                inputs: vec![
+                    LogicalId::Str("hdr_color".into()),
+                    LogicalId::Str("depth".into()),
+                ],
+                outputs: vec![LogicalId::Str("hdr_color".into())],
+                params: HashMap::new(),
+            },
             RenderGraphNode {
                 node_id: LogicalId::Str("outline_pass".into()),
                 pass_id: "outline".into(),
@@ -630,9 +758,24 @@ fn demo_004(window_id: u32) -> bool {
             },
             RenderGraphEdge {
                 from_node_id: LogicalId::Str("forward_pass".into()),
+                to_node_id: LogicalId::Str("fog_pass".into()),
+                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
+            },
+            RenderGraphEdge {
+                from_node_id: LogicalId::Str("fog_pass".into()),
+                to_node_id: LogicalId::Str("particles_pass".into()),
+                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
+            },
+            RenderGraphEdge {
+                from_node_id: LogicalId::Str("particles_pass".into()),
                 to_node_id: LogicalId::Str("bloom_pass".into()),
                 reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
             },
+            RenderGraphEdge {
+                from_node_id: LogicalId::Str("particles_pass".into()),
+                to_node_id: LogicalId::Str("post_pass".into()),
+                reason: Some(RenderGraphEdgeReason::ReadAfterWrite),
+            },
             RenderGraphEdge {
                 from_node_id: LogicalId::Str("bloom_pass".into()),
                 to_node_id: LogicalId::Str("post_pass".into()),
@@ -725,6 +868,8 @@ fn demo_004(window_id: u32) -> bool {
         outline_threshold: 0.0,
         outline_width: 2.0,
         outline_quality: 1.0,
+        outline_mode: OutlineMode::Hull,
+        outline_edge_color: Vec3::ZERO,
         filter_posterize_steps: 1.0,
         cell_shading: false,
         ssao_enabled: true,
@@ -739,6 +884,15 @@ fn demo_004(window_id: u32) -> bool {
         bloom_knee: 0.8,
         bloom_intensity: 1.0,
         bloom_scatter: 1.0,
+        dither_enabled: true,
+        ..PostProcessConfig::default()
+    };
+
+    let fog_config = FogConfig {
+        enabled: true,
+        density: 0.03,
+        height_falloff: 0.2,
+        ..FogConfig::default()
     };
 
     let audio_bytes = load_texture_bytes("assets/audio.wav");
@@ -768,6 +922,8 @@ fn demo_004(window_id: u32) -> bool {
                     cubemap_texture_id: None,
                 },
                 post: post_config.clone(),
+                fog: fog_config.clone(),
+                depth_prepass: false,
             },
         }),
         EngineCmd::CmdRenderGraphSet(CmdRenderGraphSetArgs { window_id, graph }),
@@ -777,6 +933,7 @@ fn demo_004(window_id: u32) -> bool {
             label: Some("Graph Cube".into()),
             shape: PrimitiveShape::Cube,
             options: None,
+            overwrite: false,
         }),
         EngineCmd::CmdPrimitiveGeometryCreate(CmdPrimitiveGeometryCreateArgs {
             window_id,
@@ -784,6 +941,7 @@ fn demo_004(window_id: u32) -> bool {
             label: Some("Audio Emitter".into()),
             shape: PrimitiveShape::Sphere,
             options: None,
+            overwrite: false,
         }),
         create_camera_cmd(
             camera_id,
@@ -792,6 +950,24 @@ fn demo_004(window_id: u32) -> bool {
         ),
         create_point_light_cmd(window_id, 2, Vec4::new(0.0, 5.0, 2.0, 1.0)),
         create_ambient_light_cmd(window_id, 3, Vec4::new(0.3, 0.3, 0.3, 1.0), 0.6),
+        EngineCmd::CmdParticleSystemCreate(CmdParticleSystemCreateArgs {
+            window_id,
+            particle_system_id: 1,
+            label: Some("Graph Sparks".into()),
+            config: ParticleSystemConfig {
+                position: Vec3::new(0.0, -0.5, 1.5),
+                velocity: Vec3::new(0.0, 3.0, 0.0),
+                velocity_spread: 1.2,
+                gravity: Vec3::new(0.0, -4.0, 0.0),
+                color_start: Vec4::new(4.0, 2.0, 0.6, 1.0),
+                color_end: Vec4::new(1.0, 0.2, 0.0, 0.0),
+                blend: ParticleBlendMode::Additive,
+                ..Default::default()
+            },
+            burst: 64,
+            emitting: true,
+            overwrite: false,
+        }),
         create_standard_material_cmd(
             window_id,
             material_id,
@@ -836,6 +1012,8 @@ fn demo_004(window_id: u32) -> bool {
             receive_shadow: true,
             cast_outline: true,
             outline_color: cube_models[0].2,
+            overwrite: false,
+            sort_order: 0,
         }),
         EngineCmd::CmdModelCreate(CmdModelCreateArgs {
             window_id,
@@ -849,6 +1027,8 @@ fn demo_004(window_id: u32) -> bool {
             receive_shadow: true,
             cast_outline: true,
             outline_color: cube_models[1].2,
+            overwrite: false,
+            sort_order: 0,
         }),
         EngineCmd::CmdModelCreate(CmdModelCreateArgs {
             window_id,
@@ -862,6 +1042,8 @@ fn demo_004(window_id: u32) -> bool {
             receive_shadow: true,
             cast_outline: true,
             outline_color: cube_models[2].2,
+            overwrite: false,
+            sort_order: 0,
         }),
         EngineCmd::CmdModelCreate(CmdModelCreateArgs {
             window_id,
@@ -875,6 +1057,8 @@ fn demo_004(window_id: u32) -> bool {
             receive_shadow: true,
             cast_outline: true,
             outline_color: cube_models[3].2,
+            overwrite: false,
+            sort_order: 0,
         }),
         EngineCmd::CmdModelCreate(CmdModelCreateArgs {
             window_id,
@@ -888,6 +1072,8 @@ fn demo_004(window_id: u32) -> bool {
             receive_shadow: true,
             cast_outline: true,
             outline_color: cube_models[4].2,
+            overwrite: false,
+            sort_order: 0,
         }),
         EngineCmd::CmdModelCreate(CmdModelCreateArgs {
             window_id,
@@ -901,6 +1087,8 @@ fn demo_004(window_id: u32) -> bool {
             receive_shadow: true,
             cast_outline: true,
             outline_color: cube_models[5].2,
+            overwrite: false,
+            sort_order: 0,
         }),
         EngineCmd::CmdModelCreate(CmdModelCreateArgs {
             window_id,
@@ -914,6 +1102,8 @@ fn demo_004(window_id: u32) -> bool {
             receive_shadow: false,
             cast_outline: false,
             outline_color: Vec4::ZERO,
+            overwrite: false,
+            sort_order: 0,
         }),
         EngineCmd::CmdModelCreate(CmdModelCreateArgs {
             window_id,
@@ -927,6 +1117,8 @@ fn demo_004(window_id: u32) -> bool {
             receive_shadow: true,
             cast_outline: false,
             outline_color: Vec4::ZERO,
+            overwrite: false,
+            sort_order: 0,
         }),
         create_floor_cmd(window_id, geometry_id, floor_material_id),
         create_shadow_config_cmd(window_id),
@@ -1009,18 +1201,13 @@ fn demo_004(window_id: u32) -> bool {
                 camera_height,
                 camera_radius * camera_angle.sin(),
             );
+            // The audio listener model follows the camera
             let camera_transform = Mat4::look_at_rh(camera_pos, Vec3::ZERO, Vec3::Y).inverse();
-            cmds.push(EngineCmd::CmdCameraUpdate(CmdCameraUpdateArgs {
+            cmds.push(EngineCmd::CmdCameraLookAt(CmdCameraLookAtArgs {
                 camera_id,
-                label: None,
-                transform: Some(camera_transform),
-                kind: None,
-                flags: None,
-                near_far: None,
-                layer_mask: None,
-                order: None,
-                view_position: None,
-                ortho_scale: None,
+                eye: camera_pos,
+                target: Vec3::ZERO,
+                up: Vec3::Y,
             }));
             cmds.push(EngineCmd::CmdModelUpdate(CmdModelUpdateArgs {
                 window_id,
@@ -1034,6 +1221,7 @@ fn demo_004(window_id: u32) -> bool {
                 receive_shadow: None,
                 cast_outline: None,
                 outline_color: None,
+                sort_order: None,
             }));
             if let Ok(mut slot) = skybox_bytes.lock() {
                 if let Some(bytes) = slot.take() {
@@ -1047,6 +1235,10 @@ fn demo_004(window_id: u32) -> bool {
                             srgb: Some(false),
                             mode: TextureCreateMode::Standalone,
                             atlas_options: None,
+                            overwrite: false,
+                            encoding: ImageEncoding::Auto,
+                            size: None,
+                            retain_cpu_copy: false,
                         },
                     ));
                     cmds.push(EngineCmd::CmdEnvironmentUpdate(CmdEnvironmentUpdateArgs {
@@ -1066,6 +1258,8 @@ fn demo_004(window_id: u32) -> bool {
                                 cubemap_texture_id: Some(skybox_texture_id),
                             },
                             post: post_config.clone(),
+                            fog: fog_config.clone(),
+                            depth_prepass: false,
                         },
                     }));
                 }
@@ -1093,6 +1287,7 @@ fn demo_004(window_id: u32) -> bool {
                     receive_shadow: None,
                     cast_outline: None,
                     outline_color: None,
+                    sort_order: None,
                 }));
             }
             {
@@ -1158,6 +1353,12 @@ fn create_camera_cmd(camera_id: u32, label: &str, transform: Mat4) -> EngineCmd
         order: 0,
         view_position: None,
         ortho_scale: 10.0,
+        auto_aspect: true,
+        aspect: None,
+        compose_blend: ComposeBlend::Overwrite,
+        output: CameraOutput::Window,
+        render_scale: 1.0,
+        overwrite: false,
     })
 }
 
@@ -1172,10 +1373,15 @@ fn create_point_light_cmd(window_id: u32, light_id: u32, position: Vec4) -> Engi
         color: Some(Vec4::new(1.0, 1.0, 1.0, 1.0)),
         ground_color: None,
         intensity: Some(20.0),
+        units: None,
         range: Some(30.0),
         spot_inner_outer: None,
+        area_size: None,
+        area_right: None,
+        volumetric: None,
         layer_mask: 0xFFFFFFFF,
         cast_shadow: true,
+        overwrite: false,
     })
 }
 
@@ -1195,10 +1401,15 @@ fn create_ambient_light_cmd(
         color: Some(color),
         ground_color: None,
         intensity: Some(intensity),
+        units: None,
         range: Some(1.0),
         spot_inner_outer: None,
+        area_size: None,
+        area_right: None,
+        volumetric: None,
         layer_mask: 0xFFFFFFFF,
         cast_shadow: false,
+        overwrite: false,
     })
 }
 
@@ -1222,6 +1433,7 @@ fn create_standard_material_cmd(
             emissive_color: emissive_color.unwrap_or(Vec4::ZERO),
             ..Default::default()
         })),
+        overwrite: false,
     })
 }
 
@@ -1234,6 +1446,10 @@ fn create_texture_cmd(window_id: u32, texture_id: u32, label: &str, buffer_id: u
         srgb: Some(true),
         mode: TextureCreateMode::Standalone,
         atlas_options: None,
+        overwrite: false,
+        encoding: ImageEncoding::Auto,
+        size: None,
+        retain_cpu_copy: false,
     })
 }
 
@@ -1251,6 +1467,8 @@ fn create_floor_cmd(window_id: u32, geometry_id: u32, material_id: u32) -> Engin
         receive_shadow: true,
         cast_outline: false,
         outline_color: Vec4::ZERO,
+        overwrite: false,
+        sort_order: 0,
     })
 }
 
@@ -1293,6 +1511,8 @@ fn create_instanced_cubes(
             receive_shadow: true,
             cast_outline: false,
             outline_color: Vec4::ZERO,
+            overwrite: false,
+            sort_order: 0,
         }));
     }
 
@@ -1310,6 +1530,7 @@ fn create_shadow_config_cmd(window_id: u32) -> EngineCmd {
             virtual_grid_size: 1,
             smoothing: 1,
             normal_bias: 0.01,
+            ..Default::default()
         },
     })
 }
@@ -1401,6 +1622,86 @@ fn upload_type_to_u32(upload_type: UploadType) -> u32 {
     }
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct DemoVertex {
+    position: [f32; 3],
+    normal: [f32; 3],
+    tangent: [f32; 4],
+    uv: [f32; 2],
+}
+
+impl DemoVertex {
+    fn layout() -> InterleavedLayout {
+        let attribute = |primitive_type, offset| InterleavedAttribute {
+            primitive_type,
+            offset,
+        };
+        InterleavedLayout {
+            stride: std::mem::size_of::<Self>() as u32,
+            attributes: vec![
+                attribute(GeometryPrimitiveType::Position, 0),
+                attribute(GeometryPrimitiveType::Normal, 12),
+                attribute(GeometryPrimitiveType::Tangent, 24),
+                attribute(GeometryPrimitiveType::UV, 40),
+            ],
+        }
+    }
+}
+
+/// Interleaves the streams of the built-in cube generator
+fn build_interleaved_cube() -> (Vec<DemoVertex>, Vec<u32>) {
+    let streams = crate::core::resources::generators::generate_cube(&CubeOptions::default());
+    let stream = |primitive_type| -> &[u8] {
+        streams
+            .iter()
+            .find(|(t, _)| *t == primitive_type)
+            .map(|(_, bytes)| bytes.as_slice())
+            .unwrap_or_default()
+    };
+
+    let positions: &[[f32; 3]] = cast_slice(stream(GeometryPrimitiveType::Position));
+    let normals: &[[f32; 3]] = cast_slice(stream(GeometryPrimitiveType::Normal));
+    let tangents: &[[f32; 4]] = cast_slice(stream(GeometryPrimitiveType::Tangent));
+    let uvs: &[[f32; 2]] = cast_slice(stream(GeometryPrimitiveType::UV));
+    let indices = bytemuck::pod_collect_to_vec(stream(GeometryPrimitiveType::Index));
+
+    let vertices = (0..positions.len())
+        .map(|i| DemoVertex {
+            position: positions[i],
+            normal: normals[i],
+            tangent: tangents[i],
+            uv: uvs[i],
+        })
+        .collect();
+    (vertices, indices)
+}
+
+struct LightmappedQuad {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uv0: Vec<[f32; 2]>,
+    uv1: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+}
+
+/// 6x6 quad facing +Z; UV0 spans the whole texture, UV1 only its top-left quarter
+fn build_lightmapped_quad() -> LightmappedQuad {
+    let uv0 = vec![[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]];
+    LightmappedQuad {
+        positions: vec![
+            [-3.0, -3.0, 0.0],
+            [3.0, -3.0, 0.0],
+            [3.0, 3.0, 0.0],
+            [-3.0, 3.0, 0.0],
+        ],
+        normals: vec![[0.0, 0.0, 1.0]; 4],
+        uv1: uv0.iter().map(|[u, v]| [u * 0.5, v * 0.5]).collect(),
+        uv0,
+        indices: vec![0, 1, 2, 0, 2, 3],
+    }
+}
+
 fn build_skinned_plane(
     grid_x: u32,
     grid_z: u32,
@@ -1581,23 +1882,31 @@ fn pump_for(duration: Duration) {
 }
 
 fn wait_for_confirmation(_window_id: u32) {
-    for _ in 0..100 {
-        let responses = receive_responses();
-        for response in responses {
-            match response.response {
-                CommandResponse::WindowCreate(res) => {
-                    if res.success {
-                        return;
-                    } else {
-                        panic!("Window creation failed: {}", res.message);
-                    }
-                }
-                _ => {}
+    let mut responses = Vec::new();
+    while pending_command_count() > 0 {
+        assert_eq!(core::vulfram_tick(0, 0), VulframResult::Success);
+        responses.extend(receive_responses());
+    }
+    responses.extend(receive_responses());
+
+    for response in responses {
+        if let CommandResponse::WindowCreate(res) = response.response {
+            if !res.success {
+                panic!("Window creation failed: {}", res.message);
             }
+            return;
         }
-        std::thread::sleep(Duration::from_millis(10));
-        assert_eq!(core::vulfram_tick(0, 0), VulframResult::Success);
     }
+    panic!("Window creation produced no response");
+}
+
+fn pending_command_count() -> u32 {
+    let mut count = 0;
+    assert_eq!(
+        core::vulfram_pending_command_count(&mut count),
+        VulframResult::Success
+    );
+    count
 }
 
 fn send_commands(cmds: Vec<EngineCmd>) -> VulframResult {
@@ -1622,8 +1931,10 @@ fn receive_responses() -> Vec<CommandResponseEnvelope> {
         return Vec::new();
     }
 
-    let bytes = unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, len)) };
-    let responses = from_slice(&bytes).expect("failed to deserialize responses");
+    // Decode in place, then hand the allocation back to the core
+    let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };
+    let responses = from_slice(bytes).expect("failed to deserialize responses");
+    core::vulfram_free_buffer(ptr, len);
     responses
 }
 
@@ -1636,8 +1947,10 @@ fn receive_events() -> Vec<EngineEvent> {
         return Vec::new();
     }
 
-    let bytes = unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr as *mut u8, len)) };
-    let events = from_slice(&bytes).expect("failed to deserialize events");
+    // Decode in place, then hand the allocation back to the core
+    let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };
+    let events = from_slice(bytes).expect("failed to deserialize events");
+    core::vulfram_free_buffer(ptr, len);
     events
 }
 